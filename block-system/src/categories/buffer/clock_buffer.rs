@@ -11,6 +11,19 @@
 //! performs well under concurrent access without the overhead of maintaining a
 //! strict LRU order.
 //!
+//! ## Sequential prefetch
+//!
+//! With `prefetch_distance > 0`, a request for page `n + 1` right after page
+//! `n` is treated as a sequential scan and pages `n + 2 ..= n + 1 +
+//! prefetch_distance` are loaded ahead of demand. Prefetched pages count as
+//! `prefetches`, not misses, and a later request for one is a hit.
+//!
+//! ## Dirty pages
+//!
+//! Writes (`_is_write: true`) mark a page dirty. When the clock hand evicts a
+//! dirty page it is written back first; `flush_all` simulates a checkpoint by
+//! writing back all dirty pages without evicting them.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
@@ -19,11 +32,15 @@
 //! | `cache_misses` | Counter | Page requests that missed |
 //! | `hit_rate_pct` | Gauge | cache_hits / (hits + misses) * 100 |
 //! | `evictions` | Counter | Pages evicted to make room |
+//! | `dirty_evictions` | Counter | Evicted pages that were dirty and had to be written back |
+//! | `pages_flushed` | Counter | Dirty pages written back (evictions + `flush_all`) |
 //! | `clock_hand_sweeps` | Counter | Full rotations of the clock hand |
+//! | `prefetches` | Counter | Pages loaded ahead of demand by sequential prefetch |
 //! | `current_size` | Gauge | Pages currently in the pool |
+//! | `access_latency_ms` | Histogram | Simulated latency of each page request (p50/p95/p99) |
 
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
@@ -37,6 +54,8 @@
 };
 use crate::core::port::{Port, PortDirection, PortType, PortValue};
 
+use super::access_latency_ms;
+
 // ---------------------------------------------------------------------------
 // ClockBufferBlock
 // ---------------------------------------------------------------------------
@@ -57,18 +76,27 @@ pub struct ClockBufferBlock {
     // Configuration
     capacity: usize,
     page_size: usize,
+    /// Pages to read ahead once sequential access is detected (0 = off).
+    prefetch_distance: usize,
 
     // Internal state — circular buffer with clock hand
     pages: Vec<Option<ClockEntry>>,
     /// Maps page_id → slot index for O(1) lookup
     page_map: HashMap<usize, usize>,
     clock_hand: usize,
+    /// Last demand-requested page, used to detect sequential access.
+    last_page: Option<usize>,
+    /// Pages modified since they were loaded or last flushed.
+    dirty: HashSet<usize>,
 
     // Stats
     hits: usize,
     misses: usize,
     evictions: usize,
+    dirty_evictions: usize,
+    pages_flushed: usize,
     clock_hand_sweeps: usize,
+    prefetches: usize,
 }
 
 impl ClockBufferBlock {
@@ -82,13 +110,19 @@ pub fn new() -> Self {
             metric_defs: Self::build_metrics(),
             capacity,
             page_size: 8192,
+            prefetch_distance: 0,
             pages: vec![None; capacity],
             page_map: HashMap::new(),
             clock_hand: 0,
+            last_page: None,
+            dirty: HashSet::new(),
             hits: 0,
             misses: 0,
             evictions: 0,
+            dirty_evictions: 0,
+            pages_flushed: 0,
             clock_hand_sweeps: 0,
+            prefetches: 0,
         }
     }
 
@@ -144,10 +178,14 @@ fn build_metadata() -> BlockMetadata {
                                 ELSE:\n      \
                                   EVICT entry.page_id\n      \
                                   advance clock_hand\n      \
-                                  RETURN"
+                                  RETURN\n  \
+                              // The first sweep clears every bit, so the loop\n  \
+                              // evicts within at most two sweeps (2 * n steps)"
                     .into(),
                 complexity: Complexity {
-                    time: "O(1) amortized per access; worst case O(n) sweep on eviction".into(),
+                    time: "O(1) amortized per access; worst case two sweeps (2n hand steps) on \
+                           eviction when every reference bit is set"
+                        .into(),
                     space: "O(capacity) — fixed-size circular buffer".into(),
                 },
                 use_cases: vec![
@@ -197,6 +235,14 @@ fn build_metadata() -> BlockMetadata {
                                           MySQL InnoDB) hold more rows per page but waste space \
                                           when accessing individual rows. Smaller pages (4 KB) \
                                           are better for point lookups on small records.".into()),
+                    ("prefetch_distance".into(), "How many pages to read ahead once two \
+                                                  consecutive page IDs are requested. On a \
+                                                  sequential scan this turns most demand misses \
+                                                  into hits. Prefetched pages enter with their \
+                                                  reference bit set like any other load, so a \
+                                                  large distance in a small pool can push out \
+                                                  useful pages. 0 (the default) disables \
+                                                  prefetching.".into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -290,6 +336,21 @@ fn build_parameters() -> Vec<Parameter> {
                         .with_unit("bytes".into()),
                 ),
             },
+            Parameter {
+                id: "prefetch_distance".into(),
+                name: "Prefetch Distance".into(),
+                param_type: ParameterType::Number,
+                description: "Pages to read ahead when sequential access is detected (0 = off)"
+                    .into(),
+                default_value: ParameterValue::Integer(0),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_min(0.0).with_max(64.0)),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(1.0)
+                        .with_unit("pages".into()),
+                ),
+            },
         ]
     }
 
@@ -327,6 +388,22 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Pages evicted from cache".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "dirty_evictions".into(),
+                name: "Dirty Evictions".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Evicted pages that had to be written back first".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "pages_flushed".into(),
+                name: "Pages Flushed".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Dirty pages written back, on eviction or by flush_all".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
             MetricDefinition {
                 id: "clock_hand_sweeps".into(),
                 name: "Clock Sweeps".into(),
@@ -335,6 +412,14 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Full rotations of the clock hand".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "prefetches".into(),
+                name: "Prefetches".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Pages loaded ahead of demand by sequential prefetch".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
             MetricDefinition {
                 id: "current_size".into(),
                 name: "Current Size".into(),
@@ -343,6 +428,18 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Pages currently in the buffer pool".into(),
                 aggregations: vec![AggregationType::Max],
             },
+            MetricDefinition {
+                id: "access_latency_ms".into(),
+                name: "Access Latency".into(),
+                metric_type: MetricType::Histogram,
+                unit: "ms".into(),
+                description: "Simulated latency of each page request; misses form the tail".into(),
+                aggregations: vec![
+                    AggregationType::P50,
+                    AggregationType::P95,
+                    AggregationType::P99,
+                ],
+            },
         ]
     }
 
@@ -350,7 +447,7 @@ fn build_metrics() -> Vec<MetricDefinition> {
 
     /// Request a page. Returns `true` if it was a cache hit.
     pub fn get_page(&mut self, page_id: usize) -> bool {
-        if let Some(&slot) = self.page_map.get(&page_id) {
+        let hit = if let Some(&slot) = self.page_map.get(&page_id) {
             // Hit — set reference bit.
             if let Some(entry) = &mut self.pages[slot] {
                 entry.reference_bit = true;
@@ -359,25 +456,54 @@ pub fn get_page(&mut self, page_id: usize) -> bool {
             true
         } else {
             // Miss — find a slot using clock sweep.
-            if self.page_map.len() >= self.capacity {
-                self.evict_one();
-            }
-            // Find an empty slot (there must be one after eviction).
-            let slot = self.find_empty_slot();
-            self.pages[slot] = Some(ClockEntry {
-                page_id,
-                reference_bit: true,
-            });
-            self.page_map.insert(page_id, slot);
+            self.load(page_id);
             self.misses += 1;
             false
+        };
+
+        let sequential = self.last_page.is_some_and(|last| last.checked_add(1) == Some(page_id));
+        if sequential {
+            self.prefetch_after(page_id);
+        }
+        self.last_page = Some(page_id);
+        hit
+    }
+
+    /// Load a page that is not cached, running the clock sweep if the pool is full.
+    fn load(&mut self, page_id: usize) {
+        if self.page_map.len() >= self.capacity {
+            self.evict_one();
+        }
+        // Find an empty slot (there must be one after eviction).
+        let slot = self.find_empty_slot();
+        self.pages[slot] = Some(ClockEntry {
+            page_id,
+            reference_bit: true,
+        });
+        self.page_map.insert(page_id, slot);
+    }
+
+    /// Read ahead the `prefetch_distance` pages following `page_id`.
+    ///
+    /// Capped at `capacity - 1` so read-ahead cannot fill the whole pool.
+    fn prefetch_after(&mut self, page_id: usize) {
+        let distance = self.prefetch_distance.min(self.capacity.saturating_sub(1));
+        for next in (page_id + 1..).take(distance) {
+            if !self.page_map.contains_key(&next) {
+                self.load(next);
+                self.prefetches += 1;
+            }
         }
     }
 
     /// Clock sweep: advance hand, clearing reference bits until we find one to evict.
+    ///
+    /// The first full sweep clears every reference bit it passes, so a victim is
+    /// always found within two sweeps (`2 * n` hand steps). The bound also stops
+    /// the loop if the pool has no resident pages at all.
     fn evict_one(&mut self) {
         let n = self.pages.len();
-        loop {
+        for _ in 0..2 * n {
             if let Some(entry) = &mut self.pages[self.clock_hand] {
                 if entry.reference_bit {
                     // Second chance: clear bit and move on.
@@ -386,6 +512,7 @@ fn evict_one(&mut self) {
                     // Evict this page.
                     let victim_id = entry.page_id;
                     self.page_map.remove(&victim_id);
+                    self.write_back(victim_id);
                     self.pages[self.clock_hand] = None;
                     self.evictions += 1;
                     self.advance_hand(n);
@@ -396,6 +523,7 @@ fn evict_one(&mut self) {
         }
     }
 
+    /// Move the hand one slot; `clock_hand_sweeps` counts each wrap to slot 0.
     fn advance_hand(&mut self, n: usize) {
         self.clock_hand = (self.clock_hand + 1) % n;
         if self.clock_hand == 0 {
@@ -410,6 +538,34 @@ fn find_empty_slot(&self) -> usize {
             .unwrap_or(0)
     }
 
+    /// Request a page for writing, marking it dirty. Returns `true` on a hit.
+    pub fn write_page(&mut self, page_id: usize) -> bool {
+        let hit = self.get_page(page_id);
+        self.dirty.insert(page_id);
+        hit
+    }
+
+    /// Write back every dirty page (a checkpoint). Returns the number flushed.
+    pub fn flush_all(&mut self) -> usize {
+        let flushed = self.dirty.len();
+        self.pages_flushed += flushed;
+        self.dirty.clear();
+        flushed
+    }
+
+    /// Number of cached pages modified since they were loaded or flushed.
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Write back an evicted page if it is dirty.
+    fn write_back(&mut self, page_id: usize) {
+        if self.dirty.remove(&page_id) {
+            self.dirty_evictions += 1;
+            self.pages_flushed += 1;
+        }
+    }
+
     pub fn current_size(&self) -> usize {
         self.page_map.len()
     }
@@ -471,23 +627,18 @@ async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("size") {
-            self.capacity = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("size must be an integer".into()))?
-                as usize;
-            if self.capacity == 0 {
-                return Err(BlockError::InvalidParameter("size must be at least 1".into()));
-            }
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("size").and_then(ParameterValue::as_integer) {
+            self.capacity = v as usize;
             self.pages = vec![None; self.capacity];
             self.page_map.clear();
             self.clock_hand = 0;
         }
-        if let Some(val) = params.get("page_size") {
-            self.page_size = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("page_size must be an integer".into()))?
-                as usize;
+        if let Some(v) = params.get("page_size").and_then(ParameterValue::as_integer) {
+            self.page_size = v as usize;
+        }
+        if let Some(v) = params.get("prefetch_distance").and_then(ParameterValue::as_integer) {
+            self.prefetch_distance = v as usize;
         }
         Ok(())
     }
@@ -523,13 +674,28 @@ async fn execute(
                 .flatten()
                 .unwrap_or(0);
 
-            let hit = self.get_page(page_id);
+            let is_write = record
+                .get::<bool>("_is_write")
+                .ok()
+                .flatten()
+                .unwrap_or(false);
+
+            let dirty_before = self.dirty_evictions;
+            let hit = if is_write {
+                self.write_page(page_id)
+            } else {
+                self.get_page(page_id)
+            };
 
             if hit {
                 context.metrics.increment("cache_hits");
             } else {
                 context.metrics.increment("cache_misses");
             }
+            context.metrics.observe(
+                "access_latency_ms",
+                access_latency_ms(hit, self.dirty_evictions > dirty_before),
+            );
 
             let mut out = record;
             let _ = out.insert("_cache_hit".into(), hit);
@@ -539,7 +705,10 @@ async fn execute(
 
         context.metrics.record("hit_rate_pct", self.hit_rate_pct());
         context.metrics.record("evictions", self.evictions as f64);
+        context.metrics.record("dirty_evictions", self.dirty_evictions as f64);
+        context.metrics.record("pages_flushed", self.pages_flushed as f64);
         context.metrics.record("clock_hand_sweeps", self.clock_hand_sweeps as f64);
+        context.metrics.record("prefetches", self.prefetches as f64);
         context.metrics.record("current_size", self.current_size() as f64);
 
         let mut outputs = HashMap::new();
@@ -550,7 +719,10 @@ async fn execute(
         metrics_summary.insert("cache_misses".into(), self.misses as f64);
         metrics_summary.insert("hit_rate_pct".into(), self.hit_rate_pct());
         metrics_summary.insert("evictions".into(), self.evictions as f64);
+        metrics_summary.insert("dirty_evictions".into(), self.dirty_evictions as f64);
+        metrics_summary.insert("pages_flushed".into(), self.pages_flushed as f64);
         metrics_summary.insert("clock_hand_sweeps".into(), self.clock_hand_sweeps as f64);
+        metrics_summary.insert("prefetches".into(), self.prefetches as f64);
         metrics_summary.insert("current_size".into(), self.current_size() as f64);
 
         Ok(ExecutionResult {
@@ -578,10 +750,13 @@ fn get_state(&self) -> BlockState {
         let mut state = BlockState::new();
         let _ = state.insert("capacity".into(), self.capacity);
         let _ = state.insert("page_size".into(), self.page_size);
+        let _ = state.insert("prefetch_distance".into(), self.prefetch_distance);
         let _ = state.insert("current_size".into(), self.current_size());
         let _ = state.insert("hits".into(), self.hits);
         let _ = state.insert("misses".into(), self.misses);
         let _ = state.insert("evictions".into(), self.evictions);
+        let _ = state.insert("dirty_pages".into(), self.dirty.len());
+        let _ = state.insert("prefetches".into(), self.prefetches);
         state
     }
 
@@ -592,6 +767,9 @@ fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
         if let Ok(Some(ps)) = state.get::<usize>("page_size") {
             self.page_size = ps;
         }
+        if let Ok(Some(d)) = state.get::<usize>("prefetch_distance") {
+            self.prefetch_distance = d;
+        }
         Ok(())
     }
 }
@@ -648,6 +826,80 @@ fn test_eviction_second_chance() {
         assert!(pool.contains(4));
     }
 
+    #[test]
+    fn test_eviction_with_all_reference_bits_set() {
+        let mut pool = ClockBufferBlock::new();
+        pool.capacity = 8;
+        pool.pages = vec![None; 8];
+
+        for page in 0..8 {
+            pool.get_page(page);
+        }
+        // Touch every page so each reference bit is set.
+        for page in 0..8 {
+            assert!(pool.get_page(page));
+        }
+        pool.clock_hand = 3;
+        let sweeps_before = pool.clock_hand_sweeps;
+
+        pool.get_page(100);
+
+        assert_eq!(pool.evictions, 1, "exactly one page should be evicted");
+        assert_eq!(pool.current_size(), 8);
+        assert!(pool.contains(100));
+        assert!(!pool.contains(3), "the page under the hand is evicted after a full sweep");
+        let sweeps = pool.clock_hand_sweeps - sweeps_before;
+        assert!(sweeps <= 2, "eviction took {} sweeps", sweeps);
+        assert_eq!(sweeps, 1);
+    }
+
+    #[test]
+    fn test_evict_one_terminates_on_empty_slots() {
+        let mut pool = ClockBufferBlock::new();
+        pool.capacity = 4;
+        pool.pages = vec![None; 4];
+
+        pool.evict_one();
+        assert_eq!(pool.evictions, 0);
+        assert_eq!(pool.clock_hand_sweeps, 2);
+    }
+
+    #[tokio::test]
+    async fn test_execute_tracks_dirty_evictions() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        use crate::core::port::Record;
+
+        let mut pool = ClockBufferBlock::new();
+        pool.capacity = 2;
+        pool.pages = vec![None; 2];
+
+        let requests: Vec<Record> = [(1, true), (2, false), (3, false), (4, false)]
+            .iter()
+            .map(|&(pid, write)| {
+                let mut r = Record::new();
+                r.insert("_page_id".into(), pid as usize).unwrap();
+                r.insert("_is_write".into(), write).unwrap();
+                r
+            })
+            .collect();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("requests".into(), PortValue::Stream(requests));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = pool.execute(ctx).await.unwrap();
+        assert_eq!(*result.metrics.get("evictions").unwrap(), 2.0);
+        assert_eq!(*result.metrics.get("dirty_evictions").unwrap(), 1.0);
+        assert_eq!(*result.metrics.get("pages_flushed").unwrap(), 1.0);
+        assert_eq!(pool.flush_all(), 0);
+    }
+
     #[test]
     fn test_hit_rate() {
         let mut pool = ClockBufferBlock::new();
@@ -666,6 +918,33 @@ fn test_hit_rate() {
         assert!((pool.hit_rate_pct() - 50.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_sequential_prefetch() {
+        let mut pool = ClockBufferBlock::new();
+        pool.capacity = 16;
+        pool.pages = vec![None; 16];
+        pool.prefetch_distance = 4;
+
+        for page in 0..100 {
+            pool.get_page(page);
+        }
+
+        assert!(pool.misses <= 2, "expected at most 2 misses, got {}", pool.misses);
+        assert_eq!(pool.hits + pool.misses, 100);
+        assert!(pool.prefetches >= 98);
+        assert!(pool.current_size() <= 16);
+
+        // Without prefetch every page of the scan is a miss.
+        let mut plain = ClockBufferBlock::new();
+        plain.capacity = 16;
+        plain.pages = vec![None; 16];
+        for page in 0..100 {
+            plain.get_page(page);
+        }
+        assert_eq!(plain.misses, 100);
+        assert_eq!(plain.prefetches, 0);
+    }
+
     #[test]
     fn test_metadata() {
         let pool = ClockBufferBlock::new();
@@ -6,13 +6,25 @@
 //!
 //! ## How it works
 //!
-//! The buffer pool maintains a hash map from page IDs to page data, plus an
-//! LRU ordering structure (a doubly-linked list simulated with a `VecDeque`).
+//! The buffer pool keeps cached pages in an intrusive doubly-linked list stored
+//! in a slab (`Vec` of nodes linked by index), plus a hash map from page ID to
+//! node index. Both the hit-path move-to-MRU and eviction are O(1).
 //! On every `get_page` call:
 //! - **Hit**: the page is moved to the most-recently-used position.
 //! - **Miss**: the page is "fetched" (simulated) and inserted. If the pool is
 //!   full, the least-recently-used page is evicted first.
 //!
+//! With `prefetch_distance > 0`, a request for page `n + 1` right after page
+//! `n` is treated as a sequential scan and pages `n + 2 ..= n + 1 +
+//! prefetch_distance` are loaded ahead of demand. Prefetched pages count as
+//! `prefetches`, not misses, and a later request for one is a hit.
+//!
+//! ## Dirty pages
+//!
+//! A record with `_is_write: true` marks its page dirty. Evicting a dirty page
+//! writes it back to storage first, and `flush_all` writes back every dirty
+//! page the way a checkpoint does.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
@@ -21,10 +33,14 @@
 //! | `cache_misses` | Counter | Number of page requests that missed |
 //! | `hit_rate_pct` | Gauge | cache_hits / (hits + misses) * 100 |
 //! | `evictions` | Counter | Pages evicted to make room |
+//! | `dirty_evictions` | Counter | Evicted pages that were dirty and had to be written back |
+//! | `pages_flushed` | Counter | Dirty pages written back (evictions + `flush_all`) |
+//! | `prefetches` | Counter | Pages loaded ahead of demand by sequential prefetch |
 //! | `current_size` | Gauge | Pages currently in the pool |
+//! | `access_latency_ms` | Histogram | Simulated latency of each page request (p50/p95/p99) |
 
 use async_trait::async_trait;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet};
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
@@ -38,6 +54,124 @@
 };
 use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
 
+use super::access_latency_ms;
+
+// ---------------------------------------------------------------------------
+// Intrusive LRU list
+// ---------------------------------------------------------------------------
+
+/// A cached page and its links in the LRU list.
+#[derive(Debug, Clone)]
+struct LruNode {
+    page_id: usize,
+    /// Simulated page contents.
+    data: Vec<u8>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// Doubly-linked list over a slab of nodes, indexed by page ID.
+/// Front (`head`) = least recently used, back (`tail`) = most recently used.
+/// Shared with the other list-based buffer policies (e.g. 2Q's queues).
+#[derive(Debug, Default)]
+pub(crate) struct LruList {
+    nodes: Vec<LruNode>,
+    /// Slab slots freed by eviction, reused before growing `nodes`.
+    free: Vec<usize>,
+    /// page_id → slot in `nodes`
+    index: HashMap<usize, usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+impl LruList {
+    pub(crate) fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub(crate) fn contains(&self, page_id: usize) -> bool {
+        self.index.contains_key(&page_id)
+    }
+
+    /// Insert a page at the MRU position.
+    pub(crate) fn push_back(&mut self, page_id: usize, data: Vec<u8>) {
+        let node = LruNode {
+            page_id,
+            data,
+            prev: self.tail,
+            next: None,
+        };
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot] = node;
+                slot
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(slot),
+            None => self.head = Some(slot),
+        }
+        self.tail = Some(slot);
+        self.index.insert(page_id, slot);
+    }
+
+    /// Detach a node from its neighbours without freeing it.
+    fn unlink(&mut self, slot: usize) {
+        let (prev, next) = (self.nodes[slot].prev, self.nodes[slot].next);
+        match prev {
+            Some(p) => self.nodes[p].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(n) => self.nodes[n].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    /// Move a cached page to the MRU position. Returns `false` if not cached.
+    pub(crate) fn touch(&mut self, page_id: usize) -> bool {
+        let Some(&slot) = self.index.get(&page_id) else {
+            return false;
+        };
+        if self.tail != Some(slot) {
+            self.unlink(slot);
+            self.nodes[slot].prev = self.tail;
+            self.nodes[slot].next = None;
+            if let Some(tail) = self.tail {
+                self.nodes[tail].next = Some(slot);
+            }
+            self.tail = Some(slot);
+        }
+        true
+    }
+
+    /// Remove and return the least recently used page ID.
+    pub(crate) fn pop_front(&mut self) -> Option<usize> {
+        let page_id = self.nodes[self.head?].page_id;
+        self.remove(page_id);
+        Some(page_id)
+    }
+
+    /// Remove a page wherever it sits in the list. Returns `false` if absent.
+    pub(crate) fn remove(&mut self, page_id: usize) -> bool {
+        let Some(slot) = self.index.remove(&page_id) else {
+            return false;
+        };
+        self.unlink(slot);
+        self.nodes[slot].data = Vec::new();
+        self.free.push(slot);
+        true
+    }
+
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
 // ---------------------------------------------------------------------------
 // LRUBufferBlock
 // ---------------------------------------------------------------------------
@@ -52,17 +186,24 @@ pub struct LRUBufferBlock {
     // Configuration
     pub(crate) capacity: usize, // max pages
     page_size: usize,
+    /// Pages to read ahead once sequential access is detected (0 = off).
+    prefetch_distance: usize,
 
     // Internal state
-    /// page_id → page data (simulated as a Vec<u8>)
-    cache: HashMap<usize, Vec<u8>>,
-    /// LRU order: front = least recently used, back = most recently used
-    lru_order: VecDeque<usize>,
+    /// Cached pages (simulated as a Vec<u8>) in LRU order.
+    cache: LruList,
+    /// Last demand-requested page, used to detect sequential access.
+    last_page: Option<usize>,
+    /// Pages modified since they were loaded or last flushed.
+    dirty: HashSet<usize>,
 
     // Stats
     hits: usize,
     misses: usize,
     evictions: usize,
+    dirty_evictions: usize,
+    pages_flushed: usize,
+    prefetches: usize,
 }
 
 impl LRUBufferBlock {
@@ -75,11 +216,16 @@ pub fn new() -> Self {
             metric_defs: Self::build_metrics(),
             capacity: 1024,
             page_size: 8192,
-            cache: HashMap::new(),
-            lru_order: VecDeque::new(),
+            prefetch_distance: 0,
+            cache: LruList::default(),
+            last_page: None,
+            dirty: HashSet::new(),
             hits: 0,
             misses: 0,
             evictions: 0,
+            dirty_evictions: 0,
+            pages_flushed: 0,
+            prefetches: 0,
         }
     }
 
@@ -129,12 +275,17 @@ fn build_metadata() -> BlockMetadata {
                                 Fetch page_data from storage\n    \
                                 Insert (page_id, page_data) into cache\n    \
                                 Push page_id to back of LRU list\n    \
-                                misses += 1\n    \
-                                RETURN page_data"
+                                misses += 1\n  \
+                              IF prefetch_distance > 0 AND page_id == last_page + 1:\n    \
+                                // Sequential access detected — read ahead\n    \
+                                FOR p IN page_id+1 ..= page_id+prefetch_distance:\n      \
+                                  IF p NOT IN cache: load p at MRU end, prefetches += 1\n  \
+                              last_page = page_id\n  \
+                              RETURN page_data"
                     .into(),
                 complexity: Complexity {
-                    time: "O(n) per access in this VecDeque implementation; O(1) with a \
-                           proper doubly-linked-list + hash map"
+                    time: "O(1) per access — a hash map finds the page's list node and the \
+                           doubly-linked list moves or evicts it without searching"
                         .into(),
                     space: "O(capacity) — at most `capacity` pages held in memory".into(),
                 },
@@ -188,6 +339,16 @@ fn build_metadata() -> BlockMetadata {
                                           of I/O operations but waste memory when only a few \
                                           rows per page are needed. PostgreSQL uses 8 KB, MySQL \
                                           InnoDB uses 16 KB.".into()),
+                    ("prefetch_distance".into(), "How many pages to read ahead once two \
+                                                  consecutive page IDs are requested. On a \
+                                                  sequential scan this turns most demand misses \
+                                                  into hits, because the next pages are already \
+                                                  loaded when they are requested. Prefetched pages \
+                                                  still take up space, so a large distance in a \
+                                                  small pool can evict useful pages. 0 (the \
+                                                  default) disables prefetching; PostgreSQL's \
+                                                  effective_io_concurrency and InnoDB's read-ahead \
+                                                  are the real-world equivalents.".into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -283,6 +444,21 @@ fn build_parameters() -> Vec<Parameter> {
                         .with_unit("bytes".into()),
                 ),
             },
+            Parameter {
+                id: "prefetch_distance".into(),
+                name: "Prefetch Distance".into(),
+                param_type: ParameterType::Number,
+                description: "Pages to read ahead when sequential access is detected (0 = off)"
+                    .into(),
+                default_value: ParameterValue::Integer(0),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_min(0.0).with_max(64.0)),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(1.0)
+                        .with_unit("pages".into()),
+                ),
+            },
         ]
     }
 
@@ -320,6 +496,30 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Pages evicted from cache".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "dirty_evictions".into(),
+                name: "Dirty Evictions".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Evicted pages that had to be written back first".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "pages_flushed".into(),
+                name: "Pages Flushed".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Dirty pages written back, on eviction or by flush_all".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "prefetches".into(),
+                name: "Prefetches".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Pages loaded ahead of demand by sequential prefetch".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
             MetricDefinition {
                 id: "current_size".into(),
                 name: "Current Size".into(),
@@ -328,6 +528,18 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Pages currently in the buffer pool".into(),
                 aggregations: vec![AggregationType::Max],
             },
+            MetricDefinition {
+                id: "access_latency_ms".into(),
+                name: "Access Latency".into(),
+                metric_type: MetricType::Histogram,
+                unit: "ms".into(),
+                description: "Simulated latency of each page request; misses form the tail".into(),
+                aggregations: vec![
+                    AggregationType::P50,
+                    AggregationType::P95,
+                    AggregationType::P99,
+                ],
+            },
         ]
     }
 
@@ -335,40 +547,84 @@ fn build_metrics() -> Vec<MetricDefinition> {
 
     /// Request a page. Returns `true` if it was a cache hit.
     pub fn get_page(&mut self, page_id: usize) -> bool {
-        if self.cache.contains_key(&page_id) {
-            // Hit — move to MRU position.
-            self.touch(page_id);
+        let hit = if self.cache.touch(page_id) {
+            // Hit — moved to MRU position.
             self.hits += 1;
             true
         } else {
             // Miss — possibly evict, then insert.
-            if self.cache.len() >= self.capacity {
-                self.evict();
-            }
-            // Simulate fetching the page (fill with zeros).
-            self.cache.insert(page_id, vec![0u8; self.page_size]);
-            self.lru_order.push_back(page_id);
+            self.load(page_id);
             self.misses += 1;
             false
+        };
+
+        let sequential = self.last_page.is_some_and(|last| last.checked_add(1) == Some(page_id));
+        if sequential {
+            self.prefetch_after(page_id);
         }
+        self.last_page = Some(page_id);
+        hit
     }
 
-    /// Move a page to the most-recently-used position.
-    fn touch(&mut self, page_id: usize) {
-        if let Some(pos) = self.lru_order.iter().position(|&id| id == page_id) {
-            self.lru_order.remove(pos);
+    /// Load a page that is not cached, evicting the LRU page if the pool is full.
+    fn load(&mut self, page_id: usize) {
+        if self.cache.len() >= self.capacity {
+            self.evict();
+        }
+        // Simulate fetching the page (fill with zeros).
+        self.cache.push_back(page_id, vec![0u8; self.page_size]);
+    }
+
+    /// Read ahead the `prefetch_distance` pages following `page_id`.
+    ///
+    /// Capped at `capacity - 1` so read-ahead never evicts the page that
+    /// triggered it.
+    fn prefetch_after(&mut self, page_id: usize) {
+        let distance = self.prefetch_distance.min(self.capacity.saturating_sub(1));
+        for next in (page_id + 1..).take(distance) {
+            if !self.cache.contains(next) {
+                self.load(next);
+                self.prefetches += 1;
+            }
         }
-        self.lru_order.push_back(page_id);
     }
 
     /// Evict the least recently used page.
     fn evict(&mut self) {
-        if let Some(victim) = self.lru_order.pop_front() {
-            self.cache.remove(&victim);
+        if let Some(victim) = self.cache.pop_front() {
+            self.write_back(victim);
             self.evictions += 1;
         }
     }
 
+    /// Request a page for writing, marking it dirty. Returns `true` on a hit.
+    pub fn write_page(&mut self, page_id: usize) -> bool {
+        let hit = self.get_page(page_id);
+        self.dirty.insert(page_id);
+        hit
+    }
+
+    /// Write back every dirty page (a checkpoint). Returns the number flushed.
+    pub fn flush_all(&mut self) -> usize {
+        let flushed = self.dirty.len();
+        self.pages_flushed += flushed;
+        self.dirty.clear();
+        flushed
+    }
+
+    /// Number of cached pages modified since they were loaded or flushed.
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Write back an evicted page if it is dirty.
+    fn write_back(&mut self, page_id: usize) {
+        if self.dirty.remove(&page_id) {
+            self.dirty_evictions += 1;
+            self.pages_flushed += 1;
+        }
+    }
+
     /// Current number of cached pages.
     pub fn current_size(&self) -> usize {
         self.cache.len()
@@ -390,13 +646,14 @@ pub fn memory_used(&self) -> usize {
 
     /// Check if a specific page is cached.
     pub fn contains(&self, page_id: usize) -> bool {
-        self.cache.contains_key(&page_id)
+        self.cache.contains(page_id)
     }
 
     /// Clear the entire buffer pool.
     pub fn clear(&mut self) {
         self.cache.clear();
-        self.lru_order.clear();
+        self.dirty.clear();
+        self.last_page = None;
     }
 }
 
@@ -444,24 +701,15 @@ async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("size") {
-            self.capacity = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("size must be an integer".into()))?
-                as usize;
-            if self.capacity == 0 {
-                return Err(BlockError::InvalidParameter(
-                    "size must be at least 1".into(),
-                ));
-            }
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("size").and_then(ParameterValue::as_integer) {
+            self.capacity = v as usize;
         }
-        if let Some(val) = params.get("page_size") {
-            self.page_size = val
-                .as_integer()
-                .ok_or_else(|| {
-                    BlockError::InvalidParameter("page_size must be an integer".into())
-                })?
-                as usize;
+        if let Some(v) = params.get("page_size").and_then(ParameterValue::as_integer) {
+            self.page_size = v as usize;
+        }
+        if let Some(v) = params.get("prefetch_distance").and_then(ParameterValue::as_integer) {
+            self.prefetch_distance = v as usize;
         }
         Ok(())
     }
@@ -497,13 +745,28 @@ async fn execute(
                 .flatten()
                 .unwrap_or(0);
 
-            let hit = self.get_page(page_id);
+            let is_write = record
+                .get::<bool>("_is_write")
+                .ok()
+                .flatten()
+                .unwrap_or(false);
+
+            let dirty_before = self.dirty_evictions;
+            let hit = if is_write {
+                self.write_page(page_id)
+            } else {
+                self.get_page(page_id)
+            };
 
             if hit {
                 context.metrics.increment("cache_hits");
             } else {
                 context.metrics.increment("cache_misses");
             }
+            context.metrics.observe(
+                "access_latency_ms",
+                access_latency_ms(hit, self.dirty_evictions > dirty_before),
+            );
 
             let mut out = record;
             let _ = out.insert("_cache_hit".into(), hit);
@@ -518,6 +781,15 @@ async fn execute(
         context
             .metrics
             .record("evictions", self.evictions as f64);
+        context
+            .metrics
+            .record("dirty_evictions", self.dirty_evictions as f64);
+        context
+            .metrics
+            .record("pages_flushed", self.pages_flushed as f64);
+        context
+            .metrics
+            .record("prefetches", self.prefetches as f64);
         context
             .metrics
             .record("current_size", self.current_size() as f64);
@@ -530,6 +802,9 @@ async fn execute(
         metrics_summary.insert("cache_misses".into(), self.misses as f64);
         metrics_summary.insert("hit_rate_pct".into(), self.hit_rate_pct());
         metrics_summary.insert("evictions".into(), self.evictions as f64);
+        metrics_summary.insert("dirty_evictions".into(), self.dirty_evictions as f64);
+        metrics_summary.insert("pages_flushed".into(), self.pages_flushed as f64);
+        metrics_summary.insert("prefetches".into(), self.prefetches as f64);
         metrics_summary.insert("current_size".into(), self.current_size() as f64);
 
         Ok(ExecutionResult {
@@ -559,10 +834,13 @@ fn get_state(&self) -> BlockState {
         let mut state = BlockState::new();
         let _ = state.insert("capacity".into(), self.capacity);
         let _ = state.insert("page_size".into(), self.page_size);
+        let _ = state.insert("prefetch_distance".into(), self.prefetch_distance);
         let _ = state.insert("current_size".into(), self.current_size());
         let _ = state.insert("hits".into(), self.hits);
         let _ = state.insert("misses".into(), self.misses);
         let _ = state.insert("evictions".into(), self.evictions);
+        let _ = state.insert("dirty_pages".into(), self.dirty.len());
+        let _ = state.insert("prefetches".into(), self.prefetches);
         state
     }
 
@@ -573,6 +851,9 @@ fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
         if let Ok(Some(ps)) = state.get::<usize>("page_size") {
             self.page_size = ps;
         }
+        if let Ok(Some(d)) = state.get::<usize>("prefetch_distance") {
+            self.prefetch_distance = d;
+        }
         Ok(())
     }
 }
@@ -681,6 +962,94 @@ fn test_hit_rate_accuracy() {
         assert!((pool.hit_rate_pct() - 50.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_lru_order_after_many_touches() {
+        let mut pool = LRUBufferBlock::new();
+        pool.capacity = 5;
+
+        for i in 0..5 {
+            pool.get_page(i);
+        }
+        // Touch in a scrambled order: LRU order becomes 3, 0, 4, 1, 2.
+        for i in [2, 3, 0, 4, 1, 2] {
+            assert!(pool.get_page(i));
+        }
+
+        // Each new page evicts the current LRU page, in order.
+        for (new_page, victim) in [(10, 3), (11, 0), (12, 4)] {
+            pool.get_page(new_page);
+            assert!(!pool.contains(victim), "page {} should be evicted", victim);
+        }
+        assert!(pool.contains(1));
+        assert!(pool.contains(2));
+        assert_eq!(pool.current_size(), 5);
+        // Evicted slots are reused rather than growing the slab.
+        assert_eq!(pool.cache.nodes.len(), 5);
+    }
+
+    #[test]
+    fn test_sequential_prefetch_turns_misses_into_hits() {
+        let mut pool = LRUBufferBlock::new();
+        pool.capacity = 16;
+        pool.prefetch_distance = 4;
+
+        for page in 0..100 {
+            pool.get_page(page);
+        }
+
+        // Pages 0 and 1 are demand misses; from then on every page is either
+        // already prefetched or read ahead together with the rest of its window.
+        assert!(pool.misses <= 2, "expected at most 2 misses, got {}", pool.misses);
+        assert_eq!(pool.hits + pool.misses, 100);
+        assert!(pool.prefetches >= 98);
+        assert!(pool.current_size() <= 16);
+    }
+
+    #[test]
+    fn test_no_prefetch_on_random_access() {
+        let mut pool = LRUBufferBlock::new();
+        pool.capacity = 16;
+        pool.prefetch_distance = 4;
+
+        for page in [10, 3, 7, 20, 1] {
+            pool.get_page(page);
+        }
+        assert_eq!(pool.prefetches, 0);
+        assert_eq!(pool.current_size(), 5);
+    }
+
+    #[test]
+    fn test_dirty_page_written_back_on_eviction() {
+        let mut pool = LRUBufferBlock::new();
+        pool.capacity = 2;
+
+        pool.write_page(1); // miss, dirty
+        pool.get_page(2); // miss, clean
+        assert_eq!(pool.dirty_count(), 1);
+
+        pool.get_page(3); // evicts 1 (dirty)
+        pool.get_page(4); // evicts 2 (clean)
+        assert_eq!(pool.evictions, 2);
+        assert_eq!(pool.dirty_evictions, 1);
+        assert_eq!(pool.pages_flushed, 1);
+        assert_eq!(pool.dirty_count(), 0);
+    }
+
+    #[test]
+    fn test_flush_all() {
+        let mut pool = LRUBufferBlock::new();
+        pool.capacity = 8;
+
+        for page in 0..5 {
+            pool.write_page(page);
+        }
+        pool.write_page(0); // already dirty
+        assert_eq!(pool.flush_all(), 5);
+        assert_eq!(pool.pages_flushed, 5);
+        assert_eq!(pool.dirty_count(), 0);
+        assert_eq!(pool.flush_all(), 0);
+    }
+
     #[test]
     fn test_clear() {
         let mut pool = LRUBufferBlock::new();
@@ -715,7 +1084,7 @@ fn test_metadata() {
         assert_eq!(pool.metadata().category, BlockCategory::Buffer);
         assert_eq!(pool.inputs().len(), 1);
         assert_eq!(pool.outputs().len(), 1);
-        assert_eq!(pool.parameters().len(), 2);
+        assert_eq!(pool.parameters().len(), 3);
     }
 
     #[tokio::test]
@@ -758,6 +1127,42 @@ async fn test_block_execute() {
         assert_eq!(pages_output.len(), 6);
     }
 
+    #[tokio::test]
+    async fn test_execute_observes_access_latency() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut pool = LRUBufferBlock::new();
+        // One cold miss, then 19 hits on the same page.
+        let records: Vec<Record> = (0..20)
+            .map(|_| {
+                let mut r = Record::new();
+                r.insert("_page_id".into(), 7usize).unwrap();
+                r
+            })
+            .collect();
+        let mut inputs = HashMap::new();
+        inputs.insert("requests".into(), PortValue::Stream(records));
+
+        let metrics = MetricsCollector::new();
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: metrics.clone(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        pool.execute(ctx).await.unwrap();
+
+        let histogram = metrics.histogram("access_latency_ms").unwrap();
+        assert_eq!(histogram.count, 20);
+        assert_eq!(
+            metrics.percentile("access_latency_ms", 50.0),
+            Some(access_latency_ms(true, false))
+        );
+        // The single miss only shows up in the tail.
+        assert!(metrics.percentile("access_latency_ms", 99.0).unwrap() > 5.0);
+    }
+
     #[tokio::test]
     async fn test_initialize_with_params() {
         let mut pool = LRUBufferBlock::new();
@@ -0,0 +1,864 @@
+//! LRU-K Buffer Pool Block
+//!
+//! A fixed-size page cache using the **LRU-K** replacement policy. Instead of
+//! evicting the page with the oldest *last* access (LRU), it evicts the page
+//! whose *K-th most recent* access is oldest — the page with the largest
+//! **backward K-distance**.
+//!
+//! ## How it works
+//!
+//! Each cached page keeps the timestamps of its last K accesses. A page that
+//! has been accessed fewer than K times has an infinite backward K-distance and
+//! is evicted before any page with a full history; ties among those pages are
+//! broken by plain LRU. A page touched once by a sequential scan therefore
+//! never displaces a page that has proven itself with K accesses, which is what
+//! makes LRU-K scan resistant. With `k = 1` the policy is exactly LRU.
+//!
+//! Pages requested with `_is_write: true` become dirty and are written back
+//! when evicted or when `flush_all` runs.
+//!
+//! ## Metrics tracked
+//!
+//! | Metric | Type | Description |
+//! |--------|------|-------------|
+//! | `cache_hits` | Counter | Page requests served from cache |
+//! | `cache_misses` | Counter | Page requests that missed |
+//! | `hit_rate_pct` | Gauge | cache_hits / (hits + misses) * 100 |
+//! | `evictions` | Counter | Pages evicted to make room |
+//! | `dirty_evictions` | Counter | Evicted pages that were dirty and had to be written back |
+//! | `pages_flushed` | Counter | Dirty pages written back (evictions + `flush_all`) |
+//! | `cold_evictions` | Counter | Evicted pages that had fewer than K accesses |
+//! | `current_size` | Gauge | Pages currently in the pool |
+//! | `access_latency_ms` | Histogram | Simulated latency of each page request (p50/p95/p99) |
+
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::core::block::{
+    Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
+    Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
+};
+use crate::core::constraint::{Constraint, Guarantee};
+use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
+use crate::core::parameter::{
+    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    ValidationResult, WidgetType,
+};
+use crate::core::port::{Port, PortDirection, PortType, PortValue};
+
+use super::access_latency_ms;
+
+// ---------------------------------------------------------------------------
+// LRUKBufferBlock
+// ---------------------------------------------------------------------------
+
+pub struct LRUKBufferBlock {
+    metadata: BlockMetadata,
+    input_ports: Vec<Port>,
+    output_ports: Vec<Port>,
+    params: Vec<Parameter>,
+    metric_defs: Vec<MetricDefinition>,
+
+    // Configuration
+    pub(crate) capacity: usize,
+    page_size: usize,
+    pub(crate) k: usize,
+
+    // Internal state
+    /// page_id → timestamps of its last K accesses (oldest at the front)
+    history: HashMap<usize, VecDeque<u64>>,
+    /// Logical clock, advanced on every access.
+    clock: u64,
+    /// Pages modified since they were loaded or last flushed.
+    dirty: HashSet<usize>,
+
+    // Stats
+    hits: usize,
+    misses: usize,
+    evictions: usize,
+    dirty_evictions: usize,
+    pages_flushed: usize,
+    cold_evictions: usize,
+}
+
+impl LRUKBufferBlock {
+    pub fn new() -> Self {
+        Self {
+            metadata: Self::build_metadata(),
+            input_ports: Self::build_inputs(),
+            output_ports: Self::build_outputs(),
+            params: Self::build_parameters(),
+            metric_defs: Self::build_metrics(),
+            capacity: 1024,
+            page_size: 8192,
+            k: 2,
+            history: HashMap::new(),
+            clock: 0,
+            dirty: HashSet::new(),
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+            dirty_evictions: 0,
+            pages_flushed: 0,
+            cold_evictions: 0,
+        }
+    }
+
+    // -- Metadata builders ---------------------------------------------------
+
+    fn build_metadata() -> BlockMetadata {
+        BlockMetadata {
+            id: "lru-k-buffer-pool".into(),
+            name: "LRU-K Buffer Pool".into(),
+            category: BlockCategory::Buffer,
+            description: "Scan-resistant page cache that evicts by K-th most recent access".into(),
+            version: "1.0.0".into(),
+            documentation: BlockDocumentation {
+                overview: "LRU-K is a page replacement policy that looks further back in a \
+                           page's access history than plain LRU. For every cached page it \
+                           remembers the times of the last K accesses, and when space is \
+                           needed it evicts the page whose K-th most recent access happened \
+                           longest ago. A page that has been accessed fewer than K times is \
+                           treated as infinitely old and is evicted first.\n\n\
+                           The point is to tell genuinely hot pages apart from pages that were \
+                           touched once. Plain LRU promotes every page to the most-recently-used \
+                           position on its first access, so a single large sequential scan \
+                           flushes the whole working set. Under LRU-2, scan pages have only one \
+                           access each and are the first to go, while the pages the workload \
+                           keeps coming back to stay cached.\n\n\
+                           Think of it as a club that only gives regulars a permanent seat: a \
+                           visitor who came once is shown out before anyone who has been in \
+                           twice, no matter how recently the visitor arrived."
+                    .into(),
+                algorithm: "LRU-K Buffer Pool Algorithm:\n\
+                            \n\
+                            STATE: history[page_id] = timestamps of the last K accesses\n\
+                            \n\
+                            FUNCTION get_page(page_id):\n  \
+                              now += 1\n  \
+                              IF page_id IN history:\n    \
+                                // Cache HIT\n    \
+                                Append now to history[page_id], keeping the last K\n    \
+                                hits += 1\n  \
+                              ELSE:\n    \
+                                // Cache MISS\n    \
+                                IF pool is full:\n      \
+                                  CALL evict()\n    \
+                                history[page_id] = [now]\n    \
+                                misses += 1\n\
+                            \n\
+                            FUNCTION evict():\n  \
+                              FOR each cached page p:\n    \
+                                IF p has fewer than K accesses: distance = infinity\n    \
+                                ELSE: distance = now - (K-th most recent access of p)\n  \
+                              victim = page with the largest distance\n    \
+                                (ties at infinity broken by oldest last access, i.e. LRU)\n  \
+                              Remove victim from history\n  \
+                              evictions += 1"
+                    .into(),
+                complexity: Complexity {
+                    time: "O(1) per hit; O(capacity) per eviction to find the largest backward \
+                           K-distance (a priority queue brings this to O(log capacity))"
+                        .into(),
+                    space: "O(capacity * K) — K timestamps per cached page".into(),
+                },
+                use_cases: vec![
+                    "Mixed OLTP and reporting workloads where scans must not flush hot pages".into(),
+                    "Buffer pools for databases with large table scans".into(),
+                    "Distinguishing frequently used index pages from one-off data pages".into(),
+                    "Comparing scan resistance against plain LRU and CLOCK".into(),
+                ],
+                tradeoffs: vec![
+                    "Resists scan pollution, unlike LRU and CLOCK".into(),
+                    "Needs K timestamps per page instead of a single list position".into(),
+                    "New pages are evicted quickly, so a page needs K accesses before it is \
+                     protected — a slow warm-up for genuinely new hot data"
+                        .into(),
+                    "Larger K reacts more slowly to shifts in the working set".into(),
+                    "Finding the victim is more expensive than popping an LRU list".into(),
+                ],
+                examples: vec![
+                    "The original LRU-K paper (O'Neil, O'Neil, Weikum, 1993) evaluated LRU-2 \
+                     on database buffer traces"
+                        .into(),
+                    "SQL Server's buffer pool uses an LRU-2 based replacement policy".into(),
+                    "Many teaching buffer pool managers (e.g., CMU BusTub) implement LRU-K".into(),
+                ],
+                motivation: "Plain LRU treats one access as proof that a page is worth \
+                             keeping. A query that scans a table larger than the buffer pool \
+                             touches every page once and leaves the pool full of pages nobody \
+                             will read again, evicting the index and hot data pages that every \
+                             other query needs.\n\n\
+                             LRU-K fixes this by asking for more evidence: a page has to be \
+                             accessed K times before its recency counts. Pages from a scan are \
+                             evicted among themselves, and the working set survives."
+                    .into(),
+                parameter_guide: HashMap::from([
+                    ("size".into(), "The maximum number of pages the buffer pool can hold. \
+                                     As with LRU, size the pool to fit the hot working set; \
+                                     LRU-K then keeps that working set cached even while \
+                                     scans stream through the pool. Default is 1024 pages."
+                        .into()),
+                    ("page_size".into(), "The size of each cached page in bytes, used for \
+                                          memory accounting. Should match the storage block \
+                                          size. Default is 8192 (8 KB)."
+                        .into()),
+                    ("k".into(), "How many past accesses are tracked per page. K = 1 is plain \
+                                  LRU. K = 2 (the default, and the usual choice) already \
+                                  separates pages touched once by a scan from pages that are \
+                                  re-read. Larger K demands more evidence before a page is \
+                                  protected and adapts more slowly when the working set \
+                                  changes. Range: 1-8."
+                        .into()),
+                ]),
+                alternatives: vec![
+                    Alternative {
+                        block_type: "lru-buffer-pool".into(),
+                        comparison: "LRU is LRU-K with K = 1. It is cheaper (O(1) eviction) \
+                                     and reacts immediately to new pages, but a single large \
+                                     scan evicts the whole working set. Choose LRU-K when \
+                                     scans and point lookups share the buffer pool."
+                            .into(),
+                    },
+                    Alternative {
+                        block_type: "clock-buffer-pool".into(),
+                        comparison: "CLOCK approximates LRU with a reference bit and is very \
+                                     cheap under concurrency, but like LRU it cannot tell a \
+                                     page accessed once from one accessed many times. Choose \
+                                     LRU-K when eviction quality under scans matters more than \
+                                     bookkeeping cost."
+                            .into(),
+                    },
+                ],
+                suggested_questions: vec![
+                    "Run a sequential scan larger than the pool after warming up a hot set. \
+                     How does the hit rate compare with the LRU block?"
+                        .into(),
+                    "What happens to a brand-new hot page under LRU-3 compared to LRU-2?".into(),
+                    "Why is a page with fewer than K accesses treated as infinitely far away?"
+                        .into(),
+                ],
+            },
+            references: vec![Reference {
+                ref_type: ReferenceType::Paper,
+                title: "The LRU-K Page Replacement Algorithm for Database Disk Buffering".into(),
+                url: None,
+                citation: Some(
+                    "O'Neil, E. J., O'Neil, P. E., & Weikum, G. (1993). SIGMOD '93.".into(),
+                ),
+            }],
+            icon: "layers".into(),
+            color: "#F59E0B".into(),
+        }
+    }
+
+    fn build_inputs() -> Vec<Port> {
+        vec![Port {
+            id: "requests".into(),
+            name: "Page Requests".into(),
+            port_type: PortType::DataStream,
+            direction: PortDirection::Input,
+            required: true,
+            multiple: false,
+            description: "Records with a `_page_id` field identifying the requested page".into(),
+            schema: None,
+        }]
+    }
+
+    fn build_outputs() -> Vec<Port> {
+        vec![Port {
+            id: "pages".into(),
+            name: "Served Pages".into(),
+            port_type: PortType::DataStream,
+            direction: PortDirection::Output,
+            required: false,
+            multiple: true,
+            description: "Records enriched with `_cache_hit` (bool) and `_page_data_size`".into(),
+            schema: None,
+        }]
+    }
+
+    fn build_parameters() -> Vec<Parameter> {
+        vec![
+            Parameter {
+                id: "size".into(),
+                name: "Pool Size".into(),
+                param_type: ParameterType::Number,
+                description: "Maximum number of pages to cache".into(),
+                default_value: ParameterValue::Integer(1024),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(1.0).with_max(1_000_000.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(64.0)
+                        .with_unit("pages".into()),
+                ),
+            },
+            Parameter {
+                id: "page_size".into(),
+                name: "Page Size".into(),
+                param_type: ParameterType::Number,
+                description: "Size of each page in bytes (for memory accounting)".into(),
+                default_value: ParameterValue::Integer(8192),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(512.0).with_max(65536.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(512.0)
+                        .with_unit("bytes".into()),
+                ),
+            },
+            Parameter {
+                id: "k".into(),
+                name: "K".into(),
+                param_type: ParameterType::Number,
+                description: "Number of past accesses tracked per page".into(),
+                default_value: ParameterValue::Integer(2),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_min(1.0).with_max(8.0)),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(1.0)
+                        .with_help_text("K = 1 behaves like plain LRU".into()),
+                ),
+            },
+        ]
+    }
+
+    fn build_metrics() -> Vec<MetricDefinition> {
+        vec![
+            MetricDefinition {
+                id: "cache_hits".into(),
+                name: "Cache Hits".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Page requests served from cache".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "cache_misses".into(),
+                name: "Cache Misses".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Page requests that missed the cache".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "hit_rate_pct".into(),
+                name: "Hit Rate".into(),
+                metric_type: MetricType::Gauge,
+                unit: "%".into(),
+                description: "Percentage of requests served from cache".into(),
+                aggregations: vec![AggregationType::Avg],
+            },
+            MetricDefinition {
+                id: "evictions".into(),
+                name: "Evictions".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Pages evicted from cache".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "dirty_evictions".into(),
+                name: "Dirty Evictions".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Evicted pages that had to be written back first".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "pages_flushed".into(),
+                name: "Pages Flushed".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Dirty pages written back, on eviction or by flush_all".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "cold_evictions".into(),
+                name: "Cold Evictions".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Evicted pages that had fewer than K accesses".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "current_size".into(),
+                name: "Current Size".into(),
+                metric_type: MetricType::Gauge,
+                unit: "pages".into(),
+                description: "Pages currently in the buffer pool".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "access_latency_ms".into(),
+                name: "Access Latency".into(),
+                metric_type: MetricType::Histogram,
+                unit: "ms".into(),
+                description: "Simulated latency of each page request; misses form the tail".into(),
+                aggregations: vec![
+                    AggregationType::P50,
+                    AggregationType::P95,
+                    AggregationType::P99,
+                ],
+            },
+        ]
+    }
+
+    // -- Core operations -----------------------------------------------------
+
+    /// Request a page. Returns `true` if it was a cache hit.
+    pub fn get_page(&mut self, page_id: usize) -> bool {
+        self.clock += 1;
+        let now = self.clock;
+
+        if let Some(accesses) = self.history.get_mut(&page_id) {
+            // Hit — record the access, keeping only the last K.
+            accesses.push_back(now);
+            if accesses.len() > self.k {
+                accesses.pop_front();
+            }
+            self.hits += 1;
+            true
+        } else {
+            // Miss — possibly evict, then insert.
+            if self.history.len() >= self.capacity {
+                self.evict();
+            }
+            self.history.insert(page_id, VecDeque::from([now]));
+            self.misses += 1;
+            false
+        }
+    }
+
+    /// Evict the page with the largest backward K-distance.
+    fn evict(&mut self) {
+        // Sort key: pages without K accesses (infinite distance) come first,
+        // then the oldest reference time — the K-th most recent access for
+        // full histories, the last access (LRU) for short ones.
+        let victim = self
+            .history
+            .iter()
+            .min_by_key(|(_, accesses)| {
+                if accesses.len() >= self.k {
+                    (true, accesses[accesses.len() - self.k])
+                } else {
+                    (false, *accesses.back().unwrap_or(&0))
+                }
+            })
+            .map(|(&page_id, accesses)| (page_id, accesses.len() < self.k));
+
+        if let Some((page_id, cold)) = victim {
+            self.history.remove(&page_id);
+            self.write_back(page_id);
+            self.evictions += 1;
+            if cold {
+                self.cold_evictions += 1;
+            }
+        }
+    }
+
+    /// Backward K-distance of a cached page: accesses since its K-th most
+    /// recent access, or `None` (infinite) if it has fewer than K accesses
+    /// or is not cached.
+    pub fn backward_k_distance(&self, page_id: usize) -> Option<u64> {
+        let accesses = self.history.get(&page_id)?;
+        if accesses.len() < self.k {
+            return None;
+        }
+        Some(self.clock - accesses[accesses.len() - self.k])
+    }
+
+    /// Request a page for writing, marking it dirty. Returns `true` on a hit.
+    pub fn write_page(&mut self, page_id: usize) -> bool {
+        let hit = self.get_page(page_id);
+        self.dirty.insert(page_id);
+        hit
+    }
+
+    /// Write back every dirty page (a checkpoint). Returns the number flushed.
+    pub fn flush_all(&mut self) -> usize {
+        let flushed = self.dirty.len();
+        self.pages_flushed += flushed;
+        self.dirty.clear();
+        flushed
+    }
+
+    /// Number of cached pages modified since they were loaded or flushed.
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Write back an evicted page if it is dirty.
+    fn write_back(&mut self, page_id: usize) {
+        if self.dirty.remove(&page_id) {
+            self.dirty_evictions += 1;
+            self.pages_flushed += 1;
+        }
+    }
+
+    /// Current number of cached pages.
+    pub fn current_size(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Hit rate as a percentage (0–100).
+    pub fn hit_rate_pct(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        (self.hits as f64 / total as f64) * 100.0
+    }
+
+    /// Check if a specific page is cached.
+    pub fn contains(&self, page_id: usize) -> bool {
+        self.history.contains_key(&page_id)
+    }
+}
+
+impl Default for LRUKBufferBlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Block trait
+// ---------------------------------------------------------------------------
+
+#[async_trait]
+impl Block for LRUKBufferBlock {
+    fn metadata(&self) -> &BlockMetadata {
+        &self.metadata
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.input_ports
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.output_ports
+    }
+
+    fn parameters(&self) -> &[Parameter] {
+        &self.params
+    }
+
+    fn requires(&self) -> &[Constraint] {
+        &[]
+    }
+
+    fn guarantees(&self) -> &[Guarantee] {
+        &[]
+    }
+
+    fn metrics(&self) -> &[MetricDefinition] {
+        &self.metric_defs
+    }
+
+    async fn initialize(
+        &mut self,
+        params: HashMap<String, ParameterValue>,
+    ) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("size").and_then(ParameterValue::as_integer) {
+            self.capacity = v as usize;
+        }
+        if let Some(v) = params.get("page_size").and_then(ParameterValue::as_integer) {
+            self.page_size = v as usize;
+        }
+        if let Some(v) = params.get("k").and_then(ParameterValue::as_integer) {
+            self.k = v as usize;
+        }
+        Ok(())
+    }
+
+    async fn execute(
+        &mut self,
+        context: ExecutionContext,
+    ) -> Result<ExecutionResult, BlockError> {
+        let input = context
+            .inputs
+            .get("requests")
+            .cloned()
+            .unwrap_or(PortValue::None);
+
+        let records = match input {
+            PortValue::Stream(r) => r,
+            PortValue::Batch(r) => r,
+            PortValue::Single(r) => vec![r],
+            PortValue::None => Vec::new(),
+            _ => {
+                return Err(BlockError::InvalidInput(
+                    "Expected DataStream, Batch, or Single".into(),
+                ))
+            }
+        };
+
+        let mut output_records = Vec::with_capacity(records.len());
+
+        for record in records {
+            let page_id = record
+                .get::<usize>("_page_id")
+                .ok()
+                .flatten()
+                .unwrap_or(0);
+
+            let is_write = record
+                .get::<bool>("_is_write")
+                .ok()
+                .flatten()
+                .unwrap_or(false);
+
+            let dirty_before = self.dirty_evictions;
+            let hit = if is_write {
+                self.write_page(page_id)
+            } else {
+                self.get_page(page_id)
+            };
+
+            if hit {
+                context.metrics.increment("cache_hits");
+            } else {
+                context.metrics.increment("cache_misses");
+            }
+            context.metrics.observe(
+                "access_latency_ms",
+                access_latency_ms(hit, self.dirty_evictions > dirty_before),
+            );
+
+            let mut out = record;
+            let _ = out.insert("_cache_hit".into(), hit);
+            let _ = out.insert("_page_data_size".into(), self.page_size);
+            output_records.push(out);
+        }
+
+        context.metrics.record("hit_rate_pct", self.hit_rate_pct());
+        context.metrics.record("evictions", self.evictions as f64);
+        context.metrics.record("dirty_evictions", self.dirty_evictions as f64);
+        context.metrics.record("pages_flushed", self.pages_flushed as f64);
+        context.metrics.record("cold_evictions", self.cold_evictions as f64);
+        context.metrics.record("current_size", self.current_size() as f64);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("pages".into(), PortValue::Stream(output_records));
+
+        let mut metrics_summary = HashMap::new();
+        metrics_summary.insert("cache_hits".into(), self.hits as f64);
+        metrics_summary.insert("cache_misses".into(), self.misses as f64);
+        metrics_summary.insert("hit_rate_pct".into(), self.hit_rate_pct());
+        metrics_summary.insert("evictions".into(), self.evictions as f64);
+        metrics_summary.insert("dirty_evictions".into(), self.dirty_evictions as f64);
+        metrics_summary.insert("pages_flushed".into(), self.pages_flushed as f64);
+        metrics_summary.insert("cold_evictions".into(), self.cold_evictions as f64);
+        metrics_summary.insert("current_size".into(), self.current_size() as f64);
+
+        Ok(ExecutionResult {
+            outputs,
+            metrics: metrics_summary,
+            errors: vec![],
+        })
+    }
+
+    fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
+        if let Some(input) = inputs.get("requests") {
+            match input {
+                PortValue::Stream(_) | PortValue::Batch(_) | PortValue::Single(_) => {
+                    ValidationResult::ok()
+                }
+                PortValue::None => ValidationResult::ok().with_warning("No page requests provided"),
+                _ => ValidationResult::error("requests port expects DataStream"),
+            }
+        } else {
+            ValidationResult::ok().with_warning("requests input not connected")
+        }
+    }
+
+    fn get_state(&self) -> BlockState {
+        let mut state = BlockState::new();
+        let _ = state.insert("capacity".into(), self.capacity);
+        let _ = state.insert("page_size".into(), self.page_size);
+        let _ = state.insert("k".into(), self.k);
+        let _ = state.insert("current_size".into(), self.current_size());
+        let _ = state.insert("hits".into(), self.hits);
+        let _ = state.insert("misses".into(), self.misses);
+        let _ = state.insert("evictions".into(), self.evictions);
+        let _ = state.insert("dirty_pages".into(), self.dirty.len());
+        state
+    }
+
+    fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
+        if let Ok(Some(c)) = state.get::<usize>("capacity") {
+            self.capacity = c;
+        }
+        if let Ok(Some(ps)) = state.get::<usize>("page_size") {
+            self.page_size = ps;
+        }
+        if let Ok(Some(k)) = state.get::<usize>("k") {
+            self.k = k;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::categories::buffer::LRUBufferBlock;
+    use crate::core::port::Record;
+
+    #[test]
+    fn test_basic_hit_and_miss() {
+        let mut pool = LRUKBufferBlock::new();
+        pool.capacity = 4;
+
+        assert!(!pool.get_page(1));
+        assert_eq!(pool.misses, 1);
+
+        assert!(pool.get_page(1));
+        assert_eq!(pool.hits, 1);
+    }
+
+    #[test]
+    fn test_evicts_pages_with_short_history_first() {
+        let mut pool = LRUKBufferBlock::new();
+        pool.capacity = 3;
+
+        pool.get_page(1);
+        pool.get_page(1); // page 1 has K = 2 accesses
+        pool.get_page(2);
+        pool.get_page(3);
+
+        // Pages 2 and 3 have infinite distance; 2 is the least recent of them.
+        pool.get_page(4);
+        assert!(pool.contains(1));
+        assert!(!pool.contains(2));
+        assert_eq!(pool.cold_evictions, 1);
+    }
+
+    #[test]
+    fn test_evicts_oldest_kth_access() {
+        let mut pool = LRUKBufferBlock::new();
+        pool.capacity = 2;
+
+        // t=1,2: page 1 twice. t=3,4: page 2 twice. t=5: page 1 again.
+        for page in [1, 1, 2, 2, 1] {
+            pool.get_page(page);
+        }
+        // Page 1's 2nd most recent access is t=2, page 2's is t=3.
+        assert_eq!(pool.backward_k_distance(1), Some(3));
+        assert_eq!(pool.backward_k_distance(2), Some(2));
+
+        pool.get_page(3);
+        assert!(!pool.contains(1), "page 1 has the oldest K-th access");
+        assert!(pool.contains(2));
+        assert_eq!(pool.cold_evictions, 0);
+    }
+
+    #[test]
+    fn test_k1_matches_lru() {
+        let mut lru_k = LRUKBufferBlock::new();
+        lru_k.capacity = 8;
+        lru_k.k = 1;
+        let mut lru = LRUBufferBlock::new();
+        lru.capacity = 8;
+
+        for i in 0..500usize {
+            let page = (i * 7 + i / 3) % 13;
+            assert_eq!(lru_k.get_page(page), lru.get_page(page), "access {}", i);
+        }
+    }
+
+    #[test]
+    fn test_scan_resistance() {
+        let mut pool = LRUKBufferBlock::new();
+        pool.capacity = 10;
+
+        // Warm up a hot set of 5 pages, each read twice.
+        for _ in 0..2 {
+            for page in 0..5 {
+                pool.get_page(page);
+            }
+        }
+        // A scan of 100 distinct pages streams through the pool.
+        for page in 1000..1100 {
+            pool.get_page(page);
+        }
+
+        for page in 0..5 {
+            assert!(pool.contains(page), "hot page {} was flushed by the scan", page);
+        }
+        assert_eq!(pool.current_size(), 10);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let pool = LRUKBufferBlock::new();
+        assert_eq!(pool.metadata().id, "lru-k-buffer-pool");
+        assert_eq!(pool.metadata().category, BlockCategory::Buffer);
+        assert_eq!(pool.inputs().len(), 1);
+        assert_eq!(pool.outputs().len(), 1);
+        assert_eq!(pool.parameters().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_block_execute() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut pool = LRUKBufferBlock::new();
+        pool.capacity = 4;
+
+        let page_ids = [0, 1, 2, 0, 1, 3];
+        let records: Vec<Record> = page_ids
+            .iter()
+            .map(|&pid| {
+                let mut r = Record::new();
+                r.insert("_page_id".into(), pid as usize).unwrap();
+                r
+            })
+            .collect();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("requests".into(), PortValue::Stream(records));
+
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = pool.execute(ctx).await.unwrap();
+        assert!(result.errors.is_empty());
+        assert_eq!(*result.metrics.get("cache_hits").unwrap(), 2.0);
+        assert_eq!(*result.metrics.get("cache_misses").unwrap(), 4.0);
+        assert_eq!(result.outputs.get("pages").unwrap().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_initialize_with_params() {
+        let mut pool = LRUKBufferBlock::new();
+        let mut params = HashMap::new();
+        params.insert("size".into(), ParameterValue::Integer(256));
+        params.insert("k".into(), ParameterValue::Integer(3));
+
+        pool.initialize(params).await.unwrap();
+        assert_eq!(pool.capacity, 256);
+        assert_eq!(pool.k, 3);
+
+        let mut bad = HashMap::new();
+        bad.insert("k".into(), ParameterValue::Integer(0));
+        assert!(pool.initialize(bad).await.is_err());
+    }
+}
@@ -2,8 +2,39 @@
 //!
 //! Buffer blocks cache pages in memory to reduce storage I/O.
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+use crate::runtime::cost::CostModel;
+
 pub mod lru_buffer;
 pub mod clock_buffer;
+pub mod lru_k;
+pub mod two_q;
 
 pub use lru_buffer::LRUBufferBlock;
 pub use clock_buffer::ClockBufferBlock;
+pub use lru_k::LRUKBufferBlock;
+pub use two_q::TwoQBufferBlock;
+
+/// Simulated latency of one page request under the default cost model: every
+/// request reads the page, a miss first fetches it from disk, and a miss that
+/// evicted a dirty page first writes it back. Observed into the
+/// `access_latency_ms` histogram so the miss tail shows up in p95/p99.
+pub(crate) fn access_latency_ms(hit: bool, wrote_back: bool) -> f64 {
+    let mut latency = CostModel::DEFAULT_PAGE_READ_LATENCY_MS;
+    if !hit {
+        latency += CostModel::DEFAULT_DISK_LATENCY_MS;
+    }
+    if wrote_back {
+        latency += CostModel::DEFAULT_PAGE_WRITE_LATENCY_MS;
+    }
+    latency
+}
+
+/// Register a factory for each buffer block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(LRUBufferBlock::new()))?;
+    registry.register_factory(|| Box::new(ClockBufferBlock::new()))?;
+    registry.register_factory(|| Box::new(LRUKBufferBlock::new()))?;
+    registry.register_factory(|| Box::new(TwoQBufferBlock::new()))?;
+    Ok(())
+}
@@ -0,0 +1,901 @@
+//! 2Q Buffer Pool Block
+//!
+//! A fixed-size page cache using the **2Q** (two-queue) replacement policy.
+//! New pages enter a small FIFO queue and only move to the main LRU queue once
+//! they are accessed a second time, so a one-pass sequential scan cannot flush
+//! the hot working set.
+//!
+//! ## How it works
+//!
+//! The pool is split into three queues:
+//! - **A1in** — a FIFO of resident pages seen once, capped at `kin_ratio * size`.
+//! - **Am** — an LRU of resident pages that have been accessed again.
+//! - **A1out** — a FIFO of *page IDs only* (ghost entries) recently evicted from
+//!   A1in, capped at `kout_ratio * size`.
+//!
+//! A second access to a page in A1in, or a miss on a page still remembered in
+//! A1out, promotes it to Am. When space is needed, A1in gives up its oldest page
+//! (remembered in A1out) while it is over its share; otherwise Am evicts its
+//! least recently used page.
+//!
+//! Pages requested with `_is_write: true` become dirty and are written back
+//! when evicted from A1in or Am, or when `flush_all` runs.
+//!
+//! ## Metrics tracked
+//!
+//! | Metric | Type | Description |
+//! |--------|------|-------------|
+//! | `cache_hits` | Counter | Page requests served from cache |
+//! | `cache_misses` | Counter | Page requests that missed |
+//! | `hit_rate_pct` | Gauge | cache_hits / (hits + misses) * 100 |
+//! | `evictions` | Counter | Pages evicted to make room |
+//! | `dirty_evictions` | Counter | Evicted pages that were dirty and had to be written back |
+//! | `pages_flushed` | Counter | Dirty pages written back (evictions + `flush_all`) |
+//! | `promotions` | Counter | Pages moved into the main LRU queue (Am) |
+//! | `ghost_hits` | Counter | Misses on pages remembered in A1out |
+//! | `current_size` | Gauge | Pages currently in the pool |
+//! | `access_latency_ms` | Histogram | Simulated latency of each page request (p50/p95/p99) |
+
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+
+use super::lru_buffer::LruList;
+use crate::core::block::{
+    Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
+    Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
+};
+use crate::core::constraint::{Constraint, Guarantee};
+use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
+use crate::core::parameter::{
+    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    ValidationResult, WidgetType,
+};
+use crate::core::port::{Port, PortDirection, PortType, PortValue};
+
+use super::access_latency_ms;
+
+// ---------------------------------------------------------------------------
+// TwoQBufferBlock
+// ---------------------------------------------------------------------------
+
+pub struct TwoQBufferBlock {
+    metadata: BlockMetadata,
+    input_ports: Vec<Port>,
+    output_ports: Vec<Port>,
+    params: Vec<Parameter>,
+    metric_defs: Vec<MetricDefinition>,
+
+    // Configuration
+    pub(crate) capacity: usize,
+    page_size: usize,
+    kin_ratio: f64,
+    kout_ratio: f64,
+
+    // Internal state
+    /// Resident pages seen once, in FIFO order.
+    a1in: LruList,
+    /// Resident pages accessed more than once, in LRU order.
+    am: LruList,
+    /// Ghost entries: page IDs recently evicted from A1in (no data).
+    a1out: LruList,
+    /// Pages modified since they were loaded or last flushed.
+    dirty: HashSet<usize>,
+
+    // Stats
+    hits: usize,
+    misses: usize,
+    evictions: usize,
+    dirty_evictions: usize,
+    pages_flushed: usize,
+    promotions: usize,
+    ghost_hits: usize,
+}
+
+impl TwoQBufferBlock {
+    pub fn new() -> Self {
+        Self {
+            metadata: Self::build_metadata(),
+            input_ports: Self::build_inputs(),
+            output_ports: Self::build_outputs(),
+            params: Self::build_parameters(),
+            metric_defs: Self::build_metrics(),
+            capacity: 1024,
+            page_size: 8192,
+            kin_ratio: 0.25,
+            kout_ratio: 0.5,
+            a1in: LruList::default(),
+            am: LruList::default(),
+            a1out: LruList::default(),
+            dirty: HashSet::new(),
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+            dirty_evictions: 0,
+            pages_flushed: 0,
+            promotions: 0,
+            ghost_hits: 0,
+        }
+    }
+
+    // -- Metadata builders ---------------------------------------------------
+
+    fn build_metadata() -> BlockMetadata {
+        BlockMetadata {
+            id: "2q-buffer-pool".into(),
+            name: "2Q Buffer Pool".into(),
+            category: BlockCategory::Buffer,
+            description: "Scan-resistant page cache with a FIFO probation queue and an LRU main queue"
+                .into(),
+            version: "1.0.0".into(),
+            documentation: BlockDocumentation {
+                overview: "2Q is a buffer replacement policy that gives new pages a probation \
+                           period before they can compete with the working set. A page seen for \
+                           the first time goes into a small FIFO queue (A1in). Only if it is \
+                           accessed again does it move into the main LRU queue (Am), where hot \
+                           pages live. Pages that leave A1in without a second access are \
+                           remembered for a while in a ghost queue (A1out) that stores just \
+                           their IDs, so a page that comes back soon after is recognised and \
+                           promoted straight to Am.\n\n\
+                           This matters because a single sequential scan touches every page \
+                           exactly once. Under LRU or CLOCK those pages push the whole working \
+                           set out of memory. Under 2Q they cycle through A1in and evict each \
+                           other, while Am — and the hot pages in it — stays untouched.\n\n\
+                           Think of a library's new-arrivals shelf: every new book goes there \
+                           first, and only books that get borrowed again earn a place in the \
+                           main stacks. A donation of a thousand books no one reads never \
+                           displaces the popular titles."
+                    .into(),
+                algorithm: "2Q Buffer Pool Algorithm:\n\
+                            \n\
+                            STATE: A1in (FIFO, resident), Am (LRU, resident), A1out (FIFO, IDs only)\n\
+                            Kin = kin_ratio * size, Kout = kout_ratio * size\n\
+                            \n\
+                            FUNCTION get_page(page_id):\n  \
+                              IF page_id IN Am:\n    \
+                                Move page_id to MRU end of Am      // HIT\n  \
+                              ELSE IF page_id IN A1in:\n    \
+                                Move page_id from A1in to Am       // HIT, promotion\n  \
+                              ELSE IF page_id IN A1out:\n    \
+                                Remove page_id from A1out          // MISS, promotion\n    \
+                                reclaim(); insert page_id into Am\n  \
+                              ELSE:\n    \
+                                reclaim(); insert page_id into A1in  // MISS\n\
+                            \n\
+                            FUNCTION reclaim():\n  \
+                              IF pool is not full: RETURN\n  \
+                              IF |A1in| > Kin OR Am is empty:\n    \
+                                victim = oldest page in A1in\n    \
+                                Push victim's ID to A1out (drop oldest ID if |A1out| > Kout)\n  \
+                              ELSE:\n    \
+                                victim = least recently used page in Am\n  \
+                              evictions += 1"
+                    .into(),
+                complexity: Complexity {
+                    time: "O(1) per access — each queue is a hash-indexed linked list".into(),
+                    space: "O(size + Kout) — resident pages plus ghost page IDs".into(),
+                },
+                use_cases: vec![
+                    "Buffer pools shared by OLTP point lookups and analytical scans".into(),
+                    "Protecting index and hot data pages from one-off table scans".into(),
+                    "Comparing scan resistance against LRU and CLOCK".into(),
+                    "Caches in front of slow storage with frequent cold reads".into(),
+                ],
+                tradeoffs: vec![
+                    "Resists scan pollution at O(1) cost, unlike LRU-K's eviction search".into(),
+                    "Two tuning knobs (kin_ratio, kout_ratio) instead of none".into(),
+                    "A page needs a second access before it is protected, so new hot data \
+                     takes a moment to settle into Am"
+                        .into(),
+                    "Ghost entries in A1out cost a little memory per remembered page ID".into(),
+                    "If A1in is too small, pages with a short gap between accesses are \
+                     evicted before their second access"
+                        .into(),
+                ],
+                examples: vec![
+                    "PostgreSQL 8.0.0 briefly shipped 2Q before switching to clock-sweep".into(),
+                    "MySQL InnoDB's young/old sublist LRU follows the same idea: new pages \
+                     start in the old sublist and move to the young sublist on re-access"
+                        .into(),
+                    "Linux's active/inactive page lists are a two-queue design".into(),
+                ],
+                motivation: "LRU assumes that one access predicts another, which is false for \
+                             sequential scans. A report that scans a large table leaves the \
+                             buffer pool full of pages that will never be read again and forces \
+                             every subsequent point lookup to go to disk until the working set \
+                             is reloaded.\n\n\
+                             2Q keeps LRU's O(1) cost but admits pages to the main queue only \
+                             after they prove they are reused, so scans stay in the small \
+                             probation queue."
+                    .into(),
+                parameter_guide: HashMap::from([
+                    ("size".into(), "The maximum number of resident pages, shared between \
+                                     A1in and Am. Size it to hold the hot working set plus the \
+                                     A1in share. Default is 1024 pages."
+                        .into()),
+                    ("page_size".into(), "The size of each cached page in bytes, used for \
+                                          memory accounting. Default is 8192 (8 KB)."
+                        .into()),
+                    ("kin_ratio".into(), "The fraction of the pool that A1in may hold before it \
+                                          is the first to give up pages. Smaller values protect \
+                                          Am more aggressively from scans; larger values give \
+                                          new pages more time to be accessed again before they \
+                                          are evicted. The 2Q paper recommends 0.25. Range: \
+                                          0.05-0.9."
+                        .into()),
+                    ("kout_ratio".into(), "How many ghost page IDs A1out remembers, as a \
+                                           fraction of the pool size. A larger A1out catches \
+                                           pages that are re-read after a longer gap and \
+                                           promotes them to Am; 0 disables the ghost queue. \
+                                           The 2Q paper recommends 0.5. Range: 0-2."
+                        .into()),
+                ]),
+                alternatives: vec![
+                    Alternative {
+                        block_type: "lru-buffer-pool".into(),
+                        comparison: "LRU admits every page straight into the main queue, so a \
+                                     scan larger than the pool evicts the entire working set. \
+                                     Choose LRU for workloads without large scans; choose 2Q \
+                                     when scans and point lookups share the pool."
+                            .into(),
+                    },
+                    Alternative {
+                        block_type: "lru-k-buffer-pool".into(),
+                        comparison: "LRU-K also resists scans by requiring K accesses, but \
+                                     finding its victim needs a search over all pages. 2Q gets \
+                                     similar protection with O(1) queue operations."
+                            .into(),
+                    },
+                ],
+                suggested_questions: vec![
+                    "Warm up a hot set, then run a scan larger than the pool. How many hot \
+                     pages survive under 2Q versus LRU?"
+                        .into(),
+                    "What happens to the hit rate when kin_ratio is very small and pages \
+                     are re-read after a short delay?"
+                        .into(),
+                    "Why does A1out store only page IDs and not page data?".into(),
+                ],
+            },
+            references: vec![Reference {
+                ref_type: ReferenceType::Paper,
+                title: "2Q: A Low Overhead High Performance Buffer Management Replacement \
+                        Algorithm"
+                    .into(),
+                url: None,
+                citation: Some("Johnson, T., & Shasha, D. (1994). VLDB '94.".into()),
+            }],
+            icon: "layers".into(),
+            color: "#F59E0B".into(),
+        }
+    }
+
+    fn build_inputs() -> Vec<Port> {
+        vec![Port {
+            id: "requests".into(),
+            name: "Page Requests".into(),
+            port_type: PortType::DataStream,
+            direction: PortDirection::Input,
+            required: true,
+            multiple: false,
+            description: "Records with a `_page_id` field identifying the requested page".into(),
+            schema: None,
+        }]
+    }
+
+    fn build_outputs() -> Vec<Port> {
+        vec![Port {
+            id: "pages".into(),
+            name: "Served Pages".into(),
+            port_type: PortType::DataStream,
+            direction: PortDirection::Output,
+            required: false,
+            multiple: true,
+            description: "Records enriched with `_cache_hit` (bool) and `_page_data_size`".into(),
+            schema: None,
+        }]
+    }
+
+    fn build_parameters() -> Vec<Parameter> {
+        vec![
+            Parameter {
+                id: "size".into(),
+                name: "Pool Size".into(),
+                param_type: ParameterType::Number,
+                description: "Maximum number of pages to cache".into(),
+                default_value: ParameterValue::Integer(1024),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(1.0).with_max(1_000_000.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(64.0)
+                        .with_unit("pages".into()),
+                ),
+            },
+            Parameter {
+                id: "page_size".into(),
+                name: "Page Size".into(),
+                param_type: ParameterType::Number,
+                description: "Size of each page in bytes (for memory accounting)".into(),
+                default_value: ParameterValue::Integer(8192),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(512.0).with_max(65536.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(512.0)
+                        .with_unit("bytes".into()),
+                ),
+            },
+            Parameter {
+                id: "kin_ratio".into(),
+                name: "A1in Ratio".into(),
+                param_type: ParameterType::Number,
+                description: "Share of the pool for first-access pages (A1in FIFO)".into(),
+                default_value: ParameterValue::Number(0.25),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_min(0.05).with_max(0.9)),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(0.05)
+                        .with_help_text("Smaller = stronger scan protection".into()),
+                ),
+            },
+            Parameter {
+                id: "kout_ratio".into(),
+                name: "A1out Ratio".into(),
+                param_type: ParameterType::Number,
+                description: "Ghost page IDs remembered after leaving A1in, relative to pool size"
+                    .into(),
+                default_value: ParameterValue::Number(0.5),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_min(0.0).with_max(2.0)),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(0.1)
+                        .with_help_text("0 disables the ghost queue".into()),
+                ),
+            },
+        ]
+    }
+
+    fn build_metrics() -> Vec<MetricDefinition> {
+        vec![
+            MetricDefinition {
+                id: "cache_hits".into(),
+                name: "Cache Hits".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Page requests served from cache".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "cache_misses".into(),
+                name: "Cache Misses".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Page requests that missed the cache".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "hit_rate_pct".into(),
+                name: "Hit Rate".into(),
+                metric_type: MetricType::Gauge,
+                unit: "%".into(),
+                description: "Percentage of requests served from cache".into(),
+                aggregations: vec![AggregationType::Avg],
+            },
+            MetricDefinition {
+                id: "evictions".into(),
+                name: "Evictions".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Pages evicted from cache".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "dirty_evictions".into(),
+                name: "Dirty Evictions".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Evicted pages that had to be written back first".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "pages_flushed".into(),
+                name: "Pages Flushed".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Dirty pages written back, on eviction or by flush_all".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "promotions".into(),
+                name: "Promotions".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Pages moved into the main LRU queue".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "ghost_hits".into(),
+                name: "Ghost Hits".into(),
+                metric_type: MetricType::Counter,
+                unit: "pages".into(),
+                description: "Misses on pages remembered in the A1out ghost queue".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "current_size".into(),
+                name: "Current Size".into(),
+                metric_type: MetricType::Gauge,
+                unit: "pages".into(),
+                description: "Pages currently in the buffer pool".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "access_latency_ms".into(),
+                name: "Access Latency".into(),
+                metric_type: MetricType::Histogram,
+                unit: "ms".into(),
+                description: "Simulated latency of each page request; misses form the tail".into(),
+                aggregations: vec![
+                    AggregationType::P50,
+                    AggregationType::P95,
+                    AggregationType::P99,
+                ],
+            },
+        ]
+    }
+
+    // -- Core operations -----------------------------------------------------
+
+    /// Maximum number of pages A1in holds before it gives up pages first.
+    fn kin(&self) -> usize {
+        ((self.capacity as f64 * self.kin_ratio).round() as usize).max(1)
+    }
+
+    /// Maximum number of ghost page IDs kept in A1out.
+    fn kout(&self) -> usize {
+        (self.capacity as f64 * self.kout_ratio).round() as usize
+    }
+
+    /// Request a page. Returns `true` if it was a cache hit.
+    pub fn get_page(&mut self, page_id: usize) -> bool {
+        if self.am.touch(page_id) {
+            self.hits += 1;
+            return true;
+        }
+
+        if self.a1in.remove(page_id) {
+            // Second access while on probation — promote to Am.
+            self.am.push_back(page_id, vec![0u8; self.page_size]);
+            self.promotions += 1;
+            self.hits += 1;
+            return true;
+        }
+
+        self.misses += 1;
+        self.reclaim();
+        if self.a1out.remove(page_id) {
+            // Re-read soon after leaving A1in — it is part of the working set.
+            self.am.push_back(page_id, vec![0u8; self.page_size]);
+            self.promotions += 1;
+            self.ghost_hits += 1;
+        } else {
+            self.a1in.push_back(page_id, vec![0u8; self.page_size]);
+        }
+        false
+    }
+
+    /// Free one resident slot if the pool is full.
+    fn reclaim(&mut self) {
+        if self.current_size() < self.capacity {
+            return;
+        }
+        if self.a1in.len() > self.kin() || self.am.len() == 0 {
+            if let Some(victim) = self.a1in.pop_front() {
+                self.write_back(victim);
+                if self.kout() > 0 {
+                    self.a1out.push_back(victim, Vec::new());
+                    if self.a1out.len() > self.kout() {
+                        self.a1out.pop_front();
+                    }
+                }
+                self.evictions += 1;
+            }
+        } else if let Some(victim) = self.am.pop_front() {
+            self.write_back(victim);
+            self.evictions += 1;
+        }
+    }
+
+    /// Request a page for writing, marking it dirty. Returns `true` on a hit.
+    pub fn write_page(&mut self, page_id: usize) -> bool {
+        let hit = self.get_page(page_id);
+        self.dirty.insert(page_id);
+        hit
+    }
+
+    /// Write back every dirty page (a checkpoint). Returns the number flushed.
+    pub fn flush_all(&mut self) -> usize {
+        let flushed = self.dirty.len();
+        self.pages_flushed += flushed;
+        self.dirty.clear();
+        flushed
+    }
+
+    /// Number of cached pages modified since they were loaded or flushed.
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Write back an evicted page if it is dirty.
+    fn write_back(&mut self, page_id: usize) {
+        if self.dirty.remove(&page_id) {
+            self.dirty_evictions += 1;
+            self.pages_flushed += 1;
+        }
+    }
+
+    /// Current number of resident pages (A1in + Am).
+    pub fn current_size(&self) -> usize {
+        self.a1in.len() + self.am.len()
+    }
+
+    /// Hit rate as a percentage (0–100).
+    pub fn hit_rate_pct(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        (self.hits as f64 / total as f64) * 100.0
+    }
+
+    /// Check if a specific page is resident.
+    pub fn contains(&self, page_id: usize) -> bool {
+        self.a1in.contains(page_id) || self.am.contains(page_id)
+    }
+}
+
+impl Default for TwoQBufferBlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Block trait
+// ---------------------------------------------------------------------------
+
+#[async_trait]
+impl Block for TwoQBufferBlock {
+    fn metadata(&self) -> &BlockMetadata {
+        &self.metadata
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.input_ports
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.output_ports
+    }
+
+    fn parameters(&self) -> &[Parameter] {
+        &self.params
+    }
+
+    fn requires(&self) -> &[Constraint] {
+        &[]
+    }
+
+    fn guarantees(&self) -> &[Guarantee] {
+        &[]
+    }
+
+    fn metrics(&self) -> &[MetricDefinition] {
+        &self.metric_defs
+    }
+
+    async fn initialize(
+        &mut self,
+        params: HashMap<String, ParameterValue>,
+    ) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("size").and_then(ParameterValue::as_integer) {
+            self.capacity = v as usize;
+        }
+        if let Some(v) = params.get("page_size").and_then(ParameterValue::as_integer) {
+            self.page_size = v as usize;
+        }
+        if let Some(v) = params.get("kin_ratio").and_then(ParameterValue::as_number) {
+            self.kin_ratio = v;
+        }
+        if let Some(v) = params.get("kout_ratio").and_then(ParameterValue::as_number) {
+            self.kout_ratio = v;
+        }
+        Ok(())
+    }
+
+    async fn execute(
+        &mut self,
+        context: ExecutionContext,
+    ) -> Result<ExecutionResult, BlockError> {
+        let input = context
+            .inputs
+            .get("requests")
+            .cloned()
+            .unwrap_or(PortValue::None);
+
+        let records = match input {
+            PortValue::Stream(r) => r,
+            PortValue::Batch(r) => r,
+            PortValue::Single(r) => vec![r],
+            PortValue::None => Vec::new(),
+            _ => {
+                return Err(BlockError::InvalidInput(
+                    "Expected DataStream, Batch, or Single".into(),
+                ))
+            }
+        };
+
+        let mut output_records = Vec::with_capacity(records.len());
+
+        for record in records {
+            let page_id = record
+                .get::<usize>("_page_id")
+                .ok()
+                .flatten()
+                .unwrap_or(0);
+
+            let is_write = record
+                .get::<bool>("_is_write")
+                .ok()
+                .flatten()
+                .unwrap_or(false);
+
+            let dirty_before = self.dirty_evictions;
+            let hit = if is_write {
+                self.write_page(page_id)
+            } else {
+                self.get_page(page_id)
+            };
+
+            if hit {
+                context.metrics.increment("cache_hits");
+            } else {
+                context.metrics.increment("cache_misses");
+            }
+            context.metrics.observe(
+                "access_latency_ms",
+                access_latency_ms(hit, self.dirty_evictions > dirty_before),
+            );
+
+            let mut out = record;
+            let _ = out.insert("_cache_hit".into(), hit);
+            let _ = out.insert("_page_data_size".into(), self.page_size);
+            output_records.push(out);
+        }
+
+        context.metrics.record("hit_rate_pct", self.hit_rate_pct());
+        context.metrics.record("evictions", self.evictions as f64);
+        context.metrics.record("dirty_evictions", self.dirty_evictions as f64);
+        context.metrics.record("pages_flushed", self.pages_flushed as f64);
+        context.metrics.record("promotions", self.promotions as f64);
+        context.metrics.record("ghost_hits", self.ghost_hits as f64);
+        context.metrics.record("current_size", self.current_size() as f64);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("pages".into(), PortValue::Stream(output_records));
+
+        let mut metrics_summary = HashMap::new();
+        metrics_summary.insert("cache_hits".into(), self.hits as f64);
+        metrics_summary.insert("cache_misses".into(), self.misses as f64);
+        metrics_summary.insert("hit_rate_pct".into(), self.hit_rate_pct());
+        metrics_summary.insert("evictions".into(), self.evictions as f64);
+        metrics_summary.insert("dirty_evictions".into(), self.dirty_evictions as f64);
+        metrics_summary.insert("pages_flushed".into(), self.pages_flushed as f64);
+        metrics_summary.insert("promotions".into(), self.promotions as f64);
+        metrics_summary.insert("ghost_hits".into(), self.ghost_hits as f64);
+        metrics_summary.insert("current_size".into(), self.current_size() as f64);
+
+        Ok(ExecutionResult {
+            outputs,
+            metrics: metrics_summary,
+            errors: vec![],
+        })
+    }
+
+    fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
+        if let Some(input) = inputs.get("requests") {
+            match input {
+                PortValue::Stream(_) | PortValue::Batch(_) | PortValue::Single(_) => {
+                    ValidationResult::ok()
+                }
+                PortValue::None => ValidationResult::ok().with_warning("No page requests provided"),
+                _ => ValidationResult::error("requests port expects DataStream"),
+            }
+        } else {
+            ValidationResult::ok().with_warning("requests input not connected")
+        }
+    }
+
+    fn get_state(&self) -> BlockState {
+        let mut state = BlockState::new();
+        let _ = state.insert("capacity".into(), self.capacity);
+        let _ = state.insert("page_size".into(), self.page_size);
+        let _ = state.insert("kin_ratio".into(), self.kin_ratio);
+        let _ = state.insert("kout_ratio".into(), self.kout_ratio);
+        let _ = state.insert("current_size".into(), self.current_size());
+        let _ = state.insert("hits".into(), self.hits);
+        let _ = state.insert("misses".into(), self.misses);
+        let _ = state.insert("evictions".into(), self.evictions);
+        let _ = state.insert("dirty_pages".into(), self.dirty.len());
+        state
+    }
+
+    fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
+        if let Ok(Some(c)) = state.get::<usize>("capacity") {
+            self.capacity = c;
+        }
+        if let Ok(Some(ps)) = state.get::<usize>("page_size") {
+            self.page_size = ps;
+        }
+        if let Ok(Some(r)) = state.get::<f64>("kin_ratio") {
+            self.kin_ratio = r;
+        }
+        if let Ok(Some(r)) = state.get::<f64>("kout_ratio") {
+            self.kout_ratio = r;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::categories::buffer::LRUBufferBlock;
+    use crate::core::port::Record;
+
+    #[test]
+    fn test_basic_hit_and_miss() {
+        let mut pool = TwoQBufferBlock::new();
+        pool.capacity = 4;
+
+        assert!(!pool.get_page(1));
+        assert_eq!(pool.misses, 1);
+
+        assert!(pool.get_page(1));
+        assert_eq!(pool.hits, 1);
+        assert_eq!(pool.promotions, 1, "second access promotes to Am");
+        assert!(pool.am.contains(1));
+    }
+
+    #[test]
+    fn test_ghost_hit_promotes() {
+        let mut pool = TwoQBufferBlock::new();
+        pool.capacity = 4; // Kin = 1, Kout = 2
+
+        for page in 0..5 {
+            pool.get_page(page);
+        }
+        // Page 0 was pushed out of A1in and is remembered in A1out.
+        assert!(!pool.contains(0));
+        assert!(pool.a1out.contains(0));
+
+        assert!(!pool.get_page(0), "ghost hit is still a miss");
+        assert_eq!(pool.ghost_hits, 1);
+        assert!(pool.am.contains(0));
+        assert!(pool.current_size() <= 4);
+    }
+
+    #[test]
+    fn test_a1out_is_bounded() {
+        let mut pool = TwoQBufferBlock::new();
+        pool.capacity = 8;
+        pool.kout_ratio = 0.5;
+
+        for page in 0..1000 {
+            pool.get_page(page);
+        }
+        assert_eq!(pool.a1out.len(), 4);
+        assert_eq!(pool.current_size(), 8);
+    }
+
+    #[test]
+    fn test_scan_does_not_flush_hot_set() {
+        let mut two_q = TwoQBufferBlock::new();
+        two_q.capacity = 10;
+        let mut lru = LRUBufferBlock::new();
+        lru.capacity = 10;
+
+        // Warm up a hot set of 5 pages, each read twice.
+        for _ in 0..2 {
+            for page in 0..5 {
+                two_q.get_page(page);
+                lru.get_page(page);
+            }
+        }
+        // A scan of 100 distinct pages streams through both pools.
+        for page in 1000..1100 {
+            two_q.get_page(page);
+            lru.get_page(page);
+        }
+
+        for page in 0..5 {
+            assert!(two_q.contains(page), "2Q lost hot page {} to the scan", page);
+            assert!(!lru.contains(page), "LRU is expected to lose hot page {}", page);
+        }
+    }
+
+    #[test]
+    fn test_metadata() {
+        let pool = TwoQBufferBlock::new();
+        assert_eq!(pool.metadata().id, "2q-buffer-pool");
+        assert_eq!(pool.metadata().category, BlockCategory::Buffer);
+        assert_eq!(pool.inputs().len(), 1);
+        assert_eq!(pool.outputs().len(), 1);
+        assert_eq!(pool.parameters().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_block_execute() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut pool = TwoQBufferBlock::new();
+        pool.capacity = 4;
+
+        let page_ids = [0, 1, 2, 0, 1, 3];
+        let records: Vec<Record> = page_ids
+            .iter()
+            .map(|&pid| {
+                let mut r = Record::new();
+                r.insert("_page_id".into(), pid as usize).unwrap();
+                r
+            })
+            .collect();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("requests".into(), PortValue::Stream(records));
+
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = pool.execute(ctx).await.unwrap();
+        assert!(result.errors.is_empty());
+        assert_eq!(*result.metrics.get("cache_hits").unwrap(), 2.0);
+        assert_eq!(*result.metrics.get("cache_misses").unwrap(), 4.0);
+        assert_eq!(*result.metrics.get("promotions").unwrap(), 2.0);
+        assert_eq!(result.outputs.get("pages").unwrap().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_initialize_with_params() {
+        let mut pool = TwoQBufferBlock::new();
+        let mut params = HashMap::new();
+        params.insert("size".into(), ParameterValue::Integer(100));
+        params.insert("kin_ratio".into(), ParameterValue::Number(0.1));
+        params.insert("kout_ratio".into(), ParameterValue::Number(1.0));
+
+        pool.initialize(params).await.unwrap();
+        assert_eq!(pool.capacity, 100);
+        assert_eq!(pool.kin(), 10);
+        assert_eq!(pool.kout(), 100);
+
+        let mut bad = HashMap::new();
+        bad.insert("kin_ratio".into(), ParameterValue::Number(1.5));
+        assert!(pool.initialize(bad).await.is_err());
+    }
+}
@@ -11,6 +11,20 @@
 //! is a simple lookup. Most effective for low-cardinality columns
 //! (e.g., country, status, category).
 //!
+//! In `encode` mode (the default) each record's `_key` is replaced by a
+//! `_dict_code`; in `decode` mode records carrying `_dict_code` get their
+//! `_key` back from the dictionary built by earlier encodes. An encoder also
+//! sends each dictionary entry it adds on its `dictionary_entries` port, as a
+//! `{ _dict_code, _key }` record; wire that to a separate decode-mode block's
+//! `dictionary` input and it loads the entries before decoding the
+//! records that arrive with them. Once the
+//! dictionary holds `max_dictionary_entries` values it stops growing: new
+//! values are written inline behind an escape code and counted as
+//! `dictionary_overflows`. The compression
+//! ratio charges the dictionary itself to the encoded size, so a column whose
+//! cardinality approaches its row count shows no gain — `validate` warns when
+//! the input looks like that.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
@@ -18,7 +32,7 @@
 //! | `entries_encoded` | Counter | Total records processed |
 //! | `dictionary_size` | Gauge | Number of unique values in dictionary |
 //! | `compression_ratio` | Gauge | Original size / compressed size |
-//! | `dictionary_full_events` | Counter | Times the dictionary reached capacity |
+//! | `dictionary_overflows` | Counter | Values stored inline because the dictionary was full |
 
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -30,10 +44,29 @@
 use crate::core::constraint::{Constraint, Guarantee};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
-use crate::core::port::{Port, PortDirection, PortType, PortValue};
+use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
+
+/// Bytes per raw value (u64 key).
+const RAW_VALUE_BYTES: usize = 8;
+/// Bytes per dictionary code (u32).
+const CODE_BYTES: usize = 4;
+/// Code marking a value stored inline instead of in the dictionary.
+pub const ESCAPE_CODE: u32 = u32::MAX;
+/// Inputs with at least this share of distinct keys are a poor fit.
+const POOR_FIT_DISTINCT_RATIO: f64 = 0.9;
+
+/// Whether `execute` encodes values or decodes codes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DictionaryMode {
+    Encode,
+    Decode,
+}
+
+/// Values accepted by the `mode` parameter.
+const MODES: &[&str] = &["encode", "decode"];
 
 // ---------------------------------------------------------------------------
 // DictionaryEncodingBlock
@@ -47,15 +80,17 @@ pub struct DictionaryEncodingBlock {
     metric_defs: Vec<MetricDefinition>,
 
     // Configuration
-    max_dictionary_size: usize,
+    max_dictionary_entries: usize,
+    mode: DictionaryMode,
 
     // Internal state
     dictionary: HashMap<u64, u32>, // value → code
+    values: Vec<Option<u64>>,      // code → value, `None` until loaded
     next_code: u32,
 
     // Stats
     entries_encoded: usize,
-    dictionary_full_events: usize,
+    dictionary_overflows: usize,
     original_size_bytes: usize,
     compressed_size_bytes: usize,
 }
@@ -68,11 +103,13 @@ pub fn new() -> Self {
             output_ports: Self::build_outputs(),
             params: Self::build_parameters(),
             metric_defs: Self::build_metrics(),
-            max_dictionary_size: 4096,
+            max_dictionary_entries: 4096,
+            mode: DictionaryMode::Encode,
             dictionary: HashMap::new(),
+            values: Vec::new(),
             next_code: 0,
             entries_encoded: 0,
-            dictionary_full_events: 0,
+            dictionary_overflows: 0,
             original_size_bytes: 0,
             compressed_size_bytes: 0,
         }
@@ -109,19 +146,20 @@ fn build_metadata() -> BlockMetadata {
                            For each value in values:\n    \
                              If value in dictionary:\n      \
                                encoded.push(dictionary[value])\n    \
-                             Else if dictionary.size() < max_dictionary_size:\n      \
+                             Else if dictionary.size() < max_dictionary_entries:\n      \
                                dictionary[value] = next_code\n      \
                                encoded.push(next_code)\n      \
                                next_code += 1\n    \
                              Else:\n      \
-                               // Dictionary full — fall back to uncompressed\n      \
-                               encoded.push(value)  // store raw\n      \
-                               dictionary_full_events += 1\n\n\
+                               // Dictionary full — stop growing, store inline\n      \
+                               encoded.push(ESCAPE, value)  // escape code + raw value\n      \
+                               dictionary_overflows += 1\n\n\
                            DECODE(code):\n  \
                            Return reverse_dictionary[code]  // O(1) array lookup\n\n\
                            COMPRESSION_RATIO:\n  \
-                           original_size / compressed_size\n  \
-                           E.g., 8-byte strings -> 2-byte codes = 4x compression"
+                           original_size / (compressed_size + dictionary_size * (value + code bytes))\n  \
+                           E.g., 8-byte values -> 4-byte codes = 2x, minus the dictionary's own cost\n  \
+                           All-distinct values: 8n / (4n + 12n) = 0.5x — worse than no encoding"
                     .into(),
                 complexity: Complexity {
                     time: "O(n) to encode n values — one hash lookup per value".into(),
@@ -160,9 +198,13 @@ fn build_metadata() -> BlockMetadata {
                              to the final output stage."
                     .into(),
                 parameter_guide: HashMap::from([
-                    ("max_dictionary_size".into(),
+                    ("max_dictionary_entries".into(),
                      "The maximum number of distinct values the dictionary can hold. When this limit is \
-                      reached, new values cannot be encoded and are stored uncompressed (fallback). A \
+                      reached the dictionary stops growing: values already in it keep their codes, and each \
+                      new value is stored inline behind an escape code (counted in dictionary_overflows), \
+                      which costs more than storing it raw. Parquet does the same per column chunk, falling \
+                      back to plain encoding once the dictionary page is full. (The older name \
+                      max_dictionary_size is still accepted.) A \
                       larger dictionary (8192-65536) can handle higher cardinality columns but uses more \
                       memory and may reduce compression effectiveness when the dictionary itself becomes \
                       large. A smaller dictionary (16-256) works well for very low cardinality (e.g., \
@@ -170,6 +212,14 @@ fn build_metadata() -> BlockMetadata {
                       dictionary per column chunk). Recommended: 4096 for general use; 256-1024 for columns \
                       you know have very low cardinality; 8192-65536 for medium cardinality columns."
                         .into()),
+                    ("mode".into(),
+                     "encode (the default) replaces each record's _key with a _dict_code from the \
+                      dictionary; decode does the reverse, turning _dict_code back into _key using the \
+                      dictionary built by earlier encodes. To decode in a separate block, connect the \
+                      encoder's compressed output to the decoder's records input and its \
+                      dictionary_entries output to the decoder's dictionary input, so the decoder \
+                      learns each code as it is assigned — this verifies the round trip is lossless."
+                        .into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -213,35 +263,70 @@ fn build_metadata() -> BlockMetadata {
     }
 
     fn build_inputs() -> Vec<Port> {
-        vec![Port {
-            id: "records".into(),
-            name: "Records".into(),
-            port_type: PortType::DataStream,
-            direction: PortDirection::Input,
-            required: true,
-            multiple: false,
-            description: "Records to compress using dictionary encoding".into(),
-            schema: None,
-        }]
+        vec![
+            Port {
+                id: "records".into(),
+                name: "Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Input,
+                required: true,
+                multiple: false,
+                description: "Records to compress using dictionary encoding".into(),
+                schema: None,
+            },
+            Port {
+                id: "dictionary".into(),
+                name: "Dictionary".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Input,
+                required: false,
+                multiple: false,
+                description: "`{ _dict_code, _key }` entries from an encoder, loaded before decoding".into(),
+                schema: None,
+            },
+        ]
     }
 
     fn build_outputs() -> Vec<Port> {
-        vec![Port {
-            id: "compressed".into(),
-            name: "Compressed Records".into(),
-            port_type: PortType::DataStream,
-            direction: PortDirection::Output,
-            required: false,
-            multiple: true,
-            description: "Records with `_dict_code` replacing original values".into(),
-            schema: None,
-        }]
+        vec![
+            Port {
+                id: "compressed".into(),
+                name: "Compressed Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Records with `_dict_code` replacing original values".into(),
+                schema: None,
+            },
+            Port {
+                id: "decoded".into(),
+                name: "Decoded Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Records with `_key` reconstructed from `_dict_code` (decode mode)".into(),
+                schema: None,
+            },
+            Port {
+                id: "dictionary_entries".into(),
+                name: "Dictionary Entries".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "`{ _dict_code, _key }` for each entry this call added (encode mode)".into(),
+                schema: None,
+            },
+        ]
     }
 
     fn build_parameters() -> Vec<Parameter> {
-        vec![Parameter {
-            id: "max_dictionary_size".into(),
-            name: "Max Dictionary Size".into(),
+        vec![
+        Parameter {
+            id: "max_dictionary_entries".into(),
+            name: "Max Dictionary Entries".into(),
             param_type: ParameterType::Number,
             description: "Maximum number of distinct values before falling back to uncompressed".into(),
             default_value: ParameterValue::Integer(4096),
@@ -254,6 +339,16 @@ fn build_parameters() -> Vec<Parameter> {
                     .with_step(256.0)
                     .with_unit("entries".into()),
             ),
+        },
+        Parameter {
+            id: "mode".into(),
+            name: "Mode".into(),
+            param_type: ParameterType::Enum,
+            description: "encode values to codes, or decode codes back to values".into(),
+            default_value: ParameterValue::String("encode".into()),
+            required: false,
+            constraints: Some(ParameterConstraints::new().with_choices(MODES)),
+            ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
         }]
     }
 
@@ -280,15 +375,15 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 name: "Compression Ratio".into(),
                 metric_type: MetricType::Gauge,
                 unit: "x".into(),
-                description: "Original size divided by compressed size".into(),
+                description: "Original size divided by compressed size including the dictionary".into(),
                 aggregations: vec![AggregationType::Avg],
             },
             MetricDefinition {
-                id: "dictionary_full_events".into(),
-                name: "Dictionary Full".into(),
+                id: "dictionary_overflows".into(),
+                name: "Dictionary Overflows".into(),
                 metric_type: MetricType::Counter,
-                unit: "events".into(),
-                description: "Times the dictionary reached maximum capacity".into(),
+                unit: "values".into(),
+                description: "Values stored inline behind an escape code because the dictionary was full".into(),
                 aggregations: vec![AggregationType::Sum],
             },
         ]
@@ -300,22 +395,69 @@ fn build_metrics() -> Vec<MetricDefinition> {
     pub fn encode(&mut self, value: u64) -> Option<u32> {
         if let Some(&code) = self.dictionary.get(&value) {
             Some(code)
-        } else if self.dictionary.len() < self.max_dictionary_size {
+        } else if self.dictionary.len() < self.max_dictionary_entries {
             let code = self.next_code;
             self.dictionary.insert(value, code);
+            self.values.push(Some(value));
             self.next_code += 1;
             Some(code)
         } else {
-            self.dictionary_full_events += 1;
+            self.dictionary_overflows += 1;
             None // Dictionary full — can't encode.
         }
     }
 
+    /// Decode a code back to its value, if the dictionary knows it.
+    pub fn decode(&self, code: u32) -> Option<u64> {
+        self.values.get(code as usize).copied().flatten()
+    }
+
+    /// Adopt an entry sent on an encoder's `dictionary_entries` port.
+    fn load_entry(&mut self, entry: &Record) -> Result<(), BlockError> {
+        let code = entry.get::<u32>("_dict_code").ok().flatten();
+        let value = entry.get::<u64>("_key").ok().flatten();
+        let (Some(code), Some(value)) = (code, value) else {
+            return Err(BlockError::InvalidInput(
+                "dictionary entries need `_dict_code` and `_key`".into(),
+            ));
+        };
+        let slot = code as usize;
+        if slot >= self.values.len() {
+            self.values.resize(slot + 1, None);
+        }
+        self.values[slot] = Some(value);
+        self.dictionary.insert(value, code);
+        self.next_code = self.next_code.max(code + 1);
+        Ok(())
+    }
+
+    /// Original bytes over encoded bytes, where the encoded side also pays
+    /// for storing the dictionary.
     pub fn compression_ratio(&self) -> f64 {
-        if self.compressed_size_bytes == 0 {
+        let dictionary_bytes = self.dictionary.len() * (RAW_VALUE_BYTES + CODE_BYTES);
+        let encoded = self.compressed_size_bytes + dictionary_bytes;
+        if encoded == 0 {
             return 1.0;
         }
-        self.original_size_bytes as f64 / self.compressed_size_bytes as f64
+        self.original_size_bytes as f64 / encoded as f64
+    }
+
+    /// Replace `_dict_code` with the original `_key`.
+    fn decode_record(&self, mut record: Record) -> Result<Record, BlockError> {
+        let code = record.get::<u32>("_dict_code").ok().flatten();
+        let Some(code) = code.filter(|&c| c != ESCAPE_CODE) else {
+            // Stored inline (escape code or never encoded) — `_key` is still there.
+            record.data.remove("_dict_code");
+            record.data.remove("_dict_encoded");
+            return Ok(record);
+        };
+        let value = self.decode(code).ok_or_else(|| {
+            BlockError::ExecutionError(format!("unknown dictionary code {}", code))
+        })?;
+        record.data.remove("_dict_code");
+        record.data.remove("_dict_encoded");
+        let _ = record.insert("_key".into(), value);
+        Ok(record)
     }
 }
 
@@ -341,13 +483,21 @@ fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
 
     async fn initialize(
         &mut self,
-        params: HashMap<String, ParameterValue>,
+        mut params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("max_dictionary_size") {
-            self.max_dictionary_size = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("max_dictionary_size must be an integer".into()))?
-                as usize;
+        // `max_dictionary_size` is the parameter's former name.
+        if let Some(val) = params.remove("max_dictionary_size") {
+            params.entry("max_dictionary_entries".into()).or_insert(val);
+        }
+        self.params_validate(&params)?;
+        if let Some(n) = params.get("max_dictionary_entries").and_then(ParameterValue::as_integer) {
+            self.max_dictionary_entries = n as usize;
+        }
+        if let Some(s) = enum_param(&params, "mode", MODES)? {
+            self.mode = match s {
+                "encode" => DictionaryMode::Encode,
+                _ => DictionaryMode::Decode,
+            };
         }
         Ok(())
     }
@@ -358,7 +508,7 @@ async fn execute(
     ) -> Result<ExecutionResult, BlockError> {
         let input = context.inputs.get("records").cloned().unwrap_or(PortValue::None);
 
-        let records = match input {
+        let mut records = match input {
             PortValue::Stream(r) => r,
             PortValue::Batch(r) => r,
             PortValue::Single(r) => vec![r],
@@ -367,24 +517,46 @@ async fn execute(
         };
 
         let mut output_records = Vec::with_capacity(records.len());
+        let mut decoded_records = Vec::new();
+        let mut errors = Vec::new();
+
+        if let Some(PortValue::Stream(entries) | PortValue::Batch(entries)) = context.inputs.get("dictionary") {
+            for entry in entries {
+                if let Err(e) = self.load_entry(entry) {
+                    errors.push(e);
+                }
+            }
+        }
+        let first_new_code = self.values.len();
+
+        if self.mode == DictionaryMode::Decode {
+            for record in std::mem::take(&mut records) {
+                match self.decode_record(record) {
+                    Ok(r) => decoded_records.push(r),
+                    Err(e) => errors.push(e),
+                }
+            }
+        }
 
         for record in records {
             self.entries_encoded += 1;
             let key = record.get::<u64>("_key").ok().flatten().unwrap_or(0);
 
             // Original: 8 bytes per key (u64).
-            self.original_size_bytes += 8;
+            self.original_size_bytes += RAW_VALUE_BYTES;
 
             let mut out = record;
             if let Some(code) = self.encode(key) {
                 // Compressed: 4 bytes per code (u32) — when dictionary is small,
                 // could be even less with varint.
-                self.compressed_size_bytes += 4;
+                self.compressed_size_bytes += CODE_BYTES;
+                out.data.remove("_key");
                 let _ = out.insert("_dict_code".into(), code as usize);
                 let _ = out.insert("_dict_encoded".into(), true);
             } else {
-                // Dictionary full — pass through uncompressed.
-                self.compressed_size_bytes += 8;
+                // Dictionary full — escape code followed by the raw value.
+                self.compressed_size_bytes += CODE_BYTES + RAW_VALUE_BYTES;
+                let _ = out.insert("_dict_code".into(), ESCAPE_CODE);
                 let _ = out.insert("_dict_encoded".into(), false);
             }
 
@@ -394,27 +566,56 @@ async fn execute(
 
         context.metrics.record("dictionary_size", self.dictionary.len() as f64);
         context.metrics.record("compression_ratio", self.compression_ratio());
-        context.metrics.record("dictionary_full_events", self.dictionary_full_events as f64);
+        context.metrics.record("dictionary_overflows", self.dictionary_overflows as f64);
+
+        let added = self.values[first_new_code..]
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| {
+                let mut entry = Record::new();
+                entry.insert("_dict_code".into(), first_new_code + i).ok()?;
+                entry.insert("_key".into(), (*value)?).ok()?;
+                Some(entry)
+            })
+            .collect();
 
         let mut outputs = HashMap::new();
         outputs.insert("compressed".into(), PortValue::Stream(output_records));
+        outputs.insert("decoded".into(), PortValue::Stream(decoded_records));
+        outputs.insert("dictionary_entries".into(), PortValue::Stream(added));
 
         let mut metrics_summary = HashMap::new();
         metrics_summary.insert("entries_encoded".into(), self.entries_encoded as f64);
         metrics_summary.insert("dictionary_size".into(), self.dictionary.len() as f64);
         metrics_summary.insert("compression_ratio".into(), self.compression_ratio());
-        metrics_summary.insert("dictionary_full_events".into(), self.dictionary_full_events as f64);
+        metrics_summary.insert("dictionary_overflows".into(), self.dictionary_overflows as f64);
 
         Ok(ExecutionResult {
             outputs,
             metrics: metrics_summary,
-            errors: vec![],
+            errors,
         })
     }
 
     fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
         if let Some(input) = inputs.get("records") {
             match input {
+                PortValue::Stream(records) | PortValue::Batch(records) if self.mode == DictionaryMode::Encode => {
+                    let distinct: std::collections::HashSet<u64> = records
+                        .iter()
+                        .filter_map(|r| r.get::<u64>("_key").ok().flatten())
+                        .collect();
+                    let ratio = distinct.len() as f64 / records.len().max(1) as f64;
+                    if records.len() >= 10 && ratio >= POOR_FIT_DISTINCT_RATIO {
+                        ValidationResult::ok().with_warning(format!(
+                            "{} of {} keys are distinct: dictionary encoding is a poor fit and will not compress",
+                            distinct.len(),
+                            records.len()
+                        ))
+                    } else {
+                        ValidationResult::ok()
+                    }
+                }
                 PortValue::Stream(_) | PortValue::Batch(_) | PortValue::Single(_) => ValidationResult::ok(),
                 PortValue::None => ValidationResult::ok().with_warning("No records to compress"),
                 _ => ValidationResult::error("records port expects DataStream"),
@@ -426,14 +627,14 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
 
     fn get_state(&self) -> BlockState {
         let mut state = BlockState::new();
-        let _ = state.insert("max_dictionary_size".into(), self.max_dictionary_size);
+        let _ = state.insert("max_dictionary_entries".into(), self.max_dictionary_entries);
         let _ = state.insert("dictionary_size".into(), self.dictionary.len());
         let _ = state.insert("entries_encoded".into(), self.entries_encoded);
         state
     }
 
     fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
-        if let Ok(Some(m)) = state.get::<usize>("max_dictionary_size") { self.max_dictionary_size = m; }
+        if let Ok(Some(m)) = state.get::<usize>("max_dictionary_entries") { self.max_dictionary_entries = m; }
         Ok(())
     }
 }
@@ -459,13 +660,54 @@ fn test_encoding_basic() {
     #[test]
     fn test_dictionary_full() {
         let mut enc = DictionaryEncodingBlock::new();
-        enc.max_dictionary_size = 3;
+        enc.max_dictionary_entries = 3;
 
         assert!(enc.encode(1).is_some());
         assert!(enc.encode(2).is_some());
         assert!(enc.encode(3).is_some());
         assert!(enc.encode(4).is_none()); // Dictionary full
-        assert_eq!(enc.dictionary_full_events, 1);
+        assert_eq!(enc.dictionary_overflows, 1);
+    }
+
+    #[tokio::test]
+    async fn test_overflow_escapes_values_inline() {
+        let mut enc = DictionaryEncodingBlock::new();
+        let mut params = HashMap::new();
+        params.insert("max_dictionary_entries".into(), ParameterValue::Integer(16));
+        enc.initialize(params).await.unwrap();
+
+        let original = keyed(0..40);
+        let result = run(&mut enc, original.clone()).await;
+        // The dictionary stops at 16 entries; the other 24 values are escaped.
+        assert_eq!(enc.dictionary.len(), 16);
+        assert_eq!(result.metrics["dictionary_overflows"], 24.0);
+        let Some(PortValue::Stream(compressed)) = result.outputs.get("compressed") else {
+            panic!("expected stream");
+        };
+        let escaped: Vec<&Record> = compressed
+            .iter()
+            .filter(|r| r.get::<u32>("_dict_code").unwrap() == Some(ESCAPE_CODE))
+            .collect();
+        assert_eq!(escaped.len(), 24);
+        assert!(escaped.iter().all(|r| r.data.contains_key("_key")));
+
+        // Escaped values still round-trip.
+        enc.mode = DictionaryMode::Decode;
+        let result = run(&mut enc, compressed.clone()).await;
+        let Some(PortValue::Stream(decoded)) = result.outputs.get("decoded") else {
+            panic!("expected stream");
+        };
+        let keys: Vec<u64> = decoded.iter().map(|r| r.get("_key").unwrap().unwrap()).collect();
+        assert_eq!(keys, (0..40).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_parameter_name() {
+        let mut enc = DictionaryEncodingBlock::new();
+        let mut params = HashMap::new();
+        params.insert("max_dictionary_size".into(), ParameterValue::Integer(32));
+        enc.initialize(params).await.unwrap();
+        assert_eq!(enc.max_dictionary_entries, 32);
     }
 
     #[test]
@@ -476,6 +718,79 @@ fn test_compression_ratio() {
         assert!((enc.compression_ratio() - 2.0).abs() < 0.01);
     }
 
+    fn keyed(keys: impl Iterator<Item = u64>) -> Vec<Record> {
+        keys.map(|k| {
+            let mut r = Record::new();
+            r.insert("_key".into(), k).unwrap();
+            r.insert("payload".into(), "x").unwrap();
+            r
+        })
+        .collect()
+    }
+
+    async fn run(enc: &mut DictionaryEncodingBlock, records: Vec<Record>) -> ExecutionResult {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        enc.execute(ctx).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_encode_decode_round_trip() {
+        let mut enc = DictionaryEncodingBlock::new();
+        let original = keyed((0..100).map(|i| i % 5));
+        let result = run(&mut enc, original.clone()).await;
+        let Some(PortValue::Stream(compressed)) = result.outputs.get("compressed") else {
+            panic!("expected stream");
+        };
+        assert!(compressed.iter().all(|r| !r.data.contains_key("_key")));
+        assert_eq!(*result.metrics.get("dictionary_size").unwrap(), 5.0);
+        // 800 raw bytes vs 400 code bytes + 5 * 12 dictionary bytes.
+        assert!((result.metrics["compression_ratio"] - 800.0 / 460.0).abs() < 1e-9);
+
+        enc.mode = DictionaryMode::Decode;
+        let result = run(&mut enc, compressed.clone()).await;
+        assert!(result.errors.is_empty());
+        let Some(PortValue::Stream(decoded)) = result.outputs.get("decoded") else {
+            panic!("expected stream");
+        };
+        let data = |rs: &[Record]| rs.iter().map(|r| r.data.clone()).collect::<Vec<_>>();
+        assert_eq!(data(decoded), data(&original));
+    }
+
+    #[tokio::test]
+    async fn test_decode_unknown_code_is_an_error() {
+        let mut enc = DictionaryEncodingBlock::new();
+        enc.mode = DictionaryMode::Decode;
+        let mut r = Record::new();
+        r.insert("_dict_code".into(), 7).unwrap();
+        let result = run(&mut enc, vec![r]).await;
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_high_cardinality_is_a_poor_fit() {
+        let mut enc = DictionaryEncodingBlock::new();
+        let unique = keyed(0..100);
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(unique.clone()));
+        assert_eq!(enc.validate(&inputs).warnings.len(), 1);
+
+        let result = run(&mut enc, unique).await;
+        assert!(result.metrics["compression_ratio"] <= 1.0);
+
+        let mut low = HashMap::new();
+        low.insert("records".into(), PortValue::Stream(keyed((0..100).map(|i| i % 3))));
+        assert!(enc.validate(&low).warnings.is_empty());
+    }
+
     #[test]
     fn test_metadata() {
         let enc = DictionaryEncodingBlock::new();
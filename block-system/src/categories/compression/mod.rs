@@ -2,6 +2,14 @@
 //!
 //! Blocks that compress data to reduce storage and I/O costs.
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+
 pub mod dictionary_encoding;
 
 pub use dictionary_encoding::DictionaryEncodingBlock;
+
+/// Register a factory for each compression block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(DictionaryEncodingBlock::new()))?;
+    Ok(())
+}
@@ -2,8 +2,17 @@
 //!
 //! Concurrency blocks manage how multiple transactions access shared data safely.
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+
 pub mod row_lock;
 pub mod mvcc;
 
 pub use row_lock::RowLockBlock;
 pub use mvcc::MVCCBlock;
+
+/// Register a factory for each concurrency block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(RowLockBlock::new()))?;
+    registry.register_factory(|| Box::new(MVCCBlock::new()))?;
+    Ok(())
+}
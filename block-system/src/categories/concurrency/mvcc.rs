@@ -12,8 +12,28 @@
 //! - `xmin <= txn_timestamp` (version was created before the snapshot)
 //! - `xmax` is None or `xmax > txn_timestamp` (version was not yet deleted)
 //!
+//! A delete does not add a version: it sets `xmax` on the latest visible one,
+//! so snapshots taken after the delete see the key disappear while older
+//! snapshots still see the value.
+//!
 //! Garbage collection removes versions that are no longer visible to any
-//! active transaction.
+//! active transaction. The `long_readers` parameter keeps that many snapshot
+//! readers open across batches (each batch opens one and closes the oldest),
+//! so GC must preserve every version the oldest open reader can still see.
+//!
+//! With `isolation_level = "serializable"` the block adds **Serializable
+//! Snapshot Isolation** (SSI): it records each transaction's reads and writes,
+//! tracks rw-antidependencies between concurrent transactions (T1 read a key
+//! that T2 wrote), and aborts a committing transaction that has both an
+//! incoming and an outgoing rw-antidependency — the "dangerous structure"
+//! behind write skew. Write skew needs transactions that overlap, so SSI only
+//! comes into play for `_txn` client transactions (see below) or the direct
+//! `begin_txn`/`read`/`write`/`commit` API.
+//!
+//! Records on the `snapshot` port are time-travel reads: each key is read as of
+//! its `_snapshot_ts` (or the `as_of` parameter). If GC has already reclaimed
+//! versions that could have been visible at that timestamp, the result is
+//! flagged `_snapshot_unavailable` instead of returning a newer version.
 //!
 //! ## Metrics tracked
 //!
@@ -21,25 +41,40 @@
 //! |--------|------|-------------|
 //! | `versions_created` | Counter | New versions written |
 //! | `versions_visible` | Gauge | Versions visible to latest snapshot |
-//! | `versions_garbage` | Gauge | Versions eligible for GC |
+//! | `versions_garbage` | Gauge | Dead versions (deleted or superseded) not yet reclaimed |
 //! | `gc_runs` | Counter | Garbage collection cycles |
 //! | `gc_reclaimed` | Counter | Versions reclaimed by GC |
 //! | `snapshot_reads` | Counter | Reads served from snapshot |
+//! | `snapshots_unavailable` | Counter | Time-travel reads older than the GC horizon |
 //! | `write_conflicts` | Counter | Write-write conflicts detected |
+//! | `ssi_aborts` | Counter | Commits aborted by SSI to prevent write skew |
+//! | `deletes` | Counter | Keys deleted (latest version's `xmax` set) |
 //! | `chain_length_avg` | Gauge | Average version chain length |
+//! | `max_chain_length` | Gauge | Longest version chain (the hottest key) |
+//! | `chain_length` | Histogram | Chain length of each written key, after the write (p50/p95/p99) |
+//!
+//! ## Input records
+//!
+//! Records without a `_txn` field each run as their own transaction that
+//! writes (or, with `_op: "delete"`, deletes) the record's `id` and commits at
+//! once. Records with `_txn` belong to a client transaction that stays open
+//! across records: `_op` is `read`, `write` (the default), `delete`, `commit`
+//! or `abort`. A transaction's writes are emitted on `visible` when it
+//! commits; if it loses a write-write conflict or SSI aborts its commit they
+//! are dropped.
 
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
     Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
 };
 use crate::core::constraint::{Constraint, Guarantee, GuaranteeType};
-use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
+use crate::core::metrics::{AggregationType, MetricDefinition, MetricType, MetricsCollector};
 use crate::core::parameter::{
-    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
 use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
@@ -103,15 +138,9 @@ fn visible_at(&self, ts: Timestamp) -> Option<&Version> {
         None
     }
 
-    /// Count versions visible to no active transaction (all below min_active).
-    fn garbage_versions(&self, min_active: Timestamp) -> usize {
-        self.versions
-            .iter()
-            .filter(|v| {
-                // A version is garbage if it was deleted before any active txn
-                v.xmax.map_or(false, |xmax| xmax < min_active)
-            })
-            .count()
+    /// Count dead versions: deleted or superseded, waiting for GC.
+    fn dead_versions(&self) -> usize {
+        self.versions.iter().filter(|v| v.xmax.is_some()).count()
     }
 
     /// Remove garbage versions.
@@ -123,6 +152,28 @@ fn gc(&mut self, min_active: Timestamp) -> usize {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum IsolationLevel {
+    /// Plain snapshot isolation: write-write conflicts only.
+    Snapshot,
+    /// Snapshot isolation plus SSI rw-antidependency tracking.
+    Serializable,
+}
+
+/// Values accepted by the `isolation_level` parameter.
+const ISOLATION_LEVELS: &[&str] = &["snapshot", "serializable"];
+
+/// Outcome of a time-travel read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotRead {
+    /// The version visible at the requested timestamp.
+    Visible(JsonValue),
+    /// The key did not exist (or was deleted) at the requested timestamp.
+    Absent,
+    /// GC reclaimed versions that may have been visible at that timestamp.
+    Unavailable,
+}
+
 // ---------------------------------------------------------------------------
 // MVCCBlock
 // ---------------------------------------------------------------------------
@@ -136,6 +187,11 @@ pub struct MVCCBlock {
 
     // Configuration
     gc_threshold: usize,
+    /// Default timestamp for snapshot reads (0 = latest).
+    as_of: Timestamp,
+    isolation: IsolationLevel,
+    /// Snapshot readers kept open across batches.
+    long_readers: usize,
 
     // Internal state
     /// Key → version chain.
@@ -146,6 +202,22 @@ pub struct MVCCBlock {
     active_txns: HashMap<Timestamp, Timestamp>,
     /// Committed transactions: txn_ts → commit_ts.
     commit_times: HashMap<Timestamp, Timestamp>,
+    /// Key → largest `xmax` among its reclaimed versions. Snapshots older than
+    /// this may have seen a version that no longer exists.
+    gc_horizon: HashMap<String, Timestamp>,
+    /// SSI bookkeeping (serializable only): keys read and written per txn.
+    txn_reads: HashMap<Timestamp, HashSet<String>>,
+    txn_writes: HashMap<Timestamp, HashSet<String>>,
+    /// rw-antidependencies as (reader, writer) pairs.
+    rw_edges: HashSet<(Timestamp, Timestamp)>,
+    /// Transactions rolled back by `abort`.
+    aborted: HashSet<Timestamp>,
+    /// Open long-running readers, oldest first.
+    open_readers: VecDeque<Timestamp>,
+    /// Client transactions driven by `_txn` records: label → txn timestamp,
+    /// and the writes each has issued so far.
+    client_txns: HashMap<String, Timestamp>,
+    txn_records: HashMap<Timestamp, Vec<Record>>,
 
     // Counters
     versions_created: usize,
@@ -153,6 +225,9 @@ pub struct MVCCBlock {
     gc_reclaimed: usize,
     snapshot_reads: usize,
     write_conflicts: usize,
+    deletes: usize,
+    snapshots_unavailable: usize,
+    ssi_aborts: usize,
 }
 
 impl MVCCBlock {
@@ -164,15 +239,29 @@ pub fn new() -> Self {
             params: Self::build_parameters(),
             metric_defs: Self::build_metrics(),
             gc_threshold: 100,
+            as_of: 0,
+            isolation: IsolationLevel::Snapshot,
+            long_readers: 0,
             store: HashMap::new(),
             current_ts: 1,
             active_txns: HashMap::new(),
             commit_times: HashMap::new(),
+            gc_horizon: HashMap::new(),
+            txn_reads: HashMap::new(),
+            txn_writes: HashMap::new(),
+            rw_edges: HashSet::new(),
+            aborted: HashSet::new(),
+            open_readers: VecDeque::new(),
+            client_txns: HashMap::new(),
+            txn_records: HashMap::new(),
             versions_created: 0,
             gc_runs: 0,
             gc_reclaimed: 0,
             snapshot_reads: 0,
             write_conflicts: 0,
+            deletes: 0,
+            snapshots_unavailable: 0,
+            ssi_aborts: 0,
         }
     }
 
@@ -206,12 +295,23 @@ fn build_metadata() -> BlockMetadata {
                            3. Create new version: Version { data, xmin=txn_ts, xmax=None }\n  \
                            4. Insert at head of version chain\n  \
                            5. If versions_created % gc_threshold == 0: trigger GC\n\n\
+                           DELETE(txn_ts, key):\n  \
+                           1. If no version of key is visible at txn_ts -> return false\n  \
+                           2. Check for write-write conflict (same rule as WRITE)\n  \
+                           3. Set xmax = txn_ts on the latest version (no new version)\n\n\
                            READ(snapshot_ts, key):\n  \
                            Walk version chain for key:\n    \
                              For each version v:\n      \
                                If v.xmin <= snapshot_ts AND (v.xmax is None OR v.xmax > snapshot_ts):\n        \
                                  Return v.data  (this version is visible)\n    \
                              Return None (key does not exist at this snapshot)\n\n\
+                           COMMIT(txn) under serializable (SSI):\n  \
+                           in  = some live txn R read a key txn wrote, concurrently\n  \
+                           out = txn read a key some live concurrent txn W wrote\n  \
+                           If in AND out -> ABORT txn (dangerous structure)\n\n\
+                           READ_AS_OF(ts, key):\n  \
+                           If ts < largest xmax GC has reclaimed for key -> UNAVAILABLE\n  \
+                           Otherwise READ(ts, key)\n\n\
                            GARBAGE_COLLECTION():\n  \
                            min_active = minimum timestamp among all active transactions\n  \
                            For each key's version chain:\n    \
@@ -264,6 +364,29 @@ fn build_metadata() -> BlockMetadata {
                       × table size). Recommended: 100 for balanced workloads, lower for write-heavy, higher \
                       for read-heavy with infrequent updates."
                         .into()),
+                    ("isolation_level".into(),
+                     "snapshot (the default) gives snapshot isolation: readers see a consistent snapshot \
+                      and only write-write conflicts abort. It allows write skew — two transactions read \
+                      the same keys, write different ones, and together break an invariant neither broke \
+                      alone. serializable adds SSI, which tracks who read what and aborts a transaction \
+                      caught in the middle of two rw-antidependencies. This is PostgreSQL's SERIALIZABLE \
+                      level; it costs read/write set tracking and some false-positive aborts. Only \
+                      transactions that overlap can write-skew, so drive it with `_txn` records."
+                        .into()),
+                    ("long_readers".into(),
+                     "How many long-running snapshot readers to keep open. Each batch opens a new \
+                      reader and closes the oldest, so with N readers the oldest snapshot is N batches \
+                      old. GC cannot reclaim any version that reader might still see, so dead versions \
+                      pile up — the effect of a forgotten open transaction or a long report on \
+                      PostgreSQL's VACUUM. 0 (the default) means no reader holds GC back."
+                        .into()),
+                    ("as_of".into(),
+                     "The timestamp used for records on the snapshot port that do not carry their own \
+                      `_snapshot_ts`. 0 (the default) reads the latest committed state. Any other value \
+                      reads the database as it was at that logical timestamp — a time-travel query. \
+                      Reads older than what GC has kept are reported as unavailable, so a lower \
+                      gc_threshold shortens how far back you can travel."
+                        .into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -297,50 +420,110 @@ fn build_metadata() -> BlockMetadata {
     }
 
     fn build_inputs() -> Vec<Port> {
-        vec![Port {
-            id: "records".into(),
-            name: "Records".into(),
-            port_type: PortType::DataStream,
-            direction: PortDirection::Input,
-            required: true,
-            multiple: false,
-            description: "Records to process with MVCC versioning".into(),
-            schema: None,
-        }]
+        vec![
+            Port {
+                id: "records".into(),
+                name: "Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Input,
+                required: true,
+                multiple: false,
+                description: "Records to process with MVCC versioning".into(),
+                schema: None,
+            },
+            Port {
+                id: "snapshot".into(),
+                name: "Snapshot Reads".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Input,
+                required: false,
+                multiple: false,
+                description: "Keys (`id`) to read as of `_snapshot_ts` or the as_of parameter"
+                    .into(),
+                schema: None,
+            },
+        ]
     }
 
     fn build_outputs() -> Vec<Port> {
-        vec![Port {
-            id: "visible".into(),
-            name: "Visible Records".into(),
-            port_type: PortType::DataStream,
-            direction: PortDirection::Output,
-            required: false,
-            multiple: true,
-            description: "Records visible at the latest snapshot".into(),
-            schema: None,
-        }]
+        vec![
+            Port {
+                id: "visible".into(),
+                name: "Visible Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Records visible at the latest snapshot".into(),
+                schema: None,
+            },
+            Port {
+                id: "snapshot_results".into(),
+                name: "Snapshot Results".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Versions visible at each requested snapshot timestamp, or records \
+                              flagged `_snapshot_unavailable`"
+                    .into(),
+                schema: None,
+            },
+        ]
     }
 
     fn build_parameters() -> Vec<Parameter> {
-        vec![Parameter {
-            id: "gc_threshold".into(),
-            name: "GC Threshold".into(),
-            param_type: ParameterType::Number,
-            description: "Run garbage collection every N writes".into(),
-            default_value: ParameterValue::Integer(100),
-            required: false,
-            constraints: Some(
-                ParameterConstraints::new()
-                    .with_min(10.0)
-                    .with_max(10000.0),
-            ),
-            ui_hint: Some(
-                ParameterUIHint::new(WidgetType::Slider)
-                    .with_step(10.0)
-                    .with_help_text("Lower = less space overhead, higher = less GC cost".into()),
-            ),
-        }]
+        vec![
+            Parameter {
+                id: "gc_threshold".into(),
+                name: "GC Threshold".into(),
+                param_type: ParameterType::Number,
+                description: "Run garbage collection every N writes".into(),
+                default_value: ParameterValue::Integer(100),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new()
+                        .with_min(10.0)
+                        .with_max(10000.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(10.0)
+                        .with_help_text("Lower = less space overhead, higher = less GC cost".into()),
+                ),
+            },
+            Parameter {
+                id: "isolation_level".into(),
+                name: "Isolation Level".into(),
+                param_type: ParameterType::Enum,
+                description: "snapshot or serializable (SSI write-skew detection)".into(),
+                default_value: ParameterValue::String("snapshot".into()),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_choices(ISOLATION_LEVELS)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+            },
+            Parameter {
+                id: "long_readers".into(),
+                name: "Long-Running Readers".into(),
+                param_type: ParameterType::Number,
+                description: "Snapshot readers kept open across batches, holding back GC".into(),
+                default_value: ParameterValue::Integer(0),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_min(0.0).with_max(16.0)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Slider).with_step(1.0)),
+            },
+            Parameter {
+                id: "as_of".into(),
+                name: "As Of Timestamp".into(),
+                param_type: ParameterType::Number,
+                description: "Timestamp for snapshot reads without `_snapshot_ts` (0 = latest)"
+                    .into(),
+                default_value: ParameterValue::Integer(0),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_min(0.0)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+        ]
     }
 
     fn build_metrics() -> Vec<MetricDefinition> {
@@ -366,7 +549,7 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 name: "Garbage Versions".into(),
                 metric_type: MetricType::Gauge,
                 unit: "versions".into(),
-                description: "Versions eligible for GC".into(),
+                description: "Dead versions (deleted or superseded) not yet reclaimed".into(),
                 aggregations: vec![AggregationType::Max],
             },
             MetricDefinition {
@@ -393,6 +576,14 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Reads served from snapshot".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "snapshots_unavailable".into(),
+                name: "Snapshots Unavailable".into(),
+                metric_type: MetricType::Counter,
+                unit: "reads".into(),
+                description: "Time-travel reads older than the GC horizon".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
             MetricDefinition {
                 id: "write_conflicts".into(),
                 name: "Write Conflicts".into(),
@@ -401,6 +592,22 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Write-write conflicts detected".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "ssi_aborts".into(),
+                name: "SSI Aborts".into(),
+                metric_type: MetricType::Counter,
+                unit: "txns".into(),
+                description: "Commits aborted by SSI to prevent write skew".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "deletes".into(),
+                name: "Deletes".into(),
+                metric_type: MetricType::Counter,
+                unit: "keys".into(),
+                description: "Keys deleted by setting xmax on their latest version".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
             MetricDefinition {
                 id: "chain_length_avg".into(),
                 name: "Avg Chain Length".into(),
@@ -409,6 +616,26 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Average version chain length".into(),
                 aggregations: vec![AggregationType::Avg],
             },
+            MetricDefinition {
+                id: "max_chain_length".into(),
+                name: "Max Chain Length".into(),
+                metric_type: MetricType::Gauge,
+                unit: "versions".into(),
+                description: "Longest version chain — a hot key lagging behind GC".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "chain_length".into(),
+                name: "Chain Length".into(),
+                metric_type: MetricType::Histogram,
+                unit: "versions".into(),
+                description: "Chain length of each written key — versions a reader may walk".into(),
+                aggregations: vec![
+                    AggregationType::P50,
+                    AggregationType::P95,
+                    AggregationType::P99,
+                ],
+            },
         ]
     }
 
@@ -422,37 +649,44 @@ pub fn begin_txn(&mut self) -> Timestamp {
         ts
     }
 
+    /// Check for a write-write conflict on `key`: another transaction modified
+    /// its latest version (wrote it, or deleted it) and either has not
+    /// committed or committed after our snapshot was taken.
+    fn has_write_conflict(&self, txn_ts: Timestamp, key: &str) -> bool {
+        let Some(latest) = self.store.get(key).and_then(|c| c.versions.first()) else {
+            return false;
+        };
+        let modifier = latest.xmax.unwrap_or(latest.xmin);
+        if modifier == txn_ts {
+            return false;
+        }
+        match self.commit_times.get(&modifier) {
+            // Modifier committed after our snapshot → conflict
+            Some(&commit_ts) => commit_ts >= txn_ts,
+            // Modifier hasn't committed yet → concurrent → conflict
+            None => true,
+        }
+    }
+
     /// Write a new version of a key.
     pub fn write(&mut self, txn_ts: Timestamp, key: &str, data: JsonValue) -> bool {
+        if self.has_write_conflict(txn_ts, key) {
+            self.write_conflicts += 1;
+            return false;
+        }
+
         let chain = self
             .store
             .entry(key.to_string())
             .or_insert_with(VersionChain::new);
 
-        // Check for write-write conflict: if another transaction wrote this key
-        // and committed after our snapshot was taken, it's a conflict.
-        if let Some(latest) = chain.versions.first() {
-            if latest.xmin != txn_ts {
-                if let Some(&commit_ts) = self.commit_times.get(&latest.xmin) {
-                    // Writer committed after our snapshot → conflict
-                    if commit_ts >= txn_ts {
-                        self.write_conflicts += 1;
-                        return false;
-                    }
-                } else {
-                    // Writer hasn't committed yet → concurrent → conflict
-                    self.write_conflicts += 1;
-                    return false;
-                }
-            }
-        }
-
         // Mark old version as deleted (if exists).
         chain.delete_latest(txn_ts);
 
         // Create new version.
         chain.add_version(data, txn_ts);
         self.versions_created += 1;
+        self.track_write(txn_ts, key);
 
         // Maybe trigger GC.
         if self.versions_created % self.gc_threshold == 0 {
@@ -462,21 +696,169 @@ pub fn write(&mut self, txn_ts: Timestamp, key: &str, data: JsonValue) -> bool {
         true
     }
 
+    /// Delete a key by setting `xmax` on its latest visible version.
+    ///
+    /// Returns `false` if the key is not visible at `txn_ts` or a concurrent
+    /// transaction modified it (a write-write conflict).
+    pub fn delete(&mut self, txn_ts: Timestamp, key: &str) -> bool {
+        let visible = self
+            .store
+            .get(key)
+            .is_some_and(|chain| chain.visible_at(txn_ts).is_some());
+        if !visible {
+            return false;
+        }
+        if self.has_write_conflict(txn_ts, key) {
+            self.write_conflicts += 1;
+            return false;
+        }
+
+        let deleted = self
+            .store
+            .get_mut(key)
+            .is_some_and(|chain| chain.delete_latest(txn_ts));
+        if deleted {
+            self.deletes += 1;
+            self.track_write(txn_ts, key);
+        }
+        deleted
+    }
+
     /// Read the visible version of a key at a snapshot timestamp.
     pub fn read(&mut self, snapshot_ts: Timestamp, key: &str) -> Option<JsonValue> {
         self.snapshot_reads += 1;
+        self.track_read(snapshot_ts, key);
         self.store
             .get(key)
             .and_then(|chain| chain.visible_at(snapshot_ts))
             .map(|v| v.data.clone())
     }
 
-    /// Commit a transaction.
-    pub fn commit(&mut self, txn_ts: Timestamp) {
+    /// Read a key as of a past timestamp (a time-travel read).
+    ///
+    /// Unlike [`read`](Self::read), this reports [`SnapshotRead::Unavailable`]
+    /// when GC has reclaimed versions that could have been visible at `ts`,
+    /// rather than silently returning a newer version or `None`.
+    pub fn read_as_of(&mut self, ts: Timestamp, key: &str) -> SnapshotRead {
+        if self.gc_horizon.get(key).is_some_and(|&horizon| ts < horizon) {
+            self.snapshots_unavailable += 1;
+            return SnapshotRead::Unavailable;
+        }
+        match self.read(ts, key) {
+            Some(data) => SnapshotRead::Visible(data),
+            None => SnapshotRead::Absent,
+        }
+    }
+
+    /// Commit a transaction. Returns `false` if SSI aborted it instead.
+    pub fn commit(&mut self, txn_ts: Timestamp) -> bool {
+        if self.isolation == IsolationLevel::Serializable && self.is_dangerous(txn_ts) {
+            self.abort(txn_ts);
+            self.ssi_aborts += 1;
+            return false;
+        }
+
         self.active_txns.remove(&txn_ts);
         let commit_ts = self.current_ts;
         self.current_ts += 1;
         self.commit_times.insert(txn_ts, commit_ts);
+        self.prune_ssi_state();
+        true
+    }
+
+    /// Abort a transaction, rolling back every version it wrote or deleted.
+    pub fn abort(&mut self, txn_ts: Timestamp) {
+        for chain in self.store.values_mut() {
+            chain.versions.retain(|v| v.xmin != txn_ts);
+            for v in &mut chain.versions {
+                if v.xmax == Some(txn_ts) {
+                    v.xmax = None;
+                }
+            }
+        }
+        self.store.retain(|_, chain| !chain.versions.is_empty());
+        self.active_txns.remove(&txn_ts);
+        self.aborted.insert(txn_ts);
+        self.prune_ssi_state();
+    }
+
+    /// Whether two transactions overlapped: neither committed before the
+    /// other started, and neither was aborted.
+    fn concurrent(&self, a: Timestamp, b: Timestamp) -> bool {
+        let finished_before =
+            |x: Timestamp, y: Timestamp| self.commit_times.get(&x).is_some_and(|&c| c < y);
+        a != b
+            && !self.aborted.contains(&a)
+            && !self.aborted.contains(&b)
+            && !finished_before(a, b)
+            && !finished_before(b, a)
+    }
+
+    /// Record a read for SSI and add rw-antidependencies to concurrent writers.
+    fn track_read(&mut self, txn_ts: Timestamp, key: &str) {
+        if self.isolation != IsolationLevel::Serializable || !self.active_txns.contains_key(&txn_ts)
+        {
+            return;
+        }
+        self.txn_reads
+            .entry(txn_ts)
+            .or_default()
+            .insert(key.to_string());
+        let writers: Vec<Timestamp> = self
+            .txn_writes
+            .iter()
+            .filter(|(&w, keys)| keys.contains(key) && self.concurrent(txn_ts, w))
+            .map(|(&w, _)| w)
+            .collect();
+        for writer in writers {
+            self.rw_edges.insert((txn_ts, writer));
+        }
+    }
+
+    /// Record a write for SSI and add rw-antidependencies from concurrent readers.
+    fn track_write(&mut self, txn_ts: Timestamp, key: &str) {
+        if self.isolation != IsolationLevel::Serializable {
+            return;
+        }
+        self.txn_writes
+            .entry(txn_ts)
+            .or_default()
+            .insert(key.to_string());
+        let readers: Vec<Timestamp> = self
+            .txn_reads
+            .iter()
+            .filter(|(&r, keys)| keys.contains(key) && self.concurrent(r, txn_ts))
+            .map(|(&r, _)| r)
+            .collect();
+        for reader in readers {
+            self.rw_edges.insert((reader, txn_ts));
+        }
+    }
+
+    /// A transaction is the pivot of a dangerous structure if it has both an
+    /// incoming and an outgoing rw-antidependency with a transaction that was
+    /// not aborted.
+    fn is_dangerous(&self, txn_ts: Timestamp) -> bool {
+        let live = |t: &Timestamp| !self.aborted.contains(t);
+        let has_in = self
+            .rw_edges
+            .iter()
+            .any(|(r, w)| *w == txn_ts && live(r));
+        let has_out = self
+            .rw_edges
+            .iter()
+            .any(|(r, w)| *r == txn_ts && live(w));
+        has_in && has_out
+    }
+
+    /// Drop SSI bookkeeping once no transaction is open to conflict with it.
+    fn prune_ssi_state(&mut self) {
+        if self.active_txns.is_empty() {
+            self.txn_reads.clear();
+            self.txn_writes.clear();
+            self.rw_edges.clear();
+            self.aborted.clear();
+        }
     }
 
     /// Run garbage collection.
@@ -489,7 +871,17 @@ pub fn run_gc(&mut self) {
             .unwrap_or(self.current_ts);
 
         let mut reclaimed = 0;
-        for chain in self.store.values_mut() {
+        for (key, chain) in self.store.iter_mut() {
+            let horizon = chain
+                .versions
+                .iter()
+                .filter_map(|v| v.xmax)
+                .filter(|&xmax| xmax < min_active)
+                .max();
+            if let Some(horizon) = horizon {
+                let entry = self.gc_horizon.entry(key.clone()).or_insert(0);
+                *entry = (*entry).max(horizon);
+            }
             reclaimed += chain.gc(min_active);
         }
 
@@ -513,18 +905,27 @@ fn visible_count(&self, ts: Timestamp) -> usize {
             .count()
     }
 
-    /// Count garbage versions.
+    /// Count dead versions that GC has not reclaimed yet.
     fn garbage_count(&self) -> usize {
-        let min_active = self
-            .active_txns
-            .keys()
-            .copied()
-            .min()
-            .unwrap_or(self.current_ts);
-        self.store
-            .values()
-            .map(|c| c.garbage_versions(min_active))
-            .sum()
+        self.store.values().map(|c| c.dead_versions()).sum()
+    }
+
+    /// Open a new long-running snapshot reader and close the oldest ones so
+    /// that at most `long_readers` stay open.
+    fn rotate_long_readers(&mut self) {
+        if self.long_readers == 0 {
+            while let Some(reader) = self.open_readers.pop_front() {
+                self.commit(reader);
+            }
+            return;
+        }
+        let reader = self.begin_txn();
+        self.open_readers.push_back(reader);
+        while self.open_readers.len() > self.long_readers {
+            if let Some(oldest) = self.open_readers.pop_front() {
+                self.commit(oldest);
+            }
+        }
     }
 
     /// Average version chain length.
@@ -534,6 +935,91 @@ fn avg_chain_length(&self) -> f64 {
         }
         self.total_versions() as f64 / self.store.len() as f64
     }
+
+    /// Length of the longest version chain.
+    pub fn max_chain_length(&self) -> usize {
+        self.store
+            .values()
+            .map(|c| c.versions.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Number of keys per version chain length (length → key count).
+    pub fn chain_length_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for chain in self.store.values() {
+            *histogram.entry(chain.versions.len()).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// Apply one record of a `_txn` client transaction. Returns the records
+    /// to emit if this record committed the transaction.
+    fn apply_client_record(
+        &mut self,
+        label: String,
+        record: &Record,
+        metrics: &MetricsCollector,
+    ) -> Vec<Record> {
+        let op = record.get::<String>("_op").ok().flatten();
+        let txn = match self.client_txns.get(&label) {
+            Some(&ts) => ts,
+            // Ending a transaction that is not open (e.g. already aborted) is a no-op.
+            None if matches!(op.as_deref(), Some("commit" | "abort")) => return Vec::new(),
+            None => {
+                let ts = self.begin_txn();
+                self.client_txns.insert(label.clone(), ts);
+                ts
+            }
+        };
+        let key = record
+            .data
+            .get("id")
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| format!("key_{}", txn));
+
+        match op.as_deref() {
+            Some("commit") => {
+                self.client_txns.remove(&label);
+                let records = self.txn_records.remove(&txn).unwrap_or_default();
+                if self.commit(txn) {
+                    records
+                } else {
+                    Vec::new()
+                }
+            }
+            Some("abort") => {
+                self.abort(txn);
+                self.client_txns.remove(&label);
+                self.txn_records.remove(&txn);
+                Vec::new()
+            }
+            Some("read") => {
+                self.read(txn, &key);
+                Vec::new()
+            }
+            Some("delete") => {
+                self.delete(txn, &key);
+                Vec::new()
+            }
+            _ => {
+                let data = serde_json::to_value(&record.data).unwrap_or(JsonValue::Null);
+                if self.write(txn, &key, data) {
+                    if let Some(chain) = self.store.get(&key) {
+                        metrics.observe("chain_length", chain.versions.len() as f64);
+                    }
+                    self.txn_records.entry(txn).or_default().push(record.clone());
+                } else {
+                    // First updater wins: the loser of a write-write conflict aborts.
+                    self.abort(txn);
+                    self.client_txns.remove(&label);
+                    self.txn_records.remove(&txn);
+                }
+                Vec::new()
+            }
+        }
+    }
 }
 
 impl Default for MVCCBlock {
@@ -582,12 +1068,21 @@ async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("gc_threshold") {
-            self.gc_threshold = val
-                .as_integer()
-                .ok_or_else(|| {
-                    BlockError::InvalidParameter("gc_threshold must be an integer".into())
-                })? as usize;
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("gc_threshold").and_then(ParameterValue::as_integer) {
+            self.gc_threshold = v as usize;
+        }
+        if let Some(s) = enum_param(&params, "isolation_level", ISOLATION_LEVELS)? {
+            self.isolation = match s {
+                "snapshot" => IsolationLevel::Snapshot,
+                _ => IsolationLevel::Serializable,
+            };
+        }
+        if let Some(n) = params.get("long_readers").and_then(ParameterValue::as_integer) {
+            self.long_readers = n as usize;
+        }
+        if let Some(ts) = params.get("as_of").and_then(ParameterValue::as_integer) {
+            self.as_of = ts as Timestamp;
         }
         Ok(())
     }
@@ -614,19 +1109,76 @@ async fn execute(
             }
         };
 
-        // Simulate: each record is a write in its own transaction.
-        for record in &records {
+        self.rotate_long_readers();
+
+        // Records with `_txn` join an open client transaction. Otherwise each
+        // record is a write (or, with `_op: "delete"`, a delete) in its own
+        // transaction.
+        let mut visible_records = Vec::with_capacity(records.len());
+        for record in records {
+            if let Some(label) = record.data.get("_txn").map(|v| v.to_string()) {
+                visible_records.extend(self.apply_client_record(label, &record, &context.metrics));
+                continue;
+            }
+
             let txn = self.begin_txn();
             let key = record
                 .data
                 .get("id")
                 .map(|v| v.to_string())
                 .unwrap_or_else(|| format!("key_{}", txn));
-            let data = serde_json::to_value(&record.data).unwrap_or(JsonValue::Null);
-            self.write(txn, &key, data);
+            let op = record.get::<String>("_op").ok().flatten();
+
+            if op.as_deref() == Some("delete") {
+                // Counted in `self.deletes` only if the key was visible and
+                // unconflicted; recorded as a running total below.
+                self.delete(txn, &key);
+            } else {
+                let data = serde_json::to_value(&record.data).unwrap_or(JsonValue::Null);
+                self.write(txn, &key, data);
+                if let Some(chain) = self.store.get(&key) {
+                    context.metrics.observe("chain_length", chain.versions.len() as f64);
+                }
+                visible_records.push(record);
+            }
             self.commit(txn);
         }
 
+        // Time-travel reads from the snapshot port.
+        let snapshot_requests = match context.inputs.get("snapshot") {
+            Some(PortValue::Stream(r)) | Some(PortValue::Batch(r)) => r.clone(),
+            Some(PortValue::Single(r)) => vec![r.clone()],
+            _ => Vec::new(),
+        };
+        let mut snapshot_results = Vec::with_capacity(snapshot_requests.len());
+        for request in snapshot_requests {
+            let ts = request
+                .get::<Timestamp>("_snapshot_ts")
+                .ok()
+                .flatten()
+                .unwrap_or(self.as_of);
+            let ts = if ts == 0 { self.current_ts } else { ts };
+            let Some(id) = request.data.get("id") else {
+                continue;
+            };
+            match self.read_as_of(ts, &id.to_string()) {
+                SnapshotRead::Visible(data) => {
+                    let fields = serde_json::from_value(data).unwrap_or_default();
+                    let mut out = Record::from_map(fields);
+                    let _ = out.insert("_snapshot_ts".into(), ts);
+                    snapshot_results.push(out);
+                }
+                SnapshotRead::Absent => {}
+                SnapshotRead::Unavailable => {
+                    let mut out = Record::new();
+                    out.data.insert("id".into(), id.clone());
+                    let _ = out.insert("_snapshot_ts".into(), ts);
+                    let _ = out.insert("_snapshot_unavailable".into(), true);
+                    snapshot_results.push(out);
+                }
+            }
+        }
+
         // Read snapshot at current time.
         let snap_ts = self.current_ts;
         let visible = self.visible_count(snap_ts);
@@ -647,22 +1199,39 @@ async fn execute(
         context
             .metrics
             .record("snapshot_reads", self.snapshot_reads as f64);
+        context
+            .metrics
+            .record("snapshots_unavailable", self.snapshots_unavailable as f64);
         context
             .metrics
             .record("write_conflicts", self.write_conflicts as f64);
+        context.metrics.record("ssi_aborts", self.ssi_aborts as f64);
+        context.metrics.record("deletes", self.deletes as f64);
         context
             .metrics
             .record("chain_length_avg", self.avg_chain_length());
+        context
+            .metrics
+            .record("max_chain_length", self.max_chain_length() as f64);
 
         let mut outputs = HashMap::new();
-        outputs.insert("visible".into(), PortValue::Stream(records.to_vec()));
+        outputs.insert("visible".into(), PortValue::Stream(visible_records));
+        outputs.insert("snapshot_results".into(), PortValue::Stream(snapshot_results));
 
         let mut metrics_summary = HashMap::new();
         metrics_summary.insert("versions_created".into(), self.versions_created as f64);
         metrics_summary.insert("versions_visible".into(), visible as f64);
         metrics_summary.insert("gc_runs".into(), self.gc_runs as f64);
         metrics_summary.insert("gc_reclaimed".into(), self.gc_reclaimed as f64);
+        metrics_summary.insert("deletes".into(), self.deletes as f64);
+        metrics_summary.insert("ssi_aborts".into(), self.ssi_aborts as f64);
+        metrics_summary.insert("snapshot_reads".into(), self.snapshot_reads as f64);
+        metrics_summary.insert(
+            "snapshots_unavailable".into(),
+            self.snapshots_unavailable as f64,
+        );
         metrics_summary.insert("chain_length_avg".into(), self.avg_chain_length());
+        metrics_summary.insert("max_chain_length".into(), self.max_chain_length() as f64);
 
         Ok(ExecutionResult {
             outputs,
@@ -766,6 +1335,153 @@ fn test_write_conflict() {
         assert_eq!(mvcc.write_conflicts, 1);
     }
 
+    #[test]
+    fn test_delete_visibility() {
+        let mut mvcc = MVCCBlock::new();
+        mvcc.gc_threshold = 10000;
+
+        let txn1 = mvcc.begin_txn();
+        mvcc.write(txn1, "key1", json!("v1"));
+        mvcc.commit(txn1);
+
+        // Snapshot taken before the delete.
+        let old_reader = mvcc.begin_txn();
+
+        let txn2 = mvcc.begin_txn();
+        assert!(mvcc.delete(txn2, "key1"));
+        mvcc.commit(txn2);
+        assert_eq!(mvcc.deletes, 1);
+        assert_eq!(mvcc.total_versions(), 1, "delete must not add a version");
+
+        let new_reader = mvcc.begin_txn();
+        assert_eq!(mvcc.read(new_reader, "key1"), None);
+        assert_eq!(mvcc.read(old_reader, "key1"), Some(json!("v1")));
+
+        // Deleting again (or a key that never existed) is a no-op.
+        let txn3 = mvcc.begin_txn();
+        assert!(!mvcc.delete(txn3, "key1"));
+        assert!(!mvcc.delete(txn3, "missing"));
+        assert_eq!(mvcc.deletes, 1);
+
+        // The key can be re-inserted after the delete.
+        assert!(mvcc.write(txn3, "key1", json!("v2")));
+        mvcc.commit(txn3);
+        let reader = mvcc.begin_txn();
+        assert_eq!(mvcc.read(reader, "key1"), Some(json!("v2")));
+    }
+
+    #[test]
+    fn test_delete_conflicts_with_concurrent_write() {
+        let mut mvcc = MVCCBlock::new();
+        mvcc.gc_threshold = 10000;
+
+        let setup = mvcc.begin_txn();
+        mvcc.write(setup, "key1", json!(0));
+        mvcc.commit(setup);
+
+        let deleter = mvcc.begin_txn();
+        let writer = mvcc.begin_txn();
+        assert!(mvcc.write(writer, "key1", json!(1)));
+        mvcc.commit(writer);
+
+        assert!(!mvcc.delete(deleter, "key1"));
+        assert_eq!(mvcc.write_conflicts, 1);
+        assert_eq!(mvcc.deletes, 0);
+    }
+
+    #[test]
+    fn test_read_as_of() {
+        let mut mvcc = MVCCBlock::new();
+        mvcc.gc_threshold = 10000;
+
+        let mut write_ts = Vec::new();
+        for i in 0..3 {
+            let txn = mvcc.begin_txn();
+            mvcc.write(txn, "key1", json!(i));
+            mvcc.commit(txn);
+            write_ts.push(txn);
+        }
+
+        assert_eq!(mvcc.read_as_of(write_ts[0], "key1"), SnapshotRead::Visible(json!(0)));
+        assert_eq!(mvcc.read_as_of(write_ts[1], "key1"), SnapshotRead::Visible(json!(1)));
+        assert_eq!(mvcc.read_as_of(write_ts[0] - 1, "key1"), SnapshotRead::Absent);
+
+        // After GC the old versions are gone: old snapshots are unavailable,
+        // not silently answered with the latest version.
+        mvcc.run_gc();
+        assert_eq!(mvcc.read_as_of(write_ts[0], "key1"), SnapshotRead::Unavailable);
+        assert_eq!(mvcc.read_as_of(write_ts[1], "key1"), SnapshotRead::Unavailable);
+        assert_eq!(mvcc.read_as_of(write_ts[2], "key1"), SnapshotRead::Visible(json!(2)));
+        assert_eq!(mvcc.snapshots_unavailable, 2);
+    }
+
+    /// Two on-call doctors, invariant: at least one stays on call. Each
+    /// transaction checks both, sees two on call, and takes itself off.
+    fn run_write_skew(isolation: IsolationLevel) -> (MVCCBlock, bool, bool) {
+        let mut mvcc = MVCCBlock::new();
+        mvcc.gc_threshold = 10000;
+        mvcc.isolation = isolation;
+
+        let setup = mvcc.begin_txn();
+        mvcc.write(setup, "alice", json!(true));
+        mvcc.write(setup, "bob", json!(true));
+        assert!(mvcc.commit(setup));
+
+        let t1 = mvcc.begin_txn();
+        let t2 = mvcc.begin_txn();
+        for txn in [t1, t2] {
+            assert_eq!(mvcc.read(txn, "alice"), Some(json!(true)));
+            assert_eq!(mvcc.read(txn, "bob"), Some(json!(true)));
+        }
+        assert!(mvcc.write(t1, "alice", json!(false)));
+        assert!(mvcc.write(t2, "bob", json!(false)));
+
+        let c1 = mvcc.commit(t1);
+        let c2 = mvcc.commit(t2);
+        (mvcc, c1, c2)
+    }
+
+    #[test]
+    fn test_write_skew_allowed_under_snapshot() {
+        let (mut mvcc, c1, c2) = run_write_skew(IsolationLevel::Snapshot);
+        assert!(c1 && c2, "snapshot isolation lets both commit");
+        assert_eq!(mvcc.ssi_aborts, 0);
+
+        let reader = mvcc.begin_txn();
+        assert_eq!(mvcc.read(reader, "alice"), Some(json!(false)));
+        assert_eq!(mvcc.read(reader, "bob"), Some(json!(false)), "invariant broken");
+    }
+
+    #[test]
+    fn test_write_skew_aborted_under_serializable() {
+        let (mut mvcc, c1, c2) = run_write_skew(IsolationLevel::Serializable);
+        assert!(c1 ^ c2, "exactly one transaction must abort");
+        assert_eq!(mvcc.ssi_aborts, 1);
+
+        let reader = mvcc.begin_txn();
+        let on_call = ["alice", "bob"]
+            .iter()
+            .filter(|k| mvcc.read(reader, k) == Some(json!(true)))
+            .count();
+        assert_eq!(on_call, 1, "the aborted transaction's write is rolled back");
+    }
+
+    #[test]
+    fn test_serializable_allows_disjoint_transactions() {
+        let mut mvcc = MVCCBlock::new();
+        mvcc.isolation = IsolationLevel::Serializable;
+
+        let t1 = mvcc.begin_txn();
+        let t2 = mvcc.begin_txn();
+        mvcc.read(t1, "a");
+        mvcc.write(t1, "a", json!(1));
+        mvcc.read(t2, "b");
+        mvcc.write(t2, "b", json!(2));
+        assert!(mvcc.commit(t1));
+        assert!(mvcc.commit(t2));
+        assert_eq!(mvcc.ssi_aborts, 0);
+    }
+
     #[test]
     fn test_garbage_collection() {
         let mut mvcc = MVCCBlock::new();
@@ -790,6 +1506,60 @@ fn test_garbage_collection() {
         assert!(mvcc.gc_reclaimed > 0);
     }
 
+    #[tokio::test]
+    async fn test_long_reader_holds_back_gc() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        async fn run(long_readers: i64) -> (MVCCBlock, f64) {
+            let mut mvcc = MVCCBlock::new();
+            let mut params = HashMap::new();
+            params.insert("gc_threshold".into(), ParameterValue::Integer(10));
+            params.insert("long_readers".into(), ParameterValue::Integer(long_readers));
+            mvcc.initialize(params).await.unwrap();
+
+            // 20 updates over 5 keys: GC runs after the 10th and 20th write.
+            let records: Vec<Record> = (0..20)
+                .map(|i| {
+                    let mut r = Record::new();
+                    r.insert("id".into(), (i % 5) as i64).unwrap();
+                    r.insert("v".into(), i as i64).unwrap();
+                    r
+                })
+                .collect();
+            let mut inputs = HashMap::new();
+            inputs.insert("records".into(), PortValue::Stream(records));
+            let ctx = ExecutionContext {
+                inputs,
+                parameters: HashMap::new(),
+                metrics: MetricsCollector::new(),
+                logger: Logger::new(),
+                storage: StorageContext::new(),
+            };
+            mvcc.execute(ctx).await.unwrap();
+            let garbage = mvcc.garbage_count() as f64;
+            (mvcc, garbage)
+        }
+
+        // Without a reader, only the version superseded by the write that
+        // triggered the last GC (still in flight at the time) survives.
+        let (_, garbage_without) = run(0).await;
+        assert!(garbage_without <= 1.0);
+
+        let (mut mvcc, garbage_with) = run(1).await;
+        assert!(mvcc.gc_runs >= 2);
+        assert_eq!(garbage_with, 15.0, "GC must keep versions the open reader can see");
+        assert_eq!(mvcc.gc_reclaimed, 0);
+
+        // The open reader started before the batch, so it still sees nothing
+        // of it; once it closes, GC can reclaim the dead versions.
+        let reader = mvcc.open_readers[0];
+        assert_eq!(mvcc.read(reader, "0"), None);
+        mvcc.long_readers = 0;
+        mvcc.rotate_long_readers();
+        mvcc.run_gc();
+        assert_eq!(mvcc.garbage_count(), 0);
+    }
+
     #[test]
     fn test_version_chain_length() {
         let mut mvcc = MVCCBlock::new();
@@ -805,13 +1575,40 @@ fn test_version_chain_length() {
         assert!(mvcc.avg_chain_length() >= 1.0);
     }
 
+    #[test]
+    fn test_chain_length_histogram_skewed_updates() {
+        let mut mvcc = MVCCBlock::new();
+        mvcc.gc_threshold = 10000;
+
+        // One hot key updated 10 times, nine cold keys written once.
+        for i in 0..10 {
+            let txn = mvcc.begin_txn();
+            mvcc.write(txn, "hot", json!(i));
+            mvcc.commit(txn);
+        }
+        for i in 0..9 {
+            let txn = mvcc.begin_txn();
+            mvcc.write(txn, &format!("cold{}", i), json!(i));
+            mvcc.commit(txn);
+        }
+
+        assert_eq!(mvcc.max_chain_length(), 10);
+        assert!(mvcc.avg_chain_length() < 2.0, "the average hides the hot key");
+        let histogram = mvcc.chain_length_histogram();
+        assert_eq!(histogram, BTreeMap::from([(1, 9), (10, 1)]));
+
+        mvcc.run_gc();
+        assert_eq!(mvcc.max_chain_length(), 1);
+        assert_eq!(mvcc.chain_length_histogram(), BTreeMap::from([(1, 10)]));
+    }
+
     #[test]
     fn test_metadata() {
         let mvcc = MVCCBlock::new();
         assert_eq!(mvcc.metadata().id, "mvcc");
         assert_eq!(mvcc.metadata().category, BlockCategory::Concurrency);
-        assert_eq!(mvcc.inputs().len(), 1);
-        assert_eq!(mvcc.outputs().len(), 1);
+        assert_eq!(mvcc.inputs().len(), 2);
+        assert_eq!(mvcc.outputs().len(), 2);
     }
 
     #[tokio::test]
@@ -843,4 +1640,190 @@ async fn test_block_execute() {
         assert_eq!(*result.metrics.get("versions_created").unwrap(), 20.0);
         assert!(result.errors.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_execute_observes_chain_length() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut mvcc = MVCCBlock::new();
+        // 40 keys written once and one hot key written 10 times — fewer
+        // writes than the GC threshold, so no version is reclaimed.
+        let records: Vec<Record> = (0..40)
+            .chain(std::iter::repeat_n(999, 10))
+            .map(|i| {
+                let mut r = Record::new();
+                r.insert("id".into(), i as i64).unwrap();
+                r
+            })
+            .collect();
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+
+        let metrics = MetricsCollector::new();
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: metrics.clone(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        let result = mvcc.execute(ctx).await.unwrap();
+
+        assert_eq!(metrics.histogram("chain_length").unwrap().count, 50);
+        assert_eq!(metrics.percentile("chain_length", 50.0), Some(1.0));
+        // The average hides the hot key; p99 does not.
+        assert!(*result.metrics.get("chain_length_avg").unwrap() < 2.0);
+        assert!(metrics.percentile("chain_length", 99.0).unwrap() > 9.0);
+    }
+
+    #[tokio::test]
+    async fn test_execute_snapshot_port() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut mvcc = MVCCBlock::new();
+        mvcc.gc_threshold = 10000;
+        for i in 0..3 {
+            let txn = mvcc.begin_txn();
+            mvcc.write(txn, "7", json!({"id": 7, "v": i}));
+            mvcc.commit(txn);
+        }
+        // Writes happened at timestamps 1, 3 and 5.
+        let request = |ts: u64| {
+            let mut r = Record::new();
+            r.insert("id".into(), 7).unwrap();
+            r.insert("_snapshot_ts".into(), ts).unwrap();
+            r
+        };
+
+        let mut inputs = HashMap::new();
+        inputs.insert("snapshot".into(), PortValue::Stream(vec![request(3), request(5)]));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = mvcc.execute(ctx).await.unwrap();
+        let PortValue::Stream(rows) = result.outputs.get("snapshot_results").unwrap() else {
+            panic!("expected stream");
+        };
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].data.get("v"), Some(&json!(1)));
+        assert_eq!(rows[1].data.get("v"), Some(&json!(2)));
+
+        mvcc.run_gc();
+        let mut inputs = HashMap::new();
+        inputs.insert("snapshot".into(), PortValue::Stream(vec![request(3)]));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        let result = mvcc.execute(ctx).await.unwrap();
+        let PortValue::Stream(rows) = result.outputs.get("snapshot_results").unwrap() else {
+            panic!("expected stream");
+        };
+        assert_eq!(rows[0].data.get("_snapshot_unavailable"), Some(&json!(true)));
+    }
+
+    #[tokio::test]
+    async fn test_execute_delete_op() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut mvcc = MVCCBlock::new();
+
+        let mut records: Vec<Record> = (0..3)
+            .map(|i| {
+                let mut r = Record::new();
+                r.insert("id".into(), i as i64).unwrap();
+                r
+            })
+            .collect();
+        let mut delete = Record::new();
+        delete.insert("id".into(), 1i64).unwrap();
+        delete.insert("_op".into(), "delete").unwrap();
+        records.push(delete);
+        // Never written: nothing to delete.
+        let mut missing = Record::new();
+        missing.insert("id".into(), 9i64).unwrap();
+        missing.insert("_op".into(), "delete").unwrap();
+        records.push(missing);
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+
+        let metrics = MetricsCollector::new();
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: metrics.clone(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = mvcc.execute(ctx).await.unwrap();
+        assert_eq!(*result.metrics.get("deletes").unwrap(), 1.0);
+        assert_eq!(metrics.get_values("deletes"), vec![1.0]);
+        assert_eq!(*result.metrics.get("versions_visible").unwrap(), 2.0);
+        assert_eq!(result.outputs.get("visible").unwrap().len(), 3);
+
+        let reader = mvcc.begin_txn();
+        assert_eq!(mvcc.read(reader, "1"), None);
+    }
+
+    /// Execute the write-skew schedule from [`run_write_skew`] as `_txn`
+    /// records and return the committed writes and SSI aborts.
+    async fn execute_write_skew(isolation: &str) -> (usize, f64) {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut mvcc = MVCCBlock::new();
+        let mut params = HashMap::new();
+        params.insert("isolation_level".into(), ParameterValue::String(isolation.into()));
+        mvcc.initialize(params).await.unwrap();
+
+        let step = |txn: Option<&str>, op: &str, id: &str| {
+            let mut r = Record::new();
+            if let Some(txn) = txn {
+                r.insert("_txn".into(), txn).unwrap();
+            }
+            r.insert("_op".into(), op).unwrap();
+            r.insert("id".into(), id).unwrap();
+            r
+        };
+        let mut records = vec![step(None, "write", "alice"), step(None, "write", "bob")];
+        for id in ["alice", "bob"] {
+            records.push(step(Some("t1"), "read", id));
+            records.push(step(Some("t2"), "read", id));
+        }
+        records.extend([
+            step(Some("t1"), "write", "alice"),
+            step(Some("t2"), "write", "bob"),
+            step(Some("t1"), "commit", ""),
+            step(Some("t2"), "commit", ""),
+        ]);
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        let result = mvcc.execute(ctx).await.unwrap();
+        (result.outputs["visible"].len(), result.metrics["ssi_aborts"])
+    }
+
+    #[tokio::test]
+    async fn test_execute_client_txns_reach_ssi() {
+        // Two setup writes plus both doctors' updates.
+        assert_eq!(execute_write_skew("snapshot").await, (4, 0.0));
+        // SSI aborts one doctor's transaction and drops its write.
+        assert_eq!(execute_write_skew("serializable").await, (3, 1.0));
+    }
 }
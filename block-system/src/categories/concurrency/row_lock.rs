@@ -8,8 +8,18 @@
 //!
 //! - **Growing phase**: Locks are acquired as records are accessed.
 //! - **Shrinking phase**: All locks released at once when the transaction commits.
-//! - **Lock modes**: Shared (S) for reads, Exclusive (X) for writes.
-//! - **Deadlock detection**: Uses a wait-for graph with cycle detection.
+//! - **Lock modes**: Shared (S) for reads, Exclusive (X) for writes. Any
+//!   number of transactions may hold S on a key together; X excludes everyone.
+//!   A holder of S that asks for X is upgraded in place once it is the only
+//!   holder — two readers upgrading the same key wait on each other and deadlock.
+//! - **Waiting**: An incompatible request is queued on the resource and adds
+//!   edges to the wait-for graph; it is granted when the holders release.
+//! - **Deadlock detection**: Uses a wait-for graph with cycle detection. When a
+//!   cycle forms, the youngest transaction in it (highest ID) is aborted.
+//! - **Deadlock policy**: `deadlock_policy` swaps detection for prevention —
+//!   `wait_die` and `wound_wait` decide by transaction age (lower ID = older)
+//!   so a cycle can never form, and `timeout` aborts any request that has
+//!   waited `lock_timeout_ms` on the simulated clock.
 //!
 //! ## Metrics tracked
 //!
@@ -18,13 +28,27 @@
 //! | `locks_acquired` | Counter | Total locks granted |
 //! | `lock_waits` | Counter | Lock requests that had to wait |
 //! | `deadlocks_detected` | Counter | Deadlock cycles found |
+//! | `aborts_by_policy` | Counter | Transactions aborted by wait-die, wound-wait or timeout |
 //! | `lock_upgrades` | Counter | S → X upgrades |
 //! | `active_locks` | Gauge | Currently held locks |
+//! | `shared_locks_held` | Gauge | Currently held locks in S mode |
 //! | `transactions_committed` | Counter | Successfully committed txns |
 //! | `transactions_aborted` | Counter | Aborted transactions |
+//!
+//! ## Input records
+//!
+//! Records without a `_txn` field each run as their own transaction that takes
+//! X on the record's `id` and commits at once, or, if a client transaction
+//! holds the row, as soon as the lock is granted. Records with `_txn` belong to a
+//! client transaction that stays open across records: `_op` is `read` (S on
+//! `id`), `write` (X on `id`, the default), `commit` or `abort`. A transaction's
+//! records are emitted on `committed` when it commits; if it is aborted as a
+//! deadlock victim or by the policy they are dropped. A `commit` from a
+//! transaction that is still waiting for a lock is deferred: it commits once
+//! the lock is granted, or is dropped if the transaction is aborted first.
 
 use async_trait::async_trait;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
@@ -33,7 +57,7 @@
 use crate::core::constraint::{Constraint, Guarantee, GuaranteeType};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
 use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
@@ -54,12 +78,40 @@ struct LockEntry {
     holders: HashSet<u64>, // Transaction IDs
 }
 
+/// How lock conflicts that could deadlock are resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadlockPolicy {
+    /// Wait, and abort the youngest transaction when a wait-for cycle forms.
+    Detect,
+    /// An older requester waits; a younger requester aborts itself ("dies").
+    WaitDie,
+    /// An older requester aborts ("wounds") younger holders; a younger one waits.
+    WoundWait,
+    /// Wait without detection; abort requests that wait `lock_timeout_ms`.
+    Timeout,
+}
+
+/// Values accepted by the `deadlock_policy` parameter.
+const DEADLOCK_POLICIES: &[&str] = &["detect", "wait_die", "wound_wait", "timeout"];
+
+/// A queued lock request waiting for incompatible holders to release.
+#[derive(Debug, Clone)]
+struct LockRequest {
+    txn_id: u64,
+    mode: LockMode,
+}
+
 /// Result of a lock request.
 #[derive(Debug, Clone, PartialEq)]
 pub enum LockResult {
+    /// The lock was granted immediately.
     Granted,
+    /// The request is queued; it is granted when the holders release.
     Waited,
+    /// The request closed a wait-for cycle and the requester was aborted.
     Deadlock,
+    /// The deadlock policy aborted the requester instead of letting it wait.
+    Aborted,
 }
 
 // ---------------------------------------------------------------------------
@@ -75,18 +127,33 @@ pub struct RowLockBlock {
 
     // Configuration
     max_locks_per_txn: usize,
+    deadlock_policy: DeadlockPolicy,
+    lock_timeout_ms: u64,
 
     // Internal state — lock table: resource_id → LockEntry
     lock_table: HashMap<String, LockEntry>,
     // Tracks which resources each transaction holds.
     txn_locks: HashMap<u64, Vec<String>>,
+    // Pending requests per resource, in arrival order.
+    wait_queue: HashMap<String, VecDeque<LockRequest>>,
     // Wait-for graph: txn → set of txns it's waiting for.
     wait_for: HashMap<u64, HashSet<u64>>,
+    // Simulated clock (ms) and when each waiting txn started waiting.
+    clock_ms: u64,
+    wait_started: HashMap<u64, u64>,
+    // Client transactions driven by `_txn` records: label → txn id, and the
+    // records each has issued so far.
+    client_txns: HashMap<String, u64>,
+    txn_records: HashMap<u64, Vec<Record>>,
+    // Transactions asked to commit while still waiting for a lock, in the
+    // order the commits arrived.
+    pending_commits: Vec<u64>,
 
     // Counters
     locks_acquired: usize,
     lock_waits: usize,
     deadlocks_detected: usize,
+    aborts_by_policy: usize,
     lock_upgrades: usize,
     txn_committed: usize,
     txn_aborted: usize,
@@ -102,12 +169,21 @@ pub fn new() -> Self {
             params: Self::build_parameters(),
             metric_defs: Self::build_metrics(),
             max_locks_per_txn: 1000,
+            deadlock_policy: DeadlockPolicy::Detect,
+            lock_timeout_ms: 1000,
             lock_table: HashMap::new(),
             txn_locks: HashMap::new(),
+            wait_queue: HashMap::new(),
             wait_for: HashMap::new(),
+            clock_ms: 0,
+            wait_started: HashMap::new(),
+            client_txns: HashMap::new(),
+            txn_records: HashMap::new(),
+            pending_commits: Vec::new(),
             locks_acquired: 0,
             lock_waits: 0,
             deadlocks_detected: 0,
+            aborts_by_policy: 0,
             lock_upgrades: 0,
             txn_committed: 0,
             txn_aborted: 0,
@@ -146,17 +222,21 @@ fn build_metadata() -> BlockMetadata {
                            3. If lock exists and is compatible (S+S):\n     \
                               Add txn_id to holders set\n  \
                            4. If lock exists and is incompatible (S+X or X+any):\n     \
-                              a. Add edge txn_id -> holders to wait-for graph\n     \
-                              b. Run cycle detection (DFS from txn_id)\n     \
-                              c. If cycle found: ABORT requesting txn (deadlock victim)\n     \
-                              d. If no cycle: WAIT, then grant when holders release\n\n\
+                              a. Queue the request, add edges txn_id -> holders to wait-for graph\n     \
+                              b. Run cycle detection (DFS over the wait-for graph)\n     \
+                              c. If cycle found: ABORT the youngest txn in the cycle (victim)\n     \
+                              d. Otherwise: WAIT until the holders release\n\n\
                            COMMIT(txn_id):\n  \
                            1. Release ALL locks held by txn_id\n  \
-                           2. Remove txn_id from wait-for graph\n  \
-                           3. Wake up any transactions waiting for released locks\n\n\
-                           DEADLOCK_CHECK(start_txn):\n  \
-                           DFS traversal of wait-for graph from start_txn\n  \
-                           If we reach start_txn again -> cycle -> deadlock"
+                           2. Remove txn_id from wait-for graph and wait queues\n  \
+                           3. Grant queued requests that are now compatible\n\n\
+                           PREVENTION POLICIES (on an incompatible request):\n  \
+                           wait_die:   requester older than every holder -> WAIT, else ABORT requester\n  \
+                           wound_wait: ABORT holders younger than requester, WAIT on the rest\n  \
+                           timeout:    WAIT; ABORT once waiting >= lock_timeout_ms\n\n\
+                           DETECT_DEADLOCK():\n  \
+                           DFS over the wait-for graph, tracking the current path\n  \
+                           Reaching a txn already on the path -> that part of the path is a cycle"
                     .into(),
                 complexity: Complexity {
                     time: "Lock acquire O(1), deadlock check O(V+E) in wait-for graph".into(),
@@ -201,6 +281,22 @@ fn build_metadata() -> BlockMetadata {
                       SQL Server, the default escalation threshold is around 5000 locks. Recommended: start \
                       at 1000 and increase if you see frequent lock escalation with short transactions."
                         .into()),
+                    ("deadlock_policy".into(),
+                     "How deadlocks are handled. detect (the default) lets transactions wait and aborts the \
+                      youngest member of a wait-for cycle once one forms — few unnecessary aborts, but it \
+                      needs cycle detection. wait_die and wound_wait never let a cycle form: they compare \
+                      transaction ages on every conflict and abort someone up front, so they are deadlock-free \
+                      by construction but abort transactions that would never have deadlocked. timeout skips \
+                      detection entirely and aborts any request that waits too long — cheap, but deadlocked \
+                      transactions sit idle until the timeout expires."
+                        .into()),
+                    ("lock_timeout_ms".into(),
+                     "How long a request may wait, on the simulated clock, before the timeout policy aborts \
+                      it (other policies ignore it). Short timeouts resolve deadlocks quickly but also abort \
+                      transactions that were only waiting behind a slow holder. MySQL's \
+                      innodb_lock_wait_timeout defaults to 50 seconds; PostgreSQL's lock_timeout is off by \
+                      default."
+                        .into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -240,7 +336,7 @@ fn build_inputs() -> Vec<Port> {
             direction: PortDirection::Input,
             required: true,
             multiple: false,
-            description: "Records to process with locking".into(),
+            description: "Records to process with locking; `_txn` and `_op` drive multi-record transactions".into(),
             schema: None,
         }]
     }
@@ -259,7 +355,8 @@ fn build_outputs() -> Vec<Port> {
     }
 
     fn build_parameters() -> Vec<Parameter> {
-        vec![Parameter {
+        vec![
+        Parameter {
             id: "max_locks_per_txn".into(),
             name: "Max Locks Per Txn".into(),
             param_type: ParameterType::Number,
@@ -276,6 +373,30 @@ fn build_parameters() -> Vec<Parameter> {
                     .with_step(100.0)
                     .with_help_text("Lock escalation threshold".into()),
             ),
+        },
+        Parameter {
+            id: "deadlock_policy".into(),
+            name: "Deadlock Policy".into(),
+            param_type: ParameterType::Enum,
+            description: "detect, wait_die, wound_wait, or timeout".into(),
+            default_value: ParameterValue::String("detect".into()),
+            required: false,
+            constraints: Some(ParameterConstraints::new().with_choices(DEADLOCK_POLICIES)),
+            ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+        },
+        Parameter {
+            id: "lock_timeout_ms".into(),
+            name: "Lock Timeout".into(),
+            param_type: ParameterType::Number,
+            description: "Maximum lock wait before abort (timeout policy only)".into(),
+            default_value: ParameterValue::Integer(1000),
+            required: false,
+            constraints: Some(ParameterConstraints::new().with_min(1.0).with_max(60000.0)),
+            ui_hint: Some(
+                ParameterUIHint::new(WidgetType::Slider)
+                    .with_step(100.0)
+                    .with_unit("ms".into()),
+            ),
         }]
     }
 
@@ -305,6 +426,22 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Deadlock cycles detected".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "aborts_by_policy".into(),
+                name: "Policy Aborts".into(),
+                metric_type: MetricType::Counter,
+                unit: "txns".into(),
+                description: "Transactions aborted by wait-die, wound-wait or lock timeout".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "shared_locks_held".into(),
+                name: "Shared Locks Held".into(),
+                metric_type: MetricType::Gauge,
+                unit: "locks".into(),
+                description: "Locks currently held in shared mode".into(),
+                aggregations: vec![AggregationType::Avg, AggregationType::Max],
+            },
             MetricDefinition {
                 id: "lock_upgrades".into(),
                 name: "Lock Upgrades".into(),
@@ -351,6 +488,12 @@ pub fn begin_txn(&mut self) -> u64 {
     }
 
     /// Request a lock on a resource.
+    ///
+    /// An incompatible request is queued and returns [`LockResult::Waited`];
+    /// it is granted once the conflicting holders commit or abort. If queuing
+    /// it closes a wait-for cycle, the youngest transaction in the cycle is
+    /// aborted — when that is the requester, this returns
+    /// [`LockResult::Deadlock`].
     pub fn acquire_lock(
         &mut self,
         txn_id: u64,
@@ -358,106 +501,115 @@ pub fn acquire_lock(
         mode: LockMode,
     ) -> LockResult {
         if let Some(entry) = self.lock_table.get(resource) {
-            // Already locked.
-            if entry.holders.contains(&txn_id) {
-                // Already hold a lock — check for upgrade.
-                if entry.mode == LockMode::Shared && mode == LockMode::Exclusive {
-                    if entry.holders.len() == 1 {
-                        // Only holder — upgrade.
-                        self.lock_table.get_mut(resource).unwrap().mode = LockMode::Exclusive;
-                        self.lock_upgrades += 1;
-                        self.locks_acquired += 1;
-                        return LockResult::Granted;
-                    } else {
-                        // Others hold shared locks — potential deadlock.
-                        // Add to wait-for graph.
-                        let waitees: HashSet<u64> =
-                            entry.holders.iter().filter(|&&h| h != txn_id).copied().collect();
-                        self.wait_for.insert(txn_id, waitees);
-
-                        if self.has_cycle(txn_id) {
-                            self.wait_for.remove(&txn_id);
-                            self.deadlocks_detected += 1;
-                            return LockResult::Deadlock;
-                        }
-
-                        // Simulate wait then grant.
-                        self.wait_for.remove(&txn_id);
-                        self.lock_waits += 1;
-                        let entry = self.lock_table.get_mut(resource).unwrap();
-                        entry.mode = LockMode::Exclusive;
-                        // Remove other holders (they would have been resolved).
-                        entry.holders.clear();
-                        entry.holders.insert(txn_id);
-                        self.lock_upgrades += 1;
-                        self.locks_acquired += 1;
-                        return LockResult::Waited;
-                    }
-                }
-                // Already hold compatible lock.
+            if entry.holders.contains(&txn_id)
+                && (entry.mode == LockMode::Exclusive || mode == LockMode::Shared)
+            {
+                // Already hold a lock at least as strong.
                 return LockResult::Granted;
             }
+        }
 
-            // Someone else holds the lock — check compatibility.
-            let compatible = entry.mode == LockMode::Shared && mode == LockMode::Shared;
-
-            if compatible {
-                self.lock_table
-                    .get_mut(resource)
-                    .unwrap()
-                    .holders
-                    .insert(txn_id);
-                self.txn_locks
-                    .entry(txn_id)
-                    .or_default()
-                    .push(resource.to_string());
-                self.locks_acquired += 1;
-                return LockResult::Granted;
+        let blockers = self.blockers(txn_id, resource, mode);
+        if blockers.is_empty() {
+            self.grant(txn_id, resource, mode);
+            return LockResult::Granted;
+        }
+
+        // Incompatible — apply the prevention policy (lower ID = older).
+        let blockers = match self.deadlock_policy {
+            DeadlockPolicy::WaitDie if blockers.iter().any(|&b| b < txn_id) => {
+                // Younger than a holder: die.
+                self.aborts_by_policy += 1;
+                self.abort(txn_id);
+                return LockResult::Aborted;
+            }
+            DeadlockPolicy::WoundWait if blockers.iter().any(|&b| b > txn_id) => {
+                // Older than some holders: wound them, then retry.
+                for victim in blockers.iter().copied().filter(|&b| b > txn_id) {
+                    self.aborts_by_policy += 1;
+                    self.abort(victim);
+                }
+                let remaining = self.blockers(txn_id, resource, mode);
+                if remaining.is_empty() {
+                    self.grant(txn_id, resource, mode);
+                    return LockResult::Granted;
+                }
+                remaining
             }
+            _ => blockers,
+        };
 
-            // Incompatible — check for deadlock.
-            let waitees = entry.holders.clone();
-            self.wait_for.insert(txn_id, waitees);
+        // Queue the request and check for deadlock.
+        self.lock_waits += 1;
+        self.wait_queue
+            .entry(resource.to_string())
+            .or_default()
+            .push_back(LockRequest { txn_id, mode });
+        self.wait_for.insert(txn_id, blockers);
+        self.wait_started.entry(txn_id).or_insert(self.clock_ms);
 
-            if self.has_cycle(txn_id) {
-                self.wait_for.remove(&txn_id);
+        if self.deadlock_policy == DeadlockPolicy::Detect {
+            if let Some(cycle) = self.detect_deadlock() {
                 self.deadlocks_detected += 1;
-                return LockResult::Deadlock;
+                let victim = Self::select_victim(&cycle);
+                self.abort(victim);
+                if victim == txn_id {
+                    return LockResult::Deadlock;
+                }
             }
+        }
+
+        if self.holds(txn_id, resource, mode) {
+            LockResult::Granted
+        } else {
+            LockResult::Waited
+        }
+    }
+
+    /// Transactions (other than `txn_id`) whose locks on `resource` conflict
+    /// with a `mode` request. Empty means the lock can be granted.
+    fn blockers(&self, txn_id: u64, resource: &str, mode: LockMode) -> HashSet<u64> {
+        match self.lock_table.get(resource) {
+            Some(entry) if mode == LockMode::Exclusive || entry.mode == LockMode::Exclusive => {
+                entry.holders.iter().filter(|&&h| h != txn_id).copied().collect()
+            }
+            _ => HashSet::new(),
+        }
+    }
 
-            // Simulate wait then grant — overwrite the lock entry.
-            self.wait_for.remove(&txn_id);
-            self.lock_waits += 1;
-            let mut holders = HashSet::new();
-            holders.insert(txn_id);
-            self.lock_table.insert(
-                resource.to_string(),
-                LockEntry { mode, holders },
-            );
+    /// Whether `txn_id` holds a lock on `resource` at least as strong as `mode`.
+    fn holds(&self, txn_id: u64, resource: &str, mode: LockMode) -> bool {
+        self.lock_table.get(resource).is_some_and(|entry| {
+            entry.holders.contains(&txn_id)
+                && (entry.mode == LockMode::Exclusive || mode == LockMode::Shared)
+        })
+    }
+
+    /// Add `txn_id` to the holders of `resource`, upgrading S → X if needed.
+    fn grant(&mut self, txn_id: u64, resource: &str, mode: LockMode) {
+        let entry = self
+            .lock_table
+            .entry(resource.to_string())
+            .or_insert_with(|| LockEntry {
+                mode,
+                holders: HashSet::new(),
+            });
+        if entry.holders.contains(&txn_id) {
+            if entry.mode == LockMode::Shared && mode == LockMode::Exclusive {
+                entry.mode = LockMode::Exclusive;
+                self.lock_upgrades += 1;
+            }
+        } else {
+            if entry.holders.is_empty() {
+                entry.mode = mode;
+            }
+            entry.holders.insert(txn_id);
             self.txn_locks
                 .entry(txn_id)
                 .or_default()
                 .push(resource.to_string());
-            self.locks_acquired += 1;
-            return LockResult::Waited;
         }
-
-        // No existing lock — grant immediately.
-        let mut holders = HashSet::new();
-        holders.insert(txn_id);
-        self.lock_table.insert(
-            resource.to_string(),
-            LockEntry {
-                mode,
-                holders,
-            },
-        );
-        self.txn_locks
-            .entry(txn_id)
-            .or_default()
-            .push(resource.to_string());
         self.locks_acquired += 1;
-        LockResult::Granted
     }
 
     /// Commit a transaction — release all its locks.
@@ -473,6 +625,7 @@ pub fn abort(&mut self, txn_id: u64) {
     }
 
     fn release_locks(&mut self, txn_id: u64) {
+        let mut touched: Vec<String> = Vec::new();
         if let Some(resources) = self.txn_locks.remove(&txn_id) {
             for resource in resources {
                 if let Some(entry) = self.lock_table.get_mut(&resource) {
@@ -481,37 +634,235 @@ fn release_locks(&mut self, txn_id: u64) {
                         self.lock_table.remove(&resource);
                     }
                 }
+                touched.push(resource);
+            }
+        }
+        // Drop any request the transaction still had queued.
+        for (resource, queue) in self.wait_queue.iter_mut() {
+            let before = queue.len();
+            queue.retain(|req| req.txn_id != txn_id);
+            if queue.len() != before {
+                touched.push(resource.clone());
             }
         }
+        self.wait_queue.retain(|_, queue| !queue.is_empty());
         self.wait_for.remove(&txn_id);
+        self.wait_started.remove(&txn_id);
+
+        for resource in touched {
+            self.grant_waiters(&resource);
+        }
+    }
+
+    /// Grant queued requests on `resource` that no longer conflict, in
+    /// arrival order, and refresh the wait-for edges of those still waiting.
+    fn grant_waiters(&mut self, resource: &str) {
+        let Some(queue) = self.wait_queue.remove(resource) else {
+            return;
+        };
+        let mut still_waiting = VecDeque::new();
+        for req in queue {
+            let blockers = self.blockers(req.txn_id, resource, req.mode);
+            if blockers.is_empty() {
+                self.grant(req.txn_id, resource, req.mode);
+                self.wait_for.remove(&req.txn_id);
+                self.wait_started.remove(&req.txn_id);
+            } else {
+                self.wait_for.insert(req.txn_id, blockers);
+                still_waiting.push_back(req);
+            }
+        }
+        if !still_waiting.is_empty() {
+            self.wait_queue.insert(resource.to_string(), still_waiting);
+        }
     }
 
-    /// Detect cycle in wait-for graph using DFS from start_txn.
-    fn has_cycle(&self, start_txn: u64) -> bool {
-        let mut visited = HashSet::new();
-        let mut stack = vec![start_txn];
+    /// Advance the simulated clock. Under the timeout policy, abort requests
+    /// that have now waited at least `lock_timeout_ms`, longest wait first.
+    pub fn advance_clock(&mut self, ms: u64) {
+        self.clock_ms += ms;
+        if self.deadlock_policy != DeadlockPolicy::Timeout {
+            return;
+        }
+        let mut expired: Vec<(u64, u64)> = self
+            .wait_started
+            .iter()
+            .filter(|(_, &start)| self.clock_ms - start >= self.lock_timeout_ms)
+            .map(|(&txn, &start)| (start, txn))
+            .collect();
+        expired.sort_unstable();
+        for (_, txn) in expired {
+            // An earlier abort may already have let this request through.
+            if self.wait_for.contains_key(&txn) {
+                self.aborts_by_policy += 1;
+                self.abort(txn);
+            }
+        }
+    }
 
-        while let Some(txn) = stack.pop() {
-            if !visited.insert(txn) {
+    /// Find a cycle in the current wait-for graph.
+    ///
+    /// Returns the transactions forming the cycle, in wait order (each one
+    /// waits for the next, and the last waits for the first), or `None` if no
+    /// transaction is deadlocked.
+    pub fn detect_deadlock(&self) -> Option<Vec<u64>> {
+        let mut starts: Vec<u64> = self.wait_for.keys().copied().collect();
+        starts.sort_unstable();
+
+        let mut done = HashSet::new();
+        for start in starts {
+            if done.contains(&start) {
                 continue;
             }
-            if let Some(waitees) = self.wait_for.get(&txn) {
-                for &w in waitees {
-                    if w == start_txn {
-                        return true; // Cycle back to start
-                    }
-                    if !visited.contains(&w) {
-                        stack.push(w);
-                    }
+            let mut path = Vec::new();
+            if let Some(cycle) = self.find_cycle(start, &mut path, &mut done) {
+                return Some(cycle);
+            }
+        }
+        None
+    }
+
+    /// DFS helper for [`detect_deadlock`](Self::detect_deadlock). `path` holds
+    /// the transactions on the current DFS branch; `done` those fully explored.
+    fn find_cycle(
+        &self,
+        txn: u64,
+        path: &mut Vec<u64>,
+        done: &mut HashSet<u64>,
+    ) -> Option<Vec<u64>> {
+        if let Some(pos) = path.iter().position(|&t| t == txn) {
+            return Some(path[pos..].to_vec());
+        }
+        if done.contains(&txn) {
+            return None;
+        }
+        path.push(txn);
+        if let Some(waitees) = self.wait_for.get(&txn) {
+            let mut next: Vec<u64> = waitees.iter().copied().collect();
+            next.sort_unstable();
+            for w in next {
+                if let Some(cycle) = self.find_cycle(w, path, done) {
+                    return Some(cycle);
                 }
             }
         }
-        false
+        path.pop();
+        done.insert(txn);
+        None
+    }
+
+    /// Deadlock victim policy: abort the youngest transaction in the cycle
+    /// (the highest ID), since it has likely done the least work.
+    pub fn select_victim(cycle: &[u64]) -> u64 {
+        cycle.iter().copied().max().unwrap_or(0)
     }
 
     pub fn active_lock_count(&self) -> usize {
         self.lock_table.values().map(|e| e.holders.len()).sum()
     }
+
+    /// Number of locks currently held in shared mode.
+    pub fn shared_lock_count(&self) -> usize {
+        self.lock_table
+            .values()
+            .filter(|e| e.mode == LockMode::Shared)
+            .map(|e| e.holders.len())
+            .sum()
+    }
+
+    /// Apply one record of a `_txn` client transaction. Returns the records
+    /// to emit if this record committed the transaction.
+    fn apply_client_record(&mut self, label: String, record: &Record) -> Vec<Record> {
+        let op = record.get::<String>("_op").ok().flatten();
+        let txn_id = match self.client_txns.get(&label) {
+            Some(&id) => id,
+            // Ending a transaction that is not open (e.g. already aborted) is a no-op.
+            None if matches!(op.as_deref(), Some("commit" | "abort")) => return Vec::new(),
+            None => {
+                let id = self.begin_txn();
+                self.client_txns.insert(label.clone(), id);
+                id
+            }
+        };
+        match op.as_deref() {
+            Some("commit") => {
+                self.client_txns.remove(&label);
+                if self.wait_for.contains_key(&txn_id) {
+                    self.pending_commits.push(txn_id);
+                    return Vec::new();
+                }
+                self.commit(txn_id);
+                self.txn_records.remove(&txn_id).unwrap_or_default()
+            }
+            Some("abort") => {
+                self.abort(txn_id);
+                self.client_txns.remove(&label);
+                self.txn_records.remove(&txn_id);
+                Vec::new()
+            }
+            _ => {
+                let mode = if op.as_deref() == Some("read") {
+                    LockMode::Shared
+                } else {
+                    LockMode::Exclusive
+                };
+                let resource = record
+                    .data
+                    .get("id")
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| format!("row_{}", txn_id));
+                match self.acquire_lock(txn_id, &resource, mode) {
+                    LockResult::Granted | LockResult::Waited => {
+                        self.txn_records.entry(txn_id).or_default().push(record.clone());
+                    }
+                    LockResult::Deadlock | LockResult::Aborted => {}
+                }
+                Vec::new()
+            }
+        }
+    }
+
+    /// Commit the deferred transactions whose waits have cleared and return
+    /// their records. Deferred transactions aborted in the meantime are
+    /// dropped. A commit releases locks and may clear another wait, so this
+    /// repeats until nothing changes.
+    fn finish_pending_commits(&mut self) -> Vec<Record> {
+        let mut committed = Vec::new();
+        loop {
+            let ready: Vec<u64> = self
+                .pending_commits
+                .iter()
+                .copied()
+                .filter(|id| !self.txn_locks.contains_key(id) || !self.wait_for.contains_key(id))
+                .collect();
+            if ready.is_empty() {
+                return committed;
+            }
+            self.pending_commits.retain(|id| !ready.contains(id));
+            for txn_id in ready {
+                let records = self.txn_records.remove(&txn_id).unwrap_or_default();
+                if self.txn_locks.contains_key(&txn_id) {
+                    self.commit(txn_id);
+                    committed.extend(records);
+                }
+            }
+        }
+    }
+
+    /// Forget client transactions that were aborted as deadlock victims or
+    /// by the deadlock policy.
+    fn drop_aborted_client_txns(&mut self) {
+        let aborted: Vec<(String, u64)> = self
+            .client_txns
+            .iter()
+            .filter(|(_, id)| !self.txn_locks.contains_key(id))
+            .map(|(label, &id)| (label.clone(), id))
+            .collect();
+        for (label, id) in aborted {
+            self.client_txns.remove(&label);
+            self.txn_records.remove(&id);
+        }
+    }
 }
 
 impl Default for RowLockBlock {
@@ -560,12 +911,20 @@ async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("max_locks_per_txn") {
-            self.max_locks_per_txn = val
-                .as_integer()
-                .ok_or_else(|| {
-                    BlockError::InvalidParameter("max_locks_per_txn must be an integer".into())
-                })? as usize;
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("max_locks_per_txn").and_then(ParameterValue::as_integer) {
+            self.max_locks_per_txn = v as usize;
+        }
+        if let Some(s) = enum_param(&params, "deadlock_policy", DEADLOCK_POLICIES)? {
+            self.deadlock_policy = match s {
+                "detect" => DeadlockPolicy::Detect,
+                "wait_die" => DeadlockPolicy::WaitDie,
+                "wound_wait" => DeadlockPolicy::WoundWait,
+                _ => DeadlockPolicy::Timeout,
+            };
+        }
+        if let Some(ms) = params.get("lock_timeout_ms").and_then(ParameterValue::as_integer) {
+            self.lock_timeout_ms = ms as u64;
         }
         Ok(())
     }
@@ -592,10 +951,18 @@ async fn execute(
             }
         };
 
-        // Simulate: each record is a separate transaction that acquires an exclusive lock.
         let mut committed_records = Vec::new();
 
         for record in &records {
+            if let Some(label) = record.data.get("_txn").map(|v| v.to_string()) {
+                committed_records.extend(self.apply_client_record(label, record));
+                self.advance_clock(1);
+                self.drop_aborted_client_txns();
+                committed_records.extend(self.finish_pending_commits());
+                continue;
+            }
+
+            // No `_txn`: the record is its own transaction taking an exclusive lock.
             let txn_id = self.begin_txn();
             let resource = record
                 .data
@@ -606,14 +973,20 @@ async fn execute(
             let result = self.acquire_lock(txn_id, &resource, LockMode::Exclusive);
 
             match result {
-                LockResult::Granted | LockResult::Waited => {
+                LockResult::Granted => {
                     committed_records.push(record.clone());
                     self.commit(txn_id);
                 }
-                LockResult::Deadlock => {
-                    self.abort(txn_id);
+                LockResult::Waited => {
+                    // Commit once a client transaction releases the row.
+                    self.txn_records.insert(txn_id, vec![record.clone()]);
+                    self.pending_commits.push(txn_id);
                 }
+                LockResult::Deadlock | LockResult::Aborted => {}
             }
+            // Each record advances the simulated clock by 1 ms.
+            self.advance_clock(1);
+            committed_records.extend(self.finish_pending_commits());
         }
 
         context
@@ -625,9 +998,15 @@ async fn execute(
         context
             .metrics
             .record("deadlocks_detected", self.deadlocks_detected as f64);
+        context
+            .metrics
+            .record("aborts_by_policy", self.aborts_by_policy as f64);
         context
             .metrics
             .record("lock_upgrades", self.lock_upgrades as f64);
+        context
+            .metrics
+            .record("shared_locks_held", self.shared_lock_count() as f64);
         context
             .metrics
             .record("active_locks", self.active_lock_count() as f64);
@@ -645,6 +1024,9 @@ async fn execute(
         metrics_summary.insert("locks_acquired".into(), self.locks_acquired as f64);
         metrics_summary.insert("lock_waits".into(), self.lock_waits as f64);
         metrics_summary.insert("deadlocks_detected".into(), self.deadlocks_detected as f64);
+        metrics_summary.insert("lock_upgrades".into(), self.lock_upgrades as f64);
+        metrics_summary.insert("shared_locks_held".into(), self.shared_lock_count() as f64);
+        metrics_summary.insert("aborts_by_policy".into(), self.aborts_by_policy as f64);
         metrics_summary.insert(
             "transactions_committed".into(),
             self.txn_committed as f64,
@@ -744,6 +1126,284 @@ fn test_exclusive_lock_conflict() {
         assert_eq!(lock.lock_waits, 1);
     }
 
+    #[test]
+    fn test_waiter_granted_on_release() {
+        let mut lock = RowLockBlock::new();
+        let txn1 = lock.begin_txn();
+        let txn2 = lock.begin_txn();
+
+        lock.acquire_lock(txn1, "row_1", LockMode::Exclusive);
+        assert_eq!(
+            lock.acquire_lock(txn2, "row_1", LockMode::Exclusive),
+            LockResult::Waited
+        );
+        assert!(!lock.holds(txn2, "row_1", LockMode::Exclusive));
+
+        lock.commit(txn1);
+        assert!(lock.holds(txn2, "row_1", LockMode::Exclusive));
+        assert!(lock.wait_for.is_empty());
+    }
+
+    #[test]
+    fn test_deadlock_detected_and_youngest_aborted() {
+        let mut lock = RowLockBlock::new();
+        let t1 = lock.begin_txn();
+        let t2 = lock.begin_txn();
+
+        lock.acquire_lock(t1, "A", LockMode::Exclusive);
+        lock.acquire_lock(t2, "B", LockMode::Exclusive);
+
+        // T1 waits for B (held by T2).
+        assert_eq!(lock.acquire_lock(t1, "B", LockMode::Exclusive), LockResult::Waited);
+        assert_eq!(lock.detect_deadlock(), None);
+
+        // T2 waits for A (held by T1) — cycle; T2 is younger and is the victim.
+        assert_eq!(lock.acquire_lock(t2, "A", LockMode::Exclusive), LockResult::Deadlock);
+        assert_eq!(lock.deadlocks_detected, 1);
+        assert_eq!(lock.txn_aborted, 1);
+
+        // T2's abort released B, so T1's queued request was granted.
+        assert!(lock.holds(t1, "B", LockMode::Exclusive));
+        assert_eq!(lock.detect_deadlock(), None);
+    }
+
+    #[test]
+    fn test_detect_deadlock_reports_cycle() {
+        let mut lock = RowLockBlock::new();
+        // T1 → T2 → T3 → T1, plus T4 waiting on T1 outside the cycle.
+        lock.wait_for.insert(1, HashSet::from([2]));
+        lock.wait_for.insert(2, HashSet::from([3]));
+        lock.wait_for.insert(3, HashSet::from([1]));
+        lock.wait_for.insert(4, HashSet::from([1]));
+
+        let cycle = lock.detect_deadlock().expect("cycle");
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert_eq!(RowLockBlock::select_victim(&cycle), 3);
+
+        lock.wait_for.remove(&3);
+        assert_eq!(lock.detect_deadlock(), None);
+    }
+
+    /// T1 (older) holds A, T2 (younger) holds B, then each requests the other.
+    fn run_crossed_requests(policy: DeadlockPolicy) -> (RowLockBlock, LockResult, LockResult) {
+        let mut lock = RowLockBlock::new();
+        lock.deadlock_policy = policy;
+        let t1 = lock.begin_txn();
+        let t2 = lock.begin_txn();
+        lock.acquire_lock(t1, "A", LockMode::Exclusive);
+        lock.acquire_lock(t2, "B", LockMode::Exclusive);
+        let r1 = lock.acquire_lock(t1, "B", LockMode::Exclusive);
+        let r2 = lock.acquire_lock(t2, "A", LockMode::Exclusive);
+        (lock, r1, r2)
+    }
+
+    #[test]
+    fn test_wait_die() {
+        let (lock, r1, r2) = run_crossed_requests(DeadlockPolicy::WaitDie);
+        // Older T1 waits for B; younger T2 dies instead of waiting for A.
+        assert_eq!(r1, LockResult::Waited);
+        assert_eq!(r2, LockResult::Aborted);
+        assert_eq!(lock.aborts_by_policy, 1);
+        assert_eq!(lock.deadlocks_detected, 0);
+        assert!(lock.holds(1, "B", LockMode::Exclusive));
+    }
+
+    #[test]
+    fn test_wound_wait() {
+        let (lock, r1, r2) = run_crossed_requests(DeadlockPolicy::WoundWait);
+        // Older T1 wounds T2 and takes B immediately; T2 never gets to ask.
+        assert_eq!(r1, LockResult::Granted);
+        assert_eq!(lock.aborts_by_policy, 1);
+        assert_eq!(lock.deadlocks_detected, 0);
+        assert!(lock.holds(1, "B", LockMode::Exclusive));
+        // T2 was aborted, so its later request is just a fresh (younger) waiter.
+        assert_eq!(r2, LockResult::Waited);
+        assert!(lock.detect_deadlock().is_none());
+    }
+
+    #[test]
+    fn test_wound_wait_younger_waits() {
+        let mut lock = RowLockBlock::new();
+        lock.deadlock_policy = DeadlockPolicy::WoundWait;
+        let t1 = lock.begin_txn();
+        let t2 = lock.begin_txn();
+        lock.acquire_lock(t1, "A", LockMode::Exclusive);
+        assert_eq!(lock.acquire_lock(t2, "A", LockMode::Exclusive), LockResult::Waited);
+        assert_eq!(lock.aborts_by_policy, 0);
+    }
+
+    #[test]
+    fn test_timeout_policy() {
+        let (mut lock, r1, r2) = run_crossed_requests(DeadlockPolicy::Timeout);
+        lock.lock_timeout_ms = 50;
+        // No detection: both wait and the cycle stays in place.
+        assert_eq!(r1, LockResult::Waited);
+        assert_eq!(r2, LockResult::Waited);
+        assert!(lock.detect_deadlock().is_some());
+        assert_eq!(lock.deadlocks_detected, 0);
+
+        lock.advance_clock(49);
+        assert_eq!(lock.aborts_by_policy, 0);
+
+        // Both time out together; aborting T1 first lets T2's request through.
+        lock.advance_clock(1);
+        assert_eq!(lock.aborts_by_policy, 1);
+        assert!(lock.holds(2, "A", LockMode::Exclusive));
+        assert!(lock.detect_deadlock().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_deadlock_policy() {
+        let mut lock = RowLockBlock::new();
+        let mut params = HashMap::new();
+        params.insert("deadlock_policy".into(), ParameterValue::String("wound_wait".into()));
+        params.insert("lock_timeout_ms".into(), ParameterValue::Integer(200));
+        lock.initialize(params).await.unwrap();
+        assert_eq!(lock.deadlock_policy, DeadlockPolicy::WoundWait);
+        assert_eq!(lock.lock_timeout_ms, 200);
+
+        let mut bad = HashMap::new();
+        bad.insert("deadlock_policy".into(), ParameterValue::String("ostrich".into()));
+        assert!(lock.initialize(bad).await.is_err());
+    }
+
+    async fn run_client_ops(lock: &mut RowLockBlock, ops: &[(&str, &str)]) -> ExecutionResult {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        use serde_json::json;
+
+        let records = ops
+            .iter()
+            .map(|(txn, op)| {
+                let mut r = Record::new();
+                r.insert("_txn".into(), json!(txn)).unwrap();
+                r.insert("_op".into(), json!(op)).unwrap();
+                r.insert("id".into(), json!("k")).unwrap();
+                r
+            })
+            .collect();
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        lock.execute(ctx).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_execute_shared_exclusive_compatibility() {
+        let mut lock = RowLockBlock::new();
+
+        // Two readers coexist on the same key.
+        let result = run_client_ops(&mut lock, &[("t1", "read"), ("t2", "read")]).await;
+        assert_eq!(lock.lock_waits, 0);
+        assert_eq!(*result.metrics.get("shared_locks_held").unwrap(), 2.0);
+
+        // A writer waits until both readers have released.
+        run_client_ops(&mut lock, &[("t3", "write"), ("t1", "commit")]).await;
+        assert_eq!(lock.lock_waits, 1);
+        assert_eq!(lock.shared_lock_count(), 1);
+        let result = run_client_ops(&mut lock, &[("t2", "commit"), ("t3", "commit")]).await;
+        assert_eq!(lock.shared_lock_count(), 0);
+        assert_eq!(lock.txn_committed, 3);
+        let PortValue::Stream(committed) = result.outputs.get("committed").unwrap() else {
+            panic!("expected stream");
+        };
+        assert_eq!(committed.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_execute_concurrent_upgrades_deadlock() {
+        let mut lock = RowLockBlock::new();
+        let result = run_client_ops(
+            &mut lock,
+            &[
+                ("t1", "read"),
+                ("t2", "read"),
+                ("t1", "write"), // waits for t2's S lock
+                ("t2", "write"), // waits for t1's S lock -> cycle
+                ("t1", "commit"),
+                ("t2", "commit"),
+            ],
+        )
+        .await;
+
+        assert_eq!(lock.deadlocks_detected, 1);
+        assert_eq!(lock.lock_upgrades, 1);
+        assert_eq!(lock.txn_aborted, 1);
+        assert_eq!(lock.txn_committed, 1);
+        assert_eq!(*result.metrics.get("lock_upgrades").unwrap(), 1.0);
+        // t2 (the younger) was the victim: only t1's read and write commit,
+        // and t2's later commit is ignored.
+        let PortValue::Stream(committed) = result.outputs.get("committed").unwrap() else {
+            panic!("expected stream");
+        };
+        assert_eq!(committed.len(), 2);
+        assert!(committed
+            .iter()
+            .all(|r| r.get::<String>("_txn").unwrap().as_deref() == Some("t1")));
+    }
+
+    #[tokio::test]
+    async fn test_execute_commit_while_waiting_is_deferred() {
+        let mut lock = RowLockBlock::new();
+        let result = run_client_ops(
+            &mut lock,
+            &[
+                ("t1", "write"),
+                ("t2", "write"),  // waits for t1's X lock
+                ("t2", "commit"), // deferred until the lock is granted
+            ],
+        )
+        .await;
+        assert_eq!(result.outputs.get("committed").unwrap().len(), 0);
+        assert_eq!(lock.txn_committed, 0);
+
+        // t1 releases, t2 is granted the lock and its deferred commit completes.
+        let result = run_client_ops(&mut lock, &[("t1", "commit")]).await;
+        let PortValue::Stream(committed) = result.outputs.get("committed").unwrap() else {
+            panic!("expected stream");
+        };
+        let txns: Vec<String> = committed.iter().map(|r| r.get("_txn").unwrap().unwrap()).collect();
+        assert_eq!(txns, vec!["t1", "t2"]);
+        assert_eq!(lock.txn_committed, 2);
+        assert_eq!(lock.active_lock_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_execute_plain_record_waits_for_client_lock() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        use serde_json::json;
+
+        let mut lock = RowLockBlock::new();
+        run_client_ops(&mut lock, &[("t1", "write")]).await;
+
+        // A record without `_txn` on the same row must not commit while t1
+        // holds its X lock.
+        let mut plain = Record::new();
+        plain.insert("id".into(), json!("k")).unwrap();
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Single(plain));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        let result = lock.execute(ctx).await.unwrap();
+        assert_eq!(result.outputs.get("committed").unwrap().len(), 0);
+        assert_eq!(lock.txn_committed, 0);
+
+        let result = run_client_ops(&mut lock, &[("t1", "commit")]).await;
+        assert_eq!(result.outputs.get("committed").unwrap().len(), 2);
+        assert_eq!(lock.txn_committed, 2);
+        assert_eq!(lock.active_lock_count(), 0);
+    }
+
     #[test]
     fn test_lock_upgrade() {
         let mut lock = RowLockBlock::new();
@@ -2,6 +2,14 @@
 //!
 //! Blocks that handle data replication and distributed system concerns.
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+
 pub mod replication;
 
 pub use replication::ReplicationBlock;
+
+/// Register a factory for each distribution block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(ReplicationBlock::new()))?;
+    Ok(())
+}
@@ -4,6 +4,37 @@
 //! levels. Fundamental to every distributed database — Cassandra, MongoDB,
 //! DynamoDB, and PostgreSQL streaming replication all use variants of this.
 //!
+//! ## How it works
+//!
+//! The block keeps a copy of the data on each of `replica_count` simulated
+//! nodes; node 0 is the primary. Every request advances a logical clock by
+//! one operation. A write (`_op` absent or `"write"`) is applied to the
+//! primary and then, depending on `replication_mode`:
+//!
+//! - `sync` — applied to every replica before it is acknowledged.
+//! - `semi_sync` — applied to one replica before the ack, the rest later.
+//! - `async` — acknowledged by the primary alone; replicas apply it
+//!   `replication_lag` operations later.
+//! - `quorum` — leaderless, Dynamo-style: the write goes to all N nodes and
+//!   succeeds once the W fastest have applied it; the rest catch up later.
+//!
+//! A read (`_op: "read"`) is served round-robin by the replicas, so under
+//! async replication it can return an older version than the primary holds —
+//! a stale read. With `read_your_writes`, a client (`_client`) that wrote in
+//! the last `replication_lag` operations reads from the primary instead. In
+//! `quorum` mode a read asks R nodes and returns the newest version among
+//! them, which is guaranteed to be the latest only when R + W > N.
+//!
+//! Node i acknowledges after `1 + 2i` ms, so a write's latency is that of the
+//! slowest node it waited for (`quorum_write_latency`).
+//!
+//! `fail_node` / `recover_node` (or `_op: "fail_node"` / `"recover_node"`
+//! records with a `_node` field) inject failures. Failed nodes receive no
+//! writes and reads route around them. The next write after the primary
+//! fails triggers a failover to the most up-to-date live node; writes the old
+//! primary acknowledged but had not shipped to it are counted as
+//! `lost_writes`.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
@@ -13,21 +44,28 @@
 //! | `replication_lag_ms` | Gauge | Simulated lag for async replication |
 //! | `consistency_met` | Counter | Writes that met the consistency level |
 //! | `consistency_violations` | Counter | Writes that didn't meet consistency |
+//! | `reads` | Counter | Read requests served |
+//! | `stale_reads` | Counter | Reads that returned an older version than the latest write |
+//! | `primary_reads` | Counter | Reads routed to the primary by read-your-writes |
+//! | `quorum_write_latency` | Gauge | Simulated ms until a write had the acks it waited for |
+//! | `failed_nodes` | Gauge | Nodes currently down |
+//! | `failovers` | Counter | Primary elections after the primary failed |
+//! | `lost_writes` | Counter | Acknowledged writes discarded by a failover |
 
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
     Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
 };
-use crate::core::constraint::{Constraint, Guarantee};
+use crate::core::constraint::{Constraint, Guarantee, GuaranteeType};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
-use crate::core::port::{Port, PortDirection, PortType, PortValue};
+use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
 
 // ---------------------------------------------------------------------------
 // ReplicationBlock
@@ -40,6 +78,73 @@ enum ConsistencyLevel {
     All,
 }
 
+/// Values accepted by the `consistency_level` parameter.
+const CONSISTENCY_LEVELS: &[&str] = &["one", "quorum", "all"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReplicationMode {
+    Sync,
+    SemiSync,
+    Async,
+    Quorum,
+}
+
+/// Values accepted by the `replication_mode` parameter.
+const REPLICATION_MODES: &[&str] = &["sync", "semi_sync", "async", "quorum"];
+
+/// Simulated acknowledgement latency of `node`; higher-numbered nodes are
+/// farther away.
+fn node_latency_ms(node: usize) -> f64 {
+    1.0 + 2.0 * node as f64
+}
+
+/// One simulated node's copy of the data: key → (version, record).
+#[derive(Debug, Clone, Default)]
+struct Node {
+    data: HashMap<String, (u64, Record)>,
+    /// Highest version this node has applied.
+    applied: u64,
+    failed: bool,
+}
+
+impl Node {
+    fn version_of(&self, key: &str) -> u64 {
+        self.data.get(key).map_or(0, |(v, _)| *v)
+    }
+
+    fn apply(&mut self, key: String, version: u64, record: Record) {
+        if version > self.version_of(&key) {
+            self.data.insert(key, (version, record));
+        }
+        self.applied = self.applied.max(version);
+    }
+}
+
+/// A write the primary has accepted that has not yet reached `node`.
+#[derive(Debug, Clone)]
+struct PendingWrite {
+    node: usize,
+    key: String,
+    version: u64,
+    record: Record,
+    apply_at: u64,
+}
+
+/// How many nodes acknowledged a write and how long that took.
+#[derive(Debug, Clone, Copy)]
+pub struct WriteOutcome {
+    pub acks: usize,
+    pub latency_ms: f64,
+}
+
+/// Where a read was served and whether it saw the latest write.
+#[derive(Debug, Clone)]
+pub struct ReadOutcome {
+    pub node: usize,
+    pub record: Option<Record>,
+    pub stale: bool,
+}
+
 pub struct ReplicationBlock {
     metadata: BlockMetadata,
     input_ports: Vec<Port>,
@@ -48,15 +153,41 @@ pub struct ReplicationBlock {
     metric_defs: Vec<MetricDefinition>,
 
     // Configuration
-    replication_factor: usize,
+    replica_count: usize,
     consistency_level: ConsistencyLevel,
-    async_replication: bool,
+    mode: ReplicationMode,
+    replication_lag: u64,
+    read_your_writes: bool,
+    /// W; `None` derives it from `consistency_level`.
+    write_quorum: Option<usize>,
+    /// R; `None` derives it from `consistency_level`.
+    read_quorum: Option<usize>,
+
+    // State
+    nodes: Vec<Node>,
+    pending: VecDeque<PendingWrite>,
+    /// Logical clock, advanced once per operation.
+    clock: u64,
+    next_version: u64,
+    /// Latest acknowledged version of each key.
+    latest: HashMap<String, u64>,
+    /// Clock of each client's most recent write.
+    last_write_at: HashMap<String, u64>,
+    next_read_node: usize,
+    /// Current primary in the leader-based modes.
+    primary: usize,
 
     // Stats
     writes_replicated: usize,
     acks_received: usize,
     consistency_met: usize,
     consistency_violations: usize,
+    reads: usize,
+    stale_reads: usize,
+    primary_reads: usize,
+    failovers: usize,
+    lost_writes: usize,
+    total_write_latency_ms: f64,
 }
 
 impl ReplicationBlock {
@@ -67,13 +198,31 @@ pub fn new() -> Self {
             output_ports: Self::build_outputs(),
             params: Self::build_parameters(),
             metric_defs: Self::build_metrics(),
-            replication_factor: 3,
+            replica_count: 3,
             consistency_level: ConsistencyLevel::Quorum,
-            async_replication: false,
+            mode: ReplicationMode::Sync,
+            replication_lag: 3,
+            read_your_writes: false,
+            write_quorum: None,
+            read_quorum: None,
+            nodes: vec![Node::default(); 3],
+            pending: VecDeque::new(),
+            clock: 0,
+            next_version: 1,
+            latest: HashMap::new(),
+            last_write_at: HashMap::new(),
+            next_read_node: 0,
+            primary: 0,
             writes_replicated: 0,
             acks_received: 0,
             consistency_met: 0,
             consistency_violations: 0,
+            reads: 0,
+            stale_reads: 0,
+            primary_reads: 0,
+            failovers: 0,
+            lost_writes: 0,
+            total_write_latency_ms: 0.0,
         }
     }
 
@@ -111,12 +260,29 @@ fn build_metadata() -> BlockMetadata {
                            4. If timeout before required acks: return FAILURE\n\n\
                            ASYNC MODE:\n  \
                            1. Write to primary replica, ack immediately\n  \
-                           2. Propagate to other replicas in background\n  \
+                           2. Propagate to other replicas after replication_lag operations\n  \
                            3. Risk: if primary fails before propagation, data is lost\n\n\
+                           SEMI-SYNC MODE:\n  \
+                           1. Write to primary and one replica, then ack\n  \
+                           2. Propagate to the remaining replicas asynchronously\n\n\
+                           QUORUM MODE (N replicas, W write quorum, R read quorum):\n  \
+                           WRITE: send to all N, succeed once the W fastest have applied it\n  \
+                           READ:  ask R nodes, return the highest version seen\n  \
+                           R + W > N: every read quorum overlaps every write quorum\n\n\
+                           REPLICA READ(key, client):\n  \
+                           1. If read_your_writes and client wrote within replication_lag ops:\n     \
+                              serve from primary\n  \
+                           2. Else serve from the next replica (round-robin)\n  \
+                           3. stale if replica's version < latest acknowledged version\n\n\
                            READ(key, consistency_level):\n  \
                            1. Read from R replicas (or subset based on CL)\n  \
                            2. If QUORUM reads + QUORUM writes: R+W > N guarantees\n     \
-                              at least one replica has the latest write (linearizable)"
+                              at least one replica has the latest write (linearizable)\n\n\
+                           FAILOVER (next write after the primary fails):\n  \
+                           1. Elect the live node with the highest applied version\n  \
+                           2. Writes still in flight to it are lost (lost_writes)\n  \
+                           3. Other replicas re-follow the new primary\n  \
+                           4. A recovered node rejoins as a follower and catches up"
                     .into(),
                 complexity: Complexity {
                     time: "O(R) per write where R is replication factor; quorum latency = max of fastest ⌊R/2⌋+1 replicas".into(),
@@ -155,29 +321,63 @@ fn build_metadata() -> BlockMetadata {
                              latency (waiting for acks), and the complexity of keeping replicas in sync."
                     .into(),
                 parameter_guide: HashMap::from([
-                    ("replication_factor".into(),
-                     "The number of copies of each piece of data maintained across the cluster. A replication \
-                      factor of 3 means every record exists on 3 different nodes. Higher values provide \
+                    ("replica_count".into(),
+                     "The number of copies of each piece of data maintained across the cluster (the \
+                      replication factor). A value of 3 means every record exists on 3 different nodes: \
+                      the primary (node 0) and two replicas. Higher values provide \
                       better durability (can survive more simultaneous node failures) but use proportionally \
                       more storage and increase write latency. Industry standard is 3 for most production \
                       systems (Cassandra, DynamoDB, MongoDB). A factor of 5 or 7 is used for critical data \
-                      or systems spanning multiple data centers. Recommended: 3 for most workloads."
+                      or systems spanning multiple data centers. Recommended: 3 for most workloads. (The \
+                      older name replication_factor is still accepted.)"
                         .into()),
                     ("consistency_level".into(),
                      "Determines how many replicas must acknowledge a write before it is considered \
                       successful. 'one' gives lowest latency but risks stale reads and data loss. 'quorum' \
                       (majority) provides a good balance — with RF=3, quorum requires 2 acks. 'all' provides \
                       strongest consistency but any single slow or failed replica blocks the entire write. \
-                      The key insight: if quorum_reads + quorum_writes > replication_factor, you get \
+                      The key insight: if quorum_reads + quorum_writes > replica_count, you get \
                       linearizability (strong consistency). Recommended: 'quorum' for most use cases."
                         .into()),
-                    ("async_replication".into(),
-                     "When enabled, the write is acknowledged after reaching only the primary replica, and \
-                      other replicas receive the data asynchronously in the background. This minimizes write \
-                      latency but introduces a window where data exists on only one node — if that node \
-                      fails before propagation completes, the data is lost. PostgreSQL's synchronous_commit=off \
-                      is similar. Recommended: false (synchronous) for critical data, true for high-throughput \
-                      scenarios where some data loss is acceptable (e.g., logging, analytics ingestion)."
+                    ("replication_mode".into(),
+                     "When a write is acknowledged. 'sync' waits until every replica has applied it, so \
+                      replica reads are never stale but every write pays for the slowest replica. 'async' \
+                      acknowledges once the primary has it and ships it to replicas in the background: \
+                      writes are fast, but for replication_lag operations replicas serve stale data, and \
+                      a primary failure in that window loses the write. 'semi_sync' (MySQL's semi-synchronous \
+                      replication) waits for one replica, so an acknowledged write survives a primary \
+                      failure while the rest still lag. Recommended: sync or semi_sync for critical data, \
+                      async for high-throughput scenarios where some staleness is acceptable. 'quorum' \
+                      drops the primary altogether (Dynamo, Cassandra, Riak): any node takes writes, and \
+                      write_quorum / read_quorum decide how many must answer. (The older \
+                      async_replication flag is still accepted.)"
+                        .into()),
+                    ("write_quorum".into(),
+                     "W — how many nodes must apply a write before it succeeds. In quorum mode the write \
+                      waits for the W fastest nodes, so quorum_write_latency grows with W; in the other \
+                      modes a write acknowledged by fewer than W nodes counts as a consistency violation. \
+                      0 derives W from consistency_level (one = 1, quorum = majority, all = N)."
+                        .into()),
+                    ("read_quorum".into(),
+                     "R — how many nodes a quorum-mode read asks before returning the newest version it \
+                      saw. If R + W > N, every read set overlaps every write set, so reads never miss an \
+                      acknowledged write; with R + W <= N (e.g., N=3, W=1, R=1) reads can be stale. \
+                      Cassandra's QUORUM/QUORUM and DynamoDB's strongly consistent reads rely on this. \
+                      0 derives R from consistency_level."
+                        .into()),
+                    ("replication_lag".into(),
+                     "How many operations pass before an asynchronously replicated write reaches a replica. \
+                      Real lag is measured in milliseconds to seconds and grows under load; counting \
+                      operations keeps the simulation deterministic. The larger the lag, the more replica \
+                      reads return stale data (see stale_reads). Default: 3."
+                        .into()),
+                    ("read_your_writes".into(),
+                     "When enabled, a client that wrote within the last replication_lag operations reads \
+                      from the primary instead of a replica, so it always sees its own writes. Other \
+                      clients can still read stale data. This is the 'read from the leader for a while \
+                      after updating' technique from Kleppmann's DDIA; MongoDB's causally consistent \
+                      sessions achieve the same guarantee. Costs: extra load on the primary \
+                      (primary_reads)."
                         .into()),
                 ]),
                 alternatives: vec![
@@ -213,12 +413,12 @@ fn build_metadata() -> BlockMetadata {
     fn build_inputs() -> Vec<Port> {
         vec![Port {
             id: "requests".into(),
-            name: "Write Requests".into(),
+            name: "Requests".into(),
             port_type: PortType::DataStream,
             direction: PortDirection::Input,
             required: true,
             multiple: false,
-            description: "Records to replicate across nodes".into(),
+            description: "Writes to replicate, keyed by `_key`. Records with `_op: \"read\"` are reads.".into(),
             schema: None,
         }]
     }
@@ -233,14 +433,24 @@ fn build_outputs() -> Vec<Port> {
             multiple: true,
             description: "Records enriched with `_replicas` and `_acks` fields".into(),
             schema: None,
+        },
+        Port {
+            id: "reads".into(),
+            name: "Read Results".into(),
+            port_type: PortType::DataStream,
+            direction: PortDirection::Output,
+            required: false,
+            multiple: true,
+            description: "Values returned by reads, with `_served_by` and `_stale` fields".into(),
+            schema: None,
         }]
     }
 
     fn build_parameters() -> Vec<Parameter> {
         vec![
             Parameter {
-                id: "replication_factor".into(),
-                name: "Replication Factor".into(),
+                id: "replica_count".into(),
+                name: "Replica Count".into(),
                 param_type: ParameterType::Number,
                 description: "Number of copies to maintain (typical: 3)".into(),
                 default_value: ParameterValue::Integer(3),
@@ -255,20 +465,74 @@ fn build_parameters() -> Vec<Parameter> {
             Parameter {
                 id: "consistency_level".into(),
                 name: "Consistency Level".into(),
-                param_type: ParameterType::String,
+                param_type: ParameterType::Enum,
                 description: "How many replicas must ack: one, quorum, or all".into(),
                 default_value: ParameterValue::String("quorum".into()),
                 required: false,
-                constraints: None,
+                constraints: Some(ParameterConstraints::new().with_choices(CONSISTENCY_LEVELS)),
                 ui_hint: Some(
                     ParameterUIHint::new(WidgetType::Select),
                 ),
             },
             Parameter {
-                id: "async_replication".into(),
-                name: "Async Mode".into(),
+                id: "replication_mode".into(),
+                name: "Replication Mode".into(),
+                param_type: ParameterType::Enum,
+                description: "When writes are acknowledged: sync, semi_sync, async, or quorum".into(),
+                default_value: ParameterValue::String("sync".into()),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_choices(REPLICATION_MODES)),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Select),
+                ),
+            },
+            Parameter {
+                id: "replication_lag".into(),
+                name: "Replication Lag".into(),
+                param_type: ParameterType::Number,
+                description: "Operations before an async write reaches the replicas".into(),
+                default_value: ParameterValue::Integer(3),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(0.0).with_max(100.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider).with_step(1.0),
+                ),
+            },
+            Parameter {
+                id: "write_quorum".into(),
+                name: "Write Quorum (W)".into(),
+                param_type: ParameterType::Number,
+                description: "Nodes that must apply a write (0 = from consistency level)".into(),
+                default_value: ParameterValue::Integer(0),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(0.0).with_max(7.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider).with_step(1.0),
+                ),
+            },
+            Parameter {
+                id: "read_quorum".into(),
+                name: "Read Quorum (R)".into(),
+                param_type: ParameterType::Number,
+                description: "Nodes a quorum read consults (0 = from consistency level)".into(),
+                default_value: ParameterValue::Integer(0),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(0.0).with_max(7.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider).with_step(1.0),
+                ),
+            },
+            Parameter {
+                id: "read_your_writes".into(),
+                name: "Read Your Writes".into(),
                 param_type: ParameterType::Boolean,
-                description: "If true, ack after writing to one replica (lower latency, risk of data loss)".into(),
+                description: "Route a client's reads to the primary right after it writes".into(),
                 default_value: ParameterValue::Boolean(false),
                 required: false,
                 constraints: None,
@@ -321,23 +585,289 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Writes that didn't meet consistency (in simulation)".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "reads".into(),
+                name: "Reads".into(),
+                metric_type: MetricType::Counter,
+                unit: "reads".into(),
+                description: "Read requests served".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "stale_reads".into(),
+                name: "Stale Reads".into(),
+                metric_type: MetricType::Counter,
+                unit: "reads".into(),
+                description: "Reads that returned an older version than the latest acknowledged write".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "primary_reads".into(),
+                name: "Primary Reads".into(),
+                metric_type: MetricType::Counter,
+                unit: "reads".into(),
+                description: "Reads routed to the primary to guarantee read-your-writes".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "quorum_write_latency".into(),
+                name: "Quorum Write Latency".into(),
+                metric_type: MetricType::Gauge,
+                unit: "ms".into(),
+                description: "Simulated time until a write received the acknowledgements it waits for".into(),
+                aggregations: vec![AggregationType::Avg, AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "failed_nodes".into(),
+                name: "Failed Nodes".into(),
+                metric_type: MetricType::Gauge,
+                unit: "nodes".into(),
+                description: "Nodes currently failed".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "failovers".into(),
+                name: "Failovers".into(),
+                metric_type: MetricType::Counter,
+                unit: "elections".into(),
+                description: "Times a replica was promoted after the primary failed".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "lost_writes".into(),
+                name: "Lost Writes".into(),
+                metric_type: MetricType::Counter,
+                unit: "writes".into(),
+                description: "Acknowledged writes that never reached the newly elected primary".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
         ]
     }
 
-    fn required_acks(&self) -> usize {
+    fn level_acks(&self) -> usize {
         match self.consistency_level {
             ConsistencyLevel::One => 1,
-            ConsistencyLevel::Quorum => self.replication_factor / 2 + 1,
-            ConsistencyLevel::All => self.replication_factor,
+            ConsistencyLevel::Quorum => self.replica_count / 2 + 1,
+            ConsistencyLevel::All => self.replica_count,
         }
     }
 
+    /// W: acknowledgements a write needs.
+    fn required_acks(&self) -> usize {
+        self.write_quorum.unwrap_or_else(|| self.level_acks())
+    }
+
+    /// R: nodes a quorum-mode read consults.
+    fn read_quorum(&self) -> usize {
+        self.read_quorum.unwrap_or_else(|| self.level_acks())
+    }
+
     fn simulated_lag(&self) -> f64 {
-        if self.async_replication {
-            // Simulate ~5ms lag per extra replica.
-            (self.replication_factor as f64 - 1.0) * 5.0
+        // Simulate ~5ms lag per replica that is updated in the background.
+        let background = self.replica_count.saturating_sub(self.sync_nodes());
+        background as f64 * 5.0
+    }
+
+    /// Nodes (primary included) that apply a write before it is acknowledged.
+    fn sync_nodes(&self) -> usize {
+        match self.mode {
+            ReplicationMode::Sync => self.replica_count,
+            ReplicationMode::SemiSync => self.replica_count.min(2),
+            ReplicationMode::Async => 1,
+            ReplicationMode::Quorum => self.required_acks(),
+        }
+    }
+
+    /// Advances the logical clock and delivers replication that is now due.
+    fn tick(&mut self) {
+        self.clock += 1;
+        // A failed primary cannot ship its log; delivery resumes if it recovers.
+        if self.is_leader_based() && self.nodes[self.primary].failed {
+            return;
+        }
+        while self.pending.front().is_some_and(|p| p.apply_at <= self.clock) {
+            if let Some(p) = self.pending.pop_front() {
+                let node = &mut self.nodes[p.node];
+                if !node.failed {
+                    node.apply(p.key, p.version, p.record);
+                }
+            }
+        }
+    }
+
+    fn is_leader_based(&self) -> bool {
+        self.mode != ReplicationMode::Quorum
+    }
+
+    fn live_nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.replica_count).filter(|&n| !self.nodes[n].failed)
+    }
+
+    fn failed_nodes(&self) -> usize {
+        self.nodes.iter().filter(|n| n.failed).count()
+    }
+
+    fn check_node(&self, node: usize) -> Result<(), BlockError> {
+        if node >= self.replica_count {
+            return Err(BlockError::InvalidInput(format!(
+                "node {} does not exist (replica_count is {})",
+                node, self.replica_count
+            )));
+        }
+        Ok(())
+    }
+
+    /// Marks `node` as failed. It stops receiving writes and serving reads.
+    pub fn fail_node(&mut self, node: usize) -> Result<(), BlockError> {
+        self.check_node(node)?;
+        self.nodes[node].failed = true;
+        Ok(())
+    }
+
+    /// Brings `node` back. In the leader-based modes it rejoins as a follower
+    /// and copies the primary's data and log position, discarding anything it
+    /// had that the primary does not; in quorum mode it keeps its (possibly
+    /// stale) data.
+    pub fn recover_node(&mut self, node: usize) -> Result<(), BlockError> {
+        self.check_node(node)?;
+        self.nodes[node].failed = false;
+        if self.is_leader_based() && node != self.primary && !self.nodes[self.primary].failed {
+            self.nodes[node].data = self.nodes[self.primary].data.clone();
+            self.nodes[node].applied = self.nodes[self.primary].applied;
+        }
+        Ok(())
+    }
+
+    /// Elects the most up-to-date live node as primary. Writes the old
+    /// primary acknowledged but never shipped to the new one are lost.
+    fn failover(&mut self) -> Result<(), BlockError> {
+        let new_primary = self
+            .live_nodes()
+            .max_by_key(|&n| (self.nodes[n].applied, std::cmp::Reverse(n)))
+            .ok_or_else(|| BlockError::ExecutionError("all replicas have failed".into()))?;
+
+        let lost = self.pending.iter().filter(|p| p.node == new_primary).count();
+        self.pending.clear();
+        self.lost_writes += lost;
+        self.failovers += 1;
+        self.primary = new_primary;
+
+        // History is now whatever the new primary has.
+        let survivor = self.nodes[new_primary].clone();
+        for (key, version) in self.latest.iter_mut() {
+            *version = (*version).min(survivor.version_of(key));
+        }
+        // Remaining replicas re-follow the new primary.
+        for n in 0..self.replica_count {
+            if n != new_primary && !self.nodes[n].failed {
+                self.nodes[n].data = survivor.data.clone();
+                self.nodes[n].applied = survivor.applied;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes `record` under `key`.
+    pub fn write(&mut self, key: String, client: &str, record: Record) -> Result<WriteOutcome, BlockError> {
+        if self.is_leader_based() && self.nodes[self.primary].failed {
+            self.failover()?;
+        }
+        self.tick();
+
+        // Nodes are numbered by latency, so the synchronous set is the
+        // fastest live nodes — led by the primary, or the W fastest in
+        // quorum mode.
+        let mut candidates: Vec<usize> = Vec::with_capacity(self.replica_count);
+        if self.is_leader_based() {
+            candidates.push(self.primary);
+        }
+        candidates.extend(self.live_nodes().filter(|&n| !(self.is_leader_based() && n == self.primary)));
+        if candidates.is_empty() {
+            return Err(BlockError::ExecutionError("all replicas have failed".into()));
+        }
+        candidates.truncate(self.sync_nodes());
+
+        let version = self.next_version;
+        self.next_version += 1;
+        self.latest.insert(key.clone(), version);
+        self.last_write_at.insert(client.to_string(), self.clock);
+
+        let latency_ms = candidates.iter().map(|&n| node_latency_ms(n)).fold(0.0, f64::max);
+        self.total_write_latency_ms += latency_ms;
+        for node in 0..self.replica_count {
+            if self.nodes[node].failed {
+                continue;
+            }
+            if candidates.contains(&node) || self.replication_lag == 0 {
+                self.nodes[node].apply(key.clone(), version, record.clone());
+            } else {
+                self.pending.push_back(PendingWrite {
+                    node,
+                    key: key.clone(),
+                    version,
+                    record: record.clone(),
+                    apply_at: self.clock + self.replication_lag,
+                });
+            }
+        }
+        Ok(WriteOutcome { acks: candidates.len(), latency_ms })
+    }
+
+    /// Reads `key` on behalf of `client` from a live replica, or from the
+    /// primary when read-your-writes requires it or no replica is up.
+    pub fn read(&mut self, key: &str, client: &str) -> Result<ReadOutcome, BlockError> {
+        self.tick();
+        let wrote_recently = self
+            .last_write_at
+            .get(client)
+            .is_some_and(|&at| self.clock - at < self.replication_lag);
+
+        let latest = self.latest.get(key).copied().unwrap_or(0);
+        let live: Vec<usize> = self.live_nodes().collect();
+        if live.is_empty() {
+            return Err(BlockError::ExecutionError("all replicas have failed".into()));
+        }
+
+        if self.mode == ReplicationMode::Quorum {
+            // Ask R consecutive live nodes and keep the newest version any returns.
+            let start = self.next_read_node;
+            self.next_read_node += 1;
+            let mut node = live[start % live.len()];
+            for i in 1..self.read_quorum().min(live.len()) {
+                let candidate = live[(start + i) % live.len()];
+                if self.nodes[candidate].version_of(key) > self.nodes[node].version_of(key) {
+                    node = candidate;
+                }
+            }
+            return Ok(self.finish_read(key, node, latest));
+        }
+
+        let primary_up = !self.nodes[self.primary].failed;
+        let replicas: Vec<usize> = live.into_iter().filter(|&n| n != self.primary).collect();
+        let node = if primary_up && (replicas.is_empty() || (self.read_your_writes && wrote_recently)) {
+            if self.replica_count > 1 {
+                self.primary_reads += 1;
+            }
+            self.primary
         } else {
-            0.0
+            // Round-robin over the live replicas.
+            let node = replicas[self.next_read_node % replicas.len()];
+            self.next_read_node += 1;
+            node
+        };
+        Ok(self.finish_read(key, node, latest))
+    }
+
+    fn finish_read(&mut self, key: &str, node: usize, latest: u64) -> ReadOutcome {
+        let stale = self.nodes[node].version_of(key) < latest;
+        self.reads += 1;
+        if stale {
+            self.stale_reads += 1;
+        }
+        ReadOutcome {
+            node,
+            record: self.nodes[node].data.get(key).map(|(_, r)| r.clone()),
+            stale,
         }
     }
 }
@@ -358,34 +888,75 @@ fn metadata(&self) -> &BlockMetadata { &self.metadata }
     fn inputs(&self) -> &[Port] { &self.input_ports }
     fn outputs(&self) -> &[Port] { &self.output_ports }
     fn parameters(&self) -> &[Parameter] { &self.params }
-    fn requires(&self) -> &[Constraint] { &[] }
+    fn requires(&self) -> &[Constraint] {
+        static REQUIRES: std::sync::LazyLock<Vec<Constraint>> = std::sync::LazyLock::new(|| {
+            vec![Constraint::requires_strict_guarantee(
+                GuaranteeType::Durability,
+                "Replicas ship writes the primary has made durable; without a log, a crash loses acknowledged writes",
+            )]
+        });
+        &REQUIRES
+    }
     fn guarantees(&self) -> &[Guarantee] { &[] }
     fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
 
     async fn initialize(
         &mut self,
-        params: HashMap<String, ParameterValue>,
+        mut params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("replication_factor") {
-            self.replication_factor = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("replication_factor must be an integer".into()))?
-                as usize;
-        }
-        if let Some(val) = params.get("consistency_level") {
-            let s = val
-                .as_string()
-                .ok_or_else(|| BlockError::InvalidParameter("consistency_level must be a string".into()))?;
-            self.consistency_level = match s.to_lowercase().as_str() {
-                "one" | "1" => ConsistencyLevel::One,
-                "all" => ConsistencyLevel::All,
-                _ => ConsistencyLevel::Quorum,
+        // `replication_factor` is the parameter's former name.
+        if let Some(val) = params.remove("replication_factor") {
+            params.entry("replica_count".into()).or_insert(val);
+        }
+        self.params_validate(&params)?;
+        if let Some(n) = params.get("replica_count").and_then(ParameterValue::as_integer) {
+            self.replica_count = n as usize;
+            self.nodes = vec![Node::default(); self.replica_count];
+            self.primary = 0;
+        }
+        if let Some(s) = enum_param(&params, "consistency_level", CONSISTENCY_LEVELS)? {
+            self.consistency_level = match s {
+                "one" => ConsistencyLevel::One,
+                "quorum" => ConsistencyLevel::Quorum,
+                _ => ConsistencyLevel::All,
             };
         }
+        // Superseded by `replication_mode`, which wins if both are given.
         if let Some(val) = params.get("async_replication") {
-            self.async_replication = val
+            let is_async = val
                 .as_bool()
                 .ok_or_else(|| BlockError::InvalidParameter("async_replication must be a boolean".into()))?;
+            if is_async {
+                self.mode = ReplicationMode::Async;
+            }
+        }
+        if let Some(s) = enum_param(&params, "replication_mode", REPLICATION_MODES)? {
+            self.mode = match s {
+                "sync" => ReplicationMode::Sync,
+                "semi_sync" => ReplicationMode::SemiSync,
+                "async" => ReplicationMode::Async,
+                _ => ReplicationMode::Quorum,
+            };
+        }
+        if let Some(lag) = params.get("replication_lag").and_then(ParameterValue::as_integer) {
+            self.replication_lag = lag as u64;
+        }
+        for (name, slot) in [
+            ("write_quorum", &mut self.write_quorum),
+            ("read_quorum", &mut self.read_quorum),
+        ] {
+            if let Some(n) = params.get(name).and_then(ParameterValue::as_integer) {
+                *slot = if n > 0 { Some(n as usize) } else { None };
+            }
+        }
+        if self.required_acks() > self.replica_count || self.read_quorum() > self.replica_count {
+            return Err(BlockError::InvalidParameter(format!(
+                "write_quorum and read_quorum must not exceed replica_count ({})",
+                self.replica_count
+            )));
+        }
+        if let Some(v) = params.get("read_your_writes").and_then(ParameterValue::as_bool) {
+            self.read_your_writes = v;
         }
         Ok(())
     }
@@ -406,13 +977,56 @@ async fn execute(
 
         let required_acks = self.required_acks();
         let mut output_records = Vec::with_capacity(records.len());
+        let mut read_results = Vec::new();
+        let mut errors = Vec::new();
 
         for record in records {
-            self.writes_replicated += 1;
+            let key = record.data.get("_key").map(|v| v.to_string()).unwrap_or_default();
+            let client = record.get::<String>("_client").ok().flatten().unwrap_or_default();
+            let op = record.get::<String>("_op").ok().flatten();
+
+            if let Some(op @ ("fail_node" | "recover_node")) = op.as_deref() {
+                let node = record.get::<usize>("_node").ok().flatten().unwrap_or(0);
+                let result = if op == "fail_node" { self.fail_node(node) } else { self.recover_node(node) };
+                if let Err(e) = result {
+                    errors.push(e);
+                }
+                continue;
+            }
 
-            // Simulate: all replicas ack (in this simulation we don't model failures).
-            let acks = self.replication_factor;
+            if op.as_deref() == Some("read") {
+                let outcome = match self.read(&key, &client) {
+                    Ok(outcome) => outcome,
+                    Err(e) => {
+                        errors.push(e);
+                        continue;
+                    }
+                };
+                context.metrics.increment("reads");
+                if outcome.stale {
+                    context.metrics.increment("stale_reads");
+                }
+                let mut out = outcome.record.unwrap_or(record);
+                let _ = out.insert("_served_by".into(), outcome.node);
+                let _ = out.insert("_stale".into(), outcome.stale);
+                read_results.push(out);
+                continue;
+            }
+
+            let failovers_before = self.failovers;
+            let WriteOutcome { acks, latency_ms } = match self.write(key, &client, record.clone()) {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            if self.failovers > failovers_before {
+                context.metrics.increment("failovers");
+            }
+            self.writes_replicated += 1;
             self.acks_received += acks;
+            context.metrics.record("quorum_write_latency", latency_ms);
 
             let meets_consistency = acks >= required_acks;
             if meets_consistency {
@@ -424,7 +1038,7 @@ async fn execute(
             context.metrics.increment("writes_replicated");
 
             let mut out = record;
-            let _ = out.insert("_replicas".into(), self.replication_factor);
+            let _ = out.insert("_replicas".into(), self.replica_count);
             let _ = out.insert("_acks".into(), acks);
             let _ = out.insert("_consistency_met".into(), meets_consistency);
             output_records.push(out);
@@ -434,9 +1048,11 @@ async fn execute(
         context.metrics.record("replication_lag_ms", lag);
         context.metrics.record("consistency_met", self.consistency_met as f64);
         context.metrics.record("consistency_violations", self.consistency_violations as f64);
+        context.metrics.record("failed_nodes", self.failed_nodes() as f64);
 
         let mut outputs = HashMap::new();
         outputs.insert("replicated".into(), PortValue::Stream(output_records));
+        outputs.insert("reads".into(), PortValue::Stream(read_results));
 
         let mut metrics_summary = HashMap::new();
         metrics_summary.insert("writes_replicated".into(), self.writes_replicated as f64);
@@ -444,16 +1060,28 @@ async fn execute(
         metrics_summary.insert("replication_lag_ms".into(), lag);
         metrics_summary.insert("consistency_met".into(), self.consistency_met as f64);
         metrics_summary.insert("consistency_violations".into(), self.consistency_violations as f64);
+        metrics_summary.insert("reads".into(), self.reads as f64);
+        metrics_summary.insert("stale_reads".into(), self.stale_reads as f64);
+        metrics_summary.insert("primary_reads".into(), self.primary_reads as f64);
+        metrics_summary.insert("failed_nodes".into(), self.failed_nodes() as f64);
+        metrics_summary.insert("failovers".into(), self.failovers as f64);
+        metrics_summary.insert("lost_writes".into(), self.lost_writes as f64);
+        if self.writes_replicated > 0 {
+            metrics_summary.insert(
+                "quorum_write_latency".into(),
+                self.total_write_latency_ms / self.writes_replicated as f64,
+            );
+        }
 
         Ok(ExecutionResult {
             outputs,
             metrics: metrics_summary,
-            errors: vec![],
+            errors,
         })
     }
 
     fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
-        if let Some(input) = inputs.get("requests") {
+        let result = if let Some(input) = inputs.get("requests") {
             match input {
                 PortValue::Stream(_) | PortValue::Batch(_) | PortValue::Single(_) => ValidationResult::ok(),
                 PortValue::None => ValidationResult::ok().with_warning("No writes to replicate"),
@@ -461,18 +1089,34 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
             }
         } else {
             ValidationResult::ok().with_warning("requests input not connected")
+        };
+        if self.mode == ReplicationMode::Quorum
+            && self.read_quorum() + self.required_acks() <= self.replica_count
+        {
+            return result.with_warning(format!(
+                "R + W = {} does not exceed N = {}; quorum reads may be stale",
+                self.read_quorum() + self.required_acks(),
+                self.replica_count
+            ));
         }
+        result
     }
 
     fn get_state(&self) -> BlockState {
         let mut state = BlockState::new();
-        let _ = state.insert("replication_factor".into(), self.replication_factor);
+        let _ = state.insert("replica_count".into(), self.replica_count);
         let _ = state.insert("writes_replicated".into(), self.writes_replicated);
+        let _ = state.insert("primary".into(), self.primary);
         state
     }
 
     fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
-        if let Ok(Some(r)) = state.get::<usize>("replication_factor") { self.replication_factor = r; }
+        if let Ok(Some(r)) = state.get::<usize>("replica_count") {
+            self.replica_count = r;
+            self.nodes = vec![Node::default(); r];
+            self.pending.clear();
+            self.primary = 0;
+        }
         Ok(())
     }
 }
@@ -484,15 +1128,16 @@ fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
 
     #[test]
     fn test_quorum_calculation() {
         let mut rep = ReplicationBlock::new();
-        rep.replication_factor = 3;
+        rep.replica_count = 3;
         rep.consistency_level = ConsistencyLevel::Quorum;
         assert_eq!(rep.required_acks(), 2); // ⌊3/2⌋ + 1 = 2
 
-        rep.replication_factor = 5;
+        rep.replica_count = 5;
         assert_eq!(rep.required_acks(), 3); // ⌊5/2⌋ + 1 = 3
 
         rep.consistency_level = ConsistencyLevel::All;
@@ -505,13 +1150,271 @@ fn test_quorum_calculation() {
     #[test]
     fn test_async_lag() {
         let mut rep = ReplicationBlock::new();
-        rep.replication_factor = 3;
+        rep.replica_count = 3;
 
-        rep.async_replication = false;
+        rep.mode = ReplicationMode::Sync;
         assert_eq!(rep.simulated_lag(), 0.0);
 
-        rep.async_replication = true;
+        rep.mode = ReplicationMode::Async;
         assert_eq!(rep.simulated_lag(), 10.0); // (3-1) * 5ms
+
+        rep.mode = ReplicationMode::SemiSync;
+        assert_eq!(rep.simulated_lag(), 5.0);
+    }
+
+    fn op(op: &str, key: u64, client: &str) -> Record {
+        let mut r = Record::new();
+        let _ = r.insert("_op".into(), op);
+        let _ = r.insert("_key".into(), key);
+        let _ = r.insert("_client".into(), client);
+        r
+    }
+
+    async fn run(rep: &mut ReplicationBlock, records: Vec<Record>) -> ExecutionResult {
+        let mut inputs = HashMap::new();
+        inputs.insert("requests".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        rep.execute(ctx).await.unwrap()
+    }
+
+    async fn replication(mode: &str, read_your_writes: bool) -> ReplicationBlock {
+        let mut rep = ReplicationBlock::new();
+        let mut params = HashMap::new();
+        params.insert("replication_mode".into(), ParameterValue::String(mode.into()));
+        params.insert("replication_lag".into(), ParameterValue::Integer(3));
+        params.insert("read_your_writes".into(), ParameterValue::Boolean(read_your_writes));
+        rep.initialize(params).await.unwrap();
+        rep
+    }
+
+    #[tokio::test]
+    async fn test_async_reads_are_stale_until_lag_passes() {
+        let mut rep = replication("async", false).await;
+        // Write at t=1; replicas apply it at t=4.
+        let workload = vec![
+            op("write", 1, "a"),
+            op("read", 1, "b"), // t=2, stale
+            op("read", 1, "b"), // t=3, stale
+            op("read", 1, "b"), // t=4, fresh
+        ];
+        let result = run(&mut rep, workload).await;
+        assert_eq!(result.metrics["stale_reads"], 2.0);
+        assert_eq!(result.metrics["consistency_violations"], 1.0, "async acks with one node");
+
+        let Some(PortValue::Stream(reads)) = result.outputs.get("reads") else {
+            panic!("expected stream");
+        };
+        let stale: Vec<bool> = reads.iter().map(|r| r.get("_stale").unwrap().unwrap()).collect();
+        assert_eq!(stale, vec![true, true, false]);
+    }
+
+    #[tokio::test]
+    async fn test_sync_and_semi_sync_acks() {
+        let mut sync = replication("sync", false).await;
+        let workload = vec![op("write", 1, "a"), op("read", 1, "b"), op("read", 1, "b")];
+        let result = run(&mut sync, workload.clone()).await;
+        assert_eq!(result.metrics["stale_reads"], 0.0);
+        assert_eq!(result.metrics["acks_received"], 3.0);
+
+        // Semi-sync: replica 1 has the write, replica 2 does not yet.
+        let mut semi = replication("semi_sync", false).await;
+        let result = run(&mut semi, workload).await;
+        assert_eq!(result.metrics["acks_received"], 2.0);
+        assert_eq!(result.metrics["consistency_met"], 1.0);
+        assert_eq!(result.metrics["stale_reads"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_read_your_writes_routes_writer_to_primary() {
+        let mut rep = replication("async", true).await;
+        let workload = vec![
+            op("write", 1, "a"),
+            op("read", 1, "a"), // writer: primary, fresh
+            op("read", 1, "b"), // other client: replica, stale
+        ];
+        let result = run(&mut rep, workload).await;
+        assert_eq!(result.metrics["primary_reads"], 1.0);
+        assert_eq!(result.metrics["stale_reads"], 1.0);
+
+        let Some(PortValue::Stream(reads)) = result.outputs.get("reads") else {
+            panic!("expected stream");
+        };
+        assert_eq!(reads[0].get::<usize>("_served_by").unwrap(), Some(0));
+        assert!(!reads[0].get::<bool>("_stale").unwrap().unwrap());
+    }
+
+    async fn quorum(n: i64, w: i64, r: i64) -> ReplicationBlock {
+        let mut rep = ReplicationBlock::new();
+        let mut params = HashMap::new();
+        params.insert("replication_mode".into(), ParameterValue::String("quorum".into()));
+        params.insert("replica_count".into(), ParameterValue::Integer(n));
+        params.insert("write_quorum".into(), ParameterValue::Integer(w));
+        params.insert("read_quorum".into(), ParameterValue::Integer(r));
+        params.insert("replication_lag".into(), ParameterValue::Integer(10));
+        rep.initialize(params).await.unwrap();
+        rep
+    }
+
+    /// Alternating writes and reads of a few keys.
+    fn quorum_workload() -> Vec<Record> {
+        (0..30u64)
+            .flat_map(|i| [op("write", i % 3, "a"), op("read", i % 3, "b")])
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_quorum_overlap_prevents_stale_reads() {
+        let mut weak = quorum(3, 1, 1).await;
+        let result = run(&mut weak, quorum_workload()).await;
+        assert!(result.metrics["stale_reads"] > 0.0, "R + W <= N should allow stale reads");
+        assert!(!weak.validate(&HashMap::new()).warnings.is_empty());
+
+        let mut strong = quorum(3, 2, 2).await;
+        let result = run(&mut strong, quorum_workload()).await;
+        assert_eq!(result.metrics["stale_reads"], 0.0, "R + W > N must never be stale");
+        assert_eq!(result.metrics["reads"], 30.0);
+    }
+
+    #[tokio::test]
+    async fn test_quorum_write_latency_grows_with_w() {
+        let mut w1 = quorum(3, 1, 3).await;
+        let mut w3 = quorum(3, 3, 1).await;
+        let fast = run(&mut w1, quorum_workload()).await.metrics["quorum_write_latency"];
+        let slow = run(&mut w3, quorum_workload()).await.metrics["quorum_write_latency"];
+        assert_eq!(fast, 1.0);
+        assert_eq!(slow, 5.0);
+    }
+
+    #[tokio::test]
+    async fn test_quorum_larger_than_replicas_is_rejected() {
+        let mut rep = ReplicationBlock::new();
+        let mut params = HashMap::new();
+        params.insert("write_quorum".into(), ParameterValue::Integer(4));
+        assert!(rep.initialize(params).await.is_err());
+    }
+
+    fn node_op(op: &str, node: usize) -> Record {
+        let mut r = Record::new();
+        let _ = r.insert("_op".into(), op);
+        let _ = r.insert("_node".into(), node);
+        r
+    }
+
+    /// Write key 1 and let it replicate, write key 2, then fail the primary
+    /// before key 2 reaches the replicas and write key 3.
+    fn failover_workload() -> Vec<Record> {
+        vec![
+            op("write", 1, "a"),
+            op("read", 1, "b"),
+            op("read", 1, "b"),
+            op("read", 1, "b"),
+            op("write", 2, "a"),
+            node_op("fail_node", 0),
+            op("write", 3, "a"),
+            op("read", 2, "b"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_async_failover_loses_unreplicated_writes() {
+        let mut rep = replication("async", false).await;
+        let result = run(&mut rep, failover_workload()).await;
+        assert!(result.errors.is_empty());
+        assert_eq!(result.metrics["failovers"], 1.0);
+        assert_eq!(result.metrics["lost_writes"], 1.0);
+        assert_eq!(result.metrics["failed_nodes"], 1.0);
+        assert_eq!(rep.primary, 1);
+
+        // Key 2 is gone for good: the read finds nothing, and it is not stale
+        // because the surviving history never had it.
+        let Some(PortValue::Stream(reads)) = result.outputs.get("reads") else {
+            panic!("expected stream");
+        };
+        let last = reads.last().unwrap();
+        assert_eq!(last.get::<usize>("_served_by").unwrap(), Some(2));
+        assert!(!last.get::<bool>("_stale").unwrap().unwrap());
+        assert!(!last.data.contains_key("_replicas"));
+    }
+
+    #[tokio::test]
+    async fn test_semi_sync_failover_keeps_acknowledged_writes() {
+        let mut rep = replication("semi_sync", false).await;
+        let result = run(&mut rep, failover_workload()).await;
+        assert_eq!(result.metrics["failovers"], 1.0);
+        assert_eq!(result.metrics["lost_writes"], 0.0);
+        // Node 1 was in every write's synchronous set, so it wins the election.
+        assert_eq!(rep.primary, 1);
+    }
+
+    #[tokio::test]
+    async fn test_recovered_node_resyncs_log_position() {
+        let mut rep = replication("async", false).await;
+        let mut workload = failover_workload();
+        workload.extend([
+            op("read", 1, "b"),
+            op("read", 1, "b"), // Key 3 reaches node 2.
+            node_op("recover_node", 0),
+            node_op("fail_node", 1),
+            op("write", 4, "a"),
+        ]);
+        let result = run(&mut rep, workload).await;
+        assert!(result.errors.is_empty());
+
+        // Node 0 dropped its unshipped key 2 and took node 1's position, so
+        // it is as current as node 2 and wins the tie for the next election.
+        assert_eq!(result.metrics["failovers"], 2.0);
+        assert_eq!(rep.primary, 0);
+        assert_eq!(rep.nodes[0].applied, 4);
+    }
+
+    #[tokio::test]
+    async fn test_reads_route_around_failed_nodes() {
+        let mut rep = replication("sync", false).await;
+        let workload = vec![
+            op("write", 1, "a"),
+            node_op("fail_node", 1),
+            op("read", 1, "b"),
+            op("read", 1, "b"),
+            node_op("fail_node", 2),
+            op("read", 1, "b"),
+            node_op("recover_node", 1),
+            op("read", 1, "b"),
+        ];
+        let result = run(&mut rep, workload).await;
+        let Some(PortValue::Stream(reads)) = result.outputs.get("reads") else {
+            panic!("expected stream");
+        };
+        let served: Vec<usize> = reads.iter().map(|r| r.get("_served_by").unwrap().unwrap()).collect();
+        assert_eq!(served, vec![2, 2, 0, 1]);
+        assert_eq!(result.metrics["stale_reads"], 0.0, "recovered node catches up");
+        assert_eq!(result.metrics["failed_nodes"], 1.0);
+
+        let mut bad = vec![node_op("fail_node", 7)];
+        bad.extend((0..3).map(|n| node_op("fail_node", n)));
+        bad.push(op("write", 1, "a"));
+        let result = run(&mut rep, bad).await;
+        assert_eq!(result.errors.len(), 2, "unknown node and no live replicas");
+    }
+
+    #[tokio::test]
+    async fn test_legacy_parameters() {
+        let mut rep = ReplicationBlock::new();
+        let mut params = HashMap::new();
+        params.insert("replication_factor".into(), ParameterValue::Integer(5));
+        params.insert("async_replication".into(), ParameterValue::Boolean(true));
+        rep.initialize(params).await.unwrap();
+        assert_eq!(rep.replica_count, 5);
+        assert_eq!(rep.mode, ReplicationMode::Async);
+
+        let mut bad = HashMap::new();
+        bad.insert("replication_mode".into(), ParameterValue::String("eventual".into()));
+        assert!(rep.initialize(bad).await.is_err());
     }
 
     #[test]
@@ -0,0 +1,539 @@
+//! Access Path Selection Block
+//!
+//! Chooses between an index scan and a sequential scan for a predicate, the
+//! way a cost-based optimizer does, and reports whether the choice paid off.
+//!
+//! ## How it works
+//!
+//! The block estimates the predicate's selectivity from the column
+//! statistics on its optional `statistics` port (falling back to fixed
+//! guesses without them, as [`FilterExpr::estimate_selectivity`] does) and
+//! prices both paths with a PostgreSQL-style I/O cost model:
+//!
+//! - a sequential scan reads every page once, in order, at a cost of 1 per
+//!   page;
+//! - an index scan fetches each matching row's page at random, at a cost of
+//!   `random_page_cost` per row — the index order is assumed uncorrelated
+//!   with the heap order, so neighbouring matches rarely share a fetch.
+//!
+//! The index scan is cheaper below the *crossover selectivity*
+//! `pages / (rows × random_page_cost)`; above it the sequential scan wins.
+//! In `auto` mode the block takes whichever path the estimate says is
+//! cheaper, then runs it and prices the other path with the rows that
+//! actually matched. When `alternative_io_cost` comes out below `io_cost`,
+//! the estimate picked the wrong side of the crossover. The latest
+//! statistics are kept across calls; each call chooses afresh.
+//!
+//! Pages come from each record's `_page_id` field, or from its position
+//! and `records_per_page` when the field is absent.
+//!
+//! ## Metrics tracked
+//!
+//! | Metric | Type | Description |
+//! |--------|------|-------------|
+//! | `chosen_path` | Gauge | 0 = sequential scan, 1 = index scan |
+//! | `estimated_selectivity` | Gauge | Fraction of rows the statistics predict will match |
+//! | `observed_selectivity` | Gauge | Fraction of rows that matched |
+//! | `crossover_selectivity` | Gauge | Selectivity at which both paths cost the same |
+//! | `estimated_rows` | Counter | Rows the statistics predict will match |
+//! | `actual_rows` | Counter | Rows that matched |
+//! | `pages_read` | Counter | Distinct pages the chosen path read |
+//! | `random_ios` | Counter | Random page fetches (index path only) |
+//! | `io_cost` | Gauge | I/O cost of the chosen path |
+//! | `alternative_io_cost` | Gauge | I/O cost the other path would have had |
+
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+
+use crate::categories::execution::filter::FilterExpr;
+use crate::categories::optimization::statistics_collector::ColumnStats;
+use crate::core::block::{
+    Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
+    Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
+};
+use crate::core::constraint::{Constraint, Guarantee};
+use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
+use crate::core::parameter::{
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    ValidationResult, WidgetType,
+};
+use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
+
+/// Which scan the block runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccessPath {
+    /// Choose by estimated cost.
+    Auto,
+    /// Always scan the whole table.
+    Sequential,
+    /// Always fetch matches through the index.
+    Index,
+}
+
+/// Values accepted by the `access_path` parameter.
+const ACCESS_PATHS: &[&str] = &["auto", "sequential", "index"];
+
+pub struct AccessPathBlock {
+    metadata: BlockMetadata,
+    input_ports: Vec<Port>,
+    output_ports: Vec<Port>,
+    params: Vec<Parameter>,
+    metric_defs: Vec<MetricDefinition>,
+
+    predicate: FilterExpr,
+    access_path: AccessPath,
+    random_page_cost: f64,
+    records_per_page: usize,
+    /// Column statistics from the `statistics` input, kept across calls.
+    stats: HashMap<String, ColumnStats>,
+}
+
+impl AccessPathBlock {
+    pub fn new() -> Self {
+        Self {
+            metadata: Self::build_metadata(),
+            input_ports: Self::build_inputs(),
+            output_ports: Self::build_outputs(),
+            params: Self::build_parameters(),
+            metric_defs: Self::build_metrics(),
+            predicate: FilterExpr::IsNull("id".into()),
+            access_path: AccessPath::Auto,
+            random_page_cost: 4.0,
+            records_per_page: 100,
+            stats: HashMap::new(),
+        }
+    }
+
+    fn build_metadata() -> BlockMetadata {
+        BlockMetadata {
+            id: "access-path".into(),
+            name: "Access Path".into(),
+            category: BlockCategory::Execution,
+            description: "Picks an index or sequential scan from estimated selectivity".into(),
+            version: "1.0.0".into(),
+            documentation: BlockDocumentation {
+                overview: "Given a WHERE clause, a query optimizer has to decide how to read \
+                           the table: walk an index to the matching rows, or read every page \
+                           and test each row. Neither always wins. An index scan touches only \
+                           the rows that match, but each fetch is a random read. A sequential \
+                           scan touches everything, but reads it in order, which disks and \
+                           operating systems make several times cheaper per page.\n\n\
+                           The break-even point is the crossover selectivity. Below it the \
+                           index scan is cheaper; above it the sequential scan is. With 100 \
+                           rows per page and random reads costing 4× sequential ones, the \
+                           crossover is at 0.25% of the table — far lower than most people \
+                           guess.\n\n\
+                           The optimizer only knows the selectivity from statistics, so its \
+                           choice is only as good as its estimate. This block makes the choice \
+                           from a statistics collector's estimate, runs the chosen path, and \
+                           reports what both paths actually cost."
+                    .into(),
+                algorithm: "Access Path Selection:\n\
+                            \n\
+                            FUNCTION scan(table, predicate, stats):\n  \
+                              est_rows = estimate_selectivity(predicate, stats) × table.rows\n  \
+                              seq_cost = table.pages × 1.0\n  \
+                              index_cost = est_rows × random_page_cost\n  \
+                              IF index_cost < seq_cost:\n    \
+                                FOR EACH tid IN index.lookup(predicate):\n      \
+                                  emit(fetch_page(tid.page)[tid.slot])   // random I/O\n  \
+                              ELSE:\n    \
+                                FOR EACH page IN table:                  // sequential I/O\n      \
+                                  FOR EACH row IN page:\n        \
+                                    IF predicate(row): emit(row)"
+                    .into(),
+                complexity: Complexity {
+                    time: "O(n) to evaluate the predicate; I/O O(pages) sequential or O(k) random"
+                        .into(),
+                    space: "O(k) for k matching rows".into(),
+                },
+                use_cases: vec![
+                    "Showing where an index stops paying off as a predicate widens".into(),
+                    "Seeing how a stale or missing statistic leads to the wrong scan".into(),
+                    "Comparing correlated predicates, which independence-based estimates \
+                     underestimate"
+                        .into(),
+                ],
+                tradeoffs: vec![
+                    "A cost model is only as good as its selectivity estimate — \
+                     underestimates push the optimizer into slow index scans"
+                        .into(),
+                    "Raising random_page_cost (spinning disks) lowers the crossover; SSDs, \
+                     where random reads are nearly as cheap, raise it"
+                        .into(),
+                    "This model charges one random fetch per matching row; a clustered index or \
+                     a bitmap heap scan would fetch each page once"
+                        .into(),
+                ],
+                examples: vec![
+                    "PostgreSQL chooses between Seq Scan, Index Scan and Bitmap Heap Scan using \
+                     seq_page_cost, random_page_cost and pg_statistic"
+                        .into(),
+                    "MySQL's optimizer ignores an index when it expects to read more than \
+                     roughly a fifth to a third of the table"
+                        .into(),
+                ],
+                motivation: "The crate has index scans, sequential scans and a statistics \
+                             collector, but a real database never lets the user pick the \
+                             scan: the optimizer does, from estimates. This block closes that \
+                             loop, so the crossover point and the cost of a bad estimate can \
+                             be seen rather than read about."
+                    .into(),
+                parameter_guide: HashMap::from([
+                    ("predicate".into(), "The WHERE clause to scan for, with the same syntax as \
+                                          the Filter block: comparisons combined with AND, OR \
+                                          and NOT. Its selectivity is estimated from the \
+                                          statistics input.".into()),
+                    ("access_path".into(), "auto picks the cheaper path by estimated cost. \
+                                            sequential and index force one path, so the two \
+                                            can be compared on the same data; the cost of the \
+                                            other path is still reported.".into()),
+                    ("random_page_cost".into(), "Cost of one random page fetch relative to a \
+                                                 sequential one. PostgreSQL's default is 4; \
+                                                 on SSDs 1.1-1.5 is typical. Higher values \
+                                                 make index scans look more expensive and move \
+                                                 the crossover lower.".into()),
+                    ("records_per_page".into(), "Rows per page when records carry no \
+                                                 `_page_id`. More rows per page makes a \
+                                                 sequential scan cheaper per row, so the \
+                                                 crossover moves lower.".into()),
+                ]),
+                alternatives: vec![
+                    Alternative {
+                        block_type: "index-scan".into(),
+                        comparison: "Index Scan always uses the index results it is given; \
+                                     this block decides whether using an index is worth it."
+                            .into(),
+                    },
+                    Alternative {
+                        block_type: "sequential-scan".into(),
+                        comparison: "Sequential Scan always reads every page; this block does \
+                                     so only when the predicate is estimated to match more \
+                                     than the crossover fraction of rows."
+                            .into(),
+                    },
+                ],
+                suggested_questions: vec![
+                    "Why does an index scan lose to a sequential scan at only a few percent \
+                     selectivity?"
+                        .into(),
+                    "How does a correlated predicate such as city = 'Paris' AND country = \
+                     'France' fool the optimizer into the wrong scan?"
+                        .into(),
+                    "What does a bitmap heap scan do to the crossover point?".into(),
+                ],
+            },
+            references: vec![Reference {
+                ref_type: ReferenceType::Paper,
+                title: "Access Path Selection in a Relational Database Management System".into(),
+                url: None,
+                citation: Some("Selinger, P. G. et al. (1979). SIGMOD.".into()),
+            }],
+            icon: "git-branch".into(),
+            color: "#14B8A6".into(),
+        }
+    }
+
+    fn build_inputs() -> Vec<Port> {
+        vec![
+            Port {
+                id: "records".into(), name: "Table".into(), port_type: PortType::DataStream,
+                direction: PortDirection::Input, required: true, multiple: false,
+                description: "The table's records (with _page_id when available)".into(),
+                schema: None,
+            },
+            Port {
+                id: "statistics".into(), name: "Statistics".into(), port_type: PortType::DataStream,
+                direction: PortDirection::Input, required: false, multiple: false,
+                description: "A statistics collector's output, for estimated selectivity".into(),
+                schema: None,
+            },
+        ]
+    }
+
+    fn build_outputs() -> Vec<Port> {
+        vec![Port {
+            id: "results".into(), name: "Matching Records".into(), port_type: PortType::DataStream,
+            direction: PortDirection::Output, required: false, multiple: true,
+            description: "Records that satisfy the predicate".into(), schema: None,
+        }]
+    }
+
+    fn build_parameters() -> Vec<Parameter> {
+        vec![
+            Parameter {
+                id: "predicate".into(), name: "Predicate".into(), param_type: ParameterType::String,
+                description: "WHERE clause with AND/OR/NOT".into(),
+                default_value: ParameterValue::String("id < 10".into()), required: true,
+                constraints: None,
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+            Parameter {
+                id: "access_path".into(), name: "Access Path".into(), param_type: ParameterType::Enum,
+                description: "auto (by estimated cost), sequential, or index".into(),
+                default_value: ParameterValue::String("auto".into()), required: false,
+                constraints: Some(ParameterConstraints::new().with_choices(ACCESS_PATHS)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+            },
+            Parameter {
+                id: "random_page_cost".into(), name: "Random Page Cost".into(), param_type: ParameterType::Number,
+                description: "Cost of a random page fetch relative to a sequential one".into(),
+                default_value: ParameterValue::Number(4.0), required: false,
+                constraints: Some(ParameterConstraints::new().with_min(0.1).with_max(100.0)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Slider)),
+            },
+            Parameter {
+                id: "records_per_page".into(), name: "Records Per Page".into(), param_type: ParameterType::Number,
+                description: "Rows per page for records without _page_id".into(),
+                default_value: ParameterValue::Integer(100), required: false,
+                constraints: Some(ParameterConstraints::new().with_min(1.0)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+        ]
+    }
+
+    fn build_metrics() -> Vec<MetricDefinition> {
+        vec![
+            MetricDefinition { id: "chosen_path".into(), name: "Chosen Path".into(), metric_type: MetricType::Gauge, unit: "".into(), description: "0 = sequential scan, 1 = index scan".into(), aggregations: vec![AggregationType::Max] },
+            MetricDefinition { id: "estimated_selectivity".into(), name: "Estimated Selectivity".into(), metric_type: MetricType::Gauge, unit: "fraction".into(), description: "Fraction of rows the statistics predict will match".into(), aggregations: vec![AggregationType::Avg] },
+            MetricDefinition { id: "observed_selectivity".into(), name: "Observed Selectivity".into(), metric_type: MetricType::Gauge, unit: "fraction".into(), description: "Fraction of rows that matched".into(), aggregations: vec![AggregationType::Avg] },
+            MetricDefinition { id: "crossover_selectivity".into(), name: "Crossover Selectivity".into(), metric_type: MetricType::Gauge, unit: "fraction".into(), description: "Selectivity at which both paths cost the same".into(), aggregations: vec![AggregationType::Avg] },
+            MetricDefinition { id: "estimated_rows".into(), name: "Estimated Rows".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Rows the statistics predict will match".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "actual_rows".into(), name: "Actual Rows".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Rows that matched".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "pages_read".into(), name: "Pages Read".into(), metric_type: MetricType::Counter, unit: "pages".into(), description: "Distinct pages the chosen path read".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "random_ios".into(), name: "Random I/Os".into(), metric_type: MetricType::Counter, unit: "ops".into(), description: "Random page fetches (index path only)".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "io_cost".into(), name: "I/O Cost".into(), metric_type: MetricType::Gauge, unit: "".into(), description: "I/O cost of the chosen path".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "alternative_io_cost".into(), name: "Alternative I/O Cost".into(), metric_type: MetricType::Gauge, unit: "".into(), description: "I/O cost the other path would have had".into(), aggregations: vec![AggregationType::Sum] },
+        ]
+    }
+
+    /// The page a record lives on: its `_page_id`, or its position's page.
+    fn page_of(&self, position: usize, record: &Record) -> usize {
+        record.get::<usize>("_page_id").ok().flatten().unwrap_or(position / self.records_per_page)
+    }
+}
+
+fn parse_access_path(s: &str) -> AccessPath {
+    match s {
+        "sequential" => AccessPath::Sequential,
+        "index" => AccessPath::Index,
+        _ => AccessPath::Auto,
+    }
+}
+
+impl Default for AccessPathBlock {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl Block for AccessPathBlock {
+    fn metadata(&self) -> &BlockMetadata { &self.metadata }
+    fn inputs(&self) -> &[Port] { &self.input_ports }
+    fn outputs(&self) -> &[Port] { &self.output_ports }
+    fn parameters(&self) -> &[Parameter] { &self.params }
+    fn requires(&self) -> &[Constraint] { &[] }
+    fn guarantees(&self) -> &[Guarantee] { &[] }
+    fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
+    fn reports_running_totals(&self) -> bool { false }
+
+    async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
+        if let Some(p) = params.get("predicate").and_then(ParameterValue::as_string) {
+            self.predicate = FilterExpr::parse(p.trim())
+                .map_err(|e| BlockError::InvalidParameter(format!("predicate: {}", e)))?;
+        } else {
+            self.predicate = FilterExpr::parse("id < 10").expect("default predicate parses");
+        }
+        self.access_path = enum_param(&params, "access_path", ACCESS_PATHS)?.map(parse_access_path).unwrap_or(AccessPath::Auto);
+        if let Some(v) = params.get("random_page_cost").and_then(ParameterValue::as_number) { self.random_page_cost = v; }
+        if let Some(v) = params.get("records_per_page").and_then(ParameterValue::as_integer) { self.records_per_page = v as usize; }
+        Ok(())
+    }
+
+    async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult, BlockError> {
+        let records = match context.inputs.get("records").cloned().unwrap_or(PortValue::None) {
+            PortValue::Stream(r) => r, PortValue::Batch(r) => r, PortValue::Single(r) => vec![r],
+            PortValue::None => Vec::new(),
+            _ => return Err(BlockError::InvalidInput("Expected DataStream for records".into())),
+        };
+
+        let statistics = match context.inputs.get("statistics") {
+            Some(PortValue::Single(r)) => Some(r),
+            Some(PortValue::Stream(r)) | Some(PortValue::Batch(r)) => r.last(),
+            _ => None,
+        };
+        if let Some(columns) = statistics.and_then(|r| r.get::<HashMap<String, ColumnStats>>("_columns").ok().flatten()) {
+            self.stats = columns;
+        }
+
+        let rows = records.len();
+        let mut table_pages = HashSet::new();
+        let mut match_pages = HashSet::new();
+        let mut results = Vec::new();
+        for (position, record) in records.into_iter().enumerate() {
+            let page = self.page_of(position, &record);
+            table_pages.insert(page);
+            if self.predicate.evaluate(&record) == Some(true) {
+                match_pages.insert(page);
+                results.push(record);
+            }
+        }
+        let actual_rows = results.len();
+
+        let estimated = self.predicate.estimate_selectivity(&self.stats).clamp(0.0, 1.0);
+        let estimated_rows = estimated * rows as f64;
+        let observed = if rows > 0 { actual_rows as f64 / rows as f64 } else { 0.0 };
+        let seq_cost = table_pages.len() as f64;
+        let index_cost = actual_rows as f64 * self.random_page_cost;
+        let crossover = if rows > 0 { seq_cost / (rows as f64 * self.random_page_cost) } else { 0.0 };
+
+        let use_index = match self.access_path {
+            AccessPath::Auto => estimated_rows * self.random_page_cost < seq_cost,
+            AccessPath::Sequential => false,
+            AccessPath::Index => true,
+        };
+        let (pages_read, random_ios, io_cost, alternative_io_cost) = if use_index {
+            (match_pages.len(), actual_rows, index_cost, seq_cost)
+        } else {
+            (table_pages.len(), 0, seq_cost, index_cost)
+        };
+        let chosen = if use_index { 1.0 } else { 0.0 };
+
+        context.metrics.record("chosen_path", chosen);
+        context.metrics.record("estimated_selectivity", estimated);
+        context.metrics.record("observed_selectivity", observed);
+        context.metrics.record("crossover_selectivity", crossover);
+        context.metrics.record("estimated_rows", estimated_rows);
+        context.metrics.record("actual_rows", actual_rows as f64);
+        context.metrics.record("pages_read", pages_read as f64);
+        context.metrics.record("random_ios", random_ios as f64);
+        context.metrics.record("io_cost", io_cost);
+        context.metrics.record("alternative_io_cost", alternative_io_cost);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("results".into(), PortValue::Stream(results));
+        let mut ms = HashMap::new();
+        ms.insert("chosen_path".into(), chosen);
+        ms.insert("estimated_selectivity".into(), estimated);
+        ms.insert("observed_selectivity".into(), observed);
+        ms.insert("crossover_selectivity".into(), crossover);
+        ms.insert("estimated_rows".into(), estimated_rows);
+        ms.insert("actual_rows".into(), actual_rows as f64);
+        ms.insert("pages_read".into(), pages_read as f64);
+        ms.insert("random_ios".into(), random_ios as f64);
+        ms.insert("io_cost".into(), io_cost);
+        ms.insert("alternative_io_cost".into(), alternative_io_cost);
+
+        Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
+    }
+
+    fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
+        if inputs.get("records").is_none() { ValidationResult::ok().with_warning("records input not connected") }
+        else { ValidationResult::ok() }
+    }
+
+    fn get_state(&self) -> BlockState { BlockState::new() }
+    fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::categories::optimization::StatisticsCollectorBlock;
+    use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+    fn ctx(inputs: HashMap<String, PortValue>) -> ExecutionContext {
+        ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() }
+    }
+
+    /// 1000 rows on pages of 10; `id` is unique, `bucket` has 4 values.
+    fn table() -> Vec<Record> {
+        (0..1000).map(|i| {
+            let mut r = Record::new();
+            r.insert("id".into(), i as i64).unwrap();
+            r.insert("bucket".into(), (i % 4) as i64).unwrap();
+            r.insert("_page_id".into(), (i / 10) as usize).unwrap();
+            r
+        }).collect()
+    }
+
+    async fn statistics(records: &[Record]) -> PortValue {
+        let mut collector = StatisticsCollectorBlock::new();
+        collector.initialize(HashMap::from([("sample_rate".to_string(), ParameterValue::Number(1.0))])).await.unwrap();
+        let inputs = HashMap::from([("records".to_string(), PortValue::Stream(records.to_vec()))]);
+        collector.execute(ctx(inputs)).await.unwrap().outputs.remove("statistics").unwrap()
+    }
+
+    async fn scan(predicate: &str, access_path: &str, with_stats: bool) -> ExecutionResult {
+        let mut block = AccessPathBlock::new();
+        block.initialize(HashMap::from([
+            ("predicate".to_string(), ParameterValue::from(predicate)),
+            ("access_path".to_string(), ParameterValue::from(access_path)),
+        ])).await.unwrap();
+        let records = table();
+        let mut inputs = HashMap::new();
+        if with_stats {
+            inputs.insert("statistics".to_string(), statistics(&records).await);
+        }
+        inputs.insert("records".to_string(), PortValue::Stream(records));
+        block.execute(ctx(inputs)).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_selective_predicate_uses_the_index() {
+        let result = scan("id < 5", "auto", true).await;
+        let m = &result.metrics;
+        // 100 pages / (1000 rows × 4) = 2.5%.
+        assert!((m["crossover_selectivity"] - 0.025).abs() < 1e-9);
+        assert_eq!(m["chosen_path"], 1.0);
+        assert_eq!(m["actual_rows"], 5.0);
+        assert_eq!(m["random_ios"], 5.0);
+        assert_eq!(m["pages_read"], 1.0);
+        assert_eq!(m["io_cost"], 20.0);
+        assert_eq!(m["alternative_io_cost"], 100.0);
+        match result.outputs.get("results").unwrap() {
+            PortValue::Stream(r) => assert_eq!(r.len(), 5),
+            _ => panic!("expected stream"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unselective_predicate_uses_a_sequential_scan() {
+        let m = scan("bucket = 1", "auto", true).await.metrics;
+        assert!((m["estimated_selectivity"] - 0.25).abs() < 0.02, "estimate {}", m["estimated_selectivity"]);
+        assert_eq!(m["chosen_path"], 0.0);
+        assert_eq!(m["actual_rows"], 250.0);
+        assert_eq!(m["pages_read"], 100.0);
+        assert_eq!(m["random_ios"], 0.0);
+        assert_eq!(m["io_cost"], 100.0);
+        assert_eq!(m["alternative_io_cost"], 1000.0);
+    }
+
+    #[tokio::test]
+    async fn test_bad_estimate_without_statistics_picks_the_wrong_path() {
+        // Without statistics, equality is guessed at 0.5% — under the
+        // crossover — but a quarter of the table matches.
+        let m = scan("bucket = 1", "auto", false).await.metrics;
+        assert_eq!(m["estimated_selectivity"], 0.005);
+        assert_eq!(m["observed_selectivity"], 0.25);
+        assert_eq!(m["chosen_path"], 1.0);
+        assert!(m["alternative_io_cost"] < m["io_cost"]);
+    }
+
+    #[tokio::test]
+    async fn test_forced_path_still_reports_the_alternative() {
+        let index = scan("id < 500", "index", true).await.metrics;
+        let seq = scan("id < 500", "sequential", true).await.metrics;
+        assert_eq!(index["chosen_path"], 1.0);
+        assert_eq!(seq["chosen_path"], 0.0);
+        assert_eq!(index["io_cost"], seq["alternative_io_cost"]);
+        assert_eq!(seq["io_cost"], index["alternative_io_cost"]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_predicate_is_a_parameter_error() {
+        let params = HashMap::from([("predicate".to_string(), ParameterValue::from("id <"))]);
+        let err = AccessPathBlock::new().initialize(params).await.err().unwrap();
+        assert!(matches!(err, BlockError::InvalidParameter(ref m) if m.contains("predicate")), "{}", err);
+    }
+}
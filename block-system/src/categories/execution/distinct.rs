@@ -0,0 +1,443 @@
+//! Distinct Execution Block
+//!
+//! Removes duplicate rows — SQL's `SELECT DISTINCT`, or PostgreSQL's
+//! `DISTINCT ON (columns)` when only some columns are compared.
+//!
+//! ## How it works
+//!
+//! Each record's key is the tuple of its `columns` values, or every
+//! non-null field when `columns` is empty. As in SQL, nulls are not
+//! distinct from each other, and an absent column counts as null. The first
+//! record with each key is passed on whole; later ones are dropped.
+//!
+//! - `hash` keeps every key seen so far in a hash set. It accepts input in
+//!   any order but holds one entry per distinct key.
+//! - `sorted` compares each record only with the one before it, so it holds
+//!   a single key. It relies on duplicates being adjacent: with `columns`
+//!   set, input that is not sorted ascending on them is rejected; with the
+//!   whole row, identical rows are assumed to arrive together.
+//!
+//! Keys seen are kept across calls, so batched input is deduplicated as a
+//! whole.
+//!
+//! ## Metrics tracked
+//!
+//! | Metric | Type | Description |
+//! |--------|------|-------------|
+//! | `rows_in` | Counter | Records received |
+//! | `rows_out` | Counter | Distinct records passed on |
+//! | `duplicates_removed` | Counter | Records dropped as duplicates |
+//! | `keys_in_memory` | Gauge | Keys the method is holding |
+
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::core::block::{
+    Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
+    Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
+};
+use crate::core::constraint::{Constraint, Guarantee, GuaranteeType};
+use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
+use crate::core::parameter::{
+    enum_param, list_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint,
+    ParameterValue, ValidationResult, WidgetType,
+};
+use crate::core::port::{cmp_json, Port, PortDirection, PortType, PortValue, Record};
+
+/// How duplicates are found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistinctMethod {
+    /// Remember every key in a hash set.
+    Hash,
+    /// Compare with the previous record; input must be sorted.
+    Sorted,
+}
+
+/// Values accepted by the `method` parameter.
+const METHODS: &[&str] = &["hash", "sorted"];
+
+pub struct DistinctBlock {
+    metadata: BlockMetadata,
+    input_ports: Vec<Port>,
+    output_ports: Vec<Port>,
+    params: Vec<Parameter>,
+    metric_defs: Vec<MetricDefinition>,
+
+    columns: Vec<String>,
+    method: DistinctMethod,
+    /// Keys seen so far (hash method).
+    seen: HashSet<String>,
+    /// The previous record's key (sorted method).
+    last: Option<Vec<JsonValue>>,
+    /// Sorted input, for the sorted method.
+    requirements: Vec<Constraint>,
+}
+
+impl DistinctBlock {
+    pub fn new() -> Self {
+        Self {
+            metadata: Self::build_metadata(),
+            input_ports: Self::build_inputs(),
+            output_ports: Self::build_outputs(),
+            params: Self::build_parameters(),
+            metric_defs: Self::build_metrics(),
+            columns: Vec::new(),
+            method: DistinctMethod::Hash,
+            seen: HashSet::new(),
+            last: None,
+            requirements: Vec::new(),
+        }
+    }
+
+    fn build_metadata() -> BlockMetadata {
+        BlockMetadata {
+            id: "distinct".into(),
+            name: "Distinct".into(),
+            category: BlockCategory::Execution,
+            description: "Removes duplicate rows by hashing or by comparing sorted neighbours"
+                .into(),
+            version: "1.0.0".into(),
+            documentation: BlockDocumentation {
+                overview: "DISTINCT removes duplicate rows from a result. Over all columns it \
+                           is SELECT DISTINCT; over a subset it keeps the first row for each \
+                           combination of those columns, like PostgreSQL's DISTINCT ON.\n\n\
+                           There are two classic ways to find duplicates. Hashing remembers \
+                           every key it has seen and drops a row whose key is already there — \
+                           it works on any input order, but memory grows with the number of \
+                           distinct keys. If the input is already sorted on the key, \
+                           duplicates sit next to each other, so comparing each row with the \
+                           previous one is enough and memory stays constant. The same \
+                           trade-off separates hash aggregation from sort-based aggregation."
+                    .into(),
+                algorithm: "Distinct:\n\
+                            \n\
+                            FUNCTION hash_distinct(records, columns):\n  \
+                              seen = {}\n  \
+                              FOR EACH r IN records:\n    \
+                                key = (r[c] FOR c IN columns)\n    \
+                                IF key NOT IN seen: seen.add(key); emit(r)\n\
+                            \n\
+                            FUNCTION sorted_distinct(records, columns):\n  \
+                              last = NONE\n  \
+                              FOR EACH r IN records:     // sorted on columns\n    \
+                                key = (r[c] FOR c IN columns)\n    \
+                                IF key != last: emit(r)\n    \
+                                last = key"
+                    .into(),
+                complexity: Complexity {
+                    time: "O(n) for both methods".into(),
+                    space: "O(d) for d distinct keys with hash; O(1) with sorted".into(),
+                },
+                use_cases: vec![
+                    "SELECT DISTINCT over a result with repeated rows".into(),
+                    "DISTINCT ON (customer_id) to keep one row per customer".into(),
+                    "Removing duplicates after a UNION or a fan-out join".into(),
+                ],
+                tradeoffs: vec![
+                    "Hashing needs no ordering but holds every distinct key in memory".into(),
+                    "Sorted dedup holds one key, but someone has to sort the input first — \
+                     unless it comes from an index or a sort that was needed anyway"
+                        .into(),
+                    "Sorted output stays sorted; hashed output keeps input order".into(),
+                ],
+                examples: vec![
+                    "PostgreSQL HashAggregate or Unique (over a Sort) for SELECT DISTINCT".into(),
+                    "PostgreSQL DISTINCT ON, which keeps the first row of each group".into(),
+                ],
+                motivation: "Duplicates creep in through joins, unions and projections that \
+                             drop a unique column. Removing them is cheap when the input is \
+                             sorted and memory-hungry when it is not; keys_in_memory makes the \
+                             difference between the two methods visible."
+                    .into(),
+                parameter_guide: HashMap::from([
+                    ("columns".into(), "Columns that decide whether two rows are duplicates. \
+                                        Leave empty to compare whole rows. With a subset, the \
+                                        first row of each key is kept whole, as DISTINCT ON \
+                                        does. Nulls compare equal to each other.".into()),
+                    ("method".into(), "hash remembers every key and accepts any input order; \
+                                       keys_in_memory grows with the number of distinct keys. \
+                                       sorted compares each row with the previous one and \
+                                       holds a single key, but the input must be sorted on \
+                                       the columns — unsorted input is rejected.".into()),
+                ]),
+                alternatives: vec![Alternative {
+                    block_type: "aggregate".into(),
+                    comparison: "DISTINCT is GROUP BY with no aggregates: an aggregate grouped \
+                                 on the same columns yields the same keys, but emits only the \
+                                 group columns rather than the first full row."
+                        .into(),
+                }],
+                suggested_questions: vec![
+                    "Why might SELECT DISTINCT be planned as a sort followed by Unique rather \
+                     than a hash?"
+                        .into(),
+                    "Why are NULLs treated as equal by DISTINCT but not by =?".into(),
+                ],
+            },
+            references: vec![Reference {
+                ref_type: ReferenceType::Paper,
+                title: "Query Evaluation Techniques for Large Databases".into(),
+                url: None,
+                citation: Some("Graefe, G. (1993). ACM Computing Surveys 25(2).".into()),
+            }],
+            icon: "copy".into(),
+            color: "#EC4899".into(),
+        }
+    }
+
+    fn build_inputs() -> Vec<Port> {
+        vec![Port {
+            id: "records".into(), name: "Records".into(), port_type: PortType::DataStream,
+            direction: PortDirection::Input, required: true, multiple: false,
+            description: "Records to deduplicate".into(), schema: None,
+        }]
+    }
+
+    fn build_outputs() -> Vec<Port> {
+        vec![Port {
+            id: "distinct".into(), name: "Distinct Records".into(), port_type: PortType::DataStream,
+            direction: PortDirection::Output, required: false, multiple: true,
+            description: "The first record for each key".into(), schema: None,
+        }]
+    }
+
+    fn build_parameters() -> Vec<Parameter> {
+        vec![
+            Parameter {
+                id: "columns".into(), name: "Columns".into(), param_type: ParameterType::Array,
+                description: "Columns to compare (empty = whole row)".into(),
+                default_value: ParameterValue::Array(Vec::new()), required: false,
+                constraints: Some(columns_constraints()),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+            Parameter {
+                id: "method".into(), name: "Method".into(), param_type: ParameterType::Enum,
+                description: "hash (any order) or sorted (adjacent duplicates)".into(),
+                default_value: ParameterValue::String("hash".into()), required: false,
+                constraints: Some(ParameterConstraints::new().with_choices(METHODS)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+            },
+        ]
+    }
+
+    fn build_metrics() -> Vec<MetricDefinition> {
+        vec![
+            MetricDefinition { id: "rows_in".into(), name: "Rows In".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Records received".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "rows_out".into(), name: "Rows Out".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Distinct records passed on".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "duplicates_removed".into(), name: "Duplicates Removed".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Records dropped as duplicates".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "keys_in_memory".into(), name: "Keys In Memory".into(), metric_type: MetricType::Gauge, unit: "keys".into(), description: "Keys the method is holding".into(), aggregations: vec![AggregationType::Max] },
+        ]
+    }
+
+    /// The values that decide whether `record` duplicates another.
+    fn key(&self, record: &Record) -> Vec<JsonValue> {
+        if self.columns.is_empty() {
+            row_key(record)
+        } else {
+            self.columns.iter().map(|c| record.get_or_null(c).clone()).collect()
+        }
+    }
+}
+
+/// A whole-row key: each non-null field as a `[name, value]` pair, by name,
+/// so rows differing only in absent versus null fields compare equal.
+pub(crate) fn row_key(record: &Record) -> Vec<JsonValue> {
+    let fields: BTreeMap<&String, &JsonValue> =
+        record.data.iter().filter(|(_, v)| !v.is_null()).collect();
+    fields.into_iter().map(|(k, v)| JsonValue::Array(vec![k.as_str().into(), v.clone()])).collect()
+}
+
+/// Lexicographic order of two keys, column by column.
+fn cmp_keys(a: &[JsonValue], b: &[JsonValue]) -> Ordering {
+    a.iter().zip(b).map(|(x, y)| cmp_json(x, y)).find(|o| o.is_ne()).unwrap_or(Ordering::Equal)
+}
+
+/// Constraints on `columns`: column names.
+fn columns_constraints() -> ParameterConstraints {
+    ParameterConstraints::new().with_element_type(ParameterType::String)
+}
+
+fn parse_method(s: &str) -> DistinctMethod {
+    match s {
+        "sorted" => DistinctMethod::Sorted,
+        _ => DistinctMethod::Hash,
+    }
+}
+
+impl Default for DistinctBlock {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl Block for DistinctBlock {
+    fn metadata(&self) -> &BlockMetadata { &self.metadata }
+    fn inputs(&self) -> &[Port] { &self.input_ports }
+    fn outputs(&self) -> &[Port] { &self.output_ports }
+    fn parameters(&self) -> &[Parameter] { &self.params }
+    fn requires(&self) -> &[Constraint] { &self.requirements }
+    fn guarantees(&self) -> &[Guarantee] { &[] }
+    fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
+    fn reports_running_totals(&self) -> bool { false }
+
+    async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
+        if let Some(items) = list_param(&params, "columns", &columns_constraints())? {
+            self.columns = items.iter().filter_map(|c| c.as_string().map(str::to_string)).collect();
+        }
+        self.method = enum_param(&params, "method", METHODS)?.map(parse_method).unwrap_or(DistinctMethod::Hash);
+        self.requirements = match self.method {
+            DistinctMethod::Sorted if !self.columns.is_empty() => {
+                vec![Constraint::requires_guarantee(GuaranteeType::SortedOrder, "Input must arrive sorted on the distinct columns")]
+            }
+            _ => Vec::new(),
+        };
+        self.seen.clear();
+        self.last = None;
+        Ok(())
+    }
+
+    async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult, BlockError> {
+        let records = match context.inputs.get("records").cloned().unwrap_or(PortValue::None) {
+            PortValue::Stream(r) => r, PortValue::Batch(r) => r, PortValue::Single(r) => vec![r],
+            PortValue::None => Vec::new(),
+            _ => return Err(BlockError::InvalidInput("Expected DataStream".into())),
+        };
+
+        let rows_in = records.len();
+        let mut results = Vec::new();
+        for record in records {
+            let key = self.key(&record);
+            let fresh = match self.method {
+                DistinctMethod::Hash => self.seen.insert(JsonValue::Array(key).to_string()),
+                DistinctMethod::Sorted => {
+                    if let Some(last) = self.last.as_deref().filter(|_| !self.columns.is_empty()) {
+                        if cmp_keys(last, &key) == Ordering::Greater {
+                            return Err(BlockError::InvalidInput(format!(
+                                "sorted distinct needs input sorted on {:?}, but {} follows {}",
+                                self.columns, JsonValue::Array(key), JsonValue::Array(last.to_vec())
+                            )));
+                        }
+                    }
+                    let fresh = self.last.as_ref() != Some(&key);
+                    self.last = Some(key);
+                    fresh
+                }
+            };
+            if fresh {
+                results.push(record);
+            }
+        }
+        let rows_out = results.len();
+        let removed = rows_in - rows_out;
+        let keys = match self.method {
+            DistinctMethod::Hash => self.seen.len(),
+            DistinctMethod::Sorted => usize::from(self.last.is_some()),
+        };
+
+        context.metrics.record("rows_in", rows_in as f64);
+        context.metrics.record("rows_out", rows_out as f64);
+        context.metrics.record("duplicates_removed", removed as f64);
+        context.metrics.record("keys_in_memory", keys as f64);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("distinct".into(), PortValue::Stream(results));
+        let mut ms = HashMap::new();
+        ms.insert("rows_in".into(), rows_in as f64);
+        ms.insert("rows_out".into(), rows_out as f64);
+        ms.insert("duplicates_removed".into(), removed as f64);
+        ms.insert("keys_in_memory".into(), keys as f64);
+
+        Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
+    }
+
+    fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
+        if inputs.get("records").is_none() { ValidationResult::ok().with_warning("records input not connected") }
+        else { ValidationResult::ok() }
+    }
+
+    fn get_state(&self) -> BlockState { BlockState::new() }
+    fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+    use serde_json::json;
+
+    fn rows(values: &[(i64, &str)]) -> Vec<Record> {
+        values.iter().map(|&(id, city)| {
+            let mut r = Record::new();
+            r.insert("id".into(), id).unwrap();
+            r.insert("city".into(), city).unwrap();
+            r
+        }).collect()
+    }
+
+    async fn distinct(columns: &[&str], method: &str) -> DistinctBlock {
+        let mut block = DistinctBlock::new();
+        let columns = columns.iter().map(|&c| ParameterValue::from(c)).collect();
+        block.initialize(HashMap::from([
+            ("columns".to_string(), ParameterValue::Array(columns)),
+            ("method".to_string(), ParameterValue::from(method)),
+        ])).await.unwrap();
+        block
+    }
+
+    async fn feed(block: &mut DistinctBlock, records: Vec<Record>) -> Result<(Vec<Record>, ExecutionResult), BlockError> {
+        let inputs = HashMap::from([("records".to_string(), PortValue::Batch(records))]);
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        let result = block.execute(ctx).await?;
+        let out = match result.outputs.get("distinct").unwrap() {
+            PortValue::Stream(r) => r.clone(),
+            _ => panic!("expected stream"),
+        };
+        Ok((out, result))
+    }
+
+    #[tokio::test]
+    async fn test_hash_distinct_over_whole_rows() {
+        let mut block = distinct(&[], "hash").await;
+        let (out, result) = feed(&mut block, rows(&[(1, "a"), (2, "b"), (1, "a"), (1, "b"), (2, "b")])).await.unwrap();
+        assert_eq!(out.len(), 3);
+        assert_eq!(result.metrics["duplicates_removed"], 2.0);
+        assert_eq!(result.metrics["keys_in_memory"], 3.0);
+    }
+
+    #[tokio::test]
+    async fn test_subset_keeps_first_row_and_treats_nulls_as_equal() {
+        let mut block = distinct(&["city"], "hash").await;
+        let mut input = rows(&[(1, "a"), (2, "b"), (3, "a"), (4, "x"), (5, "x")]);
+        input[3].data.insert("city".into(), JsonValue::Null);
+        input[4].data.remove("city");
+        let (out, _) = feed(&mut block, input).await.unwrap();
+        let ids: Vec<JsonValue> = out.iter().map(|r| r.get_or_null("id").clone()).collect();
+        assert_eq!(ids, vec![json!(1), json!(2), json!(4)]);
+    }
+
+    #[tokio::test]
+    async fn test_sorted_distinct_holds_one_key_across_batches() {
+        let mut block = distinct(&["city"], "sorted").await;
+        let (first, _) = feed(&mut block, rows(&[(1, "a"), (2, "a"), (3, "b")])).await.unwrap();
+        let (second, result) = feed(&mut block, rows(&[(4, "b"), (5, "c"), (6, "c")])).await.unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 1, "'b' continues from the previous batch");
+        assert_eq!(result.metrics["duplicates_removed"], 2.0);
+        assert_eq!(result.metrics["keys_in_memory"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_sorted_distinct_rejects_unsorted_input() {
+        let mut block = distinct(&["city"], "sorted").await;
+        let err = feed(&mut block, rows(&[(1, "b"), (2, "a")])).await.err().unwrap();
+        assert!(matches!(err, BlockError::InvalidInput(ref m) if m.contains("sorted on")), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_method() {
+        let params = HashMap::from([("method".to_string(), ParameterValue::from("bitmap"))]);
+        assert!(DistinctBlock::new().initialize(params).await.is_err());
+    }
+}
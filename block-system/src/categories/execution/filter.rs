@@ -1,7 +1,24 @@
 //! Filter Execution Block
 //!
 //! Evaluates a predicate on each input record and passes through only matching
-//! rows. Supports comparison operators on a single column.
+//! rows.
+//!
+//! ## How it works
+//!
+//! The predicate is a tree of column comparisons combined with AND, OR and
+//! NOT, parsed from the `predicate` parameter — for example
+//! `age >= 18 AND (city = 'NYC' OR NOT vip = true)` — or, when that is
+//! empty, the single comparison `column operator value`. Evaluation follows
+//! SQL's three-valued logic: comparing a null or absent column is UNKNOWN,
+//! `NOT UNKNOWN` is still UNKNOWN, and only rows that come out TRUE pass.
+//! `column IS NULL` and `column IS NOT NULL` test for nulls directly.
+//!
+//! When the optional `statistics` port carries a statistics collector's
+//! output, the filter also estimates its selectivity from the column
+//! histograms, the way a cost-based optimizer would, and reports it next
+//! to the selectivity it observed. The gap between the two is the
+//! optimizer's estimation error. The latest statistics are kept across
+//! calls.
 //!
 //! ## Metrics tracked
 //!
@@ -10,10 +27,14 @@
 //! | `rows_in` | Counter | Total rows evaluated |
 //! | `rows_out` | Counter | Rows that passed the filter |
 //! | `selectivity` | Gauge | rows_out / rows_in as percentage |
+//! | `observed_selectivity` | Gauge | rows_out / rows_in as a fraction |
+//! | `estimated_selectivity` | Gauge | Fraction the statistics predict (only with statistics) |
 
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
@@ -22,9 +43,11 @@
 use crate::core::constraint::{Constraint, Guarantee};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterType, ParameterUIHint, ParameterValue, ValidationResult, WidgetType,
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    ValidationResult, WidgetType,
 };
-use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
+use crate::core::port::{cmp_json, Port, PortDirection, PortType, PortValue, Record};
+use crate::categories::optimization::statistics_collector::{ColumnStats, Predicate};
 
 // ---------------------------------------------------------------------------
 // FilterBlock
@@ -40,6 +63,306 @@ pub enum FilterOp {
     Ge,
 }
 
+impl FilterOp {
+    /// Whether `field <op> value` holds, given `cmp_json(field, value)`.
+    fn holds(&self, ord: Ordering) -> bool {
+        match self {
+            FilterOp::Eq => ord == Ordering::Equal,
+            FilterOp::Ne => ord != Ordering::Equal,
+            FilterOp::Lt => ord == Ordering::Less,
+            FilterOp::Le => ord != Ordering::Greater,
+            FilterOp::Gt => ord == Ordering::Greater,
+            FilterOp::Ge => ord != Ordering::Less,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            FilterOp::Eq => "=",
+            FilterOp::Ne => "<>",
+            FilterOp::Lt => "<",
+            FilterOp::Le => "<=",
+            FilterOp::Gt => ">",
+            FilterOp::Ge => ">=",
+        }
+    }
+}
+
+/// Selectivity PostgreSQL assumes for `column = value` without statistics.
+const DEFAULT_EQ_SELECTIVITY: f64 = 0.005;
+/// Selectivity PostgreSQL assumes for a range comparison without statistics.
+const DEFAULT_RANGE_SELECTIVITY: f64 = 1.0 / 3.0;
+
+/// A WHERE clause: column comparisons combined with AND, OR and NOT.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    /// `column <op> value`
+    Compare { column: String, op: FilterOp, value: JsonValue },
+    /// `column IS NULL`; `IS NOT NULL` is `Not(IsNull)`.
+    IsNull(String),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+}
+
+impl FilterExpr {
+    /// Parse a predicate such as `age >= 18 AND (city = 'NYC' OR NOT vip = true)`.
+    ///
+    /// Comparisons are `column op value` with op one of `=`, `<>` (or `!=`),
+    /// `<`, `<=`, `>`, `>=`; values are numbers, `true`/`false`, quoted
+    /// strings or bare words. NOT binds tighter than AND, and AND tighter
+    /// than OR. Keywords are case-insensitive.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut parser = Parser { tokens: tokenize(input)?, pos: 0 };
+        let expr = parser.or()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some(token) => Err(format!("unexpected {} after a complete predicate", token)),
+        }
+    }
+
+    /// Evaluate under SQL's three-valued logic, with `None` for UNKNOWN.
+    pub fn evaluate(&self, record: &Record) -> Option<bool> {
+        match self {
+            FilterExpr::Compare { column, op, value } => {
+                let field = record.get_or_null(column);
+                if field.is_null() || value.is_null() {
+                    return None;
+                }
+                Some(op.holds(cmp_json(field, value)))
+            }
+            FilterExpr::IsNull(column) => Some(record.get_or_null(column).is_null()),
+            FilterExpr::And(a, b) => match (a.evaluate(record), b.evaluate(record)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true)) => Some(true),
+                _ => None,
+            },
+            FilterExpr::Or(a, b) => match (a.evaluate(record), b.evaluate(record)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false)) => Some(false),
+                _ => None,
+            },
+            FilterExpr::Not(e) => e.evaluate(record).map(|b| !b),
+        }
+    }
+
+    /// Fraction of rows expected to match, from per-column statistics.
+    ///
+    /// Comparisons are assumed independent, as most optimizers assume: AND
+    /// multiplies, OR adds and subtracts the overlap, NOT complements. A
+    /// column without statistics, or a range over a non-numeric column, gets
+    /// PostgreSQL's fixed guesses — 0.5% for equality, a third for ranges.
+    pub fn estimate_selectivity(&self, stats: &HashMap<String, ColumnStats>) -> f64 {
+        match self {
+            FilterExpr::Compare { column, op, value } => {
+                let Some(s) = stats.get(column) else {
+                    return match op {
+                        FilterOp::Eq => DEFAULT_EQ_SELECTIVITY,
+                        FilterOp::Ne => 1.0 - DEFAULT_EQ_SELECTIVITY,
+                        _ => DEFAULT_RANGE_SELECTIVITY,
+                    };
+                };
+                let non_null = s.non_null_fraction();
+                let numeric = value.as_f64().filter(|_| !s.histogram.is_empty());
+                let eq = match numeric {
+                    Some(v) => s.selectivity(&Predicate::Eq(v)),
+                    None if s.ndv > 0 => non_null / s.ndv as f64,
+                    None => 0.0,
+                };
+                let range = |low, high| match numeric {
+                    Some(_) => s.selectivity(&Predicate::Range { low, high }),
+                    None => DEFAULT_RANGE_SELECTIVITY * non_null,
+                };
+                match op {
+                    FilterOp::Eq => eq,
+                    FilterOp::Ne => (non_null - eq).max(0.0),
+                    FilterOp::Lt | FilterOp::Le => range(None, numeric),
+                    FilterOp::Gt | FilterOp::Ge => range(numeric, None),
+                }
+            }
+            FilterExpr::IsNull(column) => match stats.get(column) {
+                Some(s) => 1.0 - s.non_null_fraction(),
+                None => DEFAULT_EQ_SELECTIVITY,
+            },
+            FilterExpr::And(a, b) => a.estimate_selectivity(stats) * b.estimate_selectivity(stats),
+            FilterExpr::Or(a, b) => {
+                let (a, b) = (a.estimate_selectivity(stats), b.estimate_selectivity(stats));
+                a + b - a * b
+            }
+            FilterExpr::Not(e) => 1.0 - e.estimate_selectivity(stats),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(FilterOp),
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(w) | Token::Quoted(w) => write!(f, "'{}'", w),
+            Token::Op(op) => write!(f, "'{}'", op.symbol()),
+            Token::Open => write!(f, "'('"),
+            Token::Close => write!(f, "')'"),
+        }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '\'' | '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some(ch) => s.push(ch),
+                        None => return Err(format!("unterminated string {}{}", c, s)),
+                    }
+                }
+                tokens.push(Token::Quoted(s));
+            }
+            '=' | '!' | '<' | '>' => {
+                let next = chars.peek().copied();
+                let op = match (c, next) {
+                    ('<', Some('=')) => FilterOp::Le,
+                    ('<', Some('>')) | ('!', Some('=')) => FilterOp::Ne,
+                    ('>', Some('=')) => FilterOp::Ge,
+                    ('<', _) => FilterOp::Lt,
+                    ('>', _) => FilterOp::Gt,
+                    ('=', _) => FilterOp::Eq,
+                    _ => return Err("expected '=' after '!'".into()),
+                };
+                if matches!(next, Some('=')) || (c, next) == ('<', Some('>')) {
+                    chars.next();
+                }
+                tokens.push(Token::Op(op));
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some(&ch) = chars.peek().filter(|ch| is_word_char(**ch)) {
+                    word.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+            other => return Err(format!("unexpected character '{}'", other)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over the tokens of a predicate.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    /// Consume the keyword `kw` if it is next.
+    fn keyword(&mut self, kw: &str) -> bool {
+        let found =
+            matches!(self.tokens.get(self.pos), Some(Token::Word(w)) if w.eq_ignore_ascii_case(kw));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.and()?;
+        while self.keyword("or") {
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.not()?;
+        while self.keyword("and") {
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<FilterExpr, String> {
+        if self.keyword("not") {
+            return Ok(FilterExpr::Not(Box::new(self.not()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<FilterExpr, String> {
+        let column = match self.next() {
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                return match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err("missing ')'".into()),
+                };
+            }
+            Some(Token::Word(column)) => column,
+            Some(token) => return Err(format!("expected a column, found {}", token)),
+            None => return Err("expected a column, found the end of the predicate".into()),
+        };
+        if self.keyword("is") {
+            let negated = self.keyword("not");
+            if !self.keyword("null") {
+                return Err(format!("expected NULL after '{} IS'", column));
+            }
+            let test = FilterExpr::IsNull(column);
+            return Ok(if negated { FilterExpr::Not(Box::new(test)) } else { test });
+        }
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            _ => return Err(format!("expected a comparison after '{}'", column)),
+        };
+        let value = match self.next() {
+            Some(Token::Quoted(s)) => JsonValue::String(s),
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("null") => {
+                return Err(format!("'{} {} NULL' is never true; use IS NULL", column, op.symbol()));
+            }
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("true") => JsonValue::Bool(true),
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("false") => JsonValue::Bool(false),
+            Some(Token::Word(w)) => parse_value(&w),
+            _ => return Err(format!("expected a value after '{} {}'", column, op.symbol())),
+        };
+        Ok(FilterExpr::Compare { column, op, value })
+    }
+}
+
+/// An integer if `s` parses as one, else a float, else the string itself.
+fn parse_value(s: &str) -> JsonValue {
+    s.parse::<i64>()
+        .map(|n| JsonValue::Number(n.into()))
+        .ok()
+        .or_else(|| {
+            s.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(JsonValue::Number)
+        })
+        .unwrap_or_else(|| JsonValue::String(s.to_string()))
+}
+
 pub struct FilterBlock {
     metadata: BlockMetadata,
     input_ports: Vec<Port>,
@@ -47,9 +370,9 @@ pub struct FilterBlock {
     params: Vec<Parameter>,
     metric_defs: Vec<MetricDefinition>,
 
-    column: String,
-    op: FilterOp,
-    value: JsonValue,
+    predicate: FilterExpr,
+    /// Column statistics from the `statistics` input, kept across calls.
+    stats: HashMap<String, ColumnStats>,
 }
 
 impl FilterBlock {
@@ -60,9 +383,8 @@ pub fn new() -> Self {
             output_ports: Self::build_outputs(),
             params: Self::build_parameters(),
             metric_defs: Self::build_metrics(),
-            column: "id".into(),
-            op: FilterOp::Eq,
-            value: JsonValue::Null,
+            predicate: FilterExpr::Compare { column: "id".into(), op: FilterOp::Eq, value: JsonValue::Null },
+            stats: HashMap::new(),
         }
     }
 
@@ -91,19 +413,24 @@ fn build_metadata() -> BlockMetadata {
                     .into(),
                 algorithm: "Filter Algorithm:\n\
                             \n\
-                            FUNCTION filter(records, column, op, value):\n  \
+                            FUNCTION filter(records, predicate):\n  \
                               results = []\n  \
                               FOR EACH record IN records:\n    \
-                                field = record[column]\n    \
-                                IF field IS NULL:\n      \
-                                  SKIP  // NULL never matches\n    \
-                                match = EVALUATE(field, op, value)\n    \
-                                  // op is one of: eq, ne, lt, le, gt, ge\n    \
-                                IF match:\n      \
+                                IF EVAL(predicate, record) == TRUE:\n      \
                                   results.append(record)\n  \
-                              selectivity = results.len / records.len * 100\n  \
+                              selectivity = results.len / records.len\n  \
                               RETURN results\n\
                             \n\
+                            FUNCTION EVAL(p, record):   // TRUE, FALSE or UNKNOWN\n  \
+                              CASE p OF\n    \
+                                col op value:  IF record[col] IS NULL: UNKNOWN\n                   \
+                                               ELSE compare(record[col], op, value)\n    \
+                                a AND b:       FALSE if either is FALSE,\n                   \
+                                               else UNKNOWN if either is UNKNOWN\n    \
+                                a OR b:        TRUE if either is TRUE,\n                   \
+                                               else UNKNOWN if either is UNKNOWN\n    \
+                                NOT a:         UNKNOWN stays UNKNOWN\n\
+                            \n\
                             NOTE: This is a streaming operator — records can be\n\
                             emitted immediately without waiting for all input."
                     .into(),
@@ -143,6 +470,14 @@ fn build_metadata() -> BlockMetadata {
                              selectivity is fundamental to understanding query plans."
                     .into(),
                 parameter_guide: HashMap::from([
+                    ("predicate".into(), "A full WHERE clause, such as \
+                                          `age >= 18 AND (city = 'NYC' OR NOT vip = true)`. \
+                                          Comparisons use =, <>, <, <=, >, >= against numbers, \
+                                          true/false or quoted strings; combine them with AND, \
+                                          OR, NOT and parentheses, and test nulls with IS NULL. \
+                                          When set, it replaces column/operator/value. Connect a \
+                                          statistics collector to compare the selectivity the \
+                                          optimizer would estimate with the one observed.".into()),
                     ("column".into(), "The name of the column to evaluate the predicate against. \
                                        This must match a field name in the input records. If the \
                                        column does not exist in a record, that record is filtered \
@@ -209,6 +544,15 @@ fn build_inputs() -> Vec<Port> {
             multiple: false,
             description: "Records to filter".into(),
             schema: None,
+        }, Port {
+            id: "statistics".into(),
+            name: "Statistics".into(),
+            port_type: PortType::DataStream,
+            direction: PortDirection::Input,
+            required: false,
+            multiple: false,
+            description: "A statistics collector's output, for estimated selectivity".into(),
+            schema: None,
         }]
     }
 
@@ -227,6 +571,16 @@ fn build_outputs() -> Vec<Port> {
 
     fn build_parameters() -> Vec<Parameter> {
         vec![
+            Parameter {
+                id: "predicate".into(),
+                name: "Predicate".into(),
+                param_type: ParameterType::String,
+                description: "WHERE clause with AND/OR/NOT; overrides column/operator/value".into(),
+                default_value: ParameterValue::String("".into()),
+                required: false,
+                constraints: None,
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
             Parameter {
                 id: "column".into(),
                 name: "Column".into(),
@@ -240,11 +594,11 @@ fn build_parameters() -> Vec<Parameter> {
             Parameter {
                 id: "operator".into(),
                 name: "Operator".into(),
-                param_type: ParameterType::String,
+                param_type: ParameterType::Enum,
                 description: "Comparison operator (eq, ne, lt, le, gt, ge)".into(),
                 default_value: ParameterValue::String("eq".into()),
                 required: true,
-                constraints: None,
+                constraints: Some(ParameterConstraints::new().with_choices(OPERATORS)),
                 ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
             },
             Parameter {
@@ -286,44 +640,36 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Fraction of rows that passed".into(),
                 aggregations: vec![AggregationType::Avg],
             },
+            MetricDefinition {
+                id: "observed_selectivity".into(),
+                name: "Observed Selectivity".into(),
+                metric_type: MetricType::Gauge,
+                unit: "fraction".into(),
+                description: "rows_out / rows_in".into(),
+                aggregations: vec![AggregationType::Avg],
+            },
+            MetricDefinition {
+                id: "estimated_selectivity".into(),
+                name: "Estimated Selectivity".into(),
+                metric_type: MetricType::Gauge,
+                unit: "fraction".into(),
+                description: "Fraction of rows the column statistics predict will pass".into(),
+                aggregations: vec![AggregationType::Avg],
+            },
         ]
     }
-
-    fn evaluate(&self, record: &Record) -> bool {
-        let field = match record.data.get(&self.column) {
-            Some(v) => v,
-            None => return false,
-        };
-        match &self.op {
-            FilterOp::Eq => field == &self.value,
-            FilterOp::Ne => field != &self.value,
-            FilterOp::Lt => cmp_json(field, &self.value) == std::cmp::Ordering::Less,
-            FilterOp::Le => cmp_json(field, &self.value) != std::cmp::Ordering::Greater,
-            FilterOp::Gt => cmp_json(field, &self.value) == std::cmp::Ordering::Greater,
-            FilterOp::Ge => cmp_json(field, &self.value) != std::cmp::Ordering::Less,
-        }
-    }
 }
 
-fn cmp_json(a: &JsonValue, b: &JsonValue) -> std::cmp::Ordering {
-    match (a, b) {
-        (JsonValue::Number(na), JsonValue::Number(nb)) => {
-            let fa = na.as_f64().unwrap_or(0.0);
-            let fb = nb.as_f64().unwrap_or(0.0);
-            fa.partial_cmp(&fb).unwrap_or(std::cmp::Ordering::Equal)
-        }
-        (JsonValue::String(sa), JsonValue::String(sb)) => sa.cmp(sb),
-        _ => a.to_string().cmp(&b.to_string()),
-    }
-}
+/// Values accepted by the `operator` parameter.
+const OPERATORS: &[&str] = &["eq", "ne", "lt", "le", "gt", "ge"];
 
 fn parse_op(s: &str) -> FilterOp {
-    match s.to_lowercase().as_str() {
-        "ne" | "!=" | "<>" => FilterOp::Ne,
-        "lt" | "<" => FilterOp::Lt,
-        "le" | "<=" => FilterOp::Le,
-        "gt" | ">" => FilterOp::Gt,
-        "ge" | ">=" => FilterOp::Ge,
+    match s {
+        "ne" => FilterOp::Ne,
+        "lt" => FilterOp::Lt,
+        "le" => FilterOp::Le,
+        "gt" => FilterOp::Gt,
+        "ge" => FilterOp::Ge,
         _ => FilterOp::Eq,
     }
 }
@@ -343,18 +689,20 @@ fn parameters(&self) -> &[Parameter] { &self.params }
     fn requires(&self) -> &[Constraint] { &[] }
     fn guarantees(&self) -> &[Guarantee] { &[] }
     fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
+    fn reports_running_totals(&self) -> bool { false }
 
     async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
-        if let Some(v) = params.get("column") { if let Some(s) = v.as_string() { self.column = s.to_string(); } }
-        if let Some(v) = params.get("operator") { if let Some(s) = v.as_string() { self.op = parse_op(s); } }
-        if let Some(v) = params.get("value") {
-            if let Some(s) = v.as_string() {
-                self.value = s.parse::<i64>()
-                    .map(|n| JsonValue::Number(n.into()))
-                    .or_else(|_| s.parse::<f64>().map(|f| serde_json::Number::from_f64(f).map(JsonValue::Number).unwrap_or(JsonValue::String(s.to_string()))))
-                    .unwrap_or_else(|_| JsonValue::String(s.to_string()));
-            }
+        self.params_validate(&params)?;
+        let predicate = params.get("predicate").and_then(ParameterValue::as_string).unwrap_or("").trim();
+        if !predicate.is_empty() {
+            self.predicate = FilterExpr::parse(predicate)
+                .map_err(|e| BlockError::InvalidParameter(format!("predicate: {}", e)))?;
+            return Ok(());
         }
+        let column = params.get("column").and_then(ParameterValue::as_string).unwrap_or("id").to_string();
+        let op = enum_param(&params, "operator", OPERATORS)?.map(parse_op).unwrap_or(FilterOp::Eq);
+        let value = params.get("value").and_then(ParameterValue::as_string).map(parse_value).unwrap_or(JsonValue::Null);
+        self.predicate = FilterExpr::Compare { column, op, value };
         Ok(())
     }
 
@@ -365,14 +713,27 @@ async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult
             _ => return Err(BlockError::InvalidInput("Expected DataStream".into())),
         };
 
+        let statistics = match context.inputs.get("statistics") {
+            Some(PortValue::Single(r)) => Some(r),
+            Some(PortValue::Stream(r)) | Some(PortValue::Batch(r)) => r.last(),
+            _ => None,
+        };
+        if let Some(columns) = statistics.and_then(|r| r.get::<HashMap<String, ColumnStats>>("_columns").ok().flatten()) {
+            self.stats = columns;
+        }
+
         let rows_in = records.len();
-        let results: Vec<Record> = records.into_iter().filter(|r| self.evaluate(r)).collect();
+        let results: Vec<Record> = records.into_iter().filter(|r| self.predicate.evaluate(r) == Some(true)).collect();
         let rows_out = results.len();
-        let selectivity = if rows_in > 0 { (rows_out as f64 / rows_in as f64) * 100.0 } else { 0.0 };
+        let observed = if rows_in > 0 { rows_out as f64 / rows_in as f64 } else { 0.0 };
+        let selectivity = observed * 100.0;
+        let estimated = (!self.stats.is_empty()).then(|| self.predicate.estimate_selectivity(&self.stats));
 
         context.metrics.record("rows_in", rows_in as f64);
         context.metrics.record("rows_out", rows_out as f64);
         context.metrics.record("selectivity", selectivity);
+        context.metrics.record("observed_selectivity", observed);
+        if let Some(estimated) = estimated { context.metrics.record("estimated_selectivity", estimated); }
 
         let mut outputs = HashMap::new();
         outputs.insert("results".into(), PortValue::Stream(results));
@@ -380,6 +741,8 @@ async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult
         ms.insert("rows_in".into(), rows_in as f64);
         ms.insert("rows_out".into(), rows_out as f64);
         ms.insert("selectivity".into(), selectivity);
+        ms.insert("observed_selectivity".into(), observed);
+        if let Some(estimated) = estimated { ms.insert("estimated_selectivity".into(), estimated); }
 
         Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
     }
@@ -406,7 +769,7 @@ fn make_records() -> Vec<Record> {
     async fn test_filter_eq() {
         use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
         let mut f = FilterBlock::new();
-        f.column = "id".into(); f.op = FilterOp::Eq; f.value = json!(5);
+        f.predicate = FilterExpr::Compare { column: "id".into(), op: FilterOp::Eq, value: json!(5) };
         let mut inputs = HashMap::new();
         inputs.insert("records".into(), PortValue::Stream(make_records()));
         let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
@@ -418,7 +781,7 @@ async fn test_filter_eq() {
     async fn test_filter_lt() {
         use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
         let mut f = FilterBlock::new();
-        f.column = "id".into(); f.op = FilterOp::Lt; f.value = json!(5);
+        f.predicate = FilterExpr::Compare { column: "id".into(), op: FilterOp::Lt, value: json!(5) };
         let mut inputs = HashMap::new();
         inputs.insert("records".into(), PortValue::Stream(make_records()));
         let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
@@ -426,6 +789,123 @@ async fn test_filter_lt() {
         assert_eq!(*result.metrics.get("rows_out").unwrap(), 5.0); // 0,1,2,3,4
     }
 
+    async fn run_filter(predicate: &str, inputs: HashMap<String, PortValue>) -> ExecutionResult {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        let mut f = FilterBlock::new();
+        f.initialize(HashMap::from([("predicate".to_string(), ParameterValue::from(predicate))])).await.unwrap();
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        f.execute(ctx).await.unwrap()
+    }
+
+    fn passed_ids(result: &ExecutionResult) -> Vec<i64> {
+        match result.outputs.get("results").unwrap() {
+            PortValue::Stream(r) => r.iter().map(|r| r.get::<i64>("id").unwrap().unwrap()).collect(),
+            _ => panic!("expected stream"),
+        }
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        let cmp = |column: &str, op, value| FilterExpr::Compare { column: column.into(), op, value };
+        // NOT binds tighter than AND, AND tighter than OR.
+        let parsed = FilterExpr::parse("a = 1 or not b <> 'x y' AND c >= -2.5").unwrap();
+        let expected = FilterExpr::Or(
+            Box::new(cmp("a", FilterOp::Eq, json!(1))),
+            Box::new(FilterExpr::And(
+                Box::new(FilterExpr::Not(Box::new(cmp("b", FilterOp::Ne, json!("x y"))))),
+                Box::new(cmp("c", FilterOp::Ge, json!(-2.5))),
+            )),
+        );
+        assert_eq!(parsed, expected);
+        assert_eq!(
+            FilterExpr::parse("(a = 1 OR a = 2) AND flag != true").unwrap(),
+            FilterExpr::And(
+                Box::new(FilterExpr::Or(Box::new(cmp("a", FilterOp::Eq, json!(1))), Box::new(cmp("a", FilterOp::Eq, json!(2))))),
+                Box::new(cmp("flag", FilterOp::Ne, json!(true))),
+            )
+        );
+        assert_eq!(FilterExpr::parse("x IS NOT NULL").unwrap(), FilterExpr::Not(Box::new(FilterExpr::IsNull("x".into()))));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (input, expected) in [
+            ("a = 1 AND", "expected a column"),
+            ("(a = 1", "missing ')'"),
+            ("a 1", "expected a comparison after 'a'"),
+            ("a = 'open", "unterminated string"),
+            ("a = 1 b = 2", "unexpected 'b'"),
+            ("a = null", "use IS NULL"),
+            ("a IS 5", "expected NULL"),
+            ("a ! 5", "expected '='"),
+        ] {
+            let err = FilterExpr::parse(input).unwrap_err();
+            assert!(err.contains(expected), "{:?}: {}", input, err);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compound_predicate_with_three_valued_logic() {
+        let mut records = make_records();
+        records[3].data.insert("name".into(), JsonValue::Null);
+        records[4].data.remove("name");
+        let inputs = || HashMap::from([("records".to_string(), PortValue::Stream(records.clone()))]);
+
+        let result = run_filter("id >= 2 AND (id < 4 OR name = 'u8')", inputs()).await;
+        assert_eq!(passed_ids(&result), vec![2, 3, 8]);
+        assert_eq!(result.metrics["observed_selectivity"], 0.3);
+        assert!(!result.metrics.contains_key("estimated_selectivity"), "no statistics connected");
+
+        // NOT of UNKNOWN is UNKNOWN, so rows with a null or absent name
+        // pass neither the predicate nor its negation.
+        assert_eq!(passed_ids(&run_filter("name = 'u1'", inputs()).await), vec![1]);
+        assert_eq!(passed_ids(&run_filter("NOT name = 'u1'", inputs()).await), vec![0, 2, 5, 6, 7, 8, 9]);
+        assert_eq!(passed_ids(&run_filter("name IS NULL", inputs()).await), vec![3, 4]);
+        assert_eq!(passed_ids(&run_filter("name IS NULL OR id = 0", inputs()).await), vec![0, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_predicate_is_a_parameter_error() {
+        let params = HashMap::from([("predicate".to_string(), ParameterValue::from("id >"))]);
+        let err = FilterBlock::new().initialize(params).await.err().unwrap();
+        assert!(matches!(err, BlockError::InvalidParameter(ref m) if m.contains("predicate")), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_estimated_selectivity_from_collected_statistics() {
+        use crate::categories::optimization::StatisticsCollectorBlock;
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        // ages 0..99, with city 'a' for the first fifth.
+        let records: Vec<Record> = (0..100).map(|i| {
+            let mut r = Record::new();
+            r.insert("id".into(), i as i64).unwrap();
+            r.insert("age".into(), i as i64).unwrap();
+            r.insert("city".into(), if i < 20 { "a" } else { "b" }).unwrap();
+            r
+        }).collect();
+        let mut collector = StatisticsCollectorBlock::new();
+        collector.initialize(HashMap::from([("sample_rate".to_string(), ParameterValue::Number(1.0))])).await.unwrap();
+        let ctx = ExecutionContext {
+            inputs: HashMap::from([("records".to_string(), PortValue::Stream(records.clone()))]),
+            parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new(),
+        };
+        let stats = collector.execute(ctx).await.unwrap().outputs.remove("statistics").unwrap();
+
+        let inputs = HashMap::from([
+            ("records".to_string(), PortValue::Stream(records)),
+            ("statistics".to_string(), stats),
+        ]);
+        // Independent estimates multiply — about 0.5 for the age range times
+        // 1/2 for one of two cities — but age and city are correlated and
+        // the true answer is 0.2.
+        let result = run_filter("age < 50 AND city = 'a'", inputs).await;
+        assert_eq!(result.metrics["observed_selectivity"], 0.2);
+        let age = collector.estimate_selectivity("age", &Predicate::Range { low: None, high: Some(50.0) }).unwrap();
+        assert!((age - 0.5).abs() < 0.02, "age estimate {}", age);
+        assert!((result.metrics["estimated_selectivity"] - age * 0.5).abs() < 1e-9);
+    }
+
     #[test]
     fn test_metadata() {
         let f = FilterBlock::new();
@@ -159,7 +159,7 @@ fn build_metadata() -> BlockMetadata {
                 ]),
                 alternatives: vec![
                     Alternative {
-                        block_type: "sort".into(),
+                        block_type: "merge-join".into(),
                         comparison: "A sort-merge join sorts both inputs on the join column, then \
                                      merges them in order. Sort-merge is better when inputs are \
                                      already sorted (e.g., from an index) or when the result must \
@@ -251,8 +251,10 @@ fn parameters(&self) -> &[Parameter] { &self.params }
     fn requires(&self) -> &[Constraint] { &[] }
     fn guarantees(&self) -> &[Guarantee] { &[] }
     fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
+    fn reports_running_totals(&self) -> bool { false }
 
     async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
         if let Some(v) = params.get("join_column") { if let Some(s) = v.as_string() { self.join_column = s.to_string(); } }
         Ok(())
     }
@@ -323,6 +325,7 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
         else if !has_probe { ValidationResult::ok().with_warning("probe input not connected") }
         else { ValidationResult::ok() }
     }
+    fn state_round_trips(&self) -> bool { true }
     fn get_state(&self) -> BlockState { BlockState::new() }
     fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
 }
@@ -316,16 +316,18 @@ fn metrics(&self) -> &[MetricDefinition] {
         &self.metric_defs
     }
 
+    fn reports_running_totals(&self) -> bool {
+        false
+    }
+
     async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("limit") {
-            let v = val.as_integer().ok_or_else(|| {
-                BlockError::InvalidParameter("limit must be an integer".into())
-            })? as usize;
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("limit").and_then(ParameterValue::as_integer) {
             if v > 0 {
-                self.limit = Some(v);
+                self.limit = Some(v as usize);
             }
         }
         Ok(())
@@ -435,6 +437,10 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
         }
     }
 
+    fn state_round_trips(&self) -> bool {
+        true
+    }
+
     fn get_state(&self) -> BlockState {
         BlockState::new()
     }
@@ -0,0 +1,357 @@
+//! Limit Execution Block
+//!
+//! LIMIT/OFFSET: skip the first `offset` rows, pass on the next `limit`, and
+//! then stop. Stopping is the interesting part — a limit that has emitted
+//! its rows reports itself [exhausted](Block::is_exhausted), and under
+//! batched execution the engine stops running it and every upstream block
+//! that only feeds it, so a scan under `LIMIT 10` reads a batch or so
+//! instead of the whole table.
+//!
+//! ## How it works
+//!
+//! The block keeps how many rows it has skipped and emitted across calls,
+//! so it behaves the same whether fed the whole input at once or one batch
+//! at a time. Rows that arrive after the limit is reached are dropped and
+//! counted as `rows_discarded`: upstream work that was wasted. Fed the whole
+//! input at once, that is everything past the limit; batched, it is at most
+//! the rest of one batch. The run's `short_circuited` flag (see
+//! [`ExecutionMetrics`](crate::runtime::engine::ExecutionMetrics)) reports
+//! whether stopping early actually let the engine skip input.
+//!
+//! A `limit` of 0 emits nothing and is exhausted after its first call.
+//!
+//! ## Metrics tracked
+//!
+//! | Metric | Type | Description |
+//! |--------|------|-------------|
+//! | `rows_skipped` | Counter | Rows dropped to satisfy the offset |
+//! | `rows_emitted` | Counter | Rows passed on |
+//! | `rows_discarded` | Counter | Rows received after the limit was reached |
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use crate::core::block::{
+    Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
+    Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
+};
+use crate::core::constraint::{Constraint, Guarantee};
+use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
+use crate::core::parameter::{
+    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    ValidationResult, WidgetType,
+};
+use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
+
+pub struct LimitBlock {
+    metadata: BlockMetadata,
+    input_ports: Vec<Port>,
+    output_ports: Vec<Port>,
+    params: Vec<Parameter>,
+    metric_defs: Vec<MetricDefinition>,
+
+    limit: usize,
+    offset: usize,
+    /// Rows skipped for the offset so far.
+    skipped: usize,
+    /// Rows emitted so far.
+    emitted: usize,
+    /// Whether the block has executed since initialization.
+    started: bool,
+}
+
+impl LimitBlock {
+    pub fn new() -> Self {
+        Self {
+            metadata: Self::build_metadata(),
+            input_ports: Self::build_inputs(),
+            output_ports: Self::build_outputs(),
+            params: Self::build_parameters(),
+            metric_defs: Self::build_metrics(),
+            limit: 10,
+            offset: 0,
+            skipped: 0,
+            emitted: 0,
+            started: false,
+        }
+    }
+
+    fn build_metadata() -> BlockMetadata {
+        BlockMetadata {
+            id: "limit".into(),
+            name: "Limit".into(),
+            category: BlockCategory::Execution,
+            description: "Skips offset rows, emits limit rows, then stops its input".into(),
+            version: "1.0.0".into(),
+            documentation: BlockDocumentation {
+                overview: "LIMIT and OFFSET cut a result down to a window of rows: skip the \
+                           first `offset`, return the next `limit`. On its own that is trivial. \
+                           What makes LIMIT important is that a pipelined engine can stop \
+                           early: once the limit has its rows, nothing upstream needs to \
+                           produce another one. A `SELECT * FROM orders LIMIT 10` over a \
+                           billion-row table reads one page, not the table.\n\n\
+                           This block stops consuming input after emitting `limit` rows past \
+                           the offset. Run the pipeline with a batch size and the engine stops \
+                           feeding the limit, and the scans feeding it, as soon as it is done. \
+                           Run it in one pass and every upstream block still processes the \
+                           whole input — rows_discarded shows how much of that was wasted."
+                    .into(),
+                algorithm: "Limit:\n\
+                            \n\
+                            FUNCTION next_batch(batch):\n  \
+                              FOR EACH row IN batch:\n    \
+                                IF skipped < offset: skipped += 1; CONTINUE\n    \
+                                IF emitted == limit: discarded += 1; CONTINUE\n    \
+                                emit(row); emitted += 1\n  \
+                              IF emitted == limit:\n    \
+                                signal exhausted    // engine stops upstream"
+                    .into(),
+                complexity: Complexity {
+                    time: "O(offset + limit) rows consumed when pipelined".into(),
+                    space: "O(1)".into(),
+                },
+                use_cases: vec![
+                    "Top-N queries: ORDER BY ... LIMIT N".into(),
+                    "Pagination with LIMIT/OFFSET".into(),
+                    "EXISTS-style checks that only need the first row".into(),
+                ],
+                tradeoffs: vec![
+                    "Early termination only helps a pipelined engine; a materializing one has \
+                     already produced every row".into(),
+                    "Large offsets still read and throw away every skipped row — keyset \
+                     pagination (WHERE id > last_id) avoids that".into(),
+                    "Below a Sort, nothing can stop early: the sort needs all its input before \
+                     emitting the first row".into(),
+                ],
+                examples: vec![
+                    "PostgreSQL Limit node — stops pulling from its child once satisfied".into(),
+                    "SQL Server TOP, Oracle FETCH FIRST n ROWS ONLY".into(),
+                ],
+                motivation: "LIMIT is the clearest demonstration of why engines pipeline rows \
+                             instead of materializing each operator's full output. Compare a \
+                             batched run with a one-pass run of the same plan: the limit's \
+                             output is identical, but the scan upstream does a fraction of the \
+                             work."
+                    .into(),
+                parameter_guide: HashMap::from([
+                    ("limit".into(), "How many rows to emit after the offset. Once that many \
+                                      have gone out the block is done and, under batched \
+                                      execution, the pipeline feeding it stops. 0 emits \
+                                      nothing.".into()),
+                    ("offset".into(), "How many leading rows to skip before emitting. Skipped \
+                                       rows still have to be produced and read, so a large \
+                                       offset costs as much as returning those rows — the \
+                                       classic deep-pagination problem.".into()),
+                ]),
+                alternatives: vec![Alternative {
+                    block_type: "filter".into(),
+                    comparison: "A filter drops rows by predicate and must see every row; a \
+                                 limit drops rows by position and can stop early.".into(),
+                }],
+                suggested_questions: vec![
+                    "Why can LIMIT stop a table scan early but not a sort?".into(),
+                    "Why does OFFSET 100000 get slow, and what does keyset pagination do \
+                     instead?".into(),
+                ],
+            },
+            references: vec![Reference {
+                ref_type: ReferenceType::Paper,
+                title: "Volcano — An Extensible and Parallel Query Evaluation System".into(),
+                url: None,
+                citation: Some("Graefe, G. (1994). IEEE TKDE 6(1).".into()),
+            }],
+            icon: "scissors".into(),
+            color: "#0EA5E9".into(),
+        }
+    }
+
+    fn build_inputs() -> Vec<Port> {
+        vec![Port {
+            id: "records".into(), name: "Records".into(), port_type: PortType::DataStream,
+            direction: PortDirection::Input, required: true, multiple: false,
+            description: "Rows to limit".into(), schema: None,
+        }]
+    }
+
+    fn build_outputs() -> Vec<Port> {
+        vec![Port {
+            id: "limited".into(), name: "Limited Records".into(), port_type: PortType::DataStream,
+            direction: PortDirection::Output, required: false, multiple: true,
+            description: "At most `limit` rows after the offset".into(), schema: None,
+        }]
+    }
+
+    fn build_parameters() -> Vec<Parameter> {
+        vec![
+            Parameter {
+                id: "limit".into(), name: "Limit".into(), param_type: ParameterType::Number,
+                description: "Rows to emit after the offset".into(),
+                default_value: ParameterValue::Integer(10), required: true,
+                constraints: Some(ParameterConstraints::new().with_min(0.0)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+            Parameter {
+                id: "offset".into(), name: "Offset".into(), param_type: ParameterType::Number,
+                description: "Leading rows to skip".into(),
+                default_value: ParameterValue::Integer(0), required: false,
+                constraints: Some(ParameterConstraints::new().with_min(0.0)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+        ]
+    }
+
+    fn build_metrics() -> Vec<MetricDefinition> {
+        vec![
+            MetricDefinition { id: "rows_skipped".into(), name: "Rows Skipped".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Rows dropped to satisfy the offset".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "rows_emitted".into(), name: "Rows Emitted".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Rows passed on".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "rows_discarded".into(), name: "Rows Discarded".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Rows received after the limit was reached".into(), aggregations: vec![AggregationType::Sum] },
+        ]
+    }
+}
+
+impl Default for LimitBlock {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl Block for LimitBlock {
+    fn metadata(&self) -> &BlockMetadata { &self.metadata }
+    fn inputs(&self) -> &[Port] { &self.input_ports }
+    fn outputs(&self) -> &[Port] { &self.output_ports }
+    fn parameters(&self) -> &[Parameter] { &self.params }
+    fn requires(&self) -> &[Constraint] { &[] }
+    fn guarantees(&self) -> &[Guarantee] { &[] }
+    fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
+    fn reports_running_totals(&self) -> bool { false }
+    fn is_exhausted(&self) -> bool { self.started && self.emitted >= self.limit }
+
+    async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("limit").and_then(ParameterValue::as_integer) { self.limit = v as usize; }
+        if let Some(v) = params.get("offset").and_then(ParameterValue::as_integer) { self.offset = v as usize; }
+        self.skipped = 0;
+        self.emitted = 0;
+        self.started = false;
+        Ok(())
+    }
+
+    async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult, BlockError> {
+        let records: Vec<Record> = match context.inputs.get("records").cloned().unwrap_or(PortValue::None) {
+            PortValue::Stream(r) => r, PortValue::Batch(r) => r, PortValue::Single(r) => vec![r],
+            _ => Vec::new(),
+        };
+        self.started = true;
+
+        let total = records.len();
+        let skip = (self.offset - self.skipped).min(total);
+        let take = (self.limit - self.emitted).min(total - skip);
+        let limited: Vec<Record> = records.into_iter().skip(skip).take(take).collect();
+        let discarded = total - skip - take;
+        self.skipped += skip;
+        self.emitted += take;
+
+        context.metrics.record("rows_skipped", skip as f64);
+        context.metrics.record("rows_emitted", take as f64);
+        context.metrics.record("rows_discarded", discarded as f64);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("limited".into(), PortValue::Stream(limited));
+        let mut ms = HashMap::new();
+        ms.insert("rows_skipped".into(), skip as f64);
+        ms.insert("rows_emitted".into(), take as f64);
+        ms.insert("rows_discarded".into(), discarded as f64);
+
+        Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
+    }
+
+    fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
+        if inputs.get("records").is_none() { ValidationResult::ok().with_warning("records input not connected") }
+        else { ValidationResult::ok() }
+    }
+
+    fn get_state(&self) -> BlockState {
+        let mut state = BlockState::new();
+        let _ = state.insert("skipped".into(), self.skipped);
+        let _ = state.insert("emitted".into(), self.emitted);
+        let _ = state.insert("started".into(), self.started);
+        state
+    }
+
+    fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
+        if let Ok(Some(n)) = state.get::<usize>("skipped") { self.skipped = n; }
+        if let Ok(Some(n)) = state.get::<usize>("emitted") { self.emitted = n; }
+        if let Ok(Some(b)) = state.get::<bool>("started") { self.started = b; }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+    fn rows(range: std::ops::Range<i64>) -> Vec<Record> {
+        range.map(|i| { let mut r = Record::new(); r.insert("id".into(), i).unwrap(); r }).collect()
+    }
+
+    async fn limit(limit: i64, offset: i64) -> LimitBlock {
+        let mut block = LimitBlock::new();
+        let params = HashMap::from([
+            ("limit".to_string(), ParameterValue::Integer(limit)),
+            ("offset".to_string(), ParameterValue::Integer(offset)),
+        ]);
+        block.initialize(params).await.unwrap();
+        block
+    }
+
+    async fn feed(block: &mut LimitBlock, records: Vec<Record>) -> (Vec<i64>, ExecutionResult) {
+        let inputs = HashMap::from([("records".to_string(), PortValue::Batch(records))]);
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        let result = block.execute(ctx).await.unwrap();
+        let ids = match result.outputs.get("limited").unwrap() {
+            PortValue::Stream(r) => r.iter().map(|r| r.get::<i64>("id").unwrap().unwrap()).collect(),
+            _ => panic!("expected stream"),
+        };
+        (ids, result)
+    }
+
+    #[tokio::test]
+    async fn test_offset_and_limit_in_one_call() {
+        let mut block = limit(3, 2).await;
+        let (ids, result) = feed(&mut block, rows(0..10)).await;
+        assert_eq!(ids, vec![2, 3, 4]);
+        assert_eq!(result.metrics["rows_skipped"], 2.0);
+        assert_eq!(result.metrics["rows_emitted"], 3.0);
+        assert_eq!(result.metrics["rows_discarded"], 5.0);
+        assert!(block.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_offset_and_limit_span_batches() {
+        let mut block = limit(4, 3).await;
+        let (first, _) = feed(&mut block, rows(0..2)).await;
+        assert!(first.is_empty(), "still inside the offset");
+        let (second, _) = feed(&mut block, rows(2..5)).await;
+        assert_eq!(second, vec![3, 4]);
+        assert!(!block.is_exhausted());
+        let (third, result) = feed(&mut block, rows(5..10)).await;
+        assert_eq!(third, vec![5, 6]);
+        assert_eq!(result.metrics["rows_discarded"], 3.0);
+        assert!(block.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_limit_zero_is_exhausted_after_first_call() {
+        let mut block = limit(0, 0).await;
+        assert!(!block.is_exhausted(), "not before it has run");
+        let (ids, _) = feed(&mut block, rows(0..5)).await;
+        assert!(ids.is_empty());
+        assert!(block.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_negative_limit() {
+        let params = HashMap::from([("limit".to_string(), ParameterValue::Integer(-1))]);
+        assert!(LimitBlock::new().initialize(params).await.is_err());
+    }
+}
@@ -1,15 +1,53 @@
 //! Execution block implementations
 //!
-//! Execution blocks implement query processing operators like scans, joins, and filters.
+//! Execution blocks implement query processing operators like scans, joins, filters,
+//! projections, aggregations, deduplication, unions and limits, plus access path selection between
+//! index and sequential scans.
+
+use crate::core::registry::{BlockRegistry, RegistryError};
 
 pub mod sequential_scan;
 pub mod index_scan;
 pub mod filter;
 pub mod sort;
 pub mod hash_join;
+pub mod projection;
+pub mod aggregate;
+pub mod merge_join;
+pub mod nested_loop_join;
+pub mod limit;
+pub mod access_path;
+pub mod distinct;
+pub mod union;
 
 pub use sequential_scan::SequentialScanBlock;
 pub use index_scan::IndexScanBlock;
 pub use filter::FilterBlock;
 pub use sort::SortBlock;
 pub use hash_join::HashJoinBlock;
+pub use projection::ProjectionBlock;
+pub use aggregate::AggregateBlock;
+pub use merge_join::MergeJoinBlock;
+pub use nested_loop_join::NestedLoopJoinBlock;
+pub use limit::LimitBlock;
+pub use access_path::AccessPathBlock;
+pub use distinct::DistinctBlock;
+pub use union::UnionBlock;
+
+/// Register a factory for each execution block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(SequentialScanBlock::new()))?;
+    registry.register_factory(|| Box::new(IndexScanBlock::new()))?;
+    registry.register_factory(|| Box::new(FilterBlock::new()))?;
+    registry.register_factory(|| Box::new(SortBlock::new()))?;
+    registry.register_factory(|| Box::new(HashJoinBlock::new()))?;
+    registry.register_factory(|| Box::new(ProjectionBlock::new()))?;
+    registry.register_factory(|| Box::new(AggregateBlock::new()))?;
+    registry.register_factory(|| Box::new(MergeJoinBlock::new()))?;
+    registry.register_factory(|| Box::new(NestedLoopJoinBlock::new()))?;
+    registry.register_factory(|| Box::new(LimitBlock::new()))?;
+    registry.register_factory(|| Box::new(AccessPathBlock::new()))?;
+    registry.register_factory(|| Box::new(DistinctBlock::new()))?;
+    registry.register_factory(|| Box::new(UnionBlock::new()))?;
+    Ok(())
+}
@@ -0,0 +1,566 @@
+//! Nested-Loop Join Execution Block
+//!
+//! The fallback join: for every row of the outer input, look at the inner
+//! input and emit each pair that satisfies `join_predicate`. Works for any
+//! comparison, not just equality, and for tiny inputs it is the cheapest
+//! join there is.
+//!
+//! ## How it works
+//!
+//! The predicate reads `outer.outer_key <op> inner.inner_key`, with `op` one
+//! of eq, ne, lt, le, gt, ge. Null or absent keys never match.
+//!
+//! - **Plain nested loop** — each outer row scans the whole inner input, so
+//!   the join makes `outer × inner` comparisons.
+//! - **Index nested loop** — when the optional `inner_index` port is
+//!   connected, it carries index entries for the inner input: records with
+//!   the `inner_key` column and the `_page_id`/`_slot_id` of the inner row
+//!   (the shape a B-tree lookup emits). Each outer row then binary-searches
+//!   those entries and fetches only the matching inner rows by tuple id,
+//!   making about `log2(entries)` comparisons per outer row. `ne` cannot use
+//!   an index and falls back to a scan.
+//!
+//! Output columns are prefixed `outer_` and `inner_`.
+//!
+//! ## Metrics tracked
+//!
+//! | Metric | Type | Description |
+//! |--------|------|-------------|
+//! | `inner_scans` | Counter | Full passes over the inner input |
+//! | `index_lookups` | Counter | Outer rows answered through the index |
+//! | `rows_compared` | Counter | Predicate or index-key comparisons |
+//! | `rows_emitted` | Counter | Joined rows produced |
+
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::core::block::{
+    Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
+    Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
+};
+use crate::core::constraint::{Constraint, Guarantee};
+use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
+use crate::core::parameter::{
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    ValidationResult, WidgetType,
+};
+use crate::core::port::{cmp_json, Port, PortDirection, PortType, PortValue, Record};
+
+/// `outer.outer_key <op> inner.inner_key`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinPredicate {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Values accepted by the `join_predicate` parameter.
+const PREDICATES: &[&str] = &["eq", "ne", "lt", "le", "gt", "ge"];
+
+impl JoinPredicate {
+    fn parse(s: &str) -> Self {
+        match s {
+            "ne" => JoinPredicate::Ne,
+            "lt" => JoinPredicate::Lt,
+            "le" => JoinPredicate::Le,
+            "gt" => JoinPredicate::Gt,
+            "ge" => JoinPredicate::Ge,
+            _ => JoinPredicate::Eq,
+        }
+    }
+
+    /// Whether `outer <op> inner` holds, given `cmp_json(outer, inner)`.
+    fn holds(self, ord: Ordering) -> bool {
+        match self {
+            JoinPredicate::Eq => ord == Ordering::Equal,
+            JoinPredicate::Ne => ord != Ordering::Equal,
+            JoinPredicate::Lt => ord == Ordering::Less,
+            JoinPredicate::Le => ord != Ordering::Greater,
+            JoinPredicate::Gt => ord == Ordering::Greater,
+            JoinPredicate::Ge => ord != Ordering::Less,
+        }
+    }
+}
+
+pub struct NestedLoopJoinBlock {
+    metadata: BlockMetadata,
+    input_ports: Vec<Port>,
+    output_ports: Vec<Port>,
+    params: Vec<Parameter>,
+    metric_defs: Vec<MetricDefinition>,
+
+    outer_key: String,
+    inner_key: String,
+    predicate: JoinPredicate,
+}
+
+impl NestedLoopJoinBlock {
+    pub fn new() -> Self {
+        Self {
+            metadata: Self::build_metadata(),
+            input_ports: Self::build_inputs(),
+            output_ports: Self::build_outputs(),
+            params: Self::build_parameters(),
+            metric_defs: Self::build_metrics(),
+            outer_key: "id".into(),
+            inner_key: "id".into(),
+            predicate: JoinPredicate::Eq,
+        }
+    }
+
+    fn build_metadata() -> BlockMetadata {
+        BlockMetadata {
+            id: "nested-loop-join".into(),
+            name: "Nested Loop Join".into(),
+            category: BlockCategory::Execution,
+            description: "Compares every outer row with the inner input, optionally through an index"
+                .into(),
+            version: "1.0.0".into(),
+            documentation: BlockDocumentation {
+                overview: "A nested-loop join is the simplest join: two nested FOR loops. For \
+                           each row of the outer input, walk the inner input and emit every pair \
+                           that satisfies the join predicate. It needs no hash table and no \
+                           sorted input, and it works for any predicate — equality, ranges \
+                           (a.start <= b.ts), or inequality — which hash and merge joins cannot \
+                           all handle.\n\n\
+                           The price is O(n × m) comparisons. That is fine when one side has a \
+                           handful of rows and ruinous when both are large.\n\n\
+                           The index nested-loop join fixes the inner loop: if the inner input \
+                           has an index on the join column, each outer row does an O(log m) \
+                           index lookup instead of an O(m) scan and fetches only the matching \
+                           rows. For a small outer input probing a large indexed table, this is \
+                           often the best plan of all — it is how most OLTP joins run."
+                    .into(),
+                algorithm: "Nested-Loop Join:\n\
+                            \n\
+                            FUNCTION nested_loop_join(outer, inner, op):\n  \
+                              FOR EACH o IN outer:\n    \
+                                FOR EACH i IN inner:          // one inner scan per outer row\n      \
+                                  IF o.outer_key <op> i.inner_key:\n        \
+                                    emit(merge(o, i))\n\
+                            \n\
+                            Index Nested-Loop Join:\n\
+                            \n\
+                            FUNCTION index_nested_loop_join(outer, index, inner, op):\n  \
+                              FOR EACH o IN outer:\n    \
+                                entries = index.search(o.outer_key, op)  // O(log m)\n    \
+                                FOR EACH e IN entries:\n      \
+                                  emit(merge(o, inner.fetch(e.tuple_id)))"
+                    .into(),
+                complexity: Complexity {
+                    time: "O(n × m) plain; O(n log m + matches) with an index".into(),
+                    space: "O(1) beyond the inputs".into(),
+                },
+                use_cases: vec![
+                    "Joins where one input has only a few rows".into(),
+                    "Non-equi joins (range, inequality) that hash joins cannot answer".into(),
+                    "OLTP lookups: a few outer rows probing a large indexed table".into(),
+                    "Correlated subqueries, which are nested loops by definition".into(),
+                ],
+                tradeoffs: vec![
+                    "Supports any predicate, but costs outer × inner comparisons without an index"
+                        .into(),
+                    "With an index on the inner join column, cost falls to one index lookup per \
+                     outer row".into(),
+                    "Outer order is preserved, so an ordered outer input stays ordered".into(),
+                    "The optimizer should put the smaller input on the outside".into(),
+                ],
+                examples: vec![
+                    "PostgreSQL Nested Loop, often over an Index Scan as the inner side".into(),
+                    "MySQL — nested loops were its only join algorithm before 8.0.18".into(),
+                    "SQLite — every join is a nested loop, made fast by automatic indexes".into(),
+                ],
+                motivation: "Hash and merge joins only answer equality predicates and need memory \
+                             or sorted input. The nested loop has no such requirements, which \
+                             makes it the universal fallback, and with an index on the inner side \
+                             it is the fastest way to join a few rows against a big table. \
+                             Comparing its rows_compared with and without inner_index shows why \
+                             indexes matter so much for joins."
+                    .into(),
+                parameter_guide: HashMap::from([
+                    ("outer_key".into(), "The join column of the outer input — the left side of \
+                                          the predicate. Each outer row is compared against the \
+                                          inner input once. Put the smaller input on the \
+                                          outside.".into()),
+                    ("inner_key".into(), "The join column of the inner input — the right side \
+                                          of the predicate. When inner_index is connected, its \
+                                          entries must carry this column along with \
+                                          _page_id/_slot_id of the inner rows.".into()),
+                    ("join_predicate".into(), "How the keys must compare for a pair to join: \
+                                               eq (=), ne (<>), lt (<), le (<=), gt (>) or ge \
+                                               (>=), read as outer_key <op> inner_key. Range \
+                                               predicates are where the nested loop shines, \
+                                               since hash joins cannot evaluate them. ne cannot \
+                                               use an index.".into()),
+                ]),
+                alternatives: vec![
+                    Alternative {
+                        block_type: "hash-join".into(),
+                        comparison: "A hash join answers equality predicates in O(n + m) by \
+                                     hashing one side, but cannot handle range or inequality \
+                                     predicates and needs memory for its hash table.".into(),
+                    },
+                    Alternative {
+                        block_type: "merge-join".into(),
+                        comparison: "A merge join joins sorted inputs in one pass. It beats a \
+                                     plain nested loop on large sorted inputs; an index nested \
+                                     loop still wins when the outer side is tiny.".into(),
+                    },
+                ],
+                suggested_questions: vec![
+                    "Why does the optimizer put the smaller input on the outside of a nested \
+                     loop?".into(),
+                    "How does an index on the inner join column change a nested-loop join's \
+                     cost?".into(),
+                    "Which join algorithms can evaluate a range predicate like a.ts BETWEEN \
+                     b.start AND b.end?".into(),
+                ],
+            },
+            references: vec![Reference {
+                ref_type: ReferenceType::Book,
+                title: "Database System Concepts — Chapter 15: Join Algorithms".into(),
+                url: None,
+                citation: Some("Silberschatz, A. et al. (2019). McGraw-Hill.".into()),
+            }],
+            icon: "merge".into(),
+            color: "#0EA5E9".into(),
+        }
+    }
+
+    fn build_inputs() -> Vec<Port> {
+        vec![
+            Port {
+                id: "outer".into(), name: "Outer".into(), port_type: PortType::DataStream,
+                direction: PortDirection::Input, required: true, multiple: false,
+                description: "Outer input — iterated once".into(), schema: None,
+            },
+            Port {
+                id: "inner".into(), name: "Inner".into(), port_type: PortType::DataStream,
+                direction: PortDirection::Input, required: true, multiple: false,
+                description: "Inner input — scanned, or fetched by tuple id via the index".into(),
+                schema: None,
+            },
+            Port {
+                id: "inner_index".into(), name: "Inner Index".into(), port_type: PortType::DataStream,
+                direction: PortDirection::Input, required: false, multiple: false,
+                description: "Index entries for the inner input (inner_key, _page_id, _slot_id)".into(),
+                schema: None,
+            },
+        ]
+    }
+
+    fn build_outputs() -> Vec<Port> {
+        vec![Port {
+            id: "joined".into(), name: "Joined Records".into(), port_type: PortType::DataStream,
+            direction: PortDirection::Output, required: false, multiple: true,
+            description: "Matched rows from both inputs combined".into(), schema: None,
+        }]
+    }
+
+    fn build_parameters() -> Vec<Parameter> {
+        vec![
+            Parameter {
+                id: "outer_key".into(), name: "Outer Key".into(), param_type: ParameterType::String,
+                description: "Join column of the outer input".into(),
+                default_value: ParameterValue::String("id".into()), required: true, constraints: None,
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+            Parameter {
+                id: "inner_key".into(), name: "Inner Key".into(), param_type: ParameterType::String,
+                description: "Join column of the inner input".into(),
+                default_value: ParameterValue::String("id".into()), required: true, constraints: None,
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+            Parameter {
+                id: "join_predicate".into(), name: "Join Predicate".into(), param_type: ParameterType::Enum,
+                description: "outer_key <op> inner_key (eq, ne, lt, le, gt, ge)".into(),
+                default_value: ParameterValue::String("eq".into()), required: true,
+                constraints: Some(ParameterConstraints::new().with_choices(PREDICATES)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+            },
+        ]
+    }
+
+    fn build_metrics() -> Vec<MetricDefinition> {
+        vec![
+            MetricDefinition { id: "inner_scans".into(), name: "Inner Scans".into(), metric_type: MetricType::Counter, unit: "scans".into(), description: "Full passes over the inner input".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "index_lookups".into(), name: "Index Lookups".into(), metric_type: MetricType::Counter, unit: "ops".into(), description: "Outer rows answered through the index".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "rows_compared".into(), name: "Rows Compared".into(), metric_type: MetricType::Counter, unit: "ops".into(), description: "Predicate or index-key comparisons".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "rows_emitted".into(), name: "Rows Emitted".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Joined rows produced".into(), aggregations: vec![AggregationType::Sum] },
+        ]
+    }
+
+    /// Index entries sorted by key, paired with the `(page, slot)` they point at.
+    fn sorted_entries<'a>(&self, entries: &'a [Record]) -> Vec<(&'a JsonValue, (usize, usize))> {
+        let mut sorted: Vec<_> = entries
+            .iter()
+            .filter(|e| !e.get_or_null(&self.inner_key).is_null())
+            .map(|e| (e.get_or_null(&self.inner_key), tuple_id(e)))
+            .collect();
+        sorted.sort_by(|a, b| cmp_json(a.0, b.0));
+        sorted
+    }
+
+    /// Range of `entries` whose key satisfies `key <op> entry`, found by
+    /// binary search. `ne` is not answerable and yields `None`.
+    fn index_range(
+        &self,
+        entries: &[(&JsonValue, (usize, usize))],
+        key: &JsonValue,
+        compared: &mut usize,
+    ) -> Option<std::ops::Range<usize>> {
+        // First entry >= key (inclusive) or > key (exclusive).
+        let mut bound = |inclusive: bool| {
+            entries.partition_point(|(k, _)| {
+                *compared += 1;
+                match cmp_json(k, key) {
+                    Ordering::Less => true,
+                    Ordering::Equal => !inclusive,
+                    Ordering::Greater => false,
+                }
+            })
+        };
+        Some(match self.predicate {
+            JoinPredicate::Eq => bound(true)..bound(false),
+            JoinPredicate::Lt => bound(false)..entries.len(),
+            JoinPredicate::Le => bound(true)..entries.len(),
+            JoinPredicate::Gt => 0..bound(true),
+            JoinPredicate::Ge => 0..bound(false),
+            JoinPredicate::Ne => return None,
+        })
+    }
+}
+
+fn tuple_id(record: &Record) -> (usize, usize) {
+    let page_id = record.get::<usize>("_page_id").ok().flatten().unwrap_or(0);
+    let slot_id = record.get::<usize>("_slot_id").ok().flatten().unwrap_or(0);
+    (page_id, slot_id)
+}
+
+fn combine(outer: &Record, inner: &Record) -> Record {
+    let mut combined = Record::new();
+    for (k, v) in &outer.data {
+        combined.data.insert(format!("outer_{}", k), v.clone());
+    }
+    for (k, v) in &inner.data {
+        combined.data.insert(format!("inner_{}", k), v.clone());
+    }
+    combined
+}
+
+impl Default for NestedLoopJoinBlock {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl Block for NestedLoopJoinBlock {
+    fn metadata(&self) -> &BlockMetadata { &self.metadata }
+    fn inputs(&self) -> &[Port] { &self.input_ports }
+    fn outputs(&self) -> &[Port] { &self.output_ports }
+    fn parameters(&self) -> &[Parameter] { &self.params }
+    fn requires(&self) -> &[Constraint] { &[] }
+    fn guarantees(&self) -> &[Guarantee] { &[] }
+    fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
+    fn reports_running_totals(&self) -> bool { false }
+
+    async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
+        if let Some(s) = params.get("outer_key").and_then(ParameterValue::as_string) { self.outer_key = s.to_string(); }
+        if let Some(s) = params.get("inner_key").and_then(ParameterValue::as_string) { self.inner_key = s.to_string(); }
+        if let Some(s) = enum_param(&params, "join_predicate", PREDICATES)? { self.predicate = JoinPredicate::parse(s); }
+        Ok(())
+    }
+
+    async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult, BlockError> {
+        let extract = |key: &str| -> Vec<Record> {
+            match context.inputs.get(key).cloned().unwrap_or(PortValue::None) {
+                PortValue::Stream(r) => r, PortValue::Batch(r) => r, PortValue::Single(r) => vec![r],
+                _ => Vec::new(),
+            }
+        };
+        let outer = extract("outer");
+        let inner = extract("inner");
+        let index_entries = extract("inner_index");
+        let use_index = context.inputs.contains_key("inner_index");
+
+        let entries = self.sorted_entries(&index_entries);
+        let by_tid: HashMap<(usize, usize), &Record> = if use_index {
+            inner.iter().map(|r| (tuple_id(r), r)).collect()
+        } else {
+            HashMap::new()
+        };
+
+        let mut joined = Vec::new();
+        let (mut inner_scans, mut index_lookups, mut compared) = (0usize, 0usize, 0usize);
+        for o in &outer {
+            let key = o.get_or_null(&self.outer_key);
+            if key.is_null() {
+                continue;
+            }
+            if use_index {
+                if let Some(range) = self.index_range(&entries, key, &mut compared) {
+                    index_lookups += 1;
+                    for (_, tid) in &entries[range] {
+                        if let Some(i) = by_tid.get(tid) {
+                            joined.push(combine(o, i));
+                        }
+                    }
+                    continue;
+                }
+            }
+            inner_scans += 1;
+            for i in &inner {
+                let inner_key = i.get_or_null(&self.inner_key);
+                if inner_key.is_null() {
+                    continue;
+                }
+                compared += 1;
+                if self.predicate.holds(cmp_json(key, inner_key)) {
+                    joined.push(combine(o, i));
+                }
+            }
+        }
+
+        let rows_emitted = joined.len();
+        context.metrics.record("inner_scans", inner_scans as f64);
+        context.metrics.record("index_lookups", index_lookups as f64);
+        context.metrics.record("rows_compared", compared as f64);
+        context.metrics.record("rows_emitted", rows_emitted as f64);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("joined".into(), PortValue::Stream(joined));
+        let mut ms = HashMap::new();
+        ms.insert("inner_scans".into(), inner_scans as f64);
+        ms.insert("index_lookups".into(), index_lookups as f64);
+        ms.insert("rows_compared".into(), compared as f64);
+        ms.insert("rows_emitted".into(), rows_emitted as f64);
+
+        Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
+    }
+
+    fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
+        let has_outer = inputs.get("outer").is_some();
+        let has_inner = inputs.get("inner").is_some();
+        if !has_outer && !has_inner { ValidationResult::ok().with_warning("Neither outer nor inner connected") }
+        else if !has_outer { ValidationResult::ok().with_warning("outer input not connected") }
+        else if !has_inner { ValidationResult::ok().with_warning("inner input not connected") }
+        else { ValidationResult::ok() }
+    }
+    fn state_round_trips(&self) -> bool { true }
+    fn get_state(&self) -> BlockState { BlockState::new() }
+    fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Inner rows as a heap would store them: `id` plus a tuple id.
+    fn inner_rows(n: usize) -> Vec<Record> {
+        (0..n).map(|i| {
+            let mut r = Record::new();
+            r.insert("id".into(), i as i64).unwrap();
+            r.insert("_page_id".into(), i / 10).unwrap();
+            r.insert("_slot_id".into(), i % 10).unwrap();
+            r
+        }).collect()
+    }
+
+    /// Index entries over the inner rows, in arbitrary order.
+    fn index_of(inner: &[Record]) -> Vec<Record> {
+        inner.iter().rev().map(|r| {
+            let mut e = Record::new();
+            for col in ["id", "_page_id", "_slot_id"] {
+                e.data.insert(col.into(), r.data[col].clone());
+            }
+            e
+        }).collect()
+    }
+
+    fn outer_rows(keys: &[JsonValue]) -> Vec<Record> {
+        keys.iter().map(|k| { let mut r = Record::new(); r.data.insert("ref".into(), k.clone()); r }).collect()
+    }
+
+    async fn join(predicate: &str, inputs: HashMap<String, PortValue>) -> ExecutionResult {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        let mut nl = NestedLoopJoinBlock::new();
+        let params = HashMap::from([
+            ("outer_key".to_string(), ParameterValue::from("ref")),
+            ("join_predicate".to_string(), ParameterValue::from(predicate)),
+        ]);
+        nl.initialize(params).await.unwrap();
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        nl.execute(ctx).await.unwrap()
+    }
+
+    fn inputs(outer: Vec<Record>, inner: Vec<Record>, index: Option<Vec<Record>>) -> HashMap<String, PortValue> {
+        let mut inputs = HashMap::from([
+            ("outer".to_string(), PortValue::Stream(outer)),
+            ("inner".to_string(), PortValue::Stream(inner)),
+        ]);
+        if let Some(index) = index {
+            inputs.insert("inner_index".into(), PortValue::Stream(index));
+        }
+        inputs
+    }
+
+    fn inner_ids(result: &ExecutionResult) -> Vec<i64> {
+        let out = match result.outputs.get("joined").unwrap() { PortValue::Stream(r) => r.clone(), _ => panic!() };
+        let mut ids: Vec<i64> = out.iter().map(|r| r.get::<i64>("inner_id").unwrap().unwrap()).collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_plain_nested_loop_scans_inner_once_per_outer_row() {
+        let outer = outer_rows(&[json!(3), json!(7), JsonValue::Null]);
+        let result = join("eq", inputs(outer.clone(), inner_rows(50), None)).await;
+        assert_eq!(inner_ids(&result), vec![3, 7]);
+        assert_eq!(result.metrics["inner_scans"], 2.0, "null outer keys are skipped");
+        assert_eq!(result.metrics["rows_compared"], 100.0);
+
+        // Range predicate: outer.ref > inner.id
+        let result = join("gt", inputs(outer_rows(&[json!(3)]), inner_rows(50), None)).await;
+        assert_eq!(inner_ids(&result), vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_index_nested_loop_matches_plain_with_far_fewer_comparisons() {
+        let inner = inner_rows(1000);
+        let outer = outer_rows(&[json!(5), json!(500), json!(998), json!(2000)]);
+        for predicate in ["eq", "lt", "le", "gt", "ge"] {
+            let plain = join(predicate, inputs(outer.clone(), inner.clone(), None)).await;
+            let indexed = join(predicate, inputs(outer.clone(), inner.clone(), Some(index_of(&inner)))).await;
+            assert_eq!(inner_ids(&indexed), inner_ids(&plain), "{}", predicate);
+            assert_eq!(indexed.metrics["inner_scans"], 0.0);
+            assert_eq!(indexed.metrics["index_lookups"], 4.0);
+            assert!(indexed.metrics["rows_compared"] < 100.0, "{}: {}", predicate, indexed.metrics["rows_compared"]);
+            assert_eq!(plain.metrics["rows_compared"], 4000.0);
+        }
+
+        // `ne` cannot use the index and falls back to scanning.
+        let ne = join("ne", inputs(outer_rows(&[json!(5)]), inner.clone(), Some(index_of(&inner)))).await;
+        assert_eq!(ne.metrics["inner_scans"], 1.0);
+        assert_eq!(ne.metrics["rows_emitted"], 999.0);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unknown_predicate() {
+        let params = HashMap::from([("join_predicate".to_string(), ParameterValue::from("between"))]);
+        assert!(NestedLoopJoinBlock::new().initialize(params).await.is_err());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let nl = NestedLoopJoinBlock::new();
+        assert_eq!(nl.metadata().id, "nested-loop-join");
+        assert_eq!(nl.metadata().category, BlockCategory::Execution);
+        assert_eq!(nl.inputs().len(), 3);
+    }
+}
@@ -0,0 +1,416 @@
+//! Projection Execution Block
+//!
+//! Keeps only the listed columns of each input record, optionally renaming
+//! them (`"name AS customer"`), and drops everything else so narrower rows
+//! flow to downstream operators.
+//!
+//! ## How it works
+//!
+//! The `columns` list is parsed once at initialization into
+//! `(source, output)` pairs. Each record is rebuilt from those pairs in
+//! order. A listed column the record lacks stays absent in the output rather
+//! than becoming null, so downstream blocks can still tell the two apart.
+//! Bytes are measured as the JSON size of each record before and after.
+//!
+//! ## Metrics tracked
+//!
+//! | Metric | Type | Description |
+//! |--------|------|-------------|
+//! | `rows_projected` | Counter | Records passed through the projection |
+//! | `bytes_in` | Counter | JSON bytes of the input records |
+//! | `bytes_out` | Counter | JSON bytes of the projected records |
+//! | `bytes_reduced` | Counter | `bytes_in - bytes_out` |
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use crate::core::block::{
+    Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
+    Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
+};
+use crate::core::constraint::{Constraint, Guarantee};
+use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
+use crate::core::parameter::{
+    list_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    ValidationResult, WidgetType,
+};
+use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
+
+// ---------------------------------------------------------------------------
+// ProjectionBlock
+// ---------------------------------------------------------------------------
+
+pub struct ProjectionBlock {
+    metadata: BlockMetadata,
+    input_ports: Vec<Port>,
+    output_ports: Vec<Port>,
+    params: Vec<Parameter>,
+    metric_defs: Vec<MetricDefinition>,
+
+    /// `(source column, output name)` pairs in output order.
+    columns: Vec<(String, String)>,
+}
+
+impl ProjectionBlock {
+    pub fn new() -> Self {
+        Self {
+            metadata: Self::build_metadata(),
+            input_ports: Self::build_inputs(),
+            output_ports: Self::build_outputs(),
+            params: Self::build_parameters(),
+            metric_defs: Self::build_metrics(),
+            columns: vec![("id".into(), "id".into())],
+        }
+    }
+
+    fn build_metadata() -> BlockMetadata {
+        BlockMetadata {
+            id: "projection".into(),
+            name: "Projection".into(),
+            category: BlockCategory::Execution,
+            description: "Keeps only the selected columns, optionally renaming them".into(),
+            version: "1.0.0".into(),
+            documentation: BlockDocumentation {
+                overview: "A projection operator selects which columns of each row continue \
+                           through the query. In SQL terms, it is the SELECT list: \
+                           `SELECT id, name AS customer FROM ...` keeps two columns and renames \
+                           one, discarding every other column the scan produced.\n\n\
+                           Like a filter, a projection is a streaming operator that handles one \
+                           row at a time. Where a filter reduces the number of rows, a \
+                           projection reduces the width of each row. Narrower rows mean less \
+                           memory in sort buffers and hash tables, fewer bytes shipped between \
+                           operators, and — when pushed into a columnar scan — fewer columns \
+                           read from disk at all."
+                    .into(),
+                algorithm: "Projection Algorithm:\n\
+                            \n\
+                            FUNCTION project(records, columns):\n  \
+                              // columns: list of (source, output) pairs\n  \
+                              results = []\n  \
+                              FOR EACH record IN records:\n    \
+                                out = {}\n    \
+                                FOR EACH (source, output) IN columns:\n      \
+                                  IF source IN record:\n        \
+                                    out[output] = record[source]\n    \
+                                results.append(out)\n  \
+                              RETURN results"
+                    .into(),
+                complexity: Complexity {
+                    time: "O(n × k) — k selected columns per row".into(),
+                    space: "O(1) — streaming, no buffering".into(),
+                },
+                use_cases: vec![
+                    "SELECT list evaluation".into(),
+                    "Dropping wide columns before a sort or hash join".into(),
+                    "Renaming columns so two inputs line up for a join or union".into(),
+                ],
+                tradeoffs: vec![
+                    "Cheap per row, and saves memory and bandwidth in every operator above it".into(),
+                    "In a row store the full row is still read from disk; only a columnar scan \
+                     turns a narrow projection into less I/O".into(),
+                    "Projecting too early can drop a column a later operator needs".into(),
+                ],
+                examples: vec![
+                    "PostgreSQL — the target list of a plan node, shown as Output: in EXPLAIN \
+                     VERBOSE".into(),
+                    "Spark SQL Project node — column pruning pushes it down to Parquet scans".into(),
+                ],
+                motivation: "Queries rarely need every column of a table. Carrying unused \
+                             columns through a plan wastes memory in every buffering operator \
+                             (sort, hash join, aggregation) and bytes in every hand-off. A \
+                             projection trims rows to what the query actually uses, and the \
+                             bytes_reduced metric shows how much that saves."
+                    .into(),
+                parameter_guide: HashMap::from([
+                    ("columns".into(), "The columns to keep, in output order. Write \"name AS \
+                                        customer\" to rename a column on the way through. \
+                                        Columns not listed are dropped; a listed column that a \
+                                        record lacks is left out of that record rather than set \
+                                        to null. Try projecting a wide record down to one or two \
+                                        columns and watch bytes_reduced.".into()),
+                ]),
+                alternatives: vec![
+                    Alternative {
+                        block_type: "columnar-storage".into(),
+                        comparison: "Columnar storage's projection parameter reads only the \
+                                     listed columns from disk, so unused columns cost no I/O. \
+                                     A projection block trims rows after they have been read, \
+                                     which works with any source but saves only downstream \
+                                     memory and bandwidth.".into(),
+                    },
+                    Alternative {
+                        block_type: "covering-index".into(),
+                        comparison: "A covering index answers a query from the index alone when \
+                                     it includes every projected column, avoiding the table \
+                                     entirely.".into(),
+                    },
+                ],
+                suggested_questions: vec![
+                    "Why does projection pushdown save I/O in a column store but not in a \
+                     row store?".into(),
+                    "Where in a query plan should a projection go, and what goes wrong if it \
+                     drops a column too early?".into(),
+                ],
+            },
+            references: vec![Reference {
+                ref_type: ReferenceType::Book,
+                title: "Database System Concepts — Chapter 15: Query Processing".into(),
+                url: None,
+                citation: Some("Silberschatz, A. et al. (2019). McGraw-Hill.".into()),
+            }],
+            icon: "columns".into(),
+            color: "#EC4899".into(),
+        }
+    }
+
+    fn build_inputs() -> Vec<Port> {
+        vec![Port {
+            id: "records".into(),
+            name: "Records".into(),
+            port_type: PortType::DataStream,
+            direction: PortDirection::Input,
+            required: true,
+            multiple: false,
+            description: "Records to project".into(),
+            schema: None,
+        }]
+    }
+
+    fn build_outputs() -> Vec<Port> {
+        vec![Port {
+            id: "projected".into(),
+            name: "Projected Records".into(),
+            port_type: PortType::DataStream,
+            direction: PortDirection::Output,
+            required: false,
+            multiple: true,
+            description: "Records with only the selected columns".into(),
+            schema: None,
+        }]
+    }
+
+    fn build_parameters() -> Vec<Parameter> {
+        vec![Parameter {
+            id: "columns".into(),
+            name: "Columns".into(),
+            param_type: ParameterType::Array,
+            description: "Columns to keep, each 'col' or 'col AS alias'".into(),
+            default_value: ParameterValue::Array(vec![ParameterValue::from("id")]),
+            required: true,
+            constraints: Some(columns_constraints()),
+            ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+        }]
+    }
+
+    fn build_metrics() -> Vec<MetricDefinition> {
+        vec![
+            MetricDefinition {
+                id: "rows_projected".into(),
+                name: "Rows Projected".into(),
+                metric_type: MetricType::Counter,
+                unit: "rows".into(),
+                description: "Records passed through the projection".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "bytes_in".into(),
+                name: "Bytes In".into(),
+                metric_type: MetricType::Counter,
+                unit: "bytes".into(),
+                description: "JSON bytes of the input records".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "bytes_out".into(),
+                name: "Bytes Out".into(),
+                metric_type: MetricType::Counter,
+                unit: "bytes".into(),
+                description: "JSON bytes of the projected records".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "bytes_reduced".into(),
+                name: "Bytes Reduced".into(),
+                metric_type: MetricType::Counter,
+                unit: "bytes".into(),
+                description: "Bytes dropped by the projection".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+        ]
+    }
+
+    fn project(&self, record: &Record) -> Record {
+        let mut out = Record::new();
+        for (source, output) in &self.columns {
+            if let Some(value) = record.data.get(source) {
+                out.data.insert(output.clone(), value.clone());
+            }
+        }
+        out
+    }
+}
+
+/// Constraints on the `columns` list: at least one column name.
+fn columns_constraints() -> ParameterConstraints {
+    ParameterConstraints::new()
+        .with_element_type(ParameterType::String)
+        .with_length_range(Some(1), None)
+}
+
+/// Parse `columns` entries into `(source, output)` pairs. `AS` is matched
+/// case-insensitively; output names must be unique.
+fn parse_columns(items: &[ParameterValue]) -> Result<Vec<(String, String)>, BlockError> {
+    let mut columns: Vec<(String, String)> = Vec::with_capacity(items.len());
+    for item in items.iter().filter_map(ParameterValue::as_string) {
+        let words: Vec<&str> = item.split_whitespace().collect();
+        let (source, output) = match words.as_slice() {
+            [col] => (*col, *col),
+            [col, kw, alias] if kw.eq_ignore_ascii_case("as") => (*col, *alias),
+            _ => {
+                return Err(BlockError::InvalidParameter(format!(
+                    "columns entry '{}' must be 'col' or 'col AS alias'",
+                    item
+                )))
+            }
+        };
+        if columns.iter().any(|(_, o)| o == output) {
+            return Err(BlockError::InvalidParameter(format!(
+                "columns outputs '{}' more than once",
+                output
+            )));
+        }
+        columns.push((source.to_string(), output.to_string()));
+    }
+    Ok(columns)
+}
+
+impl Default for ProjectionBlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Block for ProjectionBlock {
+    fn metadata(&self) -> &BlockMetadata { &self.metadata }
+    fn inputs(&self) -> &[Port] { &self.input_ports }
+    fn outputs(&self) -> &[Port] { &self.output_ports }
+    fn parameters(&self) -> &[Parameter] { &self.params }
+    fn requires(&self) -> &[Constraint] { &[] }
+    fn guarantees(&self) -> &[Guarantee] { &[] }
+    fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
+    fn reports_running_totals(&self) -> bool { false }
+
+    async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
+        if let Some(items) = list_param(&params, "columns", &columns_constraints())? {
+            self.columns = parse_columns(&items)?;
+        }
+        Ok(())
+    }
+
+    async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult, BlockError> {
+        let records = match context.inputs.get("records").cloned().unwrap_or(PortValue::None) {
+            PortValue::Stream(r) => r, PortValue::Batch(r) => r, PortValue::Single(r) => vec![r],
+            PortValue::None => Vec::new(),
+            _ => return Err(BlockError::InvalidInput("Expected DataStream".into())),
+        };
+
+        let rows = records.len();
+        let mut bytes_in = 0usize;
+        let mut bytes_out = 0usize;
+        let projected: Vec<Record> = records
+            .iter()
+            .map(|r| {
+                let out = self.project(r);
+                bytes_in += r.json_size();
+                bytes_out += out.json_size();
+                out
+            })
+            .collect();
+        let bytes_reduced = bytes_in.saturating_sub(bytes_out);
+
+        context.metrics.record("rows_projected", rows as f64);
+        context.metrics.record("bytes_in", bytes_in as f64);
+        context.metrics.record("bytes_out", bytes_out as f64);
+        context.metrics.record("bytes_reduced", bytes_reduced as f64);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("projected".into(), PortValue::Stream(projected));
+        let mut ms = HashMap::new();
+        ms.insert("rows_projected".into(), rows as f64);
+        ms.insert("bytes_in".into(), bytes_in as f64);
+        ms.insert("bytes_out".into(), bytes_out as f64);
+        ms.insert("bytes_reduced".into(), bytes_reduced as f64);
+
+        Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
+    }
+
+    fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
+        if inputs.get("records").is_none() { ValidationResult::ok().with_warning("records input not connected") }
+        else { ValidationResult::ok() }
+    }
+
+    fn state_round_trips(&self) -> bool { true }
+    fn get_state(&self) -> BlockState { BlockState::new() }
+    fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn make_records() -> Vec<Record> {
+        (0..10).map(|i| {
+            let mut r = Record::new();
+            r.insert("id".into(), i as i64).unwrap();
+            r.insert("name".into(), format!("user-{}", i)).unwrap();
+            r.insert("bio".into(), "a long free-text column nobody selected".to_string()).unwrap();
+            r
+        }).collect()
+    }
+
+    fn columns(items: &[&str]) -> HashMap<String, ParameterValue> {
+        let list = items.iter().map(|c| ParameterValue::from(*c)).collect();
+        HashMap::from([("columns".to_string(), ParameterValue::Array(list))])
+    }
+
+    #[tokio::test]
+    async fn test_projection_keeps_and_renames_columns() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        let mut p = ProjectionBlock::new();
+        p.initialize(columns(&["id", "name as customer", "missing"])).await.unwrap();
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(make_records()));
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        let result = p.execute(ctx).await.unwrap();
+
+        let out = match result.outputs.get("projected").unwrap() { PortValue::Stream(r) => r.clone(), _ => panic!() };
+        assert_eq!(out.len(), 10);
+        assert_eq!(out[3].data.len(), 2);
+        assert_eq!(out[3].data.get("id"), Some(&json!(3)));
+        assert_eq!(out[3].data.get("customer"), Some(&json!("user-3")));
+        assert!(!out[3].contains("missing"), "absent columns stay absent");
+
+        let m = &result.metrics;
+        assert_eq!(m["rows_projected"], 10.0);
+        assert!(m["bytes_reduced"] > 0.0);
+        assert_eq!(m["bytes_reduced"], m["bytes_in"] - m["bytes_out"]);
+    }
+
+    #[tokio::test]
+    async fn test_projection_rejects_bad_columns() {
+        for bad in [vec![], vec!["id AS"], vec!["id", "name AS id"]] {
+            let err = ProjectionBlock::new().initialize(columns(&bad)).await.unwrap_err();
+            assert!(err.to_string().contains("columns"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_metadata() {
+        let p = ProjectionBlock::new();
+        assert_eq!(p.metadata().id, "projection");
+        assert_eq!(p.metadata().category, BlockCategory::Execution);
+    }
+}
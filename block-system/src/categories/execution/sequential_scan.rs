@@ -347,10 +347,15 @@ fn metrics(&self) -> &[MetricDefinition] {
         &self.metric_defs
     }
 
+    fn reports_running_totals(&self) -> bool {
+        false
+    }
+
     async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
         if let Some(val) = params.get("filter_column") {
             if let Some(s) = val.as_string() {
                 if !s.is_empty() {
@@ -370,12 +375,8 @@ async fn initialize(
                 }
             }
         }
-        if let Some(val) = params.get("records_per_page") {
-            self.records_per_page = val
-                .as_integer()
-                .ok_or_else(|| {
-                    BlockError::InvalidParameter("records_per_page must be an integer".into())
-                })? as usize;
+        if let Some(v) = params.get("records_per_page").and_then(ParameterValue::as_integer) {
+            self.records_per_page = v as usize;
         }
         Ok(())
     }
@@ -464,6 +465,10 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
         }
     }
 
+    fn state_round_trips(&self) -> bool {
+        true
+    }
+
     fn get_state(&self) -> BlockState {
         BlockState::new()
     }
@@ -3,42 +3,50 @@
 //! Sorts input records by a specified column. Simulates both in-memory and
 //! external sort (when data exceeds a configurable memory limit).
 //!
+//! Null and missing sort-column values sort together, last by default
+//! (`nulls = "last"`, SQL `NULLS LAST`) or first; their position does not
+//! flip with `descending`.
+//!
+//! ## How it works
+//!
+//! Input that fits within `memory_limit` — counted in records or in JSON
+//! bytes, per `memory_unit` — is sorted in memory. Larger input is cut
+//! into memory-sized sorted runs, which are "spilled" to temporary storage
+//! (simulated). The runs are then merged `merge_fan_in` at a time, pass after
+//! pass, until one remains. Every pass but the last writes its merged runs
+//! back out, so `n` bytes spread over `r` runs cost `n × ceil(log_F r)`
+//! bytes of spill — the O((n/B) log_F(n/M)) I/O of the textbook external
+//! merge sort.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
 //! |--------|------|-------------|
 //! | `rows_sorted` | Counter | Total rows sorted |
 //! | `comparisons` | Counter | Key comparisons made |
-//! | `external_runs` | Gauge | Merge runs for external sort |
+//! | `external_runs` | Gauge | Initial sorted runs for external sort |
 //! | `sort_type` | Gauge | 0 = in-memory, 1 = external |
+//! | `runs_spilled` | Counter | Runs written to temporary storage, initial and intermediate |
+//! | `merge_passes` | Counter | Merge passes over the data |
+//! | `bytes_spilled` | Counter | JSON bytes written to temporary storage |
 
 use async_trait::async_trait;
-use serde_json::Value as JsonValue;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
     Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
 };
-use crate::core::constraint::{Constraint, Guarantee};
+use crate::core::constraint::{Constraint, Guarantee, GuaranteeType};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
-use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
-
-fn cmp_json(a: &JsonValue, b: &JsonValue) -> std::cmp::Ordering {
-    match (a, b) {
-        (JsonValue::Number(na), JsonValue::Number(nb)) => {
-            let fa = na.as_f64().unwrap_or(0.0);
-            let fb = nb.as_f64().unwrap_or(0.0);
-            fa.partial_cmp(&fb).unwrap_or(std::cmp::Ordering::Equal)
-        }
-        (JsonValue::String(sa), JsonValue::String(sb)) => sa.cmp(sb),
-        _ => a.to_string().cmp(&b.to_string()),
-    }
-}
+use crate::core::port::{
+    cmp_json_nulls, NullOrder, Port, PortDirection, PortType, PortValue, Record,
+};
 
 pub struct SortBlock {
     metadata: BlockMetadata,
@@ -49,7 +57,19 @@ pub struct SortBlock {
 
     sort_column: String,
     descending: bool,
-    memory_limit: usize, // Max records for in-memory sort
+    nulls: NullOrder,
+    memory_limit: usize, // Max records (or bytes) for in-memory sort
+    memory_unit: MemoryUnit,
+    merge_fan_in: usize,
+    /// Sorted order when ascending — what a merge join needs.
+    guarantees: Vec<Guarantee>,
+}
+
+/// What `memory_limit` counts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MemoryUnit {
+    Records,
+    Bytes,
 }
 
 impl SortBlock {
@@ -62,7 +82,11 @@ pub fn new() -> Self {
             metric_defs: Self::build_metrics(),
             sort_column: "id".into(),
             descending: false,
+            nulls: NullOrder::Last,
             memory_limit: 10000,
+            memory_unit: MemoryUnit::Records,
+            merge_fan_in: 8,
+            guarantees: sorted_guarantees(false),
         }
     }
 
@@ -83,7 +107,10 @@ fn build_metadata() -> BlockMetadata {
                            (Rust's pdqsort, a pattern-defeating quicksort). When the data exceeds \
                            the memory limit, an external merge sort is used: the input is divided \
                            into sorted runs that fit in memory, each run is sorted and written to \
-                           temporary storage, then all runs are merged using a k-way merge.\n\n\
+                           temporary storage, then the runs are merged using k-way merges. A \
+                           merge can only read so many runs at once (one buffer each), so with \
+                           many runs it takes several passes, each reading and rewriting all of \
+                           the data.\n\n\
                            Think of sorting as organizing a deck of cards. If you have a small \
                            deck, you can spread them all on a table and sort directly. But if you \
                            have thousands of cards and a small table, you sort small piles first, \
@@ -100,19 +127,24 @@ fn build_metadata() -> BlockMetadata {
                               ELSE:\n    \
                                 // External merge sort\n    \
                                 runs = []\n    \
-                                FOR EACH chunk of memory_limit records:\n      \
+                                FOR EACH chunk of memory_limit records (or bytes):\n      \
                                   chunk.sort_by(column)\n      \
-                                  runs.append(chunk)  // write to temp storage\n    \
+                                  runs.append(chunk)  // spill to temp storage\n    \
                                 \n    \
-                                // K-way merge\n    \
-                                result = []\n    \
-                                WHILE any run has records:\n      \
-                                  Pick the run with the smallest (or largest) front element\n      \
-                                  Move that element to result\n    \
-                                RETURN result"
+                                // Merge passes, merge_fan_in runs at a time\n    \
+                                WHILE runs.len > 1:\n      \
+                                  runs = [k_way_merge(group)\n              \
+                                          FOR EACH group of merge_fan_in runs]\n      \
+                                  IF runs.len > 1: spill runs  // not the final pass\n    \
+                                RETURN runs[0]\n\
+                            \n\
+                            FUNCTION k_way_merge(runs):\n  \
+                              WHILE any run has records:\n    \
+                                Move the smallest front element to the output"
                     .into(),
                 complexity: Complexity {
-                    time: "O(n log n) in-memory, O(n log n * log(n/M)) external".into(),
+                    time: "O(n log n) comparisons; external I/O O((n/B) log_F(n/M)) for fan-in F"
+                        .into(),
                     space: "O(n) in-memory, O(M) external where M = memory limit".into(),
                 },
                 use_cases: vec![
@@ -154,7 +186,7 @@ fn build_metadata() -> BlockMetadata {
                     ("sort_column".into(), "The column name to sort by. The sort compares values \
                                             in this column across all input records. If a record \
                                             is missing this column, it is treated as NULL and \
-                                            sorted to a default position. Try sorting on different \
+                                            placed according to the nulls setting. Try sorting on different \
                                             columns to see how data distribution affects the number \
                                             of comparisons (already-sorted data requires fewer).".into()),
                     ("descending".into(), "When true, sorts in descending order (largest first). \
@@ -162,6 +194,11 @@ fn build_metadata() -> BlockMetadata {
                                            first). This corresponds to ASC/DESC in SQL. The cost \
                                            of sorting is the same regardless of direction — only \
                                            the comparison function is inverted.".into()),
+                    ("nulls".into(), "Where NULL (or missing) sort-column values go: \"last\" \
+                                      (default) or \"first\", like NULLS LAST / NULLS FIRST in \
+                                      SQL. The setting holds in both directions, so nulls stay \
+                                      last under a descending sort unless you ask otherwise \
+                                      (PostgreSQL instead defaults to NULLS FIRST for DESC).".into()),
                     ("memory_limit".into(), "Maximum number of records to sort in memory before \
                                              switching to external merge sort. This simulates \
                                              PostgreSQL's work_mem parameter. When the input \
@@ -170,7 +207,18 @@ fn build_metadata() -> BlockMetadata {
                                              sort_type metrics: sort_type=0 means in-memory, \
                                              sort_type=1 means external. Start with 10000 and try \
                                              lowering it to force external sort and observe the \
-                                             performance impact. Range: 10 to 1000000 records.".into()),
+                                             performance impact. Range: 10 to 1000000, in \
+                                             memory_unit.".into()),
+                    ("memory_unit".into(), "Whether memory_limit counts records (default) or \
+                                            bytes. Real systems budget bytes: with a byte limit, \
+                                            wide rows fill memory sooner, so the same row count \
+                                            makes more runs. Bytes are the records' JSON size.".into()),
+                    ("merge_fan_in".into(), "How many runs one merge reads at once — one input \
+                                             buffer per run, so in a real system this is bounded \
+                                             by memory. With r initial runs the sort needs \
+                                             ceil(log_F r) merge passes, each re-reading all the \
+                                             data and all but the last re-writing it. Lower it \
+                                             and watch merge_passes and bytes_spilled climb.".into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -240,6 +288,13 @@ fn build_parameters() -> Vec<Parameter> {
                 description: "Sort in descending order".into(), default_value: ParameterValue::Boolean(false),
                 required: false, constraints: None, ui_hint: Some(ParameterUIHint::new(WidgetType::Checkbox)),
             },
+            Parameter {
+                id: "nulls".into(), name: "Nulls".into(), param_type: ParameterType::Enum,
+                description: "Where null values sort (first, last)".into(),
+                default_value: ParameterValue::String("last".into()), required: false,
+                constraints: Some(ParameterConstraints::new().with_choices(NULL_ORDERS)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+            },
             Parameter {
                 id: "memory_limit".into(), name: "Memory Limit".into(), param_type: ParameterType::Number,
                 description: "Max records for in-memory sort (exceeding triggers external sort)".into(),
@@ -247,6 +302,20 @@ fn build_parameters() -> Vec<Parameter> {
                 constraints: Some(ParameterConstraints::new().with_min(10.0).with_max(1000000.0)),
                 ui_hint: Some(ParameterUIHint::new(WidgetType::Slider).with_step(100.0).with_unit("records".into())),
             },
+            Parameter {
+                id: "memory_unit".into(), name: "Memory Unit".into(), param_type: ParameterType::Enum,
+                description: "What memory_limit counts (records, bytes)".into(),
+                default_value: ParameterValue::String("records".into()), required: false,
+                constraints: Some(ParameterConstraints::new().with_choices(MEMORY_UNITS)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+            },
+            Parameter {
+                id: "merge_fan_in".into(), name: "Merge Fan-In".into(), param_type: ParameterType::Number,
+                description: "Runs merged at once in each merge pass".into(),
+                default_value: ParameterValue::Integer(8), required: false,
+                constraints: Some(ParameterConstraints::new().with_min(2.0).with_max(1024.0)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
         ]
     }
 
@@ -254,10 +323,84 @@ fn build_metrics() -> Vec<MetricDefinition> {
         vec![
             MetricDefinition { id: "rows_sorted".into(), name: "Rows Sorted".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Total rows sorted".into(), aggregations: vec![AggregationType::Sum] },
             MetricDefinition { id: "comparisons".into(), name: "Comparisons".into(), metric_type: MetricType::Counter, unit: "ops".into(), description: "Key comparisons made".into(), aggregations: vec![AggregationType::Sum] },
-            MetricDefinition { id: "external_runs".into(), name: "External Runs".into(), metric_type: MetricType::Gauge, unit: "runs".into(), description: "Merge runs for external sort".into(), aggregations: vec![AggregationType::Max] },
+            MetricDefinition { id: "external_runs".into(), name: "External Runs".into(), metric_type: MetricType::Gauge, unit: "runs".into(), description: "Initial sorted runs for external sort".into(), aggregations: vec![AggregationType::Max] },
             MetricDefinition { id: "sort_type".into(), name: "Sort Type".into(), metric_type: MetricType::Gauge, unit: "".into(), description: "0 = in-memory, 1 = external".into(), aggregations: vec![AggregationType::Max] },
+            MetricDefinition { id: "runs_spilled".into(), name: "Runs Spilled".into(), metric_type: MetricType::Counter, unit: "runs".into(), description: "Runs written to temporary storage".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "merge_passes".into(), name: "Merge Passes".into(), metric_type: MetricType::Counter, unit: "passes".into(), description: "Merge passes over the data".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "bytes_spilled".into(), name: "Bytes Spilled".into(), metric_type: MetricType::Counter, unit: "bytes".into(), description: "Bytes written to temporary storage".into(), aggregations: vec![AggregationType::Sum] },
         ]
     }
+
+    /// Cut `records` into sorted runs of at most `memory_limit` records or
+    /// bytes. A single record larger than a byte limit gets a run of its own.
+    fn form_runs(
+        &self,
+        records: Vec<Record>,
+        cmp: &mut impl FnMut(&Record, &Record) -> Ordering,
+    ) -> Vec<Vec<Record>> {
+        let mut runs: Vec<Vec<Record>> = Vec::new();
+        let mut run = Vec::new();
+        let mut used = 0;
+        for record in records {
+            let size = match self.memory_unit {
+                MemoryUnit::Records => 1,
+                MemoryUnit::Bytes => record.json_size(),
+            };
+            if !run.is_empty() && used + size > self.memory_limit {
+                runs.push(std::mem::take(&mut run));
+                used = 0;
+            }
+            used += size;
+            run.push(record);
+        }
+        if !run.is_empty() { runs.push(run); }
+        for run in &mut runs { run.sort_by(|a, b| cmp(a, b)); }
+        runs
+    }
+}
+
+/// Merge sorted runs into one by repeatedly taking the smallest head.
+fn k_way_merge(
+    runs: Vec<Vec<Record>>,
+    cmp: &mut impl FnMut(&Record, &Record) -> Ordering,
+) -> Vec<Record> {
+    let mut merged = Vec::with_capacity(runs.iter().map(Vec::len).sum());
+    let mut runs: Vec<std::vec::IntoIter<Record>> = runs.into_iter().map(Vec::into_iter).collect();
+    let mut heads: Vec<Option<Record>> = runs.iter_mut().map(Iterator::next).collect();
+    loop {
+        let mut best: Option<usize> = None;
+        for (i, head) in heads.iter().enumerate() {
+            let Some(record) = head else { continue };
+            let better = match best.and_then(|b| heads[b].as_ref()) {
+                Some(current) => cmp(record, current) == Ordering::Less,
+                None => true,
+            };
+            if better { best = Some(i); }
+        }
+        match best {
+            Some(i) => {
+                let next = runs[i].next();
+                merged.extend(std::mem::replace(&mut heads[i], next));
+            }
+            None => return merged,
+        }
+    }
+}
+
+/// Values accepted by the `nulls` parameter.
+const NULL_ORDERS: &[&str] = &["first", "last"];
+
+/// Values accepted by the `memory_unit` parameter.
+const MEMORY_UNITS: &[&str] = &["records", "bytes"];
+
+/// An ascending sort guarantees sorted output; a descending one promises
+/// nothing consumers expecting ascending keys can use.
+fn sorted_guarantees(descending: bool) -> Vec<Guarantee> {
+    if descending {
+        Vec::new()
+    } else {
+        vec![Guarantee::strict(GuaranteeType::SortedOrder, "Output sorted ascending on sort_column")]
+    }
 }
 
 impl Default for SortBlock {
@@ -271,82 +414,79 @@ fn inputs(&self) -> &[Port] { &self.input_ports }
     fn outputs(&self) -> &[Port] { &self.output_ports }
     fn parameters(&self) -> &[Parameter] { &self.params }
     fn requires(&self) -> &[Constraint] { &[] }
-    fn guarantees(&self) -> &[Guarantee] { &[] }
+    fn guarantees(&self) -> &[Guarantee] { &self.guarantees }
     fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
+    fn reports_running_totals(&self) -> bool { false }
 
     async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
         if let Some(v) = params.get("sort_column") { if let Some(s) = v.as_string() { self.sort_column = s.to_string(); } }
         if let Some(v) = params.get("descending") { if let Some(b) = v.as_bool() { self.descending = b; } }
+        self.guarantees = sorted_guarantees(self.descending);
+        if let Some(s) = enum_param(&params, "nulls", NULL_ORDERS)? {
+            self.nulls = if s == "first" { NullOrder::First } else { NullOrder::Last };
+        }
         if let Some(v) = params.get("memory_limit") { self.memory_limit = v.as_integer().unwrap_or(10000) as usize; }
+        if let Some(s) = enum_param(&params, "memory_unit", MEMORY_UNITS)? {
+            self.memory_unit = if s == "bytes" { MemoryUnit::Bytes } else { MemoryUnit::Records };
+        }
+        if let Some(v) = params.get("merge_fan_in").and_then(ParameterValue::as_integer) { self.merge_fan_in = v as usize; }
         Ok(())
     }
 
     async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult, BlockError> {
-        let mut records = match context.inputs.get("records").cloned().unwrap_or(PortValue::None) {
+        let records = match context.inputs.get("records").cloned().unwrap_or(PortValue::None) {
             PortValue::Stream(r) => r, PortValue::Batch(r) => r, PortValue::Single(r) => vec![r],
             PortValue::None => Vec::new(),
             _ => return Err(BlockError::InvalidInput("Expected DataStream".into())),
         };
 
         let rows = records.len();
-        let is_external = rows > self.memory_limit;
-        let external_runs = if is_external { (rows + self.memory_limit - 1) / self.memory_limit } else { 0 };
-
         let mut comparisons = 0usize;
         let col = self.sort_column.clone();
         let desc = self.descending;
+        let nulls = self.nulls;
+        // Direction flips the value order but not where nulls go.
+        let mut cmp = |a: &Record, b: &Record| {
+            comparisons += 1;
+            let (a, b) = (a.get_or_null(&col), b.get_or_null(&col));
+            let ord = cmp_json_nulls(a, b, nulls);
+            if desc && !a.is_null() && !b.is_null() { ord.reverse() } else { ord }
+        };
 
+        let mut runs = self.form_runs(records, &mut cmp);
+        let external_runs = if runs.len() > 1 { runs.len() } else { 0 };
+        let is_external = external_runs > 0;
+        let (mut runs_spilled, mut merge_passes, mut bytes_spilled) = (0usize, 0usize, 0usize);
         if is_external {
-            // Simulate external sort: sort each run, then merge.
-            let mut runs: Vec<Vec<Record>> = records.chunks(self.memory_limit).map(|c| {
-                let mut chunk = c.to_vec();
-                chunk.sort_by(|a, b| {
-                    comparisons += 1;
-                    let va = a.data.get(&col).unwrap_or(&JsonValue::Null);
-                    let vb = b.data.get(&col).unwrap_or(&JsonValue::Null);
-                    let ord = cmp_json(va, vb);
-                    if desc { ord.reverse() } else { ord }
-                });
-                chunk
-            }).collect();
-
-            // K-way merge (simplified: merge all runs into one sorted vec).
-            records = Vec::with_capacity(rows);
-            loop {
-                let mut best_run = None;
-                let mut best_val = None;
-                for (i, run) in runs.iter().enumerate() {
-                    if let Some(rec) = run.first() {
-                        let v = rec.data.get(&col).unwrap_or(&JsonValue::Null).clone();
-                        comparisons += 1;
-                        if best_val.is_none() || {
-                            let ord = cmp_json(&v, best_val.as_ref().unwrap());
-                            if desc { ord == std::cmp::Ordering::Greater } else { ord == std::cmp::Ordering::Less }
-                        } {
-                            best_run = Some(i);
-                            best_val = Some(v);
-                        }
-                    }
+            let data_bytes: usize = runs.iter().flatten().map(Record::json_size).sum();
+            runs_spilled += runs.len();
+            bytes_spilled += data_bytes;
+            while runs.len() > 1 {
+                merge_passes += 1;
+                let mut merged = Vec::with_capacity(runs.len().div_ceil(self.merge_fan_in));
+                let mut pending = runs.into_iter().peekable();
+                while pending.peek().is_some() {
+                    let group: Vec<Vec<Record>> = pending.by_ref().take(self.merge_fan_in).collect();
+                    merged.push(k_way_merge(group, &mut cmp));
                 }
-                match best_run {
-                    Some(i) => records.push(runs[i].remove(0)),
-                    None => break,
+                runs = merged;
+                // The final pass streams its output instead of spilling it.
+                if runs.len() > 1 {
+                    runs_spilled += runs.len();
+                    bytes_spilled += data_bytes;
                 }
             }
-        } else {
-            records.sort_by(|a, b| {
-                comparisons += 1;
-                let va = a.data.get(&col).unwrap_or(&JsonValue::Null);
-                let vb = b.data.get(&col).unwrap_or(&JsonValue::Null);
-                let ord = cmp_json(va, vb);
-                if desc { ord.reverse() } else { ord }
-            });
         }
+        let records = runs.pop().unwrap_or_default();
 
         context.metrics.record("rows_sorted", rows as f64);
         context.metrics.record("comparisons", comparisons as f64);
         context.metrics.record("external_runs", external_runs as f64);
         context.metrics.record("sort_type", if is_external { 1.0 } else { 0.0 });
+        context.metrics.record("runs_spilled", runs_spilled as f64);
+        context.metrics.record("merge_passes", merge_passes as f64);
+        context.metrics.record("bytes_spilled", bytes_spilled as f64);
 
         let mut outputs = HashMap::new();
         outputs.insert("sorted".into(), PortValue::Stream(records));
@@ -354,6 +494,9 @@ async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult
         ms.insert("rows_sorted".into(), rows as f64);
         ms.insert("comparisons".into(), comparisons as f64);
         ms.insert("external_runs".into(), external_runs as f64);
+        ms.insert("runs_spilled".into(), runs_spilled as f64);
+        ms.insert("merge_passes".into(), merge_passes as f64);
+        ms.insert("bytes_spilled".into(), bytes_spilled as f64);
 
         Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
     }
@@ -362,6 +505,7 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
         if inputs.get("records").is_none() { ValidationResult::ok().with_warning("records input not connected") }
         else { ValidationResult::ok() }
     }
+    fn state_round_trips(&self) -> bool { true }
     fn get_state(&self) -> BlockState { BlockState::new() }
     fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
 }
@@ -369,7 +513,7 @@ fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde_json::json;
+    use serde_json::{json, Value as JsonValue};
 
     fn make_records() -> Vec<Record> {
         [5, 3, 8, 1, 9, 2, 7, 4, 6, 0].iter().map(|&i| {
@@ -411,6 +555,41 @@ async fn test_sort_descending() {
         }
     }
 
+    #[tokio::test]
+    async fn test_nulls_sort_last_in_both_directions_unless_first() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        let mut records = make_records();
+        let mut null_id = Record::new();
+        null_id.data.insert("id".into(), JsonValue::Null);
+        records.insert(3, null_id);
+        records.insert(6, Record::new());
+
+        for (descending, nulls, memory_limit) in [
+            (false, "last", 10000), (true, "last", 10000), (false, "first", 10000), (true, "last", 3),
+        ] {
+            let mut s = SortBlock::new();
+            let mut params = HashMap::new();
+            params.insert("descending".to_string(), ParameterValue::Boolean(descending));
+            params.insert("nulls".to_string(), ParameterValue::from(nulls));
+            s.initialize(params).await.unwrap();
+            s.memory_limit = memory_limit;
+            let mut inputs = HashMap::new();
+            inputs.insert("records".into(), PortValue::Stream(records.clone()));
+            let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+            let result = s.execute(ctx).await.unwrap();
+            let sorted = match result.outputs.get("sorted").unwrap() { PortValue::Stream(r) => r.clone(), _ => panic!() };
+            let ids: Vec<Option<i64>> = sorted.iter().map(|r| r.get::<i64>("id").unwrap()).collect();
+            let (nulls_at, values) = if nulls == "first" { (&ids[..2], &ids[2..]) } else { (&ids[10..], &ids[..10]) };
+            assert_eq!(nulls_at, &[None, None], "{:?}", ids);
+            let expected: Vec<Option<i64>> = if descending { (0..10).rev().map(Some).collect() } else { (0..10).map(Some).collect() };
+            assert_eq!(values, expected.as_slice());
+        }
+
+        let mut params = HashMap::new();
+        params.insert("nulls".to_string(), ParameterValue::from("middle"));
+        assert!(SortBlock::new().initialize(params).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_external_sort() {
         use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
@@ -429,6 +608,78 @@ async fn test_external_sort() {
         }
     }
 
+    async fn sort_with(params: Vec<(&str, ParameterValue)>, records: Vec<Record>) -> ExecutionResult {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        let mut s = SortBlock::new();
+        s.initialize(params.into_iter().map(|(k, v)| (k.to_string(), v)).collect()).await.unwrap();
+        let inputs = HashMap::from([("records".to_string(), PortValue::Stream(records))]);
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        s.execute(ctx).await.unwrap()
+    }
+
+    /// ids 0..n in a scrambled order, each padded with `width` bytes.
+    fn scrambled(n: i64, width: usize) -> Vec<Record> {
+        (0..n).map(|i| {
+            let mut r = Record::new();
+            r.insert("id".into(), (i * 7919) % n).unwrap();
+            r.insert("pad".into(), "x".repeat(width)).unwrap();
+            r
+        }).collect()
+    }
+
+    fn assert_sorted(result: &ExecutionResult, n: i64) {
+        let sorted = match result.outputs.get("sorted").unwrap() { PortValue::Stream(r) => r.clone(), _ => panic!() };
+        let ids: Vec<i64> = sorted.iter().map(|r| r.get::<i64>("id").unwrap().unwrap()).collect();
+        assert_eq!(ids, (0..n).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_external_sort_merges_in_passes_bounded_by_fan_in() {
+        let records = scrambled(1000, 0);
+        let data_bytes: usize = records.iter().map(Record::json_size).sum();
+
+        // 100 runs merged 8 at a time: 100 → 13 → 2 → 1, three passes. The
+        // first two passes spill their output; the last streams it.
+        let result = sort_with(vec![("memory_limit", ParameterValue::Integer(10))], records.clone()).await;
+        assert_sorted(&result, 1000);
+        assert_eq!(result.metrics["external_runs"], 100.0);
+        assert_eq!(result.metrics["merge_passes"], 3.0);
+        assert_eq!(result.metrics["runs_spilled"], (100 + 13 + 2) as f64);
+        assert_eq!(result.metrics["bytes_spilled"], (3 * data_bytes) as f64);
+
+        // A fan-in covering every run merges in one pass: each byte is
+        // spilled once.
+        let wide = sort_with(vec![
+            ("memory_limit", ParameterValue::Integer(10)),
+            ("merge_fan_in", ParameterValue::Integer(100)),
+        ], records.clone()).await;
+        assert_sorted(&wide, 1000);
+        assert_eq!(wide.metrics["merge_passes"], 1.0);
+        assert_eq!(wide.metrics["runs_spilled"], 100.0);
+        assert_eq!(wide.metrics["bytes_spilled"], data_bytes as f64);
+
+        let in_memory = sort_with(vec![], records).await;
+        assert_sorted(&in_memory, 1000);
+        assert_eq!(in_memory.metrics["merge_passes"], 0.0);
+        assert_eq!(in_memory.metrics["bytes_spilled"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_byte_memory_limit_makes_more_runs_for_wider_rows() {
+        let by_bytes = |n| vec![
+            ("memory_limit", ParameterValue::Integer(n)),
+            ("memory_unit", ParameterValue::from("bytes")),
+        ];
+        let narrow = sort_with(by_bytes(2000), scrambled(200, 10)).await;
+        let wide = sort_with(by_bytes(2000), scrambled(200, 100)).await;
+        assert_sorted(&narrow, 200);
+        assert_sorted(&wide, 200);
+        assert!(
+            wide.metrics["external_runs"] > 3.0 * narrow.metrics["external_runs"],
+            "narrow {} runs, wide {} runs", narrow.metrics["external_runs"], wide.metrics["external_runs"]
+        );
+    }
+
     #[test]
     fn test_metadata() {
         let s = SortBlock::new();
@@ -0,0 +1,305 @@
+//! Union Execution Block
+//!
+//! Concatenates every stream connected to its input — SQL's `UNION ALL` —
+//! or, with `distinct` set, also removes duplicate rows — `UNION`.
+//!
+//! ## How it works
+//!
+//! The `records` port accepts any number of connections. The engine
+//! concatenates them before the block runs (external input first, then
+//! connections in the order they were added; see the engine's "Data
+//! routing" docs) and reports how many non-empty streams went in through
+//! the [`FAN_IN_PARAMETER`] execution parameter, which becomes
+//! `inputs_merged`. Called directly, without an engine, a non-empty input
+//! counts as one stream.
+//!
+//! With `distinct`, rows are compared whole, as by the Distinct block with
+//! no columns: nulls equal nulls, and an absent field equals a null one.
+//! Rows seen are kept across calls, so a batched run removes duplicates
+//! across batches as well as within them.
+//!
+//! ## Metrics tracked
+//!
+//! | Metric | Type | Description |
+//! |--------|------|-------------|
+//! | `inputs_merged` | Gauge | Most non-empty input streams merged in one call |
+//! | `rows_in` | Counter | Rows received across all inputs |
+//! | `rows_out` | Counter | Rows passed on |
+//! | `duplicates_removed` | Counter | Rows dropped as duplicates (`distinct` only) |
+
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
+
+use crate::categories::execution::distinct::row_key;
+use crate::core::block::{
+    Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
+    Complexity, ExecutionContext, ExecutionResult, Reference, ReferenceType,
+};
+use crate::core::constraint::{Constraint, Guarantee};
+use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
+use crate::core::parameter::{
+    Parameter, ParameterType, ParameterUIHint, ParameterValue, ValidationResult, WidgetType,
+};
+use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
+use crate::runtime::engine::FAN_IN_PARAMETER;
+
+pub struct UnionBlock {
+    metadata: BlockMetadata,
+    input_ports: Vec<Port>,
+    output_ports: Vec<Port>,
+    params: Vec<Parameter>,
+    metric_defs: Vec<MetricDefinition>,
+
+    distinct: bool,
+    /// Rows seen so far (`distinct` only).
+    seen: HashSet<String>,
+    /// Most input streams merged in one call so far.
+    inputs_merged: usize,
+}
+
+impl UnionBlock {
+    pub fn new() -> Self {
+        Self {
+            metadata: Self::build_metadata(),
+            input_ports: Self::build_inputs(),
+            output_ports: Self::build_outputs(),
+            params: Self::build_parameters(),
+            metric_defs: Self::build_metrics(),
+            distinct: false,
+            seen: HashSet::new(),
+            inputs_merged: 0,
+        }
+    }
+
+    fn build_metadata() -> BlockMetadata {
+        BlockMetadata {
+            id: "union".into(),
+            name: "Union".into(),
+            category: BlockCategory::Execution,
+            description: "Concatenates any number of inputs, optionally removing duplicates"
+                .into(),
+            version: "1.0.0".into(),
+            documentation: BlockDocumentation {
+                overview: "UNION ALL stacks the rows of several queries into one result; \
+                           UNION does the same and then removes duplicate rows. The inputs \
+                           need the same columns, but nothing else connects them — they can \
+                           come from different tables, different indexes, or different \
+                           branches of the same plan.\n\n\
+                           UNION ALL is almost free: rows pass straight through. UNION has to \
+                           remember every row it has emitted to recognise a repeat, which \
+                           costs memory proportional to the distinct rows. That is why \
+                           experienced SQL writers reach for UNION ALL whenever the inputs \
+                           cannot overlap.\n\n\
+                           Connect as many inputs as you like to the one records port; \
+                           inputs_merged shows how many actually delivered rows."
+                    .into(),
+                algorithm: "Union:\n\
+                            \n\
+                            FUNCTION union(inputs, distinct):\n  \
+                              seen = {}\n  \
+                              FOR EACH input IN inputs:          // in connection order\n    \
+                                FOR EACH row IN input:\n      \
+                                  IF distinct:\n        \
+                                    IF row IN seen: CONTINUE\n        \
+                                    seen.add(row)\n      \
+                                  emit(row)"
+                    .into(),
+                complexity: Complexity {
+                    time: "O(n) over all inputs".into(),
+                    space: "O(1) for UNION ALL; O(d) for UNION with d distinct rows".into(),
+                },
+                use_cases: vec![
+                    "Combining partitions or shards of the same table".into(),
+                    "OR predicates rewritten as a union of two index scans".into(),
+                    "Merging current and archived rows into one result".into(),
+                ],
+                tradeoffs: vec![
+                    "UNION ALL streams with no memory; UNION must hold every distinct row"
+                        .into(),
+                    "UNION compares whole rows, so inputs that differ only in an unused \
+                     column are not treated as duplicates"
+                        .into(),
+                ],
+                examples: vec![
+                    "PostgreSQL Append (UNION ALL), with HashAggregate or Unique on top for \
+                     UNION"
+                        .into(),
+                    "Partitioned tables, scanned as an Append over each partition".into(),
+                ],
+                motivation: "Many plans have more than one source for the same rows — \
+                             partitions, shards, or the two halves of an OR. A union is the \
+                             operator that brings them back together, and the choice between \
+                             UNION and UNION ALL is one of the easiest performance wins in SQL."
+                    .into(),
+                parameter_guide: HashMap::from([(
+                    "distinct".into(),
+                    "Off for UNION ALL: every row from every input passes through. On for \
+                     UNION: a row identical to one already emitted is dropped, and \
+                     duplicates_removed counts them. Identical means every non-null field \
+                     matches."
+                        .into(),
+                )]),
+                alternatives: vec![Alternative {
+                    block_type: "distinct".into(),
+                    comparison: "UNION is UNION ALL followed by a whole-row DISTINCT. Use a \
+                                 Distinct block after the union instead to deduplicate on a \
+                                 subset of columns or to exploit sorted input."
+                        .into(),
+                }],
+                suggested_questions: vec![
+                    "Why is UNION ALL usually faster than UNION?".into(),
+                    "When can an optimizer turn an OR into a union of index scans?".into(),
+                ],
+            },
+            references: vec![Reference {
+                ref_type: ReferenceType::Book,
+                title: "Database System Concepts — Chapter 15: Query Processing".into(),
+                url: None,
+                citation: Some("Silberschatz, A. et al. (2019). McGraw-Hill.".into()),
+            }],
+            icon: "merge".into(),
+            color: "#EC4899".into(),
+        }
+    }
+
+    fn build_inputs() -> Vec<Port> {
+        vec![Port {
+            id: "records".into(), name: "Inputs".into(), port_type: PortType::DataStream,
+            direction: PortDirection::Input, required: true, multiple: true,
+            description: "Any number of record streams to combine".into(), schema: None,
+        }]
+    }
+
+    fn build_outputs() -> Vec<Port> {
+        vec![Port {
+            id: "merged".into(), name: "Union".into(), port_type: PortType::DataStream,
+            direction: PortDirection::Output, required: false, multiple: true,
+            description: "Every input's rows, in connection order".into(), schema: None,
+        }]
+    }
+
+    fn build_parameters() -> Vec<Parameter> {
+        vec![Parameter {
+            id: "distinct".into(), name: "Distinct".into(), param_type: ParameterType::Boolean,
+            description: "Remove duplicate rows (UNION) instead of keeping all (UNION ALL)".into(),
+            default_value: ParameterValue::Boolean(false), required: false,
+            constraints: None,
+            ui_hint: Some(ParameterUIHint::new(WidgetType::Checkbox)),
+        }]
+    }
+
+    fn build_metrics() -> Vec<MetricDefinition> {
+        vec![
+            MetricDefinition { id: "inputs_merged".into(), name: "Inputs Merged".into(), metric_type: MetricType::Gauge, unit: "streams".into(), description: "Most non-empty input streams merged in one call".into(), aggregations: vec![AggregationType::Max] },
+            MetricDefinition { id: "rows_in".into(), name: "Rows In".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Rows received across all inputs".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "rows_out".into(), name: "Rows Out".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Rows passed on".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "duplicates_removed".into(), name: "Duplicates Removed".into(), metric_type: MetricType::Counter, unit: "rows".into(), description: "Rows dropped as duplicates".into(), aggregations: vec![AggregationType::Sum] },
+        ]
+    }
+}
+
+impl Default for UnionBlock {
+    fn default() -> Self { Self::new() }
+}
+
+#[async_trait]
+impl Block for UnionBlock {
+    fn metadata(&self) -> &BlockMetadata { &self.metadata }
+    fn inputs(&self) -> &[Port] { &self.input_ports }
+    fn outputs(&self) -> &[Port] { &self.output_ports }
+    fn parameters(&self) -> &[Parameter] { &self.params }
+    fn requires(&self) -> &[Constraint] { &[] }
+    fn guarantees(&self) -> &[Guarantee] { &[] }
+    fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
+    fn reports_running_totals(&self) -> bool { false }
+
+    async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
+        self.distinct = params.get("distinct").and_then(ParameterValue::as_bool).unwrap_or(false);
+        self.seen.clear();
+        self.inputs_merged = 0;
+        Ok(())
+    }
+
+    async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult, BlockError> {
+        let records: Vec<Record> = match context.inputs.get("records").cloned().unwrap_or(PortValue::None) {
+            PortValue::Stream(r) => r, PortValue::Batch(r) => r, PortValue::Single(r) => vec![r],
+            PortValue::None => Vec::new(),
+            _ => return Err(BlockError::InvalidInput("Expected DataStream".into())),
+        };
+        let fan_in = context.parameters.get(FAN_IN_PARAMETER)
+            .and_then(ParameterValue::as_object)
+            .and_then(|ports| ports.get("records"))
+            .and_then(ParameterValue::as_integer);
+        let streams = fan_in.map_or(usize::from(!records.is_empty()), |n| n as usize);
+        self.inputs_merged = self.inputs_merged.max(streams);
+
+        let rows_in = records.len();
+        let merged: Vec<Record> = if self.distinct {
+            records.into_iter().filter(|r| self.seen.insert(JsonValue::Array(row_key(r)).to_string())).collect()
+        } else {
+            records
+        };
+        let rows_out = merged.len();
+        let removed = rows_in - rows_out;
+
+        context.metrics.record("inputs_merged", self.inputs_merged as f64);
+        context.metrics.record("rows_in", rows_in as f64);
+        context.metrics.record("rows_out", rows_out as f64);
+        context.metrics.record("duplicates_removed", removed as f64);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("merged".into(), PortValue::Stream(merged));
+        let mut ms = HashMap::new();
+        ms.insert("inputs_merged".into(), self.inputs_merged as f64);
+        ms.insert("rows_in".into(), rows_in as f64);
+        ms.insert("rows_out".into(), rows_out as f64);
+        ms.insert("duplicates_removed".into(), removed as f64);
+
+        Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
+    }
+
+    fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
+        if inputs.get("records").is_none() { ValidationResult::ok().with_warning("no inputs connected") }
+        else { ValidationResult::ok() }
+    }
+
+    fn get_state(&self) -> BlockState { BlockState::new() }
+    fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+    fn rows(ids: &[i64]) -> Vec<Record> {
+        ids.iter().map(|&i| { let mut r = Record::new(); r.insert("id".into(), i).unwrap(); r }).collect()
+    }
+
+    async fn union(distinct: bool, records: Vec<Record>) -> ExecutionResult {
+        let mut block = UnionBlock::new();
+        block.initialize(HashMap::from([("distinct".to_string(), ParameterValue::Boolean(distinct))])).await.unwrap();
+        let inputs = HashMap::from([("records".to_string(), PortValue::Stream(records))]);
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        block.execute(ctx).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_union_all_keeps_duplicates() {
+        let result = union(false, rows(&[1, 2, 2, 3])).await;
+        assert_eq!(result.metrics["rows_out"], 4.0);
+        assert_eq!(result.metrics["duplicates_removed"], 0.0);
+        assert_eq!(result.metrics["inputs_merged"], 1.0, "one stream without an engine");
+    }
+
+    #[tokio::test]
+    async fn test_union_distinct_drops_whole_row_duplicates() {
+        let mut records = rows(&[1, 2, 2, 3, 1]);
+        records[4].data.insert("note".into(), JsonValue::Null);
+        let result = union(true, records).await;
+        assert_eq!(result.metrics["rows_out"], 3.0);
+        assert_eq!(result.metrics["duplicates_removed"], 2.0);
+    }
+}
@@ -12,7 +12,28 @@
 //! affects tree depth and therefore lookup speed.
 //!
 //! Leaf nodes are linked via `next_leaf` pointers so range scans can walk the
-//! leaf chain without revisiting internal nodes.
+//! leaf chain without revisiting internal nodes. A matching `prev_leaf` link
+//! lets descending scans (`ORDER BY ... DESC`) walk the chain backwards.
+//!
+//! A comma-separated `key_column` (e.g. `"a,b"`) builds a **composite** key:
+//! an array of the column values, ordered lexicographically. Range scans
+//! accept prefix bounds, so `[1]..=[1]` returns every key whose first
+//! column is `1`.
+//!
+//! Input records are inserted by default. A record with `_op: "lookup"` probes
+//! for its key column value, and `_op: "range"` scans from its `start` to
+//! `end` fields; matches are emitted on `lookup_results` with their TupleId.
+//!
+//! Records on the optional `remap` input re-point an entry whose record moved
+//! in the heap — as a heap file's auto-vacuum reports on its `remapped`
+//! output: the entry for the record's key at `_old_page_id`/`_old_slot_id`
+//! moves to `_page_id`/`_slot_id`. A remap record with `_op: "delete"`
+//! instead drops the entry for its key at `_page_id`/`_slot_id`, whose record
+//! the heap reclaimed. Remaps apply in order, before the batch's records.
+//!
+//! Deletes keep the tree balanced: a node that drops below `fanout / 2`
+//! entries borrows one from a sibling or merges with it, and the tree loses a
+//! level when the root is left with a single child.
 //!
 //! ## Metrics tracked
 //!
@@ -23,6 +44,7 @@
 //! | `lookups` | Counter | Point lookups performed |
 //! | `range_scans` | Counter | Range scans performed |
 //! | `splits` | Counter | Node splits during insert |
+//! | `merges` | Counter | Node merges during delete |
 //! | `comparisons` | Counter | Key comparisons made |
 
 use async_trait::async_trait;
@@ -40,7 +62,7 @@
     Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
-use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
+use crate::core::port::{cmp_json, Port, PortDirection, PortType, PortValue, Record};
 
 // ---------------------------------------------------------------------------
 // Internal B-tree model
@@ -63,21 +85,47 @@ enum BTreeNode {
     Leaf {
         entries: Vec<LeafEntry>,
         next_leaf: Option<usize>, // linked-list for range scans
+        prev_leaf: Option<usize>, // backward link for descending scans
     },
 }
 
-/// Compare two JSON values for ordering.
-/// Numbers are compared numerically, strings lexicographically.
-fn cmp_json(a: &JsonValue, b: &JsonValue) -> std::cmp::Ordering {
-    match (a, b) {
-        (JsonValue::Number(na), JsonValue::Number(nb)) => {
-            let fa = na.as_f64().unwrap_or(0.0);
-            let fb = nb.as_f64().unwrap_or(0.0);
-            fa.partial_cmp(&fb).unwrap_or(std::cmp::Ordering::Equal)
+impl BTreeNode {
+    /// Build an internal node, checking that it has one more child than keys.
+    fn internal(keys: Vec<JsonValue>, children: Vec<usize>) -> Self {
+        debug_assert_eq!(
+            children.len(),
+            keys.len() + 1,
+            "internal node must have keys.len() + 1 children"
+        );
+        BTreeNode::Internal { keys, children }
+    }
+}
+
+/// Split `total` items into node-sized chunks of at most `cap`, packing all
+/// but the last chunk full. If the last chunk would hold fewer than `min`
+/// items, the last two are evened out so every node meets the minimum.
+fn chunk_sizes(total: usize, cap: usize, min: usize) -> Vec<usize> {
+    let mut sizes = vec![cap; total / cap];
+    if !total.is_multiple_of(cap) || sizes.is_empty() {
+        sizes.push(total % cap);
+    }
+    let n = sizes.len();
+    if n > 1 && sizes[n - 1] < min {
+        let combined = sizes[n - 2] + sizes[n - 1];
+        sizes[n - 2] = combined - combined / 2;
+        sizes[n - 1] = combined / 2;
+    }
+    sizes
+}
+
+/// Compare a key against a range bound. When the bound is a shorter
+/// composite prefix, only the leading columns it pins are compared.
+fn cmp_bound(key: &JsonValue, bound: &JsonValue) -> std::cmp::Ordering {
+    match (key, bound) {
+        (JsonValue::Array(vk), JsonValue::Array(vb)) if vb.len() < vk.len() => {
+            cmp_json(&JsonValue::Array(vk[..vb.len()].to_vec()), bound)
         }
-        (JsonValue::String(sa), JsonValue::String(sb)) => sa.cmp(sb),
-        // Fall back to string representation for mixed types.
-        _ => a.to_string().cmp(&b.to_string()),
+        _ => cmp_json(key, bound),
     }
 }
 
@@ -96,13 +144,17 @@ pub struct BTreeIndexBlock {
     fanout: usize,
     key_column: String,
     unique: bool,
+    sorted: bool,
 
     // Internal state
     nodes: Vec<BTreeNode>,
     root: usize,
     total_keys: usize,
     split_count: usize,
+    merge_count: usize,
     comparison_count: usize,
+    lookup_count: usize,
+    range_scan_count: usize,
 }
 
 impl BTreeIndexBlock {
@@ -116,16 +168,21 @@ pub fn new() -> Self {
             fanout: 128,
             key_column: "id".into(),
             unique: false,
+            sorted: false,
             nodes: Vec::new(),
             root: 0,
             total_keys: 0,
             split_count: 0,
+            merge_count: 0,
             comparison_count: 0,
+            lookup_count: 0,
+            range_scan_count: 0,
         };
         // Start with an empty leaf as root.
         block.nodes.push(BTreeNode::Leaf {
             entries: Vec::new(),
             next_leaf: None,
+            prev_leaf: None,
         });
         block
     }
@@ -174,6 +231,18 @@ fn build_metadata() -> BlockMetadata {
                            2. Descend to the appropriate child\n  \
                            3. At the leaf, scan entries for an exact match\n  \
                            4. Total pages read = tree depth (typically 3-4)\n\n\
+                           BULK LOAD (sorted input):\n  \
+                           1. Pack the sorted entries into full leaves and chain them\n  \
+                           2. Group each level's nodes into parents of up to fanout+1 children,\n     \
+                              using each child's smallest key as a separator\n  \
+                           3. Repeat until a single root remains (no splits, minimal depth)\n\n\
+                           DELETE:\n  \
+                           1. Descend to the leaf holding the key and remove the entry\n  \
+                           2. If the leaf now has < fanout/2 entries (underflow):\n    \
+                              a. Borrow an entry from a sibling that has more than the minimum\n    \
+                              b. Otherwise merge with a sibling and drop their separator from the parent\n    \
+                              c. If the parent underflows, rebalance it the same way\n  \
+                           3. If the root is left with a single child, that child becomes the root\n\n\
                            RANGE SCAN (start_key to end_key):\n  \
                            1. Descend from root to the leaf containing start_key\n  \
                            2. Scan entries in the leaf where key >= start_key\n  \
@@ -229,8 +298,10 @@ fn build_metadata() -> BlockMetadata {
                     ("key_column".into(),
                      "The column to build the index on. This should be the column most frequently \
                       used in WHERE clauses, JOIN conditions, or ORDER BY. The column values must \
-                      be comparable (numbers or strings). For composite indexes, you would create \
-                      multiple B-tree blocks or use a concatenated key. Default is 'id'."
+                      be comparable (numbers or strings). For a composite index, list several \
+                      columns separated by commas (e.g. 'customer_id,order_date'): keys are \
+                      compared column by column, so the index serves queries that pin the leading \
+                      columns, like WHERE a = 1 AND b > 5. Default is 'id'."
                          .into()),
                     ("unique".into(),
                      "When enabled, the index rejects duplicate key values on insert, effectively \
@@ -238,6 +309,16 @@ fn build_metadata() -> BlockMetadata {
                       and UNIQUE constraints — via a unique B-tree index. When disabled, multiple \
                       records can have the same key value. Default is false."
                          .into()),
+                    ("sorted".into(),
+                     "When enabled, a batch arriving at an empty index is treated as already sorted \
+                      by key and bulk-loaded bottom-up instead of inserted one record at a time. \
+                      Leaves are packed full and the upper levels are built directly, so the tree \
+                      ends up at minimal depth with no splits — this is how CREATE INDEX builds an \
+                      index over existing data. An unsorted initial batch is rejected. Later \
+                      batches, and a batch that also carries lookups or range scans, are inserted \
+                      record by record, since bulk-loading a populated tree would rebuild it from \
+                      every existing key. Default is false."
+                         .into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -280,16 +361,33 @@ fn build_metadata() -> BlockMetadata {
     }
 
     fn build_inputs() -> Vec<Port> {
-        vec![Port {
-            id: "records".into(),
-            name: "Records".into(),
-            port_type: PortType::DataStream,
-            direction: PortDirection::Input,
-            required: true,
-            multiple: false,
-            description: "Records to index (must contain key_column and _page_id/_slot_id)".into(),
-            schema: None,
-        }]
+        vec![
+            Port {
+                id: "records".into(),
+                name: "Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Input,
+                required: true,
+                multiple: false,
+                description: "Records to index (key_column and _page_id/_slot_id); optional _op \
+                              of insert/lookup/range"
+                    .into(),
+                schema: None,
+            },
+            Port {
+                id: "remap".into(),
+                name: "Remap".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Input,
+                required: false,
+                multiple: false,
+                description: "Moved records (key_column, _page_id/_slot_id, \
+                              _old_page_id/_old_slot_id) whose entries to re-point, or \
+                              reclaimed ones (_op delete) whose entries to drop"
+                    .into(),
+                schema: None,
+            },
+        ]
     }
 
     fn build_outputs() -> Vec<Port> {
@@ -327,7 +425,8 @@ fn build_parameters() -> Vec<Parameter> {
                 id: "key_column".into(),
                 name: "Key Column".into(),
                 param_type: ParameterType::String,
-                description: "Name of the column to index".into(),
+                description: "Column to index, or a comma-separated list for a composite key"
+                    .into(),
                 default_value: ParameterValue::String("id".into()),
                 required: true,
                 constraints: None,
@@ -343,6 +442,17 @@ fn build_parameters() -> Vec<Parameter> {
                 constraints: None,
                 ui_hint: Some(ParameterUIHint::new(WidgetType::Checkbox)),
             },
+            Parameter {
+                id: "sorted".into(),
+                name: "Sorted Input".into(),
+                param_type: ParameterType::Boolean,
+                description: "Bulk-load a sorted initial batch bottom-up instead of inserting one by one"
+                    .into(),
+                default_value: ParameterValue::Boolean(false),
+                required: false,
+                constraints: None,
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Checkbox)),
+            },
         ]
     }
 
@@ -388,6 +498,14 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Node splits during inserts".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "merges".into(),
+                name: "Node Merges".into(),
+                metric_type: MetricType::Counter,
+                unit: "ops".into(),
+                description: "Node merges during deletes".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
             MetricDefinition {
                 id: "comparisons".into(),
                 name: "Comparisons".into(),
@@ -433,10 +551,7 @@ pub fn insert_key(
         if let Some((median, new_child)) = result {
             // Root was split — create a new root.
             let old_root = self.root;
-            let new_root = BTreeNode::Internal {
-                keys: vec![median],
-                children: vec![old_root, new_child],
-            };
+            let new_root = BTreeNode::internal(vec![median], vec![old_root, new_child]);
             let new_root_idx = self.nodes.len();
             self.nodes.push(new_root);
             self.root = new_root_idx;
@@ -447,6 +562,80 @@ pub fn insert_key(
         Ok(())
     }
 
+    /// Rebuild the tree bottom-up from entries sorted by key, replacing its
+    /// current contents. Leaves are packed to `fanout` entries and each
+    /// internal level is built directly, so no splits occur.
+    pub fn bulk_load(&mut self, entries: Vec<(JsonValue, TupleId)>) -> Result<(), String> {
+        for pair in entries.windows(2) {
+            self.comparison_count += 1;
+            match cmp_json(&pair[0].0, &pair[1].0) {
+                std::cmp::Ordering::Greater => {
+                    return Err(format!("Bulk load input is not sorted at key {}", pair[1].0));
+                }
+                std::cmp::Ordering::Equal if self.unique => {
+                    return Err(format!("Duplicate key: {}", pair[1].0));
+                }
+                _ => {}
+            }
+        }
+
+        self.nodes.clear();
+        self.total_keys = entries.len();
+
+        // Leaf level: (smallest key, node index) for each packed leaf.
+        let mut level: Vec<(JsonValue, usize)> = Vec::new();
+        let mut remaining = entries.into_iter();
+        for size in chunk_sizes(self.total_keys, self.fanout, self.fanout / 2) {
+            let leaf: Vec<LeafEntry> = remaining
+                .by_ref()
+                .take(size)
+                .map(|(key, tuple_id)| LeafEntry { key, tuple_id })
+                .collect();
+            let idx = self.nodes.len();
+            let min_key = leaf.first().map(|e| e.key.clone()).unwrap_or(JsonValue::Null);
+            let prev = level.last().map(|&(_, prev)| prev);
+            if let Some(prev) = prev {
+                if let BTreeNode::Leaf { next_leaf, .. } = &mut self.nodes[prev] {
+                    *next_leaf = Some(idx);
+                }
+            }
+            self.nodes.push(BTreeNode::Leaf {
+                entries: leaf,
+                next_leaf: None,
+                prev_leaf: prev,
+            });
+            level.push((min_key, idx));
+        }
+
+        // Internal levels, until a single root remains.
+        while level.len() > 1 {
+            let sizes = chunk_sizes(level.len(), self.fanout + 1, self.fanout / 2 + 1);
+            let mut remaining = level.into_iter();
+            level = Vec::new();
+            for size in sizes {
+                let group: Vec<(JsonValue, usize)> = remaining.by_ref().take(size).collect();
+                let idx = self.nodes.len();
+                self.nodes.push(BTreeNode::internal(
+                    group[1..].iter().map(|(k, _)| k.clone()).collect(),
+                    group.iter().map(|&(_, c)| c).collect(),
+                ));
+                level.push((group[0].0.clone(), idx));
+            }
+        }
+
+        self.root = level[0].1;
+        Ok(())
+    }
+
+    /// A record's `_op`, defaulting to `insert`.
+    fn record_op(record: &Record) -> String {
+        record
+            .get::<String>("_op")
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "insert".into())
+    }
+
     /// Recursively insert into the subtree rooted at `node_idx`.
     /// Returns `Some((median_key, new_node_idx))` if the node was split.
     fn insert_recursive(
@@ -456,7 +645,11 @@ fn insert_recursive(
         tuple_id: TupleId,
     ) -> Option<(JsonValue, usize)> {
         match self.nodes[node_idx].clone() {
-            BTreeNode::Leaf { mut entries, next_leaf } => {
+            BTreeNode::Leaf {
+                mut entries,
+                next_leaf,
+                prev_leaf,
+            } => {
                 // Find position via binary search.
                 let pos = entries
                     .binary_search_by(|e| {
@@ -479,21 +672,31 @@ fn insert_recursive(
                     self.nodes[node_idx] = BTreeNode::Leaf {
                         entries,
                         next_leaf: Some(new_leaf_idx),
+                        prev_leaf,
                     };
                     // Right leaf gets entries[mid..], inherits old next_leaf.
                     self.nodes.push(BTreeNode::Leaf {
                         entries: right_entries,
                         next_leaf,
+                        prev_leaf: Some(node_idx),
                     });
+                    self.set_prev_leaf(next_leaf, new_leaf_idx);
 
                     self.split_count += 1;
                     Some((median, new_leaf_idx))
                 } else {
-                    self.nodes[node_idx] = BTreeNode::Leaf { entries, next_leaf };
+                    self.nodes[node_idx] = BTreeNode::Leaf {
+                        entries,
+                        next_leaf,
+                        prev_leaf,
+                    };
                     None
                 }
             }
-            BTreeNode::Internal { keys, children } => {
+            BTreeNode::Internal {
+                mut keys,
+                mut children,
+            } => {
                 // Find which child to descend into.
                 let mut child_pos = keys.len();
                 for (i, k) in keys.iter().enumerate() {
@@ -505,65 +708,271 @@ fn insert_recursive(
                 }
 
                 let child_idx = children[child_pos];
-                let split_result = self.insert_recursive(child_idx, key, tuple_id);
-
-                if let Some((median, new_child_idx)) = split_result {
-                    let mut keys = self.internal_keys(node_idx);
-                    let children_ref = self.internal_children_mut(node_idx);
-                    // Insert median and new child pointer.
-                    keys.insert(child_pos, median);
-                    children_ref.insert(child_pos + 1, new_child_idx);
-                    let children_new = children_ref.clone();
-
-                    if keys.len() > self.fanout {
-                        // Split the internal node.
-                        let mid = keys.len() / 2;
-                        let up_key = keys[mid].clone();
-
-                        let right_keys: Vec<_> = keys.drain(mid + 1..).collect();
-                        keys.truncate(mid);
-                        // children_new has keys.len()+1 entries before drain.
-                        // After splitting keys at mid, left gets keys[0..mid], right gets keys[mid+1..].
-                        let left_children: Vec<_> = children_new[..mid + 1].to_vec();
-                        let right_children: Vec<_> = children_new[mid + 1..].to_vec();
-
-                        let new_internal_idx = self.nodes.len();
-                        self.nodes[node_idx] = BTreeNode::Internal {
-                            keys,
-                            children: left_children,
-                        };
-                        self.nodes.push(BTreeNode::Internal {
-                            keys: right_keys,
-                            children: right_children,
-                        });
-
-                        self.split_count += 1;
-                        Some((up_key, new_internal_idx))
-                    } else {
-                        self.nodes[node_idx] = BTreeNode::Internal {
-                            keys,
-                            children: children_new,
-                        };
-                        None
+                let (median, new_child_idx) = self.insert_recursive(child_idx, key, tuple_id)?;
+
+                // The recursive call only rewrites descendants, so the copy of
+                // this node taken above is still current. Insert the median
+                // and the new right sibling's pointer next to the split child.
+                keys.insert(child_pos, median);
+                children.insert(child_pos + 1, new_child_idx);
+
+                if keys.len() <= self.fanout {
+                    self.nodes[node_idx] = BTreeNode::internal(keys, children);
+                    return None;
+                }
+
+                // Split the internal node: keys[mid] moves up, the left node
+                // keeps keys[..mid] with children[..=mid], and the right node
+                // takes keys[mid + 1..] with children[mid + 1..].
+                let mid = keys.len() / 2;
+                let right_keys = keys.split_off(mid + 1);
+                let up_key = keys.pop().expect("split node has a median key");
+                let right_children = children.split_off(mid + 1);
+
+                let new_internal_idx = self.nodes.len();
+                self.nodes[node_idx] = BTreeNode::internal(keys, children);
+                self.nodes.push(BTreeNode::internal(right_keys, right_children));
+
+                self.split_count += 1;
+                Some((up_key, new_internal_idx))
+            }
+        }
+    }
+
+    /// Delete one entry with the given key.
+    ///
+    /// Returns `false` if the key is not present. Underfull nodes borrow from
+    /// a sibling or merge with it; the root collapses when it is left with a
+    /// single child, shrinking the tree's depth.
+    pub fn delete_key(&mut self, key: &JsonValue) -> bool {
+        self.delete_matching(key, None)
+    }
+
+    /// Delete the entry for `key` that points at `tid`, leaving any other
+    /// entries with the same key. Returns `false` if there is no such entry.
+    pub fn delete_entry(&mut self, key: &JsonValue, tid: TupleId) -> bool {
+        self.delete_matching(key, Some(tid))
+    }
+
+    /// Delete one entry with the given key, restricted to `tid` if given,
+    /// then collapse the root while it has a single child.
+    fn delete_matching(&mut self, key: &JsonValue, tid: Option<TupleId>) -> bool {
+        if self.delete_recursive(self.root, key, tid).is_none() {
+            return false;
+        }
+
+        while let BTreeNode::Internal { keys, children } = &self.nodes[self.root] {
+            if !keys.is_empty() {
+                break;
+            }
+            let only_child = children[0];
+            self.take_node(self.root);
+            self.root = only_child;
+        }
+
+        self.total_keys -= 1;
+        true
+    }
+
+    /// Recursively delete from the subtree rooted at `node_idx`.
+    /// Returns `None` if no matching entry was found, otherwise whether the
+    /// node is now underfull.
+    fn delete_recursive(
+        &mut self,
+        node_idx: usize,
+        key: &JsonValue,
+        tid: Option<TupleId>,
+    ) -> Option<bool> {
+        let min = self.fanout / 2;
+        let (first_pos, candidates) = match &mut self.nodes[node_idx] {
+            BTreeNode::Leaf { entries, .. } => {
+                let mut found = None;
+                for (i, entry) in entries.iter().enumerate() {
+                    self.comparison_count += 1;
+                    if cmp_json(&entry.key, key) == std::cmp::Ordering::Equal
+                        && tid.is_none_or(|tid| entry.tuple_id == tid)
+                    {
+                        found = Some(i);
+                        break;
                     }
-                } else {
-                    None
                 }
+                entries.remove(found?);
+                return Some(entries.len() < min);
+            }
+            BTreeNode::Internal { keys, children } => {
+                let mut child_pos = keys.len();
+                for (i, k) in keys.iter().enumerate() {
+                    self.comparison_count += 1;
+                    if cmp_json(key, k) == std::cmp::Ordering::Less {
+                        child_pos = i;
+                        break;
+                    }
+                }
+                // Duplicates can straddle separators equal to `key`, so a
+                // specific entry may sit in any child from the leftmost one
+                // that can hold `key`.
+                let first_pos = match tid {
+                    Some(_) => keys
+                        .iter()
+                        .position(|k| cmp_json(key, k) != std::cmp::Ordering::Greater)
+                        .unwrap_or(keys.len()),
+                    None => child_pos,
+                };
+                (first_pos, children[first_pos..=child_pos].to_vec())
+            }
+        };
+
+        for (pos, child_idx) in (first_pos..).zip(candidates) {
+            if let Some(underfull) = self.delete_recursive(child_idx, key, tid) {
+                if underfull {
+                    self.rebalance_child(node_idx, pos);
+                }
+                return Some(self.node_len(node_idx) < min);
             }
         }
+        None
     }
 
-    fn internal_keys(&self, idx: usize) -> Vec<JsonValue> {
-        match &self.nodes[idx] {
-            BTreeNode::Internal { keys, .. } => keys.clone(),
-            _ => vec![],
+    /// Fix an underfull child by borrowing from or merging with a sibling.
+    fn rebalance_child(&mut self, parent_idx: usize, child_pos: usize) {
+        let min = self.fanout / 2;
+        let (mut keys, mut children) = match self.take_node(parent_idx) {
+            BTreeNode::Internal { keys, children } => (keys, children),
+            leaf => {
+                self.nodes[parent_idx] = leaf;
+                return;
+            }
+        };
+
+        if child_pos > 0 && self.node_len(children[child_pos - 1]) > min {
+            self.borrow_from_left(&mut keys, &children, child_pos);
+        } else if child_pos + 1 < children.len() && self.node_len(children[child_pos + 1]) > min {
+            self.borrow_from_right(&mut keys, &children, child_pos);
+        } else if children.len() > 1 {
+            let left_pos = child_pos.saturating_sub(1);
+            self.merge_children(&mut keys, &mut children, left_pos);
+        }
+
+        self.nodes[parent_idx] = BTreeNode::internal(keys, children);
+    }
+
+    /// Move the last entry of the left sibling into the child at `pos`.
+    fn borrow_from_left(&mut self, keys: &mut [JsonValue], children: &[usize], pos: usize) {
+        let mut left = self.take_node(children[pos - 1]);
+        let mut child = self.take_node(children[pos]);
+        match (&mut left, &mut child) {
+            (BTreeNode::Leaf { entries: le, .. }, BTreeNode::Leaf { entries: ce, .. }) => {
+                let moved = le.pop().expect("sibling has spare entries");
+                keys[pos - 1] = moved.key.clone();
+                ce.insert(0, moved);
+            }
+            (
+                BTreeNode::Internal { keys: lk, children: lc },
+                BTreeNode::Internal { keys: ck, children: cc },
+            ) => {
+                let up = lk.pop().expect("sibling has spare keys");
+                ck.insert(0, std::mem::replace(&mut keys[pos - 1], up));
+                cc.insert(0, lc.pop().expect("sibling has spare children"));
+                debug_assert_eq!(lc.len(), lk.len() + 1);
+                debug_assert_eq!(cc.len(), ck.len() + 1);
+            }
+            _ => {}
+        }
+        self.nodes[children[pos - 1]] = left;
+        self.nodes[children[pos]] = child;
+    }
+
+    /// Move the first entry of the right sibling into the child at `pos`.
+    fn borrow_from_right(&mut self, keys: &mut [JsonValue], children: &[usize], pos: usize) {
+        let mut child = self.take_node(children[pos]);
+        let mut right = self.take_node(children[pos + 1]);
+        match (&mut child, &mut right) {
+            (BTreeNode::Leaf { entries: ce, .. }, BTreeNode::Leaf { entries: re, .. }) => {
+                ce.push(re.remove(0));
+                keys[pos] = re[0].key.clone();
+            }
+            (
+                BTreeNode::Internal { keys: ck, children: cc },
+                BTreeNode::Internal { keys: rk, children: rc },
+            ) => {
+                let up = rk.remove(0);
+                ck.push(std::mem::replace(&mut keys[pos], up));
+                cc.push(rc.remove(0));
+                debug_assert_eq!(cc.len(), ck.len() + 1);
+                debug_assert_eq!(rc.len(), rk.len() + 1);
+            }
+            _ => {}
+        }
+        self.nodes[children[pos]] = child;
+        self.nodes[children[pos + 1]] = right;
+    }
+
+    /// Merge the child at `left_pos + 1` into the child at `left_pos`,
+    /// removing their separator key from the parent.
+    fn merge_children(
+        &mut self,
+        keys: &mut Vec<JsonValue>,
+        children: &mut Vec<usize>,
+        left_pos: usize,
+    ) {
+        let separator = keys.remove(left_pos);
+        let left_idx = children[left_pos];
+        let right = self.take_node(children.remove(left_pos + 1));
+        match (&mut self.nodes[left_idx], right) {
+            (
+                BTreeNode::Leaf {
+                    entries, next_leaf, ..
+                },
+                BTreeNode::Leaf {
+                    entries: re,
+                    next_leaf: rn,
+                    ..
+                },
+            ) => {
+                entries.extend(re);
+                *next_leaf = rn;
+                self.set_prev_leaf(rn, left_idx);
+            }
+            (
+                BTreeNode::Internal { keys: lk, children: lc },
+                BTreeNode::Internal { keys: rk, children: rc },
+            ) => {
+                lk.push(separator);
+                lk.extend(rk);
+                lc.extend(rc);
+                debug_assert_eq!(lc.len(), lk.len() + 1);
+            }
+            _ => {}
+        }
+        self.merge_count += 1;
+    }
+
+    /// Point the leaf at `idx` (if any) back at `prev`.
+    fn set_prev_leaf(&mut self, idx: Option<usize>, prev: usize) {
+        if let Some(idx) = idx {
+            if let BTreeNode::Leaf { prev_leaf, .. } = &mut self.nodes[idx] {
+                *prev_leaf = Some(prev);
+            }
         }
     }
 
-    fn internal_children_mut(&mut self, idx: usize) -> &mut Vec<usize> {
-        match &mut self.nodes[idx] {
-            BTreeNode::Internal { children, .. } => children,
-            _ => panic!("Expected internal node"),
+    /// Take a node out of the arena, leaving an empty leaf in its slot.
+    fn take_node(&mut self, idx: usize) -> BTreeNode {
+        std::mem::replace(
+            &mut self.nodes[idx],
+            BTreeNode::Leaf {
+                entries: Vec::new(),
+                next_leaf: None,
+                prev_leaf: None,
+            },
+        )
+    }
+
+    /// Entries in a leaf, or keys in an internal node.
+    fn node_len(&self, idx: usize) -> usize {
+        match &self.nodes[idx] {
+            BTreeNode::Leaf { entries, .. } => entries.len(),
+            BTreeNode::Internal { keys, .. } => keys.len(),
         }
     }
 
@@ -596,7 +1005,90 @@ pub fn lookup(&mut self, key: &JsonValue) -> Option<TupleId> {
         }
     }
 
+    /// Re-point the entry for `key` at `old` to `new`, after the record it
+    /// indexes moved. Returns whether such an entry was found.
+    pub fn remap(&mut self, key: &JsonValue, old: TupleId, new: TupleId) -> bool {
+        // Descend to the leftmost leaf that may hold `key`: duplicates can
+        // straddle a separator equal to it.
+        let mut idx = self.root;
+        while let BTreeNode::Internal { keys, children } = &self.nodes[idx] {
+            let pos = keys
+                .iter()
+                .position(|k| cmp_json(key, k) != std::cmp::Ordering::Greater)
+                .unwrap_or(keys.len());
+            idx = children[pos];
+        }
+
+        loop {
+            let BTreeNode::Leaf {
+                entries, next_leaf, ..
+            } = &mut self.nodes[idx]
+            else {
+                return false;
+            };
+            for entry in entries.iter_mut() {
+                self.comparison_count += 1;
+                match cmp_json(&entry.key, key) {
+                    std::cmp::Ordering::Less => {}
+                    std::cmp::Ordering::Greater => return false,
+                    std::cmp::Ordering::Equal if entry.tuple_id == old => {
+                        entry.tuple_id = new;
+                        return true;
+                    }
+                    std::cmp::Ordering::Equal => {}
+                }
+            }
+            match *next_leaf {
+                Some(next) => idx = next,
+                None => return false,
+            }
+        }
+    }
+
+    /// Build the index key for a record from `key_column`. A comma-separated
+    /// list of columns yields a composite key as a JSON array.
+    ///
+    /// A key column that is present but null is indexed as a null key (sorting
+    /// last); one that is absent is an error rather than a silent null entry.
+    pub fn extract_key(&self, record: &Record) -> Result<JsonValue, String> {
+        let columns: Vec<&str> = self.key_column.split(',').map(str::trim).collect();
+        let value_of = |col: &str| {
+            record
+                .data
+                .get(col)
+                .cloned()
+                .ok_or_else(|| format!("record is missing key column '{}'", col))
+        };
+        if columns.len() == 1 {
+            value_of(columns[0])
+        } else {
+            columns.into_iter().map(value_of).collect::<Result<_, _>>().map(JsonValue::Array)
+        }
+    }
+
+    /// Output record for an index match: the key column(s) plus the
+    /// resolved TupleId as `_page_id`/`_slot_id`/`_tuple_id`.
+    fn result_record(&self, key: &JsonValue, tid: TupleId) -> Record {
+        let columns: Vec<&str> = self.key_column.split(',').map(str::trim).collect();
+        let mut record = Record::new();
+        match key {
+            JsonValue::Array(parts) if columns.len() > 1 => {
+                for (col, part) in columns.iter().zip(parts) {
+                    record.data.insert((*col).to_string(), part.clone());
+                }
+            }
+            _ => {
+                record.data.insert(columns[0].to_string(), key.clone());
+            }
+        }
+        let _ = record.insert("_page_id".into(), tid.page_id);
+        let _ = record.insert("_slot_id".into(), tid.slot_id);
+        let _ = record.insert("_tuple_id".into(), tid);
+        record
+    }
+
     /// Range scan — returns all entries where start <= key <= end, in order.
+    /// For composite keys, either bound may be a prefix of the key columns.
     pub fn range_scan(
         &mut self,
         start: &JsonValue,
@@ -626,16 +1118,18 @@ pub fn range_scan(
         // Walk the leaf chain collecting matching entries.
         loop {
             let (entries, next) = match &self.nodes[idx] {
-                BTreeNode::Leaf { entries, next_leaf } => (entries.clone(), *next_leaf),
+                BTreeNode::Leaf {
+                    entries, next_leaf, ..
+                } => (entries.clone(), *next_leaf),
                 _ => break,
             };
 
             for entry in &entries {
                 self.comparison_count += 1;
-                if cmp_json(&entry.key, start) == std::cmp::Ordering::Less {
+                if cmp_bound(&entry.key, start) == std::cmp::Ordering::Less {
                     continue;
                 }
-                if cmp_json(&entry.key, end) == std::cmp::Ordering::Greater {
+                if cmp_bound(&entry.key, end) == std::cmp::Ordering::Greater {
                     return results;
                 }
                 results.push((entry.key.clone(), entry.tuple_id));
@@ -650,6 +1144,57 @@ pub fn range_scan(
         results
     }
 
+    /// Descending range scan — returns all entries where start <= key <= end,
+    /// from the highest key to the lowest. Walks the leaf chain backwards via
+    /// `prev_leaf`, so the cost is proportional to the result set.
+    pub fn range_scan_rev(
+        &mut self,
+        start: &JsonValue,
+        end: &JsonValue,
+    ) -> Vec<(JsonValue, TupleId)> {
+        let mut results = Vec::new();
+
+        // Walk to the last leaf that might contain `end`.
+        let mut idx = self.root;
+        while let BTreeNode::Internal { keys, children } = &self.nodes[idx] {
+            let mut child_pos = keys.len();
+            for (i, k) in keys.iter().enumerate() {
+                self.comparison_count += 1;
+                if cmp_bound(k, end) == std::cmp::Ordering::Greater {
+                    child_pos = i;
+                    break;
+                }
+            }
+            idx = children[child_pos];
+        }
+
+        // Walk the leaf chain backwards collecting matching entries.
+        while let BTreeNode::Leaf {
+            entries, prev_leaf, ..
+        } = &self.nodes[idx]
+        {
+            let (entries, prev) = (entries.clone(), *prev_leaf);
+
+            for entry in entries.iter().rev() {
+                self.comparison_count += 1;
+                if cmp_bound(&entry.key, end) == std::cmp::Ordering::Greater {
+                    continue;
+                }
+                if cmp_bound(&entry.key, start) == std::cmp::Ordering::Less {
+                    return results;
+                }
+                results.push((entry.key.clone(), entry.tuple_id));
+            }
+
+            match prev {
+                Some(prev_idx) => idx = prev_idx,
+                None => break,
+            }
+        }
+
+        results
+    }
+
     pub fn key_count(&self) -> usize {
         self.total_keys
     }
@@ -705,30 +1250,18 @@ async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("fanout") {
-            let f = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("fanout must be an integer".into()))?
-                as usize;
-            if f < 3 || f > 1024 {
-                return Err(BlockError::InvalidParameter(
-                    "fanout must be between 3 and 1024".into(),
-                ));
-            }
-            self.fanout = f;
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("fanout").and_then(ParameterValue::as_integer) {
+            self.fanout = v as usize;
         }
-        if let Some(val) = params.get("key_column") {
-            self.key_column = val
-                .as_string()
-                .ok_or_else(|| {
-                    BlockError::InvalidParameter("key_column must be a string".into())
-                })?
-                .to_string();
+        if let Some(v) = params.get("key_column").and_then(ParameterValue::as_string) {
+            self.key_column = v.to_string();
         }
-        if let Some(val) = params.get("unique") {
-            self.unique = val.as_bool().ok_or_else(|| {
-                BlockError::InvalidParameter("unique must be a boolean".into())
-            })?;
+        if let Some(v) = params.get("unique").and_then(ParameterValue::as_bool) {
+            self.unique = v;
+        }
+        if let Some(v) = params.get("sorted").and_then(ParameterValue::as_bool) {
+            self.sorted = v;
         }
         Ok(())
     }
@@ -756,28 +1289,129 @@ async fn execute(
         };
 
         let mut errors = Vec::new();
+        let mut batch = Vec::new();
+        let mut results = Vec::new();
+        let mut lookups = 0usize;
+        let mut range_scans = 0usize;
+
+        // Re-point entries for records the heap moved.
+        let remaps = match context.inputs.get("remap") {
+            Some(PortValue::Stream(r)) | Some(PortValue::Batch(r)) => r.clone(),
+            Some(PortValue::Single(r)) => vec![r.clone()],
+            _ => Vec::new(),
+        };
+        for record in &remaps {
+            let key = match self.extract_key(record) {
+                Ok(key) => key,
+                Err(e) => {
+                    errors.push(BlockError::InvalidInput(e));
+                    continue;
+                }
+            };
+            let tid = |page: &str, slot: &str| {
+                let page = record.get::<usize>(page).ok().flatten()?;
+                let slot = record.get::<usize>(slot).ok().flatten()?;
+                Some(TupleId::new(page, slot))
+            };
+            if Self::record_op(record) == "delete" {
+                match tid("_page_id", "_slot_id") {
+                    Some(reclaimed) => {
+                        self.delete_entry(&key, reclaimed);
+                    }
+                    None => errors.push(BlockError::InvalidInput(
+                        "remap delete requires _page_id/_slot_id".into(),
+                    )),
+                }
+                continue;
+            }
+            let (Some(old), Some(new)) =
+                (tid("_old_page_id", "_old_slot_id"), tid("_page_id", "_slot_id"))
+            else {
+                errors.push(BlockError::InvalidInput(
+                    "remap requires _old_page_id/_old_slot_id and _page_id/_slot_id".into(),
+                ));
+                continue;
+            };
+            self.remap(&key, old, new);
+        }
+
+        // Bulk-load only a batch of pure inserts into an empty tree. Loading
+        // into a populated tree would rebuild it from every existing key, so
+        // later batches — and reads interleaved with inserts — go through
+        // ordinary inserts instead.
+        let bulk = self.sorted
+            && self.total_keys == 0
+            && records.iter().all(|r| Self::record_op(r) == "insert");
 
         for record in &records {
-            let key = record
-                .data
-                .get(&self.key_column)
-                .cloned()
-                .unwrap_or(JsonValue::Null);
-
-            let page_id = record
-                .get::<usize>("_page_id")
-                .ok()
-                .flatten()
-                .unwrap_or(0);
-            let slot_id = record
-                .get::<usize>("_slot_id")
-                .ok()
-                .flatten()
-                .unwrap_or(0);
-
-            let tid = TupleId::new(page_id, slot_id);
-
-            if let Err(e) = self.insert_key(key, tid) {
+            let op = Self::record_op(record);
+
+            match op.as_str() {
+                "insert" => {
+                    let key = match self.extract_key(record) {
+                        Ok(key) => key,
+                        Err(e) => {
+                            errors.push(BlockError::InvalidInput(e));
+                            continue;
+                        }
+                    };
+
+                    let page_id = record
+                        .get::<usize>("_page_id")
+                        .ok()
+                        .flatten()
+                        .unwrap_or(0);
+                    let slot_id = record
+                        .get::<usize>("_slot_id")
+                        .ok()
+                        .flatten()
+                        .unwrap_or(0);
+
+                    let tid = TupleId::new(page_id, slot_id);
+
+                    if bulk {
+                        batch.push((key, tid));
+                    } else if let Err(e) = self.insert_key(key, tid) {
+                        errors.push(BlockError::ExecutionError(e));
+                    }
+                }
+                "lookup" => {
+                    let key = match self.extract_key(record) {
+                        Ok(key) => key,
+                        Err(e) => {
+                            errors.push(BlockError::InvalidInput(e));
+                            continue;
+                        }
+                    };
+                    lookups += 1;
+                    context.metrics.increment("lookups");
+                    if let Some(tid) = self.lookup(&key) {
+                        results.push(self.result_record(&key, tid));
+                    }
+                }
+                "range" => {
+                    let (Some(start), Some(end)) = (record.data.get("start"), record.data.get("end"))
+                    else {
+                        errors.push(BlockError::InvalidInput(
+                            "range requires start and end fields".into(),
+                        ));
+                        continue;
+                    };
+                    range_scans += 1;
+                    context.metrics.increment("range_scans");
+                    for (key, tid) in self.range_scan(start, end) {
+                        results.push(self.result_record(&key, tid));
+                    }
+                }
+                other => errors.push(BlockError::InvalidInput(format!(
+                    "unknown _op '{}' (expected insert, lookup, or range)",
+                    other
+                ))),
+            }
+        }
+
+        if !batch.is_empty() {
+            if let Err(e) = self.bulk_load(batch) {
                 errors.push(BlockError::ExecutionError(e));
             }
         }
@@ -790,6 +1424,9 @@ async fn execute(
         context
             .metrics
             .record("splits", self.split_count as f64);
+        context
+            .metrics
+            .record("merges", self.merge_count as f64);
         context
             .metrics
             .record("comparisons", self.comparison_count as f64);
@@ -798,9 +1435,17 @@ async fn execute(
         metrics_summary.insert("tree_depth".into(), self.depth() as f64);
         metrics_summary.insert("total_keys".into(), self.total_keys as f64);
         metrics_summary.insert("splits".into(), self.split_count as f64);
+        metrics_summary.insert("merges".into(), self.merge_count as f64);
+        self.lookup_count += lookups;
+        self.range_scan_count += range_scans;
+        metrics_summary.insert("lookups".into(), self.lookup_count as f64);
+        metrics_summary.insert("range_scans".into(), self.range_scan_count as f64);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("lookup_results".into(), PortValue::Stream(results));
 
         Ok(ExecutionResult {
-            outputs: HashMap::new(),
+            outputs,
             metrics: metrics_summary,
             errors,
         })
@@ -827,6 +1472,7 @@ fn get_state(&self) -> BlockState {
         let _ = state.insert("fanout".into(), self.fanout);
         let _ = state.insert("key_column".into(), self.key_column.clone());
         let _ = state.insert("unique".into(), self.unique);
+        let _ = state.insert("sorted".into(), self.sorted);
         let _ = state.insert("total_keys".into(), self.total_keys);
         let _ = state.insert("depth".into(), self.depth());
         state
@@ -842,6 +1488,9 @@ fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
         if let Ok(Some(u)) = state.get::<bool>("unique") {
             self.unique = u;
         }
+        if let Ok(Some(s)) = state.get::<bool>("sorted") {
+            self.sorted = s;
+        }
         Ok(())
     }
 }
@@ -968,14 +1617,390 @@ fn test_splits_counted() {
         );
     }
 
+    #[test]
+    fn test_small_fanout_reverse_inserts() {
+        for fanout in [3, 4] {
+            let mut tree = BTreeIndexBlock::new();
+            tree.fanout = fanout;
+
+            for i in (0..500).rev() {
+                tree.insert_key(json!(i), TupleId::new(0, i as usize)).unwrap();
+            }
+
+            assert!(tree.split_count > 0);
+            assert_balanced(&tree);
+            for i in 0..500 {
+                assert_eq!(tree.lookup(&json!(i)).unwrap().slot_id, i as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn test_small_fanout_interleaved_inserts() {
+        for fanout in [3, 4] {
+            let mut tree = BTreeIndexBlock::new();
+            tree.fanout = fanout;
+
+            // Alternate between both ends so splits cascade on both sides.
+            for i in 0..250 {
+                tree.insert_key(json!(i), TupleId::new(0, i)).unwrap();
+                tree.insert_key(json!(999 - i), TupleId::new(0, 999 - i)).unwrap();
+            }
+
+            assert_balanced(&tree);
+            assert_eq!(tree.range_scan(&json!(0), &json!(999)).len(), 500);
+        }
+    }
+
+    /// Every non-root node must hold at least fanout/2 entries (keys for
+    /// internal nodes), and all leaves must sit at the same depth.
+    fn assert_balanced(tree: &BTreeIndexBlock) {
+        fn walk(tree: &BTreeIndexBlock, idx: usize, depth: usize, leaf_depths: &mut Vec<usize>) {
+            if idx != tree.root {
+                assert!(tree.node_len(idx) >= tree.fanout / 2, "node {} is underfull", idx);
+            }
+            match &tree.nodes[idx] {
+                BTreeNode::Internal { keys, children } => {
+                    assert_eq!(children.len(), keys.len() + 1);
+                    for &c in children {
+                        walk(tree, c, depth + 1, leaf_depths);
+                    }
+                }
+                BTreeNode::Leaf { .. } => leaf_depths.push(depth),
+            }
+        }
+        let mut leaf_depths = Vec::new();
+        walk(tree, tree.root, 1, &mut leaf_depths);
+        assert!(leaf_depths.iter().all(|&d| d == leaf_depths[0]), "leaves at mixed depths");
+
+        // The leaf chain must be consistently doubly linked.
+        let mut idx = tree.root;
+        while let BTreeNode::Internal { children, .. } = &tree.nodes[idx] {
+            idx = children[0];
+        }
+        let mut expected_prev = None;
+        let mut leaves = 0;
+        while let BTreeNode::Leaf {
+            next_leaf, prev_leaf, ..
+        } = &tree.nodes[idx]
+        {
+            assert_eq!(*prev_leaf, expected_prev, "leaf {} has a stale prev_leaf", idx);
+            leaves += 1;
+            expected_prev = Some(idx);
+            match next_leaf {
+                Some(next) => idx = *next,
+                None => break,
+            }
+        }
+        assert_eq!(leaves, leaf_depths.len(), "leaf chain skips leaves");
+    }
+
+    #[test]
+    fn test_range_scan_rev_matches_forward() {
+        let mut tree = BTreeIndexBlock::new();
+        tree.fanout = 3;
+
+        for i in (0..300).rev() {
+            tree.insert_key(json!(i), TupleId::new(0, i as usize)).unwrap();
+        }
+        for i in (0..300).filter(|i| i % 4 == 1) {
+            assert!(tree.delete_key(&json!(i)));
+        }
+        assert_balanced(&tree);
+
+        for (start, end) in [(0, 299), (17, 123), (150, 150), (290, 400), (-5, 3)] {
+            let mut forward = tree.range_scan(&json!(start), &json!(end));
+            forward.reverse();
+            let before = tree.comparison_count;
+            let reverse = tree.range_scan_rev(&json!(start), &json!(end));
+            assert!(tree.comparison_count > before);
+            assert_eq!(reverse, forward, "range [{}, {}]", start, end);
+        }
+    }
+
+    #[test]
+    fn test_range_scan_rev_composite_prefix() {
+        let mut tree = BTreeIndexBlock::new();
+        tree.fanout = 4;
+        let entries: Vec<_> = (0..5)
+            .flat_map(|a| (0..10).map(move |b| (json!([a, b]), TupleId::new(a, b))))
+            .collect();
+        tree.bulk_load(entries).unwrap();
+        assert_balanced(&tree);
+
+        let desc: Vec<i64> = tree
+            .range_scan_rev(&json!([2]), &json!([2]))
+            .iter()
+            .map(|(k, _)| k[1].as_i64().unwrap())
+            .collect();
+        assert_eq!(desc, (0..10).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_delete_missing_key() {
+        let mut tree = BTreeIndexBlock::new();
+        tree.insert_key(json!(1), TupleId::new(0, 1)).unwrap();
+        assert!(!tree.delete_key(&json!(2)));
+        assert_eq!(tree.key_count(), 1);
+    }
+
+    #[test]
+    fn test_delete_entry_finds_duplicate_in_any_leaf() {
+        let mut tree = BTreeIndexBlock::new();
+        tree.fanout = 3;
+
+        // Enough duplicates of one key to spread over several leaves.
+        for slot in 0..20 {
+            tree.insert_key(json!(7), TupleId::new(0, slot)).unwrap();
+        }
+        assert!(tree.depth() >= 2);
+
+        for slot in (0..20).rev().step_by(3) {
+            assert!(tree.delete_entry(&json!(7), TupleId::new(0, slot)), "slot {}", slot);
+            assert!(!tree.delete_entry(&json!(7), TupleId::new(0, slot)));
+        }
+        assert_balanced(&tree);
+        assert_eq!(tree.key_count(), 13);
+
+        // Exactly the untouched entries remain.
+        for slot in (0..20).filter(|s| (19 - s) % 3 != 0) {
+            assert!(tree.delete_entry(&json!(7), TupleId::new(0, slot)), "slot {}", slot);
+        }
+        assert_eq!(tree.key_count(), 0);
+    }
+
+    #[test]
+    fn test_delete_rebalances_and_shrinks() {
+        let mut tree = BTreeIndexBlock::new();
+        tree.fanout = 4;
+
+        for i in 0..200 {
+            tree.insert_key(json!(i), TupleId::new(0, i as usize)).unwrap();
+        }
+        let full_depth = tree.depth();
+        assert!(full_depth >= 3);
+
+        // Delete in an interleaved order so both borrow and merge paths run.
+        let order: Vec<i64> = (0..200).map(|i| (i * 7) % 200).collect();
+        for (n, &k) in order.iter().enumerate() {
+            assert!(tree.delete_key(&json!(k)), "key {} should be deletable", k);
+            assert!(tree.lookup(&json!(k)).is_none());
+            if n % 25 == 0 {
+                assert_balanced(&tree);
+            }
+        }
+
+        assert_eq!(tree.key_count(), 0);
+        assert!(tree.merge_count > 0, "deletes should merge nodes");
+        assert_eq!(tree.depth(), 1, "empty tree should collapse to a single leaf");
+    }
+
+    #[test]
+    fn test_delete_keeps_remaining_keys_ordered() {
+        let mut tree = BTreeIndexBlock::new();
+        tree.fanout = 3;
+
+        for i in 0..100 {
+            tree.insert_key(json!(i), TupleId::new(0, i as usize)).unwrap();
+        }
+        for i in (0..100).filter(|i| i % 3 == 0) {
+            assert!(tree.delete_key(&json!(i)));
+        }
+        assert_balanced(&tree);
+
+        let remaining: Vec<i64> = (0..100).filter(|i| i % 3 != 0).collect();
+        let scanned: Vec<i64> = tree
+            .range_scan(&json!(0), &json!(99))
+            .iter()
+            .map(|(k, _)| k.as_i64().unwrap())
+            .collect();
+        assert_eq!(scanned, remaining);
+        for k in remaining {
+            assert_eq!(tree.lookup(&json!(k)).unwrap().slot_id, k as usize);
+        }
+    }
+
+    #[test]
+    fn test_composite_keys_order_lexicographically() {
+        assert_eq!(cmp_json(&json!([1, 9]), &json!([2, 0])), std::cmp::Ordering::Less);
+        assert_eq!(cmp_json(&json!([1, "b"]), &json!([1, "a"])), std::cmp::Ordering::Greater);
+        assert_eq!(cmp_json(&json!([1]), &json!([1, 0])), std::cmp::Ordering::Less);
+        assert_eq!(cmp_json(&json!([1, 2]), &json!([1, 2])), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_composite_prefix_range_scan() {
+        let mut tree = BTreeIndexBlock::new();
+        tree.fanout = 4;
+        tree.key_column = "a, b".into();
+
+        let mut slot = 0;
+        for a in 0..5 {
+            for b in 0..10 {
+                let mut r = Record::new();
+                r.insert("a".into(), a as i64).unwrap();
+                r.insert("b".into(), b as i64).unwrap();
+                let key = tree.extract_key(&r).unwrap();
+                tree.insert_key(key, TupleId::new(0, slot)).unwrap();
+                slot += 1;
+            }
+        }
+
+        assert_eq!(tree.lookup(&json!([3, 4])).unwrap().slot_id, 34);
+
+        // WHERE a = 2
+        let a_eq_2 = tree.range_scan(&json!([2]), &json!([2]));
+        assert_eq!(a_eq_2.len(), 10);
+        assert!(a_eq_2.iter().all(|(k, _)| k[0] == json!(2)));
+
+        // WHERE a = 1 AND b > 5
+        let b_gt_5 = tree.range_scan(&json!([1, 6]), &json!([1]));
+        let bs: Vec<i64> = b_gt_5.iter().map(|(k, _)| k[1].as_i64().unwrap()).collect();
+        assert_eq!(bs, vec![6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_bulk_load_builds_minimal_tree() {
+        let mut tree = BTreeIndexBlock::new();
+        tree.fanout = 4;
+        let entries: Vec<_> = (0..1000)
+            .map(|i| (json!(i), TupleId::new(0, i as usize)))
+            .collect();
+        tree.bulk_load(entries).unwrap();
+
+        assert_eq!(tree.split_count, 0);
+        assert_eq!(tree.key_count(), 1000);
+        // 250 full leaves under parents of 5 children: 250 -> 50 -> 10 -> 2 -> 1.
+        assert_eq!(tree.depth(), 5);
+        assert_balanced(&tree);
+
+        for i in 0..1000 {
+            assert_eq!(tree.lookup(&json!(i)).unwrap().slot_id, i as usize);
+        }
+        assert_eq!(tree.range_scan(&json!(0), &json!(999)).len(), 1000);
+
+        // The bulk-loaded tree stays valid under deletes.
+        for i in 0..500 {
+            assert!(tree.delete_key(&json!(i)));
+        }
+        assert_balanced(&tree);
+    }
+
+    #[test]
+    fn test_bulk_load_rejects_unsorted_input() {
+        let mut tree = BTreeIndexBlock::new();
+        tree.insert_key(json!(1), TupleId::new(0, 1)).unwrap();
+        let entries = vec![(json!(5), TupleId::new(0, 5)), (json!(2), TupleId::new(0, 2))];
+        assert!(tree.bulk_load(entries).is_err());
+        // The tree is untouched.
+        assert_eq!(tree.key_count(), 1);
+        assert!(tree.lookup(&json!(1)).is_some());
+    }
+
+    #[test]
+    fn test_chunk_sizes_meet_minimum() {
+        assert_eq!(chunk_sizes(0, 4, 2), vec![0]);
+        assert_eq!(chunk_sizes(8, 4, 2), vec![4, 4]);
+        assert_eq!(chunk_sizes(9, 4, 2), vec![4, 3, 2]);
+        assert_eq!(chunk_sizes(10, 4, 2), vec![4, 4, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_sorted_execute_bulk_loads() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut tree = BTreeIndexBlock::new();
+        let mut params = HashMap::new();
+        params.insert("fanout".into(), ParameterValue::Integer(4));
+        params.insert("sorted".into(), ParameterValue::Boolean(true));
+        tree.initialize(params).await.unwrap();
+
+        for batch in 0..2 {
+            let records: Vec<Record> = (0..100)
+                .map(|i| {
+                    let mut r = Record::new();
+                    r.insert("id".into(), (i * 2 + batch) as i64).unwrap();
+                    r.insert("_slot_id".into(), (i * 2 + batch) as usize).unwrap();
+                    r
+                })
+                .collect();
+            let mut inputs = HashMap::new();
+            inputs.insert("records".into(), PortValue::Stream(records));
+            let ctx = ExecutionContext {
+                inputs,
+                parameters: HashMap::new(),
+                metrics: MetricsCollector::new(),
+                logger: Logger::new(),
+                storage: StorageContext::new(),
+            };
+            let result = tree.execute(ctx).await.unwrap();
+            assert!(result.errors.is_empty());
+            if batch == 0 {
+                // The first batch is bulk-loaded into the empty tree.
+                assert_eq!(tree.split_count, 0);
+            }
+        }
+
+        // The second batch interleaves with the first, so it is inserted
+        // into the populated tree rather than rebuilding it.
+        assert_eq!(tree.key_count(), 200);
+        assert!(tree.split_count > 0);
+        let keys: Vec<i64> = tree
+            .range_scan(&json!(0), &json!(199))
+            .iter()
+            .map(|(k, _)| k.as_i64().unwrap())
+            .collect();
+        assert_eq!(keys, (0..200).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_sorted_execute_with_reads_inserts_incrementally() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut tree = BTreeIndexBlock::new();
+        let mut params = HashMap::new();
+        params.insert("fanout".into(), ParameterValue::Integer(4));
+        params.insert("sorted".into(), ParameterValue::Boolean(true));
+        tree.initialize(params).await.unwrap();
+
+        // Each insert is followed by a lookup of the key just inserted.
+        let mut records = Vec::new();
+        for i in 0..100i64 {
+            let mut insert = Record::new();
+            insert.insert("id".into(), i).unwrap();
+            insert.insert("_slot_id".into(), i as usize).unwrap();
+            records.push(insert);
+            let mut lookup = Record::new();
+            lookup.insert("id".into(), i).unwrap();
+            lookup.insert("_op".into(), "lookup").unwrap();
+            records.push(lookup);
+        }
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        let result = tree.execute(ctx).await.unwrap();
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+
+        assert_eq!(result.outputs["lookup_results"].len(), 100);
+        assert_eq!(tree.key_count(), 100);
+        // Built by ordinary inserts, not a bulk load per lookup.
+        assert!(tree.split_count > 0);
+    }
+
     #[test]
     fn test_metadata() {
         let tree = BTreeIndexBlock::new();
         assert_eq!(tree.metadata().id, "btree-index");
         assert_eq!(tree.metadata().category, BlockCategory::Index);
-        assert_eq!(tree.inputs().len(), 1);
+        assert_eq!(tree.inputs().len(), 2);
         assert_eq!(tree.outputs().len(), 1);
-        assert_eq!(tree.parameters().len(), 3);
+        assert_eq!(tree.parameters().len(), 4);
     }
 
     #[tokio::test]
@@ -1010,6 +2035,107 @@ async fn test_block_execute() {
         assert!(result.errors.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_missing_key_column_is_an_error_not_a_null_key() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut tree = BTreeIndexBlock::new();
+        let mut present = Record::new();
+        present.insert("id".into(), 1i64).unwrap();
+        let mut null_key = Record::new();
+        null_key.data.insert("id".into(), JsonValue::Null);
+        let mut missing = Record::new();
+        missing.insert("name".into(), "no id").unwrap();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(vec![present, null_key, missing]));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = tree.execute(ctx).await.unwrap();
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].to_string().contains("missing key column 'id'"));
+        // The explicit null is indexed; the missing column is not.
+        assert_eq!(*result.metrics.get("total_keys").unwrap(), 2.0);
+        assert!(tree.lookup(&JsonValue::Null).is_some());
+        let keys: Vec<JsonValue> =
+            tree.range_scan(&json!(0), &JsonValue::Null).into_iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![json!(1), JsonValue::Null], "nulls sort last");
+
+        tree.key_column = "id, name".into();
+        let mut partial = Record::new();
+        partial.insert("id".into(), 1i64).unwrap();
+        assert!(tree.extract_key(&partial).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_lookup_and_range_ops() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut tree = BTreeIndexBlock::new();
+        tree.fanout = 4;
+
+        let mut records: Vec<Record> = Vec::new();
+        for i in 0..20 {
+            let mut r = Record::new();
+            r.insert("id".into(), i as i64).unwrap();
+            r.insert("_page_id".into(), 1usize).unwrap();
+            r.insert("_slot_id".into(), i as usize).unwrap();
+            records.push(r);
+        }
+        let mut probe = Record::new();
+        probe.insert("_op".into(), "lookup").unwrap();
+        probe.insert("id".into(), 7i64).unwrap();
+        records.push(probe);
+        let mut missing = Record::new();
+        missing.insert("_op".into(), "lookup").unwrap();
+        missing.insert("id".into(), 99i64).unwrap();
+        records.push(missing);
+        let mut range = Record::new();
+        range.insert("_op".into(), "range").unwrap();
+        range.insert("start".into(), 10i64).unwrap();
+        range.insert("end".into(), 12i64).unwrap();
+        records.push(range);
+        let mut bad = Record::new();
+        bad.insert("_op".into(), "range").unwrap();
+        records.push(bad);
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = tree.execute(ctx).await.unwrap();
+        assert_eq!(result.errors.len(), 1, "range without bounds is rejected");
+        assert_eq!(*result.metrics.get("lookups").unwrap(), 2.0);
+        assert_eq!(*result.metrics.get("range_scans").unwrap(), 1.0);
+
+        let out = match result.outputs.get("lookup_results") {
+            Some(PortValue::Stream(r)) => r.clone(),
+            _ => panic!("expected lookup_results stream"),
+        };
+        let slots: Vec<usize> = out
+            .iter()
+            .map(|r| r.get::<usize>("_slot_id").unwrap().unwrap())
+            .collect();
+        assert_eq!(slots, vec![7, 10, 11, 12]);
+        assert_eq!(
+            out[0].get::<TupleId>("_tuple_id").unwrap(),
+            Some(TupleId::new(1, 7))
+        );
+        assert_eq!(out[0].data.get("id"), Some(&json!(7)));
+    }
+
     #[tokio::test]
     async fn test_initialize_with_params() {
         let mut tree = BTreeIndexBlock::new();
@@ -25,7 +25,8 @@
 use crate::core::constraint::{Constraint, Guarantee, GuaranteeType};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterType, ParameterUIHint, ParameterValue, ValidationResult, WidgetType,
+    list_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    ValidationResult, WidgetType,
 };
 use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
 
@@ -163,13 +164,14 @@ fn build_metadata() -> BlockMetadata {
                       most frequently filtered on. Default is 'id'."
                          .into()),
                     ("included_columns".into(),
-                     "Comma-separated list of columns to include in the index alongside the key. \
+                     "List of columns to include in the index alongside the key. \
                       These columns are stored in the index leaf nodes so that queries requesting \
                       only these columns can be served without touching the base table (index-only \
                       scan). Include columns that are frequently in the SELECT list of queries \
                       that filter by the key_column. Adding too many columns makes the index \
                       bloated. A good rule of thumb: include 1-3 columns that cover your most \
-                      common query pattern. Example: 'name,email' for a user lookup index."
+                      common query pattern. Example: [name, email] (or the string \
+                      'name,email') for a user lookup index."
                          .into()),
                     ("lookup_key".into(),
                      "The key value to search for during execution. When empty, the block only \
@@ -246,10 +248,10 @@ fn build_parameters() -> Vec<Parameter> {
             },
             Parameter {
                 id: "included_columns".into(), name: "Included Columns".into(),
-                param_type: ParameterType::String,
-                description: "Comma-separated columns to include in index (for index-only scans)".into(),
-                default_value: ParameterValue::String("".into()),
-                required: false, constraints: None,
+                param_type: ParameterType::Array,
+                description: "Columns to include in index (for index-only scans)".into(),
+                default_value: ParameterValue::Array(Vec::new()),
+                required: false, constraints: Some(included_columns_constraints()),
                 ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
             },
             Parameter {
@@ -321,6 +323,19 @@ fn lookup(&mut self, lookup_key: &str) -> Vec<Record> {
 
 impl Default for CoveringIndexBlock { fn default() -> Self { Self::new() } }
 
+/// Constraints on the `included_columns` list: column names.
+fn included_columns_constraints() -> ParameterConstraints {
+    ParameterConstraints::new().with_element_type(ParameterType::String)
+}
+
+/// Read the `included_columns` list from `params`.
+fn parse_included_columns(
+    params: &HashMap<String, ParameterValue>,
+) -> Result<Option<Vec<String>>, BlockError> {
+    let items = list_param(params, "included_columns", &included_columns_constraints())?;
+    Ok(items.map(|items| items.iter().filter_map(|c| c.as_string().map(str::to_string)).collect()))
+}
+
 #[async_trait]
 impl Block for CoveringIndexBlock {
     fn metadata(&self) -> &BlockMetadata { &self.metadata }
@@ -337,16 +352,12 @@ fn guarantees(&self) -> &[Guarantee] {
     fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
 
     async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
         if let Some(v) = params.get("key_column") {
             if let Some(s) = v.as_string() { self.key_column = s.to_string(); }
         }
-        if let Some(v) = params.get("included_columns") {
-            if let Some(s) = v.as_string() {
-                self.included_columns = s.split(',')
-                    .map(|c| c.trim().to_string())
-                    .filter(|c| !c.is_empty())
-                    .collect();
-            }
+        if let Some(columns) = parse_included_columns(&params)? {
+            self.included_columns = columns;
         }
         Ok(())
     }
@@ -360,13 +371,8 @@ async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult
 
         // Parse included_columns from context params if not set during initialize
         if self.included_columns.is_empty() {
-            if let Some(v) = context.parameters.get("included_columns") {
-                if let Some(s) = v.as_string() {
-                    self.included_columns = s.split(',')
-                        .map(|c| c.trim().to_string())
-                        .filter(|c| !c.is_empty())
-                        .collect();
-                }
+            if let Some(columns) = parse_included_columns(&context.parameters)? {
+                self.included_columns = columns;
             }
         }
 
@@ -481,6 +487,24 @@ async fn test_lookup_miss() {
         assert_eq!(ci.index_only_scans, 0); // miss = no index-only scan
     }
 
+    #[tokio::test]
+    async fn test_included_columns_parameter() {
+        let mut ci = CoveringIndexBlock::new();
+        let mut params = HashMap::new();
+        params.insert("included_columns".into(), ParameterValue::String("name, email".into()));
+        ci.initialize(params).await.unwrap();
+        assert_eq!(ci.included_columns, vec!["name".to_string(), "email".to_string()]);
+
+        let mut params = HashMap::new();
+        params.insert("included_columns".into(), ParameterValue::Array(vec!["email".into()]));
+        ci.initialize(params).await.unwrap();
+        assert_eq!(ci.included_columns, vec!["email".to_string()]);
+
+        let mut params = HashMap::new();
+        params.insert("included_columns".into(), ParameterValue::Boolean(true));
+        assert!(ci.initialize(params).await.is_err());
+    }
+
     #[test]
     fn test_metadata() {
         let ci = CoveringIndexBlock::new();
@@ -21,6 +21,7 @@
 //! | `collisions` | Counter | Inserts that hit an occupied bucket |
 //! | `rehashes` | Counter | Table resizes performed |
 //! | `max_chain_len` | Gauge | Longest bucket chain |
+//! | `avg_chain_len` | Gauge | Mean chain length over non-empty buckets |
 
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
@@ -382,6 +383,14 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Longest bucket chain".into(),
                 aggregations: vec![AggregationType::Max],
             },
+            MetricDefinition {
+                id: "avg_chain_len".into(),
+                name: "Avg Chain Length".into(),
+                metric_type: MetricType::Gauge,
+                unit: "entries".into(),
+                description: "Mean chain length over non-empty buckets".into(),
+                aggregations: vec![AggregationType::Avg, AggregationType::Max],
+            },
         ]
     }
 
@@ -428,8 +437,33 @@ pub fn max_chain_length(&self) -> usize {
         self.buckets.iter().map(|b| b.len()).max().unwrap_or(0)
     }
 
+    /// Mean chain length over non-empty buckets — the expected number of
+    /// entries scanned by a successful lookup's bucket.
+    pub fn avg_chain_length(&self) -> f64 {
+        let occupied = self.buckets.iter().filter(|b| !b.is_empty()).count();
+        if occupied == 0 {
+            0.0
+        } else {
+            self.total_keys as f64 / occupied as f64
+        }
+    }
+
+    /// Histogram of chain lengths: chain length → number of buckets with
+    /// that length (empty buckets are counted under 0).
+    pub fn chain_length_distribution(&self) -> HashMap<usize, usize> {
+        let mut dist = HashMap::new();
+        for bucket in &self.buckets {
+            *dist.entry(bucket.len()).or_insert(0) += 1;
+        }
+        dist
+    }
+
     /// Double the bucket count and redistribute all entries.
-    fn rehash(&mut self) {
+    ///
+    /// Called automatically by [`insert_key`](Self::insert_key) once the load
+    /// factor exceeds `max_load_factor`; it can also be invoked directly to
+    /// grow the table ahead of a bulk insert.
+    pub fn rehash(&mut self) {
         let new_size = self.buckets.len() * 2;
         let old_buckets = std::mem::replace(&mut self.buckets, vec![Vec::new(); new_size]);
 
@@ -494,35 +528,16 @@ async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("initial_buckets") {
-            let v = val.as_integer().ok_or_else(|| {
-                BlockError::InvalidParameter("initial_buckets must be an integer".into())
-            })? as usize;
-            if v < 4 || v > 65536 {
-                return Err(BlockError::InvalidParameter(
-                    "initial_buckets must be between 4 and 65536".into(),
-                ));
-            }
-            self.initial_buckets = v;
-            self.buckets = vec![Vec::new(); v];
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("initial_buckets").and_then(ParameterValue::as_integer) {
+            self.initial_buckets = v as usize;
+            self.buckets = vec![Vec::new(); self.initial_buckets];
         }
-        if let Some(val) = params.get("max_load_factor") {
-            self.max_load_factor = val.as_number().ok_or_else(|| {
-                BlockError::InvalidParameter("max_load_factor must be a number".into())
-            })?;
-            if !(0.1..=2.0).contains(&self.max_load_factor) {
-                return Err(BlockError::InvalidParameter(
-                    "max_load_factor must be between 0.1 and 2.0".into(),
-                ));
-            }
+        if let Some(v) = params.get("max_load_factor").and_then(ParameterValue::as_number) {
+            self.max_load_factor = v;
         }
-        if let Some(val) = params.get("key_column") {
-            self.key_column = val
-                .as_string()
-                .ok_or_else(|| {
-                    BlockError::InvalidParameter("key_column must be a string".into())
-                })?
-                .to_string();
+        if let Some(v) = params.get("key_column").and_then(ParameterValue::as_string) {
+            self.key_column = v.to_string();
         }
         Ok(())
     }
@@ -586,6 +601,9 @@ async fn execute(
         context
             .metrics
             .record("max_chain_len", self.max_chain_length() as f64);
+        context
+            .metrics
+            .record("avg_chain_len", self.avg_chain_length());
 
         let mut metrics_summary = HashMap::new();
         metrics_summary.insert("total_keys".into(), self.total_keys as f64);
@@ -593,6 +611,8 @@ async fn execute(
         metrics_summary.insert("load_factor".into(), self.load_factor());
         metrics_summary.insert("collisions".into(), self.collision_count as f64);
         metrics_summary.insert("rehashes".into(), self.rehash_count as f64);
+        metrics_summary.insert("max_chain_len".into(), self.max_chain_length() as f64);
+        metrics_summary.insert("avg_chain_len".into(), self.avg_chain_length());
 
         Ok(ExecutionResult {
             outputs: HashMap::new(),
@@ -679,6 +699,26 @@ fn test_rehash_on_load() {
         }
     }
 
+    #[test]
+    fn test_explicit_rehash_keeps_keys() {
+        let mut idx = HashIndexBlock::new();
+        for i in 0..40 {
+            idx.insert_key(json!(format!("k{i}")), TupleId::new(1, i as usize));
+        }
+        let buckets = idx.buckets.len();
+        let rehashes = idx.rehash_count;
+
+        idx.rehash();
+        idx.rehash();
+
+        assert_eq!(idx.buckets.len(), buckets * 4);
+        assert_eq!(idx.rehash_count, rehashes + 2);
+        for i in 0..40 {
+            let tid = idx.lookup(&json!(format!("k{i}"))).unwrap();
+            assert_eq!(tid, TupleId::new(1, i as usize));
+        }
+    }
+
     #[test]
     fn test_collision_counting() {
         let mut idx = HashIndexBlock::new();
@@ -693,6 +733,38 @@ fn test_collision_counting() {
         assert_eq!(idx.max_chain_length(), 3);
     }
 
+    #[test]
+    fn test_chain_length_distribution() {
+        let mut idx = HashIndexBlock::new();
+        idx.buckets = vec![Vec::new(); 4];
+        idx.max_load_factor = 100.0; // Prevent rehash
+
+        for i in 0..200 {
+            idx.insert_key(json!(i), TupleId::new(0, i as usize));
+        }
+
+        let dist = idx.chain_length_distribution();
+        assert_eq!(dist.values().sum::<usize>(), 4, "every bucket is counted once");
+        assert_eq!(
+            dist.iter().map(|(len, n)| len * n).sum::<usize>(),
+            200,
+            "chain lengths add up to the entry count"
+        );
+        assert_eq!(*dist.keys().max().unwrap(), idx.max_chain_length());
+        assert!(idx.avg_chain_length() >= 50.0);
+    }
+
+    #[test]
+    fn test_avg_chain_length_ignores_empty_buckets() {
+        let mut idx = HashIndexBlock::new();
+        assert_eq!(idx.avg_chain_length(), 0.0);
+
+        idx.buckets = vec![Vec::new(); 1024];
+        idx.insert_key(json!("only"), TupleId::new(0, 0));
+        assert_eq!(idx.avg_chain_length(), 1.0);
+        assert_eq!(idx.chain_length_distribution().get(&0), Some(&1023));
+    }
+
     #[test]
     fn test_load_factor() {
         let mut idx = HashIndexBlock::new();
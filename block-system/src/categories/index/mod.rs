@@ -2,6 +2,8 @@
 //!
 //! Index blocks provide fast lookup structures over stored data.
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+
 pub mod btree;
 pub mod hash_index;
 pub mod covering_index;
@@ -9,3 +11,11 @@
 pub use btree::BTreeIndexBlock;
 pub use hash_index::HashIndexBlock;
 pub use covering_index::CoveringIndexBlock;
+
+/// Register a factory for each index block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(BTreeIndexBlock::new()))?;
+    registry.register_factory(|| Box::new(HashIndexBlock::new()))?;
+    registry.register_factory(|| Box::new(CoveringIndexBlock::new()))?;
+    Ok(())
+}
@@ -16,6 +16,23 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+
+/// Register a factory for every built-in block, keyed by metadata id.
+pub fn register_all(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    storage::register(registry)?;
+    index::register(registry)?;
+    buffer::register(registry)?;
+    execution::register(registry)?;
+    concurrency::register(registry)?;
+    transaction::register(registry)?;
+    optimization::register(registry)?;
+    partitioning::register(registry)?;
+    distribution::register(registry)?;
+    compression::register(registry)?;
+    Ok(())
+}
+
 /// Block category enumeration
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BlockCategory {
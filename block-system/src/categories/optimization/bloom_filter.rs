@@ -10,6 +10,20 @@
 //! with each function and set those bits. To test membership, check if all k
 //! bits are set — if any is 0, the element is definitely absent.
 //!
+//! Given `expected_items` (n) and `target_fp_rate` (p), the block sizes itself
+//! with the standard formulas m = -n·ln(p) / (ln 2)² and k = (m/n)·ln 2,
+//! reported as `bit_count` and `hash_count`. Because every check is compared
+//! against the set of keys actually inserted, `observed_fp_rate` shows the
+//! real false-positive rate — and how it climbs past the target once more
+//! than `expected_items` keys are inserted.
+//!
+//! With `counting` enabled each bit becomes a 4-bit saturating counter
+//! (a counting Bloom filter): inserts increment, deletes (`_op_type:
+//! "DELETE"`) decrement, and a key whose counters all return to zero is no
+//! longer reported. Counters that reach their maximum stay there forever,
+//! since decrementing them could create false negatives; each time that
+//! happens is counted in `counter_saturations`. The price is 4× the memory.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
@@ -20,6 +34,12 @@
 //! | `true_negatives` | Counter | Queries correctly identified as absent |
 //! | `false_positive_rate` | Gauge | false_positives / (false_positives + true_negatives) |
 //! | `bits_used` | Gauge | Number of set bits in the filter |
+//! | `bit_count` | Gauge | Size of the bit array (m) |
+//! | `hash_count` | Gauge | Number of hash functions (k) |
+//! | `observed_fp_rate` | Gauge | Measured false-positive rate, as a fraction comparable to `target_fp_rate` |
+//! | `deletes` | Counter | Keys removed (counting mode) |
+//! | `counter_saturations` | Counter | Times a counter reached its maximum value |
+//! | `filter_bytes` | Gauge | Memory used by the bit or counter array |
 
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -34,7 +54,12 @@
     Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
-use crate::core::port::{Port, PortDirection, PortType, PortValue};
+use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
+
+/// Largest value of a counting-mode counter (4 bits, as in Fan et al.).
+const COUNTER_MAX: u8 = 15;
+/// Bits of memory per slot in counting mode.
+const COUNTER_BITS: usize = 4;
 
 // ---------------------------------------------------------------------------
 // BloomFilterBlock
@@ -50,9 +75,15 @@ pub struct BloomFilterBlock {
     // Configuration
     num_bits: usize,
     num_hash_fns: usize,
+    /// Items the filter was sized for; 0 when sized by hand.
+    expected_items: usize,
+    target_fp_rate: f64,
+    counting: bool,
 
     // Internal state
     bits: Vec<bool>,
+    /// Per-slot counters, used instead of `bits` in counting mode.
+    counters: Vec<u8>,
     /// Tracks actually inserted keys for ground-truth comparison
     inserted_keys: std::collections::HashSet<u64>,
 
@@ -61,6 +92,8 @@ pub struct BloomFilterBlock {
     true_positives: usize,
     false_positives: usize,
     true_negatives: usize,
+    deletes: usize,
+    counter_saturations: usize,
 }
 
 impl BloomFilterBlock {
@@ -74,12 +107,18 @@ pub fn new() -> Self {
             metric_defs: Self::build_metrics(),
             num_bits,
             num_hash_fns: 7,
+            expected_items: 0,
+            target_fp_rate: 0.01,
+            counting: false,
             bits: vec![false; num_bits],
+            counters: Vec::new(),
             inserted_keys: std::collections::HashSet::new(),
             checks: 0,
             true_positives: 0,
             false_positives: 0,
             true_negatives: 0,
+            deletes: 0,
+            counter_saturations: 0,
         }
     }
 
@@ -110,6 +149,10 @@ fn build_metadata() -> BlockMetadata {
                            For i in 0..k (each hash function):\n    \
                              index = hash_i(key) % num_bits\n    \
                              bits[index] = 1\n\n\
+                           DELETE(key), counting mode only:\n  \
+                           For i in 0..k:\n    \
+                             index = hash_i(key) % num_bits\n    \
+                             If counters[index] < MAX: counters[index] -= 1\n\n\
                            QUERY(key):\n  \
                            For i in 0..k:\n    \
                              index = hash_i(key) % num_bits\n    \
@@ -119,7 +162,10 @@ fn build_metadata() -> BlockMetadata {
                            FALSE_POSITIVE_RATE:\n  \
                            Theoretical: (1 - e^(-kn/m))^k\n  \
                            Where n=items inserted, m=total bits, k=hash functions\n  \
-                           Optimal k = (m/n) * ln(2) ≈ 0.693 * (m/n)"
+                           Optimal k = (m/n) * ln(2) ≈ 0.693 * (m/n)\n\n\
+                           SIZING(expected_items n, target_fp_rate p):\n  \
+                           m = ceil(-n * ln(p) / ln(2)^2)\n  \
+                           k = max(1, round((m/n) * ln(2)))"
                     .into(),
                 complexity: Complexity {
                     time: "O(k) per insert and query, where k is the number of hash functions"
@@ -137,7 +183,7 @@ fn build_metadata() -> BlockMetadata {
                 tradeoffs: vec![
                     "More bits = lower false positive rate but more memory".into(),
                     "More hash functions = lower false positives up to a point, then diminishing returns".into(),
-                    "Cannot delete elements (use Counting Bloom filter for that)".into(),
+                    "Cannot delete elements unless counting mode is on, which costs 4x the memory".into(),
                     "Optimal k = (m/n) · ln(2) ≈ 0.693 · (m/n)".into(),
                     "Filter must be rebuilt if the underlying data changes (no incremental delete)".into(),
                     "At high fill ratios (>50% bits set), false positive rate degrades rapidly".into(),
@@ -175,6 +221,31 @@ fn build_metadata() -> BlockMetadata {
                       per key, optimal k is about 3-4. Recommended: 7 for the default 10,000 bits with \
                       ~1000 items; adjust based on your bits-per-key ratio."
                         .into()),
+                    ("expected_items".into(),
+                     "How many keys you plan to insert. When set (non-zero), the block derives num_bits \
+                      and num_hash_functions from it and target_fp_rate, overriding any values given for \
+                      those. The sizing is only valid up to this many keys: insert more and the bit array \
+                      saturates, so observed_fp_rate rises above the target (validation warns when this \
+                      happens). RocksDB and Cassandra size each SSTable's filter from its exact key count \
+                      for this reason. 0 (default) keeps manual sizing."
+                        .into()),
+                    ("counting".into(),
+                     "Replace each bit with a 4-bit saturating counter so keys can be deleted. Insert \
+                      increments the key's k counters, delete decrements them, and a query checks that all \
+                      are non-zero — so a deleted key stops matching (unless its counters are shared with \
+                      other keys). The filter needs 4x the memory of a plain one (see filter_bytes) for the \
+                      same false-positive rate. A counter that reaches 15 is stuck there (decrementing it \
+                      could cause false negatives); counter_saturations shows how often that happens, which \
+                      is rare unless the filter is badly undersized. Used where the set changes in place, \
+                      e.g. cache summaries in Summary Cache (Fan et al.); LSM stores avoid it by rebuilding \
+                      filters during compaction."
+                        .into()),
+                    ("target_fp_rate".into(),
+                     "The false-positive rate the filter is sized for when expected_items is set. Each \
+                      10x reduction costs about 4.8 extra bits per key: 1% needs ~9.6 bits/key with k=7, \
+                      0.1% needs ~14.4 bits/key with k=10. Cassandra's bloom_filter_fp_chance defaults to \
+                      0.01. Compare it against observed_fp_rate to see how well the theory holds."
+                        .into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -272,6 +343,46 @@ fn build_parameters() -> Vec<Parameter> {
                     ParameterUIHint::new(WidgetType::Slider).with_step(1.0),
                 ),
             },
+            Parameter {
+                id: "expected_items".into(),
+                name: "Expected Items".into(),
+                param_type: ParameterType::Number,
+                description: "Keys to size the filter for (0 = use num_bits and num_hash_functions)".into(),
+                default_value: ParameterValue::Integer(0),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(0.0).with_max(1_000_000.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Input).with_unit("items".into()),
+                ),
+            },
+            Parameter {
+                id: "counting".into(),
+                name: "Counting Mode".into(),
+                param_type: ParameterType::Boolean,
+                description: "Use 4-bit counters instead of bits so keys can be deleted".into(),
+                default_value: ParameterValue::Boolean(false),
+                required: false,
+                constraints: None,
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Checkbox),
+                ),
+            },
+            Parameter {
+                id: "target_fp_rate".into(),
+                name: "Target FP Rate".into(),
+                param_type: ParameterType::Number,
+                description: "False-positive rate to size for when expected_items is set".into(),
+                default_value: ParameterValue::Number(0.01),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(0.0001).with_max(0.5),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider).with_step(0.001),
+                ),
+            },
         ]
     }
 
@@ -325,18 +436,91 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Number of set bits in the filter".into(),
                 aggregations: vec![AggregationType::Max],
             },
+            MetricDefinition {
+                id: "bit_count".into(),
+                name: "Bit Count".into(),
+                metric_type: MetricType::Gauge,
+                unit: "bits".into(),
+                description: "Size of the filter's bit array (m)".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "hash_count".into(),
+                name: "Hash Count".into(),
+                metric_type: MetricType::Gauge,
+                unit: "functions".into(),
+                description: "Number of hash functions (k)".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "observed_fp_rate".into(),
+                name: "Observed FP Rate".into(),
+                metric_type: MetricType::Gauge,
+                unit: "ratio".into(),
+                description: "Measured false positives / negative queries, comparable to target_fp_rate".into(),
+                aggregations: vec![AggregationType::Avg, AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "deletes".into(),
+                name: "Deletes".into(),
+                metric_type: MetricType::Counter,
+                unit: "keys".into(),
+                description: "Keys removed from a counting filter".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "counter_saturations".into(),
+                name: "Counter Saturations".into(),
+                metric_type: MetricType::Counter,
+                unit: "events".into(),
+                description: "Times a counting-mode counter reached its maximum value".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "filter_bytes".into(),
+                name: "Filter Memory".into(),
+                metric_type: MetricType::Gauge,
+                unit: "bytes".into(),
+                description: "Memory used by the bit array (or counter array in counting mode)".into(),
+                aggregations: vec![AggregationType::Max],
+            },
         ]
     }
 
     // -- Core operations -----------------------------------------------------
 
-    /// Simple hash function using FNV-1a-like mixing.
+    /// Optimal (bits, hash functions) for `n` items at false-positive rate `p`.
+    pub fn optimal_size(n: usize, p: f64) -> (usize, usize) {
+        let ln2 = std::f64::consts::LN_2;
+        let m = (-(n as f64) * p.ln() / (ln2 * ln2)).ceil().max(1.0);
+        let k = (m / n as f64 * ln2).round().max(1.0);
+        (m as usize, k as usize)
+    }
+
+    /// The `seed`-th of k hash functions, by double hashing (Kirsch &
+    /// Mitzenmacher): h1 + seed·h2. Deriving each function from `key + seed`
+    /// instead would make neighbouring keys share bit positions, so the
+    /// observed false-positive rate would not follow the theory.
     fn hash(&self, key: u64, seed: usize) -> usize {
-        let mut h = key.wrapping_add(seed as u64).wrapping_mul(6364136223846793005);
-        h ^= h >> 33;
-        h = h.wrapping_mul(0xff51afd7ed558ccd);
-        h ^= h >> 33;
-        (h as usize) % self.num_bits
+        let mix = |mut h: u64| {
+            h ^= h >> 33;
+            h = h.wrapping_mul(0xff51afd7ed558ccd);
+            h ^= h >> 33;
+            h = h.wrapping_mul(0xc4ceb9fe1a85ec53);
+            h ^= h >> 33;
+            h
+        };
+        let h1 = mix(key.wrapping_mul(6364136223846793005));
+        let h2 = mix(h1 ^ 0x9e3779b97f4a7c15) | 1;
+        (h1.wrapping_add((seed as u64).wrapping_mul(h2)) % self.num_bits as u64) as usize
+    }
+
+    fn is_set(&self, idx: usize) -> bool {
+        if self.counting {
+            self.counters[idx] > 0
+        } else {
+            self.bits[idx]
+        }
     }
 
     /// Insert a key into the filter.
@@ -344,17 +528,48 @@ pub fn insert(&mut self, key: u64) {
         self.inserted_keys.insert(key);
         for i in 0..self.num_hash_fns {
             let idx = self.hash(key, i);
-            self.bits[idx] = true;
+            if self.counting {
+                let counter = &mut self.counters[idx];
+                if *counter < COUNTER_MAX {
+                    *counter += 1;
+                    if *counter == COUNTER_MAX {
+                        self.counter_saturations += 1;
+                    }
+                }
+            } else {
+                self.bits[idx] = true;
+            }
         }
     }
 
+    /// Remove a key from a counting filter. Returns whether the key had been
+    /// inserted; removing a key that never was is ignored, since decrementing
+    /// its counters would create false negatives for other keys.
+    pub fn remove(&mut self, key: u64) -> Result<bool, BlockError> {
+        if !self.counting {
+            return Err(BlockError::InvalidInput(
+                "a standard Bloom filter cannot delete keys; enable counting mode".into(),
+            ));
+        }
+        if !self.inserted_keys.remove(&key) {
+            return Ok(false);
+        }
+        for i in 0..self.num_hash_fns {
+            let idx = self.hash(key, i);
+            let counter = &mut self.counters[idx];
+            // A saturated counter no longer knows its true count.
+            if *counter > 0 && *counter < COUNTER_MAX {
+                *counter -= 1;
+            }
+        }
+        self.deletes += 1;
+        Ok(true)
+    }
+
     /// Check if a key might be in the set.
     pub fn might_contain(&mut self, key: u64) -> bool {
         self.checks += 1;
-        let bloom_says_yes = (0..self.num_hash_fns).all(|i| {
-            let idx = self.hash(key, i);
-            self.bits[idx]
-        });
+        let bloom_says_yes = (0..self.num_hash_fns).all(|i| self.is_set(self.hash(key, i)));
 
         let actually_present = self.inserted_keys.contains(&key);
 
@@ -380,8 +595,18 @@ pub fn false_positive_rate(&self) -> f64 {
         (self.false_positives as f64 / negatives as f64) * 100.0
     }
 
+    /// False positives as a fraction of negative queries (0.0–1.0).
+    pub fn observed_fp_rate(&self) -> f64 {
+        self.false_positive_rate() / 100.0
+    }
+
     pub fn bits_used(&self) -> usize {
-        self.bits.iter().filter(|&&b| b).count()
+        (0..self.num_bits).filter(|&i| self.is_set(i)).count()
+    }
+
+    pub fn filter_bytes(&self) -> usize {
+        let bits_per_slot = if self.counting { COUNTER_BITS } else { 1 };
+        (self.num_bits * bits_per_slot).div_ceil(8)
     }
 }
 
@@ -409,19 +634,30 @@ async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("num_bits") {
-            self.num_bits = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("num_bits must be an integer".into()))?
-                as usize;
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("num_bits").and_then(ParameterValue::as_integer) {
+            self.num_bits = v as usize;
             self.bits = vec![false; self.num_bits];
         }
-        if let Some(val) = params.get("num_hash_functions") {
-            self.num_hash_fns = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("num_hash_functions must be an integer".into()))?
-                as usize;
+        if let Some(v) = params.get("num_hash_functions").and_then(ParameterValue::as_integer) {
+            self.num_hash_fns = v as usize;
+        }
+        if let Some(v) = params.get("target_fp_rate").and_then(ParameterValue::as_number) {
+            self.target_fp_rate = v;
+        }
+        if let Some(v) = params.get("expected_items").and_then(ParameterValue::as_integer) {
+            self.expected_items = v as usize;
         }
+        if self.expected_items > 0 {
+            let (m, k) = Self::optimal_size(self.expected_items, self.target_fp_rate);
+            self.num_bits = m;
+            self.num_hash_fns = k;
+            self.bits = vec![false; m];
+        }
+        if let Some(v) = params.get("counting").and_then(ParameterValue::as_bool) {
+            self.counting = v;
+        }
+        self.counters = if self.counting { vec![0; self.num_bits] } else { Vec::new() };
         Ok(())
     }
 
@@ -440,22 +676,40 @@ async fn execute(
         };
 
         let mut output_records = Vec::with_capacity(records.len());
+        let mut errors = Vec::new();
 
         // Phase 1: Insert operations (writes) — records with _op_type == "INSERT"
-        // Phase 2: Query operations (reads) — everything else
-        let (inserts, queries): (Vec<_>, Vec<_>) = records.into_iter().partition(|r| {
+        // Phase 2: Delete operations — records with _op_type == "DELETE"
+        // Phase 3: Query operations (reads) — everything else
+        let op_is = |r: &Record, op: &str| {
             r.get::<String>("_op_type")
                 .ok()
                 .flatten()
-                .map(|s| s == "INSERT" || s == "insert")
-                .unwrap_or(false)
-        });
+                .is_some_and(|s| s.eq_ignore_ascii_case(op))
+        };
+        let (inserts, rest): (Vec<_>, Vec<_>) = records.into_iter().partition(|r| op_is(r, "insert"));
+        let (deletes, queries): (Vec<_>, Vec<_>) = rest.into_iter().partition(|r| op_is(r, "delete"));
 
         for record in &inserts {
             let key = record.get::<u64>("_key").ok().flatten().unwrap_or(0);
             self.insert(key);
         }
 
+        for record in deletes {
+            let key = record.get::<u64>("_key").ok().flatten().unwrap_or(0);
+            match self.remove(key) {
+                Ok(removed) => {
+                    if removed {
+                        context.metrics.increment("deletes");
+                    }
+                    let mut out = record;
+                    let _ = out.insert("_deleted".into(), removed);
+                    output_records.push(out);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
         for record in queries {
             let key = record.get::<u64>("_key").ok().flatten().unwrap_or(0);
             let hit = self.might_contain(key);
@@ -480,6 +734,10 @@ async fn execute(
 
         context.metrics.record("false_positive_rate", self.false_positive_rate());
         context.metrics.record("bits_used", self.bits_used() as f64);
+        context.metrics.record("bit_count", self.num_bits as f64);
+        context.metrics.record("hash_count", self.num_hash_fns as f64);
+        context.metrics.record("observed_fp_rate", self.observed_fp_rate());
+        context.metrics.record("filter_bytes", self.filter_bytes() as f64);
 
         let mut outputs = HashMap::new();
         outputs.insert("filtered".into(), PortValue::Stream(output_records));
@@ -491,6 +749,12 @@ async fn execute(
         metrics_summary.insert("true_negatives".into(), self.true_negatives as f64);
         metrics_summary.insert("false_positive_rate".into(), self.false_positive_rate());
         metrics_summary.insert("bits_used".into(), self.bits_used() as f64);
+        metrics_summary.insert("bit_count".into(), self.num_bits as f64);
+        metrics_summary.insert("hash_count".into(), self.num_hash_fns as f64);
+        metrics_summary.insert("observed_fp_rate".into(), self.observed_fp_rate());
+        metrics_summary.insert("deletes".into(), self.deletes as f64);
+        metrics_summary.insert("counter_saturations".into(), self.counter_saturations as f64);
+        metrics_summary.insert("filter_bytes".into(), self.filter_bytes() as f64);
 
         Ok(ExecutionResult {
             outputs,
@@ -500,28 +764,105 @@ async fn execute(
     }
 
     fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
-        if let Some(input) = inputs.get("requests") {
+        let (result, incoming) = if let Some(input) = inputs.get("requests") {
             match input {
-                PortValue::Stream(_) | PortValue::Batch(_) | PortValue::Single(_) => ValidationResult::ok(),
-                PortValue::None => ValidationResult::ok().with_warning("No requests provided"),
-                _ => ValidationResult::error("requests port expects DataStream"),
+                PortValue::Stream(r) | PortValue::Batch(r) => (ValidationResult::ok(), r.as_slice()),
+                PortValue::Single(r) => (ValidationResult::ok(), std::slice::from_ref(r)),
+                PortValue::None => (ValidationResult::ok().with_warning("No requests provided"), &[][..]),
+                _ => return ValidationResult::error("requests port expects DataStream"),
             }
         } else {
-            ValidationResult::ok().with_warning("requests input not connected")
+            (ValidationResult::ok().with_warning("requests input not connected"), &[][..])
+        };
+
+        let incoming_inserts = incoming
+            .iter()
+            .filter(|r| {
+                r.get::<String>("_op_type")
+                    .ok()
+                    .flatten()
+                    .is_some_and(|s| s.eq_ignore_ascii_case("insert"))
+            })
+            .count();
+        let items = self.inserted_keys.len() + incoming_inserts;
+        if self.expected_items > 0 && items > self.expected_items {
+            return result.with_warning(format!(
+                "{} items exceed expected_items ({}); the false-positive rate will rise above target_fp_rate ({})",
+                items, self.expected_items, self.target_fp_rate
+            ));
         }
+        result
     }
 
+    fn state_round_trips(&self) -> bool { true }
     fn get_state(&self) -> BlockState {
         let mut state = BlockState::new();
         let _ = state.insert("num_bits".into(), self.num_bits);
         let _ = state.insert("num_hash_functions".into(), self.num_hash_fns);
+        let _ = state.insert("counting".into(), self.counting);
+        // Set bit positions rather than every bit: a sparse filter stays small.
+        let set_bits: Vec<usize> = (0..self.bits.len()).filter(|&i| self.bits[i]).collect();
+        let _ = state.insert("set_bits".into(), set_bits);
+        let _ = state.insert("counters".into(), &self.counters);
+        let mut keys: Vec<u64> = self.inserted_keys.iter().copied().collect();
+        keys.sort_unstable();
+        let _ = state.insert("inserted_keys".into(), keys);
         let _ = state.insert("checks".into(), self.checks);
+        let _ = state.insert("true_positives".into(), self.true_positives);
+        let _ = state.insert("false_positives".into(), self.false_positives);
+        let _ = state.insert("true_negatives".into(), self.true_negatives);
+        let _ = state.insert("deletes".into(), self.deletes);
+        let _ = state.insert("counter_saturations".into(), self.counter_saturations);
         state
     }
 
     fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
-        if let Ok(Some(n)) = state.get::<usize>("num_bits") { self.num_bits = n; }
-        if let Ok(Some(k)) = state.get::<usize>("num_hash_functions") { self.num_hash_fns = k; }
+        let field = |e: serde_json::Error| BlockError::StateError(e.to_string());
+        if let Some(n) = state.get::<usize>("num_bits").map_err(field)? {
+            self.num_bits = n;
+        }
+        if let Some(k) = state.get::<usize>("num_hash_functions").map_err(field)? {
+            self.num_hash_fns = k;
+        }
+        if let Some(c) = state.get::<bool>("counting").map_err(field)? {
+            self.counting = c;
+        }
+        // Size the arrays to `num_bits` before loading, so hashing (which
+        // indexes modulo `num_bits`) never runs past them.
+        self.bits = vec![false; self.num_bits];
+        for i in state.get::<Vec<usize>>("set_bits").map_err(field)?.unwrap_or_default() {
+            let bit = self.bits.get_mut(i).ok_or_else(|| {
+                BlockError::StateError(format!("set bit {} outside a {}-bit filter", i, self.num_bits))
+            })?;
+            *bit = true;
+        }
+        self.counters = match state.get::<Vec<u8>>("counters").map_err(field)? {
+            Some(counters) if self.counting && counters.len() != self.num_bits => {
+                return Err(BlockError::StateError(format!(
+                    "{} counters for a {}-bit filter",
+                    counters.len(),
+                    self.num_bits
+                )))
+            }
+            Some(counters) if self.counting => counters,
+            _ if self.counting => vec![0; self.num_bits],
+            _ => Vec::new(),
+        };
+        if let Some(keys) = state.get::<Vec<u64>>("inserted_keys").map_err(field)? {
+            self.inserted_keys = keys.into_iter().collect();
+        }
+        for (key, stat) in [
+            ("checks", &mut self.checks),
+            ("true_positives", &mut self.true_positives),
+            ("false_positives", &mut self.false_positives),
+            ("true_negatives", &mut self.true_negatives),
+            ("deletes", &mut self.deletes),
+            ("counter_saturations", &mut self.counter_saturations),
+        ] {
+            if let Some(n) = state.get::<usize>(key).map_err(field)? {
+                *stat = n;
+            }
+        }
         Ok(())
     }
 }
@@ -533,6 +874,7 @@ fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
 
     #[test]
     fn test_no_false_negatives() {
@@ -552,6 +894,26 @@ fn test_no_false_negatives() {
         }
     }
 
+    #[test]
+    fn test_state_round_trips_into_differently_sized_filter() {
+        let mut bf = BloomFilterBlock::new();
+        bf.num_bits = 50_000;
+        bf.bits = vec![false; 50_000];
+        for i in 0..200u64 {
+            bf.insert(i);
+        }
+
+        // A default 10,000-bit filter takes on the snapshot's size and bits.
+        let mut restored = BloomFilterBlock::new();
+        restored.set_state(bf.get_state()).unwrap();
+        assert_eq!(restored.bits.len(), 50_000);
+        assert_eq!(restored.bits, bf.bits);
+        assert!((0..2000u64).all(|k| restored.might_contain(k) == bf.might_contain(k)));
+        // Hashing now spans the larger filter without running off the end.
+        restored.insert(1_000_000);
+        assert!(restored.might_contain(1_000_000));
+    }
+
     #[test]
     fn test_false_positive_rate_reasonable() {
         let mut bf = BloomFilterBlock::new();
@@ -575,6 +937,123 @@ fn test_false_positive_rate_reasonable() {
         assert!(fp < 50, "False positive rate too high: {}/1000", fp);
     }
 
+    #[tokio::test]
+    async fn test_sized_filter_meets_target_until_overfilled() {
+        let mut bf = BloomFilterBlock::new();
+        let mut params = HashMap::new();
+        params.insert("expected_items".into(), ParameterValue::Integer(1000));
+        params.insert("target_fp_rate".into(), ParameterValue::Number(0.01));
+        bf.initialize(params).await.unwrap();
+        assert_eq!(bf.num_bits, 9586);
+        assert_eq!(bf.num_hash_fns, 7);
+
+        for i in 0..1000u64 {
+            bf.insert(i);
+        }
+        for i in 1_000_000..1_010_000u64 {
+            bf.might_contain(i);
+        }
+        let at_capacity = bf.observed_fp_rate();
+        assert!(at_capacity < 0.02, "observed {} at expected_items", at_capacity);
+        let mut inputs = HashMap::new();
+        inputs.insert("requests".to_string(), PortValue::Stream(vec![]));
+        assert!(bf.validate(&inputs).warnings.is_empty());
+
+        // Four times the expected load: the filter saturates.
+        for i in 1000..4000u64 {
+            bf.insert(i);
+        }
+        bf.false_positives = 0;
+        bf.true_negatives = 0;
+        for i in 1_000_000..1_010_000u64 {
+            bf.might_contain(i);
+        }
+        assert!(bf.observed_fp_rate() > 0.1, "observed {} when overfilled", bf.observed_fp_rate());
+        assert!(bf.validate(&inputs).warnings[0].contains("expected_items"));
+    }
+
+    async fn counting_filter(counting: bool) -> BloomFilterBlock {
+        let mut bf = BloomFilterBlock::new();
+        let mut params = HashMap::new();
+        params.insert("counting".into(), ParameterValue::Boolean(counting));
+        bf.initialize(params).await.unwrap();
+        bf
+    }
+
+    #[tokio::test]
+    async fn test_counting_filter_supports_delete() {
+        let mut bf = counting_filter(true).await;
+        for i in 0..100u64 {
+            bf.insert(i);
+        }
+        assert!(bf.might_contain(42));
+        assert!(bf.remove(42).unwrap());
+        assert!(!bf.might_contain(42), "deleted key should no longer match");
+        // Other keys are unaffected: no false negatives.
+        assert!((0..100u64).filter(|&i| i != 42).all(|i| bf.might_contain(i)));
+        // Deleting a key that was never inserted is ignored.
+        assert!(!bf.remove(5000).unwrap());
+        assert_eq!(bf.filter_bytes(), 4 * counting_filter(false).await.filter_bytes());
+
+        let mut plain = counting_filter(false).await;
+        plain.insert(42);
+        assert!(plain.remove(42).is_err());
+        assert!(plain.might_contain(42), "a standard filter cannot forget");
+    }
+
+    #[tokio::test]
+    async fn test_delete_through_execute() {
+        let mut bf = counting_filter(true).await;
+        let op = |op: &str, key: u64| {
+            let mut r = Record::new();
+            let _ = r.insert("_op_type".into(), op);
+            let _ = r.insert("_key".into(), key);
+            r
+        };
+        let mut inputs = HashMap::new();
+        inputs.insert(
+            "requests".into(),
+            PortValue::Stream(vec![op("INSERT", 7), op("INSERT", 8), op("DELETE", 7), op("LOOKUP", 7)]),
+        );
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        let result = bf.execute(ctx).await.unwrap();
+        assert!(result.errors.is_empty());
+        assert_eq!(result.metrics["deletes"], 1.0);
+        assert_eq!(result.metrics["true_negatives"], 1.0);
+    }
+
+    #[test]
+    fn test_counters_saturate() {
+        let mut bf = BloomFilterBlock::new();
+        bf.counting = true;
+        bf.num_bits = 4;
+        bf.num_hash_fns = 1;
+        bf.counters = vec![0; 4];
+        for i in 0..100u64 {
+            bf.insert(i);
+        }
+        // 100 increments across 4 counters: every counter tops out at 15.
+        assert_eq!(bf.counter_saturations, 4);
+        assert!(bf.counters.iter().all(|&c| c == COUNTER_MAX));
+        // Saturated counters are never decremented, so nothing is forgotten.
+        bf.remove(0).unwrap();
+        assert!(bf.counters.iter().all(|&c| c == COUNTER_MAX));
+    }
+
+    #[test]
+    fn test_optimal_size() {
+        // 0.1% needs ~14.4 bits per key and 10 hash functions.
+        let (m, k) = BloomFilterBlock::optimal_size(10_000, 0.001);
+        assert_eq!(m, 143_776);
+        assert_eq!(k, 10);
+    }
+
     #[test]
     fn test_metadata() {
         let bf = BloomFilterBlock::new();
@@ -2,8 +2,17 @@
 //!
 //! Blocks that help query planners and execution engines make better decisions.
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+
 pub mod bloom_filter;
 pub mod statistics_collector;
 
 pub use bloom_filter::BloomFilterBlock;
 pub use statistics_collector::StatisticsCollectorBlock;
+
+/// Register a factory for each optimization block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(BloomFilterBlock::new()))?;
+    registry.register_factory(|| Box::new(StatisticsCollectorBlock::new()))?;
+    Ok(())
+}
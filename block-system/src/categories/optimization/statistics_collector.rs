@@ -4,19 +4,41 @@
 //! costs and choose optimal execution strategies. This is the foundation
 //! for cost-based query optimization — PostgreSQL's ANALYZE command does this.
 //!
+//! ## How it works
+//!
+//! Each execution analyzes a deterministic sample of the input (every Nth row
+//! for `sample_rate`) and replaces the per-column statistics: `ndv` (distinct
+//! values), `min` / `max`, `null_count`, and — for numeric columns — an
+//! equi-depth histogram of `bucket_count` buckets, each holding the same
+//! number of sampled values. [`StatisticsCollectorBlock::estimate_selectivity`]
+//! turns those into the fraction of rows an equality or range predicate is
+//! expected to match, the number a cost-based optimizer compares against its
+//! index-scan break-even point.
+//!
+//! Distinct counts come from a HyperLogLog sketch of `2^precision` registers
+//! by default, so memory stays fixed however many values are sampled; the
+//! estimate's relative standard error is `1.04 / sqrt(2^precision)`. Set
+//! `use_exact` to count with a hash set instead, which is exact but grows
+//! with the number of distinct values.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
 //! |--------|------|-------------|
 //! | `rows_sampled` | Counter | Number of rows analyzed |
 //! | `distinct_values` | Gauge | Estimated distinct values (cardinality) |
+//! | `ndv_estimate` | Gauge | Unrounded distinct-value estimate for `_key` |
+//! | `ndv_standard_error` | Gauge | Relative standard error of `ndv_estimate` (0 when exact) |
 //! | `null_count` | Counter | NULL values encountered |
 //! | `min_value` | Gauge | Minimum value seen |
 //! | `max_value` | Gauge | Maximum value seen |
 //! | `avg_row_width` | Gauge | Average row size in bytes |
+//! | `columns_analyzed` | Gauge | Columns with statistics from the latest run |
 
 use async_trait::async_trait;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
@@ -30,6 +52,237 @@
 };
 use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
 
+/// A predicate on a numeric column, for selectivity estimation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Predicate {
+    /// `column = value`
+    Eq(f64),
+    /// `low <= column <= high`; a missing bound is unbounded.
+    Range { low: Option<f64>, high: Option<f64> },
+}
+
+/// HyperLogLog cardinality sketch with `2^precision` 6-bit registers.
+#[derive(Debug, Clone)]
+pub struct HyperLogLog {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    pub const MIN_PRECISION: u8 = 4;
+    pub const MAX_PRECISION: u8 = 16;
+
+    pub fn new(precision: u8) -> Self {
+        let precision = precision.clamp(Self::MIN_PRECISION, Self::MAX_PRECISION);
+        Self { precision, registers: vec![0; 1 << precision] }
+    }
+
+    pub fn insert(&mut self, value: &str) {
+        let hash = Self::hash(value);
+        let index = (hash >> (64 - self.precision)) as usize;
+        // Rank of the first 1-bit in the remaining bits, capped when they are all 0.
+        let rank = ((hash << self.precision).leading_zeros() + 1).min(65 - self.precision as u32) as u8;
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let raw = alpha * m * m / sum;
+
+        // Small cardinalities: linear counting over the empty registers.
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+
+    /// Relative standard error of [`HyperLogLog::estimate`].
+    pub fn standard_error(&self) -> f64 {
+        1.04 / (self.registers.len() as f64).sqrt()
+    }
+
+    /// FNV-1a followed by a Murmur3-style finalizer, since HyperLogLog reads
+    /// the high bits and FNV alone leaves them poorly mixed for short inputs.
+    fn hash(value: &str) -> u64 {
+        let mut h: u64 = 0xcbf29ce484222325;
+        for b in value.bytes() {
+            h ^= b as u64;
+            h = h.wrapping_mul(0x100000001b3);
+        }
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51afd7ed558ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ceb9fe1a85ec53);
+        h ^ (h >> 33)
+    }
+}
+
+/// Distinct-value counter: exact, or a HyperLogLog sketch.
+enum DistinctCounter {
+    Exact(HashSet<String>),
+    Sketch(HyperLogLog),
+}
+
+impl DistinctCounter {
+    fn new(use_exact: bool, precision: u8) -> Self {
+        if use_exact {
+            Self::Exact(HashSet::new())
+        } else {
+            Self::Sketch(HyperLogLog::new(precision))
+        }
+    }
+
+    fn insert(&mut self, value: String) {
+        match self {
+            Self::Exact(set) => {
+                set.insert(value);
+            }
+            Self::Sketch(hll) => hll.insert(&value),
+        }
+    }
+
+    fn estimate(&self) -> f64 {
+        match self {
+            Self::Exact(set) => set.len() as f64,
+            Self::Sketch(hll) => hll.estimate(),
+        }
+    }
+
+    fn standard_error(&self) -> f64 {
+        match self {
+            Self::Exact(_) => 0.0,
+            Self::Sketch(hll) => hll.standard_error(),
+        }
+    }
+}
+
+/// Optimizer statistics for one column.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ColumnStats {
+    /// Sampled rows, including those where the column is NULL.
+    pub row_count: usize,
+    pub null_count: usize,
+    /// Number of distinct non-NULL values (`ndv_estimate` rounded).
+    pub ndv: usize,
+    pub ndv_estimate: f64,
+    /// Relative standard error of `ndv_estimate`; 0 for exact counts.
+    pub ndv_standard_error: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Equi-depth bucket boundaries: `bucket_count + 1` ascending values, with
+    /// the same number of sampled values between each adjacent pair. Empty
+    /// for non-numeric columns.
+    pub histogram: Vec<f64>,
+}
+
+impl ColumnStats {
+    fn build(values: &[&JsonValue], bucket_count: usize, mut distinct: DistinctCounter) -> Self {
+        let mut numbers = Vec::new();
+        let mut null_count = 0;
+        for value in values {
+            if value.is_null() {
+                null_count += 1;
+                continue;
+            }
+            distinct.insert(value.to_string());
+            if let Some(n) = value.as_f64() {
+                numbers.push(n);
+            }
+        }
+        numbers.sort_by(f64::total_cmp);
+
+        // Only numeric columns get a range histogram.
+        let histogram = if !numbers.is_empty() && numbers.len() + null_count == values.len() {
+            (0..=bucket_count)
+                .map(|i| numbers[(i * (numbers.len() - 1)) / bucket_count])
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            row_count: values.len(),
+            null_count,
+            ndv: distinct.estimate().round() as usize,
+            ndv_estimate: distinct.estimate(),
+            ndv_standard_error: distinct.standard_error(),
+            min: numbers.first().copied(),
+            max: numbers.last().copied(),
+            histogram,
+        }
+    }
+
+    /// Fraction of sampled rows where the column is not NULL.
+    pub fn non_null_fraction(&self) -> f64 {
+        if self.row_count == 0 {
+            return 0.0;
+        }
+        1.0 - self.null_count as f64 / self.row_count as f64
+    }
+
+    /// Fraction of non-NULL values below `x` (or at most `x` if `inclusive`),
+    /// interpolating linearly inside the bucket that contains it.
+    fn fraction_below(&self, x: f64, inclusive: bool) -> f64 {
+        let buckets = self.histogram.len().saturating_sub(1);
+        if buckets == 0 {
+            return 0.0;
+        }
+        let covered: f64 = self
+            .histogram
+            .windows(2)
+            .map(|w| {
+                let (lo, hi) = (w[0], w[1]);
+                if hi < x || (inclusive && hi <= x) {
+                    1.0
+                } else if lo < x {
+                    (x - lo) / (hi - lo)
+                } else {
+                    0.0
+                }
+            })
+            .sum();
+        covered / buckets as f64
+    }
+
+    /// Estimated fraction of rows (NULLs included) matching `predicate`.
+    pub fn selectivity(&self, predicate: &Predicate) -> f64 {
+        let non_null = self.non_null_fraction();
+        match *predicate {
+            Predicate::Eq(v) => {
+                if self.ndv == 0 || self.min.is_some_and(|m| v < m) || self.max.is_some_and(|m| v > m) {
+                    return 0.0;
+                }
+                // Uniform over distinct values, unless the histogram shows the
+                // value filling whole buckets (a heavy hitter).
+                let buckets = self.histogram.len().saturating_sub(1).max(1);
+                let full_buckets =
+                    self.histogram.windows(2).filter(|w| w[0] == v && w[1] == v).count();
+                let fraction = (full_buckets as f64 / buckets as f64).max(1.0 / self.ndv as f64);
+                fraction * non_null
+            }
+            Predicate::Range { low, high } => {
+                if self.histogram.is_empty() {
+                    return 0.0;
+                }
+                let upto = high.map_or(1.0, |h| self.fraction_below(h, true));
+                let below = low.map_or(0.0, |l| self.fraction_below(l, false));
+                (upto - below).max(0.0) * non_null
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // StatisticsCollectorBlock
 // ---------------------------------------------------------------------------
@@ -43,14 +296,19 @@ pub struct StatisticsCollectorBlock {
 
     // Configuration
     sample_rate: f64,
-    histogram_buckets: usize,
+    bucket_count: usize,
+    precision: u8,
+    use_exact: bool,
 
     // Stats
     rows_sampled: usize,
     distinct_values: usize,
+    ndv_estimate: f64,
+    ndv_standard_error: f64,
     null_count: usize,
     min_value: f64,
     max_value: f64,
+    columns: HashMap<String, ColumnStats>,
 }
 
 impl StatisticsCollectorBlock {
@@ -62,12 +320,17 @@ pub fn new() -> Self {
             params: Self::build_parameters(),
             metric_defs: Self::build_metrics(),
             sample_rate: 0.1,
-            histogram_buckets: 100,
+            bucket_count: 100,
+            precision: 14,
+            use_exact: false,
             rows_sampled: 0,
             distinct_values: 0,
+            ndv_estimate: 0.0,
+            ndv_standard_error: 0.0,
             null_count: 0,
             min_value: f64::MAX,
             max_value: f64::MIN,
+            columns: HashMap::new(),
         }
     }
 
@@ -97,7 +360,7 @@ fn build_metadata() -> BlockMetadata {
                 algorithm: "COLLECT_STATISTICS(table, sample_rate):\n  \
                            1. Determine sample step: step = ceil(1.0 / sample_rate)\n  \
                            2. Initialize trackers:\n     \
-                              distinct_set = HashSet (or HyperLogLog in production)\n     \
+                              distinct = HyperLogLog(2^precision registers), or HashSet if use_exact\n     \
                               min_value = +infinity, max_value = -infinity\n     \
                               null_count = 0, total_width = 0\n  \
                            3. For each row at index i where i % step == 0:\n     \
@@ -106,15 +369,23 @@ fn build_metadata() -> BlockMetadata {
                               c. Else: add to distinct_set, update min/max\n     \
                               d. Accumulate row width estimate\n  \
                            4. Compute derived statistics:\n     \
-                              cardinality = distinct_set.size()\n     \
+                              cardinality = distinct.estimate()\n     \
+                                 HLL: alpha * m^2 / sum(2^-register), or linear counting\n     \
+                                 m * ln(m / empty_registers) while the sketch is sparse\n     \
                               null_fraction = null_count / rows_sampled\n     \
                               avg_width = total_width / rows_sampled\n  \
-                           5. Build equi-depth histogram with configured bucket count\n  \
-                           6. Store statistics in system catalog"
+                           5. Per column: sort sampled numeric values v[0..n] and take\n     \
+                              bucket_count + 1 boundaries at v[i * (n-1) / bucket_count]\n  \
+                           6. Store statistics in system catalog\n\n\
+                           ESTIMATE_SELECTIVITY(column, predicate):\n  \
+                           non_null = 1 - null_count / row_count\n  \
+                           col = v:        max(1 / ndv, buckets entirely equal to v / B) * non_null\n  \
+                           lo <= col <= hi: (F(hi) - F(lo)) * non_null\n     \
+                              F(x) = (full buckets below x + fraction of x's bucket) / B"
                     .into(),
                 complexity: Complexity {
                     time: "O(n × sample_rate) — reads a fraction of the table".into(),
-                    space: "O(distinct_values + histogram_buckets)".into(),
+                    space: "O(2^precision + bucket_count) per column; O(distinct_values) with use_exact".into(),
                 },
                 use_cases: vec![
                     "PostgreSQL's ANALYZE populates pg_statistic for the query planner".into(),
@@ -129,6 +400,7 @@ fn build_metadata() -> BlockMetadata {
                     "More histogram buckets = better selectivity estimates".into(),
                     "Auto-analyze in PostgreSQL triggers after enough row changes".into(),
                     "Sampling introduces estimation error — rare values may be missed entirely".into(),
+                    "HyperLogLog trades a few percent of NDV accuracy for fixed memory; exact counting is precise but O(distinct values)".into(),
                     "Correlated columns are hard to capture with per-column statistics (multi-column stats help)".into(),
                 ],
                 examples: vec![
@@ -155,13 +427,26 @@ fn build_metadata() -> BlockMetadata {
                       controls a related concept (number of histogram buckets × 300 = rows sampled). \
                       Recommended: 0.1 for most tables, 0.5-1.0 for small tables or columns with high skew."
                         .into()),
-                    ("histogram_buckets".into(),
+                    ("bucket_count".into(),
                      "The number of equi-depth histogram buckets used to capture value distribution. More \
                       buckets provide finer-grained selectivity estimates but use more memory and take \
                       longer to compute. PostgreSQL defaults to 100 buckets (via default_statistics_target). \
                       For columns with uniform distribution, even 10 buckets suffice. For highly skewed \
                       columns, 200-500 buckets may be needed to capture the distribution accurately. \
-                      Recommended: 100 for most columns, increase for skewed columns used in WHERE clauses."
+                      Recommended: 100 for most columns, increase for skewed columns used in WHERE clauses. \
+                      (The older name histogram_buckets is still accepted.)"
+                        .into()),
+                    ("precision".into(),
+                     "HyperLogLog precision p: distinct counts use 2^p one-byte registers per column and \
+                      have a relative standard error of 1.04 / sqrt(2^p) — about 1.6% at p = 12 and 0.8% \
+                      at p = 14. Each extra bit halves the variance and doubles the memory. Redis's PFCOUNT \
+                      uses p = 14. Recommended: 12-14; ignored when use_exact is set."
+                        .into()),
+                    ("use_exact".into(),
+                     "Count distinct values exactly with a hash set instead of HyperLogLog. Exact counts \
+                      cost memory proportional to the number of distinct values, which is fine for small \
+                      tables but not at scale. Recommended: off, unless the table is small or you are \
+                      comparing the sketch against the true count."
                         .into()),
                 ]),
                 alternatives: vec![
@@ -239,7 +524,7 @@ fn build_parameters() -> Vec<Parameter> {
                 ),
             },
             Parameter {
-                id: "histogram_buckets".into(),
+                id: "bucket_count".into(),
                 name: "Histogram Buckets".into(),
                 param_type: ParameterType::Number,
                 description: "Number of equi-depth histogram buckets".into(),
@@ -252,6 +537,32 @@ fn build_parameters() -> Vec<Parameter> {
                     ParameterUIHint::new(WidgetType::Slider).with_step(10.0),
                 ),
             },
+            Parameter {
+                id: "precision".into(),
+                name: "HLL Precision".into(),
+                param_type: ParameterType::Number,
+                description: "HyperLogLog precision p (2^p registers, standard error 1.04 / sqrt(2^p))".into(),
+                default_value: ParameterValue::Integer(14),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new()
+                        .with_min(HyperLogLog::MIN_PRECISION as f64)
+                        .with_max(HyperLogLog::MAX_PRECISION as f64),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider).with_step(1.0),
+                ),
+            },
+            Parameter {
+                id: "use_exact".into(),
+                name: "Exact Distinct Counts".into(),
+                param_type: ParameterType::Boolean,
+                description: "Count distinct values exactly instead of with HyperLogLog".into(),
+                default_value: ParameterValue::Boolean(false),
+                required: false,
+                constraints: None,
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Checkbox)),
+            },
         ]
     }
 
@@ -273,6 +584,22 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Estimated number of distinct values (cardinality)".into(),
                 aggregations: vec![AggregationType::Max],
             },
+            MetricDefinition {
+                id: "ndv_estimate".into(),
+                name: "NDV Estimate".into(),
+                metric_type: MetricType::Gauge,
+                unit: "values".into(),
+                description: "Unrounded distinct-value estimate for _key".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "ndv_standard_error".into(),
+                name: "NDV Standard Error".into(),
+                metric_type: MetricType::Gauge,
+                unit: "ratio".into(),
+                description: "Relative standard error of the distinct-value estimate (0 when exact)".into(),
+                aggregations: vec![AggregationType::Max],
+            },
             MetricDefinition {
                 id: "null_count".into(),
                 name: "NULL Count".into(),
@@ -305,8 +632,42 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Average row size in bytes (estimated)".into(),
                 aggregations: vec![AggregationType::Avg],
             },
+            MetricDefinition {
+                id: "columns_analyzed".into(),
+                name: "Columns Analyzed".into(),
+                metric_type: MetricType::Gauge,
+                unit: "columns".into(),
+                description: "Columns with statistics from the latest run".into(),
+                aggregations: vec![AggregationType::Max],
+            },
         ]
     }
+
+    /// Statistics for `column` from the most recent run.
+    pub fn column_stats(&self, column: &str) -> Option<&ColumnStats> {
+        self.columns.get(column)
+    }
+
+    /// Estimated fraction of rows matching `predicate` on `column`, or `None`
+    /// if the column has not been analyzed.
+    pub fn estimate_selectivity(&self, column: &str, predicate: &Predicate) -> Option<f64> {
+        self.columns.get(column).map(|stats| stats.selectivity(predicate))
+    }
+
+    /// Rebuilds per-column statistics from the sampled rows.
+    fn analyze_columns(&mut self, sample: &[&Record]) {
+        let names: HashSet<&String> = sample.iter().flat_map(|r| r.data.keys()).collect();
+        let null = JsonValue::Null;
+        self.columns = names
+            .into_iter()
+            .map(|name| {
+                let values: Vec<&JsonValue> =
+                    sample.iter().map(|r| r.data.get(name).unwrap_or(&null)).collect();
+                let distinct = DistinctCounter::new(self.use_exact, self.precision);
+                (name.clone(), ColumnStats::build(&values, self.bucket_count, distinct))
+            })
+            .collect();
+    }
 }
 
 impl Default for StatisticsCollectorBlock {
@@ -331,18 +692,24 @@ fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
 
     async fn initialize(
         &mut self,
-        params: HashMap<String, ParameterValue>,
+        mut params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("sample_rate") {
-            self.sample_rate = val
-                .as_number()
-                .ok_or_else(|| BlockError::InvalidParameter("sample_rate must be a number".into()))?;
+        // `histogram_buckets` is the parameter's former name.
+        if let Some(val) = params.remove("histogram_buckets") {
+            params.entry("bucket_count".into()).or_insert(val);
         }
-        if let Some(val) = params.get("histogram_buckets") {
-            self.histogram_buckets = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("histogram_buckets must be an integer".into()))?
-                as usize;
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("sample_rate").and_then(ParameterValue::as_number) {
+            self.sample_rate = v;
+        }
+        if let Some(v) = params.get("bucket_count").and_then(ParameterValue::as_integer) {
+            self.bucket_count = v as usize;
+        }
+        if let Some(v) = params.get("precision").and_then(ParameterValue::as_integer) {
+            self.precision = v as u8;
+        }
+        if let Some(v) = params.get("use_exact").and_then(ParameterValue::as_bool) {
+            self.use_exact = v;
         }
         Ok(())
     }
@@ -362,7 +729,8 @@ async fn execute(
         };
 
         let total_rows = records.len();
-        let mut distinct_set = std::collections::HashSet::new();
+        let mut distinct = DistinctCounter::new(self.use_exact, self.precision);
+        let mut sample = Vec::new();
         let mut total_width: usize = 0;
 
         // Simple deterministic sampling: take every Nth row.
@@ -377,10 +745,11 @@ async fn execute(
                 continue;
             }
             self.rows_sampled += 1;
+            sample.push(record);
 
             // Get _key for distinct value tracking.
             if let Ok(Some(key)) = record.get::<u64>("_key") {
-                distinct_set.insert(key);
+                distinct.insert(key.to_string());
                 let fkey = key as f64;
                 if fkey < self.min_value { self.min_value = fkey; }
                 if fkey > self.max_value { self.max_value = fkey; }
@@ -392,7 +761,10 @@ async fn execute(
             total_width += record.data.len() * 8;
         }
 
-        self.distinct_values = distinct_set.len();
+        self.ndv_estimate = distinct.estimate();
+        self.ndv_standard_error = distinct.standard_error();
+        self.distinct_values = self.ndv_estimate.round() as usize;
+        self.analyze_columns(&sample);
         let avg_width = if self.rows_sampled > 0 {
             total_width as f64 / self.rows_sampled as f64
         } else {
@@ -404,11 +776,16 @@ async fn execute(
         let _ = stats.insert("_total_rows".into(), total_rows);
         let _ = stats.insert("_rows_sampled".into(), self.rows_sampled);
         let _ = stats.insert("_distinct_values".into(), self.distinct_values);
+        let _ = stats.insert("_ndv_estimate".into(), self.ndv_estimate);
+        let _ = stats.insert("_ndv_standard_error".into(), self.ndv_standard_error);
         let _ = stats.insert("_null_count".into(), self.null_count);
         let _ = stats.insert("_avg_row_width".into(), avg_width as usize);
+        let _ = stats.insert("_columns".into(), &self.columns);
 
         context.metrics.record("rows_sampled", self.rows_sampled as f64);
         context.metrics.record("distinct_values", self.distinct_values as f64);
+        context.metrics.record("ndv_estimate", self.ndv_estimate);
+        context.metrics.record("ndv_standard_error", self.ndv_standard_error);
         context.metrics.record("null_count", self.null_count as f64);
         if self.min_value != f64::MAX {
             context.metrics.record("min_value", self.min_value);
@@ -417,6 +794,7 @@ async fn execute(
             context.metrics.record("max_value", self.max_value);
         }
         context.metrics.record("avg_row_width", avg_width);
+        context.metrics.record("columns_analyzed", self.columns.len() as f64);
 
         let mut outputs = HashMap::new();
         outputs.insert("statistics".into(), PortValue::Single(stats));
@@ -424,6 +802,8 @@ async fn execute(
         let mut metrics_summary = HashMap::new();
         metrics_summary.insert("rows_sampled".into(), self.rows_sampled as f64);
         metrics_summary.insert("distinct_values".into(), self.distinct_values as f64);
+        metrics_summary.insert("ndv_estimate".into(), self.ndv_estimate);
+        metrics_summary.insert("ndv_standard_error".into(), self.ndv_standard_error);
         metrics_summary.insert("null_count".into(), self.null_count as f64);
         if self.min_value != f64::MAX {
             metrics_summary.insert("min_value".into(), self.min_value);
@@ -432,6 +812,7 @@ async fn execute(
             metrics_summary.insert("max_value".into(), self.max_value);
         }
         metrics_summary.insert("avg_row_width".into(), avg_width);
+        metrics_summary.insert("columns_analyzed".into(), self.columns.len() as f64);
 
         Ok(ExecutionResult {
             outputs,
@@ -455,14 +836,18 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
     fn get_state(&self) -> BlockState {
         let mut state = BlockState::new();
         let _ = state.insert("sample_rate".into(), self.sample_rate);
-        let _ = state.insert("histogram_buckets".into(), self.histogram_buckets);
+        let _ = state.insert("bucket_count".into(), self.bucket_count);
+        let _ = state.insert("precision".into(), self.precision);
+        let _ = state.insert("use_exact".into(), self.use_exact);
         let _ = state.insert("rows_sampled".into(), self.rows_sampled);
         state
     }
 
     fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
         if let Ok(Some(r)) = state.get::<f64>("sample_rate") { self.sample_rate = r; }
-        if let Ok(Some(b)) = state.get::<usize>("histogram_buckets") { self.histogram_buckets = b; }
+        if let Ok(Some(b)) = state.get::<usize>("bucket_count") { self.bucket_count = b; }
+        if let Ok(Some(p)) = state.get::<u8>("precision") { self.precision = p; }
+        if let Ok(Some(e)) = state.get::<bool>("use_exact") { self.use_exact = e; }
         Ok(())
     }
 }
@@ -481,6 +866,7 @@ async fn test_basic_collection() {
 
         let mut collector = StatisticsCollectorBlock::new();
         collector.sample_rate = 1.0; // Sample everything
+        collector.use_exact = true;
 
         let records: Vec<Record> = (0..100u64).map(|i| {
             let mut r = Record::new();
@@ -504,6 +890,154 @@ async fn test_basic_collection() {
         assert_eq!(*result.metrics.get("distinct_values").unwrap(), 100.0);
     }
 
+    /// Analyzes `records` with every row sampled and `bucket_count` buckets.
+    async fn analyze(records: Vec<Record>, bucket_count: i64) -> StatisticsCollectorBlock {
+        let mut params = HashMap::new();
+        params.insert("bucket_count".into(), ParameterValue::Integer(bucket_count));
+        params.insert("use_exact".into(), ParameterValue::Boolean(true));
+        analyze_with(records, params).await
+    }
+
+    async fn analyze_with(
+        records: Vec<Record>,
+        mut params: HashMap<String, ParameterValue>,
+    ) -> StatisticsCollectorBlock {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut collector = StatisticsCollectorBlock::new();
+        params.insert("sample_rate".into(), ParameterValue::Number(1.0));
+        collector.initialize(params).await.unwrap();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        collector.execute(ctx).await.unwrap();
+        collector
+    }
+
+    #[tokio::test]
+    async fn test_column_statistics() {
+        let records: Vec<Record> = (0..1000u64)
+            .map(|i| {
+                let mut r = Record::new();
+                r.insert("age".into(), i % 100).unwrap();
+                if i % 10 == 0 {
+                    r.insert("email".into(), JsonValue::Null).unwrap();
+                } else {
+                    r.insert("email".into(), format!("user{}@example.com", i)).unwrap();
+                }
+                r
+            })
+            .collect();
+        let collector = analyze(records, 10).await;
+
+        let age = collector.column_stats("age").unwrap();
+        assert_eq!(age.ndv, 100);
+        assert_eq!((age.min, age.max), (Some(0.0), Some(99.0)));
+        assert_eq!(age.null_count, 0);
+        assert_eq!(age.histogram.len(), 11);
+
+        let email = collector.column_stats("email").unwrap();
+        assert_eq!(email.null_count, 100);
+        assert_eq!(email.ndv, 900);
+        assert!(email.histogram.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_selectivity_estimates() {
+        // 1000 rows: 500 with status 1, the other 500 spread over 2..=501;
+        // score uniform over 0..1000.
+        let records: Vec<Record> = (0..1000u64)
+            .map(|i| {
+                let mut r = Record::new();
+                r.insert("status".into(), if i < 500 { 1 } else { i - 498 }).unwrap();
+                r.insert("score".into(), i).unwrap();
+                r
+            })
+            .collect();
+        let collector = analyze(records, 20).await;
+        let est = |col: &str, p: Predicate| collector.estimate_selectivity(col, &p).unwrap();
+
+        let close = |a: f64, b: f64| (a - b).abs() < 0.02;
+        assert!(close(est("score", Predicate::Range { low: Some(100.0), high: Some(299.0) }), 0.2));
+        assert!(close(est("score", Predicate::Range { low: None, high: Some(499.0) }), 0.5));
+        assert!(close(est("score", Predicate::Eq(42.0)), 0.001));
+        assert_eq!(est("score", Predicate::Eq(5000.0)), 0.0);
+        // The histogram reveals the heavy hitter that 1 / ndv would miss.
+        assert!(close(est("status", Predicate::Eq(1.0)), 0.5));
+        assert!(collector.estimate_selectivity("missing", &Predicate::Eq(1.0)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_selectivity_accounts_for_nulls() {
+        let records: Vec<Record> = (0..100u64)
+            .map(|i| {
+                let mut r = Record::new();
+                if i % 2 == 0 {
+                    r.insert("x".into(), i).unwrap();
+                } else {
+                    r.insert("x".into(), JsonValue::Null).unwrap();
+                }
+                r
+            })
+            .collect();
+        let collector = analyze(records, 10).await;
+        let all = collector
+            .estimate_selectivity("x", &Predicate::Range { low: None, high: None })
+            .unwrap();
+        assert!((all - 0.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_hyperloglog_estimate() {
+        let records: Vec<Record> = (0..100_000u64)
+            .map(|i| {
+                let mut r = Record::new();
+                r.insert("_key".into(), i % 1000).unwrap();
+                r
+            })
+            .collect();
+
+        let collector = analyze_with(records.clone(), HashMap::new()).await;
+        let key = collector.column_stats("_key").unwrap();
+        let error = (key.ndv_estimate - 1000.0).abs() / 1000.0;
+        assert!(error < 0.03, "estimate {} off by {:.1}%", key.ndv_estimate, error * 100.0);
+        assert!((key.ndv_standard_error - 1.04 / 128.0).abs() < 1e-12);
+        assert_eq!(collector.ndv_estimate, key.ndv_estimate);
+
+        let mut params = HashMap::new();
+        params.insert("use_exact".into(), ParameterValue::Boolean(true));
+        let exact = analyze_with(records, params).await;
+        let key = exact.column_stats("_key").unwrap();
+        assert_eq!(key.ndv, 1000);
+        assert_eq!(key.ndv_standard_error, 0.0);
+    }
+
+    #[test]
+    fn test_hyperloglog_precision_bounds_error() {
+        let values: Vec<String> = (0..50_000).map(|i| format!("v{}", i)).collect();
+        for precision in [10, 12, 14] {
+            let mut hll = HyperLogLog::new(precision);
+            values.iter().for_each(|v| hll.insert(v));
+            let error = (hll.estimate() - 50_000.0).abs() / 50_000.0;
+            assert!(error < 3.0 * hll.standard_error(), "p={} error {}", precision, error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_precision() {
+        let mut collector = StatisticsCollectorBlock::new();
+        let mut params = HashMap::new();
+        params.insert("precision".into(), ParameterValue::Integer(20));
+        assert!(collector.initialize(params).await.is_err());
+    }
+
     #[test]
     fn test_metadata() {
         let sc = StatisticsCollectorBlock::new();
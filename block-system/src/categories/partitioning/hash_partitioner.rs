@@ -4,6 +4,33 @@
 //! every distributed database — Cassandra, DynamoDB, CockroachDB all use
 //! hash partitioning to spread data evenly across nodes.
 //!
+//! ## How it works
+//!
+//! Each record is assigned a partition according to `strategy` and the
+//! result is written to `_partition_id`:
+//!
+//! - `hash` — `hash(partition_key) % partition_count`. Integer keys are mixed
+//!   with a Murmur3-style finalizer, other values are FNV-hashed from their
+//!   JSON form.
+//! - `range` — the numeric key is compared against the sorted `boundaries`;
+//!   N boundaries give N + 1 partitions.
+//! - `round_robin` — the key is ignored and partitions are filled in turn.
+//! - `consistent_hash` — each partition owns `virtual_nodes` points on a hash
+//!   ring and a key goes to the first point at or after its hash.
+//!
+//! `reshard(new_partition_count)` changes the partition count and reports how
+//! many distinct keys changed partition. With `hash` almost every key moves;
+//! with `consistent_hash` only about 1/(N+1) of them do when going from N to
+//! N + 1 partitions. A record with `_op: "reshard"` and `_partition_count`
+//! does the same from a pipeline.
+//!
+//! Per-partition counts feed the skew metrics, so a low-cardinality key
+//! (e.g. `country`) shows up as a high `skew_factor` rather than silently
+//! overloading one partition. Records with `_op: "range_query"` and
+//! `_start` / `_end` fields are not partitioned; instead the block checks
+//! whether the key range lives in a single partition, which is what
+//! `range_queries_local_pct` reports.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
@@ -12,8 +39,13 @@
 //! | `partitions_used` | Gauge | Number of partitions that received data |
 //! | `hottest_partition_pct` | Gauge | % of records in the most loaded partition |
 //! | `evenness_score` | Gauge | 0–100 score of distribution evenness |
+//! | `skew_factor` | Gauge | Largest partition / average partition (1.0 = perfectly even) |
+//! | `range_queries` | Counter | Range queries checked for locality |
+//! | `range_queries_local_pct` | Gauge | % of range queries answerable by a single partition |
+//! | `keys_moved` | Counter | Distinct keys reassigned by `reshard` |
 
 use async_trait::async_trait;
+use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 
 use crate::core::block::{
@@ -23,11 +55,66 @@
 use crate::core::constraint::{Constraint, Guarantee};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
-    ValidationResult, WidgetType,
+    enum_param, list_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint,
+    ParameterValue, ValidationResult, WidgetType,
 };
 use crate::core::port::{Port, PortDirection, PortType, PortValue};
 
+/// How records are assigned to partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PartitionStrategy {
+    Hash,
+    Range,
+    RoundRobin,
+    ConsistentHash,
+}
+
+/// Values accepted by the `strategy` parameter.
+const STRATEGIES: &[&str] = &["hash", "range", "round_robin", "consistent_hash"];
+
+/// Constraints on the `boundaries` list: split points are numbers.
+fn boundaries_constraints() -> ParameterConstraints {
+    ParameterConstraints::new().with_element_type(ParameterType::Number)
+}
+
+/// Reads the split points from a validated `boundaries` list, e.g.
+/// `[100, 200, 300]` (or the string `"100, 200, 300"`).
+fn parse_boundaries(items: &[ParameterValue]) -> Result<Vec<f64>, BlockError> {
+    let boundaries: Vec<f64> = items.iter().filter_map(ParameterValue::as_number).collect();
+    if boundaries.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(BlockError::InvalidParameter(
+            "boundaries must be strictly increasing".into(),
+        ));
+    }
+    Ok(boundaries)
+}
+
+/// Murmur3 64-bit finalizer — cheap, well-distributed integer mixing.
+fn mix64(key: u64) -> u64 {
+    let mut h = key;
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51afd7ed558ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ceb9fe1a85ec53);
+    h ^= h >> 33;
+    h
+}
+
+/// Maps a partition key value to the integer fed to the hash mixer. Integer
+/// keys pass through unchanged; anything else is FNV-1a hashed.
+fn key_to_u64(value: &JsonValue) -> u64 {
+    if let Some(n) = value.as_u64() {
+        return n;
+    }
+    let s = value.to_string();
+    let mut h: u64 = 14695981039346656037;
+    for b in s.bytes() {
+        h ^= b as u64;
+        h = h.wrapping_mul(1099511628211);
+    }
+    h
+}
+
 // ---------------------------------------------------------------------------
 // HashPartitionerBlock
 // ---------------------------------------------------------------------------
@@ -40,25 +127,49 @@ pub struct HashPartitionerBlock {
     metric_defs: Vec<MetricDefinition>,
 
     // Configuration
-    num_partitions: usize,
+    partition_count: usize,
+    partition_key: String,
+    strategy: PartitionStrategy,
+    boundaries: Vec<f64>,
+    virtual_nodes: usize,
+
+    // State
+    next_round_robin: usize,
+    /// Consistent-hashing ring: (point, partition), sorted by point.
+    ring: Vec<(u64, usize)>,
+    /// Records seen per distinct key (hashed form), used by `reshard`.
+    key_counts: HashMap<u64, usize>,
 
     // Stats
     partition_counts: Vec<usize>,
     records_partitioned: usize,
+    range_queries: usize,
+    range_queries_local: usize,
+    keys_moved: usize,
 }
 
 impl HashPartitionerBlock {
     pub fn new() -> Self {
-        let num_partitions = 4;
+        let partition_count = 4;
         Self {
             metadata: Self::build_metadata(),
             input_ports: Self::build_inputs(),
             output_ports: Self::build_outputs(),
             params: Self::build_parameters(),
             metric_defs: Self::build_metrics(),
-            num_partitions,
-            partition_counts: vec![0; num_partitions],
+            partition_count,
+            partition_key: "_key".into(),
+            strategy: PartitionStrategy::Hash,
+            boundaries: Vec::new(),
+            virtual_nodes: 64,
+            next_round_robin: 0,
+            ring: Vec::new(),
+            key_counts: HashMap::new(),
+            partition_counts: vec![0; partition_count],
             records_partitioned: 0,
+            range_queries: 0,
+            range_queries_local: 0,
+            keys_moved: 0,
         }
     }
 
@@ -67,7 +178,7 @@ fn build_metadata() -> BlockMetadata {
             id: "hash-partitioner".into(),
             name: "Hash Partitioner".into(),
             category: BlockCategory::Partitioning,
-            description: "Distributes records across partitions by hashing a key".into(),
+            description: "Distributes records across partitions by hash, range, or round-robin".into(),
             version: "1.0.0".into(),
             documentation: BlockDocumentation {
                 overview: "Hash partitioning distributes data across multiple partitions by computing a \
@@ -83,26 +194,52 @@ fn build_metadata() -> BlockMetadata {
                            hash function that scrambles the name into a number and then takes modulo N to get \
                            the classroom number. This produces much more even groups. The downside: if you \
                            want to find all students whose names start with 'S', you have to check every \
-                           classroom (scatter-gather) because hashing destroyed the alphabetical ordering."
+                           classroom (scatter-gather) because hashing destroyed the alphabetical ordering.\n\n\
+                           The block also supports range partitioning (contiguous key ranges per partition, \
+                           like HBase regions or CockroachDB ranges) and round-robin (records dealt out in \
+                           turn, ignoring the key). Together they show the core trade-off: range keeps \
+                           neighbouring keys together so range queries stay local, but skews easily; \
+                           round-robin is perfectly balanced but scatters every query; hash sits in between, \
+                           balanced for point lookups and scattered for ranges.\n\n\
+                           Finally, consistent hashing (Dynamo, Cassandra, Riak) replaces the modulo with a \
+                           hash ring, so changing the number of partitions only moves the keys that land on \
+                           the new partition's slice of the ring instead of reshuffling nearly everything."
                     .into(),
-                algorithm: "PARTITION(record):\n  \
-                           1. Extract partition key from record\n  \
+                algorithm: "PARTITION(record), strategy = hash:\n  \
+                           1. Extract partition_key column from record\n  \
                            2. Compute hash: h = hash_mix(key)\n     \
                               h ^= h >> 33\n     \
                               h *= 0xff51afd7ed558ccd\n     \
                               h ^= h >> 33\n     \
                               h *= 0xc4ceb9fe1a85ec53\n     \
                               h ^= h >> 33\n  \
-                           3. Assign partition: partition_id = h % num_partitions\n  \
-                           4. Route record to partition_id\n\n\
-                           REBALANCE (when adding partition):\n  \
-                           Simple modulo: ALL records potentially need reassignment\n  \
-                           Consistent hashing: only K/N records move on average\n  \
-                           (where K = total keys, N = total partitions)"
+                           3. Assign partition: partition_id = h % partition_count\n  \
+                           4. Tag record with _partition_id\n\n\
+                           PARTITION(record), strategy = range:\n  \
+                           partition_id = count of boundaries <= key\n\n\
+                           PARTITION(record), strategy = round_robin:\n  \
+                           partition_id = next; next = (next + 1) % partition_count\n\n\
+                           PARTITION(record), strategy = consistent_hash:\n  \
+                           ring = for p in partitions, v in 0..virtual_nodes: (hash(p, v), p), sorted\n  \
+                           partition_id = owner of first ring point >= hash(key), wrapping\n\n\
+                           RANGE QUERY [start, end] is local if:\n  \
+                           range:       partition(start) == partition(end)\n  \
+                           hash / consistent_hash: every key in [start, end] maps to one partition\n  \
+                           round_robin: never (any key may be in any partition)\n\n\
+                           SKEW:\n  \
+                           skew_factor = max(partition_sizes) / (records / partition_count)\n  \
+                           1.0 = perfectly even; partition_count = every record in one partition\n\n\
+                           RESHARD(new_count):\n  \
+                           old = partition(key) for every key seen\n  \
+                           partition_count = new_count; rebuild ring\n  \
+                           keys_moved = count of keys where partition(key) != old\n  \
+                           hash: ~K * (1 - 1/new_count) keys move (nearly all)\n  \
+                           consistent_hash: ~K / new_count keys move when adding one partition\n  \
+                           (where K = distinct keys)"
                     .into(),
                 complexity: Complexity {
                     time: "O(1) per record — single hash computation".into(),
-                    space: "O(num_partitions) for partition metadata".into(),
+                    space: "O(partition_count) for partition metadata".into(),
                 },
                 use_cases: vec![
                     "Cassandra distributes rows across nodes using Murmur3 hash".into(),
@@ -134,14 +271,49 @@ fn build_metadata() -> BlockMetadata {
                              both writes and reads to be routed directly to the right node in O(1) time."
                     .into(),
                 parameter_guide: HashMap::from([
-                    ("num_partitions".into(),
+                    ("partition_count".into(),
                      "The number of partitions to distribute data across. Each partition can be assigned to \
                       a different node for horizontal scaling. More partitions enable finer-grained load \
                       balancing but add overhead for cross-partition queries (scatter-gather). Fewer partitions \
                       are simpler but limit scalability. In Cassandra, the virtual node count (num_tokens) \
                       serves a similar purpose — default is 256 vnodes per physical node. For Kafka, the \
                       partition count is typically 3-12 per topic. Recommended: start with 4-8 for development, \
-                      scale to match the number of nodes in production."
+                      scale to match the number of nodes in production. (The older name num_partitions \
+                      is still accepted.)"
+                        .into()),
+                    ("partition_key".into(),
+                     "The record column whose value is hashed to choose a partition. The key's cardinality \
+                      bounds how evenly data can spread: a column with 3 distinct values can fill at most 3 \
+                      partitions no matter how many exist, and a constant key puts everything in one \
+                      partition (skew_factor = partition_count). Good keys are high-cardinality and evenly \
+                      accessed, like user_id; poor keys are status, country, or date. Default: _key. \
+                      Ignored by round_robin; range needs a numeric key."
+                        .into()),
+                    ("strategy".into(),
+                     "How records are assigned to partitions. hash scrambles keys for an even spread and \
+                      fast point lookups, but a range query has to visit every partition. range keeps \
+                      contiguous keys together, so range queries usually hit one partition \
+                      (range_queries_local_pct near 100%), but sequential inserts or popular ranges pile \
+                      onto one partition (high skew_factor). round_robin ignores the key and deals records \
+                      out in turn: skew_factor stays at 1.0, but no query can be routed to a single \
+                      partition. consistent_hash places partitions on a hash ring: the spread is \
+                      like hash, but reshard moves only the keys the new partition takes over. \
+                      Default: hash."
+                        .into()),
+                    ("virtual_nodes".into(),
+                     "Ring points per partition for the consistent_hash strategy. With one point per \
+                      partition the ring slices are wildly unequal, so load is skewed; more points \
+                      average this out (skew_factor approaches the plain-hash value) at the cost of a \
+                      larger ring to search. Cassandra used 256 vnodes per node for years and now \
+                      defaults to 16 with a smarter token allocator. Default: 64."
+                        .into()),
+                    ("boundaries".into(),
+                     "A list of strictly increasing split points for the range strategy, e.g. \
+                      [1000, 2000, 3000] (a comma-separated string such as '1000, 2000, 3000' also \
+                      works). N boundaries define N + 1 partitions (keys below the first \
+                      boundary, between each pair, and at or above the last), overriding partition_count. \
+                      Boundaries that do not match the key distribution are the classic cause of range \
+                      skew — HBase and CockroachDB split ranges automatically for this reason."
                         .into()),
                 ]),
                 alternatives: vec![
@@ -201,20 +373,66 @@ fn build_outputs() -> Vec<Port> {
     }
 
     fn build_parameters() -> Vec<Parameter> {
-        vec![Parameter {
-            id: "num_partitions".into(),
-            name: "Partitions".into(),
-            param_type: ParameterType::Number,
-            description: "Number of partitions to distribute data across".into(),
-            default_value: ParameterValue::Integer(4),
-            required: false,
-            constraints: Some(
-                ParameterConstraints::new().with_min(2.0).with_max(256.0),
-            ),
-            ui_hint: Some(
-                ParameterUIHint::new(WidgetType::Slider).with_step(1.0),
-            ),
-        }]
+        vec![
+            Parameter {
+                id: "partition_count".into(),
+                name: "Partitions".into(),
+                param_type: ParameterType::Number,
+                description: "Number of partitions to distribute data across".into(),
+                default_value: ParameterValue::Integer(4),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(2.0).with_max(256.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider).with_step(1.0),
+                ),
+            },
+            Parameter {
+                id: "strategy".into(),
+                name: "Strategy".into(),
+                param_type: ParameterType::Enum,
+                description: "Partitioning strategy: hash, range, round_robin, or consistent_hash".into(),
+                default_value: ParameterValue::String("hash".into()),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_choices(STRATEGIES)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+            },
+            Parameter {
+                id: "virtual_nodes".into(),
+                name: "Virtual Nodes".into(),
+                param_type: ParameterType::Number,
+                description: "Ring points per partition for consistent hashing".into(),
+                default_value: ParameterValue::Integer(64),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new().with_min(1.0).with_max(1024.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider).with_step(1.0),
+                ),
+            },
+            Parameter {
+                id: "boundaries".into(),
+                name: "Range Boundaries".into(),
+                param_type: ParameterType::Array,
+                description: "Split points for range partitioning".into(),
+                default_value: ParameterValue::Array(Vec::new()),
+                required: false,
+                constraints: Some(boundaries_constraints()),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+            Parameter {
+                id: "partition_key".into(),
+                name: "Partition Key".into(),
+                param_type: ParameterType::String,
+                description: "Record column hashed to choose the partition".into(),
+                default_value: ParameterValue::String("_key".into()),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_length_range(Some(1), None)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+            },
+        ]
     }
 
     fn build_metrics() -> Vec<MetricDefinition> {
@@ -251,20 +469,146 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "0–100 score of how evenly data is distributed (100 = perfect)".into(),
                 aggregations: vec![AggregationType::Avg],
             },
+            MetricDefinition {
+                id: "skew_factor".into(),
+                name: "Skew Factor".into(),
+                metric_type: MetricType::Gauge,
+                unit: "ratio".into(),
+                description: "Largest partition size divided by the average partition size (1.0 = even)".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "range_queries".into(),
+                name: "Range Queries".into(),
+                metric_type: MetricType::Counter,
+                unit: "queries".into(),
+                description: "Range queries checked for partition locality".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "range_queries_local_pct".into(),
+                name: "Local Range Queries".into(),
+                metric_type: MetricType::Gauge,
+                unit: "%".into(),
+                description: "Percentage of range queries served by a single partition".into(),
+                aggregations: vec![AggregationType::Avg],
+            },
+            MetricDefinition {
+                id: "keys_moved".into(),
+                name: "Keys Moved".into(),
+                metric_type: MetricType::Counter,
+                unit: "keys".into(),
+                description: "Distinct keys reassigned to a different partition by resharding".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
         ]
     }
 
     // -- Core operations -----------------------------------------------------
 
     fn hash_key(&self, key: u64) -> usize {
-        // Simple but effective hash mixing (similar to Murmur3 finalizer).
-        let mut h = key;
-        h ^= h >> 33;
-        h = h.wrapping_mul(0xff51afd7ed558ccd);
-        h ^= h >> 33;
-        h = h.wrapping_mul(0xc4ceb9fe1a85ec53);
-        h ^= h >> 33;
-        (h as usize) % self.num_partitions
+        let h = mix64(key);
+        if self.strategy != PartitionStrategy::ConsistentHash || self.ring.is_empty() {
+            return (h as usize) % self.partition_count;
+        }
+        let idx = self.ring.partition_point(|&(point, _)| point < h);
+        // Past the last point wraps around to the start of the ring.
+        self.ring.get(idx).unwrap_or(&self.ring[0]).1
+    }
+
+    fn build_ring(&mut self) {
+        self.ring = (0..self.partition_count)
+            .flat_map(|p| {
+                (0..self.virtual_nodes).map(move |v| {
+                    let point = mix64((((p as u64) << 32) | v as u64) ^ 0x9e3779b97f4a7c15);
+                    (point, p)
+                })
+            })
+            .collect();
+        self.ring.sort_unstable();
+    }
+
+    /// Changes the number of partitions and reassigns every key seen so far,
+    /// returning how many distinct keys ended up in a different partition.
+    pub fn reshard(&mut self, new_partition_count: usize) -> Result<usize, BlockError> {
+        if !matches!(self.strategy, PartitionStrategy::Hash | PartitionStrategy::ConsistentHash) {
+            return Err(BlockError::InvalidParameter(
+                "reshard requires the hash or consistent_hash strategy".into(),
+            ));
+        }
+        if new_partition_count < 2 {
+            return Err(BlockError::InvalidParameter("partition_count must be at least 2".into()));
+        }
+
+        let old: HashMap<u64, usize> =
+            self.key_counts.keys().map(|&k| (k, self.hash_key(k))).collect();
+
+        self.partition_count = new_partition_count;
+        if self.strategy == PartitionStrategy::ConsistentHash {
+            self.build_ring();
+        }
+
+        let mut moved = 0;
+        self.partition_counts = vec![0; new_partition_count];
+        for (&key, &count) in &self.key_counts {
+            let partition = self.hash_key(key);
+            if partition != old[&key] {
+                moved += 1;
+            }
+            self.partition_counts[partition] += count;
+        }
+        self.keys_moved += moved;
+        Ok(moved)
+    }
+
+    fn range_partition(&self, key: f64) -> usize {
+        self.boundaries.partition_point(|&b| b <= key)
+    }
+
+    /// Chooses the partition for a record's key value.
+    fn assign(&mut self, key: Option<&JsonValue>) -> usize {
+        match self.strategy {
+            PartitionStrategy::Hash | PartitionStrategy::ConsistentHash => {
+                let key = key.map(key_to_u64).unwrap_or(0);
+                *self.key_counts.entry(key).or_insert(0) += 1;
+                self.hash_key(key)
+            }
+            PartitionStrategy::Range => {
+                self.range_partition(key.and_then(JsonValue::as_f64).unwrap_or(0.0))
+            }
+            PartitionStrategy::RoundRobin => {
+                let partition = self.next_round_robin;
+                self.next_round_robin = (self.next_round_robin + 1) % self.partition_count;
+                partition
+            }
+        }
+    }
+
+    /// Whether every key in `[start, end]` lives in a single partition, i.e.
+    /// the range query can be answered without scatter-gather.
+    pub fn range_query_is_local(&self, start: u64, end: u64) -> bool {
+        if start > end {
+            return true;
+        }
+        match self.strategy {
+            PartitionStrategy::Range => {
+                self.range_partition(start as f64) == self.range_partition(end as f64)
+            }
+            PartitionStrategy::Hash | PartitionStrategy::ConsistentHash => {
+                // Exits at the first key that hashes elsewhere, which for any
+                // reasonable hash is within a handful of keys.
+                let first = self.hash_key(start);
+                (start..=end).all(|k| self.hash_key(k) == first)
+            }
+            PartitionStrategy::RoundRobin => false,
+        }
+    }
+
+    fn range_queries_local_pct(&self) -> f64 {
+        if self.range_queries == 0 {
+            return 0.0;
+        }
+        self.range_queries_local as f64 / self.range_queries as f64 * 100.0
     }
 
     fn hottest_partition_pct(&self) -> f64 {
@@ -275,11 +619,22 @@ fn hottest_partition_pct(&self) -> f64 {
         (max as f64 / self.records_partitioned as f64) * 100.0
     }
 
+    /// Largest partition relative to the average partition. Ranges from 1.0
+    /// (perfectly even) to `partition_count` (everything in one partition).
+    pub fn skew_factor(&self) -> f64 {
+        if self.records_partitioned == 0 {
+            return 1.0;
+        }
+        let max = *self.partition_counts.iter().max().unwrap_or(&0);
+        let avg = self.records_partitioned as f64 / self.partition_count as f64;
+        max as f64 / avg
+    }
+
     fn evenness_score(&self) -> f64 {
-        if self.records_partitioned == 0 || self.num_partitions == 0 {
+        if self.records_partitioned == 0 || self.partition_count == 0 {
             return 100.0;
         }
-        let ideal = self.records_partitioned as f64 / self.num_partitions as f64;
+        let ideal = self.records_partitioned as f64 / self.partition_count as f64;
         let total_deviation: f64 = self
             .partition_counts
             .iter()
@@ -312,17 +667,45 @@ fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
 
     async fn initialize(
         &mut self,
-        params: HashMap<String, ParameterValue>,
+        mut params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("num_partitions") {
-            self.num_partitions = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("num_partitions must be an integer".into()))?
-                as usize;
-            if self.num_partitions < 2 {
-                return Err(BlockError::InvalidParameter("num_partitions must be at least 2".into()));
+        // `num_partitions` is the parameter's former name.
+        if let Some(val) = params.remove("num_partitions") {
+            params.entry("partition_count".into()).or_insert(val);
+        }
+        self.params_validate(&params)?;
+        if let Some(n) = params.get("partition_count").and_then(ParameterValue::as_integer) {
+            self.partition_count = n as usize;
+            self.partition_counts = vec![0; self.partition_count];
+        }
+        if let Some(key) = params.get("partition_key").and_then(ParameterValue::as_string) {
+            self.partition_key = key.to_string();
+        }
+        if let Some(s) = enum_param(&params, "strategy", STRATEGIES)? {
+            self.strategy = match s {
+                "hash" => PartitionStrategy::Hash,
+                "range" => PartitionStrategy::Range,
+                "round_robin" => PartitionStrategy::RoundRobin,
+                _ => PartitionStrategy::ConsistentHash,
+            };
+        }
+        if let Some(items) = list_param(&params, "boundaries", &boundaries_constraints())? {
+            self.boundaries = parse_boundaries(&items)?;
+        }
+        if self.strategy == PartitionStrategy::Range {
+            if self.boundaries.is_empty() {
+                return Err(BlockError::InvalidParameter(
+                    "boundaries must be set for the range strategy".into(),
+                ));
             }
-            self.partition_counts = vec![0; self.num_partitions];
+            self.partition_count = self.boundaries.len() + 1;
+            self.partition_counts = vec![0; self.partition_count];
+        }
+        if let Some(n) = params.get("virtual_nodes").and_then(ParameterValue::as_integer) {
+            self.virtual_nodes = n as usize;
+        }
+        if self.strategy == PartitionStrategy::ConsistentHash {
+            self.build_ring();
         }
         Ok(())
     }
@@ -342,10 +725,30 @@ async fn execute(
         };
 
         let mut output_records = Vec::with_capacity(records.len());
+        let mut errors = Vec::new();
 
         for record in records {
-            let key = record.get::<u64>("_key").ok().flatten().unwrap_or(0);
-            let partition = self.hash_key(key);
+            if record.get::<String>("_op").ok().flatten().as_deref() == Some("range_query") {
+                let start = record.get::<u64>("_start").ok().flatten().unwrap_or(0);
+                let end = record.get::<u64>("_end").ok().flatten().unwrap_or(start);
+                self.range_queries += 1;
+                if self.range_query_is_local(start, end) {
+                    self.range_queries_local += 1;
+                }
+                context.metrics.increment("range_queries");
+                continue;
+            }
+            if record.get::<String>("_op").ok().flatten().as_deref() == Some("reshard") {
+                let new_count = record.get::<usize>("_partition_count").ok().flatten().unwrap_or(0);
+                match self.reshard(new_count) {
+                    Ok(moved) => context.metrics.record("keys_moved", moved as f64),
+                    Err(e) => errors.push(e),
+                }
+                continue;
+            }
+
+            let key = record.data.get(&self.partition_key).cloned();
+            let partition = self.assign(key.as_ref());
 
             self.partition_counts[partition] += 1;
             self.records_partitioned += 1;
@@ -362,6 +765,10 @@ async fn execute(
         context.metrics.record("partitions_used", partitions_used as f64);
         context.metrics.record("hottest_partition_pct", self.hottest_partition_pct());
         context.metrics.record("evenness_score", self.evenness_score());
+        context.metrics.record("skew_factor", self.skew_factor());
+        if self.range_queries > 0 {
+            context.metrics.record("range_queries_local_pct", self.range_queries_local_pct());
+        }
 
         let mut outputs = HashMap::new();
         outputs.insert("partitioned".into(), PortValue::Stream(output_records));
@@ -371,11 +778,15 @@ async fn execute(
         metrics_summary.insert("partitions_used".into(), partitions_used as f64);
         metrics_summary.insert("hottest_partition_pct".into(), self.hottest_partition_pct());
         metrics_summary.insert("evenness_score".into(), self.evenness_score());
+        metrics_summary.insert("skew_factor".into(), self.skew_factor());
+        metrics_summary.insert("range_queries".into(), self.range_queries as f64);
+        metrics_summary.insert("range_queries_local_pct".into(), self.range_queries_local_pct());
+        metrics_summary.insert("keys_moved".into(), self.keys_moved as f64);
 
         Ok(ExecutionResult {
             outputs,
             metrics: metrics_summary,
-            errors: vec![],
+            errors,
         })
     }
 
@@ -393,13 +804,21 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
 
     fn get_state(&self) -> BlockState {
         let mut state = BlockState::new();
-        let _ = state.insert("num_partitions".into(), self.num_partitions);
+        let _ = state.insert("partition_count".into(), self.partition_count);
+        let _ = state.insert("partition_key".into(), self.partition_key.clone());
         let _ = state.insert("records_partitioned".into(), self.records_partitioned);
         state
     }
 
     fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
-        if let Ok(Some(n)) = state.get::<usize>("num_partitions") { self.num_partitions = n; }
+        if let Ok(Some(n)) = state.get::<usize>("partition_count") {
+            self.partition_count = n;
+            self.partition_counts = vec![0; n];
+        }
+        if let Ok(Some(k)) = state.get::<String>("partition_key") { self.partition_key = k; }
+        if self.strategy == PartitionStrategy::ConsistentHash {
+            self.build_ring();
+        }
         Ok(())
     }
 }
@@ -411,11 +830,32 @@ fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+    use crate::core::port::Record;
+
+    async fn run(part: &mut HashPartitionerBlock, records: Vec<Record>) -> ExecutionResult {
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        part.execute(ctx).await.unwrap()
+    }
+
+    fn record_with(field: &str, value: JsonValue) -> Record {
+        let mut r = Record::new();
+        r.data.insert(field.into(), value);
+        r
+    }
 
     #[test]
     fn test_deterministic_partitioning() {
         let mut part = HashPartitionerBlock::new();
-        part.num_partitions = 4;
+        part.partition_count = 4;
         part.partition_counts = vec![0; 4];
 
         let p1 = part.hash_key(42);
@@ -427,7 +867,7 @@ fn test_deterministic_partitioning() {
     #[test]
     fn test_distribution_evenness() {
         let mut part = HashPartitionerBlock::new();
-        part.num_partitions = 4;
+        part.partition_count = 4;
         part.partition_counts = vec![0; 4];
 
         // Distribute 1000 sequential keys.
@@ -446,6 +886,220 @@ fn test_distribution_evenness() {
         assert!(part.evenness_score() > 80.0, "Distribution should be reasonably even");
     }
 
+    #[tokio::test]
+    async fn test_constant_key_reports_max_skew() {
+        let mut part = HashPartitionerBlock::new();
+        let mut params = HashMap::new();
+        params.insert("partition_count".into(), ParameterValue::Integer(8));
+        part.initialize(params).await.unwrap();
+
+        let records = (0..100).map(|_| record_with("_key", JsonValue::from(7))).collect();
+        let result = run(&mut part, records).await;
+
+        // Every record lands in one partition: max / avg = 100 / (100 / 8) = 8.
+        assert_eq!(result.metrics["skew_factor"], 8.0);
+        assert_eq!(result.metrics["partitions_used"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_partition_key_and_ids() {
+        let mut part = HashPartitionerBlock::new();
+        let mut params = HashMap::new();
+        params.insert("partition_count".into(), ParameterValue::Integer(4));
+        params.insert("partition_key".into(), ParameterValue::String("user".into()));
+        part.initialize(params).await.unwrap();
+
+        let records = (0..1000)
+            .map(|i| record_with("user", JsonValue::from(format!("user-{}", i))))
+            .collect();
+        let result = run(&mut part, records).await;
+        let Some(PortValue::Stream(out)) = result.outputs.get("partitioned") else {
+            panic!("expected stream");
+        };
+        assert!(out.iter().all(|r| r.get::<usize>("_partition_id").unwrap().unwrap() < 4));
+        // A high-cardinality string key spreads evenly.
+        assert!(result.metrics["skew_factor"] < 1.3, "skew {}", result.metrics["skew_factor"]);
+    }
+
+    fn range_query(start: u64, end: u64) -> Record {
+        let mut r = Record::new();
+        let _ = r.insert("_op".into(), "range_query");
+        let _ = r.insert("_start".into(), start);
+        let _ = r.insert("_end".into(), end);
+        r
+    }
+
+    /// 1000 sequential keys followed by 20 range queries of width 10.
+    fn sequential_workload() -> Vec<Record> {
+        let mut records: Vec<Record> =
+            (0..1000u64).map(|i| record_with("_key", JsonValue::from(i))).collect();
+        records.extend((0..20u64).map(|i| range_query(i * 50, i * 50 + 9)));
+        records
+    }
+
+    async fn run_strategy(params: HashMap<String, ParameterValue>) -> ExecutionResult {
+        let mut part = HashPartitionerBlock::new();
+        part.initialize(params).await.unwrap();
+        run(&mut part, sequential_workload()).await
+    }
+
+    #[tokio::test]
+    async fn test_strategies_trade_locality_for_balance() {
+        let mut range_params = HashMap::new();
+        range_params.insert("strategy".into(), ParameterValue::String("range".into()));
+        range_params.insert("boundaries".into(), ParameterValue::String("250, 500, 750".into()));
+        let range = run_strategy(range_params).await;
+
+        let mut rr_params = HashMap::new();
+        rr_params.insert("strategy".into(), ParameterValue::String("round_robin".into()));
+        let rr = run_strategy(rr_params).await;
+
+        let hash = run_strategy(HashMap::new()).await;
+
+        // Range: every width-10 query starting at a multiple of 50 avoids the
+        // boundaries, so all are local.
+        assert_eq!(range.metrics["range_queries_local_pct"], 100.0);
+        assert_eq!(range.metrics["skew_factor"], 1.0);
+        // Round-robin: perfectly even, never local.
+        assert_eq!(rr.metrics["skew_factor"], 1.0);
+        assert_eq!(rr.metrics["range_queries_local_pct"], 0.0);
+        // Hash scatters ranges too.
+        assert_eq!(hash.metrics["range_queries_local_pct"], 0.0);
+        assert_eq!(hash.metrics["range_queries"], 20.0);
+        assert_eq!(hash.metrics["records_partitioned"], 1000.0);
+    }
+
+    #[tokio::test]
+    async fn test_range_partitioning_skews_with_bad_boundaries() {
+        let mut part = HashPartitionerBlock::new();
+        let mut params = HashMap::new();
+        params.insert("strategy".into(), ParameterValue::String("range".into()));
+        let boundaries = [10, 20, 30].into_iter().map(ParameterValue::Integer).collect();
+        params.insert("boundaries".into(), ParameterValue::Array(boundaries));
+        part.initialize(params).await.unwrap();
+        assert_eq!(part.partition_count, 4);
+
+        let result = run(&mut part, sequential_workload()).await;
+        // Keys 30..1000 all land in the last partition: 970 / 250 = 3.88.
+        assert!((result.metrics["skew_factor"] - 3.88).abs() < 1e-9);
+        let Some(PortValue::Stream(out)) = result.outputs.get("partitioned") else {
+            panic!("expected stream");
+        };
+        let ids: Vec<usize> = out.iter().map(|r| r.get("_partition_id").unwrap().unwrap()).collect();
+        assert_eq!(&ids[9..12], &[0, 1, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_strategy_parameter_validation() {
+        let mut part = HashPartitionerBlock::new();
+        let mut params = HashMap::new();
+        params.insert("strategy".into(), ParameterValue::String("range".into()));
+        assert!(part.initialize(params.clone()).await.is_err(), "range needs boundaries");
+
+        params.insert("boundaries".into(), ParameterValue::String("5, 3".into()));
+        assert!(part.initialize(params.clone()).await.is_err(), "boundaries must be sorted");
+
+        let mixed = vec![ParameterValue::Integer(5), ParameterValue::from("ten")];
+        params.insert("boundaries".into(), ParameterValue::Array(mixed));
+        let err = part.initialize(params.clone()).await.unwrap_err();
+        assert!(err.to_string().contains("only numbers, got 'ten'"), "{}", err);
+
+        params.insert("boundaries".into(), ParameterValue::Array(Vec::new()));
+        assert!(part.initialize(params).await.is_err(), "range needs non-empty boundaries");
+
+        let mut bad = HashMap::new();
+        bad.insert("strategy".into(), ParameterValue::String("random".into()));
+        assert!(HashPartitionerBlock::new().initialize(bad).await.is_err());
+    }
+
+    /// Partitions 10,000 distinct keys into 4 partitions, reshards to 5, and
+    /// returns the fraction of keys that moved.
+    async fn fraction_moved(strategy: &str) -> f64 {
+        let mut part = HashPartitionerBlock::new();
+        let mut params = HashMap::new();
+        params.insert("strategy".into(), ParameterValue::String(strategy.into()));
+        params.insert("partition_count".into(), ParameterValue::Integer(4));
+        part.initialize(params).await.unwrap();
+
+        let records = (0..10_000u64).map(|i| record_with("_key", JsonValue::from(i))).collect();
+        run(&mut part, records).await;
+        let moved = part.reshard(5).unwrap();
+        assert_eq!(part.partition_counts.iter().sum::<usize>(), 10_000);
+        moved as f64 / 10_000.0
+    }
+
+    #[tokio::test]
+    async fn test_consistent_hashing_moves_few_keys() {
+        let modulo = fraction_moved("hash").await;
+        let consistent = fraction_moved("consistent_hash").await;
+        // hash % N: a key stays only if h % 4 == h % 5, so ~4/5 of keys move.
+        assert!(modulo > 0.7, "modulo moved {}", modulo);
+        // Consistent hashing: only the new partition's share, ~1/5.
+        assert!(consistent > 0.1 && consistent < 0.3, "consistent moved {}", consistent);
+    }
+
+    #[tokio::test]
+    async fn test_virtual_nodes_reduce_skew() {
+        let mut skews = Vec::new();
+        for vnodes in [1, 256] {
+            let mut part = HashPartitionerBlock::new();
+            let mut params = HashMap::new();
+            params.insert("strategy".into(), ParameterValue::String("consistent_hash".into()));
+            params.insert("partition_count".into(), ParameterValue::Integer(8));
+            params.insert("virtual_nodes".into(), ParameterValue::Integer(vnodes));
+            part.initialize(params).await.unwrap();
+            let records = (0..10_000u64).map(|i| record_with("_key", JsonValue::from(i))).collect();
+            skews.push(run(&mut part, records).await.metrics["skew_factor"]);
+        }
+        assert!(skews[1] < skews[0], "skews {:?}", skews);
+        assert!(skews[1] < 1.3, "skews {:?}", skews);
+    }
+
+    #[tokio::test]
+    async fn test_reshard_via_execute() {
+        let mut part = HashPartitionerBlock::new();
+        let mut params = HashMap::new();
+        params.insert("strategy".into(), ParameterValue::String("consistent_hash".into()));
+        part.initialize(params).await.unwrap();
+
+        let mut records: Vec<Record> =
+            (0..100u64).map(|i| record_with("_key", JsonValue::from(i))).collect();
+        let mut reshard = Record::new();
+        let _ = reshard.insert("_op".into(), "reshard");
+        let _ = reshard.insert("_partition_count".into(), 6usize);
+        records.push(reshard);
+        let result = run(&mut part, records).await;
+
+        assert!(result.errors.is_empty());
+        assert_eq!(part.partition_count, 6);
+        assert!(result.metrics["keys_moved"] > 0.0);
+        assert_eq!(result.metrics["records_partitioned"], 100.0);
+
+        // Range partitions are fixed by their boundaries.
+        let mut range = HashPartitionerBlock::new();
+        let mut params = HashMap::new();
+        params.insert("strategy".into(), ParameterValue::String("range".into()));
+        params.insert("boundaries".into(), ParameterValue::String("10".into()));
+        range.initialize(params).await.unwrap();
+        assert!(range.reshard(3).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_parameter_name() {
+        let mut part = HashPartitionerBlock::new();
+        let mut params = HashMap::new();
+        params.insert("num_partitions".into(), ParameterValue::Integer(16));
+        part.initialize(params).await.unwrap();
+        assert_eq!(part.partition_count, 16);
+        assert_eq!(part.partition_counts.len(), 16);
+
+        // The old name is held to the same declared bounds.
+        let mut params = HashMap::new();
+        params.insert("num_partitions".into(), ParameterValue::Integer(1));
+        let err = part.initialize(params).await.unwrap_err();
+        assert!(err.to_string().contains("partition_count must be between 2 and 256"), "{}", err);
+    }
+
     #[test]
     fn test_metadata() {
         let part = HashPartitionerBlock::new();
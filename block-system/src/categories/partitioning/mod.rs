@@ -2,6 +2,14 @@
 //!
 //! Blocks that distribute data across multiple partitions or shards.
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+
 pub mod hash_partitioner;
 
 pub use hash_partitioner::HashPartitionerBlock;
+
+/// Register a factory for each partitioning block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(HashPartitionerBlock::new()))?;
+    Ok(())
+}
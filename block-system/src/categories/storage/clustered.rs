@@ -4,6 +4,20 @@
 //! clustered index). Records with adjacent key values share the same page,
 //! making range scans on the cluster key very fast.
 //!
+//! ## How it works
+//!
+//! Rows live in a list of pages kept in cluster-key order, each page sorted
+//! internally. An insert binary-searches to the page covering its key. A
+//! page holds at most `page_size × fill_factor` rows: when a full page is the
+//! last one and the key is beyond its end, a fresh page is simply appended;
+//! otherwise the page is **split** in half. Sorted inserts therefore append
+//! without splitting, while random inserts split constantly.
+//!
+//! A **range scan** binary-searches to the page covering the start key and
+//! reads pages sequentially until it passes the end key, so `pages_read` grows
+//! with the size of the result rather than the size of the table. Run one
+//! through `execute` with `_op: "range_scan"` and `_start` / `_end` fields.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
@@ -12,10 +26,12 @@
 //! | `records_stored` | Gauge | Total records stored |
 //! | `inserts_in_order` | Counter | Inserts that fit page order |
 //! | `page_splits` | Counter | Page splits from out-of-order inserts |
+//! | `sortedness_pct` | Gauge | % of inserts that arrived in cluster-key order |
+//! | `pages_read` | Counter | Pages read by range scans |
 
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::HashMap;
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
@@ -27,17 +43,21 @@
     Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
-use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
-
-fn cmp_json(a: &JsonValue, b: &JsonValue) -> std::cmp::Ordering {
-    match (a, b) {
-        (JsonValue::Number(na), JsonValue::Number(nb)) => {
-            let fa = na.as_f64().unwrap_or(0.0);
-            let fb = nb.as_f64().unwrap_or(0.0);
-            fa.partial_cmp(&fb).unwrap_or(std::cmp::Ordering::Equal)
-        }
-        (JsonValue::String(sa), JsonValue::String(sb)) => sa.cmp(sb),
-        _ => a.to_string().cmp(&b.to_string()),
+use crate::core::port::{cmp_json, Port, PortDirection, PortType, PortValue, Record};
+
+/// One page of rows, sorted by cluster key.
+#[derive(Debug, Clone, Default)]
+struct Page {
+    rows: Vec<(JsonValue, JsonValue)>,
+}
+
+impl Page {
+    fn first_key(&self) -> Option<&JsonValue> {
+        self.rows.first().map(|(k, _)| k)
+    }
+
+    fn last_key(&self) -> Option<&JsonValue> {
+        self.rows.last().map(|(k, _)| k)
     }
 }
 
@@ -50,9 +70,13 @@ pub struct ClusteredStorageBlock {
 
     cluster_key: String,
     page_size: usize,
+    fill_factor: f64,
 
-    /// Sorted storage: cluster_key → record data. BTreeMap keeps keys sorted.
-    store: BTreeMap<String, JsonValue>,
+    /// Pages in cluster-key order; rows within each page are sorted too.
+    pages: Vec<Page>,
+    row_count: usize,
+    inserts_total: usize,
+    pages_read: usize,
     page_splits: usize,
     inserts_in_order: usize,
     last_key_value: Option<JsonValue>,
@@ -68,7 +92,11 @@ pub fn new() -> Self {
             metric_defs: Self::build_metrics(),
             cluster_key: "id".into(),
             page_size: 100,
-            store: BTreeMap::new(),
+            fill_factor: 1.0,
+            pages: Vec::new(),
+            row_count: 0,
+            inserts_total: 0,
+            pages_read: 0,
             page_splits: 0,
             inserts_in_order: 0,
             last_key_value: None,
@@ -102,18 +130,20 @@ fn build_metadata() -> BlockMetadata {
                 algorithm: "INSERT:\n  \
                            1. Extract the cluster key value from the record\n  \
                            2. Compare with the last inserted key value\n  \
-                           3. If new key >= last key: in-order insert (append to current position)\n  \
-                           4. If new key < last key: out-of-order insert (page split simulated)\n  \
-                           5. Insert into BTreeMap which maintains sorted order automatically\n  \
-                           6. Update last_key_value for next comparison\n\n\
+                           3. Count the insert as in-order if new key >= last key (sortedness_pct)\n  \
+                           4. Binary-search the pages for the one whose key range covers the key\n  \
+                           5. If that page holds page_size * fill_factor rows:\n     \
+                              - last page and key beyond its end: append a new empty page\n     \
+                              - otherwise: PAGE SPLIT, then pick the half that covers the key\n  \
+                           6. Binary-search within the page and insert (or replace an equal key)\n\n\
                            RANGE SCAN (on cluster key):\n  \
-                           1. Seek to the start key in the sorted structure (O(log n))\n  \
-                           2. Read sequentially until end key is reached\n  \
-                           3. All matching records are physically contiguous — minimal I/O\n\n\
-                           PAGE SPLIT (out-of-order insert):\n  \
-                           1. When a record arrives with a key smaller than existing entries\n  \
-                           2. The page containing that key range must be split\n  \
-                           3. Half the entries move to a new page to make room\n  \
+                           1. Binary-search the pages for the one covering the start key (O(log p))\n  \
+                           2. Read pages sequentially until a page starts past the end key\n  \
+                           3. All matching records are physically contiguous — pages_read ~ result size / page size\n\n\
+                           PAGE SPLIT (insert into a full page):\n  \
+                           1. A key lands inside the range of a page that is already full\n  \
+                           2. The upper half of the page's rows move to a new page right after it\n  \
+                           3. Both pages are left half full — room for future random inserts\n  \
                            4. This is expensive — it requires rewriting two pages"
                     .into(),
                 complexity: Complexity {
@@ -165,6 +195,15 @@ fn build_metadata() -> BlockMetadata {
                       splits but each split is cheaper. Recommended: 100-1000 for most workloads. \
                       Range: 10-10000. Default is 100."
                          .into()),
+                    ("fill_factor".into(),
+                     "Fraction of page_size a page may hold before the next insert into it splits it \
+                      (or, for appends at the end of the table, starts a new page). 1.0 packs pages \
+                      completely, which is ideal for append-only keys; lower values leave headroom but \
+                      use more pages. Compare page_splits and sortedness_pct between a sorted and a \
+                      random key stream: sorted inserts only append pages, random inserts split \
+                      roughly every page_size * fill_factor / 2 rows. SQL Server's FILLFACTOR and \
+                      InnoDB's 15/16 sequential fill play the same role. Range: 0.5-1.0."
+                         .into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -215,12 +254,16 @@ fn build_inputs() -> Vec<Port> {
         vec![Port { id: "records".into(), name: "Records".into(), port_type: PortType::DataStream, direction: PortDirection::Input, required: true, multiple: false, description: "Records to store in clustered order".into(), schema: None }]
     }
     fn build_outputs() -> Vec<Port> {
-        vec![Port { id: "stored".into(), name: "Stored Records".into(), port_type: PortType::DataStream, direction: PortDirection::Output, required: false, multiple: true, description: "Records in clustered order".into(), schema: None }]
+        vec![
+            Port { id: "stored".into(), name: "Stored Records".into(), port_type: PortType::DataStream, direction: PortDirection::Output, required: false, multiple: true, description: "Records in clustered order".into(), schema: None },
+            Port { id: "scan_results".into(), name: "Range Scan Results".into(), port_type: PortType::DataStream, direction: PortDirection::Output, required: false, multiple: true, description: "Rows returned by range_scan requests, in cluster-key order".into(), schema: None },
+        ]
     }
     fn build_parameters() -> Vec<Parameter> {
         vec![
             Parameter { id: "cluster_key".into(), name: "Cluster Key".into(), param_type: ParameterType::String, description: "Column to cluster by".into(), default_value: ParameterValue::String("id".into()), required: true, constraints: None, ui_hint: Some(ParameterUIHint::new(WidgetType::Input)) },
             Parameter { id: "page_size".into(), name: "Page Size".into(), param_type: ParameterType::Number, description: "Records per page".into(), default_value: ParameterValue::Integer(100), required: false, constraints: Some(ParameterConstraints::new().with_min(10.0).with_max(10000.0)), ui_hint: Some(ParameterUIHint::new(WidgetType::Slider).with_step(10.0)) },
+            Parameter { id: "fill_factor".into(), name: "Fill Factor".into(), param_type: ParameterType::Number, description: "Fraction of a page filled before it splits".into(), default_value: ParameterValue::Number(1.0), required: false, constraints: Some(ParameterConstraints::new().with_min(0.5).with_max(1.0)), ui_hint: Some(ParameterUIHint::new(WidgetType::Slider).with_step(0.05)) },
         ]
     }
     fn build_metrics() -> Vec<MetricDefinition> {
@@ -229,12 +272,89 @@ fn build_metrics() -> Vec<MetricDefinition> {
             MetricDefinition { id: "records_stored".into(), name: "Records Stored".into(), metric_type: MetricType::Gauge, unit: "records".into(), description: "Total records".into(), aggregations: vec![AggregationType::Max] },
             MetricDefinition { id: "inserts_in_order".into(), name: "In-Order Inserts".into(), metric_type: MetricType::Counter, unit: "ops".into(), description: "Inserts that maintained order".into(), aggregations: vec![AggregationType::Sum] },
             MetricDefinition { id: "page_splits".into(), name: "Page Splits".into(), metric_type: MetricType::Counter, unit: "ops".into(), description: "Out-of-order page splits".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "pages_read".into(), name: "Pages Read".into(), metric_type: MetricType::Counter, unit: "pages".into(), description: "Pages read by range scans".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "sortedness_pct".into(), name: "Sortedness".into(), metric_type: MetricType::Gauge, unit: "%".into(), description: "Share of inserts that arrived in cluster-key order".into(), aggregations: vec![AggregationType::Avg, AggregationType::Min] },
         ]
     }
 
     fn pages_used(&self) -> usize {
-        if self.store.is_empty() { 0 }
-        else { (self.store.len() + self.page_size - 1) / self.page_size }
+        self.pages.len()
+    }
+
+    /// Rows a page may hold before it has to split.
+    fn page_capacity(&self) -> usize {
+        ((self.page_size as f64 * self.fill_factor) as usize).max(1)
+    }
+
+    fn sortedness_pct(&self) -> f64 {
+        let total = self.inserts_total;
+        if total == 0 { 100.0 } else { self.inserts_in_order as f64 * 100.0 / total as f64 }
+    }
+
+    /// Index of the page whose key range covers `key`.
+    fn find_page(&self, key: &JsonValue) -> usize {
+        let after = self.pages.partition_point(|p| {
+            p.first_key().is_some_and(|k| cmp_json(k, key) != std::cmp::Ordering::Greater)
+        });
+        after.saturating_sub(1)
+    }
+
+    /// Return the rows with `start <= key <= end`, reading only the
+    /// contiguous pages that cover the range.
+    pub fn range_scan(&mut self, start: &JsonValue, end: &JsonValue) -> Vec<Record> {
+        use std::cmp::Ordering;
+        let mut results = Vec::new();
+        if self.pages.is_empty() || cmp_json(start, end) == Ordering::Greater {
+            return results;
+        }
+        for page in &self.pages[self.find_page(start)..] {
+            if page.first_key().is_some_and(|k| cmp_json(k, end) == Ordering::Greater) {
+                break;
+            }
+            self.pages_read += 1;
+            for (key, data) in &page.rows {
+                if cmp_json(key, start) != Ordering::Less && cmp_json(key, end) != Ordering::Greater {
+                    let row = data.as_object().map(|m| m.clone().into_iter().collect()).unwrap_or_default();
+                    results.push(Record::from_map(row));
+                }
+            }
+        }
+        results
+    }
+
+    /// Insert a row in cluster-key order, splitting its page if full.
+    /// An existing row with an equal key is replaced.
+    fn insert(&mut self, key: JsonValue, data: JsonValue) {
+        if self.pages.is_empty() {
+            self.pages.push(Page::default());
+        }
+        let capacity = self.page_capacity();
+        let mut idx = self.find_page(&key);
+        let page = &self.pages[idx];
+        let pos = page.rows.binary_search_by(|(k, _)| cmp_json(k, &key));
+        if let Ok(pos) = pos {
+            self.pages[idx].rows[pos].1 = data;
+            return;
+        }
+        if page.rows.len() >= capacity {
+            let past_end = page.last_key().is_none_or(|k| cmp_json(&key, k) == std::cmp::Ordering::Greater);
+            if idx == self.pages.len() - 1 && past_end {
+                // Appending at the end of the table: just start a new page.
+                self.pages.push(Page::default());
+                idx += 1;
+            } else {
+                let upper = self.pages[idx].rows.split_off(capacity / 2);
+                self.pages.insert(idx + 1, Page { rows: upper });
+                self.page_splits += 1;
+                if self.pages[idx + 1].first_key().is_some_and(|k| cmp_json(k, &key) == std::cmp::Ordering::Less) {
+                    idx += 1;
+                }
+            }
+        }
+        let rows = &mut self.pages[idx].rows;
+        let pos = rows.binary_search_by(|(k, _)| cmp_json(k, &key)).unwrap_err();
+        rows.insert(pos, (key, data));
+        self.row_count += 1;
     }
 }
 
@@ -254,8 +374,10 @@ fn guarantees(&self) -> &[Guarantee] {
     fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
 
     async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
         if let Some(v) = params.get("cluster_key") { if let Some(s) = v.as_string() { self.cluster_key = s.to_string(); } }
         if let Some(v) = params.get("page_size") { self.page_size = v.as_integer().unwrap_or(100) as usize; }
+        if let Some(v) = params.get("fill_factor") { if let Some(ff) = v.as_number() { self.fill_factor = ff; } }
         Ok(())
     }
 
@@ -266,28 +388,42 @@ async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult
             _ => return Err(BlockError::InvalidInput("Expected DataStream".into())),
         };
 
-        for record in &records {
+        let mut scan_results = Vec::new();
+        let mut stored = Vec::with_capacity(records.len());
+        for record in records {
+            if record.get::<String>("_op").ok().flatten().as_deref() == Some("range_scan") {
+                let start = record.data.get("_start").cloned().unwrap_or(JsonValue::Null);
+                let end = record.data.get("_end").cloned().unwrap_or(JsonValue::Null);
+                scan_results.extend(self.range_scan(&start, &end));
+                continue;
+            }
             let key_value = record.data.get(&self.cluster_key).cloned().unwrap_or(JsonValue::Null);
-            let key_str = key_value.to_string();
-            let in_order = self.last_key_value.as_ref().map_or(true, |lk| {
+            let in_order = self.last_key_value.as_ref().is_none_or(|lk| {
                 cmp_json(&key_value, lk) != std::cmp::Ordering::Less
             });
-            if in_order { self.inserts_in_order += 1; } else { self.page_splits += 1; }
-            self.last_key_value = Some(key_value);
-            self.store.insert(key_str, serde_json::to_value(&record.data).unwrap_or(JsonValue::Null));
+            if in_order { self.inserts_in_order += 1; }
+            self.inserts_total += 1;
+            self.last_key_value = Some(key_value.clone());
+            self.insert(key_value, serde_json::to_value(&record.data).unwrap_or(JsonValue::Null));
+            stored.push(record);
         }
 
         context.metrics.record("pages_used", self.pages_used() as f64);
-        context.metrics.record("records_stored", self.store.len() as f64);
+        context.metrics.record("records_stored", self.row_count as f64);
         context.metrics.record("inserts_in_order", self.inserts_in_order as f64);
         context.metrics.record("page_splits", self.page_splits as f64);
+        context.metrics.record("sortedness_pct", self.sortedness_pct());
+        context.metrics.record("pages_read", self.pages_read as f64);
 
         let mut outputs = HashMap::new();
-        outputs.insert("stored".into(), PortValue::Stream(records));
+        outputs.insert("stored".into(), PortValue::Stream(stored));
+        outputs.insert("scan_results".into(), PortValue::Stream(scan_results));
         let mut ms = HashMap::new();
-        ms.insert("records_stored".into(), self.store.len() as f64);
+        ms.insert("records_stored".into(), self.row_count as f64);
         ms.insert("pages_used".into(), self.pages_used() as f64);
         ms.insert("page_splits".into(), self.page_splits as f64);
+        ms.insert("sortedness_pct".into(), self.sortedness_pct());
+        ms.insert("pages_read".into(), self.pages_read as f64);
 
         Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
     }
@@ -296,7 +432,7 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
         if inputs.get("records").is_none() { ValidationResult::ok().with_warning("records input not connected") }
         else { ValidationResult::ok() }
     }
-    fn get_state(&self) -> BlockState { let mut s = BlockState::new(); let _ = s.insert("records".into(), self.store.len()); s }
+    fn get_state(&self) -> BlockState { let mut s = BlockState::new(); let _ = s.insert("records".into(), self.row_count); s }
     fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
 }
 
@@ -323,6 +459,7 @@ async fn test_out_of_order_inserts() {
         use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
         let mut cs = ClusteredStorageBlock::new();
         cs.cluster_key = "id".into();
+        cs.page_size = 2;
         let records: Vec<Record> = [5, 3, 8, 1].iter().map(|&i| { let mut r = Record::new(); r.insert("id".into(), i as i64).unwrap(); r }).collect();
         let mut inputs = HashMap::new();
         inputs.insert("records".into(), PortValue::Stream(records));
@@ -331,6 +468,123 @@ async fn test_out_of_order_inserts() {
         assert!(cs.page_splits > 0, "Out-of-order inserts should cause splits");
     }
 
+    /// Insert `keys` with `page_size` 10 and return the block.
+    fn insert_keys(keys: &[i64]) -> ClusteredStorageBlock {
+        let mut cs = ClusteredStorageBlock::new();
+        cs.page_size = 10;
+        for &k in keys {
+            cs.inserts_total += 1;
+            cs.insert(JsonValue::from(k), JsonValue::Null);
+        }
+        cs
+    }
+
+    /// Deterministic shuffle of 0..n.
+    fn shuffled(n: i64) -> Vec<i64> {
+        let mut keys: Vec<i64> = (0..n).collect();
+        let mut x: u64 = 7;
+        for i in (1..keys.len()).rev() {
+            x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            keys.swap(i, (x >> 33) as usize % (i + 1));
+        }
+        keys
+    }
+
+    #[test]
+    fn test_sorted_vs_random_page_splits() {
+        let sorted = insert_keys(&(0..1000).collect::<Vec<_>>());
+        assert_eq!(sorted.page_splits, 0);
+        assert_eq!(sorted.pages_used(), 100);
+
+        let random = insert_keys(&shuffled(1000));
+        assert!(random.page_splits > 100, "random inserts split often, got {}", random.page_splits);
+        // Split pages are left partly empty.
+        assert!(random.pages_used() > sorted.pages_used());
+
+        // Pages stay sorted internally and across page boundaries.
+        let keys: Vec<i64> = random.pages.iter().flat_map(|p| p.rows.iter().map(|(k, _)| k.as_i64().unwrap())).collect();
+        assert_eq!(keys, (0..1000).collect::<Vec<_>>());
+        assert!(random.pages.iter().all(|p| p.rows.len() <= 10));
+    }
+
+    #[tokio::test]
+    async fn test_sortedness_and_fill_factor() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        let mut cs = ClusteredStorageBlock::new();
+        let mut params = HashMap::new();
+        params.insert("fill_factor".into(), ParameterValue::Number(0.5));
+        cs.initialize(params).await.unwrap();
+        assert_eq!(cs.page_capacity(), 50);
+
+        let records: Vec<Record> = [1, 2, 3, 0].iter().map(|&i| { let mut r = Record::new(); r.insert("id".into(), i as i64).unwrap(); r }).collect();
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        let result = cs.execute(ctx).await.unwrap();
+        assert_eq!(*result.metrics.get("sortedness_pct").unwrap(), 75.0);
+
+        let mut bad = HashMap::new();
+        bad.insert("fill_factor".into(), ParameterValue::Number(0.1));
+        assert!(cs.initialize(bad).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_range_scan_reads_only_covering_pages() {
+        use crate::categories::storage::heap_file::HeapFileBlock;
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let rows: Vec<Record> = shuffled(1000).into_iter().map(|i| {
+            let mut r = Record::new();
+            r.insert("id".into(), i).unwrap();
+            r.insert("payload".into(), format!("row-{:04}", i)).unwrap();
+            r
+        }).collect();
+
+        let mut cs = ClusteredStorageBlock::new();
+        cs.page_size = 10;
+        let mut scan = Record::new();
+        scan.insert("_op".into(), "range_scan").unwrap();
+        scan.insert("_start".into(), 100).unwrap();
+        scan.insert("_end".into(), 149).unwrap();
+        let mut records = rows.clone();
+        records.push(scan);
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        let result = cs.execute(ctx).await.unwrap();
+
+        let found = result.outputs.get("scan_results").unwrap();
+        assert_eq!(found.len(), 50);
+        let clustered_pages = *result.metrics.get("pages_read").unwrap() as usize;
+        // Pages after random inserts are 5-10 rows full: the range spans at most ~11.
+        assert!(clustered_pages <= 12, "read {} pages", clustered_pages);
+
+        // The heap file has no order to exploit: the same range needs a full scan.
+        let mut heap = HeapFileBlock::new();
+        let mut params = HashMap::new();
+        params.insert("page_size".into(), ParameterValue::Integer(512));
+        heap.initialize(params).await.unwrap();
+        for r in rows {
+            heap.insert(r);
+        }
+        let heap_pages = heap.page_count();
+        let in_range = heap.scan().into_iter().filter(|(_, r)| {
+            r.get::<i64>("id").unwrap().is_some_and(|id| (100..=149).contains(&id))
+        }).count();
+        assert_eq!(in_range, 50);
+        assert!(heap_pages > 5 * clustered_pages, "heap {} vs clustered {}", heap_pages, clustered_pages);
+    }
+
+    #[test]
+    fn test_range_scan_edges() {
+        let mut cs = insert_keys(&(0..100).collect::<Vec<_>>());
+        assert_eq!(cs.range_scan(&JsonValue::from(5), &JsonValue::from(4)).len(), 0);
+        assert_eq!(cs.range_scan(&JsonValue::from(-10), &JsonValue::from(0)).len(), 1);
+        assert_eq!(cs.range_scan(&JsonValue::from(95), &JsonValue::from(500)).len(), 5);
+        // One page each for the last two scans.
+        assert_eq!(cs.pages_read, 2);
+    }
+
     #[test]
     fn test_metadata() {
         let cs = ClusteredStorageBlock::new();
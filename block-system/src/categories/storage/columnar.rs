@@ -4,6 +4,17 @@
 //! column is stored as a separate contiguous array, which is ideal for
 //! analytical queries that read only a few columns from many rows.
 //!
+//! ## How it works
+//!
+//! Incoming rows are split into one value array per column. Each `execute`
+//! then scans the `projection` columns (all columns when empty) and
+//! reconstructs rows from them; columns outside the projection are never
+//! touched, so `bytes_read` grows with the projected columns only.
+//!
+//! Every column is also sized under the configured `encoding` — `none`,
+//! `rle` (runs of equal values) or `delta` (first-order differences of an
+//! integer column) — and `compression_ratio` compares raw to encoded bytes.
+//!
 //! ## Metrics tracked
 //!
 //! | Metric | Type | Description |
@@ -11,11 +22,14 @@
 //! | `columns_stored` | Gauge | Number of distinct columns |
 //! | `rows_stored` | Gauge | Total rows stored |
 //! | `columns_read` | Counter | Column reads (projections) |
-//! | `compression_ratio` | Gauge | Simulated compression ratio |
+//! | `columns_scanned` | Gauge | Columns read by the last scan |
+//! | `columns_skipped` | Gauge | Stored columns the last scan did not read |
+//! | `bytes_read` | Counter | Bytes of column data read by scans |
+//! | `compression_ratio` | Gauge | Raw bytes / encoded bytes across all columns |
 
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
@@ -24,10 +38,32 @@
 use crate::core::constraint::{Constraint, Guarantee, GuaranteeType};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterType, ParameterUIHint, ParameterValue, ValidationResult, WidgetType,
+    enum_param, list_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint,
+    ParameterValue, ValidationResult, WidgetType,
 };
 use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
 
+/// How column values are encoded on disk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Encoding {
+    None,
+    Rle,
+    Delta,
+}
+
+/// Values accepted by the `encoding` parameter.
+const ENCODINGS: &[&str] = &["none", "rle", "delta"];
+
+/// Bytes a LEB128 varint needs for `n`.
+fn varint_len(mut n: u64) -> usize {
+    let mut len = 1;
+    while n >= 0x80 {
+        n >>= 7;
+        len += 1;
+    }
+    len
+}
+
 /// A single column stored as a contiguous vector of JSON values.
 #[derive(Debug, Clone)]
 struct Column {
@@ -40,17 +76,45 @@ fn new(name: String) -> Self {
         Self { name, values: Vec::new() }
     }
 
-    /// Estimate compression ratio based on value repetition.
-    /// Columnar stores compress well when there's low cardinality.
-    fn compression_ratio(&self) -> f64 {
-        if self.values.is_empty() { return 1.0; }
-        let mut unique = self.values.clone();
-        unique.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
-        unique.dedup_by(|a, b| a.to_string() == b.to_string());
-        let cardinality = unique.len() as f64;
-        let total = self.values.len() as f64;
-        // Higher ratio = better compression (more duplicates)
-        if cardinality == 0.0 { 1.0 } else { total / cardinality }
+    /// Bytes the column occupies under `encoding`. Like real columnar
+    /// formats, falls back to plain values when the encoding would be larger.
+    fn encoded_size(&self, encoding: Encoding) -> usize {
+        let raw = self.byte_size();
+        let encoded = match encoding {
+            Encoding::None => raw,
+            // Each run stores its value once plus a varint run length.
+            Encoding::Rle => {
+                let mut size = 0;
+                let mut i = 0;
+                while i < self.values.len() {
+                    let run = self.values[i..].iter().take_while(|v| **v == self.values[i]).count();
+                    size += self.values[i].to_string().len() + varint_len(run as u64);
+                    i += run;
+                }
+                size
+            }
+            // First value stored plainly, then zigzag varint differences.
+            // Only integer columns can be delta-encoded.
+            Encoding::Delta => {
+                let ints: Option<Vec<i64>> = self.values.iter().map(|v| v.as_i64()).collect();
+                match ints {
+                    Some(ints) if !ints.is_empty() => {
+                        let deltas: usize = ints.windows(2).map(|w| {
+                            let d = w[1].wrapping_sub(w[0]);
+                            varint_len(((d << 1) ^ (d >> 63)) as u64)
+                        }).sum();
+                        self.values[0].to_string().len() + deltas
+                    }
+                    _ => raw,
+                }
+            }
+        };
+        encoded.min(raw)
+    }
+
+    /// Bytes occupied by the column's values (serialized width).
+    fn byte_size(&self) -> usize {
+        self.values.iter().map(|v| v.to_string().len()).sum()
     }
 }
 
@@ -64,7 +128,12 @@ pub struct ColumnarStorageBlock {
     /// Column-oriented storage: column_name → Column
     columns: HashMap<String, Column>,
     row_count: usize,
+    projection: Vec<String>,
+    encoding: Encoding,
     columns_read: usize,
+    columns_scanned: usize,
+    columns_skipped: usize,
+    bytes_read: usize,
 }
 
 impl ColumnarStorageBlock {
@@ -77,7 +146,12 @@ pub fn new() -> Self {
             metric_defs: Self::build_metrics(),
             columns: HashMap::new(),
             row_count: 0,
+            projection: Vec::new(),
+            encoding: Encoding::None,
             columns_read: 0,
+            columns_scanned: 0,
+            columns_skipped: 0,
+            bytes_read: 0,
         }
     }
 
@@ -121,11 +195,15 @@ fn build_metadata() -> BlockMetadata {
                               a. Create a new Record\n    \
                               b. For each selected column, read values[i]\n    \
                               c. Emit the reconstructed record\n  \
-                           3. Track columns_read for I/O metrics\n\n\
+                           3. Track columns_read for I/O metrics\n  \
+                           4. bytes_read += size of each selected column; columns_skipped = the rest\n\n\
                            COMPRESSION ESTIMATION:\n  \
-                           1. For each column, count distinct values (cardinality)\n  \
-                           2. compression_ratio = total_values / distinct_values\n  \
-                           3. Low cardinality columns (e.g., status, category) compress very well"
+                           1. For each column, size it under the encoding:\n    \
+                              none:  sum of value widths\n    \
+                              rle:   per run of equal values, value width + varint(run length)\n    \
+                              delta: first value + zigzag varint(v[i] - v[i-1]) (integer columns only)\n    \
+                              keep the smaller of encoded and raw size\n  \
+                           2. compression_ratio = total raw bytes / total encoded bytes"
                     .into(),
                 complexity: Complexity {
                     time: "Insert O(c) per row where c = columns, Projection O(n) for n rows".into(),
@@ -165,12 +243,26 @@ fn build_metadata() -> BlockMetadata {
                     .into(),
                 parameter_guide: HashMap::from([
                     ("projection".into(),
-                     "A comma-separated list of column names to include in the output. When empty, \
-                      all columns are projected (like SELECT *). When specified, only those columns \
-                      are read, simulating the I/O savings of columnar storage. For example, \
-                      'id,price' reads only those two columns. This is the key advantage of \
-                      columnar layout: you pay I/O cost only for the columns you actually need. \
-                      Try different projections to see how columns_read changes in the metrics."
+                     "A list of column names to include in the output. When empty, all columns \
+                      are projected (like SELECT *). When specified, only those columns are read, \
+                      simulating the I/O savings of columnar storage. For example, [id, price] \
+                      (or the string 'id,price') reads only those two columns. This is the key \
+                      advantage of columnar layout: you pay I/O cost only for the columns you \
+                      actually need. \
+                      Try different projections to see how columns_read changes in the metrics: \
+                      bytes_read grows with the projected columns only, so projecting 2 of 50 \
+                      equally wide columns reads about 25x less than a full scan, and \
+                      columns_skipped counts the columns that were never touched."
+                         .into()),
+                    ("encoding".into(),
+                     "How each column is encoded on disk. none stores every value as-is (ratio 1.0). \
+                      rle (run-length encoding) stores each run of equal consecutive values once with \
+                      its length — excellent for sorted or low-cardinality columns such as status or \
+                      date, useless for unique values. delta stores the first value and then the \
+                      difference to the previous one, so monotonically increasing integers like IDs \
+                      or timestamps shrink to a byte or two per value; non-integer columns stay \
+                      plain. Either way a column never grows: like Parquet and ORC, the block falls \
+                      back to plain values when the encoding would not help."
                          .into()),
                 ]),
                 alternatives: vec![
@@ -236,11 +328,19 @@ fn build_outputs() -> Vec<Port> {
     fn build_parameters() -> Vec<Parameter> {
         vec![Parameter {
             id: "projection".into(), name: "Projection Columns".into(),
-            param_type: ParameterType::String,
-            description: "Comma-separated column names to project (empty = all)".into(),
-            default_value: ParameterValue::String("".into()),
-            required: false, constraints: None,
+            param_type: ParameterType::Array,
+            description: "Column names to project (empty = all)".into(),
+            default_value: ParameterValue::Array(Vec::new()),
+            required: false, constraints: Some(projection_constraints()),
             ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
+        },
+        Parameter {
+            id: "encoding".into(), name: "Encoding".into(),
+            param_type: ParameterType::Enum,
+            description: "Column encoding: none, rle, or delta".into(),
+            default_value: ParameterValue::String("none".into()),
+            required: false, constraints: Some(ParameterConstraints::new().with_choices(ENCODINGS)),
+            ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
         }]
     }
 
@@ -249,14 +349,17 @@ fn build_metrics() -> Vec<MetricDefinition> {
             MetricDefinition { id: "columns_stored".into(), name: "Columns Stored".into(), metric_type: MetricType::Gauge, unit: "columns".into(), description: "Distinct columns".into(), aggregations: vec![AggregationType::Max] },
             MetricDefinition { id: "rows_stored".into(), name: "Rows Stored".into(), metric_type: MetricType::Gauge, unit: "rows".into(), description: "Total rows".into(), aggregations: vec![AggregationType::Max] },
             MetricDefinition { id: "columns_read".into(), name: "Columns Read".into(), metric_type: MetricType::Counter, unit: "ops".into(), description: "Column projections performed".into(), aggregations: vec![AggregationType::Sum] },
-            MetricDefinition { id: "compression_ratio".into(), name: "Compression Ratio".into(), metric_type: MetricType::Gauge, unit: "x".into(), description: "Average compression ratio across columns".into(), aggregations: vec![AggregationType::Max] },
+            MetricDefinition { id: "columns_scanned".into(), name: "Columns Scanned".into(), metric_type: MetricType::Gauge, unit: "columns".into(), description: "Columns read by the last scan".into(), aggregations: vec![AggregationType::Avg, AggregationType::Max] },
+            MetricDefinition { id: "columns_skipped".into(), name: "Columns Skipped".into(), metric_type: MetricType::Gauge, unit: "columns".into(), description: "Stored columns the last scan did not read".into(), aggregations: vec![AggregationType::Avg, AggregationType::Max] },
+            MetricDefinition { id: "bytes_read".into(), name: "Bytes Read".into(), metric_type: MetricType::Counter, unit: "bytes".into(), description: "Bytes of column data read by scans".into(), aggregations: vec![AggregationType::Sum] },
+            MetricDefinition { id: "compression_ratio".into(), name: "Compression Ratio".into(), metric_type: MetricType::Gauge, unit: "x".into(), description: "Raw bytes / encoded bytes across all columns".into(), aggregations: vec![AggregationType::Max] },
         ]
     }
 
-    fn avg_compression_ratio(&self) -> f64 {
-        if self.columns.is_empty() { return 1.0; }
-        let sum: f64 = self.columns.values().map(|c| c.compression_ratio()).sum();
-        sum / self.columns.len() as f64
+    fn compression_ratio(&self) -> f64 {
+        let raw: usize = self.columns.values().map(Column::byte_size).sum();
+        let encoded: usize = self.columns.values().map(|c| c.encoded_size(self.encoding)).sum();
+        if encoded == 0 { 1.0 } else { raw as f64 / encoded as f64 }
     }
 
     /// Store rows by decomposing into columns.
@@ -285,15 +388,27 @@ fn ingest(&mut self, records: &[Record]) {
         }
     }
 
-    /// Project selected columns back into rows.
+    /// Project selected columns back into rows. A column listed more than
+    /// once is read only once.
     fn project(&mut self, col_names: &[String]) -> Vec<Record> {
         let cols: Vec<&String> = if col_names.is_empty() {
             self.columns.keys().collect()
         } else {
-            col_names.iter().filter(|n| self.columns.contains_key(*n)).collect()
+            let mut seen = HashSet::new();
+            col_names
+                .iter()
+                .filter(|n| self.columns.contains_key(*n) && seen.insert(*n))
+                .collect()
         };
 
         self.columns_read += cols.len();
+        self.columns_scanned = cols.len();
+        self.columns_skipped = self.columns.len() - cols.len();
+        self.bytes_read += cols
+            .iter()
+            .filter_map(|n| self.columns.get(*n))
+            .map(Column::byte_size)
+            .sum::<usize>();
 
         let mut result = Vec::with_capacity(self.row_count);
         for i in 0..self.row_count {
@@ -312,6 +427,19 @@ fn project(&mut self, col_names: &[String]) -> Vec<Record> {
 
 impl Default for ColumnarStorageBlock { fn default() -> Self { Self::new() } }
 
+/// Constraints on the `projection` list: column names.
+fn projection_constraints() -> ParameterConstraints {
+    ParameterConstraints::new().with_element_type(ParameterType::String)
+}
+
+/// Read the `projection` column list from `params`; empty means all columns.
+fn parse_projection(
+    params: &HashMap<String, ParameterValue>,
+) -> Result<Option<Vec<String>>, BlockError> {
+    let items = list_param(params, "projection", &projection_constraints())?;
+    Ok(items.map(|items| items.iter().filter_map(|c| c.as_string().map(str::to_string)).collect()))
+}
+
 #[async_trait]
 impl Block for ColumnarStorageBlock {
     fn metadata(&self) -> &BlockMetadata { &self.metadata }
@@ -327,7 +455,18 @@ fn guarantees(&self) -> &[Guarantee] {
     }
     fn metrics(&self) -> &[MetricDefinition] { &self.metric_defs }
 
-    async fn initialize(&mut self, _params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+    async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        self.params_validate(&params)?;
+        if let Some(projection) = parse_projection(&params)? {
+            self.projection = projection;
+        }
+        if let Some(s) = enum_param(&params, "encoding", ENCODINGS)? {
+            self.encoding = match s {
+                "none" => Encoding::None,
+                "rle" => Encoding::Rle,
+                _ => Encoding::Delta,
+            };
+        }
         Ok(())
     }
 
@@ -341,14 +480,10 @@ async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult
         // Ingest into columnar format
         self.ingest(&records);
 
-        // Parse projection parameter
-        let projection_str = context.parameters.get("projection")
-            .and_then(|v| v.as_string())
-            .unwrap_or("");
-        let projection_cols: Vec<String> = if projection_str.is_empty() {
-            Vec::new() // empty = all columns
-        } else {
-            projection_str.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+        // A projection passed at execution time overrides the configured one.
+        let projection_cols = match parse_projection(&context.parameters)? {
+            Some(projection) => projection,
+            None => self.projection.clone(),
         };
 
         let projected = self.project(&projection_cols);
@@ -356,14 +491,20 @@ async fn execute(&mut self, context: ExecutionContext) -> Result<ExecutionResult
         context.metrics.record("columns_stored", self.columns.len() as f64);
         context.metrics.record("rows_stored", self.row_count as f64);
         context.metrics.record("columns_read", self.columns_read as f64);
-        context.metrics.record("compression_ratio", self.avg_compression_ratio());
+        context.metrics.record("columns_scanned", self.columns_scanned as f64);
+        context.metrics.record("columns_skipped", self.columns_skipped as f64);
+        context.metrics.record("bytes_read", self.bytes_read as f64);
+        context.metrics.record("compression_ratio", self.compression_ratio());
 
         let mut outputs = HashMap::new();
         outputs.insert("projected".into(), PortValue::Stream(projected));
         let mut ms = HashMap::new();
         ms.insert("rows_stored".into(), self.row_count as f64);
         ms.insert("columns_stored".into(), self.columns.len() as f64);
-        ms.insert("compression_ratio".into(), self.avg_compression_ratio());
+        ms.insert("compression_ratio".into(), self.compression_ratio());
+        ms.insert("columns_scanned".into(), self.columns_scanned as f64);
+        ms.insert("columns_skipped".into(), self.columns_skipped as f64);
+        ms.insert("bytes_read".into(), self.bytes_read as f64);
 
         Ok(ExecutionResult { outputs, metrics: ms, errors: vec![] })
     }
@@ -435,7 +576,8 @@ async fn test_columnar_selective_projection() {
     async fn test_compression_ratio() {
         use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
         let mut col = ColumnarStorageBlock::new();
-        // category column has low cardinality (only "A" and "B") → should compress well
+        col.encoding = Encoding::Rle;
+        // category column has runs of repeated "B" values → RLE shrinks it
         let records = make_records();
         let mut inputs = HashMap::new();
         inputs.insert("records".into(), PortValue::Stream(records));
@@ -445,6 +587,126 @@ async fn test_compression_ratio() {
         assert!(ratio > 1.0, "Should have compression ratio > 1 due to repeated values");
     }
 
+    /// Scan a fresh 100-row table of 50 equally wide columns.
+    async fn scan_wide_table(projection: &[&str]) -> HashMap<String, f64> {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+        let records: Vec<Record> = (0..100).map(|i| {
+            let mut r = Record::new();
+            for c in 0..50 {
+                r.insert(format!("c{:02}", c), format!("v{:05}", i)).unwrap();
+            }
+            r
+        }).collect();
+        let mut col = ColumnarStorageBlock::new();
+        col.projection = projection.iter().map(|c| c.to_string()).collect();
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext { inputs, parameters: HashMap::new(), metrics: MetricsCollector::new(), logger: Logger::new(), storage: StorageContext::new() };
+        col.execute(ctx).await.unwrap().metrics
+    }
+
+    #[tokio::test]
+    async fn test_wide_table_projection_bytes_read() {
+        let full = scan_wide_table(&[]).await;
+        let narrow = scan_wide_table(&["c00", "c01"]).await;
+
+        assert_eq!(full["columns_scanned"], 50.0);
+        assert_eq!(full["columns_skipped"], 0.0);
+        assert_eq!(narrow["columns_scanned"], 2.0);
+        assert_eq!(narrow["columns_skipped"], 48.0);
+        assert_eq!(full["bytes_read"] / narrow["bytes_read"], 25.0);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_projection_columns_read_once() {
+        let once = scan_wide_table(&["c00"]).await;
+        let twice = scan_wide_table(&["c00", "c00"]).await;
+        assert_eq!(twice["columns_scanned"], 1.0);
+        assert_eq!(twice["columns_skipped"], 49.0);
+        assert_eq!(twice["bytes_read"], once["bytes_read"]);
+
+        // Listing every column plus a repeat must not underflow the skip count.
+        let names: Vec<String> = (0..50).map(|c| format!("c{:02}", c)).collect();
+        let mut all: Vec<&str> = names.iter().map(String::as_str).collect();
+        all.push("c00");
+        assert_eq!(scan_wide_table(&all).await["columns_skipped"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_projection_from_initialize() {
+        let mut col = ColumnarStorageBlock::new();
+        let mut params = HashMap::new();
+        params.insert("projection".into(), ParameterValue::String(" id , name ".into()));
+        col.initialize(params).await.unwrap();
+        assert_eq!(col.projection, vec!["id".to_string(), "name".to_string()]);
+
+        let columns = vec![ParameterValue::from("price"), ParameterValue::from("id")];
+        let mut params = HashMap::new();
+        params.insert("projection".into(), ParameterValue::Array(columns));
+        col.initialize(params).await.unwrap();
+        assert_eq!(col.projection, vec!["price".to_string(), "id".to_string()]);
+
+        let mut params = HashMap::new();
+        params.insert("projection".into(), ParameterValue::Array(vec![ParameterValue::Integer(1)]));
+        assert!(col.initialize(params).await.is_err(), "column names must be strings");
+    }
+
+    fn column(values: impl Iterator<Item = JsonValue>) -> Column {
+        let mut c = Column::new("c".into());
+        c.values.extend(values);
+        c
+    }
+
+    #[test]
+    fn test_rle_identical_vs_random() {
+        let identical = column((0..10_000).map(|_| JsonValue::from("constant")));
+        let ratio = identical.byte_size() as f64 / identical.encoded_size(Encoding::Rle) as f64;
+        assert!(ratio > 1000.0, "identical column should compress dramatically, got {}", ratio);
+
+        // Pseudo-random values: no runs, so RLE falls back to plain.
+        let mut x: u64 = 42;
+        let random = column((0..10_000).map(|_| {
+            x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            JsonValue::from(x >> 33)
+        }));
+        let ratio = random.byte_size() as f64 / random.encoded_size(Encoding::Rle) as f64;
+        assert!((ratio - 1.0).abs() < 0.01, "random column should not compress, got {}", ratio);
+    }
+
+    #[test]
+    fn test_delta_encoding() {
+        // Sequential IDs: large values, tiny differences.
+        let ids = column((1_000_000..1_010_000).map(JsonValue::from));
+        assert!(ids.byte_size() / ids.encoded_size(Encoding::Delta) >= 6);
+        // Strings cannot be delta-encoded.
+        let names = column((0..100).map(|i| JsonValue::from(format!("user_{}", i))));
+        assert_eq!(names.encoded_size(Encoding::Delta), names.byte_size());
+        assert_eq!(ids.encoded_size(Encoding::None), ids.byte_size());
+    }
+
+    #[tokio::test]
+    async fn test_encoding_parameter() {
+        let mut col = ColumnarStorageBlock::new();
+        let records: Vec<Record> = (0..100).map(|i| {
+            let mut r = Record::new();
+            r.insert("ts".into(), 1_700_000_000i64 + i).unwrap();
+            r
+        }).collect();
+        col.ingest(&records);
+        assert_eq!(col.compression_ratio(), 1.0);
+
+        let mut params = HashMap::new();
+        params.insert("encoding".into(), ParameterValue::String("delta".into()));
+        col.initialize(params).await.unwrap();
+        assert_eq!(col.encoding, Encoding::Delta);
+        // The sequential timestamp column shrinks under delta encoding.
+        assert!(col.compression_ratio() > 1.0);
+
+        let mut bad = HashMap::new();
+        bad.insert("encoding".into(), ParameterValue::String("zstd".into()));
+        assert!(col.initialize(bad).await.is_err());
+    }
+
     #[test]
     fn test_metadata() {
         let col = ColumnarStorageBlock::new();
@@ -9,7 +9,13 @@
 //! Records are appended to whichever page has enough free space. A **free-space
 //! map** tracks how much room each page has so inserts don't have to scan every
 //! page. Deletes mark slots as dead rather than physically removing data; a
-//! future VACUUM-like compaction pass would reclaim the space.
+//! VACUUM pass rewrites pages without their dead slots to reclaim the space.
+//!
+//! VACUUM may move surviving records, invalidating TupleIds emitted earlier.
+//! When `execute` auto-vacuums, `remapped` first carries every reclaimed dead
+//! record at its old TupleId with `_op: "delete"`, then every moved record at
+//! its new TupleId with its old one in `_old_page_id`/`_old_slot_id`; connect
+//! it to an index's `remap` input to keep the index pointing at live records.
 //!
 //! ## Metrics tracked
 //!
@@ -23,6 +29,7 @@
 //! | `total_pages` | Gauge | Current page count |
 //! | `total_live_records` | Gauge | Live (non-dead) records |
 //! | `fragmentation_pct` | Gauge | Dead records / total records |
+//! | `vacuum` | Counter | VACUUM passes run |
 
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -78,6 +85,82 @@ fn dead_count(&self) -> usize {
     }
 }
 
+/// Outcome of a VACUUM pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VacuumResult {
+    /// Number of dead slots physically removed.
+    pub slots_reclaimed: usize,
+    /// Number of pages freed because they held no live records.
+    pub pages_freed: usize,
+    /// Old → new TupleId for every surviving record whose location changed.
+    pub remapped: HashMap<TupleId, TupleId>,
+}
+
+/// Free-space map: remaining bytes per page, plus a max-tree over them.
+///
+/// Like PostgreSQL's FSM, each internal tree node stores the largest free
+/// space among its children, so the first page with enough room can be found
+/// in O(log n) by descending into the leftmost child that fits.
+#[derive(Debug, Clone, Default)]
+struct FreeSpaceMap {
+    /// Free bytes per page, indexed by page_id.
+    free: Vec<usize>,
+    /// Max-tree in heap layout; leaves start at index `capacity`.
+    tree: Vec<usize>,
+    capacity: usize,
+}
+
+impl FreeSpaceMap {
+    fn from_free(free: Vec<usize>) -> Self {
+        let capacity = free.len().next_power_of_two().max(1);
+        let mut tree = vec![0; capacity * 2];
+        tree[capacity..capacity + free.len()].copy_from_slice(&free);
+        for i in (1..capacity).rev() {
+            tree[i] = tree[2 * i].max(tree[2 * i + 1]);
+        }
+        Self {
+            free,
+            tree,
+            capacity,
+        }
+    }
+
+    /// Track a newly allocated page.
+    fn push(&mut self, free: usize) {
+        if self.free.len() == self.capacity {
+            let mut all = std::mem::take(&mut self.free);
+            all.push(free);
+            *self = Self::from_free(all);
+        } else {
+            self.free.push(free);
+            self.set(self.free.len() - 1, free);
+        }
+    }
+
+    /// Update the free space recorded for a page.
+    fn set(&mut self, page_id: usize, free: usize) {
+        self.free[page_id] = free;
+        let mut i = self.capacity + page_id;
+        self.tree[i] = free;
+        while i > 1 {
+            i /= 2;
+            self.tree[i] = self.tree[2 * i].max(self.tree[2 * i + 1]);
+        }
+    }
+
+    /// Lowest page_id with at least `min_bytes` free (first-fit).
+    fn first_fit(&self, min_bytes: usize) -> Option<usize> {
+        if self.free.is_empty() || self.tree[1] < min_bytes {
+            return None;
+        }
+        let mut i = 1;
+        while i < self.capacity {
+            i = if self.tree[2 * i] >= min_bytes { 2 * i } else { 2 * i + 1 };
+        }
+        Some(i - self.capacity)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // HeapFileBlock
 // ---------------------------------------------------------------------------
@@ -96,11 +179,17 @@ pub struct HeapFileBlock {
     // Configuration (set during initialize)
     page_size: usize,
     fill_factor: f64,
+    /// Fragmentation percentage above which `execute` runs VACUUM.
+    fragmentation_threshold: f64,
 
     // Internal state
     pages: Vec<Page>,
+    fsm: FreeSpaceMap,
     /// Estimated record size in bytes (computed from first insert).
     estimated_record_size: Option<usize>,
+    vacuum_count: usize,
+    sequential_scans: usize,
+    pages_read: usize,
 }
 
 impl HeapFileBlock {
@@ -113,8 +202,13 @@ pub fn new() -> Self {
             metric_defs: Self::build_metrics(),
             page_size: 8192,
             fill_factor: 0.9,
+            fragmentation_threshold: 20.0,
             pages: Vec::new(),
+            fsm: FreeSpaceMap::default(),
             estimated_record_size: None,
+            vacuum_count: 0,
+            sequential_scans: 0,
+            pages_read: 0,
         }
     }
 
@@ -145,7 +239,8 @@ fn build_metadata() -> BlockMetadata {
                            (deleted) entries waste space until you rewrite the notebook (vacuum)."
                     .into(),
                 algorithm: "INSERT:\n  1. Estimate record size from serialized data\n  \
-                           2. Consult free-space map to find a page with enough room\n  \
+                           2. Descend the free-space map's max-tree to the first page with \
+                              enough room (O(log pages))\n  \
                            3. If no page has room, allocate a new page\n  \
                            4. Append record to the first available slot on that page\n  \
                            5. Update used_bytes on the page\n  \
@@ -161,7 +256,17 @@ fn build_metadata() -> BlockMetadata {
                            2. Cost: reads every page including dead slots\n\n\
                            DELETE:\n  \
                            1. Mark slot as is_dead = true (soft delete)\n  \
-                           2. Space is not reclaimed until VACUUM/compaction"
+                           2. Space is not reclaimed until VACUUM/compaction\n\n\
+                           UPDATE:\n  \
+                           1. Soft-delete the old slot\n  \
+                           2. Insert the new version as a fresh record (new TupleId)\n\n\
+                           VACUUM:\n  \
+                           1. For each page, keep only live slots (in their original order)\n  \
+                           2. Recompute used_bytes from the surviving records\n  \
+                           3. Drop pages left empty and renumber the remaining pages\n  \
+                           4. Return old → new TupleId mappings so indexes can be fixed up\n  \
+                           5. Runs automatically before a batch when fragmentation exceeds \
+                              fragmentation_threshold"
                     .into(),
                 complexity: Complexity {
                     time: "Insert O(1) amortized, Scan O(n), Point lookup O(n) without index"
@@ -216,6 +321,14 @@ fn build_metadata() -> BlockMetadata {
                       Recommended: 0.9-1.0 for append-only/read-heavy tables, 0.7-0.8 for tables \
                       with frequent updates. Minimum is 0.1 (very wasteful), maximum is 1.0."
                          .into()),
+                    ("fragmentation_threshold".into(),
+                     "Percentage of dead slots that triggers an automatic VACUUM before the next \
+                      batch is processed. Lower values (e.g., 5-10%) keep pages compact and scans \
+                      cheap but vacuum often, which rewrites pages and invalidates TupleIds. Higher \
+                      values (e.g., 50%) vacuum rarely and let scans read many dead slots. Set to \
+                      100 to disable auto-vacuum. Default is 20%, similar to PostgreSQL's \
+                      autovacuum scale factor."
+                         .into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -270,22 +383,38 @@ fn build_inputs() -> Vec<Port> {
             direction: PortDirection::Input,
             required: true,
             multiple: false,
-            description: "Stream of records to store".into(),
+            description: "Stream of records; optional _op of insert/update/delete/get/scan"
+                .into(),
             schema: None,
         }]
     }
 
     fn build_outputs() -> Vec<Port> {
-        vec![Port {
-            id: "stored".into(),
-            name: "Stored Records".into(),
-            port_type: PortType::DataStream,
-            direction: PortDirection::Output,
-            required: false,
-            multiple: true,
-            description: "Records after storage, enriched with _tuple_id".into(),
-            schema: None,
-        }]
+        vec![
+            Port {
+                id: "stored".into(),
+                name: "Stored Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Inserted, updated, or fetched records, enriched with _tuple_id"
+                    .into(),
+                schema: None,
+            },
+            Port {
+                id: "remapped".into(),
+                name: "Remapped Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Records an auto-vacuum reclaimed (_op delete at their old \
+                              _tuple_id) or moved (new _tuple_id, old _old_page_id/_old_slot_id)"
+                    .into(),
+                schema: None,
+            },
+        ]
     }
 
     fn build_parameters() -> Vec<Parameter> {
@@ -326,6 +455,25 @@ fn build_parameters() -> Vec<Parameter> {
                         .with_help_text("Lower values leave room for future updates".into()),
                 ),
             },
+            Parameter {
+                id: "fragmentation_threshold".into(),
+                name: "Fragmentation Threshold".into(),
+                param_type: ParameterType::Number,
+                description: "Dead-slot percentage that triggers an automatic VACUUM".into(),
+                default_value: ParameterValue::Number(20.0),
+                required: false,
+                constraints: Some(
+                    ParameterConstraints::new()
+                        .with_min(0.0)
+                        .with_max(100.0),
+                ),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(5.0)
+                        .with_unit("%".into())
+                        .with_help_text("100 disables auto-vacuum".into()),
+                ),
+            },
         ]
     }
 
@@ -395,6 +543,14 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Percentage of dead slots".into(),
                 aggregations: vec![AggregationType::Max],
             },
+            MetricDefinition {
+                id: "vacuum".into(),
+                name: "Vacuum Runs".into(),
+                metric_type: MetricType::Counter,
+                unit: "ops".into(),
+                description: "VACUUM passes that reclaimed dead slots".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
         ]
     }
 
@@ -418,19 +574,44 @@ fn usable_page_bytes(&self) -> usize {
 
     /// Find a page with enough free space, or allocate a new one.
     fn find_page_for_insert(&mut self, record_size: usize) -> usize {
-        let usable = self.usable_page_bytes();
-        // Free-space map scan — find first page with room.
-        for page in &self.pages {
-            if page.used_bytes + record_size <= usable {
-                return page.page_id;
-            }
+        // Free-space map lookup — first page with room.
+        if let Some(page_id) = self.fsm.first_fit(record_size) {
+            return page_id;
         }
         // No page with room — allocate a new one.
         let new_id = self.pages.len();
         self.pages.push(Page::new(new_id));
+        self.fsm.push(self.usable_page_bytes());
         new_id
     }
 
+    /// Rebuild the free-space map from the current pages.
+    fn rebuild_fsm(&mut self) {
+        let usable = self.usable_page_bytes();
+        let free = self
+            .pages
+            .iter()
+            .map(|p| usable.saturating_sub(p.used_bytes))
+            .collect();
+        self.fsm = FreeSpaceMap::from_free(free);
+    }
+
+    /// Free bytes remaining on each page, indexed by page_id.
+    pub fn free_space_map(&self) -> &[usize] {
+        &self.fsm.free
+    }
+
+    /// Page ids with at least `min_bytes` free, in ascending order.
+    pub fn pages_with_space(&self, min_bytes: usize) -> Vec<usize> {
+        self.fsm
+            .free
+            .iter()
+            .enumerate()
+            .filter(|(_, &free)| free >= min_bytes)
+            .map(|(page_id, _)| page_id)
+            .collect()
+    }
+
     /// Insert a single record. Returns the TupleId.
     pub fn insert(&mut self, record: Record) -> TupleId {
         let rec_size = self
@@ -441,6 +622,7 @@ pub fn insert(&mut self, record: Record) -> TupleId {
         }
 
         let page_id = self.find_page_for_insert(rec_size);
+        let usable = self.usable_page_bytes();
         let page = &mut self.pages[page_id];
         let slot_id = page.slots.len();
         page.slots.push(Slot {
@@ -448,6 +630,8 @@ pub fn insert(&mut self, record: Record) -> TupleId {
             is_dead: false,
         });
         page.used_bytes += rec_size;
+        let free = usable.saturating_sub(page.used_bytes);
+        self.fsm.set(page_id, free);
 
         TupleId::new(page_id, slot_id)
     }
@@ -464,7 +648,12 @@ pub fn get(&self, tid: TupleId) -> Option<&Record> {
     }
 
     /// Sequential scan — returns all live records with their TupleIds.
-    pub fn scan(&self) -> Vec<(TupleId, &Record)> {
+    ///
+    /// Every page is read, including pages holding only dead slots.
+    pub fn scan(&mut self) -> Vec<(TupleId, &Record)> {
+        self.sequential_scans += 1;
+        self.pages_read += self.pages.len();
+
         let mut results = Vec::new();
         for page in &self.pages {
             for (slot_idx, slot) in page.slots.iter().enumerate() {
@@ -489,6 +678,115 @@ pub fn delete(&mut self, tid: TupleId) -> bool {
         false
     }
 
+    /// VACUUM — rewrite every page without its dead slots.
+    ///
+    /// Pages left with no live records are dropped and the remaining pages are
+    /// renumbered, so surviving records may move. The returned remapping lists
+    /// every record whose TupleId changed.
+    pub fn vacuum(&mut self) -> VacuumResult {
+        let mut result = VacuumResult::default();
+        let rec_size = self.estimated_record_size.unwrap_or(0);
+
+        let old_pages = std::mem::take(&mut self.pages);
+        for old_page in old_pages {
+            let new_page_id = self.pages.len();
+            let mut page = Page::new(new_page_id);
+            for (old_slot, slot) in old_page.slots.into_iter().enumerate() {
+                if slot.is_dead {
+                    result.slots_reclaimed += 1;
+                    continue;
+                }
+                let old_tid = TupleId::new(old_page.page_id, old_slot);
+                let new_tid = TupleId::new(new_page_id, page.slots.len());
+                if old_tid != new_tid {
+                    result.remapped.insert(old_tid, new_tid);
+                }
+                page.slots.push(slot);
+            }
+
+            if page.slots.is_empty() {
+                result.pages_freed += 1;
+                continue;
+            }
+            page.used_bytes = page.slots.len() * rec_size;
+            self.pages.push(page);
+        }
+
+        self.rebuild_fsm();
+        self.vacuum_count += 1;
+        result
+    }
+
+    /// One record per dead slot, at its TupleId with `_op: "delete"`: the
+    /// records the next VACUUM will reclaim.
+    fn vacated_records(&self) -> Vec<Record> {
+        self.pages
+            .iter()
+            .flat_map(|page| {
+                page.slots.iter().enumerate().filter(|(_, slot)| slot.is_dead).map(
+                    move |(slot_idx, slot)| {
+                        let tid = TupleId::new(page.page_id, slot_idx);
+                        let mut record = Self::with_tuple_id(slot.record.clone(), tid);
+                        let _ = record.insert("_op".into(), "delete");
+                        record
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// One record per entry of `result.remapped`, in new TupleId order: the
+    /// record at its new TupleId, plus `_old_page_id`/`_old_slot_id`.
+    fn remap_records(&self, result: &VacuumResult) -> Vec<Record> {
+        let mut moves: Vec<(TupleId, TupleId)> =
+            result.remapped.iter().map(|(&old, &new)| (old, new)).collect();
+        moves.sort_by_key(|&(_, new)| (new.page_id, new.slot_id));
+        moves
+            .into_iter()
+            .filter_map(|(old, new)| {
+                let mut record = Self::with_tuple_id(self.get(new)?.clone(), new);
+                let _ = record.insert("_old_page_id".into(), old.page_id);
+                let _ = record.insert("_old_slot_id".into(), old.slot_id);
+                Some(record)
+            })
+            .collect()
+    }
+
+    /// Update a record as delete-plus-insert. Returns the new TupleId, or
+    /// None if the old record did not exist or was already dead.
+    pub fn update(&mut self, tid: TupleId, record: Record) -> Option<TupleId> {
+        if !self.delete(tid) {
+            return None;
+        }
+        Some(self.insert(record))
+    }
+
+    /// TupleId addressed by a record, from `_tuple_id` or `_page_id`/`_slot_id`.
+    fn target_tid(record: &Record) -> Option<TupleId> {
+        if let Ok(Some(tid)) = record.get::<TupleId>("_tuple_id") {
+            return Some(tid);
+        }
+        let page_id = record.get::<usize>("_page_id").ok().flatten()?;
+        let slot_id = record.get::<usize>("_slot_id").ok().flatten()?;
+        Some(TupleId::new(page_id, slot_id))
+    }
+
+    /// Strip operation and location fields before a record is stored.
+    fn strip_control_fields(mut record: Record) -> Record {
+        for field in ["_op", "_tuple_id", "_page_id", "_slot_id"] {
+            record.data.remove(field);
+        }
+        record
+    }
+
+    /// Copy a stored record and tag it with its TupleId.
+    fn with_tuple_id(mut record: Record, tid: TupleId) -> Record {
+        let _ = record.insert("_page_id".into(), tid.page_id);
+        let _ = record.insert("_slot_id".into(), tid.slot_id);
+        let _ = record.insert("_tuple_id".into(), tid);
+        record
+    }
+
     /// Total number of pages.
     pub fn page_count(&self) -> usize {
         self.pages.len()
@@ -557,33 +855,25 @@ fn metrics(&self) -> &[MetricDefinition] {
         &self.metric_defs
     }
 
+    fn reports_running_totals(&self) -> bool {
+        false
+    }
+
     async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("page_size") {
-            self.page_size = val
-                .as_integer()
-                .ok_or_else(|| BlockError::InvalidParameter("page_size must be an integer".into()))?
-                as usize;
-            if self.page_size < 512 || self.page_size > 65536 {
-                return Err(BlockError::InvalidParameter(
-                    "page_size must be between 512 and 65536".into(),
-                ));
-            }
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("page_size").and_then(ParameterValue::as_integer) {
+            self.page_size = v as usize;
         }
-        if let Some(val) = params.get("fill_factor") {
-            self.fill_factor = val
-                .as_number()
-                .ok_or_else(|| {
-                    BlockError::InvalidParameter("fill_factor must be a number".into())
-                })?;
-            if !(0.1..=1.0).contains(&self.fill_factor) {
-                return Err(BlockError::InvalidParameter(
-                    "fill_factor must be between 0.1 and 1.0".into(),
-                ));
-            }
+        if let Some(v) = params.get("fill_factor").and_then(ParameterValue::as_number) {
+            self.fill_factor = v;
+        }
+        if let Some(v) = params.get("fragmentation_threshold").and_then(ParameterValue::as_number) {
+            self.fragmentation_threshold = v;
         }
+        self.rebuild_fsm();
         Ok(())
     }
 
@@ -609,18 +899,128 @@ async fn execute(
             }
         };
 
+        // Auto-vacuum before touching the batch so the TupleIds we emit stay
+        // valid; ones emitted earlier are reported on `remapped`. Removals
+        // come first so an index drops a reclaimed entry before another
+        // record moves into its TupleId.
+        let mut remapped = Vec::new();
+        if self.fragmentation_pct() > self.fragmentation_threshold {
+            remapped = self.vacated_records();
+            let result = self.vacuum();
+            context.metrics.increment("vacuum");
+            remapped.extend(self.remap_records(&result));
+        }
+
         let mut output_records = Vec::with_capacity(records.len());
+        let mut errors = Vec::new();
+        let scans_before = self.sequential_scans;
+        let pages_read_before = self.pages_read;
+        // Per-call counts for the summary; the shared collector may hold
+        // earlier calls and other blocks.
+        let mut inserted = 0usize;
+        let mut deleted = 0usize;
+        let mut point_reads = 0usize;
 
         for record in records {
-            let tid = self.insert(record.clone());
-            context.metrics.increment("pages_written");
-            context.metrics.increment("records_inserted");
+            let op = record
+                .get::<String>("_op")
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| "insert".into());
+
+            match op.as_str() {
+                "insert" => {
+                    let record = Self::strip_control_fields(record);
+                    let tid = self.insert(record.clone());
+                    context.metrics.increment("pages_written");
+                    context.metrics.increment("records_inserted");
+                    inserted += 1;
+                    output_records.push(Self::with_tuple_id(record, tid));
+                }
+                "update" => {
+                    let Some(old_tid) = Self::target_tid(&record) else {
+                        errors.push(BlockError::InvalidInput(
+                            "update requires _tuple_id or _page_id/_slot_id".into(),
+                        ));
+                        continue;
+                    };
+                    let record = Self::strip_control_fields(record);
+                    match self.update(old_tid, record.clone()) {
+                        Some(tid) => {
+                            // Old page is rewritten (dead slot) and the new version is written.
+                            context.metrics.record("pages_written", 2.0);
+                            context.metrics.increment("records_deleted");
+                            context.metrics.increment("records_inserted");
+                            deleted += 1;
+                            inserted += 1;
+                            output_records.push(Self::with_tuple_id(record, tid));
+                        }
+                        None => errors.push(BlockError::ExecutionError(format!(
+                            "update target {} does not exist",
+                            old_tid
+                        ))),
+                    }
+                }
+                "delete" => {
+                    let Some(tid) = Self::target_tid(&record) else {
+                        errors.push(BlockError::InvalidInput(
+                            "delete requires _tuple_id or _page_id/_slot_id".into(),
+                        ));
+                        continue;
+                    };
+                    if self.delete(tid) {
+                        context.metrics.increment("pages_written");
+                        context.metrics.increment("records_deleted");
+                        deleted += 1;
+                    } else {
+                        errors.push(BlockError::ExecutionError(format!(
+                            "delete target {} does not exist",
+                            tid
+                        )));
+                    }
+                }
+                "get" => {
+                    let Some(tid) = Self::target_tid(&record) else {
+                        errors.push(BlockError::InvalidInput(
+                            "get requires _tuple_id or _page_id/_slot_id".into(),
+                        ));
+                        continue;
+                    };
+                    context.metrics.increment("pages_read");
+                    point_reads += 1;
+                    match self.get(tid) {
+                        Some(found) => {
+                            output_records.push(Self::with_tuple_id(found.clone(), tid));
+                        }
+                        None => errors.push(BlockError::ExecutionError(format!(
+                            "no live record at {}",
+                            tid
+                        ))),
+                    }
+                }
+                "scan" => {
+                    let live: Vec<Record> = self
+                        .scan()
+                        .into_iter()
+                        .map(|(tid, rec)| Self::with_tuple_id(rec.clone(), tid))
+                        .collect();
+                    output_records.extend(live);
+                }
+                other => errors.push(BlockError::InvalidInput(format!(
+                    "unknown _op '{}' (expected insert, update, delete, get, or scan)",
+                    other
+                ))),
+            }
+        }
 
-            // Enrich the output record with the assigned tuple id.
-            let mut out = record;
-            let _ = out.insert("_page_id".into(), tid.page_id);
-            let _ = out.insert("_slot_id".into(), tid.slot_id);
-            output_records.push(out);
+        if self.sequential_scans > scans_before {
+            context.metrics.record(
+                "sequential_scans",
+                (self.sequential_scans - scans_before) as f64,
+            );
+            context
+                .metrics
+                .record("pages_read", (self.pages_read - pages_read_before) as f64);
         }
 
         // Record gauges.
@@ -636,26 +1036,31 @@ async fn execute(
 
         let mut outputs = HashMap::new();
         outputs.insert("stored".into(), PortValue::Stream(output_records));
+        outputs.insert("remapped".into(), PortValue::Stream(remapped));
 
         let mut metrics_summary = HashMap::new();
-        metrics_summary.insert(
-            "records_inserted".into(),
-            context
-                .metrics
-                .aggregate("records_inserted", AggregationType::Sum)
-                .unwrap_or(0.0),
-        );
+        metrics_summary.insert("records_inserted".into(), inserted as f64);
+        metrics_summary.insert("records_deleted".into(), deleted as f64);
         metrics_summary.insert("total_pages".into(), self.page_count() as f64);
         metrics_summary.insert(
             "total_live_records".into(),
             self.live_record_count() as f64,
         );
         metrics_summary.insert("fragmentation_pct".into(), self.fragmentation_pct());
+        metrics_summary.insert("vacuum".into(), self.vacuum_count as f64);
+        metrics_summary.insert(
+            "sequential_scans".into(),
+            (self.sequential_scans - scans_before) as f64,
+        );
+        metrics_summary.insert(
+            "pages_read".into(),
+            (point_reads + self.pages_read - pages_read_before) as f64,
+        );
 
         Ok(ExecutionResult {
             outputs,
             metrics: metrics_summary,
-            errors: vec![],
+            errors,
         })
     }
 
@@ -678,6 +1083,7 @@ fn get_state(&self) -> BlockState {
         let mut state = BlockState::new();
         let _ = state.insert("page_size".into(), self.page_size);
         let _ = state.insert("fill_factor".into(), self.fill_factor);
+        let _ = state.insert("fragmentation_threshold".into(), self.fragmentation_threshold);
         let _ = state.insert("page_count".into(), self.page_count());
         let _ = state.insert("live_records".into(), self.live_record_count());
         state
@@ -690,6 +1096,10 @@ fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
         if let Ok(Some(ff)) = state.get::<f64>("fill_factor") {
             self.fill_factor = ff;
         }
+        if let Ok(Some(ft)) = state.get::<f64>("fragmentation_threshold") {
+            self.fragmentation_threshold = ft;
+        }
+        self.rebuild_fsm();
         Ok(())
     }
 }
@@ -753,6 +1163,41 @@ fn test_sequential_scan() {
         assert_eq!(results.len(), 50);
     }
 
+    #[test]
+    fn test_scan_counts_pages_read() {
+        let mut heap = HeapFileBlock::new();
+        heap.page_size = 512;
+        heap.fill_factor = 0.5;
+        for i in 0..20 {
+            heap.insert(make_record(i, "x"));
+        }
+        let pages = heap.page_count();
+
+        heap.scan();
+        heap.scan();
+        assert_eq!(heap.sequential_scans, 2);
+        assert_eq!(heap.pages_read, pages * 2);
+    }
+
+    #[tokio::test]
+    async fn test_execute_scan_op_records_read_metrics() {
+        let mut heap = HeapFileBlock::new();
+        heap.page_size = 512;
+        heap.fill_factor = 0.5;
+        for i in 0..20 {
+            heap.insert(make_record(i, "x"));
+        }
+        let pages = heap.page_count() as f64;
+
+        let mut scan = Record::new();
+        scan.insert("_op".into(), "scan").unwrap();
+        let result = run(&mut heap, vec![scan]).await;
+
+        assert_eq!(result.outputs.get("stored").unwrap().len(), 20);
+        assert_eq!(*result.metrics.get("sequential_scans").unwrap(), 1.0);
+        assert_eq!(*result.metrics.get("pages_read").unwrap(), pages);
+    }
+
     #[test]
     fn test_delete_marks_dead() {
         let mut heap = HeapFileBlock::new();
@@ -785,6 +1230,113 @@ fn test_fragmentation() {
         assert!((heap.fragmentation_pct() - 20.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_vacuum_reclaims_dead_slots() {
+        let mut heap = HeapFileBlock::new();
+        heap.page_size = 512;
+        heap.fill_factor = 0.5;
+        let tids: Vec<TupleId> = (0..20).map(|i| heap.insert(make_record(i, "x"))).collect();
+        let pages_before = heap.page_count();
+        let used_before = heap.pages[0].used_bytes;
+
+        // Kill the whole first page plus one record on the second page.
+        let first_page: Vec<TupleId> = tids.iter().copied().filter(|t| t.page_id == 0).collect();
+        for &tid in &first_page {
+            heap.delete(tid);
+        }
+        let victim = tids.iter().copied().find(|t| t.page_id == 1).unwrap();
+        heap.delete(victim);
+
+        let result = heap.vacuum();
+        assert_eq!(result.slots_reclaimed, first_page.len() + 1);
+        assert_eq!(result.pages_freed, 1);
+        assert_eq!(heap.page_count(), pages_before - 1);
+        assert_eq!(heap.fragmentation_pct(), 0.0);
+        assert_eq!(heap.live_record_count(), 20 - first_page.len() - 1);
+        assert!(heap.pages[0].used_bytes < used_before);
+
+        // Every surviving record must be reachable at its remapped TupleId.
+        for (i, &old) in tids.iter().enumerate() {
+            if old.page_id == 0 || old == victim {
+                continue;
+            }
+            let new = result.remapped.get(&old).copied().unwrap_or(old);
+            let rec = heap.get(new).expect("survivor should be readable");
+            assert_eq!(rec.get::<i64>("id").unwrap(), Some(i as i64));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_auto_vacuums_above_threshold() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut heap = HeapFileBlock::new();
+        heap.fragmentation_threshold = 25.0;
+        let tids: Vec<TupleId> = (0..10).map(|i| heap.insert(make_record(i, "x"))).collect();
+        for &tid in &tids[..3] {
+            heap.delete(tid);
+        }
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Single(make_record(10, "y")));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = heap.execute(ctx).await.unwrap();
+        assert_eq!(*result.metrics.get("vacuum").unwrap(), 1.0);
+        assert_eq!(heap.fragmentation_pct(), 0.0);
+        assert_eq!(heap.live_record_count(), 8);
+    }
+
+    #[test]
+    fn test_free_space_map_tracks_pages() {
+        let mut heap = HeapFileBlock::new();
+        heap.page_size = 512;
+        heap.fill_factor = 0.5;
+        for i in 0..20 {
+            heap.insert(make_record(i, "x"));
+        }
+
+        let usable = heap.usable_page_bytes();
+        let fsm = heap.free_space_map();
+        assert_eq!(fsm.len(), heap.page_count());
+        for (page, &free) in heap.pages.iter().zip(fsm) {
+            assert_eq!(free, usable - page.used_bytes);
+        }
+
+        let rec_size = heap.estimated_record_size.unwrap();
+        let with_room = heap.pages_with_space(rec_size);
+        assert_eq!(with_room, vec![heap.page_count() - 1], "only the last page has room");
+        assert_eq!(heap.pages_with_space(0).len(), heap.page_count());
+    }
+
+    #[test]
+    fn test_insert_placement_is_first_fit() {
+        let mut heap = HeapFileBlock::new();
+        heap.page_size = 512;
+        heap.fill_factor = 0.5;
+        let tids: Vec<TupleId> = (0..20).map(|i| heap.insert(make_record(i, "x"))).collect();
+
+        // Vacuum empties room on page 0 and page 1; the next insert must go to
+        // the lowest page with space, exactly like the old linear scan.
+        heap.delete(tids[0]);
+        heap.delete(tids.iter().copied().find(|t| t.page_id == 1).unwrap());
+        heap.vacuum();
+        let rec_size = heap.estimated_record_size.unwrap();
+        let expected = (0..heap.page_count())
+            .find(|&p| heap.pages[p].used_bytes + rec_size <= heap.usable_page_bytes())
+            .unwrap();
+
+        let tid = heap.insert(make_record(100, "x"));
+        assert_eq!(tid.page_id, expected);
+        assert_eq!(expected, 0);
+    }
+
     #[test]
     fn test_fill_factor_respected() {
         let mut heap = HeapFileBlock::new();
@@ -833,14 +1385,85 @@ async fn test_block_execute() {
         assert_eq!(*result.metrics.get("total_live_records").unwrap(), 5.0);
     }
 
+    fn op_record(op: &str, tid: TupleId) -> Record {
+        let mut r = Record::new();
+        r.insert("_op".into(), op).unwrap();
+        r.insert("_tuple_id".into(), tid).unwrap();
+        r
+    }
+
+    async fn run(heap: &mut HeapFileBlock, records: Vec<Record>) -> ExecutionResult {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        heap.execute(ctx).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_execute_update_delete_get_ops() {
+        let mut heap = HeapFileBlock::new();
+        heap.fragmentation_threshold = 100.0;
+        let alice = heap.insert(make_record(1, "Alice"));
+        let bob = heap.insert(make_record(2, "Bob"));
+
+        let mut update = op_record("update", alice);
+        update.insert("id".into(), 1i64).unwrap();
+        update.insert("name".into(), "Alicia").unwrap();
+
+        let result = run(
+            &mut heap,
+            vec![update, op_record("delete", bob), op_record("get", alice)],
+        )
+        .await;
+
+        // The get targets the old (now dead) version of Alice.
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(*result.metrics.get("records_inserted").unwrap(), 1.0);
+        assert_eq!(*result.metrics.get("records_deleted").unwrap(), 2.0);
+
+        let PortValue::Stream(out) = result.outputs.get("stored").unwrap() else {
+            panic!("stored should be a stream");
+        };
+        assert_eq!(out.len(), 1);
+        let new_tid: TupleId = out[0].get("_tuple_id").unwrap().unwrap();
+        assert_ne!(new_tid, alice);
+        assert!(!out[0].data.contains_key("_op"));
+
+        let fetched = run(&mut heap, vec![op_record("get", new_tid)]).await;
+        assert!(fetched.errors.is_empty());
+        let PortValue::Stream(out) = fetched.outputs.get("stored").unwrap() else {
+            panic!("stored should be a stream");
+        };
+        assert_eq!(out[0].get::<String>("name").unwrap(), Some("Alicia".into()));
+        assert_eq!(heap.live_record_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_unknown_op() {
+        let mut heap = HeapFileBlock::new();
+        let mut r = make_record(1, "x");
+        r.insert("_op".into(), "upsert").unwrap();
+        let result = run(&mut heap, vec![r]).await;
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(heap.live_record_count(), 0);
+    }
+
     #[test]
     fn test_metadata() {
         let heap = HeapFileBlock::new();
         assert_eq!(heap.metadata().id, "heap-file-storage");
         assert_eq!(heap.metadata().category, BlockCategory::Storage);
         assert_eq!(heap.inputs().len(), 1);
-        assert_eq!(heap.outputs().len(), 1);
-        assert_eq!(heap.parameters().len(), 2);
+        assert_eq!(heap.outputs().len(), 2);
+        assert_eq!(heap.parameters().len(), 3);
     }
 
     #[tokio::test]
@@ -10,10 +10,25 @@
 //!    structure). When the memtable reaches `memtable_size`, it is frozen and
 //!    flushed as a new SSTable in Level 0.
 //! 2. **Compaction**: When Level 0 accumulates too many SSTables, they are
-//!    merged (size-tiered) into Level 1. The same process cascades upward.
+//!    merged into Level 1. The same process cascades upward. Size-tiered
+//!    compaction rewrites the whole next level into one SSTable; leveled
+//!    compaction keeps each level as non-overlapping SSTables and only
+//!    rewrites the ones whose key ranges overlap the incoming data.
 //! 3. **Read path**: Point lookups check the memtable first, then Level 0
 //!    SSTables (newest first), then higher levels. A **Bloom filter** on each
 //!    SSTable lets us skip tables that definitely don't contain the key.
+//! 4. **Range scans**: A k-way merge walks the memtable and every SSTable in
+//!    key order, keeping only the newest version of each key.
+//! 5. **Delete path**: Deletes write a **tombstone** into the memtable that
+//!    shadows older values. Tombstones are only dropped once compaction moves
+//!    them into the deepest level, where nothing older can remain beneath.
+//! 6. **Recovery**: With `wal_enabled`, every write is also appended to a
+//!    write-ahead log. A crash loses the memtable; `recover()` replays the log
+//!    entries that were never flushed. Flushing truncates the log.
+//!
+//! The `records` input declares a schema requiring `key_column`, so when run
+//! by the engine a stream whose records lack the key fails validation up
+//! front instead of storing nothing.
 //!
 //! ## Metrics tracked
 //!
@@ -26,12 +41,18 @@
 //! | `flushes` | Counter | Memtable flushes to Level 0 |
 //! | `bloom_true_negatives` | Counter | Reads skipped by bloom filter |
 //! | `bloom_false_positives` | Counter | Bloom filter said yes but key absent |
+//! | `tombstones_purged` | Counter | Tombstones dropped at the deepest level |
+//! | `bloom_memory_bytes` | Gauge | Memory used by all SSTable Bloom filters |
+//! | `wal_entries` | Gauge | Unflushed entries held in the write-ahead log |
 //! | `write_amplification` | Gauge | Total bytes written / user bytes |
-//! | `read_amplification` | Gauge | SSTables checked per point lookup |
+//! | `read_amplification` | Gauge | SSTables checked per lookup or range scan |
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::collections::{BTreeMap, HashMap};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::ops::Bound;
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
@@ -40,19 +61,24 @@
 use crate::core::constraint::{Constraint, Guarantee, GuaranteeType};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
-use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
+use crate::core::port::{
+    cmp_json, Port, PortDirection, PortSchema, PortType, PortValue, Record,
+};
 
 // ---------------------------------------------------------------------------
 // Internal SSTable model
 // ---------------------------------------------------------------------------
 
 /// A simple Bloom filter for probabilistic key membership testing.
-#[derive(Debug, Clone)]
+///
+/// Bits are packed 64 to a word.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BloomFilter {
-    bits: Vec<bool>,
+    bits: Vec<u64>,
+    num_bits: usize,
     num_hashes: usize,
 }
 
@@ -72,28 +98,42 @@ fn new(expected_items: usize, false_positive_rate: f64) -> Self {
             (k as usize).max(1).min(10)
         };
         Self {
-            bits: vec![false; bits_count],
+            bits: vec![0; bits_count.div_ceil(64)],
+            num_bits: bits_count,
             num_hashes,
         }
     }
 
+    fn set(&mut self, idx: usize) {
+        self.bits[idx / 64] |= 1u64 << (idx % 64);
+    }
+
+    fn test(&self, idx: usize) -> bool {
+        self.bits[idx / 64] & (1u64 << (idx % 64)) != 0
+    }
+
     fn insert(&mut self, key: &str) {
         for i in 0..self.num_hashes {
-            let idx = self.hash(key, i) % self.bits.len();
-            self.bits[idx] = true;
+            let idx = self.hash(key, i) % self.num_bits;
+            self.set(idx);
         }
     }
 
     fn might_contain(&self, key: &str) -> bool {
         for i in 0..self.num_hashes {
-            let idx = self.hash(key, i) % self.bits.len();
-            if !self.bits[idx] {
+            let idx = self.hash(key, i) % self.num_bits;
+            if !self.test(idx) {
                 return false;
             }
         }
         true
     }
 
+    /// Bytes used by the packed bit array.
+    fn memory_bytes(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+
     /// Simple hash: FNV-1a variant with seed.
     fn hash(&self, key: &str, seed: usize) -> usize {
         let mut h: u64 = 14695981039346656037u64.wrapping_add(seed as u64 * 2654435761);
@@ -105,11 +145,14 @@ fn hash(&self, key: &str, seed: usize) -> usize {
     }
 }
 
+/// A stored value: `Some` for a live value, `None` for a tombstone.
+type LsmValue = Option<JsonValue>;
+
 /// A sorted string table — an immutable, sorted collection of key-value pairs.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SSTable {
-    /// Entries sorted by key.
-    entries: Vec<(String, JsonValue)>,
+    /// Entries sorted by key. Tombstones are stored as `None`.
+    entries: Vec<(String, LsmValue)>,
     /// Bloom filter for fast negative lookups.
     bloom: BloomFilter,
     /// Approximate byte size of this SSTable.
@@ -117,13 +160,13 @@ struct SSTable {
 }
 
 impl SSTable {
-    fn from_entries(mut entries: Vec<(String, JsonValue)>) -> Self {
+    fn from_entries(mut entries: Vec<(String, LsmValue)>) -> Self {
         entries.sort_by(|a, b| a.0.cmp(&b.0));
         let mut bloom = BloomFilter::new(entries.len(), 0.01);
         let mut size_bytes = 0;
         for (k, v) in &entries {
             bloom.insert(k);
-            size_bytes += k.len() + v.to_string().len() + 16; // overhead
+            size_bytes += entry_size(k, v);
         }
         Self {
             entries,
@@ -132,7 +175,7 @@ fn from_entries(mut entries: Vec<(String, JsonValue)>) -> Self {
         }
     }
 
-    fn lookup(&self, key: &str) -> Option<&JsonValue> {
+    fn lookup(&self, key: &str) -> Option<&LsmValue> {
         // Binary search in sorted entries.
         self.entries
             .binary_search_by(|(k, _)| k.as_str().cmp(key))
@@ -143,12 +186,61 @@ fn lookup(&self, key: &str) -> Option<&JsonValue> {
     fn len(&self) -> usize {
         self.entries.len()
     }
+
+    fn min_key(&self) -> Option<&str> {
+        self.entries.first().map(|(k, _)| k.as_str())
+    }
+
+    fn max_key(&self) -> Option<&str> {
+        self.entries.last().map(|(k, _)| k.as_str())
+    }
+
+    /// Whether `key` falls within this SSTable's key range.
+    fn covers(&self, key: &str) -> bool {
+        match (self.min_key(), self.max_key()) {
+            (Some(lo), Some(hi)) => lo <= key && key <= hi,
+            _ => false,
+        }
+    }
+
+    /// Whether this SSTable's key range intersects `[lo, hi]`.
+    fn overlaps(&self, lo: &str, hi: &str) -> bool {
+        match (self.min_key(), self.max_key()) {
+            (Some(min), Some(max)) => min <= hi && lo <= max,
+            _ => false,
+        }
+    }
+}
+
+/// A write-ahead log record for a single memtable write.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WalEntry {
+    seq: u64,
+    key: String,
+    value: LsmValue,
+}
+
+/// Approximate on-disk size of an entry (key + value + fixed overhead).
+fn entry_size(key: &str, value: &LsmValue) -> usize {
+    let value_len = value.as_ref().map(|v| v.to_string().len()).unwrap_or(0);
+    key.len() + value_len + 16
 }
 
 // ---------------------------------------------------------------------------
 // LSMTreeBlock
 // ---------------------------------------------------------------------------
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompactionStrategy {
+    /// Merge a whole level into a single SSTable at the next level.
+    SizeTiered,
+    /// Keep each level >= 1 as non-overlapping SSTables; merge only overlaps.
+    Leveled,
+}
+
+/// Values accepted by the `compaction_strategy` parameter.
+const COMPACTION_STRATEGIES: &[&str] = &["size_tiered", "leveled"];
+
 pub struct LSMTreeBlock {
     metadata: BlockMetadata,
     input_ports: Vec<Port>,
@@ -161,18 +253,28 @@ pub struct LSMTreeBlock {
     level0_compaction_trigger: usize,
     size_ratio: usize,
     bloom_fp_rate: f64,
+    key_column: String,
+    compaction_strategy: CompactionStrategy,
+    wal_enabled: bool,
 
     // Internal state
-    /// Active memtable — sorted by key via BTreeMap.
-    memtable: BTreeMap<String, JsonValue>,
+    /// Active memtable — sorted by key via BTreeMap. Tombstones are `None`.
+    memtable: BTreeMap<String, LsmValue>,
     /// Levels of SSTables. Level 0 has the newest, unsorted-among-tables data.
     levels: Vec<Vec<SSTable>>,
+    /// Write-ahead log of writes not yet flushed to an SSTable.
+    wal: Vec<WalEntry>,
+    /// Sequence number of the most recent write.
+    last_seq: u64,
 
     // Counters
     flush_count: usize,
     compaction_count: usize,
     bloom_true_negatives: usize,
     bloom_false_positives: usize,
+    tombstones_purged: usize,
+    lookups: usize,
+    tables_checked: usize,
     total_bytes_written: usize,
     user_bytes_written: usize,
 }
@@ -181,7 +283,7 @@ impl LSMTreeBlock {
     pub fn new() -> Self {
         Self {
             metadata: Self::build_metadata(),
-            input_ports: Self::build_inputs(),
+            input_ports: Self::build_inputs("id"),
             output_ports: Self::build_outputs(),
             params: Self::build_parameters(),
             metric_defs: Self::build_metrics(),
@@ -189,12 +291,20 @@ pub fn new() -> Self {
             level0_compaction_trigger: 4,
             size_ratio: 10,
             bloom_fp_rate: 0.01,
+            key_column: "id".into(),
+            compaction_strategy: CompactionStrategy::SizeTiered,
+            wal_enabled: false,
             memtable: BTreeMap::new(),
             levels: vec![Vec::new(); 4], // L0..L3
+            wal: Vec::new(),
+            last_seq: 0,
             flush_count: 0,
             compaction_count: 0,
             bloom_true_negatives: 0,
             bloom_false_positives: 0,
+            tombstones_purged: 0,
+            lookups: 0,
+            tables_checked: 0,
             total_bytes_written: 0,
             user_bytes_written: 0,
         }
@@ -237,20 +347,36 @@ fn build_metadata() -> BlockMetadata {
                               d. Increment flush_count\n    \
                               e. If Level 0 has >= level0_compaction_trigger SSTables:\n      \
                                  Trigger compaction of Level 0 into Level 1\n\n\
-                           COMPACTION (level L):\n  \
+                           SIZE-TIERED COMPACTION (level L):\n  \
                            1. Collect all entries from all SSTables at level L\n  \
                            2. Merge with all entries at level L+1\n  \
                            3. Sort by key, deduplicate (keep newest value)\n  \
                            4. Write as a new SSTable at level L+1\n  \
                            5. Track total_bytes_written for amplification\n  \
                            6. Check if L+1 also needs compaction (cascading)\n\n\
+                           LEVELED COMPACTION (level L):\n  \
+                           1. Pick inputs: all of L0, or one SSTable from level L >= 1\n  \
+                           2. Find the SSTables at L+1 whose key ranges overlap the inputs\n  \
+                           3. Merge inputs with only those overlapping SSTables\n  \
+                           4. Split the result into memtable_size-entry SSTables at L+1\n  \
+                           5. While L+1 exceeds its capacity, compact L+1 the same way\n\n\
                            READ (get key):\n  \
                            1. Check memtable — return if found (newest data)\n  \
                            2. For each level, newest SSTable first:\n    \
                               a. Check Bloom filter — skip if definitely absent\n    \
                               b. Binary search SSTable entries\n    \
-                              c. Return if found\n  \
-                           3. Return None if not found in any level"
+                              c. Return if found (None if the entry is a tombstone)\n  \
+                           3. Return None if not found in any level\n\n\
+                           RANGE SCAN (start, end):\n  \
+                           1. Open a sorted cursor on the memtable and every SSTable\n  \
+                           2. Rank sources newest first (memtable, L0 newest..oldest, L1, ...)\n  \
+                           3. Repeatedly pop the smallest (key, rank) from a min-heap\n  \
+                           4. Emit the first (newest) version of each key, skip older ones\n  \
+                           5. Drop keys whose newest version is a tombstone\n\n\
+                           DELETE (delete key):\n  \
+                           1. Insert a tombstone for key into the memtable\n  \
+                           2. The tombstone shadows older values in lower levels\n  \
+                           3. Compaction drops the tombstone only when it reaches the deepest level"
                     .into(),
                 complexity: Complexity {
                     time: "Write O(log n) amortized, Point read O(L * log n) where L = levels"
@@ -323,6 +449,22 @@ fn build_metadata() -> BlockMetadata {
                       and how duplicates are resolved (latest value wins). Choose the column \
                       that you will most frequently look up by. Default is 'id'."
                          .into()),
+                    ("compaction_strategy".into(),
+                     "How SSTables are merged between levels. 'size_tiered' merges an entire \
+                      level into one SSTable at the next level — cheap in write amplification \
+                      for small trees but every compaction rewrites the whole next level. \
+                      'leveled' keeps each level as non-overlapping SSTables and only rewrites \
+                      the tables that overlap the incoming data, so a point read checks at most \
+                      one SSTable per level (lower read amplification) at the cost of more \
+                      frequent, smaller compactions. Default is 'size_tiered'."
+                         .into()),
+                    ("wal_enabled".into(),
+                     "When enabled, every write is appended to a write-ahead log before it \
+                      reaches the memtable. After a simulated crash the memtable is empty, and \
+                      recovery replays the unflushed log entries to rebuild it. Each flush \
+                      truncates the log. Disable to see how much data a crash loses without a \
+                      WAL. Default is false."
+                         .into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -373,30 +515,55 @@ fn build_metadata() -> BlockMetadata {
         }
     }
 
-    fn build_inputs() -> Vec<Port> {
-        vec![Port {
-            id: "records".into(),
-            name: "Records".into(),
-            port_type: PortType::DataStream,
-            direction: PortDirection::Input,
-            required: true,
-            multiple: false,
-            description: "Stream of records to store (must have a key column)".into(),
-            schema: None,
-        }]
+    fn build_inputs(key_column: &str) -> Vec<Port> {
+        vec![
+            Port {
+                id: "records".into(),
+                name: "Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Input,
+                required: true,
+                multiple: false,
+                description: "Stream of records to store (must have a key column)".into(),
+                schema: None,
+            }
+            .with_schema(PortSchema::required_columns(&[key_column])),
+            Port {
+                id: "range".into(),
+                name: "Range".into(),
+                port_type: PortType::SingleValue,
+                direction: PortDirection::Input,
+                required: false,
+                multiple: false,
+                description: "Optional {start, end} key range to scan after writing".into(),
+                schema: None,
+            },
+        ]
     }
 
     fn build_outputs() -> Vec<Port> {
-        vec![Port {
-            id: "stored".into(),
-            name: "Stored Records".into(),
-            port_type: PortType::DataStream,
-            direction: PortDirection::Output,
-            required: false,
-            multiple: true,
-            description: "Records after storage with level metadata".into(),
-            schema: None,
-        }]
+        vec![
+            Port {
+                id: "stored".into(),
+                name: "Stored Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Records after storage with level metadata".into(),
+                schema: None,
+            },
+            Port {
+                id: "range_results".into(),
+                name: "Range Results".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Live records with start <= key <= end, in key order".into(),
+                schema: None,
+            },
+        ]
     }
 
     fn build_parameters() -> Vec<Parameter> {
@@ -459,6 +626,26 @@ fn build_parameters() -> Vec<Parameter> {
                 constraints: None,
                 ui_hint: Some(ParameterUIHint::new(WidgetType::Input)),
             },
+            Parameter {
+                id: "compaction_strategy".into(),
+                name: "Compaction Strategy".into(),
+                param_type: ParameterType::Enum,
+                description: "How SSTables are merged: size_tiered or leveled".into(),
+                default_value: ParameterValue::String("size_tiered".into()),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_choices(COMPACTION_STRATEGIES)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+            },
+            Parameter {
+                id: "wal_enabled".into(),
+                name: "WAL Enabled".into(),
+                param_type: ParameterType::Boolean,
+                description: "Log writes so the memtable can be recovered after a crash".into(),
+                default_value: ParameterValue::Boolean(false),
+                required: false,
+                constraints: None,
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Checkbox)),
+            },
         ]
     }
 
@@ -520,6 +707,38 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Bloom filter said yes but key was absent".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "tombstones_purged".into(),
+                name: "Tombstones Purged".into(),
+                metric_type: MetricType::Counter,
+                unit: "entries".into(),
+                description: "Tombstones dropped by compaction at the deepest level".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "records_deleted".into(),
+                name: "Records Deleted".into(),
+                metric_type: MetricType::Counter,
+                unit: "records".into(),
+                description: "Delete operations applied as tombstones".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "bloom_memory_bytes".into(),
+                name: "Bloom Filter Memory".into(),
+                metric_type: MetricType::Gauge,
+                unit: "bytes".into(),
+                description: "Memory used by all SSTable Bloom filters".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "wal_entries".into(),
+                name: "WAL Entries".into(),
+                metric_type: MetricType::Gauge,
+                unit: "entries".into(),
+                description: "Unflushed entries held in the write-ahead log".into(),
+                aggregations: vec![AggregationType::Max],
+            },
             MetricDefinition {
                 id: "write_amplification".into(),
                 name: "Write Amplification".into(),
@@ -528,6 +747,22 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Total bytes written / user bytes written".into(),
                 aggregations: vec![AggregationType::Max],
             },
+            MetricDefinition {
+                id: "read_amplification".into(),
+                name: "Read Amplification".into(),
+                metric_type: MetricType::Gauge,
+                unit: "tables".into(),
+                description: "SSTables searched per point lookup or range scan".into(),
+                aggregations: vec![AggregationType::Max],
+            },
+            MetricDefinition {
+                id: "range_scan_results".into(),
+                name: "Range Scan Results".into(),
+                metric_type: MetricType::Histogram,
+                unit: "records".into(),
+                description: "Records returned per range scan".into(),
+                aggregations: vec![AggregationType::Avg, AggregationType::Max],
+            },
         ]
     }
 
@@ -535,8 +770,24 @@ fn build_metrics() -> Vec<MetricDefinition> {
 
     /// Insert a key-value pair into the memtable.
     pub fn put(&mut self, key: String, value: JsonValue) {
-        let entry_size = key.len() + value.to_string().len() + 16;
-        self.user_bytes_written += entry_size;
+        self.write_entry(key, Some(value));
+    }
+
+    /// Delete a key by inserting a tombstone into the memtable.
+    pub fn delete(&mut self, key: String) {
+        self.write_entry(key, None);
+    }
+
+    fn write_entry(&mut self, key: String, value: LsmValue) {
+        self.user_bytes_written += entry_size(&key, &value);
+        self.last_seq += 1;
+        if self.wal_enabled {
+            self.wal.push(WalEntry {
+                seq: self.last_seq,
+                key: key.clone(),
+                value: value.clone(),
+            });
+        }
         self.memtable.insert(key, value);
 
         if self.memtable.len() >= self.memtable_size {
@@ -546,85 +797,288 @@ pub fn put(&mut self, key: String, value: JsonValue) {
 
     /// Point lookup — checks memtable, then L0 (newest first), then higher levels.
     pub fn get(&mut self, key: &str) -> Option<JsonValue> {
+        self.lookups += 1;
+
         // 1. Check memtable
         if let Some(v) = self.memtable.get(key) {
-            return Some(v.clone());
+            return v.clone();
         }
 
         // 2. Check each level, newest SSTables first
-        let mut tables_checked = 0;
         for level in &self.levels {
             for sst in level.iter().rev() {
+                if !sst.covers(key) {
+                    continue;
+                }
                 if !sst.bloom.might_contain(key) {
                     self.bloom_true_negatives += 1;
                     continue;
                 }
-                tables_checked += 1;
+                self.tables_checked += 1;
                 if let Some(v) = sst.lookup(key) {
-                    return Some(v.clone());
+                    return v.clone();
                 } else {
                     self.bloom_false_positives += 1;
                 }
             }
         }
-        let _ = tables_checked;
         None
     }
 
+    /// Range scan — returns live entries where start <= key <= end, in key order.
+    ///
+    /// Performs a k-way merge over the memtable and every SSTable. Sources are
+    /// ranked newest first, so when a key appears in several places the
+    /// newest version wins; keys whose newest version is a tombstone are skipped.
+    pub fn range_scan(&mut self, start: &str, end: &str) -> Vec<(String, JsonValue)> {
+        if start > end {
+            return Vec::new();
+        }
+        self.merge_scan(Some(start), Some(end))
+    }
+
+    /// Range scan over keys written by `execute`, which stores each key as its
+    /// JSON text. Keys and bounds are compared with [`cmp_json`], so integer
+    /// keys order numerically (`2 < 10`) rather than by text (`"10" < "2"`).
+    pub fn range_scan_values(
+        &mut self,
+        start: &JsonValue,
+        end: &JsonValue,
+    ) -> Vec<(String, JsonValue)> {
+        let mut matches: Vec<(JsonValue, String, JsonValue)> = self
+            .merge_scan(None, None)
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let decoded = serde_json::from_str(&key)
+                    .unwrap_or_else(|_| JsonValue::String(key.clone()));
+                let in_range = cmp_json(start, &decoded).is_le()
+                    && cmp_json(&decoded, end).is_le();
+                in_range.then_some((decoded, key, value))
+            })
+            .collect();
+        matches.sort_by(|a, b| cmp_json(&a.0, &b.0));
+        matches.into_iter().map(|(_, key, value)| (key, value)).collect()
+    }
+
+    /// K-way merge of the live entries between two inclusive text bounds,
+    /// where `None` leaves that side of the range open. Each scan counts as
+    /// one read for `read_amplification`, charged every SSTable it walks.
+    fn merge_scan(&mut self, start: Option<&str>, end: Option<&str>) -> Vec<(String, JsonValue)> {
+        let below = |k: &str| start.is_some_and(|s| k < s);
+        let above = |k: &str| end.is_some_and(|e| k > e);
+
+        // Sorted runs, newest first: memtable, L0 newest..oldest, then L1, L2, ...
+        let mut runs: Vec<Vec<(&String, &LsmValue)>> = Vec::new();
+        runs.push(
+            self.memtable
+                .range::<str, _>((
+                    start.map_or(Bound::Unbounded, Bound::Included),
+                    end.map_or(Bound::Unbounded, Bound::Included),
+                ))
+                .collect(),
+        );
+        for level in &self.levels {
+            for sst in level.iter().rev() {
+                if sst.max_key().is_none_or(below) || sst.min_key().is_none_or(above) {
+                    continue;
+                }
+                let from = sst.entries.partition_point(|(k, _)| below(k));
+                runs.push(
+                    sst.entries[from..]
+                        .iter()
+                        .take_while(|(k, _)| !above(k))
+                        .map(|(k, v)| (k, v))
+                        .collect(),
+                );
+            }
+        }
+
+        // Min-heap of (key, rank, position) — ties on key pop the newest run first.
+        let mut heap = BinaryHeap::new();
+        for (rank, run) in runs.iter().enumerate() {
+            if let Some((k, _)) = run.first() {
+                heap.push(Reverse((*k, rank, 0usize)));
+            }
+        }
+
+        let mut results = Vec::new();
+        let mut last_key: Option<&String> = None;
+        while let Some(Reverse((key, rank, pos))) = heap.pop() {
+            if let Some((next_key, _)) = runs[rank].get(pos + 1) {
+                heap.push(Reverse((*next_key, rank, pos + 1)));
+            }
+            if last_key == Some(key) {
+                continue; // Older version of a key already emitted.
+            }
+            last_key = Some(key);
+            if let Some(value) = runs[rank][pos].1 {
+                results.push((key.clone(), value.clone()));
+            }
+        }
+
+        self.lookups += 1;
+        self.tables_checked += runs.len() - 1;
+        results
+    }
+
     /// Flush the active memtable to Level 0 as a new SSTable.
     fn flush_memtable(&mut self) {
         if self.memtable.is_empty() {
             return;
         }
 
-        let entries: Vec<(String, JsonValue)> = self.memtable.drain_filter_compat();
+        let entries: Vec<(String, LsmValue)> = self.memtable.drain_filter_compat();
         let sst = SSTable::from_entries(entries);
         self.total_bytes_written += sst.size_bytes;
         self.levels[0].push(sst);
         self.flush_count += 1;
 
+        // Everything up to the current sequence number is now durable.
+        let flushed_seq = self.last_seq;
+        self.wal.retain(|e| e.seq > flushed_seq);
+
         // Check if L0 needs compaction.
         if self.levels[0].len() >= self.level0_compaction_trigger {
             self.compact_level(0);
         }
     }
 
-    /// Merge all SSTables at the given level into the next level.
+    /// Compact the given level into the next one using the configured strategy.
     fn compact_level(&mut self, level: usize) {
         if level + 1 >= self.levels.len() {
             // Add a new level if needed.
             self.levels.push(Vec::new());
         }
 
-        // Collect all entries from this level.
-        let mut all_entries: Vec<(String, JsonValue)> = Vec::new();
-        for sst in self.levels[level].drain(..) {
-            all_entries.extend(sst.entries);
+        match self.compaction_strategy {
+            CompactionStrategy::SizeTiered => self.compact_size_tiered(level),
+            CompactionStrategy::Leveled => self.compact_leveled(level),
         }
+    }
 
-        // Also merge with existing entries at the next level.
-        for sst in self.levels[level + 1].drain(..) {
-            all_entries.extend(sst.entries);
-        }
+    /// Merge all SSTables at the given level into the next level.
+    fn compact_size_tiered(&mut self, level: usize) {
+        // Newest SSTables first so deduplication keeps the latest value.
+        let mut inputs: Vec<SSTable> = self.levels[level].drain(..).rev().collect();
+        inputs.extend(self.levels[level + 1].drain(..).rev());
 
-        // Sort and deduplicate (keep latest value for duplicate keys).
-        all_entries.sort_by(|a, b| a.0.cmp(&b.0));
-        all_entries.dedup_by(|a, b| a.0 == b.0);
+        let merged = self.merge_tables(inputs, level + 1);
 
         // Create a new SSTable at the next level.
-        let sst = SSTable::from_entries(all_entries);
+        let sst = SSTable::from_entries(merged);
         self.total_bytes_written += sst.size_bytes;
         self.levels[level + 1].push(sst);
         self.compaction_count += 1;
 
         // Check if next level also needs compaction.
-        let max_tables = self.level0_compaction_trigger * self.size_ratio.pow(level as u32 + 1);
-        let next_total_entries: usize = self.levels[level + 1].iter().map(|s| s.len()).sum();
-        if next_total_entries > max_tables * self.memtable_size {
+        if self.level_entries(level + 1) > self.level_capacity(level + 1) {
             self.compact_level(level + 1);
         }
     }
 
+    /// Merge L0 (or one SSTable from level >= 1) into only the overlapping
+    /// SSTables of the next level, keeping that level non-overlapping.
+    fn compact_leveled(&mut self, level: usize) {
+        let inputs: Vec<SSTable> = if level == 0 {
+            self.levels[0].drain(..).rev().collect()
+        } else if self.levels[level].is_empty() {
+            return;
+        } else {
+            vec![self.levels[level].remove(0)]
+        };
+
+        let lo = inputs.iter().filter_map(|s| s.min_key()).min().map(str::to_string);
+        let hi = inputs.iter().filter_map(|s| s.max_key()).max().map(str::to_string);
+        let (lo, hi) = match (lo, hi) {
+            (Some(lo), Some(hi)) => (lo, hi),
+            _ => return,
+        };
+
+        let (overlapping, mut untouched): (Vec<SSTable>, Vec<SSTable>) =
+            std::mem::take(&mut self.levels[level + 1])
+                .into_iter()
+                .partition(|s| s.overlaps(&lo, &hi));
+
+        let mut all_inputs = inputs;
+        all_inputs.extend(overlapping);
+        let merged = self.merge_tables(all_inputs, level + 1);
+
+        // Split the merged run into fixed-size SSTables.
+        for chunk in merged.chunks(self.memtable_size.max(1)) {
+            let sst = SSTable::from_entries(chunk.to_vec());
+            self.total_bytes_written += sst.size_bytes;
+            untouched.push(sst);
+        }
+        untouched.sort_by(|a, b| a.min_key().cmp(&b.min_key()));
+        self.levels[level + 1] = untouched;
+        self.compaction_count += 1;
+
+        while self.level_entries(level + 1) > self.level_capacity(level + 1) {
+            self.compact_level(level + 1);
+        }
+    }
+
+    /// Merge the entries of `tables` (ordered newest first) into a single
+    /// sorted, deduplicated run destined for `target_level`.
+    fn merge_tables(
+        &mut self,
+        tables: Vec<SSTable>,
+        target_level: usize,
+    ) -> Vec<(String, LsmValue)> {
+        let mut all_entries: Vec<(String, LsmValue)> = Vec::new();
+        for sst in tables {
+            all_entries.extend(sst.entries);
+        }
+
+        // Stable sort and deduplicate (keep the first, i.e. newest, value).
+        all_entries.sort_by(|a, b| a.0.cmp(&b.0));
+        all_entries.dedup_by(|a, b| a.0 == b.0);
+
+        // Tombstones can only be dropped once nothing older can live below them.
+        let is_deepest = self.levels[target_level + 1..].iter().all(|l| l.is_empty());
+        if is_deepest {
+            let before = all_entries.len();
+            all_entries.retain(|(_, v)| v.is_some());
+            self.tombstones_purged += before - all_entries.len();
+        }
+
+        all_entries
+    }
+
+    /// Total entries stored at a level.
+    fn level_entries(&self, level: usize) -> usize {
+        self.levels[level].iter().map(|s| s.len()).sum()
+    }
+
+    /// Maximum entries a level >= 1 may hold before it is compacted further.
+    fn level_capacity(&self, level: usize) -> usize {
+        self.level0_compaction_trigger * self.size_ratio.pow(level as u32) * self.memtable_size
+    }
+
+    /// Simulate a crash: the in-memory memtable is lost, SSTables and the WAL survive.
+    pub fn simulate_crash(&mut self) {
+        self.memtable.clear();
+    }
+
+    /// Rebuild the memtable by replaying unflushed WAL entries in sequence
+    /// order, flushing it if the replay leaves it at or over `memtable_size`
+    /// (e.g. after the size was lowered). Returns the number of entries replayed.
+    pub fn recover(&mut self) -> usize {
+        let replayed = self.wal.len();
+        for entry in &self.wal {
+            self.memtable.insert(entry.key.clone(), entry.value.clone());
+        }
+        if self.memtable.len() >= self.memtable_size {
+            self.flush_memtable();
+        }
+        replayed
+    }
+
+    /// Number of unflushed entries in the write-ahead log.
+    pub fn wal_len(&self) -> usize {
+        self.wal.len()
+    }
+
     /// Total number of SSTables across all levels.
     pub fn total_sstables(&self) -> usize {
         self.levels.iter().map(|l| l.len()).sum()
@@ -646,6 +1100,38 @@ pub fn total_entries(&self) -> usize {
         self.memtable.len() + sst_entries
     }
 
+    /// Turn a stored value back into a record for output.
+    fn value_to_record(key: &str, value: JsonValue) -> Record {
+        match value {
+            JsonValue::Object(map) => Record::from_map(map.into_iter().collect()),
+            other => {
+                let mut record = Record::new();
+                let _ = record.insert("key".into(), key);
+                let _ = record.insert("value".into(), other);
+                record
+            }
+        }
+    }
+
+    /// Memory used by the Bloom filters of every SSTable.
+    pub fn bloom_memory_bytes(&self) -> usize {
+        self.levels
+            .iter()
+            .flat_map(|l| l.iter())
+            .map(|s| s.bloom.memory_bytes())
+            .sum()
+    }
+
+    /// Read amplification — average SSTables searched per point lookup or
+    /// range scan.
+    pub fn read_amplification(&self) -> f64 {
+        if self.lookups == 0 {
+            0.0
+        } else {
+            self.tables_checked as f64 / self.lookups as f64
+        }
+    }
+
     /// Write amplification factor.
     pub fn write_amplification(&self) -> f64 {
         if self.user_bytes_written == 0 {
@@ -719,38 +1205,29 @@ async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("memtable_size") {
-            let v = val.as_integer().ok_or_else(|| {
-                BlockError::InvalidParameter("memtable_size must be an integer".into())
-            })? as usize;
-            if v < 10 || v > 100000 {
-                return Err(BlockError::InvalidParameter(
-                    "memtable_size must be between 10 and 100000".into(),
-                ));
-            }
-            self.memtable_size = v;
-        }
-        if let Some(val) = params.get("level0_compaction_trigger") {
-            let v = val.as_integer().ok_or_else(|| {
-                BlockError::InvalidParameter("level0_compaction_trigger must be an integer".into())
-            })? as usize;
-            if v < 2 || v > 20 {
-                return Err(BlockError::InvalidParameter(
-                    "level0_compaction_trigger must be between 2 and 20".into(),
-                ));
-            }
-            self.level0_compaction_trigger = v;
-        }
-        if let Some(val) = params.get("size_ratio") {
-            let v = val.as_integer().ok_or_else(|| {
-                BlockError::InvalidParameter("size_ratio must be an integer".into())
-            })? as usize;
-            if v < 2 || v > 20 {
-                return Err(BlockError::InvalidParameter(
-                    "size_ratio must be between 2 and 20".into(),
-                ));
-            }
-            self.size_ratio = v;
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("memtable_size").and_then(ParameterValue::as_integer) {
+            self.memtable_size = v as usize;
+        }
+        let trigger = params.get("level0_compaction_trigger").and_then(ParameterValue::as_integer);
+        if let Some(v) = trigger {
+            self.level0_compaction_trigger = v as usize;
+        }
+        if let Some(v) = params.get("size_ratio").and_then(ParameterValue::as_integer) {
+            self.size_ratio = v as usize;
+        }
+        if let Some(v) = params.get("key_column").and_then(ParameterValue::as_string) {
+            self.key_column = v.to_string();
+            self.input_ports = Self::build_inputs(&self.key_column);
+        }
+        if let Some(s) = enum_param(&params, "compaction_strategy", COMPACTION_STRATEGIES)? {
+            self.compaction_strategy = match s {
+                "size_tiered" => CompactionStrategy::SizeTiered,
+                _ => CompactionStrategy::Leveled,
+            };
+        }
+        if let Some(v) = params.get("wal_enabled").and_then(ParameterValue::as_bool) {
+            self.wal_enabled = v;
         }
         Ok(())
     }
@@ -778,14 +1255,26 @@ async fn execute(
         };
 
         let mut output_records = Vec::with_capacity(records.len());
+        let mut errors = Vec::new();
 
         for record in records {
-            // Use the "id" field (or first field) as key.
-            let key = record
-                .data
-                .get("id")
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| format!("key_{}", self.total_entries()));
+            let is_delete = record.data.get("_op").and_then(|v| v.as_str()) == Some("delete");
+            let key = match record.data.get(&self.key_column) {
+                Some(v) => v.to_string(),
+                None => {
+                    errors.push(BlockError::InvalidInput(format!(
+                        "record is missing key column '{}'",
+                        self.key_column
+                    )));
+                    continue;
+                }
+            };
+
+            if is_delete {
+                self.delete(key);
+                context.metrics.increment("records_deleted");
+                continue;
+            }
 
             let value = serde_json::to_value(&record.data).unwrap_or(JsonValue::Null);
             self.put(key, value);
@@ -797,6 +1286,24 @@ async fn execute(
         // Flush any remaining memtable entries.
         self.flush_memtable();
 
+        // Serve an optional range scan over everything written so far.
+        let mut range_records = Vec::new();
+        if let Some(PortValue::Single(range)) = context.inputs.get("range") {
+            match (range.data.get("start"), range.data.get("end")) {
+                (Some(start), Some(end)) => {
+                    for (key, value) in self.range_scan_values(start, end) {
+                        range_records.push(Self::value_to_record(&key, value));
+                    }
+                    context
+                        .metrics
+                        .record("range_scan_results", range_records.len() as f64);
+                }
+                _ => errors.push(BlockError::InvalidInput(
+                    "range input must have 'start' and 'end' fields".into(),
+                )),
+            }
+        }
+
         // Record gauges.
         context
             .metrics
@@ -821,24 +1328,37 @@ async fn execute(
             "bloom_false_positives",
             self.bloom_false_positives as f64,
         );
+        context
+            .metrics
+            .record("tombstones_purged", self.tombstones_purged as f64);
         context
             .metrics
             .record("write_amplification", self.write_amplification());
+        context
+            .metrics
+            .record("read_amplification", self.read_amplification());
+        context
+            .metrics
+            .record("bloom_memory_bytes", self.bloom_memory_bytes() as f64);
+        context.metrics.record("wal_entries", self.wal.len() as f64);
 
         let mut outputs = HashMap::new();
         outputs.insert("stored".into(), PortValue::Stream(output_records));
+        outputs.insert("range_results".into(), PortValue::Stream(range_records));
 
         let mut metrics_summary = HashMap::new();
         metrics_summary.insert("total_sstables".into(), self.total_sstables() as f64);
         metrics_summary.insert("level_count".into(), self.non_empty_levels() as f64);
         metrics_summary.insert("flushes".into(), self.flush_count as f64);
         metrics_summary.insert("compactions".into(), self.compaction_count as f64);
+        metrics_summary.insert("tombstones_purged".into(), self.tombstones_purged as f64);
         metrics_summary.insert("write_amplification".into(), self.write_amplification());
+        metrics_summary.insert("read_amplification".into(), self.read_amplification());
 
         Ok(ExecutionResult {
             outputs,
             metrics: metrics_summary,
-            errors: vec![],
+            errors,
         })
     }
 
@@ -858,12 +1378,32 @@ fn validate(&self, inputs: &HashMap<String, PortValue>) -> ValidationResult {
         }
     }
 
+    fn state_round_trips(&self) -> bool {
+        true
+    }
+
     fn get_state(&self) -> BlockState {
         let mut state = BlockState::new();
         let _ = state.insert("memtable_size".into(), self.memtable_size);
+        let _ = state.insert("key_column".into(), self.key_column.clone());
         let _ = state.insert("memtable_entries".into(), self.memtable.len());
         let _ = state.insert("total_sstables".into(), self.total_sstables());
         let _ = state.insert("total_entries".into(), self.total_entries());
+        // The tree itself, so a snapshot can resume a half-built tree.
+        let _ = state.insert("memtable".into(), &self.memtable);
+        let _ = state.insert("levels".into(), &self.levels);
+        let _ = state.insert("wal".into(), &self.wal);
+        let _ = state.insert("last_seq".into(), self.last_seq);
+        // Counters, so metrics carry on from where the snapshot left off.
+        let _ = state.insert("flush_count".into(), self.flush_count);
+        let _ = state.insert("compaction_count".into(), self.compaction_count);
+        let _ = state.insert("bloom_true_negatives".into(), self.bloom_true_negatives);
+        let _ = state.insert("bloom_false_positives".into(), self.bloom_false_positives);
+        let _ = state.insert("tombstones_purged".into(), self.tombstones_purged);
+        let _ = state.insert("lookups".into(), self.lookups);
+        let _ = state.insert("tables_checked".into(), self.tables_checked);
+        let _ = state.insert("total_bytes_written".into(), self.total_bytes_written);
+        let _ = state.insert("user_bytes_written".into(), self.user_bytes_written);
         state
     }
 
@@ -871,6 +1411,37 @@ fn set_state(&mut self, state: BlockState) -> Result<(), BlockError> {
         if let Ok(Some(ms)) = state.get::<usize>("memtable_size") {
             self.memtable_size = ms;
         }
+        if let Ok(Some(kc)) = state.get::<String>("key_column") {
+            self.key_column = kc;
+            self.input_ports = Self::build_inputs(&self.key_column);
+        }
+        if let Ok(Some(memtable)) = state.get::<BTreeMap<String, LsmValue>>("memtable") {
+            self.memtable = memtable;
+        }
+        if let Ok(Some(levels)) = state.get::<Vec<Vec<SSTable>>>("levels") {
+            self.levels = levels;
+        }
+        if let Ok(Some(wal)) = state.get::<Vec<WalEntry>>("wal") {
+            self.wal = wal;
+        }
+        if let Ok(Some(seq)) = state.get::<u64>("last_seq") {
+            self.last_seq = seq;
+        }
+        for (key, counter) in [
+            ("flush_count", &mut self.flush_count),
+            ("compaction_count", &mut self.compaction_count),
+            ("bloom_true_negatives", &mut self.bloom_true_negatives),
+            ("bloom_false_positives", &mut self.bloom_false_positives),
+            ("tombstones_purged", &mut self.tombstones_purged),
+            ("lookups", &mut self.lookups),
+            ("tables_checked", &mut self.tables_checked),
+            ("total_bytes_written", &mut self.total_bytes_written),
+            ("user_bytes_written", &mut self.user_bytes_written),
+        ] {
+            if let Ok(Some(n)) = state.get::<usize>(key) {
+                *counter = n;
+            }
+        }
         Ok(())
     }
 }
@@ -973,18 +1544,26 @@ fn test_bloom_filter_false_positive_rate() {
         );
     }
 
+    #[test]
+    fn test_bloom_filter_is_bit_packed() {
+        let bloom = BloomFilter::new(1000, 0.01);
+        // One bit per position, rounded up to whole 64-bit words.
+        assert_eq!(bloom.memory_bytes(), bloom.num_bits.div_ceil(64) * 8);
+        assert!(bloom.memory_bytes() < bloom.num_bits / 4);
+    }
+
     #[test]
     fn test_sstable_sorted_lookup() {
         let entries = vec![
-            ("c".into(), json!(3)),
-            ("a".into(), json!(1)),
-            ("b".into(), json!(2)),
+            ("c".into(), Some(json!(3))),
+            ("a".into(), Some(json!(1))),
+            ("b".into(), Some(json!(2))),
         ];
         let sst = SSTable::from_entries(entries);
 
-        assert_eq!(sst.lookup("a"), Some(&json!(1)));
-        assert_eq!(sst.lookup("b"), Some(&json!(2)));
-        assert_eq!(sst.lookup("c"), Some(&json!(3)));
+        assert_eq!(sst.lookup("a"), Some(&Some(json!(1))));
+        assert_eq!(sst.lookup("b"), Some(&Some(json!(2))));
+        assert_eq!(sst.lookup("c"), Some(&Some(json!(3))));
         assert_eq!(sst.lookup("d"), None);
     }
 
@@ -1007,6 +1586,160 @@ fn test_write_amplification() {
         );
     }
 
+    #[test]
+    fn test_read_amplification() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.memtable_size = 10;
+        assert_eq!(lsm.read_amplification(), 0.0);
+
+        // Memtable hits search no SSTables.
+        lsm.put("mem".into(), json!(1));
+        lsm.get("mem");
+        assert_eq!(lsm.read_amplification(), 0.0);
+
+        // L0 tables are searched newest first, so the newest copy is found after
+        // searching a single table.
+        for version in 0..3 {
+            lsm.put("key".into(), json!(version));
+            lsm.put("mem".into(), json!(version));
+            lsm.flush_memtable();
+        }
+        lsm.get("key");
+        assert_eq!(lsm.tables_checked, 1, "Newest L0 table should satisfy the read");
+        assert_eq!(lsm.read_amplification(), 0.5);
+    }
+
+    #[test]
+    fn test_leveled_has_lower_read_amplification() {
+        fn run(strategy: CompactionStrategy) -> f64 {
+            let mut lsm = LSMTreeBlock::new();
+            lsm.compaction_strategy = strategy;
+            lsm.memtable_size = 10;
+            lsm.level0_compaction_trigger = 4;
+            lsm.size_ratio = 2;
+            for i in 0..500 {
+                lsm.put(format!("key_{:04}", (i * 37) % 500), json!(i));
+            }
+            for i in 0..500 {
+                lsm.get(&format!("key_{:04}", i));
+            }
+            lsm.read_amplification()
+        }
+
+        let tiered = run(CompactionStrategy::SizeTiered);
+        let leveled = run(CompactionStrategy::Leveled);
+        assert!(leveled > 0.0);
+        assert!(leveled <= tiered, "leveled {} should not exceed tiered {}", leveled, tiered);
+    }
+
+    #[test]
+    fn test_range_scan_merges_levels_newest_wins() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.memtable_size = 10;
+        lsm.level0_compaction_trigger = 2;
+
+        for i in 0..30 {
+            lsm.put(format!("key_{:02}", i), json!({"v": 0}));
+        }
+        lsm.flush_memtable();
+        // Newer versions: one in L0, one still in the memtable.
+        lsm.put("key_05".into(), json!({"v": 1}));
+        lsm.flush_memtable();
+        lsm.put("key_06".into(), json!({"v": 2}));
+        lsm.delete("key_07".into());
+
+        let results = lsm.range_scan("key_04", "key_08");
+        let keys: Vec<&str> = results.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["key_04", "key_05", "key_06", "key_08"]);
+        assert_eq!(results[1].1, json!({"v": 1}));
+        assert_eq!(results[2].1, json!({"v": 2}));
+    }
+
+    #[test]
+    fn test_range_scan_empty_range() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.put("a".into(), json!(1));
+        lsm.put("c".into(), json!(3));
+        assert!(lsm.range_scan("b", "b").is_empty());
+        assert!(lsm.range_scan("d", "a").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execute_range_port() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut lsm = LSMTreeBlock::new();
+        let records: Vec<Record> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|id| {
+                let mut r = Record::new();
+                r.insert("id".into(), *id).unwrap();
+                r
+            })
+            .collect();
+        let mut range = Record::new();
+        range.insert("start".into(), "b").unwrap();
+        range.insert("end".into(), "c").unwrap();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        inputs.insert("range".into(), PortValue::Single(range));
+
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = lsm.execute(ctx).await.unwrap();
+        let PortValue::Stream(found) = result.outputs.get("range_results").unwrap() else {
+            panic!("range_results should be a stream");
+        };
+        let ids: Vec<String> = found.iter().map(|r| r.get("id").unwrap().unwrap()).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+        // The scan walked the single SSTable flushed at the end of execute.
+        assert_eq!(result.metrics["read_amplification"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_execute_range_orders_integer_keys_numerically() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut lsm = LSMTreeBlock::new();
+        lsm.memtable_size = 4;
+        let records: Vec<Record> = (0..15)
+            .map(|id| {
+                let mut r = Record::new();
+                r.insert("id".into(), id).unwrap();
+                r
+            })
+            .collect();
+        let mut range = Record::new();
+        range.insert("start".into(), 2).unwrap();
+        range.insert("end".into(), 10).unwrap();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        inputs.insert("range".into(), PortValue::Single(range));
+
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = lsm.execute(ctx).await.unwrap();
+        let PortValue::Stream(found) = result.outputs.get("range_results").unwrap() else {
+            panic!("range_results should be a stream");
+        };
+        let ids: Vec<i64> = found.iter().map(|r| r.get("id").unwrap().unwrap()).collect();
+        assert_eq!(ids, (2..=10).collect::<Vec<i64>>());
+    }
+
     #[test]
     fn test_overwrite_key() {
         let mut lsm = LSMTreeBlock::new();
@@ -1019,14 +1752,211 @@ fn test_overwrite_key() {
         assert_eq!(lsm.get("key1"), Some(json!({"version": 2})));
     }
 
+    #[test]
+    fn test_delete_shadows_flushed_value() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.memtable_size = 10;
+
+        lsm.put("key1".into(), json!({"version": 1}));
+        lsm.flush_memtable();
+        assert!(lsm.get("key1").is_some());
+
+        lsm.delete("key1".into());
+        assert_eq!(lsm.get("key1"), None, "Tombstone in memtable should hide L0 value");
+
+        lsm.flush_memtable();
+        assert_eq!(lsm.get("key1"), None, "Tombstone in newer SSTable should hide older one");
+    }
+
+    #[test]
+    fn test_compaction_keeps_newest_l0_value() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.level0_compaction_trigger = 3;
+
+        for version in 0..3 {
+            lsm.put("key1".into(), json!(version));
+            lsm.flush_memtable();
+        }
+
+        assert!(lsm.compaction_count > 0);
+        assert_eq!(lsm.get("key1"), Some(json!(2)));
+    }
+
+    #[test]
+    fn test_tombstones_purged_only_at_deepest_level() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.level0_compaction_trigger = 2;
+
+        // Push a value down to L2 so that L1 is no longer the deepest level.
+        lsm.put("old".into(), json!(1));
+        lsm.flush_memtable();
+        lsm.put("filler".into(), json!(1));
+        lsm.flush_memtable();
+        let l1 = std::mem::take(&mut lsm.levels[1]);
+        lsm.levels[2] = l1;
+
+        // Delete and compact L0 -> L1: the tombstone must survive.
+        lsm.delete("old".into());
+        lsm.flush_memtable();
+        lsm.put("other".into(), json!(2));
+        lsm.flush_memtable();
+        assert_eq!(lsm.tombstones_purged, 0);
+        assert_eq!(lsm.get("old"), None);
+
+        // Compact L1 -> L2 (now the deepest level): the tombstone is dropped.
+        lsm.compact_level(1);
+        assert_eq!(lsm.tombstones_purged, 1);
+        assert_eq!(lsm.get("old"), None);
+        assert_eq!(lsm.get("filler"), Some(json!(1)));
+    }
+
+    #[tokio::test]
+    async fn test_execute_delete_op() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut lsm = LSMTreeBlock::new();
+        lsm.put("1".into(), json!({"id": 1}));
+
+        let mut delete = Record::new();
+        delete.insert("id".into(), 1i64).unwrap();
+        delete.insert("_op".into(), "delete").unwrap();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Single(delete));
+
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = lsm.execute(ctx).await.unwrap();
+        assert!(result.errors.is_empty());
+        assert_eq!(result.outputs.get("stored").unwrap().len(), 0);
+        assert_eq!(lsm.get("1"), None);
+    }
+
+    #[test]
+    fn test_leveled_compaction_keeps_levels_non_overlapping() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.compaction_strategy = CompactionStrategy::Leveled;
+        lsm.memtable_size = 10;
+        lsm.level0_compaction_trigger = 2;
+        lsm.size_ratio = 2;
+
+        // Interleave keys so consecutive flushes overlap in key range.
+        for i in 0..400 {
+            lsm.put(format!("key_{:04}", (i * 37) % 400), json!(i));
+        }
+        lsm.flush_memtable();
+
+        assert!(lsm.compaction_count > 0);
+        for level in lsm.levels.iter().skip(1) {
+            for pair in level.windows(2) {
+                assert!(
+                    pair[0].max_key() < pair[1].min_key(),
+                    "SSTables within a level must not overlap"
+                );
+            }
+        }
+        for i in 0..400 {
+            assert!(lsm.get(&format!("key_{:04}", i)).is_some(), "key {} lost", i);
+        }
+    }
+
+    #[test]
+    fn test_leveled_compaction_keeps_newest_value() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.compaction_strategy = CompactionStrategy::Leveled;
+        lsm.memtable_size = 10;
+        lsm.level0_compaction_trigger = 2;
+
+        for round in 0..10 {
+            for i in 0..10 {
+                lsm.put(format!("key_{:02}", i), json!(round));
+            }
+        }
+        lsm.flush_memtable();
+
+        for i in 0..10 {
+            assert_eq!(lsm.get(&format!("key_{:02}", i)), Some(json!(9)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_initialize_rejects_unknown_strategy() {
+        let mut lsm = LSMTreeBlock::new();
+        let mut params = HashMap::new();
+        params.insert("compaction_strategy".into(), ParameterValue::String("leveled".into()));
+        lsm.initialize(params).await.unwrap();
+        assert_eq!(lsm.compaction_strategy, CompactionStrategy::Leveled);
+
+        let mut params = HashMap::new();
+        params.insert("compaction_strategy".into(), ParameterValue::String("tiered".into()));
+        assert!(lsm.initialize(params).await.is_err());
+    }
+
+    #[test]
+    fn test_crash_without_wal_loses_memtable() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.put("key1".into(), json!(1));
+        lsm.simulate_crash();
+        assert_eq!(lsm.recover(), 0);
+        assert_eq!(lsm.get("key1"), None);
+    }
+
+    #[test]
+    fn test_wal_recovery_replays_unflushed_writes() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.wal_enabled = true;
+        lsm.memtable_size = 10;
+
+        lsm.put("flushed".into(), json!(1));
+        lsm.flush_memtable();
+        assert_eq!(lsm.wal_len(), 0, "Flush should truncate the WAL");
+
+        lsm.put("key1".into(), json!(1));
+        lsm.put("key1".into(), json!(2));
+        lsm.delete("flushed".into());
+        assert_eq!(lsm.wal_len(), 3);
+
+        lsm.simulate_crash();
+        assert_eq!(lsm.get("key1"), None);
+
+        assert_eq!(lsm.recover(), 3);
+        assert_eq!(lsm.get("key1"), Some(json!(2)), "Replay must apply writes in order");
+        assert_eq!(lsm.get("flushed"), None, "Replayed tombstone must shadow SSTable");
+    }
+
+    #[test]
+    fn test_wal_recovery_flushes_oversized_memtable() {
+        let mut lsm = LSMTreeBlock::new();
+        lsm.wal_enabled = true;
+        lsm.memtable_size = 10;
+
+        for i in 0..8 {
+            lsm.put(format!("key_{}", i), json!(i));
+        }
+        lsm.simulate_crash();
+        lsm.memtable_size = 5;
+
+        assert_eq!(lsm.recover(), 8);
+        assert!(lsm.memtable.is_empty(), "Replay past memtable_size should flush");
+        assert_eq!(lsm.flush_count, 1);
+        assert_eq!(lsm.wal_len(), 0, "The flush should truncate the WAL");
+        assert_eq!(lsm.get("key_7"), Some(json!(7)));
+    }
+
     #[test]
     fn test_metadata() {
         let lsm = LSMTreeBlock::new();
         assert_eq!(lsm.metadata().id, "lsm-tree-storage");
         assert_eq!(lsm.metadata().category, BlockCategory::Storage);
-        assert_eq!(lsm.inputs().len(), 1);
-        assert_eq!(lsm.outputs().len(), 1);
-        assert_eq!(lsm.parameters().len(), 4);
+        assert_eq!(lsm.inputs().len(), 2);
+        assert_eq!(lsm.outputs().len(), 2);
+        assert_eq!(lsm.parameters().len(), 6);
     }
 
     #[tokio::test]
@@ -1039,11 +1969,60 @@ async fn test_initialize_with_params() {
             ParameterValue::Integer(6),
         );
         params.insert("size_ratio".into(), ParameterValue::Integer(5));
+        params.insert("key_column".into(), ParameterValue::String("user_id".into()));
+        params.insert("wal_enabled".into(), ParameterValue::Boolean(true));
 
         lsm.initialize(params).await.unwrap();
         assert_eq!(lsm.memtable_size, 500);
         assert_eq!(lsm.level0_compaction_trigger, 6);
         assert_eq!(lsm.size_ratio, 5);
+        assert_eq!(lsm.key_column, "user_id");
+        assert!(lsm.wal_enabled);
+    }
+
+    #[tokio::test]
+    async fn test_initialize_checks_declared_constraints() {
+        let mut params = HashMap::new();
+        params.insert("memtable_size".into(), ParameterValue::Integer(5));
+        params.insert("size_ratio".into(), ParameterValue::Integer(50));
+        params.insert("wal_enabled".into(), ParameterValue::from("yes"));
+        let err = LSMTreeBlock::new().initialize(params).await.unwrap_err().to_string();
+        // Every violation is reported, not just the first.
+        assert!(err.contains("memtable_size must be between 10 and 100000, got 5"), "{}", err);
+        assert!(err.contains("size_ratio must be between 2 and 20, got 50"), "{}", err);
+        assert!(err.contains("wal_enabled must be a boolean, got 'yes'"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_execute_uses_key_column() {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut lsm = LSMTreeBlock::new();
+        lsm.key_column = "user_id".into();
+
+        let mut with_key = Record::new();
+        with_key.insert("user_id".into(), 42i64).unwrap();
+        with_key.insert("id".into(), 1i64).unwrap();
+        let mut without_key = Record::new();
+        without_key.insert("id".into(), 2i64).unwrap();
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(vec![with_key, without_key]));
+
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+
+        let result = lsm.execute(ctx).await.unwrap();
+        assert_eq!(result.errors.len(), 1, "Record without user_id should be reported");
+        assert_eq!(result.outputs.get("stored").unwrap().len(), 1);
+        assert_eq!(lsm.total_entries(), 1);
+        assert!(lsm.get("42").is_some());
+        assert!(lsm.get("1").is_none());
     }
 
     #[tokio::test]
@@ -2,6 +2,8 @@
 //!
 //! Storage blocks manage how data is physically organized and stored.
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+
 pub mod heap_file;
 pub mod lsm_tree;
 pub mod clustered;
@@ -11,3 +13,12 @@
 pub use lsm_tree::LSMTreeBlock;
 pub use clustered::ClusteredStorageBlock;
 pub use columnar::ColumnarStorageBlock;
+
+/// Register a factory for each storage block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(HeapFileBlock::new()))?;
+    registry.register_factory(|| Box::new(LSMTreeBlock::new()))?;
+    registry.register_factory(|| Box::new(ClusteredStorageBlock::new()))?;
+    registry.register_factory(|| Box::new(ColumnarStorageBlock::new()))?;
+    Ok(())
+}
@@ -2,6 +2,14 @@
 //!
 //! Transaction blocks provide durability and recovery mechanisms.
 
+use crate::core::registry::{BlockRegistry, RegistryError};
+
 pub mod wal;
 
 pub use wal::WALBlock;
+
+/// Register a factory for each transaction block.
+pub fn register(registry: &BlockRegistry) -> Result<(), RegistryError> {
+    registry.register_factory(|| Box::new(WALBlock::new()))?;
+    Ok(())
+}
@@ -9,9 +9,24 @@
 //!
 //! 1. Before any data modification, a **log record** is appended to the WAL.
 //! 2. The log is **fsync'd** (simulated) to ensure durability before
-//!    acknowledging the write.
-//! 3. Periodically, a **checkpoint** flushes all dirty data and records the
-//!    checkpoint LSN, allowing older log entries to be recycled.
+//!    acknowledging the write. Write records are flushed every
+//!    `fsync_interval` entries; COMMIT records are flushed by **group
+//!    commit** — one fsync once `group_commit_window` commits are pending.
+//! 3. Every `checkpoint_interval` entries a **checkpoint** flushes all dirty
+//!    data, records the checkpoint LSN and the transactions still active, and
+//!    truncates the log records of every transaction that finished before it.
+//!    The log size therefore follows a sawtooth: it grows between checkpoints
+//!    and drops at each one.
+//! 4. With `mode = recover`, `execute` simulates a crash — the unflushed log
+//!    tail and every page not written by a checkpoint are lost — and then
+//!    recovers ARIES-style, scanning from the last checkpoint: **redo**
+//!    replays every change after it, **undo** rolls back transactions that
+//!    were active at the checkpoint or started after it and have no COMMIT or
+//!    ABORT.
+//!
+//! Data pages use a steal / no-force policy: a checkpoint flushes pages with
+//! uncommitted changes (so undo is needed), and commits do not flush pages
+//! (so redo is needed).
 //!
 //! ## Log record types
 //!
@@ -21,6 +36,7 @@
 //! | UPDATE | table, before, after | Record modified |
 //! | DELETE | table, data | Record removed |
 //! | COMMIT | txn_id | Transaction committed |
+//! | ABORT | txn_id | Transaction rolled back (after its compensating changes) |
 //! | CHECKPOINT | lsn | Recovery point |
 //!
 //! ## Metrics tracked
@@ -28,14 +44,28 @@
 //! | Metric | Type | Description |
 //! |--------|------|-------------|
 //! | `log_entries` | Counter | Total log records written |
+//! | `log_size` | Gauge | Log records currently retained (after truncation) |
 //! | `bytes_written` | Counter | Total bytes written to log |
 //! | `fsyncs` | Counter | fsync operations (simulated) |
+//! | `commits` | Counter | COMMIT records written |
+//! | `commits_per_fsync` | Gauge | Commits made durable per fsync |
 //! | `checkpoints` | Counter | Checkpoint operations |
-//! | `log_size_bytes` | Gauge | Current log file size |
+//! | `log_size_bytes` | Gauge | Bytes currently retained in the log |
 //! | `oldest_lsn` | Gauge | Oldest un-checkpointed LSN |
+//! | `records_redone` | Counter | Changes replayed by recovery's redo pass |
+//! | `records_undone` | Counter | Changes rolled back by recovery's undo pass |
+//!
+//! ## Input records
+//!
+//! In `log` mode each record is a write keyed by its `id`. Records with a
+//! `_txn` field belong to that client transaction, which stays open until a
+//! record with `_op: "commit"` or `_op: "abort"`; `_op: "delete"` logs a
+//! delete. Records without `_txn` form one transaction committed at the end
+//! of the batch.
 
 use async_trait::async_trait;
-use std::collections::HashMap;
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
 
 use crate::core::block::{
     Alternative, Block, BlockCategory, BlockDocumentation, BlockError, BlockMetadata, BlockState,
@@ -44,7 +74,7 @@
 use crate::core::constraint::{Constraint, Guarantee, GuaranteeType};
 use crate::core::metrics::{AggregationType, MetricDefinition, MetricType};
 use crate::core::parameter::{
-    Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
+    enum_param, Parameter, ParameterConstraints, ParameterType, ParameterUIHint, ParameterValue,
     ValidationResult, WidgetType,
 };
 use crate::core::port::{Port, PortDirection, PortType, PortValue, Record};
@@ -56,24 +86,49 @@
 /// Log Sequence Number — monotonically increasing.
 type LSN = u64;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
 pub enum LogRecordType {
     Insert,
     Update,
     Delete,
     Commit,
+    Abort,
     Checkpoint,
 }
 
+/// A row's column values, as stored in the simulated data pages.
+type Row = HashMap<String, JsonValue>;
+
+/// Before and after images of one row, enough to redo or undo the change.
+#[derive(Debug, Clone)]
+struct Change {
+    key: String,
+    before: Option<Row>,
+    after: Option<Row>,
+}
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 struct LogRecord {
     lsn: LSN,
     record_type: LogRecordType,
     size_bytes: usize,
+    /// Owning transaction; 0 for records that belong to none.
+    txn_id: u64,
+    change: Option<Change>,
+}
+
+/// What `execute` does with its input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WalMode {
+    Log,
+    Recover,
 }
 
+/// Values accepted by the `mode` parameter.
+const MODES: &[&str] = &["log", "recover"];
+
 // ---------------------------------------------------------------------------
 // WALBlock
 // ---------------------------------------------------------------------------
@@ -88,18 +143,36 @@ pub struct WALBlock {
     // Configuration
     fsync_interval: usize,      // Fsync every N log entries
     checkpoint_interval: usize, // Checkpoint every N log entries
+    group_commit_window: usize, // Commits flushed together by one fsync
+    mode: WalMode,
 
     // Internal state
     log: Vec<LogRecord>,
     next_lsn: LSN,
     last_checkpoint_lsn: LSN,
+    // Transactions with changes but no outcome yet, and the set recorded by
+    // the last checkpoint.
+    active_txns: HashSet<u64>,
+    checkpoint_active: HashSet<u64>,
+    flushed_lsn: LSN,
     total_bytes: usize,
+    // Data pages: `data` is the buffer pool, `disk_data` what the last
+    // checkpoint flushed.
+    data: HashMap<String, Row>,
+    disk_data: HashMap<String, Row>,
+    next_txn_id: u64,
+    client_txns: HashMap<String, u64>,
 
     // Counters
+    entries_written: usize,
     fsync_count: usize,
+    commit_count: usize,
+    pending_commits: usize,
     checkpoint_count: usize,
     entries_since_fsync: usize,
     entries_since_checkpoint: usize,
+    records_redone: usize,
+    records_undone: usize,
 }
 
 impl WALBlock {
@@ -112,14 +185,28 @@ pub fn new() -> Self {
             metric_defs: Self::build_metrics(),
             fsync_interval: 1,
             checkpoint_interval: 100,
+            group_commit_window: 1,
+            mode: WalMode::Log,
             log: Vec::new(),
             next_lsn: 1,
             last_checkpoint_lsn: 0,
+            active_txns: HashSet::new(),
+            checkpoint_active: HashSet::new(),
+            flushed_lsn: 0,
             total_bytes: 0,
+            data: HashMap::new(),
+            disk_data: HashMap::new(),
+            next_txn_id: 1,
+            client_txns: HashMap::new(),
+            entries_written: 0,
             fsync_count: 0,
+            commit_count: 0,
+            pending_commits: 0,
             checkpoint_count: 0,
             entries_since_fsync: 0,
             entries_since_checkpoint: 0,
+            records_redone: 0,
+            records_undone: 0,
         }
     }
 
@@ -150,19 +237,27 @@ fn build_metadata() -> BlockMetadata {
                            1. Assign next LSN (Log Sequence Number) — monotonically increasing\n  \
                            2. Create log record: { lsn, type, data, checksum }\n  \
                            3. Write to WAL buffer\n  \
-                           4. If entries_since_fsync >= fsync_interval:\n     \
+                           4. If record is COMMIT:\n     \
+                              pending_commits += 1\n     \
+                              If pending_commits >= group_commit_window: fsync()  (group commit)\n  \
+                              Else if entries_since_fsync >= fsync_interval:\n     \
                               fsync() — force WAL buffer to stable storage\n  \
                            5. If entries_since_checkpoint >= checkpoint_interval:\n     \
                               CHECKPOINT()\n\n\
                            CHECKPOINT():\n  \
-                           1. Write checkpoint log record with current LSN\n  \
+                           1. Write checkpoint log record with current LSN and the active txn set\n  \
                            2. Flush all dirty data pages to disk\n  \
                            3. Record checkpoint LSN as new recovery starting point\n  \
-                           4. Old log entries before checkpoint can be recycled\n\n\
+                           4. Truncate records before the checkpoint unless their txn is still active\n\n\
+                           ABORT(txn_id):\n  \
+                           1. Scan txn's changes backward, log a compensating change restoring each before image\n  \
+                           2. Append ABORT record\n\n\
                            RECOVERY (after crash):\n  \
-                           1. Find last checkpoint LSN\n  \
-                           2. REDO pass: scan forward from checkpoint, replay all changes\n  \
-                           3. UNDO pass: scan backward, roll back uncommitted transactions"
+                           1. Reload data pages as of the last checkpoint\n  \
+                           2. ANALYSIS from checkpoint: losers = checkpoint's active txns + txns seen after it,\n     \
+                              minus those with a COMMIT/ABORT record after it\n  \
+                           3. REDO pass: scan forward from checkpoint, reapply every after image (repeat history)\n  \
+                           4. UNDO pass: for each loser, scan backward and ABORT it as above"
                     .into(),
                 complexity: Complexity {
                     time: "O(1) per log append (sequential write), O(n) for recovery replay"
@@ -209,8 +304,20 @@ fn build_metadata() -> BlockMetadata {
                       Recommended: 1 for financial/critical data, 5-10 for general OLTP, 50-100 for \
                       batch ingestion where some data loss is acceptable."
                         .into()),
+                    ("group_commit_window".into(),
+                     "How many transaction commits share one fsync. At 1 every COMMIT record is flushed \
+                      as soon as it is written (per-commit flushing). Larger windows hold commits in the log \
+                      buffer until that many are pending and then flush them all at once, so the commits / \
+                      fsyncs ratio approaches the window size — the throughput win of group commit, paid \
+                      for with commit latency and with losing the pending commits on a crash. Write records \
+                      still flush on fsync_interval, so raise that too to see the full effect. PostgreSQL's \
+                      commit_delay and commit_siblings and MySQL's binlog_group_commit_sync_delay tune the \
+                      same trade-off."
+                        .into()),
                     ("checkpoint_interval".into(),
                      "Controls how often checkpoints occur, measured in log entries between checkpoints. \
+                      Each checkpoint truncates the log records of finished transactions, so this also bounds \
+                      how large the log grows (watch log_size saw-tooth). \
                       Lower values (10-50) mean faster crash recovery (less log to replay) but more frequent \
                       I/O pauses as dirty pages are flushed. Higher values (1000-100000) reduce checkpoint \
                       overhead during normal operations but increase recovery time after a crash. PostgreSQL's \
@@ -218,6 +325,14 @@ fn build_metadata() -> BlockMetadata {
                       Recommended: 100 for most workloads, higher for write-heavy systems with tolerance \
                       for longer recovery times."
                         .into()),
+                    ("mode".into(),
+                     "What execute does with its input. log (the default) appends a log record for every \
+                      incoming write. recover ignores the input and simulates a crash followed by restart: \
+                      anything not yet fsync'd is lost, data pages revert to what the last checkpoint \
+                      flushed, and the redo and undo passes rebuild a state containing exactly the committed \
+                      transactions. Compare records_redone (grows with the distance since the last \
+                      checkpoint) with records_undone (grows with the work of in-flight transactions)."
+                        .into()),
                 ]),
                 alternatives: vec![
                     Alternative {
@@ -271,16 +386,28 @@ fn build_inputs() -> Vec<Port> {
     }
 
     fn build_outputs() -> Vec<Port> {
-        vec![Port {
-            id: "logged".into(),
-            name: "Logged Records".into(),
-            port_type: PortType::DataStream,
-            direction: PortDirection::Output,
-            required: false,
-            multiple: true,
-            description: "Records after being durably logged (with LSN)".into(),
-            schema: None,
-        }]
+        vec![
+            Port {
+                id: "logged".into(),
+                name: "Logged Records".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Records after being durably logged (with LSN)".into(),
+                schema: None,
+            },
+            Port {
+                id: "recovered".into(),
+                name: "Recovered Rows".into(),
+                port_type: PortType::DataStream,
+                direction: PortDirection::Output,
+                required: false,
+                multiple: true,
+                description: "Rows rebuilt by crash recovery (recover mode only)".into(),
+                schema: None,
+            },
+        ]
     }
 
     fn build_parameters() -> Vec<Parameter> {
@@ -320,6 +447,31 @@ fn build_parameters() -> Vec<Parameter> {
                         .with_help_text("Less frequent = faster writes, slower recovery".into()),
                 ),
             },
+            Parameter {
+                id: "group_commit_window".into(),
+                name: "Group Commit Window".into(),
+                param_type: ParameterType::Number,
+                description: "Commits flushed together by one fsync (1 = flush every commit)".into(),
+                default_value: ParameterValue::Integer(1),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_min(1.0).with_max(1000.0)),
+                ui_hint: Some(
+                    ParameterUIHint::new(WidgetType::Slider)
+                        .with_step(1.0)
+                        .with_help_text("Higher = fewer fsyncs per commit, more commit latency".into()),
+                ),
+            },
+            Parameter {
+                id: "mode".into(),
+                name: "Mode".into(),
+                param_type: ParameterType::Enum,
+                description: "log appends incoming writes; recover simulates a crash and runs recovery"
+                    .into(),
+                default_value: ParameterValue::String("log".into()),
+                required: false,
+                constraints: Some(ParameterConstraints::new().with_choices(MODES)),
+                ui_hint: Some(ParameterUIHint::new(WidgetType::Select)),
+            },
         ]
     }
 
@@ -333,6 +485,14 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Total log records written".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "log_size".into(),
+                name: "Log Size".into(),
+                metric_type: MetricType::Gauge,
+                unit: "entries".into(),
+                description: "Log records currently retained after checkpoint truncation".into(),
+                aggregations: vec![AggregationType::Avg, AggregationType::Max],
+            },
             MetricDefinition {
                 id: "bytes_written".into(),
                 name: "Bytes Written".into(),
@@ -349,6 +509,22 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "fsync operations (simulated)".into(),
                 aggregations: vec![AggregationType::Sum],
             },
+            MetricDefinition {
+                id: "commits".into(),
+                name: "Commits".into(),
+                metric_type: MetricType::Counter,
+                unit: "txns".into(),
+                description: "COMMIT records written".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "commits_per_fsync".into(),
+                name: "Commits per Fsync".into(),
+                metric_type: MetricType::Gauge,
+                unit: "ratio".into(),
+                description: "Commits made durable per fsync — the group commit batching factor".into(),
+                aggregations: vec![AggregationType::Avg, AggregationType::Max],
+            },
             MetricDefinition {
                 id: "checkpoints".into(),
                 name: "Checkpoints".into(),
@@ -359,10 +535,10 @@ fn build_metrics() -> Vec<MetricDefinition> {
             },
             MetricDefinition {
                 id: "log_size_bytes".into(),
-                name: "Log Size".into(),
+                name: "Log Size (bytes)".into(),
                 metric_type: MetricType::Gauge,
                 unit: "bytes".into(),
-                description: "Current total log size".into(),
+                description: "Bytes currently retained in the log".into(),
                 aggregations: vec![AggregationType::Max],
             },
             MetricDefinition {
@@ -373,6 +549,22 @@ fn build_metrics() -> Vec<MetricDefinition> {
                 description: "Oldest un-checkpointed log sequence number".into(),
                 aggregations: vec![AggregationType::Max],
             },
+            MetricDefinition {
+                id: "records_redone".into(),
+                name: "Records Redone".into(),
+                metric_type: MetricType::Counter,
+                unit: "records".into(),
+                description: "Changes replayed by the recovery redo pass".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
+            MetricDefinition {
+                id: "records_undone".into(),
+                name: "Records Undone".into(),
+                metric_type: MetricType::Counter,
+                unit: "records".into(),
+                description: "Changes rolled back by the recovery undo pass".into(),
+                aggregations: vec![AggregationType::Sum],
+            },
         ]
     }
 
@@ -380,23 +572,44 @@ fn build_metrics() -> Vec<MetricDefinition> {
 
     /// Append a log record.
     pub fn append(&mut self, record_type: LogRecordType, data_size: usize) -> LSN {
+        self.append_with(record_type, 0, None, data_size)
+    }
+
+    /// Append a log record owned by `txn_id`, optionally carrying a change.
+    fn append_with(
+        &mut self,
+        record_type: LogRecordType,
+        txn_id: u64,
+        change: Option<Change>,
+        data_size: usize,
+    ) -> LSN {
         let lsn = self.next_lsn;
         let header_size = 32; // LSN + type + size + checksum
         let size_bytes = header_size + data_size;
+        let is_commit = record_type == LogRecordType::Commit;
 
         self.log.push(LogRecord {
             lsn,
             record_type,
             size_bytes,
+            txn_id,
+            change,
         });
 
         self.next_lsn += 1;
         self.total_bytes += size_bytes;
+        self.entries_written += 1;
         self.entries_since_fsync += 1;
         self.entries_since_checkpoint += 1;
 
-        // Fsync if interval reached.
-        if self.entries_since_fsync >= self.fsync_interval {
+        // Commits flush as a group; other records flush on the interval.
+        if is_commit {
+            self.commit_count += 1;
+            self.pending_commits += 1;
+            if self.pending_commits >= self.group_commit_window {
+                self.fsync();
+            }
+        } else if self.entries_since_fsync >= self.fsync_interval {
             self.fsync();
         }
 
@@ -412,14 +625,202 @@ pub fn append(&mut self, record_type: LogRecordType, data_size: usize) -> LSN {
     fn fsync(&mut self) {
         self.fsync_count += 1;
         self.entries_since_fsync = 0;
+        self.pending_commits = 0;
+        self.flushed_lsn = self.current_lsn();
     }
 
-    /// Perform a checkpoint.
+    /// Perform a checkpoint: flush all data pages (committed or not), make
+    /// the checkpoint record durable, and truncate the log records of every
+    /// transaction that finished before it.
     pub fn checkpoint(&mut self) {
         let lsn = self.append_raw(LogRecordType::Checkpoint, 0);
+        self.disk_data = self.data.clone();
+        self.fsync();
         self.last_checkpoint_lsn = lsn;
+        self.checkpoint_active = self.active_txns.clone();
         self.checkpoint_count += 1;
         self.entries_since_checkpoint = 0;
+
+        // Records of still-active transactions are kept for undo.
+        let active = &self.active_txns;
+        self.log
+            .retain(|r| r.lsn >= lsn || (r.txn_id != 0 && active.contains(&r.txn_id)));
+    }
+
+    pub fn begin_txn(&mut self) -> u64 {
+        let id = self.next_txn_id;
+        self.next_txn_id += 1;
+        id
+    }
+
+    /// Log a write of `key` by `txn_id` and apply it to the data pages.
+    /// `None` deletes the row.
+    pub fn write(&mut self, txn_id: u64, key: &str, after: Option<Row>) -> LSN {
+        let before = self.data.get(key).cloned();
+        let record_type = match (&before, &after) {
+            (None, _) => LogRecordType::Insert,
+            (Some(_), Some(_)) => LogRecordType::Update,
+            (Some(_), None) => LogRecordType::Delete,
+        };
+        let data_size = [&before, &after]
+            .iter()
+            .filter_map(|row| row.as_ref())
+            .map(|row| serde_json::to_string(row).map(|s| s.len()).unwrap_or(64))
+            .sum();
+        match &after {
+            Some(row) => self.data.insert(key.to_string(), row.clone()),
+            None => self.data.remove(key),
+        };
+        self.active_txns.insert(txn_id);
+        let change = Change {
+            key: key.to_string(),
+            before,
+            after,
+        };
+        self.append_with(record_type, txn_id, Some(change), data_size)
+    }
+
+    pub fn commit_txn(&mut self, txn_id: u64) -> LSN {
+        self.active_txns.remove(&txn_id);
+        self.append_with(LogRecordType::Commit, txn_id, None, 0)
+    }
+
+    /// Roll back `txn_id`: log a compensating write restoring each before
+    /// image, newest first, then an ABORT record. Returns the changes undone.
+    pub fn abort_txn(&mut self, txn_id: u64) -> usize {
+        let changes: Vec<Change> = self
+            .log
+            .iter()
+            .rev()
+            .filter(|r| r.txn_id == txn_id)
+            .filter_map(|r| r.change.clone())
+            .collect();
+        for change in &changes {
+            self.write(txn_id, &change.key, change.before.clone());
+        }
+        self.active_txns.remove(&txn_id);
+        self.append_with(LogRecordType::Abort, txn_id, None, 0);
+        changes.len()
+    }
+
+    /// Simulate a crash: log records not yet fsync'd are lost, and so is
+    /// every data page change not flushed by a checkpoint.
+    pub fn crash(&mut self) {
+        let flushed = self.flushed_lsn;
+        self.log.retain(|r| r.lsn <= flushed);
+        self.next_lsn = flushed + 1;
+        self.entries_since_fsync = 0;
+        self.pending_commits = 0;
+        self.data = self.disk_data.clone();
+        self.client_txns.clear();
+    }
+
+    /// ARIES-style restart recovery over the durable log, scanning from the
+    /// last checkpoint: redo every change after it, then undo transactions
+    /// that never committed or aborted.
+    pub fn recover(&mut self) {
+        self.data = self.disk_data.clone();
+        let checkpoint = self.last_checkpoint_lsn;
+
+        // Analysis: start from the checkpoint's active set; transactions with
+        // changes but no outcome by the end of the log are losers.
+        let mut active = self.checkpoint_active.clone();
+        for r in self.log.iter().filter(|r| r.lsn > checkpoint) {
+            match r.record_type {
+                LogRecordType::Commit | LogRecordType::Abort => {
+                    active.remove(&r.txn_id);
+                }
+                _ if r.change.is_some() => {
+                    active.insert(r.txn_id);
+                }
+                _ => {}
+            }
+        }
+        let mut losers: Vec<u64> = active.into_iter().collect();
+        losers.sort_unstable();
+        self.active_txns = losers.iter().copied().collect();
+
+        // Redo: repeat history from the checkpoint, losers included.
+        let redo: Vec<Change> = self
+            .log
+            .iter()
+            .filter(|r| r.lsn > checkpoint)
+            .filter_map(|r| r.change.clone())
+            .collect();
+        for change in redo {
+            match change.after {
+                Some(row) => self.data.insert(change.key, row),
+                None => self.data.remove(&change.key),
+            };
+            self.records_redone += 1;
+        }
+
+        // Undo: roll the losers back, newest transaction first.
+        for txn in losers.into_iter().rev() {
+            self.records_undone += self.abort_txn(txn);
+        }
+        if self.entries_since_fsync > 0 {
+            self.fsync();
+        }
+    }
+
+    /// Current contents of the data pages.
+    pub fn get(&self, key: &str) -> Option<&Row> {
+        self.data.get(key)
+    }
+
+    /// Apply one incoming record in `log` mode, returning its LSN if it
+    /// logged a write.
+    fn log_record(&mut self, record: &Record, batch_txn: &mut Option<u64>) -> Option<LSN> {
+        let label = record.data.get("_txn").map(|v| v.to_string());
+        let txn_id = match &label {
+            Some(label) => match self.client_txns.get(label) {
+                Some(&id) => id,
+                None => {
+                    let id = self.begin_txn();
+                    self.client_txns.insert(label.clone(), id);
+                    id
+                }
+            },
+            None => match *batch_txn {
+                Some(id) => id,
+                None => {
+                    let id = self.begin_txn();
+                    *batch_txn = Some(id);
+                    id
+                }
+            },
+        };
+        let op = record.get::<String>("_op").ok().flatten();
+        match op.as_deref() {
+            Some("commit") | Some("abort") => {
+                if op.as_deref() == Some("commit") {
+                    self.commit_txn(txn_id);
+                } else {
+                    self.abort_txn(txn_id);
+                }
+                if let Some(label) = label {
+                    self.client_txns.remove(&label);
+                }
+                None
+            }
+            _ => {
+                let key = record
+                    .data
+                    .get("id")
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| format!("row_{}", self.next_lsn));
+                let after = (op.as_deref() != Some("delete")).then(|| {
+                    record
+                        .data
+                        .iter()
+                        .filter(|(k, _)| !k.starts_with('_'))
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect()
+                });
+                Some(self.write(txn_id, &key, after))
+            }
+        }
     }
 
     /// Internal append without triggering checkpoint (to avoid recursion).
@@ -432,17 +833,35 @@ fn append_raw(&mut self, record_type: LogRecordType, data_size: usize) -> LSN {
             lsn,
             record_type,
             size_bytes,
+            txn_id: 0,
+            change: None,
         });
 
         self.next_lsn += 1;
         self.total_bytes += size_bytes;
+        self.entries_written += 1;
         lsn
     }
 
+    /// Commits made durable per fsync so far.
+    pub fn commits_per_fsync(&self) -> f64 {
+        if self.fsync_count == 0 {
+            0.0
+        } else {
+            self.commit_count as f64 / self.fsync_count as f64
+        }
+    }
+
+    /// Log records currently retained.
     pub fn log_entry_count(&self) -> usize {
         self.log.len()
     }
 
+    /// Bytes currently retained in the log.
+    pub fn log_size_bytes(&self) -> usize {
+        self.log.iter().map(|r| r.size_bytes).sum()
+    }
+
     pub fn current_lsn(&self) -> LSN {
         self.next_lsn - 1
     }
@@ -494,29 +913,21 @@ async fn initialize(
         &mut self,
         params: HashMap<String, ParameterValue>,
     ) -> Result<(), BlockError> {
-        if let Some(val) = params.get("fsync_interval") {
-            self.fsync_interval = val
-                .as_integer()
-                .ok_or_else(|| {
-                    BlockError::InvalidParameter("fsync_interval must be an integer".into())
-                })? as usize;
-            if self.fsync_interval < 1 {
-                return Err(BlockError::InvalidParameter(
-                    "fsync_interval must be at least 1".into(),
-                ));
-            }
+        self.params_validate(&params)?;
+        if let Some(v) = params.get("fsync_interval").and_then(ParameterValue::as_integer) {
+            self.fsync_interval = v as usize;
         }
-        if let Some(val) = params.get("checkpoint_interval") {
-            self.checkpoint_interval = val
-                .as_integer()
-                .ok_or_else(|| {
-                    BlockError::InvalidParameter("checkpoint_interval must be an integer".into())
-                })? as usize;
-            if self.checkpoint_interval < 10 {
-                return Err(BlockError::InvalidParameter(
-                    "checkpoint_interval must be at least 10".into(),
-                ));
-            }
+        if let Some(v) = params.get("checkpoint_interval").and_then(ParameterValue::as_integer) {
+            self.checkpoint_interval = v as usize;
+        }
+        if let Some(v) = params.get("group_commit_window").and_then(ParameterValue::as_integer) {
+            self.group_commit_window = v as usize;
+        }
+        if let Some(s) = enum_param(&params, "mode", MODES)? {
+            self.mode = match s {
+                "log" => WalMode::Log,
+                _ => WalMode::Recover,
+            };
         }
         Ok(())
     }
@@ -544,24 +955,37 @@ async fn execute(
         };
 
         let mut output_records = Vec::with_capacity(records.len());
+        let mut recovered_records = Vec::new();
+
+        match self.mode {
+            WalMode::Log => {
+                let mut batch_txn = None;
+                for record in records {
+                    if let Some(lsn) = self.log_record(&record, &mut batch_txn) {
+                        // Enrich output record with LSN.
+                        let mut out = record;
+                        let _ = out.insert("_lsn".into(), lsn as i64);
+                        output_records.push(out);
+                    }
+                }
 
-        for record in records {
-            // Estimate record size from serialized data.
-            let data_size = serde_json::to_string(&record.data)
-                .map(|s| s.len())
-                .unwrap_or(64);
-
-            let lsn = self.append(LogRecordType::Insert, data_size);
-
-            // Enrich output record with LSN.
-            let mut out = record;
-            let _ = out.insert("_lsn".into(), lsn as i64);
-            output_records.push(out);
+                // Write a commit record for the batch's untagged records.
+                if let Some(txn_id) = batch_txn {
+                    self.commit_txn(txn_id);
+                }
+            }
+            WalMode::Recover => {
+                self.crash();
+                self.recover();
+                let mut keys: Vec<&String> = self.data.keys().collect();
+                keys.sort();
+                recovered_records = keys
+                    .into_iter()
+                    .map(|k| Record::from_map(self.data[k].clone()))
+                    .collect();
+            }
         }
 
-        // Write a commit record for the batch.
-        self.append(LogRecordType::Commit, 0);
-
         // Final fsync for safety.
         if self.entries_since_fsync > 0 {
             self.fsync();
@@ -569,29 +993,48 @@ async fn execute(
 
         context
             .metrics
-            .record("log_entries", self.log.len() as f64);
+            .record("log_entries", self.entries_written as f64);
+        context
+            .metrics
+            .record("log_size", self.log.len() as f64);
         context
             .metrics
             .record("bytes_written", self.total_bytes as f64);
         context.metrics.record("fsyncs", self.fsync_count as f64);
+        context.metrics.record("commits", self.commit_count as f64);
+        context
+            .metrics
+            .record("commits_per_fsync", self.commits_per_fsync());
         context
             .metrics
             .record("checkpoints", self.checkpoint_count as f64);
         context
             .metrics
-            .record("log_size_bytes", self.total_bytes as f64);
+            .record("log_size_bytes", self.log_size_bytes() as f64);
         context
             .metrics
             .record("oldest_lsn", (self.last_checkpoint_lsn + 1) as f64);
+        context
+            .metrics
+            .record("records_redone", self.records_redone as f64);
+        context
+            .metrics
+            .record("records_undone", self.records_undone as f64);
 
         let mut outputs = HashMap::new();
         outputs.insert("logged".into(), PortValue::Stream(output_records));
+        outputs.insert("recovered".into(), PortValue::Stream(recovered_records));
 
         let mut metrics_summary = HashMap::new();
-        metrics_summary.insert("log_entries".into(), self.log.len() as f64);
+        metrics_summary.insert("log_entries".into(), self.entries_written as f64);
+        metrics_summary.insert("log_size".into(), self.log.len() as f64);
         metrics_summary.insert("bytes_written".into(), self.total_bytes as f64);
         metrics_summary.insert("fsyncs".into(), self.fsync_count as f64);
+        metrics_summary.insert("commits".into(), self.commit_count as f64);
+        metrics_summary.insert("commits_per_fsync".into(), self.commits_per_fsync());
         metrics_summary.insert("checkpoints".into(), self.checkpoint_count as f64);
+        metrics_summary.insert("records_redone".into(), self.records_redone as f64);
+        metrics_summary.insert("records_undone".into(), self.records_undone as f64);
 
         Ok(ExecutionResult {
             outputs,
@@ -696,8 +1139,8 @@ fn test_metadata() {
         assert_eq!(wal.metadata().id, "wal");
         assert_eq!(wal.metadata().category, BlockCategory::Transaction);
         assert_eq!(wal.inputs().len(), 1);
-        assert_eq!(wal.outputs().len(), 1);
-        assert_eq!(wal.parameters().len(), 2);
+        assert_eq!(wal.outputs().len(), 2);
+        assert_eq!(wal.parameters().len(), 4);
     }
 
     #[tokio::test]
@@ -747,4 +1190,192 @@ async fn test_block_execute() {
         assert!(*result.metrics.get("log_entries").unwrap() > 20.0); // 20 inserts + 1 commit
         assert!(*result.metrics.get("fsyncs").unwrap() > 0.0);
     }
+
+    fn write_record(txn: &str, id: i64, value: &str) -> Record {
+        let mut r = Record::new();
+        r.insert("_txn".into(), txn).unwrap();
+        r.insert("id".into(), id).unwrap();
+        r.insert("value".into(), value).unwrap();
+        r
+    }
+
+    fn op_record(txn: &str, op: &str) -> Record {
+        let mut r = Record::new();
+        r.insert("_txn".into(), txn).unwrap();
+        r.insert("_op".into(), op).unwrap();
+        r
+    }
+
+    async fn run(wal: &mut WALBlock, records: Vec<Record>) -> ExecutionResult {
+        use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+
+        let mut inputs = HashMap::new();
+        inputs.insert("records".into(), PortValue::Stream(records));
+        let ctx = ExecutionContext {
+            inputs,
+            parameters: HashMap::new(),
+            metrics: MetricsCollector::new(),
+            logger: Logger::new(),
+            storage: StorageContext::new(),
+        };
+        wal.execute(ctx).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_crash_recovery_keeps_only_committed() {
+        let mut wal = WALBlock::new();
+
+        // t1 commits before the checkpoint; t3 writes before it but never
+        // commits, so the checkpoint flushes an uncommitted change.
+        run(
+            &mut wal,
+            vec![
+                write_record("t1", 1, "a"),
+                op_record("t1", "commit"),
+                write_record("t3", 3, "dirty"),
+                write_record("t3", 1, "dirty"),
+            ],
+        )
+        .await;
+        wal.checkpoint();
+        // t2 commits after the checkpoint: its pages only exist in memory.
+        run(
+            &mut wal,
+            vec![
+                write_record("t2", 2, "b"),
+                op_record("t2", "commit"),
+                write_record("t3", 4, "dirty"),
+            ],
+        )
+        .await;
+
+        wal.mode = WalMode::Recover;
+        let result = run(&mut wal, vec![]).await;
+
+        assert_eq!(wal.get("1").unwrap()["value"], "a");
+        assert_eq!(wal.get("2").unwrap()["value"], "b");
+        assert!(wal.get("3").is_none());
+        assert!(wal.get("4").is_none());
+        // Redo replays t2's write and t3's post-checkpoint write; undo rolls
+        // back all three of t3's writes.
+        assert_eq!(wal.records_redone, 2);
+        assert_eq!(wal.records_undone, 3);
+        assert_eq!(*result.metrics.get("records_undone").unwrap(), 3.0);
+        assert_eq!(result.outputs.get("recovered").unwrap().len(), 2);
+
+        // Recovery logged t3's abort, so recovering again changes nothing.
+        wal.recover();
+        assert_eq!(wal.records_undone, 3);
+        assert!(wal.get("3").is_none());
+        assert_eq!(wal.get("1").unwrap()["value"], "a");
+    }
+
+    #[test]
+    fn test_unflushed_commit_is_lost() {
+        let mut wal = WALBlock::new();
+        wal.fsync_interval = 100;
+        wal.group_commit_window = 100;
+        let t = wal.begin_txn();
+        wal.write(t, "k", Some(HashMap::from([("v".into(), JsonValue::from(1))])));
+        wal.commit_txn(t);
+
+        // Neither the write nor the commit reached stable storage.
+        wal.crash();
+        wal.recover();
+        assert_eq!(wal.log_entry_count(), 0);
+        assert!(wal.get("k").is_none());
+    }
+
+    /// Run `txns` single-write transactions and return (fsyncs, commits).
+    fn run_commits(fsync_interval: usize, window: usize, txns: usize) -> (usize, usize) {
+        let mut wal = WALBlock::new();
+        wal.fsync_interval = fsync_interval;
+        wal.group_commit_window = window;
+        wal.checkpoint_interval = 100_000;
+        for i in 0..txns {
+            let t = wal.begin_txn();
+            wal.write(t, &i.to_string(), Some(HashMap::new()));
+            wal.commit_txn(t);
+        }
+        (wal.fsync_count, wal.commit_count)
+    }
+
+    #[test]
+    fn test_group_commit_batches_fsyncs() {
+        let (fsyncs, commits) = run_commits(1000, 8, 64);
+        assert_eq!(commits, 64);
+        assert_eq!(fsyncs, 8);
+
+        // A window of 1 flushes every commit.
+        let (fsyncs, commits) = run_commits(1000, 1, 64);
+        assert_eq!((fsyncs, commits), (64, 64));
+    }
+
+    #[test]
+    fn test_group_commit_window_one_matches_interval_flushing() {
+        // With the default window, fsync counts are what per-entry and
+        // per-commit flushing always produced.
+        assert_eq!(run_commits(1, 1, 10), (20, 10));
+        assert_eq!(run_commits(3, 1, 10), (10, 10));
+    }
+
+    #[test]
+    fn test_checkpoint_truncates_finished_txns() {
+        let mut wal = WALBlock::new();
+        wal.checkpoint_interval = 10;
+        let mut sizes = Vec::new();
+        for i in 0..50 {
+            let t = wal.begin_txn();
+            wal.write(t, &i.to_string(), Some(HashMap::new()));
+            wal.commit_txn(t);
+            sizes.push(wal.log_entry_count());
+        }
+        // Sawtooth: the log never outgrows one checkpoint interval.
+        assert_eq!(wal.checkpoint_count, 10);
+        assert!(sizes.iter().all(|&s| s <= 10));
+        assert!(sizes.windows(2).any(|w| w[1] < w[0]));
+        assert_eq!(wal.entries_written, 110);
+    }
+
+    #[test]
+    fn test_checkpoint_keeps_active_txn_for_undo() {
+        let mut wal = WALBlock::new();
+        let row = |v: i64| HashMap::from([("v".into(), JsonValue::from(v))]);
+        let done = wal.begin_txn();
+        wal.write(done, "a", Some(row(1)));
+        wal.commit_txn(done);
+        let open = wal.begin_txn();
+        wal.write(open, "b", Some(row(2)));
+        wal.checkpoint();
+
+        // Only the open transaction's write survives truncation.
+        assert_eq!(wal.log_entry_count(), 2);
+        let late = wal.begin_txn();
+        wal.write(late, "c", Some(row(3)));
+        wal.commit_txn(late);
+
+        wal.crash();
+        wal.recover();
+        // Recovery redid only the post-checkpoint write and undid the
+        // transaction that was active at the checkpoint.
+        assert_eq!(wal.records_redone, 1);
+        assert_eq!(wal.records_undone, 1);
+        assert_eq!(wal.get("a"), Some(&row(1)));
+        assert!(wal.get("b").is_none());
+        assert_eq!(wal.get("c"), Some(&row(3)));
+    }
+
+    #[test]
+    fn test_abort_restores_before_image() {
+        let mut wal = WALBlock::new();
+        let row = |v: i64| HashMap::from([("v".into(), JsonValue::from(v))]);
+        let t1 = wal.begin_txn();
+        wal.write(t1, "k", Some(row(1)));
+        wal.commit_txn(t1);
+        let t2 = wal.begin_txn();
+        wal.write(t2, "k", Some(row(2)));
+        wal.write(t2, "k", None);
+        assert_eq!(wal.abort_txn(t2), 2);
+        assert_eq!(wal.get("k"), Some(&row(1)));
+    }
 }
@@ -8,14 +8,15 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::parameter::{Parameter, ParameterValue, ValidationResult};
+use super::parameter::{validate_params, Parameter, ParameterValue, ValidationResult};
 use super::port::{Port, PortValue};
 use super::constraint::{Constraint, Guarantee};
+use super::AsAny;
 use super::metrics::{MetricDefinition, MetricsCollector, Logger, StorageContext};
 
 /// Core block trait that all blocks must implement
 #[async_trait]
-pub trait Block: Send + Sync {
+pub trait Block: AsAny + Send + Sync {
     /// Get block metadata
     fn metadata(&self) -> &BlockMetadata;
 
@@ -37,6 +38,47 @@ pub trait Block: Send + Sync {
     /// Get metric definitions
     fn metrics(&self) -> &[MetricDefinition];
 
+    /// Whether the counters in `ExecutionResult::metrics` are running totals
+    /// over the block's lifetime — as for blocks that keep their counters in
+    /// state — rather than counts for that one call. The engine needs this
+    /// to fold results when it calls a block once per batch.
+    fn reports_running_totals(&self) -> bool {
+        true
+    }
+
+    /// Whether the block has produced all the output it ever will, so
+    /// further input would be wasted — a LIMIT that has emitted its rows.
+    /// Under batched execution the engine stops running an exhausted block
+    /// and every upstream block that only feeds exhausted ones.
+    fn is_exhausted(&self) -> bool {
+        false
+    }
+
+    /// Whether each call's outputs restate everything the block has produced
+    /// so far — an aggregate re-emitting its running groups — rather than
+    /// adding to earlier calls' outputs. Under batched execution the engine
+    /// keeps only the latest outputs of such a block, and of every block
+    /// downstream of it, instead of accumulating them.
+    fn restates_output(&self) -> bool {
+        false
+    }
+
+    /// Check `params` against the declared [`parameters`](Block::parameters)
+    ///
+    /// Applies each parameter's type, `required` flag and
+    /// [`ParameterConstraints`](super::parameter::ParameterConstraints)
+    /// (see [`validate_params`]), reporting every violation in one
+    /// [`BlockError::InvalidParameter`]. Blocks call this at the top of
+    /// `initialize` and keep only their cross-field checks.
+    fn params_validate(&self, params: &HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+        let result = validate_params(self.parameters(), params);
+        if result.has_errors() {
+            Err(BlockError::InvalidParameter(result.errors.join("; ")))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Initialize the block with parameters
     async fn initialize(&mut self, params: HashMap<String, ParameterValue>) -> Result<(), BlockError>;
 
@@ -49,6 +91,15 @@ pub trait Block: Send + Sync {
     /// Get current block state (for serialization/debugging)
     fn get_state(&self) -> BlockState;
 
+    /// Whether [`get_state`](Block::get_state) captures everything the block
+    /// carries between calls, so that [`set_state`](Block::set_state) on a
+    /// freshly initialized block resumes it exactly. Most blocks report only
+    /// a summary — counters and sizes, not the stored pages, keys or
+    /// versions — and cannot be restored from a snapshot.
+    fn state_round_trips(&self) -> bool {
+        false
+    }
+
     /// Set block state (for deserialization/recovery)
     fn set_state(&mut self, state: BlockState) -> Result<(), BlockError>;
 
@@ -39,6 +39,13 @@ pub enum ConstraintType {
     ThreadSafe,
     /// Block requires atomic operations support
     AtomicOperations,
+    /// Every block feeding this one must provide the guarantee (checked per
+    /// connection by the graph validator)
+    RequiresGuarantee(GuaranteeType),
+    /// Some block upstream of this one, directly or transitively, must
+    /// provide the guarantee at the strict level (checked by the graph
+    /// validator, which warns when only best-effort providers are found)
+    RequiresStrictGuarantee(GuaranteeType),
 }
 
 impl Constraint {
@@ -89,6 +96,28 @@ pub fn atomic_operations(description: impl Into<String>) -> Self {
             description: description.into(),
         }
     }
+
+    /// Create a constraint requiring a guarantee of every block feeding this one
+    pub fn requires_guarantee(
+        guarantee_type: GuaranteeType,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            constraint_type: ConstraintType::RequiresGuarantee(guarantee_type),
+            description: description.into(),
+        }
+    }
+
+    /// Create a constraint requiring a strict guarantee somewhere upstream
+    pub fn requires_strict_guarantee(
+        guarantee_type: GuaranteeType,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            constraint_type: ConstraintType::RequiresStrictGuarantee(guarantee_type),
+            description: description.into(),
+        }
+    }
 }
 
 /// Guarantee that a block provides
@@ -124,6 +153,8 @@ pub enum GuaranteeType {
     Serializable,
     /// Snapshot isolation level
     SnapshotIsolation,
+    /// Output records are sorted ascending on the block's key
+    SortedOrder,
 }
 
 /// Level of guarantee provided
@@ -219,6 +250,10 @@ pub fn can_satisfy(&self, constraint: &Constraint) -> bool {
                 // Atomic operations are always available in Rust
                 true
             }
+            ConstraintType::RequiresGuarantee(_) | ConstraintType::RequiresStrictGuarantee(_) => {
+                // Depends on the graph's connections; see GraphValidator
+                true
+            }
         }
     }
 
@@ -476,6 +511,7 @@ fn test_guarantee_types() {
             GuaranteeType::ThreadSafe,
             GuaranteeType::Serializable,
             GuaranteeType::SnapshotIsolation,
+            GuaranteeType::SortedOrder,
         ];
 
         for gt in types {
@@ -493,9 +529,11 @@ fn test_constraint_types() {
             Constraint::minimum_disk(1024, "test"),
             Constraint::thread_safe("test"),
             Constraint::atomic_operations("test"),
+            Constraint::requires_guarantee(GuaranteeType::SortedOrder, "test"),
+            Constraint::requires_strict_guarantee(GuaranteeType::Durability, "test"),
         ];
 
-        assert_eq!(constraints.len(), 6);
+        assert_eq!(constraints.len(), 8);
     }
 
     #[test]
@@ -3,9 +3,42 @@
 //! This module provides a comprehensive metrics collection system for blocks,
 //! supporting various metric types (counters, gauges, histograms, timing) and
 //! aggregation functions (sum, avg, percentiles, etc.).
+//!
+//! Histogram metrics are recorded with [`MetricsCollector::observe`]: each
+//! observation is kept as a sample, so percentiles are exact, and also counted
+//! into a [`Histogram`] of buckets (configurable per metric with
+//! [`MetricsCollector::set_buckets`]) for charting the distribution.
+//!
+//! With history enabled, every recorded value is also kept as a point in a
+//! per-metric time series, stamped with the step of the collector handle that
+//! recorded it. The execution engine hands each block a handle at the current
+//! batch's index, so [`MetricsCollector::series`] traces how a metric such as
+//! `hit_rate_pct` evolves batch by batch. History is off by default, since it
+//! grows with every recorded value.
+//!
+//! ## Export
+//!
+//! [`MetricsCollector::to_prometheus`] and [`MetricsCollector::to_json`] export
+//! every recorded metric, typed by the [`MetricDefinition`]s registered with
+//! [`MetricsCollector::register_definitions`] (the engine registers each
+//! block's definitions before running it):
+//!
+//! | Metric type | Exported as |
+//! |-------------|-------------|
+//! | `Counter` | counter: the sum of recorded values, or the latest for a running total |
+//! | `Gauge` | gauge: the latest recorded value |
+//! | `Histogram`, `Timing` | histogram: cumulative buckets, sum and count |
+//! | no definition | untyped: the latest recorded value |
+//!
+//! A counter registered with [`MetricsCollector::register_running_totals`]
+//! is recorded as a running total — most blocks record their lifetime count
+//! once per call — so summing its values would count every batch again; it
+//! exports its latest value instead. A counter built with
+//! [`MetricsCollector::increment`] is always summed.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// Metric definition describing a metric that a block can collect
@@ -55,8 +88,68 @@ pub enum AggregationType {
     P95,
     /// 99th percentile
     P99,
+    /// Number of recorded values
+    Count,
+}
+
+impl AggregationType {
+    /// Every aggregation, in declaration order
+    pub const ALL: [AggregationType; 8] = [
+        AggregationType::Sum,
+        AggregationType::Avg,
+        AggregationType::Min,
+        AggregationType::Max,
+        AggregationType::P50,
+        AggregationType::P95,
+        AggregationType::P99,
+        AggregationType::Count,
+    ];
+}
+
+/// Bucket upper bounds used for a histogram metric that has none configured.
+pub const DEFAULT_HISTOGRAM_BUCKETS: [f64; 12] =
+    [0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 1000.0];
+
+/// Bucketed distribution of a histogram metric's observations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    /// Ascending bucket upper bounds (inclusive)
+    pub bounds: Vec<f64>,
+    /// Observations per bucket; the last entry counts values above every bound
+    pub counts: Vec<u64>,
+    /// Sum of all observations
+    pub sum: f64,
+    /// Number of observations
+    pub count: u64,
 }
 
+impl Histogram {
+    /// Create an empty histogram; `bounds` are sorted and deduplicated
+    pub fn new(mut bounds: Vec<f64>) -> Self {
+        bounds.retain(|b| !b.is_nan());
+        bounds.sort_by(|a, b| a.total_cmp(b));
+        bounds.dedup();
+        let counts = vec![0; bounds.len() + 1];
+        Self {
+            bounds,
+            counts,
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Count one observation into its bucket
+    pub fn observe(&mut self, value: f64) {
+        let bucket = self.bounds.partition_point(|&bound| bound < value);
+        self.counts[bucket] += 1;
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// A metric's (step, value) points, in recording order
+type Series = Vec<(u64, f64)>;
+
 /// Thread-safe metrics collector for runtime metric collection
 ///
 /// This collector stores raw metric values and provides aggregation functions
@@ -64,6 +157,21 @@ pub enum AggregationType {
 pub struct MetricsCollector {
     /// Stores metric values keyed by metric ID
     metrics: Arc<Mutex<HashMap<String, Vec<f64>>>>,
+    /// Bucketed counts of observed (histogram) metrics, keyed by metric ID
+    histograms: Arc<Mutex<HashMap<String, Histogram>>>,
+    /// Whether recorded values are also kept as time series
+    history_enabled: Arc<AtomicBool>,
+    /// (step, value) points keyed by metric ID, while history is enabled
+    history: Arc<Mutex<HashMap<String, Series>>>,
+    /// Step stamped on this handle's time-series points (not shared by clones
+    /// made with `at_step`)
+    step: u64,
+    /// Definitions used to type exported metrics, keyed by metric ID
+    definitions: Arc<Mutex<HashMap<String, MetricDefinition>>>,
+    /// Counters recorded as running totals rather than per-call counts
+    running_totals: Arc<Mutex<HashSet<String>>>,
+    /// Metrics that have been recorded with `increment`
+    incremented: Arc<Mutex<HashSet<String>>>,
 }
 
 impl MetricsCollector {
@@ -71,9 +179,95 @@ impl MetricsCollector {
     pub fn new() -> Self {
         Self {
             metrics: Arc::new(Mutex::new(HashMap::new())),
+            histograms: Arc::new(Mutex::new(HashMap::new())),
+            history_enabled: Arc::new(AtomicBool::new(false)),
+            history: Arc::new(Mutex::new(HashMap::new())),
+            step: 0,
+            definitions: Arc::new(Mutex::new(HashMap::new())),
+            running_totals: Arc::new(Mutex::new(HashSet::new())),
+            incremented: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Register metric definitions used to type exported metrics
+    ///
+    /// A later definition for the same metric ID replaces an earlier one.
+    /// Definitions survive `clear`.
+    pub fn register_definitions(&self, definitions: &[MetricDefinition]) {
+        let mut registered = self.definitions.lock().unwrap();
+        for definition in definitions {
+            registered.insert(definition.id.clone(), definition.clone());
+        }
+    }
+
+    /// Mark the counters among `definitions` as recorded as running totals,
+    /// so exports report their latest value rather than their sum
+    ///
+    /// The engine calls this for blocks whose
+    /// [`reports_running_totals`](crate::core::block::Block::reports_running_totals)
+    /// is true. Survives `clear`.
+    pub fn register_running_totals(&self, definitions: &[MetricDefinition]) {
+        let mut running_totals = self.running_totals.lock().unwrap();
+        for definition in definitions {
+            if definition.metric_type == MetricType::Counter {
+                running_totals.insert(definition.id.clone());
+            }
         }
     }
 
+    /// Get the registered definition of a metric
+    pub fn definition(&self, metric_id: &str) -> Option<MetricDefinition> {
+        let definitions = self.definitions.lock().unwrap();
+        definitions.get(metric_id).cloned()
+    }
+
+    /// A handle to the same metrics that stamps time-series points with `step`
+    ///
+    /// # Examples
+    /// ```
+    /// use block_system::core::metrics::MetricsCollector;
+    ///
+    /// let collector = MetricsCollector::new();
+    /// collector.set_history_enabled(true);
+    /// collector.at_step(0).record("hit_rate_pct", 40.0);
+    /// collector.at_step(1).record("hit_rate_pct", 65.0);
+    /// assert_eq!(collector.series("hit_rate_pct"), vec![(0, 40.0), (1, 65.0)]);
+    /// ```
+    pub fn at_step(&self, step: u64) -> Self {
+        Self {
+            step,
+            ..self.clone()
+        }
+    }
+
+    /// Step stamped on this handle's time-series points
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// Start or stop keeping a time series per metric
+    ///
+    /// Applies to every handle sharing these metrics. Stopping keeps the
+    /// points already recorded.
+    pub fn set_history_enabled(&self, enabled: bool) {
+        self.history_enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether recorded values are also kept as time series
+    pub fn history_enabled(&self) -> bool {
+        self.history_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Get a metric's time series
+    ///
+    /// # Returns
+    /// The (step, value) points recorded while history was enabled, in
+    /// recording order, or an empty vector if there are none
+    pub fn series(&self, metric_id: &str) -> Vec<(u64, f64)> {
+        let history = self.history.lock().unwrap();
+        history.get(metric_id).cloned().unwrap_or_default()
+    }
+
     /// Record a metric value
     ///
     /// # Arguments
@@ -93,6 +287,15 @@ pub fn record(&self, metric_id: &str, value: f64) {
             .entry(metric_id.to_string())
             .or_insert_with(Vec::new)
             .push(value);
+        drop(metrics);
+
+        if self.history_enabled() {
+            let mut history = self.history.lock().unwrap();
+            history
+                .entry(metric_id.to_string())
+                .or_default()
+                .push((self.step, value));
+        }
     }
 
     /// Increment a counter metric by 1
@@ -111,6 +314,76 @@ pub fn record(&self, metric_id: &str, value: f64) {
     /// ```
     pub fn increment(&self, metric_id: &str) {
         self.record(metric_id, 1.0);
+        let mut incremented = self.incremented.lock().unwrap();
+        incremented.insert(metric_id.to_string());
+    }
+
+    /// Record one observation of a histogram metric
+    ///
+    /// The value is kept as a sample (so `aggregate` and `percentile` see it)
+    /// and counted into the metric's buckets, which default to
+    /// [`DEFAULT_HISTOGRAM_BUCKETS`].
+    ///
+    /// # Examples
+    /// ```
+    /// use block_system::core::metrics::MetricsCollector;
+    ///
+    /// let collector = MetricsCollector::new();
+    /// collector.set_buckets("latency_ms", vec![1.0, 10.0]);
+    /// for latency in [0.5, 0.7, 4.0, 30.0] {
+    ///     collector.observe("latency_ms", latency);
+    /// }
+    /// assert_eq!(collector.histogram("latency_ms").unwrap().counts, vec![2, 1, 1]);
+    /// ```
+    pub fn observe(&self, metric_id: &str, value: f64) {
+        self.record(metric_id, value);
+        let mut histograms = self.histograms.lock().unwrap();
+        histograms
+            .entry(metric_id.to_string())
+            .or_insert_with(|| Histogram::new(DEFAULT_HISTOGRAM_BUCKETS.to_vec()))
+            .observe(value);
+    }
+
+    /// Set the bucket upper bounds of a histogram metric
+    ///
+    /// Replaces any buckets already configured for the metric and discards
+    /// their counts; recorded samples are kept.
+    pub fn set_buckets(&self, metric_id: &str, bounds: Vec<f64>) {
+        let mut histograms = self.histograms.lock().unwrap();
+        histograms.insert(metric_id.to_string(), Histogram::new(bounds));
+    }
+
+    /// Get the bucketed distribution of a histogram metric
+    ///
+    /// # Returns
+    /// The histogram, or None if the metric has never been observed or had
+    /// buckets set
+    pub fn histogram(&self, metric_id: &str) -> Option<Histogram> {
+        let histograms = self.histograms.lock().unwrap();
+        histograms.get(metric_id).cloned()
+    }
+
+    /// Calculate a percentile of a metric's recorded values
+    ///
+    /// # Arguments
+    /// * `metric_id` - The ID of the metric
+    /// * `p` - Percentile to calculate, from 0 to 100 (e.g. 99.0 for p99)
+    ///
+    /// # Returns
+    /// The interpolated percentile, or None if no values have been recorded
+    ///
+    /// # Examples
+    /// ```
+    /// use block_system::core::metrics::MetricsCollector;
+    ///
+    /// let collector = MetricsCollector::new();
+    /// for i in 1..=100 {
+    ///     collector.observe("chain_length", i as f64);
+    /// }
+    /// assert_eq!(collector.percentile("chain_length", 50.0), Some(50.5));
+    /// ```
+    pub fn percentile(&self, metric_id: &str, p: f64) -> Option<f64> {
+        Self::percentile_of(&self.get_values(metric_id), (p / 100.0).clamp(0.0, 1.0))
     }
 
     /// Get all recorded values for a metric
@@ -127,6 +400,9 @@ pub fn get_values(&self, metric_id: &str) -> Vec<f64> {
 
     /// Aggregate metric values using the specified aggregation type
     ///
+    /// Aggregates over every value recorded for the metric since the last
+    /// `clear`. NaN values are ignored by `Min` and `Max`.
+    ///
     /// # Arguments
     /// * `metric_id` - The ID of the metric
     /// * `agg_type` - The type of aggregation to apply
@@ -156,20 +432,52 @@ pub fn aggregate(&self, metric_id: &str, agg_type: AggregationType) -> Option<f6
         match agg_type {
             AggregationType::Sum => Some(values.iter().sum()),
             AggregationType::Avg => Some(values.iter().sum::<f64>() / values.len() as f64),
-            AggregationType::Min => values
-                .iter()
-                .cloned()
-                .min_by(|a, b| a.partial_cmp(b).unwrap()),
-            AggregationType::Max => values
-                .iter()
-                .cloned()
-                .max_by(|a, b| a.partial_cmp(b).unwrap()),
-            AggregationType::P50 => Self::percentile(&values, 0.5),
-            AggregationType::P95 => Self::percentile(&values, 0.95),
-            AggregationType::P99 => Self::percentile(&values, 0.99),
+            AggregationType::Min => Some(values.iter().copied().fold(f64::INFINITY, f64::min)),
+            AggregationType::Max => Some(values.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+            AggregationType::P50 => Self::percentile_of(&values, 0.5),
+            AggregationType::P95 => Self::percentile_of(&values, 0.95),
+            AggregationType::P99 => Self::percentile_of(&values, 0.99),
+            AggregationType::Count => Some(values.len() as f64),
         }
     }
 
+    /// Apply every aggregation a metric definition declares
+    ///
+    /// # Returns
+    /// Each declared aggregation with its value, in declaration order, or an
+    /// empty vector if the metric has no recorded values
+    ///
+    /// # Examples
+    /// ```
+    /// use block_system::core::metrics::{
+    ///     AggregationType, MetricDefinition, MetricType, MetricsCollector,
+    /// };
+    ///
+    /// let definition = MetricDefinition {
+    ///     id: "latency_ms".into(),
+    ///     name: "Latency".into(),
+    ///     metric_type: MetricType::Timing,
+    ///     unit: "ms".into(),
+    ///     description: "Request latency".into(),
+    ///     aggregations: vec![AggregationType::Avg, AggregationType::Count],
+    /// };
+    /// let collector = MetricsCollector::new();
+    /// collector.record("latency_ms", 10.0);
+    /// collector.record("latency_ms", 30.0);
+    ///
+    /// assert_eq!(
+    ///     collector.aggregate_all(&definition),
+    ///     vec![(AggregationType::Avg, 20.0), (AggregationType::Count, 2.0)]
+    /// );
+    /// ```
+    pub fn aggregate_all(&self, definition: &MetricDefinition) -> Vec<(AggregationType, f64)> {
+        definition
+            .aggregations
+            .iter()
+            .filter_map(|&agg| self.aggregate(&definition.id, agg).map(|value| (agg, value)))
+            .collect()
+    }
+
     /// Calculate a percentile from a set of values
     ///
     /// Uses linear interpolation between values for more accurate percentile calculation.
@@ -180,7 +488,7 @@ pub fn aggregate(&self, metric_id: &str, agg_type: AggregationType) -> Option<f6
     ///
     /// # Returns
     /// The percentile value, or None if values is empty
-    fn percentile(values: &[f64], p: f64) -> Option<f64> {
+    fn percentile_of(values: &[f64], p: f64) -> Option<f64> {
         if values.is_empty() {
             return None;
         }
@@ -203,10 +511,179 @@ fn percentile(values: &[f64], p: f64) -> Option<f64> {
         }
     }
 
+    /// Export every recorded metric in the Prometheus text exposition format
+    ///
+    /// Metrics are sorted by ID; characters Prometheus doesn't allow in a
+    /// metric name are replaced with `_`.
+    ///
+    /// # Examples
+    /// ```
+    /// use block_system::core::metrics::{
+    ///     AggregationType, MetricDefinition, MetricType, MetricsCollector,
+    /// };
+    ///
+    /// let collector = MetricsCollector::new();
+    /// collector.register_definitions(&[MetricDefinition {
+    ///     id: "cache_hits".into(),
+    ///     name: "Cache Hits".into(),
+    ///     metric_type: MetricType::Counter,
+    ///     unit: "pages".into(),
+    ///     description: "Page requests served from cache".into(),
+    ///     aggregations: vec![AggregationType::Sum],
+    /// }]);
+    /// collector.increment("cache_hits");
+    /// collector.increment("cache_hits");
+    ///
+    /// assert_eq!(
+    ///     collector.to_prometheus(),
+    ///     concat!(
+    ///         "# HELP cache_hits Page requests served from cache (pages)\n",
+    ///         "# TYPE cache_hits counter\n",
+    ///         "cache_hits 2\n",
+    ///     )
+    /// );
+    /// ```
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        for export in self.exports() {
+            let name = prometheus_name(&export.id);
+            if let Some(definition) = &export.definition {
+                let mut help = definition.description.replace('\\', "\\\\").replace('\n', "\\n");
+                if !definition.unit.is_empty() {
+                    help.push_str(&format!(" ({})", definition.unit));
+                }
+                out.push_str(&format!("# HELP {} {}\n", name, help));
+            }
+            out.push_str(&format!("# TYPE {} {}\n", name, export.kind.name()));
+            match &export.kind {
+                ExportKind::Histogram(histogram) => {
+                    let mut cumulative = 0;
+                    for (bound, count) in histogram.bounds.iter().zip(&histogram.counts) {
+                        cumulative += count;
+                        out.push_str(&format!(
+                            "{}_bucket{{le=\"{}\"}} {}\n",
+                            name,
+                            prometheus_value(*bound),
+                            cumulative
+                        ));
+                    }
+                    out.push_str(&format!("{}_bucket{{le=\"+Inf\"}} {}\n", name, histogram.count));
+                    out.push_str(&format!("{}_sum {}\n", name, prometheus_value(histogram.sum)));
+                    out.push_str(&format!("{}_count {}\n", name, histogram.count));
+                }
+                ExportKind::Counter(value)
+                | ExportKind::Gauge(value)
+                | ExportKind::Untyped(value) => {
+                    out.push_str(&format!("{} {}\n", name, prometheus_value(*value)));
+                }
+            }
+        }
+        out
+    }
+
+    /// Export every recorded metric as JSON, keyed by metric ID
+    ///
+    /// Each entry has the metric's `type`, plus `name`, `unit`, `description`
+    /// and the values of its declared `aggregations` when it has a
+    /// definition. Counters, gauges and untyped metrics carry a `value`;
+    /// histograms carry `count`, `sum` and cumulative `buckets`, the last
+    /// with `le: "+Inf"`.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::{json, Map, Value};
+
+        let mut out = Map::new();
+        for export in self.exports() {
+            let mut entry = Map::new();
+            entry.insert("type".into(), json!(export.kind.name()));
+            if let Some(definition) = &export.definition {
+                entry.insert("name".into(), json!(definition.name));
+                entry.insert("unit".into(), json!(definition.unit));
+                entry.insert("description".into(), json!(definition.description));
+                let aggregations: Map<String, Value> = self
+                    .aggregate_all(definition)
+                    .into_iter()
+                    .map(|(agg, value)| (format!("{:?}", agg), json!(value)))
+                    .collect();
+                entry.insert("aggregations".into(), Value::Object(aggregations));
+            }
+            match &export.kind {
+                ExportKind::Histogram(histogram) => {
+                    let mut cumulative = 0;
+                    let mut buckets: Vec<Value> = histogram
+                        .bounds
+                        .iter()
+                        .zip(&histogram.counts)
+                        .map(|(bound, count)| {
+                            cumulative += count;
+                            json!({ "le": bound, "count": cumulative })
+                        })
+                        .collect();
+                    buckets.push(json!({ "le": "+Inf", "count": histogram.count }));
+                    entry.insert("count".into(), json!(histogram.count));
+                    entry.insert("sum".into(), json!(histogram.sum));
+                    entry.insert("buckets".into(), Value::Array(buckets));
+                }
+                ExportKind::Counter(value)
+                | ExportKind::Gauge(value)
+                | ExportKind::Untyped(value) => {
+                    entry.insert("value".into(), json!(value));
+                }
+            }
+            out.insert(export.id, Value::Object(entry));
+        }
+        Value::Object(out)
+    }
+
+    /// Every recorded metric, sorted by ID, typed by its definition
+    fn exports(&self) -> Vec<MetricExport> {
+        let mut ids = self.get_metric_ids();
+        ids.sort();
+        ids.into_iter()
+            .map(|id| {
+                let definition = self.definition(&id);
+                let values = self.get_values(&id);
+                let latest = values.last().copied().unwrap_or(0.0);
+                let kind = match definition.as_ref().map(|d| d.metric_type) {
+                    Some(MetricType::Counter) if self.is_running_total(&id) => {
+                        ExportKind::Counter(latest)
+                    }
+                    Some(MetricType::Counter) => ExportKind::Counter(values.iter().sum()),
+                    Some(MetricType::Gauge) => ExportKind::Gauge(latest),
+                    Some(MetricType::Histogram | MetricType::Timing) => {
+                        // Values recorded with `record` rather than `observe`
+                        // are bucketed on the fly.
+                        let histogram = self.histogram(&id).unwrap_or_else(|| {
+                            let mut histogram = Histogram::new(DEFAULT_HISTOGRAM_BUCKETS.to_vec());
+                            values.iter().for_each(|&value| histogram.observe(value));
+                            histogram
+                        });
+                        ExportKind::Histogram(histogram)
+                    }
+                    None => ExportKind::Untyped(latest),
+                };
+                MetricExport { id, definition, kind }
+            })
+            .collect()
+    }
+
+    /// Whether `metric_id` is a registered running total never recorded with
+    /// `increment`
+    fn is_running_total(&self, metric_id: &str) -> bool {
+        let running_totals = self.running_totals.lock().unwrap();
+        let incremented = self.incremented.lock().unwrap();
+        running_totals.contains(metric_id) && !incremented.contains(metric_id)
+    }
+
     /// Clear all recorded metrics
     pub fn clear(&self) {
         let mut metrics = self.metrics.lock().unwrap();
         metrics.clear();
+        let mut histograms = self.histograms.lock().unwrap();
+        histograms.clear();
+        let mut history = self.history.lock().unwrap();
+        history.clear();
+        let mut incremented = self.incremented.lock().unwrap();
+        incremented.clear();
     }
 
     /// Get all metric IDs that have recorded values
@@ -222,6 +699,56 @@ pub fn get_count(&self, metric_id: &str) -> usize {
     }
 }
 
+/// One metric as exported by `to_prometheus` / `to_json`.
+struct MetricExport {
+    id: String,
+    definition: Option<MetricDefinition>,
+    kind: ExportKind,
+}
+
+enum ExportKind {
+    Counter(f64),
+    Gauge(f64),
+    Histogram(Histogram),
+    Untyped(f64),
+}
+
+impl ExportKind {
+    fn name(&self) -> &'static str {
+        match self {
+            ExportKind::Counter(_) => "counter",
+            ExportKind::Gauge(_) => "gauge",
+            ExportKind::Histogram(_) => "histogram",
+            ExportKind::Untyped(_) => "untyped",
+        }
+    }
+}
+
+/// A metric ID as a valid Prometheus metric name.
+fn prometheus_name(id: &str) -> String {
+    let mut name: String = id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == ':' { c } else { '_' })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// A sample value in Prometheus syntax.
+fn prometheus_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".into()
+    } else if value == f64::INFINITY {
+        "+Inf".into()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".into()
+    } else {
+        value.to_string()
+    }
+}
+
 impl Default for MetricsCollector {
     fn default() -> Self {
         Self::new()
@@ -232,6 +759,13 @@ impl Clone for MetricsCollector {
     fn clone(&self) -> Self {
         Self {
             metrics: Arc::clone(&self.metrics),
+            histograms: Arc::clone(&self.histograms),
+            history_enabled: Arc::clone(&self.history_enabled),
+            history: Arc::clone(&self.history),
+            step: self.step,
+            definitions: Arc::clone(&self.definitions),
+            running_totals: Arc::clone(&self.running_totals),
+            incremented: Arc::clone(&self.incremented),
         }
     }
 }
@@ -358,6 +892,80 @@ fn test_aggregation_min_max() {
         assert_eq!(collector.aggregate("metric", AggregationType::Max), Some(8.0));
     }
 
+    #[test]
+    fn test_aggregation_count() {
+        let collector = MetricsCollector::new();
+        collector.record("metric", 4.0);
+        collector.record("metric", 4.0);
+        collector.record("metric", -1.0);
+
+        assert_eq!(collector.aggregate("metric", AggregationType::Count), Some(3.0));
+        assert_eq!(collector.aggregate("nonexistent", AggregationType::Count), None);
+    }
+
+    #[test]
+    fn test_aggregation_covers_full_history() {
+        let collector = MetricsCollector::new();
+        for value in [3.0, -2.0, 7.0, 0.0, 2.0] {
+            collector.record("metric", value);
+        }
+
+        let expected = [
+            (AggregationType::Sum, 10.0),
+            (AggregationType::Avg, 2.0),
+            (AggregationType::Min, -2.0),
+            (AggregationType::Max, 7.0),
+            (AggregationType::P50, 2.0),
+            (AggregationType::P95, 6.2),
+            (AggregationType::P99, 6.84),
+            (AggregationType::Count, 5.0),
+        ];
+        assert_eq!(expected.len(), AggregationType::ALL.len());
+        for (agg, want) in expected {
+            let got = collector.aggregate("metric", agg).unwrap();
+            assert!((got - want).abs() < 1e-9, "{:?}: {} != {}", agg, got, want);
+        }
+        for agg in AggregationType::ALL {
+            assert_eq!(collector.aggregate("nonexistent", agg), None);
+        }
+    }
+
+    #[test]
+    fn test_min_max_ignore_nan() {
+        let collector = MetricsCollector::new();
+        collector.record("metric", 1.0);
+        collector.record("metric", f64::NAN);
+        collector.record("metric", 5.0);
+
+        assert_eq!(collector.aggregate("metric", AggregationType::Min), Some(1.0));
+        assert_eq!(collector.aggregate("metric", AggregationType::Max), Some(5.0));
+    }
+
+    #[test]
+    fn test_aggregate_all_follows_definition() {
+        let definition = MetricDefinition {
+            id: "pages_read".to_string(),
+            name: "Pages Read".to_string(),
+            metric_type: MetricType::Counter,
+            unit: "pages".to_string(),
+            description: "Pages read".to_string(),
+            aggregations: vec![AggregationType::Sum, AggregationType::Max, AggregationType::Min],
+        };
+        let collector = MetricsCollector::new();
+        assert!(collector.aggregate_all(&definition).is_empty());
+
+        collector.record("pages_read", 2.0);
+        collector.record("pages_read", 6.0);
+        assert_eq!(
+            collector.aggregate_all(&definition),
+            vec![
+                (AggregationType::Sum, 8.0),
+                (AggregationType::Max, 6.0),
+                (AggregationType::Min, 2.0),
+            ]
+        );
+    }
+
     #[test]
     fn test_percentile_p50() {
         let collector = MetricsCollector::new();
@@ -486,6 +1094,201 @@ fn test_percentile_with_two_values() {
         assert_eq!(p50, 15.0); // Should be the average of 10 and 20
     }
 
+    #[test]
+    fn test_observe_fills_buckets() {
+        let collector = MetricsCollector::new();
+        collector.set_buckets("latency_ms", vec![10.0, 1.0, 5.0]);
+        for value in [0.5, 1.0, 3.0, 5.0, 7.0, 12.0] {
+            collector.observe("latency_ms", value);
+        }
+
+        let histogram = collector.histogram("latency_ms").unwrap();
+        // Bounds are sorted; a value on a bound falls in that bound's bucket.
+        assert_eq!(histogram.bounds, vec![1.0, 5.0, 10.0]);
+        assert_eq!(histogram.counts, vec![2, 2, 1, 1]);
+        assert_eq!(histogram.count, 6);
+        assert_eq!(histogram.sum, 28.5);
+        // Observations are samples too.
+        assert_eq!(collector.get_count("latency_ms"), 6);
+        assert_eq!(collector.aggregate("latency_ms", AggregationType::Max), Some(12.0));
+    }
+
+    #[test]
+    fn test_observe_uses_default_buckets() {
+        let collector = MetricsCollector::new();
+        collector.observe("chain_length", 3.0);
+        collector.observe("chain_length", 5000.0);
+
+        let histogram = collector.histogram("chain_length").unwrap();
+        assert_eq!(histogram.bounds, DEFAULT_HISTOGRAM_BUCKETS.to_vec());
+        assert_eq!(histogram.counts[5], 1);
+        assert_eq!(*histogram.counts.last().unwrap(), 1);
+        assert!(collector.histogram("never_observed").is_none());
+
+        collector.clear();
+        assert!(collector.histogram("chain_length").is_none());
+    }
+
+    #[test]
+    fn test_percentile_exposes_the_tail() {
+        let collector = MetricsCollector::new();
+        // 98 fast requests and 2 slow ones: the mean hides them, p99 doesn't.
+        for _ in 0..98 {
+            collector.observe("latency_ms", 1.0);
+        }
+        collector.observe("latency_ms", 100.0);
+        collector.observe("latency_ms", 100.0);
+
+        assert_eq!(collector.percentile("latency_ms", 50.0), Some(1.0));
+        assert!(collector.aggregate("latency_ms", AggregationType::Avg).unwrap() < 3.0);
+        assert_eq!(collector.percentile("latency_ms", 99.0), Some(100.0));
+        assert_eq!(
+            collector.percentile("latency_ms", 95.0),
+            collector.aggregate("latency_ms", AggregationType::P95)
+        );
+        assert_eq!(collector.percentile("nonexistent", 50.0), None);
+    }
+
+    #[test]
+    fn test_history_disabled_by_default() {
+        let collector = MetricsCollector::new();
+        collector.record("hit_rate_pct", 50.0);
+        assert!(!collector.history_enabled());
+        assert!(collector.series("hit_rate_pct").is_empty());
+    }
+
+    #[test]
+    fn test_series_stamps_each_handle_step() {
+        let collector = MetricsCollector::new();
+        collector.set_history_enabled(true);
+
+        let first = collector.at_step(0);
+        let second = collector.at_step(1);
+        first.record("write_amplification", 1.0);
+        second.record("write_amplification", 2.5);
+        second.observe("write_amplification", 3.0);
+        // Handles share the flag and the storage, but not the step.
+        second.set_history_enabled(false);
+        first.record("write_amplification", 9.0);
+
+        assert_eq!(collector.step(), 0);
+        assert_eq!(second.step(), 1);
+        assert_eq!(
+            collector.series("write_amplification"),
+            vec![(0, 1.0), (1, 2.5), (1, 3.0)]
+        );
+        // Samples are unaffected by the history flag.
+        assert_eq!(collector.get_count("write_amplification"), 4);
+
+        collector.clear();
+        assert!(collector.series("write_amplification").is_empty());
+    }
+
+    fn definition(id: &str, metric_type: MetricType) -> MetricDefinition {
+        MetricDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            metric_type,
+            unit: "ms".to_string(),
+            description: format!("{} description", id),
+            aggregations: vec![AggregationType::Max, AggregationType::Count],
+        }
+    }
+
+    #[test]
+    fn test_prometheus_export_by_type() {
+        let collector = MetricsCollector::new();
+        collector.register_definitions(&[
+            definition("current_size", MetricType::Gauge),
+            definition("latency", MetricType::Histogram),
+            definition("flush_time", MetricType::Timing),
+        ]);
+        collector.record("current_size", 10.0);
+        collector.record("current_size", 7.0);
+        collector.set_buckets("latency", vec![1.0, 5.0]);
+        for value in [0.5, 2.0, 3.0, 9.0] {
+            collector.observe("latency", value);
+        }
+        // Recorded rather than observed: bucketed with the default bounds.
+        collector.record("flush_time", 0.3);
+        collector.record("lsm.level-0", 2.0);
+
+        let text = collector.to_prometheus();
+        let expected = "\
+# HELP current_size current_size description (ms)
+# TYPE current_size gauge
+current_size 7
+";
+        assert!(text.starts_with(expected), "{}", text);
+        assert!(text.contains(
+            "\
+# TYPE latency histogram
+latency_bucket{le=\"1\"} 1
+latency_bucket{le=\"5\"} 3
+latency_bucket{le=\"+Inf\"} 4
+latency_sum 14.5
+latency_count 4
+"
+        ));
+        assert!(text.contains(
+            "flush_time_bucket{le=\"0.25\"} 0\nflush_time_bucket{le=\"0.5\"} 1\n"
+        ));
+        // No definition: untyped, latest value, name sanitized.
+        assert!(text.ends_with("# TYPE lsm_level_0 untyped\nlsm_level_0 2\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_counter_export_running_totals() {
+        let collector = MetricsCollector::new();
+        let counters = [
+            definition("versions_created", MetricType::Counter),
+            definition("deletes", MetricType::Counter),
+            definition("rows_out", MetricType::Counter),
+        ];
+        collector.register_definitions(&counters);
+        collector.register_running_totals(&counters[..2]);
+
+        // Three batches: a running total, increments, and per-call counts.
+        for total in [6.0, 13.0, 20.0] {
+            collector.record("versions_created", total);
+            collector.increment("deletes");
+            collector.record("rows_out", 5.0);
+        }
+
+        let text = collector.to_prometheus();
+        assert!(text.contains("\nversions_created 20\n"), "{}", text);
+        assert!(text.contains("\ndeletes 3\n"), "{}", text);
+        assert!(text.contains("\nrows_out 15\n"), "{}", text);
+        assert_eq!(collector.to_json()["versions_created"]["value"], 20.0);
+    }
+
+    #[test]
+    fn test_json_export() {
+        let collector = MetricsCollector::new();
+        collector.register_definitions(&[definition("latency", MetricType::Histogram)]);
+        collector.set_buckets("latency", vec![1.0]);
+        collector.observe("latency", 0.5);
+        collector.observe("latency", 4.0);
+        collector.record("untracked", 3.0);
+
+        let json = collector.to_json();
+        assert_eq!(
+            json["latency"],
+            serde_json::json!({
+                "type": "histogram",
+                "name": "latency",
+                "unit": "ms",
+                "description": "latency description",
+                "aggregations": { "Max": 4.0, "Count": 2.0 },
+                "count": 2,
+                "sum": 4.5,
+                "buckets": [{ "le": 1.0, "count": 1 }, { "le": "+Inf", "count": 2 }],
+            })
+        );
+        assert_eq!(json["untracked"], serde_json::json!({ "type": "untyped", "value": 3.0 }));
+        assert_eq!(MetricsCollector::new().to_json(), serde_json::json!({}));
+    }
+
     #[test]
     fn test_metric_definition_serialization() {
         let metric = MetricDefinition {
@@ -11,6 +11,7 @@
 pub mod registry;
 
 use serde::{Deserialize, Serialize};
+use std::any::Any;
 use uuid::Uuid;
 
 /// Unique identifier for a block
@@ -40,8 +41,33 @@ pub struct BlockMetadata {
     pub tags: Vec<String>,
 }
 
+/// Upcast to `Any`, so a block trait object — of this module's [`Block`] or
+/// of [`block::Block`] — can be downcast to its concrete type to reach
+/// methods the trait doesn't expose (say, `LSMTreeBlock::range_scan`).
+///
+/// Call it on the trait object, not the box: `Box<dyn Block>` is itself
+/// `Any`, so `boxed.as_any()` is the box; `boxed.as_ref().as_any()` is the
+/// block.
+pub trait AsAny {
+    /// `self` as `&dyn Any`
+    fn as_any(&self) -> &dyn Any;
+
+    /// `self` as `&mut dyn Any`
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 /// Core block trait defining the interface for all blocks
-pub trait Block: Send + Sync {
+pub trait Block: AsAny + Send + Sync {
     /// Get the block's unique identifier
     fn id(&self) -> BlockId;
 
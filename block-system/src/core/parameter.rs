@@ -6,6 +6,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::core::block::BlockError;
+
 /// Parameter definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Parameter {
@@ -27,6 +29,113 @@ pub struct Parameter {
     pub ui_hint: Option<ParameterUIHint>,
 }
 
+impl Parameter {
+    /// Check `value` against this parameter's type and declared constraints
+    ///
+    /// Numbers are checked against `min`/`max`, strings against
+    /// `allowed_values` and `min_length`/`max_length`, and arrays as in
+    /// [`list_param`]. Null is only rejected for required parameters.
+    ///
+    /// # Examples
+    /// ```
+    /// use block_system::core::parameter::{
+    ///     Parameter, ParameterConstraints, ParameterType, ParameterValue,
+    /// };
+    ///
+    /// let param = Parameter {
+    ///     id: "memtable_size".into(),
+    ///     name: "Memtable Size".into(),
+    ///     param_type: ParameterType::Number,
+    ///     description: "Records buffered before a flush".into(),
+    ///     default_value: ParameterValue::Integer(1000),
+    ///     required: false,
+    ///     constraints: Some(ParameterConstraints::new().with_min(10.0).with_max(100000.0)),
+    ///     ui_hint: None,
+    /// };
+    /// assert!(param.validate(&ParameterValue::Integer(500)).valid);
+    /// let result = param.validate(&ParameterValue::Integer(5));
+    /// assert_eq!(result.errors, vec!["memtable_size must be between 10 and 100000, got 5"]);
+    /// ```
+    pub fn validate(&self, value: &ParameterValue) -> ValidationResult {
+        match self.check(value) {
+            Ok(()) => ValidationResult::ok(),
+            Err(msg) => ValidationResult::error(msg),
+        }
+    }
+
+    fn check(&self, value: &ParameterValue) -> Result<(), String> {
+        let id = &self.id;
+        if value.is_null() {
+            if self.required {
+                return Err(format!("{} is required", id));
+            }
+            return Ok(());
+        }
+        let none = ParameterConstraints::new();
+        let constraints = self.constraints.as_ref().unwrap_or(&none);
+        let expected = |noun: &str| format!("{} must be {}, got {}", id, noun, value.describe());
+
+        match self.param_type {
+            ParameterType::Number => {
+                let n = value.as_number().ok_or_else(|| expected("a number"))?;
+                match (constraints.min, constraints.max) {
+                    (Some(min), Some(max)) if !(min..=max).contains(&n) => {
+                        Err(format!("{} must be between {} and {}, got {}", id, min, max, n))
+                    }
+                    (Some(min), _) if !(min..).contains(&n) => {
+                        Err(format!("{} must be at least {}, got {}", id, min, n))
+                    }
+                    (_, Some(max)) if !(..=max).contains(&n) => {
+                        Err(format!("{} must be at most {}, got {}", id, max, n))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            ParameterType::String | ParameterType::Enum => {
+                let s = value.as_string().ok_or_else(|| expected("a string"))?;
+                if let Some(allowed) = &constraints.allowed_values {
+                    let choices: Vec<&str> = allowed.iter().filter_map(|a| a.as_string()).collect();
+                    if !choices.contains(&s) {
+                        return Err(choices_message(id, &choices, value));
+                    }
+                }
+                let len = s.chars().count();
+                match (constraints.min_length, constraints.max_length) {
+                    (Some(min), _) if len < min => {
+                        Err(format!("{} must be at least {} characters, got {}", id, min, len))
+                    }
+                    (_, Some(max)) if len > max => {
+                        Err(format!("{} must be at most {} characters, got {}", id, max, len))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            ParameterType::Boolean => {
+                value.as_bool().map(|_| ()).ok_or_else(|| expected("a boolean"))
+            }
+            ParameterType::Object => {
+                value.as_object().map(|_| ()).ok_or_else(|| expected("an object"))
+            }
+            ParameterType::Array => list_items(id, value, constraints).map(|_| ()),
+        }
+    }
+}
+
+/// Check `values` against every declared parameter
+///
+/// Each present value is checked with [`Parameter::validate`]; an absent
+/// parameter takes its default, so it is only an error when it is required
+/// and has no default. Keys no parameter declares are ignored.
+pub fn validate_params(
+    parameters: &[Parameter],
+    values: &HashMap<String, ParameterValue>,
+) -> ValidationResult {
+    parameters.iter().fold(ValidationResult::ok(), |result, param| {
+        let value = values.get(&param.id).unwrap_or(&param.default_value);
+        result.merge(param.validate(value))
+    })
+}
+
 /// Parameter types
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ParameterType {
@@ -96,6 +205,11 @@ pub fn as_integer(&self) -> Option<i64> {
         }
     }
 
+    /// Try to convert to one of a fixed set of strings
+    pub fn as_enum<'a>(&'a self, choices: &[&str]) -> Option<&'a str> {
+        self.as_string().filter(|s| choices.contains(s))
+    }
+
     /// Try to convert to boolean
     pub fn as_bool(&self) -> Option<bool> {
         match self {
@@ -112,6 +226,34 @@ pub fn as_array(&self) -> Option<&Vec<ParameterValue>> {
         }
     }
 
+    /// Try to convert to a list of values
+    pub fn as_list(&self) -> Option<&[ParameterValue]> {
+        self.as_array().map(Vec::as_slice)
+    }
+
+    /// The parameter type this value is an instance of (`None` for null)
+    ///
+    /// Integers and floats are both [`ParameterType::Number`].
+    pub fn param_type(&self) -> Option<ParameterType> {
+        match self {
+            ParameterValue::String(_) => Some(ParameterType::String),
+            ParameterValue::Number(_) | ParameterValue::Integer(_) => Some(ParameterType::Number),
+            ParameterValue::Boolean(_) => Some(ParameterType::Boolean),
+            ParameterValue::Array(_) => Some(ParameterType::Array),
+            ParameterValue::Object(_) => Some(ParameterType::Object),
+            ParameterValue::Null => None,
+        }
+    }
+
+    /// Describe the value for an error message, e.g. `'abc'` or `a boolean`
+    fn describe(&self) -> String {
+        match self {
+            ParameterValue::String(s) => format!("'{}'", s),
+            ParameterValue::Null => "null".to_string(),
+            other => format!("a {}", type_noun(other.param_type().as_ref())),
+        }
+    }
+
     /// Try to convert to object
     pub fn as_object(&self) -> Option<&HashMap<String, ParameterValue>> {
         match self {
@@ -166,6 +308,9 @@ pub struct ParameterConstraints {
     pub min_length: Option<usize>,
     /// Maximum length (for strings/arrays)
     pub max_length: Option<usize>,
+    /// Type every element must have (for arrays)
+    #[serde(default)]
+    pub element_type: Option<ParameterType>,
 }
 
 impl ParameterConstraints {
@@ -178,6 +323,7 @@ pub fn new() -> Self {
             allowed_values: None,
             min_length: None,
             max_length: None,
+            element_type: None,
         }
     }
 
@@ -199,12 +345,23 @@ pub fn with_allowed_values(mut self, values: Vec<ParameterValue>) -> Self {
         self
     }
 
+    /// Restrict a string parameter to a fixed set of choices
+    pub fn with_choices(self, choices: &[&str]) -> Self {
+        self.with_allowed_values(choices.iter().map(|&c| ParameterValue::from(c)).collect())
+    }
+
     /// Set length constraints
     pub fn with_length_range(mut self, min_length: Option<usize>, max_length: Option<usize>) -> Self {
         self.min_length = min_length;
         self.max_length = max_length;
         self
     }
+
+    /// Require every element of an array parameter to have `element_type`
+    pub fn with_element_type(mut self, element_type: ParameterType) -> Self {
+        self.element_type = Some(element_type);
+        self
+    }
 }
 
 impl Default for ParameterConstraints {
@@ -213,6 +370,156 @@ fn default() -> Self {
     }
 }
 
+/// Read an enum parameter: a string that must be one of `choices`
+///
+/// # Returns
+/// * `Ok(None)` if the parameter is absent
+/// * `Ok(Some(choice))` if it is one of `choices`
+/// * `Err(BlockError::InvalidParameter)` listing the choices otherwise
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use block_system::core::parameter::{enum_param, ParameterValue};
+///
+/// let mut params = HashMap::new();
+/// params.insert("mode".to_string(), ParameterValue::from("lgo"));
+/// let err = enum_param(&params, "mode", &["log", "recover"]).unwrap_err();
+/// assert_eq!(err.to_string(), "Invalid parameter: mode must be 'log' or 'recover', got 'lgo'");
+/// ```
+pub fn enum_param<'a>(
+    params: &'a HashMap<String, ParameterValue>,
+    id: &str,
+    choices: &[&str],
+) -> Result<Option<&'a str>, BlockError> {
+    let Some(value) = params.get(id) else {
+        return Ok(None);
+    };
+    match value.as_enum(choices) {
+        Some(choice) => Ok(Some(choice)),
+        None => Err(BlockError::InvalidParameter(choices_message(id, choices, value))),
+    }
+}
+
+/// "`id` must be 'a', 'b' or 'c', got ..." for a value outside `choices`
+fn choices_message(id: &str, choices: &[&str], value: &ParameterValue) -> String {
+    let quoted: Vec<String> = choices.iter().map(|c| format!("'{}'", c)).collect();
+    let expected = match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => quoted.concat(),
+    };
+    let got = match value.as_string() {
+        Some(s) => format!("'{}'", s),
+        None => "a non-string value".to_string(),
+    };
+    format!("{} must be {}, got {}", id, expected, got)
+}
+
+/// Read a list parameter, checking it against `constraints`
+///
+/// The value is an array; for compatibility with text inputs a
+/// comma-separated string is also accepted and split into elements (parsed
+/// as numbers when `element_type` is [`ParameterType::Number`]). The list is
+/// then checked against `min_length`/`max_length` and `element_type`; without
+/// an `element_type` the elements must still all share one type.
+///
+/// # Returns
+/// * `Ok(None)` if the parameter is absent
+/// * `Ok(Some(items))` if it is a valid list
+/// * `Err(BlockError::InvalidParameter)` describing the first problem otherwise
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use block_system::core::parameter::{
+///     list_param, ParameterConstraints, ParameterType, ParameterValue,
+/// };
+///
+/// let numbers = ParameterConstraints::new().with_element_type(ParameterType::Number);
+/// let mut params = HashMap::new();
+/// params.insert("boundaries".to_string(), ParameterValue::from("10, 20"));
+/// let items = list_param(&params, "boundaries", &numbers).unwrap().unwrap();
+/// assert_eq!(items[1].as_number(), Some(20.0));
+///
+/// params.insert("boundaries".to_string(), ParameterValue::from("10, abc"));
+/// let err = list_param(&params, "boundaries", &numbers).unwrap_err();
+/// assert!(err.to_string().ends_with("boundaries must contain only numbers, got 'abc'"));
+/// ```
+pub fn list_param(
+    params: &HashMap<String, ParameterValue>,
+    id: &str,
+    constraints: &ParameterConstraints,
+) -> Result<Option<Vec<ParameterValue>>, BlockError> {
+    match params.get(id) {
+        Some(value) => list_items(id, value, constraints)
+            .map(Some)
+            .map_err(BlockError::InvalidParameter),
+        None => Ok(None),
+    }
+}
+
+/// The elements of a list parameter, or why it is not a valid list
+fn list_items(
+    id: &str,
+    value: &ParameterValue,
+    constraints: &ParameterConstraints,
+) -> Result<Vec<ParameterValue>, String> {
+    let element_type = constraints.element_type.as_ref();
+    let items = match value {
+        ParameterValue::Array(items) => items.clone(),
+        ParameterValue::String(s) => s
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| match (element_type, p.parse::<f64>()) {
+                (Some(ParameterType::Number), Ok(n)) => ParameterValue::Number(n),
+                _ => ParameterValue::from(p),
+            })
+            .collect(),
+        other => return Err(format!("{} must be a list, got {}", id, other.describe())),
+    };
+
+    let invalid = |msg: String| Err(format!("{} {}", id, msg));
+    if let Some(min) = constraints.min_length.filter(|&min| items.len() < min) {
+        return match items.len() {
+            0 => invalid("must not be empty".into()),
+            n => invalid(format!("must have at least {} items, got {}", min, n)),
+        };
+    }
+    if let Some(max) = constraints.max_length.filter(|&max| items.len() > max) {
+        return invalid(format!("must have at most {} items, got {}", max, items.len()));
+    }
+
+    let expected = element_type.cloned().or_else(|| items.first().and_then(|v| v.param_type()));
+    if let Some(bad) = items.iter().find(|v| v.param_type() != expected) {
+        return match element_type {
+            Some(t) => invalid(format!(
+                "must contain only {}s, got {}",
+                type_noun(Some(t)),
+                bad.describe()
+            )),
+            None => invalid(format!(
+                "must not mix {}s with {}",
+                type_noun(expected.as_ref()),
+                bad.describe()
+            )),
+        };
+    }
+    Ok(items)
+}
+
+/// Lower-case name of a parameter type for error messages
+fn type_noun(param_type: Option<&ParameterType>) -> &'static str {
+    match param_type {
+        Some(ParameterType::String) | Some(ParameterType::Enum) => "string",
+        Some(ParameterType::Number) => "number",
+        Some(ParameterType::Boolean) => "boolean",
+        Some(ParameterType::Object) => "object",
+        Some(ParameterType::Array) => "list",
+        None => "null",
+    }
+}
+
 /// UI hints for parameter rendering
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParameterUIHint {
@@ -30,6 +30,43 @@ pub struct Port {
     pub schema: Option<PortSchema>,
 }
 
+impl Port {
+    /// Declare the shape of the records this port carries
+    ///
+    /// Validation is opt-in: a port without a schema accepts any records.
+    pub fn with_schema(mut self, schema: PortSchema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+}
+
+impl PortValidator for Port {
+    /// Check every record in `value` against the port's schema, one error per
+    /// offending record. Always valid for a port without a schema.
+    fn validate(&self, value: &PortValue) -> ValidationResult {
+        let Some(schema) = &self.schema else {
+            return ValidationResult::ok();
+        };
+        let records: &[Record] = match value {
+            PortValue::Stream(records) | PortValue::Batch(records) => records,
+            PortValue::Single(record) => std::slice::from_ref(record),
+            PortValue::Signal(_) | PortValue::None => return ValidationResult::ok(),
+        };
+        let errors: Vec<String> = records
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| {
+                schema.validate_record(r).err().map(|e| format!("record {}: {}", i, e))
+            })
+            .collect();
+        if errors.is_empty() {
+            ValidationResult::ok()
+        } else {
+            ValidationResult::errors(errors)
+        }
+    }
+}
+
 /// Port direction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PortDirection {
@@ -90,6 +127,121 @@ pub enum PortSchema {
     },
 }
 
+impl PortSchema {
+    /// Schema for records with the given typed columns, all required
+    ///
+    /// # Examples
+    /// ```
+    /// use block_system::core::port::{PortSchema, PrimitiveType, Record};
+    ///
+    /// let schema = PortSchema::record(&[("id", PrimitiveType::Integer)]);
+    /// let mut record = Record::new();
+    /// record.insert("name".into(), "alice").unwrap();
+    /// assert_eq!(schema.validate_record(&record).unwrap_err(), "missing required column 'id'");
+    /// ```
+    pub fn record(columns: &[(&str, PrimitiveType)]) -> Self {
+        PortSchema::Object {
+            properties: columns
+                .iter()
+                .map(|&(name, prim_type)| {
+                    (name.to_string(), Box::new(PortSchema::Primitive { prim_type }))
+                })
+                .collect(),
+            required: columns.iter().map(|(name, _)| name.to_string()).collect(),
+        }
+    }
+
+    /// Schema for records that must carry `columns`, of any type
+    pub fn required_columns(columns: &[&str]) -> Self {
+        PortSchema::Object {
+            properties: HashMap::new(),
+            required: columns.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    /// Check a record against an `Object` schema
+    ///
+    /// Required columns must be present and not null; every column with a
+    /// declared property must match it (null is fine for optional ones).
+    /// Columns the schema doesn't mention are allowed.
+    pub fn validate_record(&self, record: &Record) -> Result<(), String> {
+        match self {
+            PortSchema::Object { properties, required } => {
+                check_columns(|c| record.data.get(c), properties, required, "")
+            }
+            _ => Err("record schemas must be objects".into()),
+        }
+    }
+
+    /// Check a single JSON value against the schema; `path` names it in errors
+    fn check(&self, value: &JsonValue, path: &str) -> Result<(), String> {
+        match (self, value) {
+            (PortSchema::Object { properties, required }, JsonValue::Object(map)) => {
+                check_columns(|c| map.get(c), properties, required, &format!("{}.", path))
+            }
+            (PortSchema::Array { items }, JsonValue::Array(values)) => values
+                .iter()
+                .enumerate()
+                .try_for_each(|(i, v)| items.check(v, &format!("{}[{}]", path, i))),
+            (PortSchema::Primitive { prim_type }, v) if prim_type.matches(v) => Ok(()),
+            _ => Err(format!(
+                "column '{}' should be {}, got {}",
+                path,
+                self.expected(),
+                json_kind(value)
+            )),
+        }
+    }
+
+    fn expected(&self) -> String {
+        match self {
+            PortSchema::Object { .. } => "an object".into(),
+            PortSchema::Array { .. } => "an array".into(),
+            PortSchema::Primitive { prim_type } => format!("{:?}", prim_type),
+        }
+    }
+}
+
+/// Check the columns of a record (or nested object) against its schema
+fn check_columns<'a>(
+    get: impl Fn(&str) -> Option<&'a JsonValue>,
+    properties: &HashMap<String, Box<PortSchema>>,
+    required: &[String],
+    prefix: &str,
+) -> Result<(), String> {
+    for column in required {
+        match get(column) {
+            None => return Err(format!("missing required column '{}{}'", prefix, column)),
+            Some(JsonValue::Null) => {
+                return Err(format!("required column '{}{}' is null", prefix, column))
+            }
+            Some(_) => {}
+        }
+    }
+    let mut columns: Vec<&String> = properties.keys().collect();
+    columns.sort();
+    for column in columns {
+        match get(column) {
+            None | Some(JsonValue::Null) => {}
+            Some(value) => properties[column].check(value, &format!("{}{}", prefix, column))?,
+        }
+    }
+    Ok(())
+}
+
+/// The JSON type of a value, for error messages
+fn json_kind(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "a boolean",
+        JsonValue::Number(n) if n.is_f64() => "a float",
+        JsonValue::Number(_) => "an integer",
+        JsonValue::String(_) => "a string",
+        JsonValue::Array(_) => "an array",
+        JsonValue::Object(_) => "an object",
+    }
+}
+
 /// Primitive types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PrimitiveType {
@@ -105,6 +257,26 @@ pub enum PrimitiveType {
     Bytes,
 }
 
+impl PrimitiveType {
+    /// Whether a JSON value is of this type. Integers are valid floats, and
+    /// bytes may be a string or an array of byte values.
+    pub fn matches(&self, value: &JsonValue) -> bool {
+        match self {
+            PrimitiveType::Integer => value.is_i64() || value.is_u64(),
+            PrimitiveType::Float => value.is_number(),
+            PrimitiveType::String => value.is_string(),
+            PrimitiveType::Boolean => value.is_boolean(),
+            PrimitiveType::Bytes => match value {
+                JsonValue::String(_) => true,
+                JsonValue::Array(bytes) => {
+                    bytes.iter().all(|b| b.as_u64().is_some_and(|b| b < 256))
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
 /// Port value - actual data flowing through ports
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PortValue {
@@ -144,6 +316,14 @@ pub fn is_empty(&self) -> bool {
 }
 
 /// A single record
+///
+/// A column can be *absent* (no key in `data`) or *present but null*
+/// (`JsonValue::Null`). Typed reads treat both as "no value": [`Record::get`]
+/// returns `Ok(None)` for either, and [`Record::get_or_null`] returns `Null`
+/// for either. Use [`Record::contains`] and [`Record::is_null`] where the
+/// difference matters — an index key column that is missing is a malformed
+/// record, while a null key is a legitimate value that sorts per
+/// [`NullOrder`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Record {
     /// Record data as key-value pairs
@@ -170,16 +350,37 @@ pub fn insert<T: Serialize>(&mut self, key: String, value: T) -> Result<(), serd
         Ok(())
     }
 
-    /// Get a field from the record
+    /// Get a field from the record, or `None` if it is absent or null
     pub fn get<T: for<'de> Deserialize<'de>>(&self, key: &str) -> Result<Option<T>, serde_json::Error> {
         match self.data.get(key) {
+            Some(JsonValue::Null) | None => Ok(None),
             Some(value) => {
                 let result = serde_json::from_value(value.clone())?;
                 Ok(Some(result))
             }
-            None => Ok(None),
         }
     }
+
+    /// Whether the record has a `key` column, null or not
+    pub fn contains(&self, key: &str) -> bool {
+        self.data.contains_key(key)
+    }
+
+    /// Whether the `key` column is present and null; `false` if it is absent
+    pub fn is_null(&self, key: &str) -> bool {
+        matches!(self.data.get(key), Some(JsonValue::Null))
+    }
+
+    /// The `key` column's value, with an absent column read as null
+    pub fn get_or_null(&self, key: &str) -> &JsonValue {
+        self.data.get(key).unwrap_or(&JsonValue::Null)
+    }
+
+    /// Size of the record serialized as JSON — the simulator's measure of
+    /// how many bytes a row occupies in memory or on disk.
+    pub fn json_size(&self) -> usize {
+        serde_json::to_string(&self.data).map(|s| s.len()).unwrap_or(0)
+    }
 }
 
 impl Default for Record {
@@ -188,6 +389,52 @@ fn default() -> Self {
     }
 }
 
+/// Where nulls sort relative to non-null values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NullOrder {
+    /// Nulls before every other value (SQL `NULLS FIRST`)
+    First,
+    /// Nulls after every other value (SQL `NULLS LAST`)
+    #[default]
+    Last,
+}
+
+/// Compare two column values, with nulls last.
+///
+/// Numbers are compared numerically, strings lexicographically, and arrays
+/// (composite keys) element by element, with a shorter prefix sorting first.
+/// Other mixed types fall back to their JSON text.
+pub fn cmp_json(a: &JsonValue, b: &JsonValue) -> std::cmp::Ordering {
+    cmp_json_nulls(a, b, NullOrder::Last)
+}
+
+/// [`cmp_json`] with nulls placed per `nulls`, including inside composite keys.
+pub fn cmp_json_nulls(a: &JsonValue, b: &JsonValue, nulls: NullOrder) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    let null_first = match nulls {
+        NullOrder::First => Ordering::Less,
+        NullOrder::Last => Ordering::Greater,
+    };
+    match (a, b) {
+        (JsonValue::Null, JsonValue::Null) => Ordering::Equal,
+        (JsonValue::Null, _) => null_first,
+        (_, JsonValue::Null) => null_first.reverse(),
+        (JsonValue::Array(va), JsonValue::Array(vb)) => va
+            .iter()
+            .zip(vb)
+            .map(|(x, y)| cmp_json_nulls(x, y, nulls))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or_else(|| va.len().cmp(&vb.len())),
+        (JsonValue::Number(na), JsonValue::Number(nb)) => {
+            let fa = na.as_f64().unwrap_or(0.0);
+            let fb = nb.as_f64().unwrap_or(0.0);
+            fa.partial_cmp(&fb).unwrap_or(Ordering::Equal)
+        }
+        (JsonValue::String(sa), JsonValue::String(sb)) => sa.cmp(sb),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
 /// Signal values for control flow
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SignalValue {
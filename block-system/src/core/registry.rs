@@ -7,13 +7,33 @@
 //! - Block validation
 //! - Dependency resolution
 //! - Compatibility checking
-
+//! - Instantiating executable blocks by their metadata id
+//!
+//! The factory table is separate from the registered `Block` instances: it
+//! maps an executable block's metadata id (`"lsm-tree-storage"`, `"filter"`,
+//! ...) to a constructor, so a saved graph of `{block_type, params}` can be
+//! turned back into live blocks. Each category module registers its own
+//! blocks; [`BlockRegistry::with_builtin_blocks`] registers all of them.
+//! Each factory's metadata is captured once at registration, so a block
+//! palette can list and search blocks without instantiating them.
+
+use crate::core::block;
 use crate::core::{Block, BlockId, BlockMetadata};
 use crate::categories::BlockCategory;
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Constructor for an executable block, in its default (uninitialized) state.
+pub type BlockFactory = fn() -> Box<dyn block::Block>;
+
+/// A registered constructor and the metadata of the block it builds.
+#[derive(Clone)]
+struct FactoryEntry {
+    metadata: block::BlockMetadata,
+    factory: BlockFactory,
+}
+
 /// Block registry for managing all available blocks
 ///
 /// The registry uses `Arc<RwLock<HashMap>>` for thread-safe access to blocks.
@@ -22,6 +42,8 @@
 #[derive(Clone)]
 pub struct BlockRegistry {
     blocks: Arc<RwLock<HashMap<String, Arc<dyn Block>>>>,
+    /// Executable block constructors, keyed by metadata id.
+    factories: Arc<RwLock<HashMap<String, FactoryEntry>>>,
 }
 
 impl BlockRegistry {
@@ -36,7 +58,103 @@ impl BlockRegistry {
     pub fn new() -> Self {
         Self {
             blocks: Arc::new(RwLock::new(HashMap::new())),
+            factories: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Create a registry with a factory for every built-in block
+    ///
+    /// # Example
+    /// ```
+    /// use block_system::core::registry::BlockRegistry;
+    ///
+    /// let registry = BlockRegistry::with_builtin_blocks();
+    /// let lsm = registry.create("lsm-tree-storage").unwrap();
+    /// assert_eq!(lsm.metadata().name, "LSM Tree");
+    /// ```
+    pub fn with_builtin_blocks() -> Self {
+        let registry = Self::new();
+        crate::categories::register_all(&registry)
+            .expect("built-in blocks have unique metadata ids");
+        registry
+    }
+
+    /// Register a constructor for an executable block
+    ///
+    /// The block is keyed by its metadata id. Its metadata is read from one
+    /// instance built at registration time and cached for listing.
+    ///
+    /// # Returns
+    /// * `Ok(())` if registration succeeds
+    /// * `Err(RegistryError)` if a factory for the same id already exists
+    pub fn register_factory(&self, factory: BlockFactory) -> Result<(), RegistryError> {
+        let metadata = factory().metadata().clone();
+        let mut factories = self.factories.write();
+
+        if factories.contains_key(&metadata.id) {
+            return Err(RegistryError::DuplicateBlock(metadata.id));
         }
+
+        factories.insert(metadata.id.clone(), FactoryEntry { metadata, factory });
+        Ok(())
+    }
+
+    /// Instantiate an executable block by its metadata id
+    ///
+    /// # Returns
+    /// A new, uninitialized block, or `None` if no factory is registered
+    /// for `id`
+    pub fn create(&self, id: &str) -> Option<Box<dyn block::Block>> {
+        let factories = self.factories.read();
+        factories.get(id).map(|entry| (entry.factory)())
+    }
+
+    /// Metadata of every registered factory, sorted by id
+    pub fn all_metadata(&self) -> Vec<block::BlockMetadata> {
+        self.filter_metadata(|_| true)
+    }
+
+    /// Metadata of every registered factory in `category`, sorted by id
+    pub fn list_by_category(&self, category: block::BlockCategory) -> Vec<block::BlockMetadata> {
+        self.filter_metadata(|meta| meta.category == category)
+    }
+
+    /// Search registered factories by query string
+    ///
+    /// Matches case-insensitively on id, name, description and category,
+    /// which stand in for tags on executable blocks.
+    ///
+    /// # Returns
+    /// Metadata of every matching block, sorted by id
+    pub fn search(&self, query: &str) -> Vec<block::BlockMetadata> {
+        let query = query.to_lowercase();
+
+        self.filter_metadata(|meta| {
+            meta.id.to_lowercase().contains(&query)
+                || meta.name.to_lowercase().contains(&query)
+                || meta.description.to_lowercase().contains(&query)
+                || format!("{:?}", meta.category).to_lowercase().contains(&query)
+        })
+    }
+
+    fn filter_metadata(
+        &self,
+        predicate: impl Fn(&block::BlockMetadata) -> bool,
+    ) -> Vec<block::BlockMetadata> {
+        let factories = self.factories.read();
+        let mut matches: Vec<block::BlockMetadata> = factories
+            .values()
+            .map(|entry| &entry.metadata)
+            .filter(|meta| predicate(meta))
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| a.id.cmp(&b.id));
+        matches
+    }
+
+    /// Metadata ids of every registered factory, sorted
+    pub fn factory_ids(&self) -> Vec<String> {
+        self.all_metadata().into_iter().map(|meta| meta.id).collect()
     }
 
     /// Register a new block in the registry
@@ -793,6 +911,71 @@ fn test_check_compatibility_with_conflicts() {
         assert_eq!(compat.conflicts[0].severity, ConflictSeverity::Warning);
     }
 
+    #[test]
+    fn test_create_builtin_blocks() {
+        let registry = BlockRegistry::with_builtin_blocks();
+        let ids = registry.factory_ids();
+        assert_eq!(ids.len(), 32);
+
+        // Every factory builds the block its id names.
+        for id in &ids {
+            let block = registry.create(id).unwrap();
+            assert_eq!(&block.metadata().id, id);
+        }
+        assert!(registry.create("no-such-block").is_none());
+    }
+
+    #[test]
+    fn test_downcast_created_block() {
+        use crate::categories::storage::{HeapFileBlock, LSMTreeBlock};
+
+        let registry = BlockRegistry::with_builtin_blocks();
+        let mut block = registry.create("lsm-tree-storage").unwrap();
+        assert!(block.as_ref().as_any().downcast_ref::<HeapFileBlock>().is_none());
+
+        let lsm = block.as_mut().as_any_mut().downcast_mut::<LSMTreeBlock>().unwrap();
+        lsm.put("k".into(), serde_json::json!(1));
+        assert_eq!(lsm.get("k"), Some(serde_json::json!(1)));
+    }
+
+    #[test]
+    fn test_list_and_search_metadata() {
+        let registry = BlockRegistry::with_builtin_blocks();
+        assert_eq!(registry.all_metadata().len(), 32);
+
+        let buffers: Vec<String> = registry
+            .list_by_category(block::BlockCategory::Buffer)
+            .into_iter()
+            .map(|meta| meta.id)
+            .collect();
+        assert_eq!(
+            buffers,
+            ["2q-buffer-pool", "clock-buffer-pool", "lru-buffer-pool", "lru-k-buffer-pool"]
+        );
+        assert_eq!(registry.list_by_category(block::BlockCategory::Transaction).len(), 1);
+
+        // Name, id and category all match, case-insensitively.
+        let lsm = registry.search("LSM");
+        assert_eq!(lsm.len(), 1);
+        assert_eq!(lsm[0].id, "lsm-tree-storage");
+        assert!(registry.search("hash-join").iter().any(|meta| meta.id == "hash-join"));
+        let index_hits = registry.search("index");
+        for meta in registry.list_by_category(block::BlockCategory::Index) {
+            assert!(index_hits.iter().any(|hit| hit.id == meta.id));
+        }
+        assert!(registry.search("no such block").is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_factory() {
+        use crate::categories::execution::FilterBlock;
+
+        let registry = BlockRegistry::new();
+        registry.register_factory(|| Box::new(FilterBlock::new())).unwrap();
+        let result = registry.register_factory(|| Box::new(FilterBlock::new()));
+        assert!(matches!(result, Err(RegistryError::DuplicateBlock(id)) if id == "filter"));
+    }
+
     #[test]
     fn test_thread_safety() {
         use std::thread;
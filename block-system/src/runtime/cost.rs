@@ -0,0 +1,150 @@
+//! Simulated cost model
+//!
+//! Wall-clock time says how fast the simulator ran, not how fast the modeled
+//! database would. A `CostModel` charges a latency per unit of the I/O
+//! counters blocks report (`pages_read`, `cache_misses`, ...), so two designs
+//! can be compared on the time their I/O would take on real hardware.
+//!
+//! Given request arrival times, [`CostModel::queueing_delay_ms`] adds the
+//! time requests spend waiting for a busy server: at a low arrival rate
+//! requests rarely wait, and as the rate approaches the service rate the
+//! wait grows without bound.
+
+use std::collections::HashMap;
+
+/// Per-unit latencies, in milliseconds, keyed by block metric id.
+///
+/// The defaults approximate an SSD behind a buffer pool: a page access costs
+/// `page_read_latency` / `page_write_latency`, a buffer miss goes to disk and
+/// costs `disk_latency`, and an fsync costs `fsync_latency`. Any other metric
+/// can be priced with [`CostModel::with_cost`].
+#[derive(Debug, Clone)]
+pub struct CostModel {
+    unit_costs: HashMap<String, f64>,
+}
+
+impl CostModel {
+    pub const DEFAULT_PAGE_READ_LATENCY_MS: f64 = 0.1;
+    pub const DEFAULT_PAGE_WRITE_LATENCY_MS: f64 = 0.2;
+    pub const DEFAULT_DISK_LATENCY_MS: f64 = 10.0;
+    pub const DEFAULT_FSYNC_LATENCY_MS: f64 = 2.0;
+
+    /// A model that charges nothing.
+    pub fn free() -> Self {
+        Self { unit_costs: HashMap::new() }
+    }
+
+    /// Charge `latency_ms` per unit of `metric_id`.
+    pub fn with_cost(mut self, metric_id: impl Into<String>, latency_ms: f64) -> Self {
+        self.unit_costs.insert(metric_id.into(), latency_ms);
+        self
+    }
+
+    /// Cost of each `pages_read`.
+    pub fn with_page_read_latency(self, latency_ms: f64) -> Self {
+        self.with_cost("pages_read", latency_ms)
+    }
+
+    /// Cost of each `pages_written` / `pages_flushed`.
+    pub fn with_page_write_latency(self, latency_ms: f64) -> Self {
+        self.with_cost("pages_written", latency_ms)
+            .with_cost("pages_flushed", latency_ms)
+    }
+
+    /// Cost of each `cache_misses` (a read that has to go to disk).
+    pub fn with_disk_latency(self, latency_ms: f64) -> Self {
+        self.with_cost("cache_misses", latency_ms)
+    }
+
+    /// Cost of each `fsyncs`.
+    pub fn with_fsync_latency(self, latency_ms: f64) -> Self {
+        self.with_cost("fsyncs", latency_ms)
+    }
+
+    /// Per-unit cost of `metric_id`, if it is priced.
+    pub fn unit_cost(&self, metric_id: &str) -> Option<f64> {
+        self.unit_costs.get(metric_id).copied()
+    }
+
+    /// Mean time requests wait before service at a single FIFO server that
+    /// takes `service_ms` per request, given their arrival times
+    /// (Lindley's recursion). Arrivals are served in time order.
+    pub fn queueing_delay_ms(arrivals_ms: &[f64], service_ms: f64) -> f64 {
+        if arrivals_ms.is_empty() {
+            return 0.0;
+        }
+        let mut arrivals = arrivals_ms.to_vec();
+        arrivals.sort_by(|a, b| a.total_cmp(b));
+        let mut free_at = f64::NEG_INFINITY;
+        let mut total_wait = 0.0;
+        for arrival in arrivals {
+            let start = free_at.max(arrival);
+            total_wait += start - arrival;
+            free_at = start + service_ms;
+        }
+        total_wait / arrivals_ms.len() as f64
+    }
+
+    /// Simulated latency of one block's work, from its reported counters.
+    pub fn latency_ms(&self, counters: &HashMap<String, f64>) -> f64 {
+        counters
+            .iter()
+            .filter_map(|(id, count)| self.unit_cost(id).map(|cost| cost * count))
+            .sum()
+    }
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self::free()
+            .with_page_read_latency(Self::DEFAULT_PAGE_READ_LATENCY_MS)
+            .with_page_write_latency(Self::DEFAULT_PAGE_WRITE_LATENCY_MS)
+            .with_disk_latency(Self::DEFAULT_DISK_LATENCY_MS)
+            .with_fsync_latency(Self::DEFAULT_FSYNC_LATENCY_MS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_costs() {
+        let model = CostModel::default();
+        let counters = HashMap::from([
+            ("pages_read".to_string(), 100.0),
+            ("cache_misses".to_string(), 3.0),
+            ("records_inserted".to_string(), 1000.0),
+        ]);
+        assert!((model.latency_ms(&counters) - (100.0 * 0.1 + 3.0 * 10.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_custom_costs() {
+        let model = CostModel::free()
+            .with_disk_latency(5.0)
+            .with_cost("compactions", 50.0);
+        let counters = HashMap::from([
+            ("pages_read".to_string(), 100.0),
+            ("cache_misses".to_string(), 2.0),
+            ("compactions".to_string(), 1.0),
+        ]);
+        assert_eq!(model.latency_ms(&counters), 60.0);
+        assert_eq!(model.unit_cost("pages_read"), None);
+    }
+
+    #[test]
+    fn test_queueing_delay() {
+        // Arrivals every 10 ms: a 5 ms server never makes anyone wait.
+        let spaced: Vec<f64> = (0..10).map(|i| i as f64 * 10.0).collect();
+        assert_eq!(CostModel::queueing_delay_ms(&spaced, 5.0), 0.0);
+
+        // A 15 ms server falls 5 ms further behind with each request:
+        // waits are 0, 5, ..., 45, averaging 22.5.
+        assert_eq!(CostModel::queueing_delay_ms(&spaced, 15.0), 22.5);
+
+        // A burst queues up behind itself, in time order.
+        assert_eq!(CostModel::queueing_delay_ms(&[2.0, 0.0, 1.0], 4.0), 3.0);
+        assert_eq!(CostModel::queueing_delay_ms(&[], 4.0), 0.0);
+    }
+}
@@ -3,16 +3,129 @@
 //! Manages a graph of blocks connected via ports. Validates the graph, executes
 //! blocks in topological order, routes data between connected ports, collects
 //! per-block timing and metrics, and supports cancellation.
-
-use std::collections::HashMap;
+//!
+//! Every block executes against the same [`MetricsCollector`], so metrics
+//! recorded under the same id by different blocks aggregate across the graph.
+//! [`ExecutionEngine::run`] is the one-call entry point: it takes a set of
+//! (already initialized) blocks and their connections and returns each
+//! block's [`ExecutionResult`].
+//!
+//! ## Data routing
+//!
+//! An output port feeding several targets (fan-out) hands each target its own
+//! clone of the value. An input port fed by several connections (fan-in, only
+//! allowed on `multiple: true` ports) receives the concatenation of every
+//! upstream value: external input first, then connections in the order they
+//! were added. `Stream`, `Batch` and `Single` all carry records and merge
+//! freely, matching the Stream/Batch compatibility the validator allows; the
+//! merged value is a `Batch` only if every part was, otherwise a `Stream`.
+//! `None` contributes nothing, and signals do not merge — the last one wins.
+//! The block learns how many non-empty values went into each port from the
+//! reserved [`FAN_IN_PARAMETER`] entry of its execution parameters.
+//!
+//! ## Batched execution
+//!
+//! With a batch size set, every record stream in the external input is cut
+//! into `Batch`es of at most that many records and the graph runs once per
+//! batch: each round pushes one batch through the whole pipeline before the
+//! next is read, so no block ever sees more than a batch of new input at a
+//! time. Blocks keep their own state between calls, so stateful blocks (LSM
+//! trees, B-trees, buffer pools) behave as if fed the whole input.
+//!
+//! Every block executes in the first round; later rounds skip blocks with no
+//! input. Per-block results are folded across rounds: a block that reports
+//! running totals (see [`Block::reports_running_totals`]) keeps its latest
+//! metrics; otherwise `Counter` metrics are summed and the rest keep their
+//! latest value. Errors accumulate.
+//! Outputs accumulate only for sink blocks (no outgoing connections) — the
+//! pipeline's result; intermediate blocks keep just their last batch. A block
+//! that [restates its output](Block::restates_output) each call, and every
+//! block downstream of it, keeps just its latest output even as a sink: that
+//! output already covers every batch so far.
+//!
+//! A block that reports itself [exhausted](Block::is_exhausted) — a LIMIT
+//! with all its rows — is not run again, and neither is any block whose
+//! consumers are all finished, so a scan under a satisfied limit stops
+//! reading. The run's `short_circuited` flag records whether this skipped
+//! any input.
+//!
+//! Each block records into a metrics handle stamped with the round's index,
+//! so with history enabled on [`ExecutionEngine::metrics`] every batch adds a
+//! point to each metric's time series — the trajectory of, say, a buffer
+//! pool's `hit_rate_pct` as it warms up.
+//!
+//! [`ExecutionEngine::execute_observed`] hands a [`BatchProgress`] to a
+//! callback after every round, with each block's results folded so far —
+//! enough to chart a long run as it goes. Pipelined execution (below) has no
+//! rounds and never calls it.
+//!
+//! ## Port schemas
+//!
+//! Before a block executes, the records arriving on each of its input ports
+//! that declares a [`PortSchema`](crate::core::port::PortSchema) are checked
+//! against it. A record missing a required column, or holding a mistyped
+//! one, fails the block with a [`BlockError::ValidationError`] instead of
+//! executing it, the same as any fatal error. Ports without a schema are not
+//! checked, so untyped pipelines run as before.
+//!
+//! The block's own [`Block::validate`] then sees the same inputs — the
+//! actual data, which the static [`GraphValidator`] never does. Its errors
+//! (say, a signal arriving where records were expected) fail the block the
+//! same way; its warnings are collected into the run's `warnings`, once each
+//! however many batches raise them.
+//!
+//! ## Parallel execution
+//!
+//! Blocks are executed level by level, where a block's level is one more
+//! than the deepest block feeding it. Blocks in the same level are
+//! independent, so with [`ExecutionEngine::set_parallel`] each level's
+//! blocks run concurrently as tokio tasks, and the engine joins them before
+//! starting the next level. Results are still handled in topological order,
+//! so a run's outputs don't depend on which task finished first. The shared
+//! [`MetricsCollector`] is behind a mutex and safe to record into from
+//! concurrent blocks. Per-block `percentage` is of wall-clock time, so in a
+//! parallel run the percentages can add up to more than 100.
+//!
+//! ## Backpressure
+//!
+//! [`ExecutionEngine::set_max_in_flight_batches`] bounds every connection to
+//! that many batches in flight. Instead of lockstep rounds, each block then
+//! runs as its own tokio task, pulling batches from its incoming connections
+//! and pushing its outputs downstream as soon as they are ready, so a fast
+//! producer can run ahead of a slow consumer — until the queue between them
+//! is full. The producer then waits for the consumer to catch up, and each
+//! such wait counts as a backpressure stall against the producer. The block
+//! with stalled producers upstream of it is the pipeline's bottleneck.
+//! Routing, result folding and early termination are as in batched
+//! execution. Like parallel
+//! execution, this needs a tokio runtime and is ignored on `wasm32`.
+//!
+//! ## Simulated cost
+//!
+//! Alongside wall-clock time, each block is charged a simulated latency by
+//! the engine's [`CostModel`] from the I/O counters it reports, and the run
+//! reports their sum as `total_simulated_latency_ms`. This is the number to
+//! compare designs on; wall-clock time only measures the simulator.
+//!
+//! If the external input carries arrival times (`_arrival_ms`, see
+//! [`crate::runtime::workload::ArrivalModel`]), the run also reports
+//! queueing delay: each request costs a block its simulated latency divided
+//! by the number of requests, and the block is modeled as a FIFO server
+//! those requests queue for. The run-level figure treats the whole pipeline
+//! as one server. A block whose queueing delay climbs with the arrival rate
+//! is the one that can't keep up.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use crate::core::block::{Block, BlockError, ExecutionContext};
-use crate::core::metrics::{Logger, MetricsCollector, StorageContext};
+use crate::core::block::{Block, BlockError, BlockState, ExecutionContext, ExecutionResult};
+use crate::core::metrics::{Logger, MetricType, MetricsCollector, StorageContext};
 use crate::core::parameter::ParameterValue;
-use crate::core::port::{Connection, PortValue};
+use crate::core::port::{Connection, Port, PortValidator, PortValue, Record};
 
+use super::cost::CostModel;
 use super::timer::Timer;
 use super::validation::{GraphValidationResult, GraphValidator};
 
@@ -30,6 +143,14 @@ pub struct BlockMetrics {
     pub percentage: f64,
     /// Block-specific counters (from the block's ExecutionResult.metrics).
     pub counters: HashMap<String, f64>,
+    /// Latency the block's I/O would take, per the engine's cost model.
+    pub simulated_latency_ms: f64,
+    /// Times the block waited on a full downstream queue (see
+    /// "Backpressure" in the module docs).
+    pub backpressure_stalls: usize,
+    /// Mean time a request waits for this block (see "Simulated cost" in
+    /// the module docs); 0 for untimed input.
+    pub queueing_delay_ms: f64,
 }
 
 /// Latency percentile metrics.
@@ -50,6 +171,17 @@ pub struct ExecutionMetrics {
     pub total_operations: usize,
     pub successful_operations: usize,
     pub failed_operations: usize,
+    /// Rounds the input was split into (1 unless a batch size is set).
+    pub batches: usize,
+    /// Sum of every block's simulated latency.
+    pub total_simulated_latency_ms: f64,
+    /// Sum of every block's backpressure stalls.
+    pub backpressure_stalls: usize,
+    /// Mean time a request waits for the pipeline; 0 for untimed input.
+    pub queueing_delay_ms: f64,
+    /// Whether exhausted blocks let the engine skip input (see "Batched
+    /// execution" in the module docs).
+    pub short_circuited: bool,
 }
 
 /// Final result of an engine execution run.
@@ -61,8 +193,48 @@ pub struct EngineExecutionResult {
     pub metrics: ExecutionMetrics,
     pub block_metrics: Vec<BlockMetrics>,
     pub errors: Vec<String>,
+    /// Warnings from the blocks' own input validation (see "Port schemas" in
+    /// the module docs), each prefixed with its block id.
+    pub warnings: Vec<String>,
+}
+
+/// Outcome of [`ExecutionEngine::run`] / [`ExecutionEngine::execute_detailed`].
+pub struct GraphRun {
+    /// Each block's `ExecutionResult`, keyed by block id. Blocks that failed
+    /// fatally have no entry; their error is in `summary.errors`.
+    pub results: HashMap<String, ExecutionResult>,
+    /// The metrics collector shared by every block in the graph.
+    pub metrics: MetricsCollector,
+    pub summary: EngineExecutionResult,
 }
 
+/// Progress after one round of a batched run, passed to the observer of
+/// [`ExecutionEngine::execute_observed`].
+pub struct BatchProgress<'a> {
+    /// Rounds completed so far, counting from 1.
+    pub batch: usize,
+    /// Rounds the input was split into.
+    pub batches: usize,
+    /// Each block's `ExecutionResult` folded across the rounds so far.
+    pub results: &'a HashMap<String, ExecutionResult>,
+}
+
+/// Every block's `BlockState`, keyed by block id. For blocks whose state
+/// [round-trips](Block::state_round_trips) this is a checkpoint
+/// [`ExecutionEngine::restore`] can resume from; for the rest it is a
+/// summary for inspection only.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphSnapshot {
+    pub blocks: HashMap<String, BlockState>,
+}
+
+/// A block ready to run: its id, the block itself and its context.
+type LevelJob = (String, Box<dyn Block>, ExecutionContext);
+
+/// A block after running: the block (unless its task panicked), its result
+/// and the elapsed milliseconds.
+type LevelOutcome = (String, Option<Box<dyn Block>>, Checked, f64);
+
 // ── Engine ──────────────────────────────────────────────────────────────────
 
 /// The execution engine wires blocks together and runs them.
@@ -73,6 +245,15 @@ pub struct ExecutionEngine {
     entry_points: Vec<String>,
     /// Cancellation flag.
     cancelled: Arc<AtomicBool>,
+    /// Shared by every block's `ExecutionContext`.
+    metrics: MetricsCollector,
+    /// Records per batch; `None` runs the whole input in one pass.
+    batch_size: Option<usize>,
+    /// Run the blocks of each topological level concurrently.
+    parallel: bool,
+    /// Queue bound per connection; `Some` switches to pipelined execution.
+    max_in_flight_batches: Option<usize>,
+    cost_model: CostModel,
 }
 
 impl ExecutionEngine {
@@ -82,7 +263,49 @@ pub fn new() -> Self {
             connections: Vec::new(),
             entry_points: Vec::new(),
             cancelled: Arc::new(AtomicBool::new(false)),
+            metrics: MetricsCollector::new(),
+            batch_size: None,
+            parallel: false,
+            max_in_flight_batches: None,
+            cost_model: CostModel::default(),
+        }
+    }
+
+    /// Run a graph end to end.
+    ///
+    /// Blocks must already be initialized. Every block addressed by
+    /// `entry_inputs` (keyed by (block_id, port_id)) is an entry point; if
+    /// there are none, blocks without incoming connections are used.
+    pub async fn run(
+        blocks: HashMap<String, Box<dyn Block>>,
+        connections: Vec<Connection>,
+        entry_inputs: HashMap<(String, String), PortValue>,
+    ) -> GraphRun {
+        Self::run_batched(blocks, connections, entry_inputs, 0, |_| {}).await
+    }
+
+    /// Like [`ExecutionEngine::run`], but `batch_size` records at a time (0
+    /// for a single pass), calling `on_batch` after every round.
+    pub async fn run_batched<F: FnMut(&BatchProgress)>(
+        blocks: HashMap<String, Box<dyn Block>>,
+        connections: Vec<Connection>,
+        entry_inputs: HashMap<(String, String), PortValue>,
+        batch_size: usize,
+        on_batch: F,
+    ) -> GraphRun {
+        let mut engine = Self::new();
+        engine.set_batch_size(batch_size);
+        engine.blocks = blocks;
+        engine.connections = connections;
+        for (block_id, _) in entry_inputs.keys() {
+            if !engine.entry_points.contains(block_id) {
+                engine.entry_points.push(block_id.clone());
+            }
+        }
+        if engine.entry_points.is_empty() {
+            engine.auto_detect_entry_points();
         }
+        engine.execute_observed(entry_inputs, on_batch).await
     }
 
     /// Add a block to the engine.
@@ -107,7 +330,6 @@ pub fn clear_entry_points(&mut self) {
 
     /// Auto-detect entry points: blocks that have no incoming connections.
     pub fn auto_detect_entry_points(&mut self) {
-        use std::collections::HashSet;
         let targets: HashSet<&str> = self
             .connections
             .iter()
@@ -155,6 +377,83 @@ pub fn is_cancelled(&self) -> bool {
         self.cancelled.load(Ordering::SeqCst)
     }
 
+    /// The metrics collector shared by all blocks. It accumulates across
+    /// executions.
+    pub fn metrics(&self) -> &MetricsCollector {
+        &self.metrics
+    }
+
+    /// Stream input through the pipeline `batch_size` records at a time; 0
+    /// turns batching off.
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = (batch_size > 0).then_some(batch_size);
+    }
+
+    /// Run independent blocks (same topological level) concurrently on tokio
+    /// tasks. Requires a tokio runtime; ignored on `wasm32`, which has no
+    /// threads to run them on.
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    /// Let at most `max` batches wait on any connection, stalling producers
+    /// that get ahead (see "Backpressure" in the module docs); 0 removes the
+    /// bound. Requires a tokio runtime; ignored on `wasm32`.
+    pub fn set_max_in_flight_batches(&mut self, max: usize) {
+        self.max_in_flight_batches = (max > 0).then_some(max);
+    }
+
+    /// Capture every block's state. Only blocks whose state
+    /// [round-trips](Block::state_round_trips) capture their data; the rest
+    /// report a summary.
+    pub fn snapshot(&self) -> GraphSnapshot {
+        GraphSnapshot {
+            blocks: self
+                .blocks
+                .iter()
+                .map(|(id, block)| (id.clone(), block.get_state()))
+                .collect(),
+        }
+    }
+
+    /// Restore block states from a snapshot. Blocks the snapshot doesn't
+    /// mention are left as they are. A snapshot naming a block this engine
+    /// doesn't have, or one whose state doesn't
+    /// [round-trip](Block::state_round_trips), is rejected before any block
+    /// is touched.
+    pub fn restore(&mut self, snapshot: GraphSnapshot) -> Result<(), BlockError> {
+        for id in snapshot.blocks.keys() {
+            match self.blocks.get(id) {
+                None => {
+                    return Err(BlockError::StateError(format!(
+                        "snapshot has state for unknown block '{}'",
+                        id
+                    )))
+                }
+                Some(block) if !block.state_round_trips() => {
+                    return Err(BlockError::StateError(format!(
+                        "block '{}' ({}) cannot be restored: its state holds only a summary",
+                        id,
+                        block.metadata().id
+                    )))
+                }
+                Some(_) => {}
+            }
+        }
+        for (id, state) in snapshot.blocks {
+            let block = self.blocks.get_mut(&id).unwrap();
+            block
+                .set_state(state)
+                .map_err(|e| BlockError::StateError(format!("restoring '{}': {}", id, e)))?;
+        }
+        Ok(())
+    }
+
+    /// Replace the cost model used for simulated latency.
+    pub fn set_cost_model(&mut self, cost_model: CostModel) {
+        self.cost_model = cost_model;
+    }
+
     /// Number of registered blocks.
     pub fn block_count(&self) -> usize {
         self.blocks.len()
@@ -170,7 +469,8 @@ pub fn validate(&self) -> GraphValidationResult {
     ///
     /// 1. Validate the graph.
     /// 2. Compute topological order.
-    /// 3. Execute each block in order, routing outputs to connected inputs.
+    /// 3. Execute each block in order, routing outputs to connected inputs —
+    ///    once per batch if a batch size is set.
     /// 4. Collect per-block metrics and timing.
     ///
     /// `input_data` provides the initial data for entry-point blocks,
@@ -179,9 +479,29 @@ pub async fn execute(
         &mut self,
         input_data: HashMap<(String, String), PortValue>,
     ) -> EngineExecutionResult {
+        self.execute_detailed(input_data).await.summary
+    }
+
+    /// Like [`ExecutionEngine::execute`], but also returns every block's
+    /// `ExecutionResult` and the shared metrics collector.
+    pub async fn execute_detailed(
+        &mut self,
+        input_data: HashMap<(String, String), PortValue>,
+    ) -> GraphRun {
+        self.execute_observed(input_data, |_| {}).await
+    }
+
+    /// Like [`ExecutionEngine::execute_detailed`], calling `on_batch` after
+    /// every round that runs to completion (see "Batched execution" in the
+    /// module docs).
+    pub async fn execute_observed<F: FnMut(&BatchProgress)>(
+        &mut self,
+        input_data: HashMap<(String, String), PortValue>,
+        mut on_batch: F,
+    ) -> GraphRun {
         let pipeline_start = Timer::now();
         let mut errors = Vec::new();
-        let mut block_metrics = Vec::new();
+        let mut warnings: Vec<String> = Vec::new();
 
         // Reset cancellation.
         self.cancelled.store(false, Ordering::SeqCst);
@@ -190,13 +510,15 @@ pub async fn execute(
         let validation = self.validate();
         if !validation.valid {
             let err_msgs: Vec<String> = validation.errors.iter().map(|e| e.message.clone()).collect();
-            return EngineExecutionResult {
-                success: false,
-                duration_ms: pipeline_start.elapsed_ms(),
-                metrics: ExecutionMetrics::default(),
-                block_metrics: Vec::new(),
-                errors: err_msgs,
-            };
+            return self.failed_run(pipeline_start.elapsed_ms(), err_msgs);
+        }
+
+        // Type the shared metrics for export.
+        for block in self.blocks.values() {
+            self.metrics.register_definitions(block.metrics());
+            if block.reports_running_totals() {
+                self.metrics.register_running_totals(block.metrics());
+            }
         }
 
         // Step 2: Topological sort.
@@ -204,123 +526,239 @@ pub async fn execute(
         let order = match GraphValidator::topological_sort(&block_ids, &self.connections) {
             Some(o) => o,
             None => {
-                return EngineExecutionResult {
-                    success: false,
-                    duration_ms: pipeline_start.elapsed_ms(),
-                    metrics: ExecutionMetrics::default(),
-                    block_metrics: Vec::new(),
-                    errors: vec!["Graph contains a cycle".into()],
-                };
+                return self.failed_run(
+                    pipeline_start.elapsed_ms(),
+                    vec!["Graph contains a cycle".into()],
+                );
             }
         };
 
-        // Step 3: Execute blocks in order.
-        // Data bus: stores output port values from completed blocks.
-        let mut data_bus: HashMap<(String, String), PortValue> = HashMap::new();
-
-        // Seed the data bus with external input data.
-        for ((block_id, port_id), value) in input_data {
-            data_bus.insert((block_id, port_id), value);
-        }
+        // Step 3: Execute blocks in order, once per round. Without a batch
+        // size there is a single round carrying all of `input_data`.
+        let arrivals = arrival_times(&input_data);
+        let rounds = self.split_into_rounds(input_data);
+        let batches = rounds.len();
 
         let mut total_ops: usize = 0;
         let mut successful_ops: usize = 0;
         let mut failed_ops: usize = 0;
-        let mut block_times: Vec<f64> = Vec::new();
-
-        for block_id in &order {
-            // Check cancellation.
-            if self.cancelled.load(Ordering::SeqCst) {
-                errors.push("Execution cancelled".into());
-                break;
-            }
-
-            // Build input map for this block by collecting data from the bus.
-            let mut inputs: HashMap<String, PortValue> = HashMap::new();
-
-            // First, check for external data directly addressed to this block.
-            for ((bid, pid), value) in &data_bus {
-                if bid == block_id {
-                    inputs.insert(pid.clone(), value.clone());
+        let mut block_times: HashMap<String, f64> = HashMap::new();
+        let mut results: HashMap<String, ExecutionResult> = HashMap::new();
+        let mut failed_blocks: Vec<String> = Vec::new();
+        let restated = restated_blocks(&order, &self.connections, &self.blocks);
+        let sinks: Vec<&String> = order
+            .iter()
+            .filter(|id| !self.connections.iter().any(|c| &c.source_block_id == *id))
+            .filter(|id| !restated.contains(*id))
+            .collect();
+        let mut stalls: HashMap<String, usize> = HashMap::new();
+        let mut short_circuited = false;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let pipelined = self.max_in_flight_batches.is_some();
+        #[cfg(target_arch = "wasm32")]
+        let pipelined = false;
+
+        if pipelined {
+            #[cfg(not(target_arch = "wasm32"))]
+            for (block_id, stage) in self.run_pipelined(&order, rounds, &sinks).await {
+                if stage.ran {
+                    block_times.insert(block_id.clone(), stage.elapsed_ms);
                 }
-            }
-
-            // Then, collect data from connections (source → this block).
-            for conn in &self.connections {
-                if &conn.target_block_id == block_id {
-                    let key = (conn.source_block_id.clone(), conn.source_port_id.clone());
-                    if let Some(value) = data_bus.get(&key) {
-                        inputs.insert(conn.target_port_id.clone(), value.clone());
-                    }
+                total_ops += stage.operations;
+                successful_ops += stage.operations;
+                stalls.insert(block_id.clone(), stage.backpressure_stalls);
+                short_circuited |= stage.short_circuited;
+                for warning in stage.warnings {
+                    push_warning(&mut warnings, &block_id, &warning);
                 }
-            }
-
-            // Build execution context.
-            let ctx = ExecutionContext {
-                inputs,
-                parameters: HashMap::new(),
-                metrics: MetricsCollector::new(),
-                logger: Logger::new(),
-                storage: StorageContext::new(),
-            };
-
-            // Execute the block.
-            let block_start = Timer::now();
-            let block = self.blocks.get_mut(block_id.as_str()).unwrap();
-            let block_name = block.metadata().name.clone();
-            let block_type = format!("{:?}", block.metadata().category);
-
-            let result = block.execute(ctx).await;
-            let block_elapsed_ms = block_start.elapsed_ms();
-            block_times.push(block_elapsed_ms);
-
-            match result {
-                Ok(exec_result) => {
-                    // Count operations from the output.
-                    let op_count: usize = exec_result
-                        .outputs
-                        .values()
-                        .map(|v| v.len())
-                        .sum();
-                    total_ops += op_count;
-                    successful_ops += op_count;
-
-                    // Store outputs in the data bus.
-                    for (port_id, value) in &exec_result.outputs {
-                        data_bus.insert((block_id.clone(), port_id.clone()), value.clone());
-                    }
-
-                    // Collect non-fatal errors.
-                    for err in &exec_result.errors {
+                if let Some(result) = stage.result {
+                    for err in &result.errors {
                         failed_ops += 1;
                         errors.push(format!("[{}] {}", block_id, err));
                     }
-
-                    block_metrics.push(BlockMetrics {
-                        block_id: block_id.clone(),
-                        block_type: block_type.clone(),
-                        block_name,
-                        execution_time_ms: block_elapsed_ms,
-                        percentage: 0.0, // computed below
-                        counters: exec_result.metrics,
-                    });
+                    results.insert(block_id.clone(), result);
                 }
-                Err(e) => {
+                if let Some(e) = stage.fatal {
                     failed_ops += 1;
                     errors.push(format!("[{}] Fatal: {}", block_id, e));
-                    block_metrics.push(BlockMetrics {
-                        block_id: block_id.clone(),
-                        block_type,
-                        block_name,
-                        execution_time_ms: block_elapsed_ms,
-                        percentage: 0.0,
-                        counters: HashMap::new(),
-                    });
-                    // Continue executing remaining blocks (best-effort).
                 }
+                if let Some(block) = stage.block {
+                    self.blocks.insert(block_id, block);
+                }
+            }
+            if self.cancelled.load(Ordering::SeqCst) {
+                errors.push("Execution cancelled".into());
+            }
+        } else {
+            let levels = Self::topological_levels(&order, &self.connections);
+            // Blocks that are exhausted or only feed finished blocks.
+            let mut finished: HashSet<String> = HashSet::new();
+
+            'rounds: for (round, round_inputs) in rounds.into_iter().enumerate() {
+                // Data bus: external input for this round, then output port values
+                // from blocks completed in this round.
+                let mut data_bus: HashMap<(String, String), PortValue> = round_inputs;
+
+                for level in &levels {
+                    // Check cancellation.
+                    if self.cancelled.load(Ordering::SeqCst) {
+                        errors.push("Execution cancelled".into());
+                        break 'rounds;
+                    }
+
+                    let mut jobs = Vec::new();
+                    for block_id in level {
+                        // A block that failed fatally sits out the remaining batches.
+                        if failed_blocks.contains(block_id) {
+                            continue;
+                        }
+
+                        // Build input map for this block by collecting data from the bus.
+                        let mut inputs: HashMap<String, PortValue> = HashMap::new();
+
+                        let mut fan_in: HashMap<String, i64> = HashMap::new();
+
+                        // First, check for external data directly addressed to this block.
+                        for ((bid, pid), value) in &data_bus {
+                            if bid == block_id {
+                                count_fan_in(&mut fan_in, pid, value);
+                                merge_input(&mut inputs, pid, value.clone());
+                            }
+                        }
+
+                        // Then, collect data from connections (source → this block), in
+                        // declaration order so fan-in concatenation is deterministic.
+                        for conn in &self.connections {
+                            if &conn.target_block_id == block_id {
+                                let key = (conn.source_block_id.clone(), conn.source_port_id.clone());
+                                if let Some(value) = data_bus.get(&key) {
+                                    count_fan_in(&mut fan_in, &conn.target_port_id, value);
+                                    merge_input(&mut inputs, &conn.target_port_id, value.clone());
+                                }
+                            }
+                        }
+
+                        // Every block runs in the first round; after that, only blocks
+                        // with data to process.
+                        if round > 0 && inputs.values().all(|v| v.is_empty()) {
+                            continue;
+                        }
+                        if finished.contains(block_id) {
+                            short_circuited = true;
+                            continue;
+                        }
+
+                        // Build execution context.
+                        let ctx = ExecutionContext {
+                            inputs,
+                            parameters: fan_in_parameters(fan_in),
+                            metrics: self.metrics.at_step(round as u64),
+                            logger: Logger::new(),
+                            storage: StorageContext::new(),
+                        };
+                        let Some(block) = self.blocks.remove(block_id) else {
+                            continue;
+                        };
+                        jobs.push((block_id.clone(), block, ctx));
+                    }
+
+                    // Execute the level, then handle results in topological order.
+                    for (block_id, block, checked, block_elapsed_ms) in self.run_level(jobs).await {
+                        *block_times.entry(block_id.clone()).or_default() += block_elapsed_ms;
+                        for warning in &checked.warnings {
+                            push_warning(&mut warnings, &block_id, warning);
+                        }
+
+                        match checked.result {
+                            Ok(exec_result) => {
+                                // Count operations from the output.
+                                let op_count: usize = exec_result
+                                    .outputs
+                                    .values()
+                                    .map(|v| v.len())
+                                    .sum();
+                                total_ops += op_count;
+                                successful_ops += op_count;
+
+                                // Store outputs in the data bus.
+                                for (port_id, value) in &exec_result.outputs {
+                                    data_bus.insert((block_id.clone(), port_id.clone()), value.clone());
+                                }
+
+                                // Collect non-fatal errors.
+                                for err in &exec_result.errors {
+                                    failed_ops += 1;
+                                    errors.push(format!("[{}] {}", block_id, err));
+                                }
+
+                                let accumulate_outputs = sinks.contains(&&block_id);
+                                match results.remove(&block_id) {
+                                    Some(previous) => {
+                                        let merged = merge_results(
+                                            previous,
+                                            exec_result,
+                                            block.as_deref(),
+                                            accumulate_outputs,
+                                        );
+                                        results.insert(block_id.clone(), merged);
+                                    }
+                                    None => {
+                                        results.insert(block_id.clone(), exec_result);
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                failed_ops += 1;
+                                errors.push(format!("[{}] Fatal: {}", block_id, e));
+                                failed_blocks.push(block_id.clone());
+                                // Continue executing remaining blocks (best-effort).
+                            }
+                        }
+                        if let Some(block) = block {
+                            if block.is_exhausted() {
+                                finished.insert(block_id.clone());
+                            }
+                            self.blocks.insert(block_id, block);
+                        }
+                    }
+                    finish_upstream(&order, &self.connections, &mut finished);
+                }
+                on_batch(&BatchProgress { batch: round + 1, batches, results: &results });
             }
         }
 
+        // One entry per block that ran, in execution order.
+        let mut block_metrics = Vec::new();
+        for block_id in &order {
+            let Some(&execution_time_ms) = block_times.get(block_id) else {
+                continue;
+            };
+            let Some(block) = self.blocks.get(block_id) else {
+                continue;
+            };
+            let counters = results.get(block_id).map(|r| r.metrics.clone()).unwrap_or_default();
+            block_metrics.push(BlockMetrics {
+                block_id: block_id.clone(),
+                block_type: format!("{:?}", block.metadata().category),
+                block_name: block.metadata().name.clone(),
+                execution_time_ms,
+                percentage: 0.0, // computed below
+                simulated_latency_ms: self.cost_model.latency_ms(&counters),
+                backpressure_stalls: stalls.get(block_id).copied().unwrap_or(0),
+                queueing_delay_ms: 0.0,
+                counters,
+            });
+        }
+        let per_request = |latency_ms: f64| latency_ms / arrivals.len().max(1) as f64;
+        for bm in &mut block_metrics {
+            bm.queueing_delay_ms =
+                CostModel::queueing_delay_ms(&arrivals, per_request(bm.simulated_latency_ms));
+        }
+        let total_simulated_latency_ms: f64 =
+            block_metrics.iter().map(|b| b.simulated_latency_ms).sum();
+        let block_times: Vec<f64> = block_metrics.iter().map(|b| b.execution_time_ms).collect();
+
         // Step 4: Compute aggregate metrics.
         let total_duration_ms = pipeline_start.elapsed_ms();
 
@@ -354,18 +792,218 @@ pub async fn execute(
 
         let success = errors.is_empty() || !errors.iter().any(|e| e.contains("Fatal"));
 
-        EngineExecutionResult {
-            success,
-            duration_ms: total_duration_ms,
-            metrics: ExecutionMetrics {
-                throughput,
-                latency,
-                total_operations: total_ops,
-                successful_operations: successful_ops,
-                failed_operations: failed_ops,
+        GraphRun {
+            results,
+            metrics: self.metrics.clone(),
+            summary: EngineExecutionResult {
+                success,
+                duration_ms: total_duration_ms,
+                metrics: ExecutionMetrics {
+                    throughput,
+                    latency,
+                    total_operations: total_ops,
+                    successful_operations: successful_ops,
+                    failed_operations: failed_ops,
+                    batches,
+                    total_simulated_latency_ms,
+                    backpressure_stalls: block_metrics.iter().map(|b| b.backpressure_stalls).sum(),
+                    queueing_delay_ms: CostModel::queueing_delay_ms(
+                        &arrivals,
+                        per_request(total_simulated_latency_ms),
+                    ),
+                    short_circuited,
+                },
+                block_metrics,
+                errors,
+                warnings,
+            },
+        }
+    }
+
+    /// Group a topological order into levels: a block's level is one more
+    /// than the deepest block feeding it, so blocks within a level never
+    /// depend on each other. Each level keeps topological order.
+    fn topological_levels(order: &[String], connections: &[Connection]) -> Vec<Vec<String>> {
+        let mut depth: HashMap<&str, usize> = HashMap::new();
+        let mut levels: Vec<Vec<String>> = Vec::new();
+        for block_id in order {
+            let level = connections
+                .iter()
+                .filter(|c| &c.target_block_id == block_id)
+                .filter_map(|c| depth.get(c.source_block_id.as_str()))
+                .map(|d| d + 1)
+                .max()
+                .unwrap_or(0);
+            depth.insert(block_id, level);
+            if levels.len() <= level {
+                levels.resize(level + 1, Vec::new());
+            }
+            levels[level].push(block_id.clone());
+        }
+        levels
+    }
+
+    /// Execute one level's blocks — concurrently if parallel execution is on
+    /// — and return each block with its result and elapsed time, in the order
+    /// given. A block whose task panicked is not returned and drops out of
+    /// the engine.
+    async fn run_level(&self, jobs: Vec<LevelJob>) -> Vec<LevelOutcome> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.parallel && jobs.len() > 1 {
+            let mut tasks = tokio::task::JoinSet::new();
+            let ids: Vec<String> = jobs.iter().map(|(id, _, _)| id.clone()).collect();
+            for (block_id, mut block, ctx) in jobs {
+                tasks.spawn(async move {
+                    let start = Timer::now();
+                    let checked = execute_checked(block.as_mut(), ctx).await;
+                    (block_id, block, checked, start.elapsed_ms())
+                });
+            }
+            let mut finished = HashMap::new();
+            while let Some(joined) = tasks.join_next().await {
+                if let Ok((block_id, block, checked, elapsed)) = joined {
+                    finished.insert(block_id, (block, checked, elapsed));
+                }
+            }
+            return ids
+                .into_iter()
+                .map(|id| match finished.remove(&id) {
+                    Some((block, checked, elapsed)) => (id, Some(block), checked, elapsed),
+                    None => {
+                        let err = BlockError::ExecutionError("block panicked".into());
+                        (id, None, Checked { result: Err(err), warnings: Vec::new() }, 0.0)
+                    }
+                })
+                .collect();
+        }
+
+        let mut outcomes = Vec::with_capacity(jobs.len());
+        for (block_id, mut block, ctx) in jobs {
+            let start = Timer::now();
+            let checked = execute_checked(block.as_mut(), ctx).await;
+            outcomes.push((block_id, Some(block), checked, start.elapsed_ms()));
+        }
+        outcomes
+    }
+
+    /// Run every block as a pipeline stage on its own task, with a bounded
+    /// queue per connection, and return each block's outcome in topological
+    /// order. Blocks are handed back in the outcomes, except one whose task
+    /// panicked.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn run_pipelined(
+        &mut self,
+        order: &[String],
+        rounds: Vec<HashMap<(String, String), PortValue>>,
+        sinks: &[&String],
+    ) -> Vec<(String, StageOutcome)> {
+        use tokio::sync::mpsc;
+
+        let capacity = self.max_in_flight_batches.unwrap_or(1);
+        let finished: HashMap<&String, Arc<AtomicBool>> =
+            order.iter().map(|id| (id, Arc::new(AtomicBool::new(false)))).collect();
+        let mut stages: HashMap<String, PipelineStage> = HashMap::new();
+        for block_id in order {
+            let Some(block) = self.blocks.remove(block_id) else {
+                continue;
+            };
+            stages.insert(
+                block_id.clone(),
+                PipelineStage {
+                    block,
+                    external: Vec::new(),
+                    inputs: Vec::new(),
+                    outputs: Vec::new(),
+                    accumulate_outputs: sinks.contains(&block_id),
+                    metrics: self.metrics.clone(),
+                    cancelled: self.cancelled.clone(),
+                    finished: finished[block_id].clone(),
+                },
+            );
+        }
+
+        for (round, round_inputs) in rounds.into_iter().enumerate() {
+            for ((block_id, port_id), value) in round_inputs {
+                if let Some(stage) = stages.get_mut(&block_id) {
+                    stage.external.resize_with(round + 1, HashMap::new);
+                    stage.external[round].insert(port_id, value);
+                }
+            }
+        }
+        for conn in &self.connections {
+            let (tx, rx) = mpsc::channel(capacity);
+            let target_finished = finished.get(&conn.target_block_id).cloned().unwrap_or_default();
+            if let Some(source) = stages.get_mut(&conn.source_block_id) {
+                source.outputs.push((conn.source_port_id.clone(), tx, target_finished));
+            }
+            if let Some(target) = stages.get_mut(&conn.target_block_id) {
+                target.inputs.push((conn.target_port_id.clone(), rx));
+            }
+        }
+
+        let handles: Vec<_> = order
+            .iter()
+            .filter_map(|id| stages.remove(id).map(|stage| (id.clone(), tokio::spawn(stage.run()))))
+            .collect();
+        let mut outcomes = Vec::with_capacity(handles.len());
+        for (block_id, handle) in handles {
+            let outcome = handle.await.unwrap_or_else(|_| StageOutcome {
+                block: None,
+                result: None,
+                fatal: Some(BlockError::ExecutionError("block panicked".into())),
+                ran: true,
+                elapsed_ms: 0.0,
+                operations: 0,
+                backpressure_stalls: 0,
+                short_circuited: false,
+                warnings: Vec::new(),
+            });
+            outcomes.push((block_id, outcome));
+        }
+        outcomes
+    }
+
+    /// Split the external input into per-round inputs. Record streams are
+    /// chunked into `Batch`es; anything else is delivered in the first round.
+    fn split_into_rounds(
+        &self,
+        input_data: HashMap<(String, String), PortValue>,
+    ) -> Vec<HashMap<(String, String), PortValue>> {
+        let Some(batch_size) = self.batch_size else {
+            return vec![input_data];
+        };
+        let mut rounds: Vec<HashMap<(String, String), PortValue>> = vec![HashMap::new()];
+        for (key, value) in input_data {
+            match value {
+                PortValue::Stream(records) | PortValue::Batch(records) => {
+                    for (i, chunk) in records.chunks(batch_size).enumerate() {
+                        if rounds.len() <= i {
+                            rounds.push(HashMap::new());
+                        }
+                        rounds[i].insert(key.clone(), PortValue::Batch(chunk.to_vec()));
+                    }
+                }
+                other => {
+                    rounds[0].insert(key, other);
+                }
+            }
+        }
+        rounds
+    }
+
+    /// A run that stopped before any block executed.
+    fn failed_run(&self, duration_ms: f64, errors: Vec<String>) -> GraphRun {
+        GraphRun {
+            results: HashMap::new(),
+            metrics: self.metrics.clone(),
+            summary: EngineExecutionResult {
+                success: false,
+                duration_ms,
+                metrics: ExecutionMetrics::default(),
+                block_metrics: Vec::new(),
+                errors,
+                warnings: Vec::new(),
             },
-            block_metrics,
-            errors,
         }
     }
 }
@@ -376,6 +1014,362 @@ fn default() -> Self {
     }
 }
 
+/// One block of a pipelined run (see "Backpressure" in the module docs).
+#[cfg(not(target_arch = "wasm32"))]
+struct PipelineStage {
+    block: Box<dyn Block>,
+    /// External input per round, keyed by port.
+    external: Vec<HashMap<String, PortValue>>,
+    /// Incoming connections' target ports and queues, in declaration order.
+    inputs: Vec<(String, tokio::sync::mpsc::Receiver<PortValue>)>,
+    /// Outgoing connections' source ports and queues, with whether the
+    /// target stage has finished.
+    outputs: Vec<(String, tokio::sync::mpsc::Sender<PortValue>, Arc<AtomicBool>)>,
+    accumulate_outputs: bool,
+    metrics: MetricsCollector,
+    cancelled: Arc<AtomicBool>,
+    /// Set once the block is exhausted or every consumer has finished.
+    finished: Arc<AtomicBool>,
+}
+
+/// What a pipeline stage did over the whole run.
+#[cfg(not(target_arch = "wasm32"))]
+struct StageOutcome {
+    /// `None` if the stage's task panicked.
+    block: Option<Box<dyn Block>>,
+    /// The block's results folded across batches.
+    result: Option<ExecutionResult>,
+    fatal: Option<BlockError>,
+    ran: bool,
+    elapsed_ms: f64,
+    operations: usize,
+    backpressure_stalls: usize,
+    /// Stopped early with external input left unread.
+    short_circuited: bool,
+    /// The block's input validation warnings, without repeats.
+    warnings: Vec<String>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PipelineStage {
+    /// Process one batch per round until the external input runs out and
+    /// every upstream stage has finished. Each round reads one message from
+    /// every open incoming queue and writes one to every outgoing queue —
+    /// `None` if the block had nothing to say — so fan-in stays aligned by
+    /// round. A fatal error stops the stage; dropping its queues lets its
+    /// neighbours finish. So does exhaustion, or every consumer finishing.
+    async fn run(mut self) -> StageOutcome {
+        use tokio::sync::mpsc::error::TrySendError;
+
+        let mut result: Option<ExecutionResult> = None;
+        let mut fatal = None;
+        let mut ran = false;
+        let mut elapsed_ms = 0.0;
+        let mut operations = 0;
+        let mut backpressure_stalls = 0;
+        let mut short_circuited = false;
+        let mut warnings: Vec<String> = Vec::new();
+        let mut open = vec![true; self.inputs.len()];
+
+        for round in 0.. {
+            if self.cancelled.load(Ordering::SeqCst) {
+                break;
+            }
+            let consumers_finished = !self.outputs.is_empty()
+                && self.outputs.iter().all(|(_, _, done)| done.load(Ordering::SeqCst));
+            if consumers_finished {
+                self.finished.store(true, Ordering::SeqCst);
+                short_circuited = round < self.external.len();
+                break;
+            }
+
+            let mut inputs = self.external.get(round).cloned().unwrap_or_default();
+            let mut received = round < self.external.len();
+            let mut fan_in: HashMap<String, i64> = HashMap::new();
+            for (port, value) in &inputs {
+                count_fan_in(&mut fan_in, port, value);
+            }
+            for ((port, queue), open) in self.inputs.iter_mut().zip(open.iter_mut()) {
+                if !*open {
+                    continue;
+                }
+                match queue.recv().await {
+                    Some(value) => {
+                        received = true;
+                        count_fan_in(&mut fan_in, port, &value);
+                        merge_input(&mut inputs, port, value);
+                    }
+                    None => *open = false,
+                }
+            }
+            if round > 0 && !received {
+                break;
+            }
+
+            // As in lockstep rounds, only the first round runs without input.
+            let mut outputs = HashMap::new();
+            if round == 0 || inputs.values().any(|v| !v.is_empty()) {
+                let ctx = ExecutionContext {
+                    inputs,
+                    parameters: fan_in_parameters(fan_in),
+                    metrics: self.metrics.at_step(round as u64),
+                    logger: Logger::new(),
+                    storage: StorageContext::new(),
+                };
+                let start = Timer::now();
+                let executed = execute_checked(self.block.as_mut(), ctx).await;
+                elapsed_ms += start.elapsed_ms();
+                ran = true;
+                for warning in executed.warnings {
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+                match executed.result {
+                    Ok(batch) => {
+                        operations += batch.outputs.values().map(|v| v.len()).sum::<usize>();
+                        outputs = batch.outputs.clone();
+                        result = Some(match result.take() {
+                            Some(total) => merge_results(
+                                total,
+                                batch,
+                                Some(self.block.as_ref()),
+                                self.accumulate_outputs,
+                            ),
+                            None => batch,
+                        });
+                    }
+                    Err(e) => {
+                        fatal = Some(e);
+                        break;
+                    }
+                }
+            }
+
+            for (port, queue, _) in &self.outputs {
+                let value = outputs.get(port).cloned().unwrap_or(PortValue::None);
+                // A closed queue means the consumer stopped; drop the value.
+                if let Err(TrySendError::Full(value)) = queue.try_send(value) {
+                    backpressure_stalls += 1;
+                    let _ = queue.send(value).await;
+                }
+            }
+            if self.block.is_exhausted() {
+                self.finished.store(true, Ordering::SeqCst);
+                break;
+            }
+        }
+
+        StageOutcome {
+            block: Some(self.block),
+            result,
+            fatal,
+            ran,
+            elapsed_ms,
+            operations,
+            backpressure_stalls,
+            short_circuited,
+            warnings,
+        }
+    }
+}
+
+/// Blocks that [restate their output](Block::restates_output), plus every
+/// block downstream of one.
+fn restated_blocks(
+    order: &[String],
+    connections: &[Connection],
+    blocks: &HashMap<String, Box<dyn Block>>,
+) -> HashSet<String> {
+    let mut restated = HashSet::new();
+    // Topological order visits producers before their consumers.
+    for block_id in order {
+        let own = blocks.get(block_id).is_some_and(|b| b.restates_output());
+        let fed = connections
+            .iter()
+            .any(|c| &c.target_block_id == block_id && restated.contains(&c.source_block_id));
+        if own || fed {
+            restated.insert(block_id.clone());
+        }
+    }
+    restated
+}
+
+/// Add to `finished` every block whose consumers have all finished, until
+/// nothing changes. Sinks have no consumers and are never added.
+fn finish_upstream(order: &[String], connections: &[Connection], finished: &mut HashSet<String>) {
+    // Reverse topological order visits consumers before their producers.
+    for block_id in order.iter().rev() {
+        let mut targets = connections
+            .iter()
+            .filter(|c| &c.source_block_id == block_id)
+            .map(|c| &c.target_block_id)
+            .peekable();
+        if targets.peek().is_some() && targets.all(|t| finished.contains(t)) {
+            finished.insert(block_id.clone());
+        }
+    }
+}
+
+/// A block's result, with the warnings its input validation raised.
+struct Checked {
+    result: Result<ExecutionResult, BlockError>,
+    warnings: Vec<String>,
+}
+
+/// Execute `block` once its inputs pass its input ports' schemas and its own
+/// [`Block::validate`] (see "Port schemas" in the module docs).
+async fn execute_checked(block: &mut dyn Block, ctx: ExecutionContext) -> Checked {
+    if let Err(e) = check_input_schemas(block.inputs(), &ctx.inputs) {
+        return Checked { result: Err(e), warnings: Vec::new() };
+    }
+    let validation = block.validate(&ctx.inputs);
+    let result = if validation.errors.is_empty() {
+        block.execute(ctx).await
+    } else {
+        Err(BlockError::ValidationError(validation.errors.join("; ")))
+    };
+    Checked { result, warnings: validation.warnings }
+}
+
+/// Add a block's warning to the run's, unless it is already there.
+fn push_warning(warnings: &mut Vec<String>, block_id: &str, warning: &str) {
+    let warning = format!("[{}] {}", block_id, warning);
+    if !warnings.contains(&warning) {
+        warnings.push(warning);
+    }
+}
+
+/// Check each input value against its port's schema, reporting the first
+/// offending record of the first port that fails.
+fn check_input_schemas(
+    ports: &[Port],
+    inputs: &HashMap<String, PortValue>,
+) -> Result<(), BlockError> {
+    for port in ports.iter().filter(|p| p.schema.is_some()) {
+        let Some(value) = inputs.get(&port.id) else {
+            continue;
+        };
+        let result = port.validate(value);
+        if let Some(first) = result.errors.first() {
+            let more = match result.errors.len() {
+                1 => String::new(),
+                n => format!(" ({} records failed)", n),
+            };
+            return Err(BlockError::ValidationError(format!(
+                "input '{}' {}{}",
+                port.id, first, more
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Fold a block's result for one batch into its running result (see
+/// "Batched execution" in the module docs).
+fn merge_results(
+    mut total: ExecutionResult,
+    batch: ExecutionResult,
+    block: Option<&dyn Block>,
+    accumulate_outputs: bool,
+) -> ExecutionResult {
+    let per_call = block.filter(|b| !b.reports_running_totals());
+    for (id, value) in batch.metrics {
+        let is_counter = per_call.is_some_and(|b| {
+            b.metrics()
+                .iter()
+                .any(|d| d.id == id && d.metric_type == MetricType::Counter)
+        });
+        match total.metrics.get_mut(&id) {
+            Some(sum) if is_counter => *sum += value,
+            _ => {
+                total.metrics.insert(id, value);
+            }
+        }
+    }
+    for (port, value) in batch.outputs {
+        if accumulate_outputs {
+            merge_input(&mut total.outputs, &port, value);
+        } else {
+            total.outputs.insert(port, value);
+        }
+    }
+    total.errors.extend(batch.errors);
+    total
+}
+
+/// Execution parameter holding, for each input port, how many non-empty
+/// values were merged into it — an object of port id to count. Ports that
+/// received nothing are absent.
+pub const FAN_IN_PARAMETER: &str = "_fan_in";
+
+/// Count `value` towards `port`'s fan-in if it carries anything.
+fn count_fan_in(fan_in: &mut HashMap<String, i64>, port: &str, value: &PortValue) {
+    if !value.is_empty() {
+        *fan_in.entry(port.to_string()).or_default() += 1;
+    }
+}
+
+/// The execution parameters a block runs with: just its fan-in counts.
+fn fan_in_parameters(fan_in: HashMap<String, i64>) -> HashMap<String, ParameterValue> {
+    let counts = fan_in.into_iter().map(|(port, n)| (port, ParameterValue::Integer(n))).collect();
+    HashMap::from([(FAN_IN_PARAMETER.to_string(), ParameterValue::Object(counts))])
+}
+
+/// Merge `incoming` into whatever `port` already holds (see "Data routing" in
+/// the module docs).
+fn merge_input(inputs: &mut HashMap<String, PortValue>, port: &str, incoming: PortValue) {
+    let merged = match inputs.remove(port) {
+        Some(existing) => merge_port_values(existing, incoming),
+        None => incoming,
+    };
+    inputs.insert(port.to_string(), merged);
+}
+
+fn merge_port_values(existing: PortValue, incoming: PortValue) -> PortValue {
+    match (existing, incoming) {
+        (PortValue::None, value) | (value, PortValue::None) => value,
+        (PortValue::Batch(mut a), PortValue::Batch(b)) => {
+            a.extend(b);
+            PortValue::Batch(a)
+        }
+        (PortValue::Signal(_), incoming) | (_, incoming @ PortValue::Signal(_)) => incoming,
+        (a, b) => {
+            let mut records = into_records(a);
+            records.extend(into_records(b));
+            PortValue::Stream(records)
+        }
+    }
+}
+
+fn into_records(value: PortValue) -> Vec<Record> {
+    match value {
+        PortValue::Stream(records) | PortValue::Batch(records) => records,
+        PortValue::Single(record) => vec![record],
+        PortValue::Signal(_) | PortValue::None => Vec::new(),
+    }
+}
+
+/// Arrival times (`_arrival_ms`) of the external input stream with the most
+/// timestamped records. The same workload is often fed to several entry
+/// points; counting it once per entry would double the arrival rate.
+fn arrival_times(input_data: &HashMap<(String, String), PortValue>) -> Vec<f64> {
+    input_data
+        .values()
+        .map(|value| match value {
+            PortValue::Stream(records) | PortValue::Batch(records) => records
+                .iter()
+                .filter_map(|r| r.get::<f64>("_arrival_ms").ok().flatten())
+                .collect(),
+            PortValue::Single(record) => {
+                record.get::<f64>("_arrival_ms").ok().flatten().into_iter().collect()
+            }
+            PortValue::Signal(_) | PortValue::None => Vec::new(),
+        })
+        .max_by_key(|times: &Vec<f64>| times.len())
+        .unwrap_or_default()
+}
+
 /// Linear interpolation percentile.
 fn percentile(sorted: &[f64], p: f64) -> f64 {
     if sorted.is_empty() {
@@ -398,10 +1392,117 @@ fn percentile(sorted: &[f64], p: f64) -> f64 {
 mod tests {
     use super::*;
     use crate::categories::buffer::LRUBufferBlock;
+    use crate::categories::compression::DictionaryEncodingBlock;
+    use crate::categories::concurrency::MVCCBlock;
+    use crate::categories::execution::{AggregateBlock, MergeJoinBlock, SortBlock};
     use crate::categories::index::BTreeIndexBlock;
     use crate::categories::storage::HeapFileBlock;
-    use crate::core::port::{Connection, PortValue, Record};
+    use crate::core::block::{
+        BlockCategory, BlockDocumentation, BlockMetadata, BlockState, Complexity,
+    };
+    use crate::core::constraint::{Constraint, Guarantee};
+    use crate::core::metrics::MetricDefinition;
+    use crate::core::parameter::{Parameter, ValidationResult};
+    use crate::core::port::{Connection, Port, PortDirection, PortType, PortValue, Record};
     use crate::runtime::workload::{WorkloadConfig, WorkloadGenerator};
+    use async_trait::async_trait;
+
+    type Spans = std::sync::Arc<std::sync::Mutex<Vec<(String, std::time::Instant, std::time::Instant)>>>;
+
+    /// Test block that forwards everything on its fan-in `records` port to
+    /// `merged`, optionally sleeping first and logging when it ran.
+    struct UnionBlock {
+        metadata: BlockMetadata,
+        inputs: Vec<Port>,
+        outputs: Vec<Port>,
+        delay: Option<(std::time::Duration, Spans)>,
+    }
+
+    impl UnionBlock {
+        fn new() -> Self {
+            let port = |id: &str, direction| Port {
+                id: id.into(),
+                name: id.into(),
+                port_type: PortType::DataStream,
+                direction,
+                required: false,
+                multiple: true,
+                description: String::new(),
+                schema: None,
+            };
+            Self {
+                metadata: BlockMetadata {
+                    id: "union".into(),
+                    name: "Union".into(),
+                    category: BlockCategory::Execution,
+                    description: String::new(),
+                    version: "1.0.0".into(),
+                    documentation: BlockDocumentation {
+                        overview: String::new(),
+                        algorithm: String::new(),
+                        complexity: Complexity { time: "O(n)".into(), space: "O(n)".into() },
+                        use_cases: vec![],
+                        tradeoffs: vec![],
+                        examples: vec![],
+                        motivation: String::new(),
+                        parameter_guide: HashMap::new(),
+                        alternatives: vec![],
+                        suggested_questions: vec![],
+                    },
+                    references: vec![],
+                    icon: String::new(),
+                    color: String::new(),
+                },
+                inputs: vec![port("records", PortDirection::Input)],
+                outputs: vec![port("merged", PortDirection::Output)],
+                delay: None,
+            }
+        }
+
+        fn sleeping(name: &str, ms: u64, spans: &Spans) -> Self {
+            let mut block = Self::new();
+            block.metadata.name = name.into();
+            block.delay = Some((std::time::Duration::from_millis(ms), spans.clone()));
+            block
+        }
+    }
+
+    #[async_trait]
+    impl Block for UnionBlock {
+        fn metadata(&self) -> &BlockMetadata { &self.metadata }
+        fn inputs(&self) -> &[Port] { &self.inputs }
+        fn outputs(&self) -> &[Port] { &self.outputs }
+        fn parameters(&self) -> &[Parameter] { &[] }
+        fn requires(&self) -> &[Constraint] { &[] }
+        fn guarantees(&self) -> &[Guarantee] { &[] }
+        fn metrics(&self) -> &[MetricDefinition] { &[] }
+
+        async fn initialize(&mut self, _: HashMap<String, ParameterValue>) -> Result<(), BlockError> {
+            Ok(())
+        }
+
+        async fn execute(&mut self, mut ctx: ExecutionContext) -> Result<ExecutionResult, BlockError> {
+            if let Some((delay, spans)) = &self.delay {
+                let start = std::time::Instant::now();
+                tokio::time::sleep(*delay).await;
+                let span = (self.metadata.name.clone(), start, std::time::Instant::now());
+                spans.lock().unwrap().push(span);
+            }
+            let merged = ctx.inputs.remove("records").unwrap_or(PortValue::None);
+            Ok(ExecutionResult {
+                outputs: HashMap::from([("merged".to_string(), merged)]),
+                metrics: HashMap::new(),
+                errors: vec![],
+            })
+        }
+
+        fn validate(&self, _: &HashMap<String, PortValue>) -> ValidationResult {
+            ValidationResult::ok()
+        }
+
+        fn get_state(&self) -> BlockState { BlockState::new() }
+        fn set_state(&mut self, _: BlockState) -> Result<(), BlockError> { Ok(()) }
+    }
 
     fn conn(id: &str, src_block: &str, src_port: &str, tgt_block: &str, tgt_port: &str) -> Connection {
         Connection::new(
@@ -518,6 +1619,820 @@ async fn test_three_block_pipeline() {
         assert_eq!(result.block_metrics.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_run_returns_per_block_results() {
+        let mut heap = HeapFileBlock::new();
+        heap.initialize(HashMap::new()).await.unwrap();
+        let mut btree = BTreeIndexBlock::new();
+        btree.initialize(HashMap::new()).await.unwrap();
+
+        let mut blocks: HashMap<String, Box<dyn Block>> = HashMap::new();
+        blocks.insert("heap".into(), Box::new(heap));
+        blocks.insert("btree".into(), Box::new(btree));
+        let connections = vec![conn("c1", "heap", "stored", "btree", "records")];
+
+        let mut input = HashMap::new();
+        input.insert(
+            ("heap".into(), "records".into()),
+            PortValue::Stream(generate_records(50)),
+        );
+
+        let run = ExecutionEngine::run(blocks, connections, input).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        assert_eq!(run.results.len(), 2);
+        assert_eq!(run.results["heap"].outputs["stored"].len(), 50);
+
+        // Both blocks recorded into the same collector.
+        assert_eq!(run.metrics.get_count("records_inserted"), 50);
+        assert!(run.metrics.get_count("tree_depth") > 0);
+    }
+
+    // ── Fan-in / fan-out ────────────────────────────────────────────────
+
+    fn ids(value: &PortValue) -> Vec<i64> {
+        into_records(value.clone())
+            .iter()
+            .map(|r| r.get::<i64>("id").unwrap().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_fan_in_concatenates_in_connection_order() {
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("union", Box::new(UnionBlock::new()));
+        engine.add_block("b", Box::new(UnionBlock::new()));
+        engine.add_block("a", Box::new(UnionBlock::new()));
+        // Declared b first, so b's records come first.
+        engine.add_connection(conn("c1", "b", "merged", "union", "records"));
+        engine.add_connection(conn("c2", "a", "merged", "union", "records"));
+        engine.set_entry_point("a");
+        engine.set_entry_point("b");
+
+        let mut input = HashMap::new();
+        let records = generate_records(6);
+        input.insert(("a".into(), "records".into()), PortValue::Stream(records[..3].to_vec()));
+        input.insert(("b".into(), "records".into()), PortValue::Batch(records[3..].to_vec()));
+
+        let run = engine.execute_detailed(input).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        let merged = &run.results["union"].outputs["merged"];
+        assert!(matches!(merged, PortValue::Stream(_)));
+        assert_eq!(ids(merged), vec![3, 4, 5, 0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_clones_output() {
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("src", Box::new(UnionBlock::new()));
+        engine.add_block("x", Box::new(UnionBlock::new()));
+        engine.add_block("y", Box::new(UnionBlock::new()));
+        engine.add_connection(conn("c1", "src", "merged", "x", "records"));
+        engine.add_connection(conn("c2", "src", "merged", "y", "records"));
+        engine.set_entry_point("src");
+
+        let mut input = HashMap::new();
+        input.insert(("src".into(), "records".into()), PortValue::Stream(generate_records(4)));
+
+        let run = engine.execute_detailed(input).await;
+        assert_eq!(ids(&run.results["x"].outputs["merged"]), vec![0, 1, 2, 3]);
+        assert_eq!(ids(&run.results["y"].outputs["merged"]), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_merge_port_values() {
+        let r = generate_records(3);
+        let batch = merge_port_values(PortValue::Batch(vec![r[0].clone()]), PortValue::Batch(vec![r[1].clone()]));
+        assert!(matches!(batch, PortValue::Batch(ref v) if v.len() == 2));
+
+        let stream = merge_port_values(batch, PortValue::Single(r[2].clone()));
+        assert_eq!(ids(&stream), vec![0, 1, 2]);
+        assert!(matches!(stream, PortValue::Stream(_)));
+
+        assert_eq!(merge_port_values(PortValue::None, stream).len(), 3);
+    }
+
+    // ── Batched execution ───────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_batches_add_points_to_metric_series() {
+        async fn run(pipelined: bool) -> Vec<(u64, f64)> {
+            let mut engine = ExecutionEngine::new();
+            engine.add_block("buffer", Box::new(LRUBufferBlock::new()));
+            engine.set_entry_point("buffer");
+            engine.initialize_block("buffer", HashMap::new()).await.unwrap();
+            engine.set_batch_size(10);
+            if pipelined {
+                engine.set_max_in_flight_batches(2);
+            }
+            engine.metrics().set_history_enabled(true);
+
+            // Pages 0..9 four times over: the first batch is all cold misses,
+            // every later one all hits.
+            let requests: Vec<Record> = (0..40)
+                .map(|i| {
+                    let mut r = Record::new();
+                    r.insert("_page_id".into(), i % 10).unwrap();
+                    r
+                })
+                .collect();
+            let mut input = HashMap::new();
+            input.insert(("buffer".into(), "requests".into()), PortValue::Stream(requests));
+            let run = engine.execute_detailed(input).await;
+            assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+            run.metrics.series("hit_rate_pct")
+        }
+
+        let expected = vec![(0, 0.0), (1, 50.0), (2, 200.0 / 3.0), (3, 75.0)];
+        for pipelined in [false, true] {
+            let series = run(pipelined).await;
+            assert_eq!(series.len(), expected.len());
+            for ((step, value), (want_step, want)) in series.iter().zip(&expected) {
+                assert_eq!(step, want_step);
+                assert!((value - want).abs() < 1e-9, "{} != {}", value, want);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_export_uses_block_definitions() {
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("buffer", Box::new(LRUBufferBlock::new()));
+        engine.set_entry_point("buffer");
+        engine.initialize_block("buffer", HashMap::new()).await.unwrap();
+        let requests: Vec<Record> = (0..8)
+            .map(|i| {
+                let mut r = Record::new();
+                r.insert("_page_id".into(), i % 4).unwrap();
+                r
+            })
+            .collect();
+        let mut input = HashMap::new();
+        input.insert(("buffer".into(), "requests".into()), PortValue::Stream(requests));
+        let run = engine.execute_detailed(input).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+
+        let text = run.metrics.to_prometheus();
+        assert!(text.contains("# TYPE cache_hits counter\ncache_hits 4\n"), "{}", text);
+        assert!(text.contains("# TYPE hit_rate_pct gauge\nhit_rate_pct 50\n"), "{}", text);
+        assert!(text.contains("# TYPE access_latency_ms histogram\n"), "{}", text);
+        assert!(text.contains("access_latency_ms_count 8\n"), "{}", text);
+
+        let json = run.metrics.to_json();
+        assert_eq!(json["cache_misses"]["type"], "counter");
+        assert_eq!(json["cache_misses"]["value"], 4.0);
+        assert_eq!(json["cache_misses"]["unit"], "pages");
+        assert_eq!(json["access_latency_ms"]["count"], 8);
+    }
+
+    #[tokio::test]
+    async fn test_index_follows_heap_auto_vacuum() {
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("heap", Box::new(HeapFileBlock::new()));
+        engine.add_block("btree", Box::new(BTreeIndexBlock::new()));
+        engine.add_connection(conn("c1", "heap", "stored", "btree", "records"));
+        engine.add_connection(conn("c2", "heap", "remapped", "btree", "remap"));
+        engine.set_entry_point("heap");
+        let mut params = HashMap::new();
+        params.insert("fragmentation_threshold".into(), ParameterValue::Number(25.0));
+        engine.initialize_block("heap", params).await.unwrap();
+        engine.initialize_block("btree", HashMap::new()).await.unwrap();
+
+        let heap_input = |records: Vec<Record>| {
+            let mut input = HashMap::new();
+            input.insert(("heap".into(), "records".into()), PortValue::Stream(records));
+            input
+        };
+
+        // Index 12 records, then delete the first four: a third of the heap
+        // is dead, so the next call vacuums and moves the survivors.
+        let run = engine.execute_detailed(heap_input(generate_records(12))).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        let deletes: Vec<Record> = (0..4usize)
+            .map(|slot| {
+                let mut r = Record::new();
+                r.insert("_op".into(), "delete").unwrap();
+                r.insert("_page_id".into(), 0usize).unwrap();
+                r.insert("_slot_id".into(), slot).unwrap();
+                r
+            })
+            .collect();
+        let run = engine.execute_detailed(heap_input(deletes)).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+
+        let mut insert = Record::new();
+        insert.insert("id".into(), 100i64).unwrap();
+        let run = engine.execute_detailed(heap_input(vec![insert])).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        assert_eq!(run.results["heap"].metrics["vacuum"], 1.0);
+        // Four reclaimed records, then eight moved ones.
+        assert_eq!(run.results["heap"].outputs["remapped"].len(), 12);
+
+        // Every surviving key still resolves to its own record.
+        let mut btree = engine.blocks.remove("btree").unwrap();
+        let btree = btree.as_mut().as_any_mut().downcast_mut::<BTreeIndexBlock>().unwrap();
+        let heap = engine.blocks["heap"].as_ref().as_any().downcast_ref::<HeapFileBlock>().unwrap();
+        for id in (4..12i64).chain([100]) {
+            let tid = btree.lookup(&serde_json::json!(id)).unwrap();
+            let record = heap.get(tid).unwrap_or_else(|| panic!("key {} points at {}", id, tid));
+            assert_eq!(record.get::<i64>("id").unwrap(), Some(id));
+        }
+        // Deleted keys are gone rather than pointing at the records that
+        // moved into their slots.
+        for id in 0..4i64 {
+            assert_eq!(btree.lookup(&serde_json::json!(id)), None, "key {}", id);
+        }
+        assert_eq!(btree.key_count(), 9);
+    }
+
+    #[tokio::test]
+    async fn test_batched_export_keeps_running_totals() {
+        let mut mvcc: Box<dyn Block> = Box::new(MVCCBlock::new());
+        mvcc.initialize(HashMap::new()).await.unwrap();
+        let mut blocks = HashMap::new();
+        blocks.insert("mvcc".to_string(), mvcc);
+        let mut input = HashMap::new();
+        input.insert(("mvcc".into(), "records".into()), PortValue::Stream(generate_records(30)));
+
+        let run = ExecutionEngine::run_batched(blocks, Vec::new(), input, 10, |_| {}).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        assert_eq!(run.summary.metrics.batches, 3);
+
+        // Recorded once per batch as a running total: the export matches the
+        // folded result instead of summing every batch's total.
+        let created = run.results["mvcc"].metrics["versions_created"];
+        assert_eq!(run.metrics.get_count("versions_created"), 3);
+        let text = run.metrics.to_prometheus();
+        assert!(text.contains(&format!("\nversions_created {}\n", created)), "{}", text);
+        assert_eq!(run.metrics.to_json()["versions_created"]["value"], created);
+    }
+
+    #[tokio::test]
+    async fn test_batched_aggregate_matches_single_pass() {
+        async fn run(batch_size: usize) -> GraphRun {
+            let mut aggregate: Box<dyn Block> = Box::new(AggregateBlock::new());
+            let mut params = HashMap::new();
+            params.insert(
+                "aggregates".into(),
+                ParameterValue::Array(vec![
+                    ParameterValue::from("count(*)"),
+                    ParameterValue::from("sum(score) AS total"),
+                ]),
+            );
+            aggregate.initialize(params).await.unwrap();
+            let mut sort: Box<dyn Block> = Box::new(SortBlock::new());
+            let mut params = HashMap::new();
+            params.insert("sort_column".into(), ParameterValue::from("count"));
+            sort.initialize(params).await.unwrap();
+            let mut blocks = HashMap::new();
+            blocks.insert("agg".to_string(), aggregate);
+            blocks.insert("sort".to_string(), sort);
+            let connections = vec![conn("c1", "agg", "aggregated", "sort", "records")];
+            let mut input = HashMap::new();
+            input.insert(("agg".into(), "records".into()), PortValue::Stream(generate_records(30)));
+            ExecutionEngine::run_batched(blocks, connections, input, batch_size, |_| {}).await
+        }
+
+        let single = run(0).await;
+        let batched = run(10).await;
+        assert!(batched.summary.success, "Errors: {:?}", batched.summary.errors);
+        assert_eq!(batched.summary.metrics.batches, 3);
+
+        // Each round restates the running totals, so the sink keeps one row
+        // covering all three batches rather than one partial row per batch.
+        let rows = |run: &GraphRun| match &run.results["sort"].outputs["sorted"] {
+            PortValue::Stream(records) => records.clone(),
+            other => panic!("unexpected output {:?}", other),
+        };
+        let out = rows(&batched);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].get_or_null("count"), &serde_json::json!(30));
+        assert_eq!(out[0].data, rows(&single)[0].data);
+        assert_eq!(batched.results["agg"].metrics["rows_aggregated"], 30.0);
+    }
+
+    #[tokio::test]
+    async fn test_batched_merge_join_matches_across_batches() {
+        async fn run(batch_size: usize) -> GraphRun {
+            let mut join: Box<dyn Block> = Box::new(MergeJoinBlock::new());
+            join.initialize(HashMap::new()).await.unwrap();
+            let mut blocks = HashMap::new();
+            blocks.insert("join".to_string(), join);
+            // Left ids 0..30, right the even ids 0..60: the right side runs
+            // ahead, so most matches pair rows from different batches.
+            let evens: Vec<Record> = (0..30i64)
+                .map(|i| {
+                    let mut r = Record::new();
+                    r.insert("id".into(), i * 2).unwrap();
+                    r
+                })
+                .collect();
+            let mut input = HashMap::new();
+            input.insert(("join".into(), "left".into()), PortValue::Stream(generate_records(30)));
+            input.insert(("join".into(), "right".into()), PortValue::Stream(evens));
+            ExecutionEngine::run_batched(blocks, Vec::new(), input, batch_size, |_| {}).await
+        }
+
+        let ids = |run: &GraphRun| match &run.results["join"].outputs["joined"] {
+            PortValue::Stream(records) | PortValue::Batch(records) => records
+                .iter()
+                .map(|r| r.get::<i64>("left_id").unwrap().unwrap())
+                .collect::<Vec<_>>(),
+            other => panic!("unexpected output {:?}", other),
+        };
+        let batched = run(10).await;
+        assert!(batched.summary.success, "Errors: {:?}", batched.summary.errors);
+        assert_eq!(batched.summary.metrics.batches, 3);
+        let expected: Vec<i64> = (0..30).step_by(2).collect();
+        assert_eq!(ids(&batched), expected);
+        assert_eq!(ids(&batched), ids(&run(0).await));
+        assert_eq!(batched.results["join"].metrics["rows_emitted"], 15.0);
+    }
+
+    #[tokio::test]
+    async fn test_dictionary_decoder_block_loads_encoder_entries() {
+        async fn run(batch_size: usize) -> GraphRun {
+            let mut encoder: Box<dyn Block> = Box::new(DictionaryEncodingBlock::new());
+            encoder.initialize(HashMap::new()).await.unwrap();
+            let mut decoder: Box<dyn Block> = Box::new(DictionaryEncodingBlock::new());
+            let mut params = HashMap::new();
+            params.insert("mode".into(), ParameterValue::from("decode"));
+            decoder.initialize(params).await.unwrap();
+            let mut blocks = HashMap::new();
+            blocks.insert("enc".to_string(), encoder);
+            blocks.insert("dec".to_string(), decoder);
+            let connections = vec![
+                conn("c1", "enc", "compressed", "dec", "records"),
+                conn("c2", "enc", "dictionary_entries", "dec", "dictionary"),
+            ];
+            let keys: Vec<Record> = (0..30u64)
+                .map(|i| {
+                    let mut r = Record::new();
+                    r.insert("_key".into(), i % 7).unwrap();
+                    r
+                })
+                .collect();
+            let mut input = HashMap::new();
+            input.insert(("enc".into(), "records".into()), PortValue::Stream(keys));
+            ExecutionEngine::run_batched(blocks, connections, input, batch_size, |_| {}).await
+        }
+
+        // A single pass, and batches where later batches only reuse codes
+        // whose entries the decoder loaded in an earlier round.
+        for batch_size in [0, 10] {
+            let run = run(batch_size).await;
+            assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+            let decoded = match &run.results["dec"].outputs["decoded"] {
+                PortValue::Stream(records) | PortValue::Batch(records) => records.clone(),
+                other => panic!("unexpected output {:?}", other),
+            };
+            let keys: Vec<u64> = decoded.iter().map(|r| r.get("_key").unwrap().unwrap()).collect();
+            assert_eq!(keys, (0..30).map(|i| i % 7).collect::<Vec<u64>>(), "batch size {}", batch_size);
+            assert!(decoded.iter().all(|r| !r.data.contains_key("_dict_code")));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_batched_reports_each_round() {
+        let mut heap: Box<dyn Block> = Box::new(HeapFileBlock::new());
+        heap.initialize(HashMap::new()).await.unwrap();
+        let mut blocks = HashMap::new();
+        blocks.insert("heap".to_string(), heap);
+        let mut input = HashMap::new();
+        input.insert(("heap".into(), "records".into()), PortValue::Stream(generate_records(250)));
+
+        let mut seen = Vec::new();
+        let run = ExecutionEngine::run_batched(blocks, Vec::new(), input, 100, |progress| {
+            let inserted = progress.results["heap"].metrics["records_inserted"];
+            seen.push((progress.batch, progress.batches, inserted));
+        })
+        .await;
+
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        // Each snapshot carries the counters folded so far.
+        assert_eq!(seen, vec![(1, 3, 100.0), (2, 3, 200.0), (3, 3, 250.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_batched_execution_matches_single_pass() {
+        async fn run(batch_size: usize) -> GraphRun {
+            let mut engine = ExecutionEngine::new();
+            engine.add_block("heap", Box::new(HeapFileBlock::new()));
+            engine.add_block("btree", Box::new(BTreeIndexBlock::new()));
+            engine.add_connection(conn("c1", "heap", "stored", "btree", "records"));
+            engine.set_entry_point("heap");
+            engine.initialize_block("heap", HashMap::new()).await.unwrap();
+            engine.initialize_block("btree", HashMap::new()).await.unwrap();
+            engine.set_batch_size(batch_size);
+
+            let mut input = HashMap::new();
+            input.insert(("heap".into(), "records".into()), PortValue::Stream(generate_records(250)));
+            engine.execute_detailed(input).await
+        }
+
+        let whole = run(0).await;
+        let batched = run(100).await;
+        assert!(batched.summary.success, "Errors: {:?}", batched.summary.errors);
+        assert_eq!(whole.summary.metrics.batches, 1);
+        assert_eq!(batched.summary.metrics.batches, 3);
+        assert_eq!(batched.summary.block_metrics.len(), 2);
+
+        // Per-call counters sum across batches and running totals keep the
+        // latest value, so both match a single pass; the sink's output
+        // accumulates.
+        let counter = |run: &GraphRun, block: &str, id: &str| run.results[block].metrics.get(id).copied();
+        assert_eq!(counter(&batched, "heap", "records_inserted"), counter(&whole, "heap", "records_inserted"));
+        assert_eq!(counter(&batched, "heap", "records_inserted"), Some(250.0));
+        assert_eq!(
+            batched.metrics.get_count("records_inserted"),
+            whole.metrics.get_count("records_inserted")
+        );
+        assert_eq!(
+            batched.summary.metrics.total_operations,
+            whole.summary.metrics.total_operations
+        );
+        assert_eq!(counter(&batched, "btree", "total_keys"), Some(250.0));
+        assert_eq!(counter(&batched, "btree", "splits"), counter(&whole, "btree", "splits"));
+    }
+
+    #[tokio::test]
+    async fn test_batches_arrive_as_partial_batches() {
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("a", Box::new(UnionBlock::new()));
+        engine.add_block("b", Box::new(UnionBlock::new()));
+        engine.add_connection(conn("c1", "a", "merged", "b", "records"));
+        engine.set_entry_point("a");
+        engine.set_batch_size(4);
+
+        let mut input = HashMap::new();
+        input.insert(("a".into(), "records".into()), PortValue::Stream(generate_records(10)));
+        let run = engine.execute_detailed(input).await;
+
+        assert_eq!(run.summary.metrics.batches, 3);
+        // Intermediate block keeps its last batch; the sink has everything.
+        assert!(matches!(&run.results["a"].outputs["merged"], PortValue::Batch(v) if v.len() == 2));
+        assert_eq!(ids(&run.results["b"].outputs["merged"]), (0..10).collect::<Vec<_>>());
+    }
+
+    /// src → limit(6), fed 100 records in batches of 4 (0 = one pass).
+    async fn run_limited(batch_size: usize, pipelined: bool, tee: bool) -> GraphRun {
+        use crate::categories::execution::LimitBlock;
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("src", Box::new(UnionBlock::new()));
+        engine.add_block("limit", Box::new(LimitBlock::new()));
+        engine.add_connection(conn("c1", "src", "merged", "limit", "records"));
+        if tee {
+            engine.add_block("tee", Box::new(UnionBlock::new()));
+            engine.add_connection(conn("c2", "src", "merged", "tee", "records"));
+        }
+        engine.set_entry_point("src");
+        let params = HashMap::from([("limit".to_string(), ParameterValue::Integer(6))]);
+        engine.initialize_block("limit", params).await.unwrap();
+        engine.set_batch_size(batch_size);
+        if pipelined {
+            engine.set_max_in_flight_batches(1);
+        }
+
+        let mut input = HashMap::new();
+        input.insert(("src".into(), "records".into()), PortValue::Stream(generate_records(100)));
+        let run = engine.execute_detailed(input).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        assert_eq!(ids(&run.results["limit"].outputs["limited"]), (0..6).collect::<Vec<_>>());
+        run
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_limit_stops_upstream_in_batched_runs() {
+        let whole = run_limited(0, false, false).await;
+        assert!(!whole.summary.metrics.short_circuited);
+        assert_eq!(whole.results["limit"].metrics["rows_discarded"], 94.0);
+        assert_eq!(whole.summary.metrics.total_operations, 106);
+
+        // The limit is satisfied in the second batch; the source then stops.
+        let batched = run_limited(4, false, false).await;
+        assert!(batched.summary.metrics.short_circuited);
+        assert_eq!(batched.results["limit"].metrics["rows_emitted"], 6.0);
+        assert_eq!(batched.results["limit"].metrics["rows_discarded"], 2.0);
+        assert_eq!(batched.summary.metrics.total_operations, 8 + 6);
+
+        // Pipelined, the source may run a batch or two ahead before it sees
+        // the limit finish, but it still stops long before the end.
+        let pipelined = run_limited(4, true, false).await;
+        assert!(pipelined.summary.metrics.short_circuited);
+        let ops = pipelined.summary.metrics.total_operations;
+        assert!(ops < 40, "source kept producing: {} operations", ops);
+    }
+
+    #[tokio::test]
+    async fn test_block_with_a_live_consumer_keeps_running() {
+        for pipelined in [false, true] {
+            let run = run_limited(4, pipelined, true).await;
+            assert_eq!(ids(&run.results["tee"].outputs["merged"]), (0..100).collect::<Vec<_>>());
+        }
+    }
+
+    // ── Parallel execution ──────────────────────────────────────────────
+
+    #[test]
+    fn test_topological_levels() {
+        let connections = vec![
+            conn("c1", "src", "merged", "b", "records"),
+            conn("c2", "src", "merged", "c", "records"),
+            conn("c3", "b", "merged", "sink", "records"),
+            conn("c4", "c", "merged", "sink", "records"),
+            conn("c5", "src", "merged", "sink", "records"),
+        ];
+        let order: Vec<String> = ["src", "c", "b", "sink"].iter().map(|s| s.to_string()).collect();
+        let levels = ExecutionEngine::topological_levels(&order, &connections);
+        assert_eq!(levels, vec![vec!["src"], vec!["c", "b"], vec!["sink"]]);
+    }
+
+    /// src → {b, c} → sink, where b and c each sleep 50 ms. Returns the run
+    /// and the (name, start, end) span of each middle block.
+    async fn run_diamond(parallel: bool) -> (GraphRun, Vec<(String, std::time::Instant, std::time::Instant)>) {
+        let spans = Spans::default();
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("src", Box::new(UnionBlock::new()));
+        engine.add_block("b", Box::new(UnionBlock::sleeping("b", 50, &spans)));
+        engine.add_block("c", Box::new(UnionBlock::sleeping("c", 50, &spans)));
+        engine.add_block("sink", Box::new(UnionBlock::new()));
+        engine.add_connection(conn("c1", "src", "merged", "b", "records"));
+        engine.add_connection(conn("c2", "src", "merged", "c", "records"));
+        engine.add_connection(conn("c3", "b", "merged", "sink", "records"));
+        engine.add_connection(conn("c4", "c", "merged", "sink", "records"));
+        engine.set_entry_point("src");
+        engine.set_parallel(parallel);
+
+        let mut input = HashMap::new();
+        input.insert(("src".into(), "records".into()), PortValue::Stream(generate_records(3)));
+        let run = engine.execute_detailed(input).await;
+        let spans = spans.lock().unwrap().clone();
+        (run, spans)
+    }
+
+    #[tokio::test]
+    async fn test_parallel_diamond_runs_middle_blocks_concurrently() {
+        let overlap = |spans: &[(String, std::time::Instant, std::time::Instant)]| {
+            let (a, b) = (&spans[0], &spans[1]);
+            a.1 < b.2 && b.1 < a.2
+        };
+
+        let (run, spans) = run_diamond(true).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        assert_eq!(spans.len(), 2);
+        assert!(overlap(&spans), "middle blocks should overlap: {:?}", spans);
+        // Fan-in order is still connection order, whichever task finished first.
+        assert_eq!(ids(&run.results["sink"].outputs["merged"]), vec![0, 1, 2, 0, 1, 2]);
+        assert_eq!(run.summary.block_metrics.len(), 4);
+
+        let (run, spans) = run_diamond(false).await;
+        assert!(run.summary.success);
+        assert!(!overlap(&spans), "sequential run should not overlap: {:?}", spans);
+    }
+
+    // ── Backpressure ────────────────────────────────────────────────────
+
+    /// src → slow → sink over 10 single-record batches, where `slow` sleeps
+    /// 5 ms per batch.
+    async fn run_slow_consumer(max_in_flight: usize) -> GraphRun {
+        let spans = Spans::default();
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("src", Box::new(UnionBlock::new()));
+        engine.add_block("slow", Box::new(UnionBlock::sleeping("slow", 5, &spans)));
+        engine.add_block("sink", Box::new(UnionBlock::new()));
+        engine.add_connection(conn("c1", "src", "merged", "slow", "records"));
+        engine.add_connection(conn("c2", "slow", "merged", "sink", "records"));
+        engine.set_entry_point("src");
+        engine.set_batch_size(1);
+        engine.set_max_in_flight_batches(max_in_flight);
+
+        let mut input = HashMap::new();
+        input.insert(("src".into(), "records".into()), PortValue::Stream(generate_records(10)));
+        engine.execute_detailed(input).await
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_stalls_producer_of_slow_block() {
+        let run = run_slow_consumer(2).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        let stalls = |id: &str| {
+            run.summary.block_metrics.iter().find(|b| b.block_id == id).unwrap().backpressure_stalls
+        };
+        assert!(stalls("src") > 0, "producer should stall behind the slow block");
+        assert_eq!(stalls("slow"), 0);
+        assert_eq!(run.summary.metrics.backpressure_stalls, stalls("src"));
+
+        // Same results as lockstep rounds.
+        assert_eq!(ids(&run.results["sink"].outputs["merged"]), (0..10).collect::<Vec<_>>());
+        assert!(matches!(&run.results["src"].outputs["merged"], PortValue::Batch(v) if v.len() == 1));
+        assert_eq!(run.summary.metrics.total_operations, 30);
+
+        let unbounded = run_slow_consumer(0).await;
+        assert_eq!(unbounded.summary.metrics.backpressure_stalls, 0);
+        assert_eq!(ids(&unbounded.results["sink"].outputs["merged"]), (0..10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_pipelined_fan_in_stays_aligned() {
+        let mut engine = ExecutionEngine::new();
+        for id in ["src", "b", "c", "sink"] {
+            engine.add_block(id, Box::new(UnionBlock::new()));
+        }
+        engine.add_connection(conn("c1", "src", "merged", "b", "records"));
+        engine.add_connection(conn("c2", "src", "merged", "c", "records"));
+        engine.add_connection(conn("c3", "b", "merged", "sink", "records"));
+        engine.add_connection(conn("c4", "c", "merged", "sink", "records"));
+        engine.set_entry_point("src");
+        engine.set_batch_size(2);
+        engine.set_max_in_flight_batches(1);
+
+        let mut input = HashMap::new();
+        input.insert(("src".into(), "records".into()), PortValue::Stream(generate_records(4)));
+        let run = engine.execute_detailed(input).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        assert_eq!(ids(&run.results["sink"].outputs["merged"]), vec![0, 1, 0, 1, 2, 3, 2, 3]);
+    }
+
+    // ── Snapshot / restore ──────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_snapshot_restore_resumes_lsm_tree() {
+        use crate::categories::storage::LSMTreeBlock;
+
+        async fn engine() -> ExecutionEngine {
+            let mut engine = ExecutionEngine::new();
+            engine.add_block("lsm", Box::new(LSMTreeBlock::new()));
+            engine.set_entry_point("lsm");
+            let params = HashMap::from([("memtable_size".to_string(), ParameterValue::Integer(50))]);
+            engine.initialize_block("lsm", params).await.unwrap();
+            engine
+        }
+
+        let mut original = engine().await;
+        let mut input = HashMap::new();
+        input.insert(("lsm".into(), "records".into()), PortValue::Stream(generate_records(120)));
+        assert!(original.execute(input).await.success);
+
+        // Round-trip through JSON, as if persisted.
+        let json = serde_json::to_string(&original.snapshot()).unwrap();
+        let snapshot: GraphSnapshot = serde_json::from_str(&json).unwrap();
+
+        let mut resumed = engine().await;
+        resumed.restore(snapshot).unwrap();
+        let state = |e: &ExecutionEngine, key: &str| e.snapshot().blocks["lsm"].data[key].clone();
+        assert_eq!(state(&resumed, "total_entries"), serde_json::json!(120));
+        assert_eq!(state(&resumed, "total_sstables"), state(&original, "total_sstables"));
+        assert_eq!(state(&resumed, "memtable"), state(&original, "memtable"));
+        assert_eq!(state(&resumed, "flush_count"), state(&original, "flush_count"));
+    }
+
+    #[tokio::test]
+    async fn test_input_schema_rejects_records_missing_key() {
+        use crate::categories::storage::LSMTreeBlock;
+
+        async fn run(key_column: &str, pipelined: bool) -> EngineExecutionResult {
+            let mut engine = ExecutionEngine::new();
+            engine.add_block("heap", Box::new(HeapFileBlock::new()));
+            engine.add_block("lsm", Box::new(LSMTreeBlock::new()));
+            engine.add_connection(conn("c1", "heap", "stored", "lsm", "records"));
+            engine.set_entry_point("heap");
+            if pipelined {
+                engine.set_batch_size(10);
+                engine.set_max_in_flight_batches(2);
+            }
+            let params = HashMap::from([("key_column".to_string(), ParameterValue::from(key_column))]);
+            engine.initialize_block("lsm", params).await.unwrap();
+            let mut input = HashMap::new();
+            input.insert(("heap".into(), "records".into()), PortValue::Stream(generate_records(30)));
+            engine.execute(input).await
+        }
+
+        assert!(run("name", false).await.success);
+        for pipelined in [false, true] {
+            let result = run("user_id", pipelined).await;
+            assert!(!result.success);
+            assert!(
+                result.errors[0].starts_with("[lsm] Fatal: Validation failed: input 'records' record 0: \
+                                              missing required column 'user_id'"),
+                "{:?}",
+                result.errors
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_block_validate_runs_against_actual_inputs() {
+        use crate::core::port::SignalValue;
+
+        async fn run(value: PortValue, pipelined: bool) -> EngineExecutionResult {
+            let mut engine = ExecutionEngine::new();
+            engine.add_block("heap", Box::new(HeapFileBlock::new()));
+            engine.set_entry_point("heap");
+            if pipelined {
+                engine.set_batch_size(10);
+                engine.set_max_in_flight_batches(2);
+            }
+            engine.execute(HashMap::from([(("heap".into(), "records".into()), value)])).await
+        }
+
+        for pipelined in [false, true] {
+            // A signal where records belong: the block's validate() rejects it.
+            let result = run(PortValue::Signal(SignalValue::Start), pipelined).await;
+            assert!(!result.success);
+            assert_eq!(
+                result.errors,
+                vec!["[heap] Fatal: Validation failed: records port expects DataStream, Batch, or Single"]
+            );
+
+            // Nothing to store is only worth a warning.
+            let result = run(PortValue::None, pipelined).await;
+            assert!(result.success, "{:?}", result.errors);
+            assert_eq!(result.warnings, vec!["[heap] No records provided — nothing to store"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_block_warnings_reported_once_across_batches() {
+        use crate::categories::distribution::ReplicationBlock;
+
+        // Every batch raises the same under-sized quorum warning.
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("repl", Box::new(ReplicationBlock::new()));
+        engine.set_entry_point("repl");
+        engine.set_batch_size(10);
+        let params = HashMap::from([
+            ("replication_mode".to_string(), ParameterValue::from("quorum")),
+            ("write_quorum".to_string(), ParameterValue::Integer(1)),
+            ("read_quorum".to_string(), ParameterValue::Integer(1)),
+        ]);
+        engine.initialize_block("repl", params).await.unwrap();
+        let input = HashMap::from([(("repl".into(), "requests".into()), PortValue::Stream(generate_records(30)))]);
+
+        let result = engine.execute(input).await;
+        assert_eq!(result.metrics.batches, 3);
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        assert!(result.warnings[0].starts_with("[repl] R + W = 2 does not exceed N = 3"));
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_unknown_block() {
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("heap", Box::new(HeapFileBlock::new()));
+        let mut snapshot = engine.snapshot();
+        snapshot.blocks.insert("ghost".into(), BlockState::new());
+        assert!(matches!(engine.restore(snapshot), Err(BlockError::StateError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_block_without_full_state() {
+        let mut engine = ExecutionEngine::new();
+        engine.add_block("heap", Box::new(HeapFileBlock::new()));
+        engine.set_entry_point("heap");
+        engine.initialize_block("heap", HashMap::new()).await.unwrap();
+        let mut input = HashMap::new();
+        input.insert(("heap".into(), "records".into()), PortValue::Stream(generate_records(10)));
+        assert!(engine.execute(input).await.success);
+
+        // The heap's state is a summary without its pages: restoring it would
+        // report ten records while holding none.
+        let snapshot = engine.snapshot();
+        let mut fresh = ExecutionEngine::new();
+        fresh.add_block("heap", Box::new(HeapFileBlock::new()));
+        let err = fresh.restore(snapshot).unwrap_err();
+        assert!(err.to_string().contains("'heap' (heap-file-storage) cannot be restored"), "{}", err);
+    }
+
+    // ── Simulated cost ──────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn test_simulated_latency_uses_cost_model() {
+        async fn run(cost_model: CostModel) -> EngineExecutionResult {
+            let mut engine = ExecutionEngine::new();
+            engine.add_block("heap", Box::new(HeapFileBlock::new()));
+            engine.set_entry_point("heap");
+            engine.initialize_block("heap", HashMap::new()).await.unwrap();
+            engine.set_cost_model(cost_model);
+
+            let mut records = generate_records(20);
+            let mut scan = Record::new();
+            scan.insert("_op".into(), "scan").unwrap();
+            records.push(scan);
+            let mut input = HashMap::new();
+            input.insert(("heap".into(), "records".into()), PortValue::Stream(records));
+            engine.execute(input).await
+        }
+
+        let free = run(CostModel::free()).await;
+        assert_eq!(free.metrics.total_simulated_latency_ms, 0.0);
+
+        let priced = run(CostModel::free().with_page_read_latency(2.0).with_cost("records_inserted", 0.5)).await;
+        let heap = &priced.block_metrics[0];
+        assert_eq!(heap.counters["records_inserted"], 20.0);
+        let expected = 2.0 * heap.counters["pages_read"] + 10.0;
+        assert!(expected > 0.0);
+        assert_eq!(heap.simulated_latency_ms, expected);
+        assert_eq!(priced.metrics.total_simulated_latency_ms, expected);
+    }
+
     // ── Validation through engine ───────────────────────────────────────
 
     #[tokio::test]
@@ -0,0 +1,235 @@
+//! Graph definitions
+//!
+//! A [`GraphDefinition`] is the serializable form of a block graph: each
+//! block's id, its type (the metadata id a [`BlockRegistry`] factory is keyed
+//! by) and the parameters it was configured with, plus the connections
+//! between them. It is the interchange format for saving and reloading
+//! designs:
+//!
+//! ```json
+//! {
+//!   "blocks": [
+//!     { "id": "lsm", "block_type": "lsm-tree-storage", "params": { "memtable_size": 500 } }
+//!   ],
+//!   "connections": []
+//! }
+//! ```
+//!
+//! [`GraphDefinition::instantiate`] turns a definition back into live,
+//! initialized blocks, ready for [`GraphValidator`] and
+//! [`ExecutionEngine::run`](super::engine::ExecutionEngine::run);
+//! [`GraphDefinition::validate`] does both steps of checking a saved design.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::block::{Block, BlockError};
+use crate::core::parameter::ParameterValue;
+use crate::core::port::Connection;
+use crate::core::registry::BlockRegistry;
+use crate::runtime::validation::{GraphValidationResult, GraphValidator};
+
+/// One block in a [`GraphDefinition`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockDefinition {
+    /// Id of the block within the graph (what connections refer to).
+    pub id: String,
+    /// Metadata id of the block's type, e.g. `"lsm-tree-storage"`.
+    pub block_type: String,
+    /// Parameters passed to `initialize`; omitted ones take their defaults.
+    #[serde(default)]
+    pub params: HashMap<String, ParameterValue>,
+}
+
+/// A serializable block graph.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphDefinition {
+    pub blocks: Vec<BlockDefinition>,
+    #[serde(default)]
+    pub connections: Vec<Connection>,
+}
+
+impl GraphDefinition {
+    /// Serialize the graph to pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("graph definitions always serialize")
+    }
+
+    /// Parse a graph from JSON.
+    pub fn from_json(json: &str) -> Result<Self, BlockError> {
+        serde_json::from_str(json)
+            .map_err(|e| BlockError::InvalidInput(format!("invalid graph JSON: {}", e)))
+    }
+
+    /// Build every block with `registry` and initialize it with its saved
+    /// parameters.
+    ///
+    /// Fails on a duplicate block id, a `block_type` the registry has no
+    /// factory for, or a block rejecting its parameters. Connections are
+    /// returned as saved; checking them is the validator's job.
+    pub async fn instantiate(
+        &self,
+        registry: &BlockRegistry,
+    ) -> Result<(HashMap<String, Box<dyn Block>>, Vec<Connection>), BlockError> {
+        let mut seen = HashSet::new();
+        if let Some(dup) = self.blocks.iter().find(|b| !seen.insert(b.id.as_str())) {
+            return Err(BlockError::InitializationError(format!(
+                "duplicate block id '{}'",
+                dup.id
+            )));
+        }
+
+        let mut blocks = HashMap::new();
+        for def in &self.blocks {
+            let mut block = registry.create(&def.block_type).ok_or_else(|| {
+                BlockError::InitializationError(format!(
+                    "block '{}' has unknown type '{}'",
+                    def.id, def.block_type
+                ))
+            })?;
+            block.initialize(def.params.clone()).await.map_err(|e| {
+                BlockError::InitializationError(format!("initializing '{}': {}", def.id, e))
+            })?;
+            blocks.insert(def.id.clone(), block);
+        }
+
+        Ok((blocks, self.connections.clone()))
+    }
+
+    /// Instantiate the graph and run [`GraphValidator::validate_with_params`]
+    /// on it, with every block that no connection feeds as an entry point
+    /// (as the engine's auto-detection picks them).
+    ///
+    /// A graph that fails to instantiate is reported as invalid with that
+    /// error, rather than as an `Err`.
+    pub async fn validate(&self, registry: &BlockRegistry) -> GraphValidationResult {
+        let (blocks, connections) = match self.instantiate(registry).await {
+            Ok(graph) => graph,
+            Err(e) => return GraphValidationResult::failed(e.to_string()),
+        };
+        let fed: HashSet<&str> = connections.iter().map(|c| c.target_block_id.as_str()).collect();
+        let entry_points: Vec<&str> = self
+            .blocks
+            .iter()
+            .map(|b| b.id.as_str())
+            .filter(|id| !fed.contains(id))
+            .collect();
+        let params = self.blocks.iter().map(|b| (b.id.clone(), b.params.clone())).collect();
+        GraphValidator::validate_with_params(&blocks, &connections, &entry_points, &params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::port::PortValue;
+    use crate::core::port::Record;
+    use crate::runtime::engine::ExecutionEngine;
+
+    fn lsm_into_btree() -> GraphDefinition {
+        let mut params = HashMap::new();
+        params.insert("memtable_size".to_string(), ParameterValue::Integer(500));
+        GraphDefinition {
+            blocks: vec![
+                BlockDefinition {
+                    id: "heap".into(),
+                    block_type: "heap-file-storage".into(),
+                    params: HashMap::new(),
+                },
+                BlockDefinition {
+                    id: "btree".into(),
+                    block_type: "btree-index".into(),
+                    params: HashMap::new(),
+                },
+                BlockDefinition {
+                    id: "lsm".into(),
+                    block_type: "lsm-tree-storage".into(),
+                    params,
+                },
+            ],
+            connections: vec![Connection::new(
+                "c1".into(),
+                "heap".into(),
+                "stored".into(),
+                "btree".into(),
+                "records".into(),
+            )],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_json_round_trip_instantiates_configured_blocks() {
+        let graph = GraphDefinition::from_json(&lsm_into_btree().to_json()).unwrap();
+        assert_eq!(graph.blocks.len(), 3);
+        assert_eq!(graph.blocks[2].params["memtable_size"].as_integer(), Some(500));
+        assert_eq!(graph.connections[0].target_block_id, "btree");
+
+        let registry = BlockRegistry::with_builtin_blocks();
+        let (blocks, connections) = graph.instantiate(&registry).await.unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(connections.len(), 1);
+        assert_eq!(blocks["lsm"].metadata().id, "lsm-tree-storage");
+        // The saved parameter survived the trip through JSON.
+        assert_eq!(blocks["lsm"].get_state().get::<usize>("memtable_size").unwrap(), Some(500));
+
+        let records: Vec<Record> = (0..20)
+            .map(|i| {
+                let mut r = Record::new();
+                r.insert("id".into(), i as i64).unwrap();
+                r
+            })
+            .collect();
+        let mut input = HashMap::new();
+        input.insert(("heap".into(), "records".into()), PortValue::Stream(records));
+        input.insert(("lsm".into(), "records".into()), PortValue::Stream(Vec::new()));
+
+        let run = ExecutionEngine::run(blocks, connections, input).await;
+        assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+        assert_eq!(run.results["heap"].outputs["stored"].len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_instantiate_rejects_bad_definitions() {
+        let registry = BlockRegistry::with_builtin_blocks();
+
+        let mut unknown = lsm_into_btree();
+        unknown.blocks[0].block_type = "no-such-block".into();
+        let err = unknown.instantiate(&registry).await.err().unwrap();
+        assert!(err.to_string().contains("no-such-block"));
+
+        let mut duplicate = lsm_into_btree();
+        duplicate.blocks[1].id = "heap".into();
+        assert!(duplicate.instantiate(&registry).await.is_err());
+
+        let mut bad_param = lsm_into_btree();
+        bad_param.blocks[2]
+            .params
+            .insert("memtable_size".into(), ParameterValue::Integer(1));
+        let err = bad_param.instantiate(&registry).await.err().unwrap();
+        assert!(err.to_string().contains("'lsm'"));
+
+        assert!(GraphDefinition::from_json("{\"blocks\": 3}").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validate_reports_graph_and_instantiation_problems() {
+        let registry = BlockRegistry::with_builtin_blocks();
+
+        let result = lsm_into_btree().validate(&registry).await;
+        assert!(result.valid, "Errors: {:?}", result.errors);
+
+        let mut self_loop = lsm_into_btree();
+        self_loop.connections[0].target_block_id = "heap".into();
+        let result = self_loop.validate(&registry).await;
+        assert!(!result.valid);
+        assert!(result.errors.iter().any(|e| e.message.contains("back into its own input")));
+
+        let mut unknown = lsm_into_btree();
+        unknown.blocks[0].block_type = "no-such-block".into();
+        let result = unknown.validate(&registry).await;
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].message.contains("no-such-block"));
+    }
+}
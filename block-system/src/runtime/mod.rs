@@ -3,7 +3,9 @@
 //! This module provides the runtime system for executing blocks and managing
 //! the data flow between blocks in a pipeline.
 
+pub mod cost;
 pub mod engine;
+pub mod graph;
 pub mod timer;
 pub mod validation;
 pub mod workload;
@@ -37,6 +39,18 @@ pub fn get_block(&self, id: BlockId) -> Option<&(dyn crate::core::Block)> {
         self.blocks.get(&id).map(|b| b.as_ref())
     }
 
+    /// Get a block by ID as its concrete type
+    ///
+    /// Returns `None` if no block has this ID or the block is not a `T`.
+    pub fn get_block_as<T: 'static>(&self, id: BlockId) -> Option<&T> {
+        self.get_block(id)?.as_any().downcast_ref::<T>()
+    }
+
+    /// Get a block by ID as its concrete type, mutably
+    pub fn get_block_as_mut<T: 'static>(&mut self, id: BlockId) -> Option<&mut T> {
+        self.blocks.get_mut(&id)?.as_mut().as_any_mut().downcast_mut::<T>()
+    }
+
     /// Get the number of registered blocks
     pub fn block_count(&self) -> usize {
         self.blocks.len()
@@ -1,12 +1,16 @@
 //! Graph validation engine
 //!
 //! Validates a block graph before execution: cycle detection, port connectivity,
-//! type compatibility, and parameter constraint checking. Produces a
-//! `GraphValidationResult` that mirrors the frontend's `ValidationResult`.
+//! type compatibility, parameter constraint checking, guarantees a block
+//! requires of the blocks feeding it, and guarantee strength along the graph.
+//! Produces a `GraphValidationResult` that mirrors the frontend's
+//! `ValidationResult`.
 
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use crate::core::block::Block;
+use crate::core::constraint::{ConstraintType, GuaranteeLevel, GuaranteeType};
+use crate::core::parameter::ParameterValue;
 use crate::core::port::{Connection, Port, PortDirection, PortType};
 
 // ── Result types ────────────────────────────────────────────────────────────
@@ -47,6 +51,14 @@ fn ok() -> Self {
         }
     }
 
+    /// An invalid result with a single graph-wide error — for when the graph
+    /// could not even be built to check it.
+    pub fn failed(message: impl Into<String>) -> Self {
+        let mut result = Self::ok();
+        result.add_error(None, message, None);
+        result
+    }
+
     fn add_error(&mut self, node_id: Option<&str>, message: impl Into<String>, suggestion: Option<&str>) {
         self.valid = false;
         self.errors.push(ValidationError {
@@ -65,7 +77,7 @@ fn add_warning(&mut self, node_id: Option<&str>, message: impl Into<String>, sug
     }
 
     /// Merge another result into this one.
-    fn merge(&mut self, other: GraphValidationResult) {
+    pub fn merge(&mut self, other: GraphValidationResult) {
         if !other.valid {
             self.valid = false;
         }
@@ -93,18 +105,68 @@ pub fn validate(
         let mut result = GraphValidationResult::ok();
 
         result.merge(Self::check_referenced_blocks_exist(blocks, connections));
+        result.merge(Self::check_self_loops(connections));
         result.merge(Self::check_duplicate_connections(connections));
         result.merge(Self::check_port_existence(blocks, connections));
         result.merge(Self::check_port_directions(blocks, connections));
         result.merge(Self::check_port_type_compatibility(blocks, connections));
         result.merge(Self::check_required_inputs_connected(blocks, connections, entry_points));
         result.merge(Self::check_multiple_connections(blocks, connections));
+        result.merge(Self::check_required_guarantees(blocks, connections));
+        result.merge(Self::check_guarantee_strength(blocks, connections));
         result.merge(Self::check_cycles(blocks, connections));
         result.merge(Self::check_disconnected_blocks(blocks, connections));
+        result.merge(Self::check_reachable_from_entry_points(blocks, connections, entry_points));
 
         result
     }
 
+    /// Run every check in [`validate`](Self::validate), plus a check that
+    /// every required parameter has a value.
+    ///
+    /// `params` holds the parameters that will be passed to each block's
+    /// `initialize`, keyed by block id; blocks without an entry get their
+    /// defaults.
+    pub fn validate_with_params(
+        blocks: &HashMap<String, Box<dyn Block>>,
+        connections: &[Connection],
+        entry_points: &[&str],
+        params: &HashMap<String, HashMap<String, ParameterValue>>,
+    ) -> GraphValidationResult {
+        let mut result = Self::validate(blocks, connections, entry_points);
+        result.merge(Self::check_required_parameters(blocks, params));
+        result
+    }
+
+    /// Every required parameter must end up with a value: supplied, or its
+    /// default when not supplied. Catches what would otherwise fail at
+    /// `initialize`.
+    pub fn check_required_parameters(
+        blocks: &HashMap<String, Box<dyn Block>>,
+        params: &HashMap<String, HashMap<String, ParameterValue>>,
+    ) -> GraphValidationResult {
+        let mut result = GraphValidationResult::ok();
+        for (block_id, block) in blocks {
+            let supplied = params.get(block_id);
+            for param in block.parameters().iter().filter(|p| p.required) {
+                let value = supplied
+                    .and_then(|values| values.get(&param.id))
+                    .unwrap_or(&param.default_value);
+                if matches!(value, ParameterValue::Null) {
+                    result.add_error(
+                        Some(block_id),
+                        format!(
+                            "Required parameter '{}' on block '{}' has no value and no default",
+                            param.id, block_id
+                        ),
+                        Some(&format!("Set '{}' to a {:?} value", param.id, param.param_type)),
+                    );
+                }
+            }
+        }
+        result
+    }
+
     // ── Individual checks ───────────────────────────────────────────────
 
     /// Every block_id referenced in a connection must exist.
@@ -338,6 +400,134 @@ fn check_multiple_connections(
         result
     }
 
+    /// A block that requires a guarantee of its inputs (e.g. a merge join
+    /// needing sorted order) must be fed only by blocks that provide it.
+    fn check_required_guarantees(
+        blocks: &HashMap<String, Box<dyn Block>>,
+        connections: &[Connection],
+    ) -> GraphValidationResult {
+        let mut result = GraphValidationResult::ok();
+        for conn in connections {
+            let (Some(src), Some(tgt)) = (
+                blocks.get(&conn.source_block_id),
+                blocks.get(&conn.target_block_id),
+            ) else {
+                continue;
+            };
+            for constraint in tgt.requires() {
+                let ConstraintType::RequiresGuarantee(required) = constraint.constraint_type else {
+                    continue;
+                };
+                if src.guarantees().iter().any(|g| g.guarantee_type == required) {
+                    continue;
+                }
+                result.add_error(
+                    Some(&conn.target_block_id),
+                    format!(
+                        "Block '{}' requires {:?} on its input ({}), but '{}' feeding port '{}' does not guarantee it",
+                        conn.target_block_id, required, constraint.description,
+                        conn.source_block_id, conn.target_port_id
+                    ),
+                    Some(Self::guarantee_suggestion(required)),
+                );
+            }
+        }
+        result
+    }
+
+    /// A block that requires a strict guarantee somewhere upstream (e.g.
+    /// replication needing strict durability) gets a warning when its
+    /// ancestors provide it only best-effort. Ancestors that provide nothing
+    /// are not flagged: the data may come from outside the graph.
+    fn check_guarantee_strength(
+        blocks: &HashMap<String, Box<dyn Block>>,
+        connections: &[Connection],
+    ) -> GraphValidationResult {
+        let mut result = GraphValidationResult::ok();
+
+        let mut sources_of: HashMap<&str, Vec<&str>> = HashMap::new();
+        for conn in connections {
+            sources_of
+                .entry(conn.target_block_id.as_str())
+                .or_default()
+                .push(conn.source_block_id.as_str());
+        }
+
+        for (block_id, block) in blocks {
+            for constraint in block.requires() {
+                let ConstraintType::RequiresStrictGuarantee(required) = constraint.constraint_type else {
+                    continue;
+                };
+
+                // Walk every ancestor, noting who provides the guarantee and how strongly.
+                let mut strict = false;
+                let mut best_effort: Vec<&str> = Vec::new();
+                let mut visited: HashSet<&str> = HashSet::from([block_id.as_str()]);
+                let mut queue: VecDeque<&str> = VecDeque::from([block_id.as_str()]);
+                while let Some(id) = queue.pop_front() {
+                    for &src in sources_of.get(id).into_iter().flatten() {
+                        if !visited.insert(src) {
+                            continue;
+                        }
+                        queue.push_back(src);
+                        let level = blocks.get(src).and_then(|b| {
+                            b.guarantees().iter().find(|g| g.guarantee_type == required).map(|g| g.level)
+                        });
+                        match level {
+                            Some(GuaranteeLevel::Strict) => strict = true,
+                            Some(GuaranteeLevel::BestEffort) => best_effort.push(src),
+                            None => {}
+                        }
+                    }
+                }
+
+                if !strict && !best_effort.is_empty() {
+                    best_effort.sort_unstable();
+                    result.add_warning(
+                        Some(block_id),
+                        format!(
+                            "Block '{}' requires a strict {:?} guarantee ({}), but upstream only '{}' provide{} it best-effort",
+                            block_id, required, constraint.description,
+                            best_effort.join("', '"),
+                            if best_effort.len() == 1 { "s" } else { "" },
+                        ),
+                        Some(Self::guarantee_suggestion(required)),
+                    );
+                }
+            }
+        }
+        result
+    }
+
+    /// How to fix a missing guarantee on an input.
+    fn guarantee_suggestion(required: GuaranteeType) -> &'static str {
+        match required {
+            GuaranteeType::SortedOrder => "Insert an ascending Sort block before this input",
+            GuaranteeType::Durability => {
+                "Insert a WAL block upstream: best-effort storage keeps data only for the \
+                 lifetime of the process, while a write-ahead log makes it survive crashes"
+            }
+            _ => "Feed this input from a block that provides the guarantee",
+        }
+    }
+
+    /// A block may not feed its own output back into its input. Reported here
+    /// rather than as a one-block cycle, which `check_cycles` skips.
+    fn check_self_loops(connections: &[Connection]) -> GraphValidationResult {
+        let mut result = GraphValidationResult::ok();
+        for conn in connections.iter().filter(|c| c.source_block_id == c.target_block_id) {
+            result.add_error(
+                Some(&conn.source_block_id),
+                format!(
+                    "Connection '{}' feeds block '{}' output '{}' back into its own input '{}'",
+                    conn.id, conn.source_block_id, conn.source_port_id, conn.target_port_id
+                ),
+                Some("Remove this connection; a block cannot consume its own output"),
+            );
+        }
+        result
+    }
+
     /// Cycle detection using Kahn's algorithm (topological sort).
     /// If we can't sort all nodes, the graph has a cycle.
     fn check_cycles(
@@ -356,9 +546,11 @@ fn check_cycles(
         }
 
         for conn in connections {
-            // Only count edges between blocks that exist.
+            // Only count edges between blocks that exist; self-loops are
+            // reported by `check_self_loops`.
             if blocks.contains_key(&conn.source_block_id)
                 && blocks.contains_key(&conn.target_block_id)
+                && conn.source_block_id != conn.target_block_id
             {
                 adj.entry(conn.source_block_id.as_str())
                     .or_default()
@@ -390,26 +582,66 @@ fn check_cycles(
         }
 
         if visited < blocks.len() {
-            // Find blocks that are part of the cycle (in_degree > 0).
-            let cycle_blocks: Vec<&str> = in_degree
+            // What Kahn's algorithm could not sort is every cycle plus
+            // everything downstream of one; a DFS over it finds the cycles.
+            let mut remaining: Vec<&str> = in_degree
                 .iter()
                 .filter(|(_, &deg)| deg > 0)
                 .map(|(&id, _)| id)
                 .collect();
+            remaining.sort_unstable();
+            for neighbors in adj.values_mut() {
+                neighbors.sort_unstable();
+            }
 
-            result.add_error(
-                None,
-                format!(
-                    "Graph contains a cycle involving blocks: [{}]",
-                    cycle_blocks.join(", ")
-                ),
-                Some("Remove connections to break the cycle"),
-            );
+            for cycle in Self::find_cycles(&adj, &remaining) {
+                result.add_error(
+                    Some(cycle[0]),
+                    format!("Graph contains a cycle: {}", cycle.join(" -> ")),
+                    Some("Remove one of the connections along this path to break the cycle"),
+                );
+            }
         }
 
         result
     }
 
+    /// Depth-first search from each of `starts`, returning one closed path
+    /// (`[a, b, c, a]`) per back edge found. Each node is expanded once, so
+    /// every cycle-closing connection is reported exactly once.
+    fn find_cycles<'a>(adj: &HashMap<&'a str, Vec<&'a str>>, starts: &[&'a str]) -> Vec<Vec<&'a str>> {
+        fn visit<'a>(
+            node: &'a str,
+            adj: &HashMap<&'a str, Vec<&'a str>>,
+            stack: &mut Vec<&'a str>,
+            done: &mut HashSet<&'a str>,
+            cycles: &mut Vec<Vec<&'a str>>,
+        ) {
+            stack.push(node);
+            for &next in adj.get(node).into_iter().flatten() {
+                if let Some(pos) = stack.iter().position(|&n| n == next) {
+                    let mut cycle = stack[pos..].to_vec();
+                    cycle.push(next);
+                    cycles.push(cycle);
+                } else if !done.contains(next) {
+                    visit(next, adj, stack, done, cycles);
+                }
+            }
+            stack.pop();
+            done.insert(node);
+        }
+
+        let mut cycles = Vec::new();
+        let mut done = HashSet::new();
+        let mut stack = Vec::new();
+        for &start in starts {
+            if !done.contains(start) {
+                visit(start, adj, &mut stack, &mut done, &mut cycles);
+            }
+        }
+        cycles
+    }
+
     /// Warn about blocks that have no connections at all.
     fn check_disconnected_blocks(
         blocks: &HashMap<String, Box<dyn Block>>,
@@ -435,8 +667,108 @@ fn check_disconnected_blocks(
         result
     }
 
+    /// Warn about connected blocks that no data can reach from the entry
+    /// points (see [`reachable`](Self::reachable)). Blocks with no
+    /// connections at all are left to `check_disconnected_blocks`; without
+    /// entry points there is nothing to measure reachability from.
+    fn check_reachable_from_entry_points(
+        blocks: &HashMap<String, Box<dyn Block>>,
+        connections: &[Connection],
+        entry_points: &[&str],
+    ) -> GraphValidationResult {
+        let mut result = GraphValidationResult::ok();
+        if entry_points.is_empty() {
+            return result;
+        }
+
+        let reached = Self::reachable(blocks, connections, entry_points);
+
+        let mut unreachable: Vec<&str> = connections
+            .iter()
+            .flat_map(|c| [c.source_block_id.as_str(), c.target_block_id.as_str()])
+            .filter(|id| blocks.contains_key(*id) && !reached.contains(id))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        if !unreachable.is_empty() {
+            unreachable.sort_unstable();
+            result.add_warning(
+                None,
+                format!(
+                    "Blocks not reachable from any entry point will never receive data: [{}]",
+                    unreachable.join(", ")
+                ),
+                Some("Connect these blocks to a path from an entry point, or mark one of them as an entry point"),
+            );
+        }
+        result
+    }
+
+    /// Warn when no block reachable from the entry points is a sink — a
+    /// block with outputs, none of them connected onward — so the pipeline
+    /// produces no observable result (everything is consumed mid-graph, or
+    /// the data only loops).
+    ///
+    /// Not run by [`validate`](Self::validate), since a graph studied only
+    /// through its blocks' metrics needs no sink. Callers that want the hint
+    /// merge it in themselves.
+    pub fn check_reachable_sink(
+        blocks: &HashMap<String, Box<dyn Block>>,
+        connections: &[Connection],
+        entry_points: &[&str],
+    ) -> GraphValidationResult {
+        let mut result = GraphValidationResult::ok();
+        if entry_points.is_empty() {
+            return result;
+        }
+
+        let feeds_onward: HashSet<&str> = connections.iter().map(|c| c.source_block_id.as_str()).collect();
+        let has_sink = Self::reachable(blocks, connections, entry_points).into_iter().any(|id| {
+            !feeds_onward.contains(id) && blocks.get(id).is_some_and(|b| !b.outputs().is_empty())
+        });
+        if !has_sink {
+            result.add_warning(
+                None,
+                "No block reachable from the entry points has an unconnected output, so the pipeline produces no observable result",
+                Some("End the pipeline with a block whose output is left unconnected, or break the loop that consumes it"),
+            );
+        }
+        result
+    }
+
     // ── Helpers ─────────────────────────────────────────────────────────
 
+    /// Blocks that data can reach: a BFS over the connections from the entry
+    /// points and from blocks with no input ports, which produce data on
+    /// their own.
+    fn reachable<'a>(
+        blocks: &'a HashMap<String, Box<dyn Block>>,
+        connections: &'a [Connection],
+        entry_points: &[&'a str],
+    ) -> HashSet<&'a str> {
+        let mut adj: HashMap<&str, Vec<&str>> = HashMap::new();
+        for conn in connections {
+            adj.entry(conn.source_block_id.as_str())
+                .or_default()
+                .push(conn.target_block_id.as_str());
+        }
+
+        let mut reached: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&str> = entry_points
+            .iter()
+            .copied()
+            .chain(blocks.iter().filter(|(_, b)| b.inputs().is_empty()).map(|(id, _)| id.as_str()))
+            .filter(|id| blocks.contains_key(*id))
+            .collect();
+        while let Some(id) = queue.pop_front() {
+            if !reached.insert(id) {
+                continue;
+            }
+            queue.extend(adj.get(id).into_iter().flatten().copied());
+        }
+        reached
+    }
+
     /// Find a port definition by id across both inputs and outputs.
     fn find_port<'a>(block: &'a dyn Block, port_id: &str) -> Option<&'a Port> {
         block
@@ -521,6 +853,10 @@ mod tests {
     use crate::categories::storage::HeapFileBlock;
     use crate::categories::index::BTreeIndexBlock;
     use crate::categories::buffer::LRUBufferBlock;
+    use crate::categories::distribution::ReplicationBlock;
+    use crate::categories::execution::{MergeJoinBlock, SequentialScanBlock, SortBlock};
+    use crate::categories::transaction::WALBlock;
+    use crate::core::parameter::ParameterValue;
     use crate::core::port::Connection;
 
     /// Helper: create a HashMap of blocks from (id, block) pairs.
@@ -656,6 +992,53 @@ fn test_three_node_cycle() {
         let result = GraphValidator::validate(&blocks, &connections, &["a", "b", "c"]);
         assert!(!result.valid);
         assert!(result.errors.iter().any(|e| e.message.contains("cycle")));
+        assert!(
+            result.errors.iter().any(|e| e.message == "Graph contains a cycle: a -> b -> c -> a"),
+            "Errors: {:?}",
+            result.errors
+        );
+    }
+
+    #[test]
+    fn test_each_cycle_reported_with_its_path() {
+        // Two separate cycles, plus "d" downstream of the first one (not in a cycle).
+        let blocks = make_blocks(vec![
+            ("a", Box::new(HeapFileBlock::new())),
+            ("b", Box::new(HeapFileBlock::new())),
+            ("d", Box::new(HeapFileBlock::new())),
+            ("x", Box::new(HeapFileBlock::new())),
+            ("y", Box::new(HeapFileBlock::new())),
+        ]);
+        let connections = vec![
+            conn("c1", "a", "stored", "b", "records"),
+            conn("c2", "b", "stored", "a", "records"),
+            conn("c3", "b", "stored", "d", "records"),
+            conn("c4", "x", "stored", "y", "records"),
+            conn("c5", "y", "stored", "x", "records"),
+        ];
+
+        let result = GraphValidator::validate(&blocks, &connections, &["a", "x"]);
+        let cycles: Vec<&str> = result
+            .errors
+            .iter()
+            .filter_map(|e| e.message.strip_prefix("Graph contains a cycle: "))
+            .collect();
+        assert_eq!(cycles, vec!["a -> b -> a", "x -> y -> x"]);
+    }
+
+    #[test]
+    fn test_self_loop_reported_without_a_cycle_error() {
+        let blocks = make_blocks(vec![("a", Box::new(HeapFileBlock::new()))]);
+        let connections = vec![conn("c1", "a", "stored", "a", "records")];
+
+        let result = GraphValidator::validate(&blocks, &connections, &["a"]);
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 1, "Errors: {:?}", result.errors);
+        assert_eq!(
+            result.errors[0].message,
+            "Connection 'c1' feeds block 'a' output 'stored' back into its own input 'records'"
+        );
+        assert_eq!(result.errors[0].node_id.as_deref(), Some("a"));
     }
 
     // ── Duplicate connections ───────────────────────────────────────────
@@ -694,6 +1077,100 @@ fn test_disconnected_block_warning() {
         assert!(result.warnings.iter().any(|w| w.message.contains("orphan")));
     }
 
+    #[test]
+    fn test_connected_island_unreachable_from_entry_points() {
+        // buf → idx is wired up, but nothing connects it to the entry point.
+        let blocks = make_blocks(vec![
+            ("heap", Box::new(HeapFileBlock::new())),
+            ("btree", Box::new(BTreeIndexBlock::new())),
+            ("buf", Box::new(LRUBufferBlock::new())),
+            ("idx", Box::new(BTreeIndexBlock::new())),
+        ]);
+        let connections = vec![
+            conn("c1", "heap", "stored", "btree", "records"),
+            conn("c2", "buf", "pages", "idx", "records"),
+        ];
+
+        let result = GraphValidator::validate(&blocks, &connections, &["heap"]);
+        let warning = result
+            .warnings
+            .iter()
+            .find(|w| w.message.contains("not reachable"))
+            .expect("reachability warning");
+        assert!(warning.message.ends_with("[buf, idx]"), "{}", warning.message);
+        assert!(!result.warnings.iter().any(|w| w.message.contains("not connected")));
+    }
+
+    #[test]
+    fn test_everything_downstream_of_an_entry_point_is_reachable() {
+        let blocks = make_blocks(vec![
+            ("heap", Box::new(HeapFileBlock::new())),
+            ("btree", Box::new(BTreeIndexBlock::new())),
+        ]);
+        let connections = vec![conn("c1", "heap", "stored", "btree", "records")];
+
+        let result = GraphValidator::validate(&blocks, &connections, &["heap"]);
+        assert!(!result.warnings.iter().any(|w| w.message.contains("not reachable")));
+    }
+
+    // ── Required parameters ─────────────────────────────────────────────
+
+    #[test]
+    fn test_required_parameter_set_to_null_is_an_error() {
+        let blocks = make_blocks(vec![("btree", Box::new(BTreeIndexBlock::new()))]);
+        let params = HashMap::from([(
+            "btree".to_string(),
+            HashMap::from([("key_column".to_string(), ParameterValue::Null)]),
+        )]);
+
+        let result = GraphValidator::validate_with_params(&blocks, &[], &["btree"], &params);
+        assert!(!result.valid);
+        let error = result.errors.iter().find(|e| e.message.contains("'key_column'")).expect("missing parameter error");
+        assert_eq!(error.node_id.as_deref(), Some("btree"));
+        assert_eq!(error.suggestion.as_deref(), Some("Set 'key_column' to a String value"));
+    }
+
+    #[test]
+    fn test_unsupplied_required_parameters_fall_back_to_defaults() {
+        let blocks = make_blocks(vec![("btree", Box::new(BTreeIndexBlock::new()))]);
+        let result = GraphValidator::validate_with_params(&blocks, &[], &["btree"], &HashMap::new());
+        assert!(result.valid, "Errors: {:?}", result.errors);
+    }
+
+    // ── Reachable sink ──────────────────────────────────────────────────
+
+    #[test]
+    fn test_pipeline_with_unconnected_output_has_a_sink() {
+        let blocks = make_blocks(vec![
+            ("heap", Box::new(HeapFileBlock::new())),
+            ("btree", Box::new(BTreeIndexBlock::new())),
+        ]);
+        let connections = vec![conn("c1", "heap", "stored", "btree", "records")];
+
+        let result = GraphValidator::check_reachable_sink(&blocks, &connections, &["heap"]);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn test_loop_with_no_terminal_output_warns() {
+        // heap → a → b → a: every reachable output feeds another block.
+        let blocks = make_blocks(vec![
+            ("heap", Box::new(HeapFileBlock::new())),
+            ("a", Box::new(HeapFileBlock::new())),
+            ("b", Box::new(HeapFileBlock::new())),
+        ]);
+        let connections = vec![
+            conn("c1", "heap", "stored", "a", "records"),
+            conn("c2", "a", "stored", "b", "records"),
+            conn("c3", "b", "stored", "a", "records"),
+        ];
+
+        let result = GraphValidator::check_reachable_sink(&blocks, &connections, &["heap"]);
+        assert!(result.valid);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("no observable result"));
+    }
+
     // ── Topological sort ────────────────────────────────────────────────
 
     #[test]
@@ -783,6 +1260,98 @@ fn test_single_block_no_required_inputs() {
         assert!(result.warnings.is_empty());
     }
 
+    // ── Required guarantees ─────────────────────────────────────────────
+
+    fn merge_join_fed_by(left: Box<dyn Block>, right: Box<dyn Block>, port: &str) -> GraphValidationResult {
+        let blocks = make_blocks(vec![
+            ("left", left),
+            ("right", right),
+            ("join", Box::new(MergeJoinBlock::new())),
+        ]);
+        let connections = vec![
+            conn("c1", "left", port, "join", "left"),
+            conn("c2", "right", port, "join", "right"),
+        ];
+        GraphValidator::validate(&blocks, &connections, &["left", "right"])
+    }
+
+    #[test]
+    fn test_merge_join_fed_by_sorts_is_valid() {
+        let result = merge_join_fed_by(Box::new(SortBlock::new()), Box::new(SortBlock::new()), "sorted");
+        assert!(result.valid, "Errors: {:?}", result.errors);
+    }
+
+    #[test]
+    fn test_merge_join_fed_by_unsorted_scans_is_an_error() {
+        let result = merge_join_fed_by(
+            Box::new(SequentialScanBlock::new()),
+            Box::new(SequentialScanBlock::new()),
+            "results",
+        );
+        assert!(!result.valid);
+        let missing: Vec<_> = result.errors.iter().filter(|e| e.message.contains("SortedOrder")).collect();
+        assert_eq!(missing.len(), 2, "one error per unsorted input: {:?}", result.errors);
+        assert_eq!(missing[0].node_id.as_deref(), Some("join"));
+        assert!(missing[0].suggestion.as_deref().unwrap().contains("Sort block"));
+    }
+
+    #[tokio::test]
+    async fn test_descending_sort_does_not_satisfy_merge_join() {
+        let mut descending = SortBlock::new();
+        descending
+            .initialize(HashMap::from([("descending".to_string(), ParameterValue::Boolean(true))]))
+            .await
+            .unwrap();
+        let result = merge_join_fed_by(Box::new(SortBlock::new()), Box::new(descending), "sorted");
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), 1, "only the descending input: {:?}", result.errors);
+        assert!(result.errors[0].message.contains("'right' feeding port 'right'"));
+    }
+
+    // ── Guarantee strength ──────────────────────────────────────────────
+
+    #[test]
+    fn test_best_effort_durability_upstream_of_replication_warns() {
+        let blocks = make_blocks(vec![
+            ("heap", Box::new(HeapFileBlock::new())),
+            ("repl", Box::new(ReplicationBlock::new())),
+        ]);
+        let connections = vec![conn("c1", "heap", "stored", "repl", "requests")];
+
+        let result = GraphValidator::validate(&blocks, &connections, &["heap"]);
+        assert!(result.valid, "a warning, not an error: {:?}", result.errors);
+        let warning = result.warnings.iter().find(|w| w.message.contains("strict Durability")).expect("strength warning");
+        assert_eq!(warning.node_id.as_deref(), Some("repl"));
+        assert!(warning.message.contains("'heap'"));
+        assert!(warning.suggestion.as_deref().unwrap().contains("WAL"));
+    }
+
+    #[test]
+    fn test_strict_durability_anywhere_upstream_satisfies_replication() {
+        // heap → wal → btree → repl: the WAL is two hops away, and still counts.
+        let blocks = make_blocks(vec![
+            ("heap", Box::new(HeapFileBlock::new())),
+            ("wal", Box::new(WALBlock::new())),
+            ("btree", Box::new(BTreeIndexBlock::new())),
+            ("repl", Box::new(ReplicationBlock::new())),
+        ]);
+        let connections = vec![
+            conn("c1", "heap", "stored", "wal", "records"),
+            conn("c2", "wal", "logged", "btree", "records"),
+            conn("c3", "btree", "lookup_results", "repl", "requests"),
+        ];
+
+        let result = GraphValidator::validate(&blocks, &connections, &["heap"]);
+        assert!(!result.warnings.iter().any(|w| w.message.contains("strict Durability")), "{:?}", result.warnings);
+    }
+
+    #[test]
+    fn test_replication_without_upstream_storage_is_not_flagged() {
+        let blocks = make_blocks(vec![("repl", Box::new(ReplicationBlock::new()))]);
+        let result = GraphValidator::validate(&blocks, &[], &["repl"]);
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+    }
+
     #[test]
     fn test_empty_graph_is_valid() {
         let blocks: HashMap<String, Box<dyn Block>> = HashMap::new();
@@ -1,13 +1,33 @@
 //! Workload generator
 //!
 //! Generates streams of database operations (INSERT, SELECT, UPDATE, DELETE)
-//! with configurable weights and key distributions (uniform, zipfian, latest).
-//! Produces `Vec<Record>` that can feed into entry-point blocks.
+//! with configurable weights and key distributions (uniform, zipfian, latest,
+//! hotspot). Produces `Vec<Record>` that can feed into entry-point blocks.
+//!
+//! Skewed distributions are what make caching and versioning blocks
+//! interesting: under a Zipfian or hotspot workload a small buffer pool
+//! serves most requests from memory, where a uniform workload of the same
+//! size mostly misses. Each record carries its key as `key` and `_page_id`,
+//! so it can drive a buffer pool directly.
+//!
+//! The `Sequential` distribution is a page access pattern rather than a key
+//! distribution: a scan walking pages in order, optionally mixed with a hot
+//! working set, to show scan pollution on LRU against the scan resistance of
+//! 2Q and LRU-K.
+//!
+//! An [`ArrivalModel`] stamps each record with a simulated arrival time
+//! (`_arrival_ms`), from a Poisson process or at a fixed interval; the engine
+//! turns those into queueing delay under its cost model.
+//!
+//! Instead of per-operation weights, an [`OperationMix`] describes the
+//! workload as a read/write ratio plus a delete ratio, and tags each record
+//! with `_op` so storage, MVCC and locking blocks see an OLTP trace rather
+//! than a stream of inserts.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::core::port::Record;
+use crate::core::port::{PortValue, Record};
 
 // ── Configuration types ─────────────────────────────────────────────────────
 
@@ -20,8 +40,15 @@ pub struct WorkloadConfig {
     pub distribution: Distribution,
     /// Total number of operations to generate.
     pub total_ops: usize,
-    /// Random seed for reproducibility (0 = non-deterministic).
+    /// Random seed. The same seed and config always generate the same
+    /// workload, so runs of different designs see identical input.
     pub seed: u64,
+    /// Ratio-driven operation mix; replaces `operations` when set.
+    #[serde(default)]
+    pub mix: Option<OperationMix>,
+    /// Arrival times for the operations; untimed when `None`.
+    #[serde(default)]
+    pub arrival: Option<ArrivalModel>,
 }
 
 impl Default for WorkloadConfig {
@@ -48,6 +75,8 @@ fn default() -> Self {
             distribution: Distribution::Uniform,
             total_ops: 1000,
             seed: 0,
+            mix: None,
+            arrival: None,
         }
     }
 }
@@ -60,6 +89,83 @@ pub struct OperationConfig {
     pub weight: u32,
 }
 
+/// An OLTP operation mix given as ratios rather than weights.
+///
+/// Records generated from a mix are tagged with `_op` (`insert`, `read`,
+/// `update` or `delete`), which storage, MVCC and locking blocks act on;
+/// weight-based workloads are not tagged, so blocks treat them as inserts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OperationMix {
+    /// Fraction of operations that are reads, in [0, 1].
+    pub read_write_ratio: f64,
+    /// Fraction of operations that are deletes, taken out of the writes.
+    /// The remaining writes are split evenly between inserts and updates.
+    pub delete_ratio: f64,
+    /// Reads, updates and deletes pick among the live keys — inserted and
+    /// not yet deleted — so reads always hit. Otherwise they pick from every
+    /// key generated so far, deleted or not.
+    pub target_existing: bool,
+}
+
+impl OperationMix {
+    /// Pick an operation type for a uniform `u` in [0, 1].
+    fn pick(&self, u: f64) -> OperationType {
+        let reads = self.read_write_ratio.clamp(0.0, 1.0);
+        let deletes = self.delete_ratio.clamp(0.0, 1.0 - reads);
+        let inserts = (1.0 - reads - deletes) / 2.0;
+        if u < reads {
+            OperationType::Select
+        } else if u < reads + deletes {
+            OperationType::Delete
+        } else if u < reads + deletes + inserts {
+            OperationType::Insert
+        } else {
+            OperationType::Update
+        }
+    }
+}
+
+/// When operations arrive, for latency simulations.
+///
+/// Arrival times come from their own random stream (derived from the seed),
+/// so adding an arrival model doesn't change which operations are generated.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ArrivalModel {
+    /// Mean arrivals per second. With a non-positive rate everything
+    /// arrives at time 0.
+    pub rate: f64,
+    pub distribution: ArrivalDistribution,
+    /// Stop generating once arrivals pass this many seconds, even if fewer
+    /// than `total_ops` have been generated.
+    pub duration: Option<f64>,
+}
+
+/// How inter-arrival gaps are spread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArrivalDistribution {
+    /// Exponential gaps with mean `1 / rate` — independent arrivals, which
+    /// bunch up and make queues form before the server is saturated.
+    Poisson,
+    /// A fixed gap of `1 / rate`.
+    Constant,
+}
+
+impl ArrivalModel {
+    /// Milliseconds until the next arrival.
+    fn gap_ms(&self, rng: &mut Rng) -> f64 {
+        if self.rate <= 0.0 {
+            return 0.0;
+        }
+        let mean_ms = 1000.0 / self.rate;
+        match self.distribution {
+            ArrivalDistribution::Poisson => {
+                -(1.0 - rng.next_f64()).max(f64::MIN_POSITIVE).ln() * mean_ms
+            }
+            ArrivalDistribution::Constant => mean_ms,
+        }
+    }
+}
+
 /// Supported operation types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OperationType {
@@ -81,14 +187,101 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 }
 
 /// Key distribution strategy.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Distribution {
     /// Each key equally likely.
     Uniform,
-    /// Popular keys accessed much more often (models real-world skew).
-    Zipfian,
+    /// Popular keys accessed much more often (models real-world skew). Key
+    /// `k` is drawn with probability proportional to `1 / (k + 1)^theta`;
+    /// `theta` is clamped to `[0, Self::MAX_ZIPF_THETA]`, 0 being uniform.
+    Zipfian { theta: f64 },
     /// Most recent keys accessed most often.
     Latest,
+    /// `hot_access_fraction` of accesses go to the lowest
+    /// `hot_key_fraction` of keys, the rest to the remaining keys; both
+    /// uniformly. `hotspot(0.8, 0.2)` is the classic 80/20 rule.
+    Hotspot {
+        hot_access_fraction: f64,
+        hot_key_fraction: f64,
+    },
+    /// Every operation's key follows a scan pattern instead of being drawn
+    /// from the keys generated so far.
+    Sequential(ScanPattern),
+}
+
+/// A sequential scan over pages `0..scan_length`, optionally mixed with
+/// accesses to a hot working set of pages `scan_length..scan_length +
+/// working_set_size` (picked uniformly from the seeded generator).
+///
+/// Without `interleave`, the stream alternates phases: `scan_length` hot
+/// accesses, then the whole scan — so the scan sweeps through a pool the
+/// working set has just warmed. With `interleave`, hot accesses and scan
+/// pages alternate one for one. Either way half the accesses are hot when
+/// there is a working set; without one the stream is the scan, repeated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanPattern {
+    pub scan_length: usize,
+    pub working_set_size: usize,
+    pub interleave: bool,
+}
+
+impl ScanPattern {
+    /// The page accessed by operation `seq`.
+    fn page(&self, seq: usize, rng: &mut Rng) -> usize {
+        let scan_length = self.scan_length.max(1);
+        if self.working_set_size == 0 {
+            return seq % scan_length;
+        }
+        let hot = |rng: &mut Rng| scan_length + rng.next_usize(self.working_set_size);
+        if self.interleave {
+            if seq.is_multiple_of(2) {
+                (seq / 2) % scan_length
+            } else {
+                hot(rng)
+            }
+        } else {
+            let pos = seq % (2 * scan_length);
+            if pos < scan_length {
+                hot(rng)
+            } else {
+                pos - scan_length
+            }
+        }
+    }
+}
+
+impl Distribution {
+    /// Zipfian skew used by YCSB.
+    pub const DEFAULT_ZIPF_THETA: f64 = 0.99;
+
+    /// Largest Zipfian skew; the sampling formula breaks down at `theta = 1`.
+    pub const MAX_ZIPF_THETA: f64 = 0.999;
+
+    /// Zipfian with the given skew.
+    pub fn zipfian(theta: f64) -> Self {
+        Distribution::Zipfian { theta }
+    }
+
+    /// Hotspot: `hot_access_fraction` of accesses hit `hot_key_fraction` of
+    /// the keys. Both are clamped to `[0, 1]`.
+    pub fn hotspot(hot_access_fraction: f64, hot_key_fraction: f64) -> Self {
+        Distribution::Hotspot {
+            hot_access_fraction,
+            hot_key_fraction,
+        }
+    }
+}
+
+impl OperationType {
+    /// The `_op` tag blocks dispatch on.
+    pub fn op_tag(&self) -> &'static str {
+        match self {
+            OperationType::Insert => "insert",
+            OperationType::Select => "read",
+            OperationType::Update => "update",
+            OperationType::Delete => "delete",
+        }
+    }
 }
 
 // ── Generated operation ─────────────────────────────────────────────────────
@@ -102,6 +295,8 @@ pub struct Operation {
     pub op_type: OperationType,
     /// Target key id.
     pub key: usize,
+    /// Simulated arrival time in milliseconds, if the workload is timed.
+    pub arrival_ms: Option<f64>,
 }
 
 impl Operation {
@@ -111,10 +306,50 @@ pub fn to_record(&self) -> Record {
         r.insert("_op_type".into(), self.op_type.to_string()).ok();
         r.insert("_op_seq".into(), self.seq as i64).ok();
         r.insert("id".into(), self.key as i64).ok();
+        r.insert("key".into(), self.key as i64).ok();
+        r.insert("_page_id".into(), self.key).ok();
         r.insert("name".into(), format!("user_{}", self.key)).ok();
         r.insert("score".into(), ((self.key * 7) % 100) as f64).ok();
+        if let Some(arrival_ms) = self.arrival_ms {
+            r.insert("_arrival_ms".into(), arrival_ms).ok();
+        }
         r
     }
+
+    /// Like [`Operation::to_record`], plus the `_op` tag.
+    pub fn to_tagged_record(&self) -> Record {
+        let mut r = self.to_record();
+        r.insert("_op".into(), self.op_type.op_tag()).ok();
+        r
+    }
+}
+
+// ── Live key set ────────────────────────────────────────────────────────────
+
+/// Keys inserted and not yet deleted, with O(1) insert, remove and lookup by
+/// position.
+#[derive(Default)]
+struct LiveKeys {
+    keys: Vec<usize>,
+    /// key → index in `keys`
+    positions: HashMap<usize, usize>,
+}
+
+impl LiveKeys {
+    fn insert(&mut self, key: usize) {
+        self.positions.insert(key, self.keys.len());
+        self.keys.push(key);
+    }
+
+    fn remove(&mut self, key: usize) {
+        let Some(pos) = self.positions.remove(&key) else {
+            return;
+        };
+        self.keys.swap_remove(pos);
+        if let Some(&moved) = self.keys.get(pos) {
+            self.positions.insert(moved, pos);
+        }
+    }
 }
 
 // ── Simple deterministic PRNG (xorshift64) ──────────────────────────────────
@@ -126,13 +361,15 @@ struct Rng {
 
 impl Rng {
     fn new(seed: u64) -> Self {
+        // Scramble the seed with a splitmix64 step: xorshift started from
+        // nearby small seeds (1, 2, ...) would produce correlated streams.
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
         // Avoid zero state.
         Self {
-            state: if seed == 0 {
-                0x853c_49e6_748f_ea9b
-            } else {
-                seed
-            },
+            state: if z == 0 { 0x853c_49e6_748f_ea9b } else { z },
         }
     }
 
@@ -149,6 +386,59 @@ fn next_u64(&mut self) -> u64 {
     fn next_usize(&mut self, n: usize) -> usize {
         (self.next_u64() % n as u64) as usize
     }
+
+    /// Uniform random in [0, 1].
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() as f64) / (u64::MAX as f64)
+    }
+}
+
+// ── Zipfian sampler ─────────────────────────────────────────────────────────
+
+/// Zipfian sampler over a key space that only grows (Gray et al., "Quickly
+/// Generating Billion-Record Synthetic Databases", as used by YCSB). The
+/// normalising constant `zeta(n) = Σ 1/i^theta` is extended incrementally as
+/// keys are inserted, so sampling stays O(1) amortised.
+struct Zipfian {
+    theta: f64,
+    /// Keys `zeta_n` covers.
+    n: usize,
+    zeta_n: f64,
+    zeta_2: f64,
+}
+
+impl Zipfian {
+    fn new(theta: f64) -> Self {
+        let theta = theta.clamp(0.0, Distribution::MAX_ZIPF_THETA);
+        Self {
+            theta,
+            n: 0,
+            zeta_n: 0.0,
+            zeta_2: 1.0 + 0.5f64.powf(theta),
+        }
+    }
+
+    /// A key in [0, key_count); key 0 is the most popular.
+    fn sample(&mut self, rng: &mut Rng, key_count: usize) -> usize {
+        while self.n < key_count {
+            self.n += 1;
+            self.zeta_n += 1.0 / (self.n as f64).powf(self.theta);
+        }
+        let n = key_count as f64;
+        let alpha = 1.0 / (1.0 - self.theta);
+        let eta = (1.0 - (2.0 / n).powf(1.0 - self.theta)) / (1.0 - self.zeta_2 / self.zeta_n);
+
+        let u = rng.next_f64();
+        let uz = u * self.zeta_n;
+        let key = if uz < 1.0 {
+            0
+        } else if uz < self.zeta_2 {
+            1
+        } else {
+            (n * (eta * u - eta + 1.0).powf(alpha)) as usize
+        };
+        key.min(key_count - 1)
+    }
 }
 
 // ── Generator ───────────────────────────────────────────────────────────────
@@ -163,55 +453,97 @@ pub fn generate(config: &WorkloadConfig) -> Vec<Operation> {
 
         // Build weighted operation type table.
         let op_table = Self::build_op_table(&config.operations);
-        if op_table.is_empty() {
+        if op_table.is_empty() && config.mix.is_none() {
             return Vec::new();
         }
+        let target_existing = config.mix.is_some_and(|m| m.target_existing);
+        let mut live = LiveKeys::default();
+        let mut arrival_rng = Rng::new(config.seed ^ 0xa076_1d64_78bd_642f);
+        let mut clock_ms = 0.0;
 
         // Key space size: for inserts we keep growing; for others we pick
         // from existing keys.  We'll track a "next_key" counter.
         let mut next_key: usize = 0;
+        let mut zipfian = match config.distribution {
+            Distribution::Zipfian { theta } => Some(Zipfian::new(theta)),
+            _ => None,
+        };
         let mut ops = Vec::with_capacity(config.total_ops);
 
         for seq in 0..config.total_ops {
+            let arrival_ms = match &config.arrival {
+                Some(arrival) => {
+                    if seq > 0 {
+                        clock_ms += arrival.gap_ms(&mut arrival_rng);
+                    }
+                    if arrival.duration.is_some_and(|secs| clock_ms > secs * 1000.0) {
+                        break;
+                    }
+                    Some(clock_ms)
+                }
+                None => None,
+            };
+
             // Pick operation type.
-            let op_idx = rng.next_usize(op_table.len());
-            let op_type = op_table[op_idx];
+            let mut op_type = match &config.mix {
+                Some(mix) => mix.pick(rng.next_f64()),
+                None => op_table[rng.next_usize(op_table.len())],
+            };
+
+            if let Distribution::Sequential(scan) = config.distribution {
+                let key = scan.page(seq, &mut rng);
+                ops.push(Operation { seq, op_type, key, arrival_ms });
+                continue;
+            }
+
+            // Reads, updates and deletes need a key to target; with none
+            // available, force an insert.
+            let key_count = if target_existing { live.keys.len() } else { next_key };
+            if key_count == 0 {
+                op_type = OperationType::Insert;
+            }
 
             // Pick key.
             let key = match op_type {
                 OperationType::Insert => {
                     let k = next_key;
                     next_key += 1;
+                    live.insert(k);
                     k
                 }
                 _ => {
-                    if next_key == 0 {
-                        // No keys inserted yet — force an insert.
-                        let k = next_key;
-                        next_key += 1;
-                        ops.push(Operation {
-                            seq,
-                            op_type: OperationType::Insert,
-                            key: k,
-                        });
-                        continue;
+                    let picked = match zipfian.as_mut() {
+                        Some(zipfian) => zipfian.sample(&mut rng, key_count),
+                        None => Self::pick_key(&mut rng, key_count, config.distribution),
+                    };
+                    let k = if target_existing { live.keys[picked] } else { picked };
+                    if op_type == OperationType::Delete {
+                        live.remove(k);
                     }
-                    Self::pick_key(&mut rng, next_key, config.distribution)
+                    k
                 }
             };
 
-            ops.push(Operation { seq, op_type, key });
+            ops.push(Operation { seq, op_type, key, arrival_ms });
         }
 
         ops
     }
 
     /// Convert generated operations into Records for block consumption.
+    /// Records from an [`OperationMix`] carry their `_op` tag.
     pub fn generate_records(config: &WorkloadConfig) -> Vec<Record> {
-        Self::generate(config)
-            .iter()
-            .map(|op| op.to_record())
-            .collect()
+        let ops = Self::generate(config);
+        if config.mix.is_some() {
+            ops.iter().map(|op| op.to_tagged_record()).collect()
+        } else {
+            ops.iter().map(|op| op.to_record()).collect()
+        }
+    }
+
+    /// The generated records as a stream, ready for an entry-point port.
+    pub fn generate_stream(config: &WorkloadConfig) -> PortValue {
+        PortValue::Stream(Self::generate_records(config))
     }
 
     /// Workload summary statistics.
@@ -245,25 +577,39 @@ fn build_op_table(configs: &[OperationConfig]) -> Vec<OperationType> {
         table
     }
 
-    /// Pick a key from [0, key_count) using the given distribution.
+    /// Pick a key from [0, key_count) using the given distribution. Zipfian
+    /// keeps state across picks, so `generate` samples it separately.
     fn pick_key(rng: &mut Rng, key_count: usize, dist: Distribution) -> usize {
         match dist {
             Distribution::Uniform => rng.next_usize(key_count),
-            Distribution::Zipfian => Self::zipfian_key(rng, key_count),
+            Distribution::Zipfian { theta } => Zipfian::new(theta).sample(rng, key_count),
             Distribution::Latest => Self::latest_key(rng, key_count),
+            Distribution::Hotspot {
+                hot_access_fraction,
+                hot_key_fraction,
+            } => Self::hotspot_key(rng, key_count, hot_access_fraction, hot_key_fraction),
+            // `generate` walks the scan itself; as a key distribution it is
+            // uniform.
+            Distribution::Sequential(_) => rng.next_usize(key_count),
         }
     }
 
-    /// Zipfian: heavily skewed toward low keys.
-    /// Approximation: take the floor of `key_count * (1 - random^2)` inverted
-    /// so key 0 is most popular.
-    fn zipfian_key(rng: &mut Rng, key_count: usize) -> usize {
-        // Generate a number in (0, 1).
-        let u = (rng.next_u64() as f64) / (u64::MAX as f64);
-        // Power-law bias toward 0.
-        let biased = u * u;
-        let key = (biased * key_count as f64) as usize;
-        key.min(key_count - 1)
+    /// Hotspot: keys [0, hot) take `hot_access_fraction` of accesses. The hot
+    /// set always has at least one key; if it covers every key, so do all
+    /// accesses.
+    fn hotspot_key(
+        rng: &mut Rng,
+        key_count: usize,
+        hot_access_fraction: f64,
+        hot_key_fraction: f64,
+    ) -> usize {
+        let hot_keys = ((key_count as f64 * hot_key_fraction.clamp(0.0, 1.0)).ceil() as usize)
+            .clamp(1, key_count);
+        if hot_keys == key_count || rng.next_f64() < hot_access_fraction.clamp(0.0, 1.0) {
+            rng.next_usize(hot_keys)
+        } else {
+            hot_keys + rng.next_usize(key_count - hot_keys)
+        }
     }
 
     /// Latest: most recent keys are most popular.
@@ -308,6 +654,40 @@ fn test_deterministic_with_seed() {
         }
     }
 
+    /// The generated stream as canonical JSON (object keys sorted).
+    fn stream_bytes(config: &WorkloadConfig) -> String {
+        let records = WorkloadGenerator::generate_records(config);
+        serde_json::to_value(&records).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_seed_reproducibility() {
+        for distribution in [
+            Distribution::Uniform,
+            Distribution::zipfian(Distribution::DEFAULT_ZIPF_THETA),
+            Distribution::hotspot(0.8, 0.2),
+        ] {
+            let config = |seed| WorkloadConfig {
+                total_ops: 500,
+                seed,
+                distribution,
+                ..Default::default()
+            };
+            assert_eq!(
+                stream_bytes(&config(17)),
+                stream_bytes(&config(17)),
+                "{:?}: same seed should give an identical stream",
+                distribution
+            );
+            assert_ne!(
+                stream_bytes(&config(17)),
+                stream_bytes(&config(18)),
+                "{:?}: different seeds should give different streams",
+                distribution
+            );
+        }
+    }
+
     #[test]
     fn test_insert_only_workload() {
         let config = WorkloadConfig {
@@ -318,6 +698,8 @@ fn test_insert_only_workload() {
             total_ops: 50,
             seed: 1,
             distribution: Distribution::Uniform,
+            mix: None,
+            arrival: None,
         };
         let ops = WorkloadGenerator::generate(&config);
         assert_eq!(ops.len(), 50);
@@ -339,6 +721,8 @@ fn test_select_only_forces_initial_insert() {
             total_ops: 10,
             seed: 1,
             distribution: Distribution::Uniform,
+            mix: None,
+            arrival: None,
         };
         let ops = WorkloadGenerator::generate(&config);
         // First op should be forced INSERT because no keys exist.
@@ -361,6 +745,8 @@ fn test_operation_weights_respected() {
             total_ops: 200,
             seed: 42,
             distribution: Distribution::Uniform,
+            mix: None,
+            arrival: None,
         };
         let ops = WorkloadGenerator::generate(&config);
         let summary = WorkloadGenerator::summarize(&ops);
@@ -374,6 +760,7 @@ fn test_to_record_has_expected_fields() {
             seq: 5,
             op_type: OperationType::Insert,
             key: 42,
+            arrival_ms: None,
         };
         let rec = op.to_record();
         assert!(rec.data.contains_key("_op_type"));
@@ -381,6 +768,9 @@ fn test_to_record_has_expected_fields() {
         assert!(rec.data.contains_key("id"));
         assert!(rec.data.contains_key("name"));
         assert!(rec.data.contains_key("score"));
+        assert!(!rec.data.contains_key("_arrival_ms"));
+        assert_eq!(rec.get::<usize>("_page_id").unwrap().unwrap(), 42);
+        assert_eq!(rec.get::<i64>("key").unwrap().unwrap(), 42);
 
         assert_eq!(rec.get::<i64>("id").unwrap().unwrap(), 42);
         assert_eq!(rec.get::<String>("_op_type").unwrap().unwrap(), "INSERT");
@@ -415,7 +805,9 @@ fn test_zipfian_distribution_skew() {
             ],
             total_ops: 10_000,
             seed: 42,
-            distribution: Distribution::Zipfian,
+            distribution: Distribution::zipfian(Distribution::DEFAULT_ZIPF_THETA),
+            mix: None,
+            arrival: None,
         };
         let ops = WorkloadGenerator::generate(&config);
 
@@ -438,6 +830,263 @@ fn test_zipfian_distribution_skew() {
         );
     }
 
+    /// Fraction of selects in a 10% insert / 90% select workload that
+    /// target the lowest `hot` keys.
+    fn select_share_below(distribution: Distribution, hot: usize) -> f64 {
+        let config = WorkloadConfig {
+            operations: vec![
+                OperationConfig { op_type: OperationType::Insert, weight: 10 },
+                OperationConfig { op_type: OperationType::Select, weight: 90 },
+            ],
+            total_ops: 20_000,
+            seed: 7,
+            distribution,
+            mix: None,
+            arrival: None,
+        };
+        let ops = WorkloadGenerator::generate(&config);
+        let selects: Vec<_> = ops.iter().filter(|o| o.op_type == OperationType::Select).collect();
+        selects.iter().filter(|o| o.key < hot).count() as f64 / selects.len() as f64
+    }
+
+    #[test]
+    fn test_zipfian_theta_controls_skew() {
+        let flat = select_share_below(Distribution::zipfian(0.0), 10);
+        let mild = select_share_below(Distribution::zipfian(0.5), 10);
+        let steep = select_share_below(Distribution::zipfian(0.99), 10);
+        assert!(flat < 0.05, "theta 0 should be near uniform, got {}", flat);
+        assert!(flat < mild && mild < steep, "{} < {} < {}", flat, mild, steep);
+        assert!(steep > 0.3, "theta 0.99 should favour the top keys, got {}", steep);
+
+        // Out-of-range skew is clamped rather than producing NaN keys.
+        let clamped = select_share_below(Distribution::zipfian(5.0), 10);
+        assert!(clamped > steep);
+    }
+
+    #[test]
+    fn test_hotspot_distribution() {
+        // 80% of accesses to 20% of keys.
+        let config = WorkloadConfig {
+            operations: vec![
+                OperationConfig { op_type: OperationType::Insert, weight: 10 },
+                OperationConfig { op_type: OperationType::Select, weight: 90 },
+            ],
+            total_ops: 20_000,
+            seed: 11,
+            distribution: Distribution::hotspot(0.8, 0.2),
+            mix: None,
+            arrival: None,
+        };
+        let ops = WorkloadGenerator::generate(&config);
+        let mut hot = 0;
+        let mut selects = 0;
+        let mut inserted = 0;
+        for op in &ops {
+            match op.op_type {
+                OperationType::Insert => inserted += 1,
+                _ => {
+                    selects += 1;
+                    if op.key < (inserted as f64 * 0.2).ceil() as usize {
+                        hot += 1;
+                    }
+                }
+            }
+        }
+        let share = hot as f64 / selects as f64;
+        assert!((share - 0.8).abs() < 0.03, "hot share {}", share);
+
+        // A hot set covering every key takes every access.
+        assert!(select_share_below(Distribution::hotspot(0.0, 1.0), usize::MAX) == 1.0);
+    }
+
+    fn mixed(read_write_ratio: f64, delete_ratio: f64, target_existing: bool) -> WorkloadConfig {
+        WorkloadConfig {
+            total_ops: 20_000,
+            seed: 21,
+            mix: Some(OperationMix {
+                read_write_ratio,
+                delete_ratio,
+                target_existing,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_operation_mix_ratios() {
+        let ops = WorkloadGenerator::generate(&mixed(0.7, 0.1, false));
+        let summary = WorkloadGenerator::summarize(&ops);
+        let share = |t| *summary.get(&t).unwrap_or(&0) as f64 / ops.len() as f64;
+        assert!((share(OperationType::Select) - 0.7).abs() < 0.02);
+        assert!((share(OperationType::Delete) - 0.1).abs() < 0.02);
+        assert!((share(OperationType::Insert) - 0.1).abs() < 0.02);
+        assert!((share(OperationType::Update) - 0.1).abs() < 0.02);
+
+        // A mix replaces the weight table, even an empty one.
+        let config = WorkloadConfig { operations: vec![], ..mixed(1.0, 0.0, false) };
+        let ops = WorkloadGenerator::generate(&config);
+        assert_eq!(ops.len(), 20_000);
+        assert_eq!(ops[0].op_type, OperationType::Insert);
+        assert!(ops[1..].iter().all(|o| o.op_type == OperationType::Select));
+    }
+
+    #[test]
+    fn test_operation_mix_tags_records() {
+        let records = WorkloadGenerator::generate_records(&mixed(0.5, 0.2, true));
+        for r in &records {
+            let op = r.get::<String>("_op").unwrap().unwrap();
+            let op_type = r.get::<String>("_op_type").unwrap().unwrap();
+            let expected = match op_type.as_str() {
+                "INSERT" => "insert",
+                "SELECT" => "read",
+                "UPDATE" => "update",
+                "DELETE" => "delete",
+                other => panic!("unexpected op type {}", other),
+            };
+            assert_eq!(op, expected);
+        }
+
+        // Weight-based workloads stay untagged.
+        let plain = WorkloadGenerator::generate_records(&WorkloadConfig { seed: 4, ..Default::default() });
+        assert!(plain.iter().all(|r| !r.data.contains_key("_op")));
+    }
+
+    #[test]
+    fn test_target_existing_only_hits_live_keys() {
+        let check = |target_existing: bool| {
+            let ops = WorkloadGenerator::generate(&mixed(0.5, 0.25, target_existing));
+            let mut live = std::collections::HashSet::new();
+            let mut misses = 0;
+            for op in &ops {
+                match op.op_type {
+                    OperationType::Insert => {
+                        live.insert(op.key);
+                    }
+                    OperationType::Delete => {
+                        if !live.remove(&op.key) {
+                            misses += 1;
+                        }
+                    }
+                    _ => {
+                        if !live.contains(&op.key) {
+                            misses += 1;
+                        }
+                    }
+                }
+            }
+            misses
+        };
+        assert_eq!(check(true), 0);
+        assert!(check(false) > 0, "without targeting, some ops should hit deleted keys");
+    }
+
+    fn scan(scan_length: usize, working_set_size: usize, interleave: bool, seed: u64) -> Vec<usize> {
+        let config = WorkloadConfig {
+            total_ops: 40,
+            seed,
+            distribution: Distribution::Sequential(ScanPattern {
+                scan_length,
+                working_set_size,
+                interleave,
+            }),
+            ..Default::default()
+        };
+        WorkloadGenerator::generate(&config).iter().map(|op| op.key).collect()
+    }
+
+    #[test]
+    fn test_sequential_pattern() {
+        // Pure scan: 0..N, repeated.
+        let keys = scan(8, 0, false, 1);
+        assert_eq!(&keys[..10], &[0, 1, 2, 3, 4, 5, 6, 7, 0, 1]);
+
+        // Phases: 10 hot accesses, then the scan.
+        let keys = scan(10, 4, false, 1);
+        assert!(keys[..10].iter().all(|k| (10..14).contains(k)));
+        assert_eq!(&keys[10..20], &(0..10).collect::<Vec<_>>()[..]);
+        assert!(keys[20..30].iter().all(|k| (10..14).contains(k)));
+
+        // Interleaved: scan page, hot page, scan page, ...
+        let keys = scan(10, 4, true, 1);
+        let scan_pages: Vec<usize> = keys.iter().step_by(2).copied().collect();
+        assert_eq!(&scan_pages[..12], &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 1]);
+        assert!(keys.iter().skip(1).step_by(2).all(|k| (10..14).contains(k)));
+
+        // Reproducible for a seed; hot picks differ across seeds.
+        assert_eq!(scan(10, 4, true, 9), scan(10, 4, true, 9));
+        assert_ne!(scan(10, 4, true, 9), scan(10, 4, true, 10));
+    }
+
+    fn timed(
+        rate: f64,
+        distribution: ArrivalDistribution,
+        duration: Option<f64>,
+    ) -> WorkloadConfig {
+        WorkloadConfig {
+            total_ops: 10_000,
+            seed: 8,
+            arrival: Some(ArrivalModel { rate, distribution, duration }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_constant_arrivals() {
+        let ops = WorkloadGenerator::generate(&timed(200.0, ArrivalDistribution::Constant, None));
+        assert_eq!(ops.len(), 10_000);
+        for (i, op) in ops.iter().take(5).enumerate() {
+            assert!((op.arrival_ms.unwrap() - i as f64 * 5.0).abs() < 1e-9);
+        }
+        let rec = ops[3].to_record();
+        assert!((rec.get::<f64>("_arrival_ms").unwrap().unwrap() - 15.0).abs() < 1e-9);
+
+        // The arrival model doesn't change which operations are generated.
+        let untimed = WorkloadGenerator::generate(&WorkloadConfig {
+            arrival: None,
+            ..timed(200.0, ArrivalDistribution::Constant, None)
+        });
+        assert!(ops.iter().zip(&untimed).all(|(a, b)| a.key == b.key && a.op_type == b.op_type));
+        assert!(untimed.iter().all(|op| op.arrival_ms.is_none()));
+    }
+
+    #[test]
+    fn test_poisson_arrivals() {
+        let ops = WorkloadGenerator::generate(&timed(100.0, ArrivalDistribution::Poisson, None));
+        let times: Vec<f64> = ops.iter().map(|op| op.arrival_ms.unwrap()).collect();
+        assert!(times.windows(2).all(|w| w[0] <= w[1]), "arrivals should be ordered");
+        let gaps: Vec<f64> = times.windows(2).map(|w| w[1] - w[0]).collect();
+        let mean = gaps.iter().sum::<f64>() / gaps.len() as f64;
+        assert!((mean - 10.0).abs() < 0.5, "mean gap {} ms", mean);
+        // Exponential gaps: standard deviation equals the mean.
+        let var = gaps.iter().map(|g| (g - mean).powi(2)).sum::<f64>() / gaps.len() as f64;
+        assert!((var.sqrt() - mean).abs() < 1.0, "gap std dev {}", var.sqrt());
+    }
+
+    #[test]
+    fn test_arrival_duration_caps_ops() {
+        // 2 seconds at 100/s: arrivals at 0, 10, ..., 2000 ms.
+        let config = timed(100.0, ArrivalDistribution::Constant, Some(2.0));
+        let ops = WorkloadGenerator::generate(&config);
+        assert_eq!(ops.len(), 201);
+        assert!(ops.iter().all(|op| op.arrival_ms.unwrap() <= 2000.0));
+    }
+
+    #[test]
+    fn test_generate_stream() {
+        let config = WorkloadConfig {
+            total_ops: 15,
+            seed: 3,
+            ..Default::default()
+        };
+        match WorkloadGenerator::generate_stream(&config) {
+            PortValue::Stream(records) => {
+                assert_eq!(records.len(), 15);
+                assert!(records.iter().all(|r| r.data.contains_key("_page_id")));
+            }
+            other => panic!("expected a stream, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_latest_distribution_skew() {
         // Compare latest vs uniform: latest should have higher average key
@@ -456,6 +1105,8 @@ fn test_latest_distribution_skew() {
             total_ops: 5_000,
             seed: 42,
             distribution: Distribution::Uniform,
+            mix: None,
+            arrival: None,
         };
 
         let uniform_ops = WorkloadGenerator::generate(&base);
@@ -491,6 +1142,8 @@ fn test_empty_operations_returns_empty() {
             total_ops: 100,
             seed: 1,
             distribution: Distribution::Uniform,
+            mix: None,
+            arrival: None,
         };
         let ops = WorkloadGenerator::generate(&config);
         assert!(ops.is_empty());
@@ -499,10 +1152,10 @@ fn test_empty_operations_returns_empty() {
     #[test]
     fn test_summarize() {
         let ops = vec![
-            Operation { seq: 0, op_type: OperationType::Insert, key: 0 },
-            Operation { seq: 1, op_type: OperationType::Insert, key: 1 },
-            Operation { seq: 2, op_type: OperationType::Select, key: 0 },
-            Operation { seq: 3, op_type: OperationType::Delete, key: 0 },
+            Operation { seq: 0, op_type: OperationType::Insert, key: 0, arrival_ms: None },
+            Operation { seq: 1, op_type: OperationType::Insert, key: 1, arrival_ms: None },
+            Operation { seq: 2, op_type: OperationType::Select, key: 0, arrival_ms: None },
+            Operation { seq: 3, op_type: OperationType::Delete, key: 0, arrival_ms: None },
         ];
         let summary = WorkloadGenerator::summarize(&ops);
         assert_eq!(*summary.get(&OperationType::Insert).unwrap(), 2);
@@ -331,6 +331,25 @@ fn test_get_nonexistent_block() {
         assert!(result.is_none());
     }
 
+    /// Test downcasting a registered block to its concrete type
+    #[test]
+    fn test_get_block_as_concrete_type() {
+        let mut runtime = BlockRuntime::new();
+        let block = Box::new(TestBlock::new("Concrete"));
+        let block_id = block.id();
+        runtime.register_block(block).unwrap();
+
+        let concrete = runtime.get_block_as::<TestBlock>(block_id).unwrap();
+        assert_eq!(concrete.metadata.name, "Concrete");
+
+        runtime.get_block_as_mut::<TestBlock>(block_id).unwrap().metadata.name = "Renamed".into();
+        assert_eq!(runtime.get_block(block_id).unwrap().metadata().name, "Renamed");
+
+        // Wrong type or unknown id
+        assert!(runtime.get_block_as::<String>(block_id).is_none());
+        assert!(runtime.get_block_as::<TestBlock>(BlockId::new()).is_none());
+    }
+
     /// Test multiple block registrations
     #[test]
     fn test_multiple_block_registrations() {
@@ -8,7 +8,8 @@
 mod tests {
     use std::collections::HashMap;
 
-    use crate::categories::buffer::LRUBufferBlock;
+    use crate::categories::buffer::{LRUBufferBlock, LRUKBufferBlock, TwoQBufferBlock};
+    use crate::categories::execution::{SequentialScanBlock, UnionBlock};
     use crate::categories::index::BTreeIndexBlock;
     use crate::categories::storage::HeapFileBlock;
     use crate::categories::TupleId;
@@ -19,7 +20,8 @@ mod tests {
     use crate::runtime::engine::ExecutionEngine;
     use crate::runtime::validation::GraphValidator;
     use crate::runtime::workload::{
-        Distribution, OperationConfig, OperationType, WorkloadConfig, WorkloadGenerator,
+        ArrivalDistribution, ArrivalModel, Distribution, OperationConfig, OperationType,
+        ScanPattern, WorkloadConfig, WorkloadGenerator,
     };
 
     /// Helper: build N records with incrementing ids.
@@ -435,6 +437,8 @@ async fn test_engine_with_workload_generator() {
             distribution: Distribution::Uniform,
             total_ops: 300,
             seed: 42,
+            mix: None,
+            arrival: None,
         };
 
         let records = WorkloadGenerator::generate_records(&config);
@@ -546,8 +550,9 @@ async fn test_engine_three_block_pipeline() {
     async fn test_workload_distributions_all_valid() {
         for dist in [
             Distribution::Uniform,
-            Distribution::Zipfian,
+            Distribution::zipfian(Distribution::DEFAULT_ZIPF_THETA),
             Distribution::Latest,
+            Distribution::hotspot(0.8, 0.2),
         ] {
             let config = WorkloadConfig {
                 operations: vec![
@@ -563,6 +568,8 @@ async fn test_workload_distributions_all_valid() {
                 distribution: dist,
                 total_ops: 500,
                 seed: 99,
+                mix: None,
+                arrival: None,
             };
 
             let ops = WorkloadGenerator::generate(&config);
@@ -577,6 +584,240 @@ async fn test_workload_distributions_all_valid() {
         }
     }
 
+    // ====================================================================
+    // Test 10b: Skewed workloads cache far better than uniform ones
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_zipfian_workload_raises_lru_hit_rate() {
+        async fn hit_rate(distribution: Distribution) -> f64 {
+            let config = WorkloadConfig {
+                operations: vec![
+                    OperationConfig {
+                        op_type: OperationType::Insert,
+                        weight: 5,
+                    },
+                    OperationConfig {
+                        op_type: OperationType::Select,
+                        weight: 95,
+                    },
+                ],
+                distribution,
+                total_ops: 20_000,
+                seed: 5,
+                mix: None,
+                arrival: None,
+            };
+
+            let mut buffer = LRUBufferBlock::new();
+            let mut params = HashMap::new();
+            params.insert("size".into(), ParameterValue::Integer(50));
+            buffer.initialize(params).await.unwrap();
+
+            let mut inputs = HashMap::new();
+            inputs.insert("requests".into(), WorkloadGenerator::generate_stream(&config));
+            let ctx = ExecutionContext {
+                inputs,
+                parameters: HashMap::new(),
+                metrics: MetricsCollector::new(),
+                logger: Logger::new(),
+                storage: StorageContext::new(),
+            };
+            let result = buffer.execute(ctx).await.unwrap();
+            *result.metrics.get("hit_rate_pct").unwrap()
+        }
+
+        let uniform = hit_rate(Distribution::Uniform).await;
+        let zipfian = hit_rate(Distribution::zipfian(Distribution::DEFAULT_ZIPF_THETA)).await;
+        let hotspot = hit_rate(Distribution::hotspot(0.9, 0.05)).await;
+        assert!(
+            zipfian > 2.0 * uniform,
+            "zipfian hit rate {:.1}% should far exceed uniform {:.1}%",
+            zipfian,
+            uniform
+        );
+        assert!(hotspot > 2.0 * uniform, "hotspot {:.1}% vs uniform {:.1}%", hotspot, uniform);
+    }
+
+    // ====================================================================
+    // Test 10c: Scans pollute LRU but not scan-resistant pools
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_sequential_scan_pollutes_lru_only() {
+        async fn hit_rate(mut pool: Box<dyn Block>, interleave: bool) -> f64 {
+            let config = WorkloadConfig {
+                operations: vec![OperationConfig {
+                    op_type: OperationType::Select,
+                    weight: 100,
+                }],
+                distribution: Distribution::Sequential(ScanPattern {
+                    scan_length: 200,
+                    working_set_size: 20,
+                    interleave,
+                }),
+                total_ops: 4_000,
+                seed: 3,
+                mix: None,
+                arrival: None,
+            };
+            let mut params = HashMap::new();
+            params.insert("size".into(), ParameterValue::Integer(30));
+            pool.initialize(params).await.unwrap();
+
+            let mut inputs = HashMap::new();
+            inputs.insert("requests".into(), WorkloadGenerator::generate_stream(&config));
+            let ctx = ExecutionContext {
+                inputs,
+                parameters: HashMap::new(),
+                metrics: MetricsCollector::new(),
+                logger: Logger::new(),
+                storage: StorageContext::new(),
+            };
+            let result = pool.execute(ctx).await.unwrap();
+            *result.metrics.get("hit_rate_pct").unwrap()
+        }
+
+        // Interleaved: every hot access competes with scan pages, so LRU keeps
+        // evicting the working set while LRU-K and 2Q keep it resident.
+        let lru = hit_rate(Box::new(LRUBufferBlock::new()), true).await;
+        let lru_k = hit_rate(Box::new(LRUKBufferBlock::new()), true).await;
+        let two_q = hit_rate(Box::new(TwoQBufferBlock::new()), true).await;
+        assert!(
+            lru_k > lru + 10.0 && two_q > lru + 10.0,
+            "interleaved: LRU {:.1}%, LRU-K {:.1}%, 2Q {:.1}%",
+            lru,
+            lru_k,
+            two_q
+        );
+
+        // Phased: each scan flushes the warmed working set out of LRU, which
+        // then misses on it again; the scan itself always misses.
+        let lru = hit_rate(Box::new(LRUBufferBlock::new()), false).await;
+        let lru_k = hit_rate(Box::new(LRUKBufferBlock::new()), false).await;
+        let two_q = hit_rate(Box::new(TwoQBufferBlock::new()), false).await;
+        assert!(
+            lru_k > lru && two_q > lru,
+            "phased: LRU {:.1}%, LRU-K {:.1}%, 2Q {:.1}%",
+            lru,
+            lru_k,
+            two_q
+        );
+    }
+
+    // ====================================================================
+    // Test 10d: An undersized buffer pool queues up as arrivals speed up
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_small_buffer_pool_queueing_delay_climbs_with_rate() {
+        async fn queueing_delay(pool_size: i64, rate: f64) -> f64 {
+            let config = WorkloadConfig {
+                operations: vec![
+                    OperationConfig {
+                        op_type: OperationType::Insert,
+                        weight: 5,
+                    },
+                    OperationConfig {
+                        op_type: OperationType::Select,
+                        weight: 95,
+                    },
+                ],
+                distribution: Distribution::Uniform,
+                total_ops: 2_000,
+                seed: 12,
+                mix: None,
+                arrival: Some(ArrivalModel {
+                    rate,
+                    distribution: ArrivalDistribution::Poisson,
+                    duration: None,
+                }),
+            };
+
+            let mut engine = ExecutionEngine::new();
+            engine.add_block("buffer", Box::new(LRUBufferBlock::new()));
+            let mut params = HashMap::new();
+            params.insert("size".into(), ParameterValue::Integer(pool_size));
+            engine.initialize_block("buffer", params).await.unwrap();
+            engine.set_entry_point("buffer");
+
+            let mut input = HashMap::new();
+            input.insert(
+                ("buffer".into(), "requests".into()),
+                WorkloadGenerator::generate_stream(&config),
+            );
+            let result = engine.execute(input).await;
+            assert!(result.success, "Errors: {:?}", result.errors);
+            assert_eq!(
+                result.block_metrics[0].queueing_delay_ms,
+                result.metrics.queueing_delay_ms
+            );
+            result.metrics.queueing_delay_ms
+        }
+
+        // ~100 keys: a 10-page pool misses most requests (~9 ms of disk
+        // each), a 200-page pool only the first touch of each key.
+        let small_slow = queueing_delay(10, 50.0).await;
+        let small_fast = queueing_delay(10, 100.0).await;
+        let large_fast = queueing_delay(200, 100.0).await;
+        assert!(
+            small_fast > 3.0 * small_slow,
+            "small pool: {:.1} ms at 50/s vs {:.1} ms at 100/s",
+            small_slow,
+            small_fast
+        );
+        assert!(
+            small_fast > 10.0 * large_fast,
+            "at 100/s: small pool {:.1} ms vs large pool {:.1} ms",
+            small_fast,
+            large_fast
+        );
+    }
+
+    // ====================================================================
+    // Test 10e: A union merges every fan-in stream, batched or not
+    // ====================================================================
+
+    #[tokio::test]
+    async fn test_union_merges_fan_in_streams() {
+        // Three scans over overlapping id ranges feed one UNION.
+        async fn run(batch_size: usize, pipelined: bool) -> HashMap<String, f64> {
+            let mut engine = ExecutionEngine::new();
+            for i in 0..3 {
+                let scan = format!("scan{}", i);
+                engine.add_block(&scan, Box::new(SequentialScanBlock::new()));
+                engine.add_connection(conn(&format!("c{}", i), &scan, "results", "union", "records"));
+                engine.set_entry_point(&scan);
+                engine.initialize_block(&scan, HashMap::new()).await.unwrap();
+            }
+            engine.add_block("union", Box::new(UnionBlock::new()));
+            let params = HashMap::from([("distinct".to_string(), ParameterValue::Boolean(true))]);
+            engine.initialize_block("union", params).await.unwrap();
+            engine.set_batch_size(batch_size);
+            if pipelined {
+                engine.set_max_in_flight_batches(2);
+            }
+
+            let records = generate_records(70);
+            let mut input = HashMap::new();
+            for (i, (lo, hi)) in [(0, 40), (20, 60), (50, 70)].into_iter().enumerate() {
+                input.insert((format!("scan{}", i), "records".to_string()), PortValue::Stream(records[lo..hi].to_vec()));
+            }
+            let run = engine.execute_detailed(input).await;
+            assert!(run.summary.success, "Errors: {:?}", run.summary.errors);
+            assert_eq!(run.results["union"].outputs["merged"].len(), 70);
+            run.results["union"].metrics.clone()
+        }
+
+        for (batch_size, pipelined) in [(0, false), (10, false), (10, true)] {
+            let m = run(batch_size, pipelined).await;
+            assert_eq!(m["inputs_merged"], 3.0, "batch size {} pipelined {}", batch_size, pipelined);
+            assert_eq!(m["rows_in"], 100.0);
+            assert_eq!(m["rows_out"], 70.0);
+            assert_eq!(m["duplicates_removed"], 30.0);
+        }
+    }
+
     // ====================================================================
     // Test 11: Graph validator standalone — fan-out graph
     // ====================================================================
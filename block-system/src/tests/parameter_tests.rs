@@ -131,6 +131,104 @@ fn test_parameter_constraints_allowed_values() {
         assert_eq!(allowed[2].as_string(), Some("large"));
     }
 
+    /// Test enum parameters: choices, `as_enum` and `enum_param`
+    ///
+    /// A value outside the choices is rejected rather than falling through
+    /// to a default
+    #[test]
+    fn test_enum_parameters() {
+        const CHOICES: &[&str] = &["size_tiered", "leveled"];
+        let constraints = ParameterConstraints::new().with_choices(CHOICES);
+        assert_eq!(constraints.allowed_values.unwrap().len(), 2);
+
+        assert_eq!(ParameterValue::from("leveled").as_enum(CHOICES), Some("leveled"));
+        assert_eq!(ParameterValue::from("Leveled").as_enum(CHOICES), None);
+        assert_eq!(ParameterValue::Integer(1).as_enum(CHOICES), None);
+
+        let mut params = HashMap::new();
+        assert_eq!(enum_param(&params, "compaction_strategy", CHOICES).unwrap(), None);
+        params.insert("compaction_strategy".to_string(), ParameterValue::from("leveled"));
+        assert_eq!(
+            enum_param(&params, "compaction_strategy", CHOICES).unwrap(),
+            Some("leveled")
+        );
+        params.insert("compaction_strategy".to_string(), ParameterValue::from("tiered"));
+        assert_eq!(
+            enum_param(&params, "compaction_strategy", CHOICES).unwrap_err().to_string(),
+            "Invalid parameter: compaction_strategy must be 'size_tiered' or 'leveled', got 'tiered'"
+        );
+        params.insert("compaction_strategy".to_string(), ParameterValue::Boolean(true));
+        assert!(enum_param(&params, "compaction_strategy", CHOICES)
+            .unwrap_err()
+            .to_string()
+            .ends_with("got a non-string value"));
+
+        let three = ["a", "b", "c"];
+        params.insert("x".to_string(), ParameterValue::from("d"));
+        assert!(enum_param(&params, "x", &three)
+            .unwrap_err()
+            .to_string()
+            .contains("must be 'a', 'b' or 'c', got 'd'"));
+    }
+
+    /// Every built-in enum parameter declares its choices, and each block
+    /// accepts exactly those
+    #[tokio::test]
+    async fn test_builtin_enum_parameters_enforce_choices() {
+        use crate::core::registry::BlockRegistry;
+
+        let registry = BlockRegistry::with_builtin_blocks();
+        let mut checked = 0;
+        for id in registry.factory_ids() {
+            let params = registry.create(&id).unwrap().parameters().to_vec();
+            // Every parameter with a fixed set of choices is an Enum, so the
+            // loop below covers all of them.
+            for param in &params {
+                let has_choices = param
+                    .constraints
+                    .as_ref()
+                    .is_some_and(|c| c.allowed_values.is_some());
+                assert!(
+                    !has_choices || param.param_type == ParameterType::Enum,
+                    "{}.{} lists allowed values but is not an Enum",
+                    id,
+                    param.id
+                );
+            }
+            for param in params.iter().filter(|p| p.param_type == ParameterType::Enum) {
+                let choices = param
+                    .constraints
+                    .as_ref()
+                    .and_then(|c| c.allowed_values.clone())
+                    .unwrap_or_else(|| panic!("{}.{} declares no choices", id, param.id));
+                assert!(
+                    choices.iter().any(|c| c.as_string() == param.default_value.as_string()),
+                    "{}.{} default is not a choice",
+                    id,
+                    param.id
+                );
+
+                for choice in &choices {
+                    let mut block = registry.create(&id).unwrap();
+                    let values = HashMap::from([(param.id.clone(), choice.clone())]);
+                    // Some choices need companion parameters (range
+                    // partitioning needs boundaries), but never reject the
+                    // choice itself.
+                    if let Err(e) = block.initialize(values).await {
+                        let rejected = format!("got '{}'", choice.as_string().unwrap());
+                        assert!(!e.to_string().contains(&rejected), "{}.{}: {}", id, param.id, e);
+                    }
+                }
+                let mut block = registry.create(&id).unwrap();
+                let typo = HashMap::from([(param.id.clone(), ParameterValue::from("typo"))]);
+                let result = block.initialize(typo).await;
+                assert!(result.is_err(), "{}.{} accepted a typo", id, param.id);
+                checked += 1;
+            }
+        }
+        assert!(checked > 0);
+    }
+
     /// Test ParameterConstraints with length range
     ///
     /// String and array parameters can have length constraints
@@ -142,6 +240,115 @@ fn test_parameter_constraints_length() {
         assert_eq!(constraints.max_length, Some(100));
     }
 
+    /// Test Parameter::validate and validate_params
+    ///
+    /// Declared types and constraints are checked generically
+    #[test]
+    fn test_parameter_validate() {
+        let param = |id: &str, param_type, constraints| Parameter {
+            id: id.to_string(),
+            name: id.to_string(),
+            param_type,
+            description: String::new(),
+            default_value: ParameterValue::Null,
+            required: false,
+            constraints: Some(constraints),
+            ui_hint: None,
+        };
+        let ratio = param(
+            "ratio",
+            ParameterType::Number,
+            ParameterConstraints::new().with_min(0.5),
+        );
+        let mode = param(
+            "mode",
+            ParameterType::Enum,
+            ParameterConstraints::new().with_choices(&["fast", "safe"]),
+        );
+        let name = param(
+            "name",
+            ParameterType::String,
+            ParameterConstraints::new().with_length_range(Some(1), Some(4)),
+        );
+
+        assert!(ratio.validate(&ParameterValue::Integer(2)).valid);
+        assert!(ratio.validate(&ParameterValue::Null).valid);
+        assert_eq!(
+            ratio.validate(&0.25.into()).errors,
+            vec!["ratio must be at least 0.5, got 0.25"]
+        );
+        assert!(!ratio.validate(&f64::NAN.into()).valid);
+        assert_eq!(
+            ratio.validate(&"1".into()).errors,
+            vec!["ratio must be a number, got '1'"]
+        );
+        assert!(mode.validate(&"safe".into()).valid);
+        assert_eq!(
+            mode.validate(&"slow".into()).errors,
+            vec!["mode must be 'fast' or 'safe', got 'slow'"]
+        );
+        assert!(name.validate(&"abcd".into()).valid);
+        assert!(!name.validate(&"".into()).valid);
+        assert!(!name.validate(&"abcde".into()).valid);
+
+        // A required parameter must be given unless it has a default.
+        let mut required = param("path", ParameterType::String, ParameterConstraints::new());
+        required.required = true;
+        assert_eq!(
+            required.validate(&ParameterValue::Null).errors,
+            vec!["path is required"]
+        );
+        let declared = vec![ratio, mode, required.clone()];
+        let mut values = HashMap::new();
+        values.insert("ratio".to_string(), ParameterValue::Number(0.1));
+        values.insert("unknown".to_string(), ParameterValue::Boolean(true));
+        let result = validate_params(&declared, &values);
+        assert_eq!(result.errors.len(), 2, "{:?}", result.errors);
+
+        required.default_value = "/tmp".into();
+        assert!(validate_params(&[required], &HashMap::new()).valid);
+    }
+
+    /// Test list parameters
+    ///
+    /// List parameters are arrays checked for length and element type
+    #[test]
+    fn test_list_parameters() {
+        let value = ParameterValue::Array(vec![ParameterValue::Integer(1), 2.5.into()]);
+        assert_eq!(value.as_list().map(|l| l.len()), Some(2));
+        assert_eq!(value.param_type(), Some(ParameterType::Array));
+        assert!(ParameterValue::from("a").as_list().is_none());
+
+        let required = ParameterConstraints::new()
+            .with_length_range(Some(1), Some(3))
+            .with_element_type(ParameterType::Number);
+        let check = |value: ParameterValue, constraints: &ParameterConstraints| {
+            let params = HashMap::from([("cols".to_string(), value)]);
+            list_param(&params, "cols", constraints).map(|items| items.unwrap().len())
+        };
+
+        // Integers and floats are both numbers.
+        assert_eq!(check(value.clone(), &required).unwrap(), 2);
+        // Comma-separated strings are split and parsed.
+        assert_eq!(check("1, 2 ,3".into(), &required).unwrap(), 3);
+        assert!(list_param(&HashMap::new(), "cols", &required).unwrap().is_none());
+
+        let err = |value, constraints| check(value, constraints).unwrap_err().to_string();
+        assert!(err(ParameterValue::Array(vec![]), &required).ends_with("cols must not be empty"));
+        assert!(err("".into(), &required).ends_with("cols must not be empty"));
+        assert!(err("1,2,3,4".into(), &required).ends_with("at most 3 items, got 4"));
+        assert!(err(true.into(), &required).ends_with("must be a list, got a boolean"));
+        let mixed = ParameterValue::Array(vec![1.0.into(), "x".into()]);
+        assert!(err(mixed.clone(), &required).ends_with("must contain only numbers, got 'x'"));
+
+        // Without an element type the elements must still agree with each other.
+        let any = ParameterConstraints::new();
+        assert!(err(mixed, &any).ends_with("cols must not mix numbers with 'x'"));
+        let nested = ParameterValue::Array(vec!["a".into(), ParameterValue::Array(vec![])]);
+        assert!(err(nested, &any).ends_with("cols must not mix strings with a list"));
+        assert_eq!(check(ParameterValue::Array(vec![]), &any).unwrap(), 0);
+    }
+
     /// Test ParameterUIHint
     ///
     /// UI hints help render parameters in the user interface
@@ -128,6 +128,46 @@ fn test_record_operations() {
         assert_eq!(score, Some(95.5));
     }
 
+    /// Test the distinction between an absent column and a null one
+    #[test]
+    fn test_record_absent_versus_null() {
+        let mut record = Record::new();
+        record.insert("id".to_string(), 7).unwrap();
+        record.insert("email".to_string(), None::<String>).unwrap();
+
+        assert!(record.contains("email"));
+        assert!(record.is_null("email"));
+        assert!(!record.contains("phone"));
+        assert!(!record.is_null("phone"));
+        assert!(!record.is_null("id"));
+
+        // Typed reads and get_or_null treat both as "no value".
+        assert_eq!(record.get::<String>("email").unwrap(), None);
+        assert_eq!(record.get::<String>("phone").unwrap(), None);
+        assert_eq!(record.get_or_null("phone"), &serde_json::Value::Null);
+        assert_eq!(record.get_or_null("id"), &serde_json::json!(7));
+    }
+
+    /// Test null placement in column-value ordering
+    #[test]
+    fn test_cmp_json_null_order() {
+        use serde_json::{json, Value};
+        use std::cmp::Ordering;
+
+        assert_eq!(cmp_json(&Value::Null, &json!(1)), Ordering::Greater);
+        assert_eq!(cmp_json(&json!("a"), &Value::Null), Ordering::Less);
+        assert_eq!(cmp_json(&json!(true), &Value::Null), Ordering::Less);
+        assert_eq!(cmp_json(&Value::Null, &Value::Null), Ordering::Equal);
+        assert_eq!(cmp_json_nulls(&Value::Null, &json!(1), NullOrder::First), Ordering::Less);
+        // Composite keys place nulls per column.
+        assert_eq!(cmp_json(&json!([1, null]), &json!([1, 2])), Ordering::Greater);
+        assert_eq!(
+            cmp_json_nulls(&json!([1, null]), &json!([1, 2]), NullOrder::First),
+            Ordering::Less
+        );
+        assert_eq!(cmp_json(&json!(2), &json!(10)), Ordering::Less);
+    }
+
     /// Test Record creation from HashMap
     #[test]
     fn test_record_from_map() {
@@ -254,6 +294,85 @@ fn test_port_schema_array() {
         }
     }
 
+    /// Test validating records against a PortSchema
+    ///
+    /// Required columns must be present and non-null; declared columns must
+    /// have the declared type; other columns pass through
+    #[test]
+    fn test_port_schema_validate_record() {
+        let primitive = |prim_type| Box::new(PortSchema::Primitive { prim_type });
+        let schema = PortSchema::Object {
+            properties: HashMap::from([
+                ("id".to_string(), primitive(PrimitiveType::Integer)),
+                ("price".to_string(), primitive(PrimitiveType::Float)),
+                (
+                    "tags".to_string(),
+                    Box::new(PortSchema::Array { items: primitive(PrimitiveType::String) }),
+                ),
+            ]),
+            required: vec!["id".to_string()],
+        };
+        let record = |fields: serde_json::Value| {
+            Record::from_map(serde_json::from_value(fields).unwrap())
+        };
+
+        assert!(schema.validate_record(&record(serde_json::json!({"id": 1}))).is_ok());
+        // Integers are valid floats; undeclared columns and optional nulls are fine.
+        let ok = record(serde_json::json!({"id": 1, "price": 3, "note": "x", "tags": null}));
+        assert!(schema.validate_record(&ok).is_ok());
+
+        let err = |fields| schema.validate_record(&record(fields)).unwrap_err();
+        assert_eq!(err(serde_json::json!({"price": 1.5})), "missing required column 'id'");
+        assert_eq!(err(serde_json::json!({"id": null})), "required column 'id' is null");
+        assert_eq!(
+            err(serde_json::json!({"id": "7"})),
+            "column 'id' should be Integer, got a string"
+        );
+        assert_eq!(
+            err(serde_json::json!({"id": 1, "tags": ["a", 2]})),
+            "column 'tags[1]' should be String, got an integer"
+        );
+
+        let keyed = PortSchema::required_columns(&["user_id"]);
+        assert!(keyed.validate_record(&record(serde_json::json!({"user_id": "u1"}))).is_ok());
+        assert!(keyed.validate_record(&record(serde_json::json!({"id": 1}))).is_err());
+    }
+
+    /// Test Port validation against its schema
+    ///
+    /// Validation is opt-in: ports without a schema accept anything
+    #[test]
+    fn test_port_validator() {
+        let port = Port {
+            id: "records".to_string(),
+            name: "Records".to_string(),
+            port_type: PortType::DataStream,
+            direction: PortDirection::Input,
+            required: true,
+            multiple: false,
+            description: String::new(),
+            schema: None,
+        };
+        let mut good = Record::new();
+        good.insert("id".to_string(), 1).unwrap();
+        let mut bad = Record::new();
+        bad.insert("id".to_string(), "one").unwrap();
+        let value = PortValue::Stream(vec![good, bad.clone(), bad]);
+
+        assert!(port.validate(&value).valid);
+
+        let typed = port.with_schema(PortSchema::record(&[("id", PrimitiveType::Integer)]));
+        let result = typed.validate(&value);
+        assert_eq!(
+            result.errors,
+            vec![
+                "record 1: column 'id' should be Integer, got a string",
+                "record 2: column 'id' should be Integer, got a string",
+            ]
+        );
+        assert!(typed.validate(&PortValue::None).valid);
+    }
+
     /// Test SignalValue variants
     ///
     /// Signals coordinate control flow between blocks
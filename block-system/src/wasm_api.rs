@@ -4,9 +4,14 @@
 //! - `init_runtime` / `destroy_runtime` — lifecycle
 //! - `register_block` / `create_connection` — graph construction
 //! - `validate` — graph validation
+//! - `validate_graph` — validation of a standalone `GraphDefinition`
 //! - `execute` — run workload with progress reporting
+//! - `run_graph` — run a workload through a standalone `GraphDefinition`
+//! - `run_graph_streaming` — `run_graph` with per-batch metrics snapshots
 //! - `cancel_execution` — cooperative cancellation
 //! - `get_metrics` / `get_block_types` — discovery and results
+//! - `get_block_catalog` — every registered block, for the palette
+//! - `get_block_docs` — one block's documentation
 
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -14,12 +19,14 @@
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
-use crate::categories::buffer::{LRUBufferBlock, ClockBufferBlock};
+use crate::categories::buffer::{LRUBufferBlock, ClockBufferBlock, LRUKBufferBlock, TwoQBufferBlock};
 use crate::categories::compression::DictionaryEncodingBlock;
 use crate::categories::concurrency::{MVCCBlock, RowLockBlock};
 use crate::categories::distribution::ReplicationBlock;
 use crate::categories::execution::{
-    FilterBlock, HashJoinBlock, IndexScanBlock, SequentialScanBlock, SortBlock,
+    AccessPathBlock, AggregateBlock, DistinctBlock, FilterBlock, HashJoinBlock, IndexScanBlock,
+    LimitBlock, MergeJoinBlock, NestedLoopJoinBlock, ProjectionBlock, SequentialScanBlock,
+    SortBlock, UnionBlock,
 };
 use crate::categories::index::{BTreeIndexBlock, CoveringIndexBlock, HashIndexBlock};
 use crate::categories::optimization::{BloomFilterBlock, StatisticsCollectorBlock};
@@ -28,12 +35,16 @@
     ClusteredStorageBlock, ColumnarStorageBlock, HeapFileBlock, LSMTreeBlock,
 };
 use crate::categories::transaction::WALBlock;
-use crate::core::block::Block;
+use crate::core::block::{Block, BlockDocumentation};
 use crate::core::parameter::ParameterValue;
 use crate::core::port::{Connection, PortValue};
+use crate::core::registry::BlockRegistry;
 use crate::runtime::engine::{EngineExecutionResult, ExecutionEngine};
+use crate::runtime::graph::GraphDefinition;
+use crate::runtime::validation::GraphValidationResult;
 use crate::runtime::workload::{
-    Distribution, OperationConfig, OperationType, WorkloadConfig, WorkloadGenerator,
+    ArrivalDistribution, ArrivalModel, Distribution, OperationConfig, OperationMix, OperationType,
+    ScanPattern, WorkloadConfig, WorkloadGenerator,
 };
 
 // ── Trivial async executor for WASM ─────────────────────────────────────────
@@ -118,11 +129,46 @@ struct WorkloadJson {
     total_ops: usize,
     #[serde(default)]
     concurrency: usize,
+    #[serde(default)]
+    seed: u64,
+    /// Arrivals per second; setting it timestamps records with `_arrival_ms`.
+    #[serde(default, rename = "arrivalRate")]
+    arrival_rate: Option<f64>,
+    #[serde(default = "default_arrival_distribution", rename = "arrivalDistribution")]
+    arrival_distribution: String,
+    /// Seconds of arrivals to generate.
+    #[serde(default)]
+    duration: Option<f64>,
+    #[serde(default, rename = "zipfTheta")]
+    zipf_theta: Option<f64>,
+    #[serde(default, rename = "hotAccessFraction")]
+    hot_access_fraction: Option<f64>,
+    #[serde(default, rename = "hotKeyFraction")]
+    hot_key_fraction: Option<f64>,
+    #[serde(default, rename = "scanLength")]
+    scan_length: Option<usize>,
+    #[serde(default, rename = "workingSetSize")]
+    working_set_size: usize,
+    #[serde(default)]
+    interleave: bool,
+    /// Setting this switches to a ratio-driven, `_op`-tagged mix.
+    #[serde(default, rename = "readWriteRatio")]
+    read_write_ratio: Option<f64>,
+    #[serde(default, rename = "deleteRatio")]
+    delete_ratio: f64,
+    #[serde(default, rename = "targetExisting")]
+    target_existing: bool,
+    /// Records per engine round for `run_graph` / `run_graph_streaming`.
+    #[serde(default, rename = "batchSize")]
+    batch_size: Option<usize>,
 }
 
 fn default_distribution() -> String {
     "uniform".into()
 }
+fn default_arrival_distribution() -> String {
+    "poisson".into()
+}
 fn default_total_ops() -> usize {
     1000
 }
@@ -171,6 +217,8 @@ struct ExecutionResponse {
     metrics: MetricsResponse,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     errors: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -185,6 +233,12 @@ struct MetricsResponse {
     failed_operations: usize,
     #[serde(rename = "blockMetrics")]
     block_metrics: Vec<BlockMetricsResponse>,
+    #[serde(rename = "totalSimulatedLatency")]
+    total_simulated_latency: f64,
+    #[serde(rename = "queueingDelay")]
+    queueing_delay: f64,
+    #[serde(rename = "shortCircuited")]
+    short_circuited: bool,
 }
 
 #[derive(Serialize)]
@@ -207,6 +261,51 @@ struct BlockMetricsResponse {
     execution_time: f64,
     percentage: f64,
     counters: HashMap<String, f64>,
+    #[serde(rename = "simulatedLatency")]
+    simulated_latency: f64,
+    #[serde(rename = "queueingDelay")]
+    queueing_delay: f64,
+}
+
+#[derive(Serialize)]
+struct RunGraphResponse {
+    success: bool,
+    duration: f64,
+    /// Each block's `ExecutionResult.metrics`, keyed by block id.
+    #[serde(rename = "blockMetrics")]
+    block_metrics: HashMap<String, HashMap<String, f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RunErrorResponse>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct RunErrorResponse {
+    /// `"parse"`, `"instantiate"` or `"execute"`.
+    stage: &'static str,
+    message: String,
+}
+
+impl RunGraphResponse {
+    fn failed(stage: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            duration: 0.0,
+            block_metrics: HashMap::new(),
+            error: Some(RunErrorResponse { stage, message: message.into() }),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Passed to the `run_graph_streaming` callback after every batch.
+#[derive(Serialize)]
+struct RunGraphSnapshot {
+    batch: usize,
+    batches: usize,
+    #[serde(rename = "blockMetrics")]
+    block_metrics: HashMap<String, HashMap<String, f64>>,
 }
 
 #[derive(Serialize)]
@@ -306,6 +405,14 @@ struct ConstraintsResponse {
     min: Option<f64>,
     max: Option<f64>,
     pattern: Option<String>,
+    #[serde(rename = "allowedValues")]
+    allowed_values: Option<serde_json::Value>,
+    #[serde(rename = "minLength")]
+    min_length: Option<usize>,
+    #[serde(rename = "maxLength")]
+    max_length: Option<usize>,
+    #[serde(rename = "elementType")]
+    element_type: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -340,10 +447,11 @@ struct PortResponse {
 
 fn build_block_detail(block_type_str: &str) -> Result<BlockDetailResponse, String> {
     let block = create_block(block_type_str)?;
-    let meta = block.metadata();
-    let doc = &meta.documentation;
+    Ok(block_detail(block_type_str, block.as_ref()))
+}
 
-    let documentation = BlockDocResponse {
+fn block_doc(doc: &BlockDocumentation) -> BlockDocResponse {
+    BlockDocResponse {
         overview: doc.overview.clone(),
         algorithm: doc.algorithm.clone(),
         complexity: ComplexityResponse {
@@ -360,7 +468,12 @@ fn build_block_detail(block_type_str: &str) -> Result<BlockDetailResponse, Strin
             comparison: a.comparison.clone(),
         }).collect(),
         suggested_questions: doc.suggested_questions.clone(),
-    };
+    }
+}
+
+fn block_detail(block_type_str: &str, block: &dyn Block) -> BlockDetailResponse {
+    let meta = block.metadata();
+    let documentation = block_doc(&meta.documentation);
 
     let references = meta.references.iter().map(|r| {
         ReferenceResponse {
@@ -377,13 +490,19 @@ fn build_block_detail(block_type_str: &str) -> Result<BlockDetailResponse, Strin
             ParameterValue::Number(n) => serde_json::json!(*n),
             ParameterValue::Integer(i) => serde_json::json!(*i),
             ParameterValue::Boolean(b) => serde_json::json!(*b),
+            ParameterValue::Array(_) | ParameterValue::Object(_) => {
+                serde_json::to_value(&p.default_value).unwrap_or(serde_json::Value::Null)
+            }
             ParameterValue::Null => serde_json::Value::Null,
-            _ => serde_json::Value::Null,
         };
         let constraints = p.constraints.as_ref().map(|c| ConstraintsResponse {
             min: c.min,
             max: c.max,
             pattern: c.pattern.clone(),
+            allowed_values: c.allowed_values.as_ref().and_then(|v| serde_json::to_value(v).ok()),
+            min_length: c.min_length,
+            max_length: c.max_length,
+            element_type: c.element_type.as_ref().map(|t| format!("{:?}", t)),
         });
         let ui_hint = p.ui_hint.as_ref().map(|h| UIHintResponse {
             widget: format!("{:?}", h.widget),
@@ -429,7 +548,7 @@ fn build_block_detail(block_type_str: &str) -> Result<BlockDetailResponse, Strin
         description: p.description.clone(),
     }).collect();
 
-    Ok(BlockDetailResponse {
+    BlockDetailResponse {
         block_type: block_type_str.to_string(),
         name: meta.name.clone(),
         category: format!("{:?}", meta.category),
@@ -443,7 +562,7 @@ fn build_block_detail(block_type_str: &str) -> Result<BlockDetailResponse, Strin
         outputs,
         icon: meta.icon.clone(),
         color: meta.color.clone(),
-    })
+    }
 }
 
 // ── Block factory ───────────────────────────────────────────────────────────
@@ -463,10 +582,20 @@ fn create_block(block_type: &str) -> Result<Box<dyn Block>, String> {
         "filter" => Ok(Box::new(FilterBlock::new())),
         "sort" => Ok(Box::new(SortBlock::new())),
         "hash_join" => Ok(Box::new(HashJoinBlock::new())),
+        "merge_join" | "sort_merge_join" => Ok(Box::new(MergeJoinBlock::new())),
+        "nested_loop_join" | "nl_join" => Ok(Box::new(NestedLoopJoinBlock::new())),
+        "projection" | "project" => Ok(Box::new(ProjectionBlock::new())),
+        "aggregate" | "hash_aggregate" => Ok(Box::new(AggregateBlock::new())),
+        "limit" => Ok(Box::new(LimitBlock::new())),
+        "access_path" => Ok(Box::new(AccessPathBlock::new())),
+        "distinct" | "dedup" => Ok(Box::new(DistinctBlock::new())),
+        "union" | "union_all" => Ok(Box::new(UnionBlock::new())),
         "row_lock" | "row_lock_2pl" => Ok(Box::new(RowLockBlock::new())),
         "mvcc" => Ok(Box::new(MVCCBlock::new())),
         "wal" | "write_ahead_log" => Ok(Box::new(WALBlock::new())),
         "clock_buffer" | "clock_cache" => Ok(Box::new(ClockBufferBlock::new())),
+        "lru_k_buffer" | "lru_k" => Ok(Box::new(LRUKBufferBlock::new())),
+        "two_q_buffer" | "2q_buffer" => Ok(Box::new(TwoQBufferBlock::new())),
         "bloom_filter" => Ok(Box::new(BloomFilterBlock::new())),
         "statistics_collector" | "stats_collector" => Ok(Box::new(StatisticsCollectorBlock::new())),
         "hash_partitioner" => Ok(Box::new(HashPartitionerBlock::new())),
@@ -475,8 +604,10 @@ fn create_block(block_type: &str) -> Result<Box<dyn Block>, String> {
         _ => Err(format!(
             "Unknown block type: '{}'. Available: heap_storage, lsm_tree, clustered_storage, \
              columnar_storage, btree_index, hash_index, covering_index, lru_buffer, clock_buffer, \
-             sequential_scan, index_scan, filter, sort, hash_join, row_lock, mvcc, wal, \
-             bloom_filter, statistics_collector, hash_partitioner, replication, dictionary_encoding",
+             lru_k_buffer, two_q_buffer, sequential_scan, index_scan, filter, sort, hash_join, \
+             merge_join, nested_loop_join, projection, aggregate, limit, access_path, distinct, \
+             union, row_lock, mvcc, wal, bloom_filter, statistics_collector, hash_partitioner, \
+             replication, dictionary_encoding",
             block_type
         )),
     }
@@ -484,28 +615,79 @@ fn create_block(block_type: &str) -> Result<Box<dyn Block>, String> {
 
 fn convert_parameters(raw: &HashMap<String, serde_json::Value>) -> HashMap<String, ParameterValue> {
     raw.iter()
-        .filter_map(|(k, v)| {
-            let pv = match v {
-                serde_json::Value::Number(n) => {
-                    if let Some(i) = n.as_i64() {
-                        Some(ParameterValue::Integer(i))
-                    } else {
-                        n.as_f64().map(ParameterValue::Number)
-                    }
-                }
-                serde_json::Value::String(s) => Some(ParameterValue::String(s.clone())),
-                serde_json::Value::Bool(b) => Some(ParameterValue::Boolean(*b)),
-                _ => None,
-            };
-            pv.map(|val| (k.clone(), val))
-        })
+        .filter_map(|(k, v)| convert_parameter(v).map(|val| (k.clone(), val)))
         .collect()
 }
 
-fn parse_distribution(s: &str) -> Distribution {
-    match s.to_lowercase().as_str() {
-        "zipfian" | "zipf" => Distribution::Zipfian,
+fn convert_parameter(v: &serde_json::Value) -> Option<ParameterValue> {
+    match v {
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Some(ParameterValue::Integer(i))
+            } else {
+                n.as_f64().map(ParameterValue::Number)
+            }
+        }
+        serde_json::Value::String(s) => Some(ParameterValue::String(s.clone())),
+        serde_json::Value::Bool(b) => Some(ParameterValue::Boolean(*b)),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(convert_parameter)
+            .collect::<Option<Vec<_>>>()
+            .map(ParameterValue::Array),
+        serde_json::Value::Object(fields) => fields
+            .iter()
+            .map(|(k, v)| convert_parameter(v).map(|v| (k.clone(), v)))
+            .collect::<Option<HashMap<_, _>>>()
+            .map(ParameterValue::Object),
+        _ => None,
+    }
+}
+
+fn workload_config(wj: &WorkloadJson) -> WorkloadConfig {
+    WorkloadConfig {
+        operations: wj
+            .operations
+            .iter()
+            .map(|o| OperationConfig {
+                op_type: parse_op_type(&o.op_type),
+                weight: o.weight,
+            })
+            .collect(),
+        distribution: parse_distribution(wj),
+        total_ops: wj.total_ops,
+        seed: wj.seed,
+        mix: wj.read_write_ratio.map(|read_write_ratio| OperationMix {
+            read_write_ratio,
+            delete_ratio: wj.delete_ratio,
+            target_existing: wj.target_existing,
+        }),
+        arrival: wj.arrival_rate.map(|rate| ArrivalModel {
+            rate,
+            distribution: match wj.arrival_distribution.to_lowercase().as_str() {
+                "constant" | "fixed" => ArrivalDistribution::Constant,
+                _ => ArrivalDistribution::Poisson,
+            },
+            duration: wj.duration,
+        }),
+    }
+}
+
+fn parse_distribution(wj: &WorkloadJson) -> Distribution {
+    match wj.distribution.to_lowercase().as_str() {
+        "zipfian" | "zipf" => Distribution::zipfian(
+            wj.zipf_theta.unwrap_or(Distribution::DEFAULT_ZIPF_THETA),
+        ),
         "latest" | "recent" => Distribution::Latest,
+        "hotspot" => Distribution::hotspot(
+            wj.hot_access_fraction.unwrap_or(0.8),
+            wj.hot_key_fraction.unwrap_or(0.2),
+        ),
+        "sequential" | "scan" => Distribution::Sequential(ScanPattern {
+            scan_length: wj.scan_length.unwrap_or(1000),
+            working_set_size: wj.working_set_size,
+            interleave: wj.interleave,
+        }),
         _ => Distribution::Uniform,
     }
 }
@@ -617,20 +799,7 @@ pub fn validate() -> String {
         rt.engine.auto_detect_entry_points();
         rt.engine.validate()
     }) {
-        Ok(v) => serde_json::to_string(&ValidationResponse {
-            valid: v.valid,
-            errors: v.errors.iter().map(|e| ValidationItemResponse {
-                message: e.message.clone(),
-                node_id: e.node_id.clone(),
-                suggestion: e.suggestion.clone(),
-            }).collect(),
-            warnings: v.warnings.iter().map(|w| ValidationItemResponse {
-                message: w.message.clone(),
-                node_id: w.node_id.clone(),
-                suggestion: w.suggestion.clone(),
-            }).collect(),
-        })
-        .unwrap_or_default(),
+        Ok(v) => serde_json::to_string(&validation_response(&v)).unwrap_or_default(),
         Err(e) => serde_json::to_string(&ValidationResponse {
             valid: false,
             errors: vec![ValidationItemResponse {
@@ -644,6 +813,23 @@ pub fn validate() -> String {
     }
 }
 
+/// Validate a graph given as `GraphDefinition` JSON, without touching the
+/// runtime's graph. Returns the same shape as `validate`, as a JS object.
+/// Block types may be metadata ids (`"lsm-tree-storage"`) or the type
+/// strings `register_block` takes (`"lsm_tree"`).
+#[wasm_bindgen]
+pub fn validate_graph(graph_json: &str) -> JsValue {
+    let result = match GraphDefinition::from_json(graph_json) {
+        Ok(mut graph) => {
+            let registry = BlockRegistry::with_builtin_blocks();
+            resolve_block_types(&mut graph, &registry);
+            block_on(graph.validate(&registry))
+        }
+        Err(e) => GraphValidationResult::failed(e.to_string()),
+    };
+    to_js(&validation_response(&result))
+}
+
 #[wasm_bindgen]
 pub fn execute(workload_json: &str, progress_callback: &js_sys::Function) -> String {
     let wj: WorkloadJson = match serde_json::from_str(workload_json) {
@@ -654,6 +840,7 @@ pub fn execute(workload_json: &str, progress_callback: &js_sys::Function) -> Str
                 duration: 0.0,
                 metrics: empty_metrics(),
                 errors: vec![format!("Invalid workload JSON: {}", e)],
+                warnings: vec![],
             })
             .unwrap_or_default();
         }
@@ -664,22 +851,7 @@ pub fn execute(workload_json: &str, progress_callback: &js_sys::Function) -> Str
     match with_runtime(|rt| {
         rt.engine.auto_detect_entry_points();
 
-        // Build workload config.
-        let config = WorkloadConfig {
-            operations: wj
-                .operations
-                .iter()
-                .map(|o| OperationConfig {
-                    op_type: parse_op_type(&o.op_type),
-                    weight: o.weight,
-                })
-                .collect(),
-            distribution: parse_distribution(&wj.distribution),
-            total_ops: wj.total_ops,
-            seed: 0,
-        };
-
-        let records = WorkloadGenerator::generate_records(&config);
+        let records = WorkloadGenerator::generate_records(&workload_config(&wj));
 
         report_progress(progress_callback, 10.0, "executing", None, "Starting execution...");
 
@@ -717,11 +889,46 @@ pub fn execute(workload_json: &str, progress_callback: &js_sys::Function) -> Str
             duration: 0.0,
             metrics: empty_metrics(),
             errors: vec![e],
+            warnings: vec![],
         })
         .unwrap_or_default(),
     }
 }
 
+/// Run a workload through a graph given as `GraphDefinition` JSON, without
+/// touching the runtime's graph, and return each block's metrics as a JS
+/// object: `{ success, duration, blockMetrics: { [blockId]: { [metric]:
+/// value } } }`. A failure — bad JSON, a block that won't instantiate, an
+/// invalid graph, a fatal block error — sets `success: false` and an
+/// `error: { stage, message }` naming the step that failed; non-fatal block
+/// errors come back in `error` too, with `success` still true.
+///
+/// Every block no connection feeds gets the workload's records on its
+/// first input port.
+#[wasm_bindgen]
+pub fn run_graph(graph_json: &str, workload_json: &str) -> JsValue {
+    to_js(&run_graph_response(graph_json, workload_json, 0, |_| {}))
+}
+
+/// `run_graph`, in rounds of the workload's `batchSize` records (100 if
+/// unset), calling `callback` after each round with `{ batch, batches,
+/// blockMetrics }` — every block's metrics folded so far — and returning the
+/// same final result as `run_graph`.
+///
+/// The callback runs synchronously on the calling thread, between rounds.
+/// An exception it throws is swallowed so the run carries on.
+#[wasm_bindgen]
+pub fn run_graph_streaming(
+    graph_json: &str,
+    workload_json: &str,
+    callback: &js_sys::Function,
+) -> JsValue {
+    let response = run_graph_response(graph_json, workload_json, DEFAULT_STREAM_BATCH_SIZE, |snapshot| {
+        let _ = callback.call1(&JsValue::NULL, &to_js(&snapshot));
+    });
+    to_js(&response)
+}
+
 #[wasm_bindgen]
 pub fn cancel_execution() {
     let _ = with_runtime(|rt| {
@@ -825,6 +1032,55 @@ pub fn get_block_types() -> String {
             category: "Execution".into(),
             description: "Build-probe hash join for equi-join queries".into(),
         },
+        BlockTypeInfo {
+            block_type: "merge_join".into(),
+            name: "Merge Join".into(),
+            category: "Execution".into(),
+            description: "Sort-merge join over inputs already sorted on the join keys".into(),
+        },
+        BlockTypeInfo {
+            block_type: "nested_loop_join".into(),
+            name: "Nested Loop Join".into(),
+            category: "Execution".into(),
+            description: "Join on any predicate, optionally probing an index on the inner side"
+                .into(),
+        },
+        BlockTypeInfo {
+            block_type: "projection".into(),
+            name: "Projection".into(),
+            category: "Execution".into(),
+            description: "Keep only selected columns, with optional renaming".into(),
+        },
+        BlockTypeInfo {
+            block_type: "aggregate".into(),
+            name: "Aggregate".into(),
+            category: "Execution".into(),
+            description: "Hash GROUP BY with COUNT, SUM, AVG, MIN and MAX".into(),
+        },
+        BlockTypeInfo {
+            block_type: "limit".into(),
+            name: "Limit".into(),
+            category: "Execution".into(),
+            description: "LIMIT/OFFSET that stops upstream work once satisfied".into(),
+        },
+        BlockTypeInfo {
+            block_type: "access_path".into(),
+            name: "Access Path".into(),
+            category: "Execution".into(),
+            description: "Chooses an index or sequential scan from estimated selectivity".into(),
+        },
+        BlockTypeInfo {
+            block_type: "distinct".into(),
+            name: "Distinct".into(),
+            category: "Execution".into(),
+            description: "Removes duplicate rows by hashing or adjacent comparison".into(),
+        },
+        BlockTypeInfo {
+            block_type: "union".into(),
+            name: "Union".into(),
+            category: "Execution".into(),
+            description: "UNION ALL over any number of inputs, or UNION with distinct".into(),
+        },
         // Concurrency
         BlockTypeInfo {
             block_type: "row_lock".into(),
@@ -852,6 +1108,18 @@ pub fn get_block_types() -> String {
             category: "Buffer".into(),
             description: "Page cache with CLOCK (second-chance) eviction — used by PostgreSQL".into(),
         },
+        BlockTypeInfo {
+            block_type: "lru_k_buffer".into(),
+            name: "LRU-K Buffer Pool".into(),
+            category: "Buffer".into(),
+            description: "Scan-resistant page cache that evicts by K-th most recent access".into(),
+        },
+        BlockTypeInfo {
+            block_type: "two_q_buffer".into(),
+            name: "2Q Buffer Pool".into(),
+            category: "Buffer".into(),
+            description: "Scan-resistant page cache with a FIFO probation queue and an LRU main queue".into(),
+        },
         // Optimization
         BlockTypeInfo {
             block_type: "bloom_filter".into(),
@@ -904,8 +1172,9 @@ pub fn get_all_block_details() -> String {
     let type_strings = [
         "heap_storage", "lsm_tree", "clustered_storage", "columnar_storage",
         "btree_index", "hash_index", "covering_index", "lru_buffer", "clock_buffer",
-        "sequential_scan", "index_scan", "filter", "sort", "hash_join",
-        "row_lock", "mvcc", "wal",
+        "lru_k_buffer", "two_q_buffer", "sequential_scan", "index_scan", "filter", "sort",
+        "hash_join", "merge_join", "nested_loop_join", "projection", "aggregate", "limit",
+        "access_path", "distinct", "union", "row_lock", "mvcc", "wal",
         "bloom_filter", "statistics_collector", "hash_partitioner", "replication",
         "dictionary_encoding",
     ];
@@ -916,8 +1185,135 @@ pub fn get_all_block_details() -> String {
     serde_json::to_string(&details).unwrap_or_default()
 }
 
+/// Every block registered in `BlockRegistry::with_builtin_blocks`, with its
+/// metadata, parameters, metrics and ports, for the frontend palette.
+///
+/// Blocks are keyed by their registry id and sorted by it, so the list
+/// tracks the registry without a hand-maintained type table.
+#[wasm_bindgen]
+pub fn get_block_catalog() -> JsValue {
+    let registry = BlockRegistry::with_builtin_blocks();
+    let catalog: Vec<BlockDetailResponse> = registry
+        .factory_ids()
+        .iter()
+        .filter_map(|id| registry.create(id).map(|block| block_detail(id, block.as_ref())))
+        .collect();
+    to_js(&catalog)
+}
+
+/// Documentation for one block, for the detail pane.
+///
+/// `block_id` may be a registry id (`"lsm-tree-storage"`) or one of the
+/// type strings accepted by `register_block` (`"lsm_tree"`). Returns `null`
+/// when neither matches.
+#[wasm_bindgen]
+pub fn get_block_docs(block_id: &str) -> JsValue {
+    let registry = BlockRegistry::with_builtin_blocks();
+    let block = registry.create(block_id).or_else(|| create_block(block_id).ok());
+    match block {
+        Some(block) => to_js(&block_doc(&block.metadata().documentation)),
+        None => JsValue::NULL,
+    }
+}
+
 // ── Internal helpers ────────────────────────────────────────────────────────
 
+fn validation_response(v: &GraphValidationResult) -> ValidationResponse {
+    ValidationResponse {
+        valid: v.valid,
+        errors: v.errors.iter().map(|e| ValidationItemResponse {
+            message: e.message.clone(),
+            node_id: e.node_id.clone(),
+            suggestion: e.suggestion.clone(),
+        }).collect(),
+        warnings: v.warnings.iter().map(|w| ValidationItemResponse {
+            message: w.message.clone(),
+            node_id: w.node_id.clone(),
+            suggestion: w.suggestion.clone(),
+        }).collect(),
+    }
+}
+
+/// Rewrite block types the registry doesn't know but `create_block` does
+/// (the frontend's type strings) to the block's metadata id.
+fn resolve_block_types(graph: &mut GraphDefinition, registry: &BlockRegistry) {
+    for def in &mut graph.blocks {
+        if registry.create(&def.block_type).is_none() {
+            if let Ok(block) = create_block(&def.block_type) {
+                def.block_type = block.metadata().id.clone();
+            }
+        }
+    }
+}
+
+/// Records per round for `run_graph_streaming` when the workload sets no
+/// `batchSize`.
+const DEFAULT_STREAM_BATCH_SIZE: usize = 100;
+
+fn run_graph_response(
+    graph_json: &str,
+    workload_json: &str,
+    default_batch_size: usize,
+    mut on_batch: impl FnMut(RunGraphSnapshot),
+) -> RunGraphResponse {
+    let mut graph = match GraphDefinition::from_json(graph_json) {
+        Ok(g) => g,
+        Err(e) => return RunGraphResponse::failed("parse", e.to_string()),
+    };
+    let wj: WorkloadJson = match serde_json::from_str(workload_json) {
+        Ok(w) => w,
+        Err(e) => return RunGraphResponse::failed("parse", format!("Invalid workload JSON: {}", e)),
+    };
+
+    let registry = BlockRegistry::with_builtin_blocks();
+    resolve_block_types(&mut graph, &registry);
+    let (blocks, connections) = match block_on(graph.instantiate(&registry)) {
+        Ok(built) => built,
+        Err(e) => return RunGraphResponse::failed("instantiate", e.to_string()),
+    };
+
+    let records = WorkloadGenerator::generate_records(&workload_config(&wj));
+    let mut input = HashMap::new();
+    for (id, block) in &blocks {
+        let fed = connections.iter().any(|c| &c.target_block_id == id);
+        if let (false, Some(port)) = (fed, block.inputs().first()) {
+            input.insert((id.clone(), port.id.clone()), PortValue::Stream(records.clone()));
+        }
+    }
+
+    let batch_size = wj.batch_size.unwrap_or(default_batch_size);
+    let run = block_on(ExecutionEngine::run_batched(blocks, connections, input, batch_size, |progress| {
+        on_batch(RunGraphSnapshot {
+            batch: progress.batch,
+            batches: progress.batches,
+            block_metrics: progress
+                .results
+                .iter()
+                .map(|(id, result)| (id.clone(), result.metrics.clone()))
+                .collect(),
+        });
+    }));
+    let summary = run.summary;
+    RunGraphResponse {
+        success: summary.success,
+        duration: summary.duration_ms,
+        block_metrics: run.results.into_iter().map(|(id, result)| (id, result.metrics)).collect(),
+        error: (!summary.errors.is_empty()).then(|| RunErrorResponse {
+            stage: "execute",
+            message: summary.errors.join("; "),
+        }),
+        warnings: summary.warnings,
+    }
+}
+
+/// Serialize `value` to a plain JS object (`null` if that fails).
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    serde_json::to_string(value)
+        .ok()
+        .and_then(|json| js_sys::JSON::parse(&json).ok())
+        .unwrap_or(JsValue::NULL)
+}
+
 fn report_progress(
     callback: &js_sys::Function,
     progress: f64,
@@ -961,10 +1357,16 @@ fn build_execution_response(exec: &EngineExecutionResult) -> ExecutionResponse {
                     execution_time: bm.execution_time_ms,
                     percentage: bm.percentage,
                     counters: bm.counters.clone(),
+                    simulated_latency: bm.simulated_latency_ms,
+                    queueing_delay: bm.queueing_delay_ms,
                 })
                 .collect(),
+            total_simulated_latency: exec.metrics.total_simulated_latency_ms,
+            queueing_delay: exec.metrics.queueing_delay_ms,
+            short_circuited: exec.metrics.short_circuited,
         },
         errors: exec.errors.clone(),
+        warnings: exec.warnings.clone(),
     }
 }
 
@@ -981,5 +1383,8 @@ fn empty_metrics() -> MetricsResponse {
         successful_operations: 0,
         failed_operations: 0,
         block_metrics: Vec::new(),
+        total_simulated_latency: 0.0,
+        queueing_delay: 0.0,
+        short_circuited: false,
     }
 }
@@ -34,6 +34,8 @@ const DISTRIBUTIONS: { value: Distribution; label: string; description: string }
   { value: 'zipfian', label: 'Zipfian', description: 'Hot keys — realistic OLTP' },
   { value: 'uniform', label: 'Uniform', description: 'All keys equally likely' },
   { value: 'latest', label: 'Latest', description: 'Recent records more likely' },
+  { value: 'hotspot', label: 'Hotspot', description: '80% of accesses hit 20% of keys' },
+  { value: 'sequential', label: 'Sequential', description: 'Pages scanned in order' },
 ];
 
 function getOpColor(type: OperationType): string {
@@ -311,8 +311,8 @@ function buildCassandra(): DBArchitecture {
   const schema = makeNode('schema_definition', 0, 200, { tableName: 'events', columns: 'partition_key:text,timestamp:bigint,data:text' });
   const lsm = makeNode('lsm_tree', 250, 200, { memtableSize: 64, levelMultiplier: 10 });
   const bloom = makeNode('bloom_filter', 500, 50, { num_bits: 100000, num_hash_functions: 10 });
-  const partitioner = makeNode('hash_partitioner', 500, 200, { num_partitions: 8 });
-  const replication = makeNode('replication', 750, 200, { replication_factor: 3, consistency_level: 'quorum' });
+  const partitioner = makeNode('hash_partitioner', 500, 200, { partition_count: 8 });
+  const replication = makeNode('replication', 750, 200, { replica_count: 3, consistency_level: 'quorum' });
 
   return {
     id: 'cassandra',
@@ -373,7 +373,7 @@ function buildRocksDB(): DBArchitecture {
   const lsm = makeNode('lsm_tree', 250, 200, { memtableSize: 64, levelMultiplier: 10, bloomFilterBits: 10 });
   const bloom = makeNode('bloom_filter', 500, 50, { num_bits: 100000, num_hash_functions: 10 });
   const buffer = makeNode('lru_buffer', 500, 200, { size: 256 });
-  const dict = makeNode('dictionary_encoding', 500, 350, { max_dictionary_size: 16384 });
+  const dict = makeNode('dictionary_encoding', 500, 350, { max_dictionary_entries: 16384 });
 
   return {
     id: 'rocksdb',
@@ -484,7 +484,7 @@ function buildMongoDB(): DBArchitecture {
   const btree = makeNode('btree_index', 250, 0, { keyColumn: '_id', fanout: 128 });
   const buffer = makeNode('lru_buffer', 500, 100, { size: 256 });
   const wal = makeNode('wal', 500, 300, { bufferSize: 16, syncMode: 'fsync' });
-  const replication = makeNode('replication', 750, 200, { replication_factor: 3, consistency_level: 'quorum' });
+  const replication = makeNode('replication', 750, 200, { replica_count: 3, consistency_level: 'quorum' });
 
   return {
     id: 'mongodb',
@@ -539,8 +539,8 @@ function buildDynamoDB(): DBArchitecture {
   nodeSeq = 0;
   const schema = makeNode('schema_definition', 0, 200, { tableName: 'items', columns: 'pk:text,sk:text,data:json' });
   const lsm = makeNode('lsm_tree', 250, 200, { memtableSize: 64, levelMultiplier: 10 });
-  const partitioner = makeNode('hash_partitioner', 500, 100, { num_partitions: 16 });
-  const replication = makeNode('replication', 500, 300, { replication_factor: 3, consistency_level: 'quorum' });
+  const partitioner = makeNode('hash_partitioner', 500, 100, { partition_count: 16 });
+  const replication = makeNode('replication', 500, 300, { replica_count: 3, consistency_level: 'quorum' });
   const buffer = makeNode('lru_buffer', 750, 200, { size: 128 });
 
   return {
@@ -188,7 +188,12 @@ const METRIC_GENERATORS: Record<string, MetricGen> = {
     estimateMs: (_p, ops) => ops * rand(0.01, 0.03),
     counters: (_p, ops) => {
       const selectivity = rand(0.1, 0.5);
-      return { rows_in: ops, rows_out: Math.ceil(ops * selectivity), selectivity_pct: Math.round(selectivity * 100) };
+      return {
+        rows_in: ops,
+        rows_out: Math.ceil(ops * selectivity),
+        selectivity_pct: Math.round(selectivity * 100),
+        observed_selectivity: selectivity,
+      };
     },
   },
   sort: {
@@ -199,11 +204,98 @@ const METRIC_GENERATORS: Record<string, MetricGen> = {
     },
     counters: (p, ops) => {
       const mem = Number(p.memoryLimit ?? 256);
+      const fanIn = Math.max(2, Number(p.merge_fan_in ?? 8));
       const needsExternal = ops > mem * 5000;
+      const runs = needsExternal ? Math.ceil(ops / (mem * 5000)) : 0;
+      const passes = needsExternal ? Math.ceil(Math.log(runs) / Math.log(fanIn)) : 0;
       return {
         comparisons: Math.ceil(ops * Math.log2(ops)),
         external_sort: needsExternal ? 1 : 0,
-        temp_files: needsExternal ? Math.ceil(ops / (mem * 5000)) : 0,
+        temp_files: runs,
+        merge_passes: passes,
+        bytes_spilled: ops * 100 * passes,
+      };
+    },
+  },
+  merge_join: {
+    estimateMs: (_p, ops) => ops * rand(0.01, 0.03),
+    counters: (_p, ops) => ({
+      left_rows: Math.ceil(ops * 0.5),
+      right_rows: Math.ceil(ops * 0.5),
+      comparisons: ops,
+      rows_emitted: Math.ceil(ops * 0.25),
+    }),
+  },
+  nested_loop_join: {
+    estimateMs: (_p, ops) => ops * rand(0.05, 0.2),
+    counters: (_p, ops) => ({
+      inner_scans: Math.ceil(ops * 0.1),
+      index_lookups: 0,
+      rows_compared: Math.ceil(ops * ops * 0.05),
+      rows_emitted: Math.ceil(ops * 0.25),
+    }),
+  },
+  projection: {
+    estimateMs: (_p, ops) => ops * rand(0.005, 0.015),
+    counters: (p, ops) => {
+      const kept = String(p.columns ?? 'id').split(',').length;
+      const bytesIn = ops * 200;
+      const bytesOut = ops * Math.min(200, kept * 24);
+      return { rows_projected: ops, bytes_in: bytesIn, bytes_out: bytesOut, bytes_reduced: bytesIn - bytesOut };
+    },
+  },
+  aggregate: {
+    estimateMs: (_p, ops) => ops * rand(0.01, 0.03),
+    counters: (p, ops) => {
+      const grouped = String(p.group_by ?? '').trim() !== '';
+      return { rows_aggregated: ops, groups_produced: grouped ? Math.ceil(ops * rand(0.01, 0.1)) : 1 };
+    },
+  },
+  limit: {
+    estimateMs: (_p, ops) => ops * rand(0.001, 0.003),
+    counters: (p, ops) => {
+      const limit = Number(p.limit ?? 10);
+      const offset = Number(p.offset ?? 0);
+      const skipped = Math.min(offset, ops);
+      const emitted = Math.min(limit, ops - skipped);
+      return { rows_skipped: skipped, rows_emitted: emitted, rows_discarded: ops - skipped - emitted };
+    },
+  },
+  distinct: {
+    estimateMs: (_p, ops) => ops * rand(0.005, 0.015),
+    counters: (p, ops) => {
+      const out = Math.ceil(ops * rand(0.2, 0.9));
+      return {
+        rows_in: ops,
+        rows_out: out,
+        duplicates_removed: ops - out,
+        keys_in_memory: p.method === 'sorted' ? 1 : out,
+      };
+    },
+  },
+  union: {
+    estimateMs: (_p, ops) => ops * rand(0.001, 0.005),
+    counters: (p, ops) => {
+      const out = p.distinct ? Math.ceil(ops * rand(0.6, 0.95)) : ops;
+      return { inputs_merged: 2, rows_in: ops, rows_out: out, duplicates_removed: ops - out };
+    },
+  },
+  access_path: {
+    estimateMs: (_p, ops) => ops * rand(0.002, 0.01),
+    counters: (p, ops) => {
+      const randomPageCost = Number(p.random_page_cost ?? 4);
+      const pages = Math.ceil(ops / Number(p.records_per_page ?? 100));
+      const rows = Math.ceil(ops * rand(0.001, 0.3));
+      const seqCost = pages;
+      const indexCost = rows * randomPageCost;
+      const useIndex = p.access_path === 'index' || (p.access_path !== 'sequential' && indexCost < seqCost);
+      return {
+        chosen_path: useIndex ? 1 : 0,
+        actual_rows: rows,
+        pages_read: useIndex ? Math.min(rows, pages) : pages,
+        random_ios: useIndex ? rows : 0,
+        io_cost: useIndex ? indexCost : seqCost,
+        alternative_io_cost: useIndex ? seqCost : indexCost,
       };
     },
   },
@@ -164,6 +164,7 @@ export class WASMExecutionEngine implements ExecutionEngine {
         },
         blockMetrics: [],
         errors: rawResult.errors ?? ['WASM execution failed.'],
+        warnings: rawResult.warnings,
       };
     }
 
@@ -176,6 +177,8 @@ export class WASMExecutionEngine implements ExecutionEngine {
       executionTime: bm.executionTime,
       percentage: bm.percentage,
       counters: bm.counters,
+      simulatedLatency: bm.simulatedLatency,
+      queueingDelay: bm.queueingDelay,
     }));
 
     return {
@@ -187,8 +190,12 @@ export class WASMExecutionEngine implements ExecutionEngine {
         totalOperations: wm.totalOperations,
         successfulOperations: wm.successfulOperations,
         failedOperations: wm.failedOperations,
+        totalSimulatedLatency: wm.totalSimulatedLatency,
+        queueingDelay: wm.queueingDelay,
+        shortCircuited: wm.shortCircuited,
       },
       blockMetrics,
+      warnings: rawResult.warnings,
     };
   }
 
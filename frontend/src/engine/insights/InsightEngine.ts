@@ -30,6 +30,14 @@ const BLOCK_CATEGORY_MAP: Record<string, Category> = {
   filter: 'execution',
   sort: 'execution',
   hash_join: 'execution',
+  merge_join: 'execution',
+  nested_loop_join: 'execution',
+  projection: 'execution',
+  aggregate: 'execution',
+  limit: 'execution',
+  access_path: 'execution',
+  distinct: 'execution',
+  union: 'execution',
   row_lock: 'concurrency',
   mvcc: 'concurrency',
   wal: 'transaction',
@@ -555,7 +563,7 @@ export class InsightEngine {
       const violations = bm.counters['consistency_violations'] ?? 0;
 
       const node = this.findNodeByBlockId(bm.blockId);
-      const rf = node ? Number((node.data as BlockNodeData).parameters['replication_factor'] ?? 3) : 3;
+      const rf = node ? Number((node.data as BlockNodeData).parameters['replica_count'] ?? 3) : 3;
       const cl = node ? String((node.data as BlockNodeData).parameters['consistency_level'] ?? 'quorum') : 'quorum';
 
       if (rf >= 3 && cl === 'all') {
@@ -565,7 +573,7 @@ export class InsightEngine {
           severity: 'important',
           title: `Full Consistency with ${rf} Replicas Adds Latency`,
           explanation:
-            `With replication_factor=${rf} and consistency=ALL, every write must wait for ALL ${rf} replicas to acknowledge. ` +
+            `With replica_count=${rf} and consistency=ALL, every write must wait for ALL ${rf} replicas to acknowledge. ` +
             `This added ${fmtMs(lagMs)} of replication latency across ${fmtNum(replicated)} writes.`,
           whyItMatters:
             'This is the CAP theorem in action. Requiring all replicas to acknowledge trades availability for consistency — ' +
@@ -625,7 +633,7 @@ export class InsightEngine {
       if (partitioned === 0) continue;
 
       const node = this.findNodeByBlockId(bm.blockId);
-      const numPartitions = node ? Number((node.data as BlockNodeData).parameters['num_partitions'] ?? 8) : 8;
+      const numPartitions = node ? Number((node.data as BlockNodeData).parameters['partition_count'] ?? 8) : 8;
 
       if (hottest > 30) {
         insights.push({
@@ -675,7 +683,7 @@ export class InsightEngine {
       const encoded = bm.counters['entries_encoded'] ?? 0;
       const dictSize = bm.counters['dictionary_size'] ?? 0;
       const ratio = bm.counters['compression_ratio'] ?? 1;
-      const fullEvents = bm.counters['dictionary_full_events'] ?? 0;
+      const fullEvents = bm.counters['dictionary_overflows'] ?? 0;
 
       if (encoded === 0) continue;
 
@@ -711,7 +719,7 @@ export class InsightEngine {
             'When the dictionary is full, new unique values can\'t benefit from compression. ' +
             'This typically means the column has higher cardinality than expected.',
           suggestion:
-            'Increase max_dictionary_size, or reconsider whether this column benefits from dictionary encoding. ' +
+            'Increase max_dictionary_entries, or reconsider whether this column benefits from dictionary encoding. ' +
             'High-cardinality columns (like UUIDs) are poor candidates.',
           learnMore: { blockType: 'dictionary_encoding', section: 'tradeoffs' },
         });
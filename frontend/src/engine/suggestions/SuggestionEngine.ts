@@ -81,7 +81,7 @@ export class SuggestionEngine {
     } else if (msg.includes('disconnected') || msg.includes('not connected to any other block')) {
       enriched = this.suggestRemoveBlock(item);
     } else if (msg.includes('cycle')) {
-      enriched = this.suggestBreakCycle();
+      enriched = this.suggestBreakCycle(item);
     } else if (msg.includes('empty') && msg.includes('block')) {
       enriched = this.suggestAddStorageBlock();
     } else if (msg.includes('storage block')) {
@@ -201,11 +201,32 @@ export class SuggestionEngine {
   }
 
   /**
-   * "Graph contains a cycle involving blocks: [a, b, c]"
-   * Find the last edge in the cycle and offer to remove it.
+   * "Graph contains a cycle: a -> b -> c -> a"
+   * Offer to remove the edge that closes the cycle (c -> a).
    */
-  private suggestBreakCycle(): EnrichedSuggestion {
-    // The last edge added is most likely the one causing the cycle
+  private suggestBreakCycle(
+    item: ValidationError | ValidationWarning,
+  ): EnrichedSuggestion {
+    const path = item.message.split(': ')[1]?.split(' -> ') ?? [];
+    if (path.length >= 2) {
+      const [from, to] = path.slice(-2);
+      const closing = this.edges.find((e) => e.source === from && e.target === to);
+      if (closing) {
+        return {
+          kind: 'break_cycle',
+          message: `Remove the connection that closes the cycle ${path.join(' → ')}.`,
+          autoFix: {
+            label: 'Remove closing edge',
+            description: `Remove the connection "${closing.id}" to break the cycle`,
+            apply: () => {
+              this.actions.removeEdge(closing.id);
+            },
+          },
+        };
+      }
+    }
+
+    // Otherwise the last edge added is most likely the one causing the cycle
     if (this.edges.length > 0) {
       const lastEdge = this.edges[this.edges.length - 1];
       return {
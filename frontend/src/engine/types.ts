@@ -37,6 +37,9 @@ export interface ExecutionMetrics {
   totalOperations: number;
   successfulOperations: number;
   failedOperations: number;
+  totalSimulatedLatency?: number; // ms, from the WASM cost model
+  queueingDelay?: number; // ms, mean wait for the pipeline under timed arrivals
+  shortCircuited?: boolean; // an exhausted block (e.g. a satisfied limit) let the engine skip input
 }
 
 export interface BlockMetrics {
@@ -46,6 +49,8 @@ export interface BlockMetrics {
   executionTime: number; // ms
   percentage: number; // of total time
   counters: Record<string, number>;
+  simulatedLatency?: number; // ms, from the WASM cost model
+  queueingDelay?: number; // ms, mean wait for this block under timed arrivals
 }
 
 export interface ExecutionResult {
@@ -54,6 +59,7 @@ export interface ExecutionResult {
   metrics: ExecutionMetrics;
   blockMetrics: BlockMetrics[];
   errors?: string[];
+  warnings?: string[]; // from blocks validating the data they received
 }
 
 // ---------------------------------------------------------------------------
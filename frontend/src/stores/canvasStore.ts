@@ -244,14 +244,20 @@ export const useCanvasStore = create<CanvasState>((set, get) => {
         }
       }
 
-      // Prevent duplicate connections to the same target port
+      // Prevent duplicate connections to the same target port, unless the
+      // port accepts fan-in — then only the exact same edge is a duplicate.
+      const fanIn = (targetNode?.data as BlockNodeData | undefined)?.inputs.find(
+        (p) => p.name === connection.targetHandle,
+      )?.multiple;
       const existing = get().edges.find(
         (e) =>
           e.target === connection.target &&
-          e.targetHandle === connection.targetHandle,
+          e.targetHandle === connection.targetHandle &&
+          (!fanIn ||
+            (e.source === connection.source && e.sourceHandle === connection.sourceHandle)),
       );
       if (existing) {
-        toast.info('That input port already has a connection.');
+        toast.info(fanIn ? 'That connection already exists.' : 'That input port already has a connection.');
         return;
       }
 
@@ -5,7 +5,7 @@ import { create } from 'zustand';
 // ---------------------------------------------------------------------------
 
 export type OperationType = 'INSERT' | 'SELECT' | 'UPDATE' | 'DELETE' | 'SCAN';
-export type Distribution = 'uniform' | 'zipfian' | 'latest';
+export type Distribution = 'uniform' | 'zipfian' | 'latest' | 'hotspot' | 'sequential';
 
 export interface Operation {
   id: string;
@@ -206,6 +206,13 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         description: 'Access to stored records',
         required: false,
       },
+      {
+        name: 'remapped',
+        type: 'output',
+        dataType: 'DataStream',
+        description: 'Records reclaimed or moved by auto-vacuum, with their old and new TupleIds',
+        required: false,
+      },
     ],
     parameters: [
       {
@@ -385,6 +392,13 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         description: 'Records to index',
         required: true,
       },
+      {
+        name: 'remap',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Reclaimed records whose index entries to drop, and moved ones to re-point',
+        required: false,
+      },
     ],
     outputs: [
       {
@@ -695,6 +709,13 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         description: 'Records to filter',
         required: true,
       },
+      {
+        name: 'statistics',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Statistics collector output, for estimated selectivity',
+        required: false,
+      },
     ],
     outputs: [
       {
@@ -710,13 +731,13 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         name: 'predicate',
         type: 'string',
         default: 'age > 18',
-        description: 'Filter condition',
+        description: "WHERE clause, e.g. age >= 18 AND (city = 'NYC' OR NOT vip = true)",
         uiHint: 'input',
       },
     ],
     documentation: {
       summary: 'Filters records matching a predicate',
-      details: 'The filter operator evaluates a predicate on each record and only passes through matching records.',
+      details: 'The filter operator evaluates a predicate tree (AND/OR/NOT over column comparisons, with SQL null semantics) on each record and only passes through matching records. Connect a statistics collector to see the selectivity the optimizer would estimate next to the one observed.',
     },
   },
   {
@@ -759,10 +780,421 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         constraints: { min: 1, max: 4096, step: 1 },
         uiHint: 'slider',
       },
+      {
+        name: 'merge_fan_in',
+        type: 'number',
+        default: 8,
+        description: 'Runs merged at once in each merge pass',
+        constraints: { min: 2, max: 1024 },
+        uiHint: 'input',
+      },
     ],
     documentation: {
       summary: 'Sorts records by one or more columns',
-      details: 'If data fits in memory, uses quicksort. Otherwise, uses external merge sort with temporary files.',
+      details: 'If data fits in memory, uses quicksort. Otherwise, uses external merge sort: memory-sized sorted runs are spilled to temporary files and merged merge_fan_in at a time, taking ceil(log_F runs) passes. runs_spilled, merge_passes and bytes_spilled show the I/O.',
+    },
+  },
+  {
+    type: 'merge_join',
+    name: 'Merge Join',
+    description: 'Joins two inputs already sorted on the join key',
+    category: 'execution',
+    icon: 'Merge',
+    inputs: [
+      {
+        name: 'left',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Left input, sorted on left_key',
+        required: true,
+      },
+      {
+        name: 'right',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Right input, sorted on right_key',
+        required: true,
+      },
+    ],
+    outputs: [
+      {
+        name: 'joined',
+        type: 'output',
+        dataType: 'DataStream',
+        description: 'Joined records',
+        required: false,
+      },
+    ],
+    parameters: [
+      {
+        name: 'left_key',
+        type: 'string',
+        default: 'id',
+        description: 'Join column of the left input',
+        uiHint: 'input',
+      },
+      {
+        name: 'right_key',
+        type: 'string',
+        default: 'id',
+        description: 'Join column of the right input',
+        uiHint: 'input',
+      },
+    ],
+    documentation: {
+      summary: 'Sort-merge join',
+      details: 'Advances a cursor through each sorted input and emits every pair of rows with equal keys, including the cross product of duplicate runs. Linear in the input size when both sides are pre-sorted (e.g. from a B-tree scan or a Sort block); unsorted input is rejected.',
+    },
+  },
+  {
+    type: 'nested_loop_join',
+    name: 'Nested Loop Join',
+    description: 'Joins on any predicate, optionally through an inner index',
+    category: 'execution',
+    icon: 'Merge',
+    inputs: [
+      {
+        name: 'outer',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Outer input, iterated once',
+        required: true,
+      },
+      {
+        name: 'inner',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Inner input, scanned per outer row or fetched by tuple id',
+        required: true,
+      },
+      {
+        name: 'inner_index',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Index entries for the inner input (inner_key, _page_id, _slot_id)',
+        required: false,
+      },
+    ],
+    outputs: [
+      {
+        name: 'joined',
+        type: 'output',
+        dataType: 'DataStream',
+        description: 'Joined records',
+        required: false,
+      },
+    ],
+    parameters: [
+      {
+        name: 'outer_key',
+        type: 'string',
+        default: 'id',
+        description: 'Join column of the outer input',
+        uiHint: 'input',
+      },
+      {
+        name: 'inner_key',
+        type: 'string',
+        default: 'id',
+        description: 'Join column of the inner input',
+        uiHint: 'input',
+      },
+      {
+        name: 'join_predicate',
+        type: 'enum',
+        default: 'eq',
+        description: 'outer_key <op> inner_key',
+        constraints: { options: ['eq', 'ne', 'lt', 'le', 'gt', 'ge'] },
+        uiHint: 'select',
+      },
+    ],
+    documentation: {
+      summary: 'Nested-loop join, with an index nested-loop variant',
+      details: 'For each outer row, scans the whole inner input and emits every pair satisfying the predicate, so any comparison works, including ranges. Connecting inner_index turns each scan into a binary-search index lookup plus fetches by tuple id; compare rows_compared with and without it.',
+    },
+  },
+  {
+    type: 'projection',
+    name: 'Projection',
+    description: 'Keeps only the selected columns',
+    category: 'execution',
+    icon: 'Grid3x3',
+    inputs: [
+      {
+        name: 'records',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Records to project',
+        required: true,
+      },
+    ],
+    outputs: [
+      {
+        name: 'projected',
+        type: 'output',
+        dataType: 'DataStream',
+        description: 'Records with only the selected columns',
+        required: false,
+      },
+    ],
+    parameters: [
+      {
+        name: 'columns',
+        type: 'string',
+        default: 'id',
+        description: "Comma-separated columns to keep; 'col AS alias' renames",
+        uiHint: 'input',
+      },
+    ],
+    documentation: {
+      summary: 'Selects and renames columns (the SQL SELECT list)',
+      details: 'Drops every column not listed so narrower records flow to sorts, joins and aggregations. The bytes_reduced metric shows how much data the projection removed.',
+    },
+  },
+  {
+    type: 'aggregate',
+    name: 'Aggregate',
+    description: 'Groups records and computes COUNT/SUM/AVG/MIN/MAX',
+    category: 'execution',
+    icon: 'Layers',
+    inputs: [
+      {
+        name: 'records',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Records to aggregate',
+        required: true,
+      },
+    ],
+    outputs: [
+      {
+        name: 'aggregated',
+        type: 'output',
+        dataType: 'DataStream',
+        description: 'One record per group',
+        required: false,
+      },
+    ],
+    parameters: [
+      {
+        name: 'group_by',
+        type: 'string',
+        default: '',
+        description: 'Comma-separated columns to group on (empty = one global group)',
+        uiHint: 'input',
+      },
+      {
+        name: 'aggregates',
+        type: 'string',
+        default: 'count(*)',
+        description: "Comma-separated aggregates, e.g. 'count(*), sum(amount) AS total'",
+        uiHint: 'input',
+      },
+    ],
+    documentation: {
+      summary: 'Hash aggregation (GROUP BY)',
+      details: 'Keeps a hash table of groups and folds each record into its group\'s accumulators. Records with a null or missing group column form one NULL group; aggregates other than count(*) skip nulls. With no group columns the result is a single global row.',
+    },
+  },
+  {
+    type: 'limit',
+    name: 'Limit',
+    description: 'Skips offset rows, emits limit rows, then stops',
+    category: 'execution',
+    icon: 'Filter',
+    inputs: [
+      {
+        name: 'records',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Rows to limit',
+        required: true,
+      },
+    ],
+    outputs: [
+      {
+        name: 'limited',
+        type: 'output',
+        dataType: 'DataStream',
+        description: 'At most limit rows after the offset',
+        required: false,
+      },
+    ],
+    parameters: [
+      {
+        name: 'limit',
+        type: 'number',
+        default: 10,
+        description: 'Rows to emit after the offset',
+        constraints: { min: 0 },
+        uiHint: 'input',
+      },
+      {
+        name: 'offset',
+        type: 'number',
+        default: 0,
+        description: 'Leading rows to skip',
+        constraints: { min: 0 },
+        uiHint: 'input',
+      },
+    ],
+    documentation: {
+      summary: 'LIMIT/OFFSET with early termination',
+      details: 'Once limit rows have gone out, the block is exhausted: under batched execution the engine stops running it and the blocks feeding only it, so a scan under LIMIT 10 reads about a batch instead of the whole table. rows_discarded counts upstream rows that arrived too late to matter.',
+    },
+  },
+  {
+    type: 'distinct',
+    name: 'Distinct',
+    description: 'Removes duplicate rows',
+    category: 'execution',
+    icon: 'Filter',
+    inputs: [
+      {
+        name: 'records',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Records to deduplicate',
+        required: true,
+      },
+    ],
+    outputs: [
+      {
+        name: 'distinct',
+        type: 'output',
+        dataType: 'DataStream',
+        description: 'The first record for each key',
+        required: false,
+      },
+    ],
+    parameters: [
+      {
+        name: 'columns',
+        type: 'string',
+        default: '',
+        description: 'Comma-separated columns to compare (empty = whole row)',
+        uiHint: 'input',
+      },
+      {
+        name: 'method',
+        type: 'enum',
+        default: 'hash',
+        description: 'hash (any order) or sorted (adjacent duplicates)',
+        constraints: { options: ['hash', 'sorted'] },
+        uiHint: 'select',
+      },
+    ],
+    documentation: {
+      summary: 'SELECT DISTINCT / DISTINCT ON',
+      details: 'hash remembers every key seen, so keys_in_memory grows with the number of distinct keys. sorted compares each row with the previous one and holds a single key, but needs input sorted on the columns. With a column subset the first full row of each key is kept. Nulls count as equal.',
+    },
+  },
+  {
+    type: 'union',
+    name: 'Union',
+    description: 'Concatenates any number of inputs (UNION ALL / UNION)',
+    category: 'execution',
+    icon: 'Merge',
+    inputs: [
+      {
+        name: 'records',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'Any number of record streams to combine',
+        required: true,
+        multiple: true,
+      },
+    ],
+    outputs: [
+      {
+        name: 'merged',
+        type: 'output',
+        dataType: 'DataStream',
+        description: "Every input's rows, in connection order",
+        required: false,
+      },
+    ],
+    parameters: [
+      {
+        name: 'distinct',
+        type: 'boolean',
+        default: false,
+        description: 'Remove duplicate rows (UNION) instead of keeping all (UNION ALL)',
+        uiHint: 'checkbox',
+      },
+    ],
+    documentation: {
+      summary: 'UNION ALL, or UNION with distinct',
+      details: 'Connect as many inputs as you like to the records port; they are concatenated in connection order and inputs_merged counts the ones that delivered rows. With distinct, whole-row duplicates are dropped and counted in duplicates_removed — which needs memory for every distinct row, while UNION ALL needs none.',
+    },
+  },
+  {
+    type: 'access_path',
+    name: 'Access Path',
+    description: 'Picks an index or sequential scan from estimated selectivity',
+    category: 'execution',
+    icon: 'GitBranch',
+    inputs: [
+      {
+        name: 'records',
+        type: 'input',
+        dataType: 'DataStream',
+        description: 'The table (with _page_id when available)',
+        required: true,
+      },
+      {
+        name: 'statistics',
+        type: 'input',
+        dataType: 'DataStream',
+        description: "A statistics collector's output, for estimated selectivity",
+        required: false,
+      },
+    ],
+    outputs: [
+      {
+        name: 'results',
+        type: 'output',
+        dataType: 'DataStream',
+        description: 'Records that satisfy the predicate',
+        required: false,
+      },
+    ],
+    parameters: [
+      {
+        name: 'predicate',
+        type: 'string',
+        default: 'id < 10',
+        description: 'WHERE clause with AND/OR/NOT',
+        uiHint: 'input',
+      },
+      {
+        name: 'access_path',
+        type: 'enum',
+        default: 'auto',
+        description: 'auto (by estimated cost), sequential, or index',
+        constraints: { options: ['auto', 'sequential', 'index'] },
+        uiHint: 'select',
+      },
+      {
+        name: 'random_page_cost',
+        type: 'number',
+        default: 4,
+        description: 'Cost of a random page fetch relative to a sequential one',
+        constraints: { min: 0.1, max: 100 },
+        uiHint: 'slider',
+      },
+      {
+        name: 'records_per_page',
+        type: 'number',
+        default: 100,
+        description: 'Rows per page for records without _page_id',
+        constraints: { min: 1 },
+        uiHint: 'input',
+      },
+    ],
+    documentation: {
+      summary: 'Cost-based choice between index scan and sequential scan',
+      details: 'Estimates the predicate\'s selectivity from the statistics input and prices both paths: a sequential scan costs one per page, an index scan random_page_cost per matching row. Below the crossover selectivity the index wins. io_cost is what the chosen path cost; alternative_io_cost is what the other would have, so a misestimate shows up as an alternative that was cheaper.',
     },
   },
   {
@@ -1005,6 +1437,29 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         constraints: { min: 1, max: 20, step: 1 },
         uiHint: 'slider',
       },
+      {
+        name: 'expected_items',
+        type: 'number',
+        default: 0,
+        description: 'Keys to size the filter for (0 = use num_bits and num_hash_functions)',
+        constraints: { min: 0, max: 1000000 },
+        uiHint: 'input',
+      },
+      {
+        name: 'target_fp_rate',
+        type: 'number',
+        default: 0.01,
+        description: 'False-positive rate to size for when expected_items is set',
+        constraints: { min: 0.0001, max: 0.5, step: 0.001 },
+        uiHint: 'slider',
+      },
+      {
+        name: 'counting',
+        type: 'boolean',
+        default: false,
+        description: 'Use 4-bit counters instead of bits so keys can be deleted',
+        uiHint: 'checkbox',
+      },
     ],
     documentation: {
       summary: 'Probabilistic set membership test used by LSM-tree databases',
@@ -1045,13 +1500,28 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         uiHint: 'slider',
       },
       {
-        name: 'histogram_buckets',
+        name: 'bucket_count',
         type: 'number',
         default: 100,
         description: 'Number of equi-depth histogram buckets',
         constraints: { min: 10, max: 500, step: 10 },
         uiHint: 'slider',
       },
+      {
+        name: 'precision',
+        type: 'number',
+        default: 14,
+        description: 'HyperLogLog precision p (2^p registers, standard error 1.04 / sqrt(2^p))',
+        constraints: { min: 4, max: 16, step: 1 },
+        uiHint: 'slider',
+      },
+      {
+        name: 'use_exact',
+        type: 'boolean',
+        default: false,
+        description: 'Count distinct values exactly instead of with HyperLogLog',
+        uiHint: 'checkbox',
+      },
     ],
     documentation: {
       summary: 'Collects statistics for cost-based query optimization',
@@ -1086,17 +1556,47 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
     ],
     parameters: [
       {
-        name: 'num_partitions',
+        name: 'partition_count',
         type: 'number',
         default: 4,
         description: 'Number of partitions',
         constraints: { min: 2, max: 256, step: 1 },
         uiHint: 'slider',
       },
+      {
+        name: 'partition_key',
+        type: 'string',
+        default: '_key',
+        description: 'Record column hashed to choose the partition',
+        uiHint: 'input',
+      },
+      {
+        name: 'strategy',
+        type: 'enum',
+        default: 'hash',
+        description: 'Partitioning strategy',
+        constraints: { options: ['hash', 'range', 'round_robin', 'consistent_hash'] },
+        uiHint: 'select',
+      },
+      {
+        name: 'virtual_nodes',
+        type: 'number',
+        default: 64,
+        description: 'Ring points per partition for consistent hashing',
+        constraints: { min: 1, max: 1024, step: 1 },
+        uiHint: 'slider',
+      },
+      {
+        name: 'boundaries',
+        type: 'string',
+        default: '',
+        description: 'Comma-separated split points for range partitioning',
+        uiHint: 'input',
+      },
     ],
     documentation: {
       summary: 'Hash-based data partitioning for horizontal scaling',
-      details: 'Computes hash(key) % num_partitions to assign each record to a partition. Core to distributed databases like Cassandra, DynamoDB, and CockroachDB. Even distribution depends on key cardinality and hash quality.',
+      details: 'Computes hash(key) % partition_count to assign each record to a partition. Core to distributed databases like Cassandra, DynamoDB, and CockroachDB. Even distribution depends on key cardinality and hash quality.',
     },
   },
 
@@ -1112,7 +1612,7 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         name: 'requests',
         type: 'input',
         dataType: 'DataStream',
-        description: 'Write operations to replicate',
+        description: 'Writes to replicate and reads to serve',
         required: true,
       },
     ],
@@ -1124,10 +1624,17 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         description: 'Acknowledged replicated writes',
         required: false,
       },
+      {
+        name: 'reads',
+        type: 'output',
+        dataType: 'DataStream',
+        description: 'Read results tagged with serving node and staleness',
+        required: false,
+      },
     ],
     parameters: [
       {
-        name: 'replication_factor',
+        name: 'replica_count',
         type: 'number',
         default: 3,
         description: 'Number of copies to maintain',
@@ -1143,10 +1650,42 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
         uiHint: 'select',
       },
       {
-        name: 'async_replication',
+        name: 'replication_mode',
+        type: 'enum',
+        default: 'sync',
+        description: 'When writes are acknowledged',
+        constraints: { options: ['sync', 'semi_sync', 'async', 'quorum'] },
+        uiHint: 'select',
+      },
+      {
+        name: 'write_quorum',
+        type: 'number',
+        default: 0,
+        description: 'W: nodes that must apply a write (0 = from consistency level)',
+        constraints: { min: 0, max: 7, step: 1 },
+        uiHint: 'slider',
+      },
+      {
+        name: 'read_quorum',
+        type: 'number',
+        default: 0,
+        description: 'R: nodes a quorum read consults (0 = from consistency level)',
+        constraints: { min: 0, max: 7, step: 1 },
+        uiHint: 'slider',
+      },
+      {
+        name: 'replication_lag',
+        type: 'number',
+        default: 3,
+        description: 'Operations before an async write reaches the replicas',
+        constraints: { min: 0, max: 100, step: 1 },
+        uiHint: 'slider',
+      },
+      {
+        name: 'read_your_writes',
         type: 'boolean',
         default: false,
-        description: 'Async mode: ack after one replica (faster but riskier)',
+        description: "Route a client's reads to the primary right after it writes",
         uiHint: 'checkbox',
       },
     ],
@@ -1183,10 +1722,10 @@ export const BLOCK_REGISTRY: BlockDefinition[] = [
     ],
     parameters: [
       {
-        name: 'max_dictionary_size',
+        name: 'max_dictionary_entries',
         type: 'number',
         default: 4096,
-        description: 'Max distinct values before falling back to uncompressed',
+        description: 'Max distinct values before new values are stored inline (escape code)',
         constraints: { min: 16, max: 65536, step: 256 },
         uiHint: 'slider',
       },
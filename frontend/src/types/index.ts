@@ -34,6 +34,8 @@ export interface PortDefinition {
   dataType: PortDataType;
   description: string;
   required: boolean;
+  /** Input accepts several connections, concatenated in connection order */
+  multiple?: boolean;
 }
 
 // Parameter types for block configuration
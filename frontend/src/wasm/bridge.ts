@@ -8,11 +8,13 @@
 import { getWASMModule } from './loader';
 import type {
   BlockConfig,
+  BlockDocumentation,
   PortRef,
   WorkloadConfig,
   WASMProgressReport,
   WASMMetricsResult,
   RustConnection,
+  RustGraphDefinition,
   WASMBlockDetail,
 } from './types';
 
@@ -41,6 +43,26 @@ interface BridgeExecutionResult {
   duration: number;
   metrics: WASMMetricsResult;
   errors?: string[];
+  warnings?: string[];
+}
+
+// ---------------------------------------------------------------------------
+// Standalone graph run result from Rust (run_graph)
+// ---------------------------------------------------------------------------
+
+interface BridgeRunGraphResult {
+  success: boolean;
+  duration: number;
+  blockMetrics: Record<string, Record<string, number>>;
+  error?: { stage: 'parse' | 'instantiate' | 'execute'; message: string };
+  warnings?: string[];
+}
+
+/** Metrics so far, passed to the runGraphStreaming callback after each batch. */
+interface BridgeRunGraphSnapshot {
+  batch: number;
+  batches: number;
+  blockMetrics: Record<string, Record<string, number>>;
 }
 
 // ---------------------------------------------------------------------------
@@ -110,6 +132,12 @@ export class WASMBridge {
     return JSON.parse(json) as BridgeValidationResult;
   }
 
+  /** Validate a whole graph in one call, independent of the runtime's graph. */
+  validateGraph(graph: RustGraphDefinition): BridgeValidationResult {
+    const wasm = this.getModule();
+    return wasm.validate_graph(JSON.stringify(graph)) as BridgeValidationResult;
+  }
+
   // -------------------------------------------------------------------
   // Execution
   // -------------------------------------------------------------------
@@ -134,6 +162,25 @@ export class WASMBridge {
     return JSON.parse(resultJson) as BridgeExecutionResult;
   }
 
+  /** Run a workload through a whole graph in one call, independent of the runtime's graph. */
+  runGraph(graph: RustGraphDefinition, workload: WorkloadConfig): BridgeRunGraphResult {
+    const wasm = this.getModule();
+    return wasm.run_graph(JSON.stringify(graph), JSON.stringify(workload)) as BridgeRunGraphResult;
+  }
+
+  runGraphStreaming(
+    graph: RustGraphDefinition,
+    workload: WorkloadConfig,
+    onBatch: (snapshot: BridgeRunGraphSnapshot) => void,
+  ): BridgeRunGraphResult {
+    const wasm = this.getModule();
+    return wasm.run_graph_streaming(
+      JSON.stringify(graph),
+      JSON.stringify(workload),
+      (snapshot: unknown) => onBatch(snapshot as BridgeRunGraphSnapshot),
+    ) as BridgeRunGraphResult;
+  }
+
   cancel(): void {
     const wasm = this.getModule();
     wasm.cancel_execution();
@@ -173,6 +220,16 @@ export class WASMBridge {
     return JSON.parse(json) as WASMBlockDetail[];
   }
 
+  getBlockCatalog(): WASMBlockDetail[] {
+    const wasm = this.getModule();
+    return wasm.get_block_catalog() as WASMBlockDetail[];
+  }
+
+  getBlockDocs(blockId: string): BlockDocumentation | null {
+    const wasm = this.getModule();
+    return (wasm.get_block_docs(blockId) as BlockDocumentation | null) ?? null;
+  }
+
   // -------------------------------------------------------------------
   // Internal
   // -------------------------------------------------------------------
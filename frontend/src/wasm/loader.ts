@@ -29,9 +29,16 @@ export interface WASMModule {
 
   // Validation
   validate: () => string; // returns ValidationResult JSON
+  validate_graph: (graphJson: string) => unknown; // returns a ValidationResult object
 
   // Execution
   execute: (workloadJson: string, progressCallback: (json: string) => void) => string;
+  run_graph: (graphJson: string, workloadJson: string) => unknown; // returns a RunGraphResult object
+  run_graph_streaming: (
+    graphJson: string,
+    workloadJson: string,
+    callback: (snapshot: unknown) => void,
+  ) => unknown; // returns a RunGraphResult object
   cancel_execution: () => void;
 
   // Metrics
@@ -41,6 +48,8 @@ export interface WASMModule {
   get_block_types: () => string; // returns BlockMetadata[] JSON
   get_block_detail: (blockType: string) => string; // returns full block metadata JSON
   get_all_block_details: () => string; // returns all block details JSON
+  get_block_catalog: () => unknown; // returns a WASMBlockDetail[] from the registry
+  get_block_docs: (blockId: string) => unknown; // returns BlockDocumentation, or null for an unknown id
 }
 
 // ---------------------------------------------------------------------------
@@ -22,6 +22,14 @@ export type RustPortType =
 
 export type PortDirection = 'Input' | 'Output';
 
+export type PrimitiveType = 'Integer' | 'Float' | 'String' | 'Boolean' | 'Bytes';
+
+/** Shape of the records a port carries; ports without one are unchecked. */
+export type PortSchema =
+  | { type: 'Object'; properties: Record<string, PortSchema>; required: string[] }
+  | { type: 'Array'; items: PortSchema }
+  | { type: 'Primitive'; prim_type: PrimitiveType };
+
 export interface RustPort {
   id: string;
   name: string;
@@ -30,6 +38,7 @@ export interface RustPort {
   required: boolean;
   multiple: boolean;
   description: string;
+  schema: PortSchema | null;
 }
 
 export interface PortRef {
@@ -47,6 +56,18 @@ export interface RustConnection {
   buffer_size: number | null;
 }
 
+// Graph definition  (mirrors block-system/src/runtime/graph.rs)
+export interface RustBlockDefinition {
+  id: string;
+  block_type: string; // metadata id ("lsm-tree-storage") or type string ("lsm_tree")
+  params?: Record<string, unknown>;
+}
+
+export interface RustGraphDefinition {
+  blocks: RustBlockDefinition[];
+  connections?: RustConnection[];
+}
+
 // Record / PortValue — runtime data flowing through ports
 export type SignalValue = 'Start' | 'Stop' | 'Commit' | 'Abort' | { Custom: string };
 
@@ -87,6 +108,7 @@ export interface ParameterConstraints {
   allowed_values?: ParameterValue[];
   min_length?: number;
   max_length?: number;
+  element_type?: RustParameterType;
 }
 
 export type WidgetType =
@@ -121,7 +143,7 @@ export interface RustParameter {
 
 export type MetricType = 'Counter' | 'Gauge' | 'Histogram' | 'Timing';
 
-export type AggregationType = 'Sum' | 'Avg' | 'Min' | 'Max' | 'P50' | 'P95' | 'P99';
+export type AggregationType = 'Sum' | 'Avg' | 'Min' | 'Max' | 'P50' | 'P95' | 'P99' | 'Count';
 
 export interface MetricDefinition {
   id: string;
@@ -233,6 +255,12 @@ export interface WASMParameterDetail {
     min?: number;
     max?: number;
     pattern?: string;
+    /** Choices of an Enum parameter. */
+    allowedValues?: string[];
+    /** Length bounds and element type of an Array parameter. */
+    minLength?: number;
+    maxLength?: number;
+    elementType?: string;
   };
   uiHint?: {
     widget: string;
@@ -282,7 +310,9 @@ export type ConstraintType =
   | { MinimumMemory: number }
   | { MinimumDisk: number }
   | 'ThreadSafe'
-  | 'AtomicOperations';
+  | 'AtomicOperations'
+  | { RequiresGuarantee: GuaranteeType }
+  | { RequiresStrictGuarantee: GuaranteeType };
 
 export interface RustConstraint {
   constraint_type: ConstraintType;
@@ -297,7 +327,8 @@ export type GuaranteeType =
   | 'Atomicity'
   | 'ThreadSafe'
   | 'Serializable'
-  | 'SnapshotIsolation';
+  | 'SnapshotIsolation'
+  | 'SortedOrder';
 
 export type GuaranteeLevel = 'Strict' | 'BestEffort';
 
@@ -331,7 +362,7 @@ export interface RustValidationResult {
 export interface BlockConfig {
   type: string;
   id: string;
-  parameters: Record<string, string | number | boolean>;
+  parameters: Record<string, string | number | boolean | Array<string | number | boolean>>;
 }
 
 /** Sent from frontend to WASM when executing a workload. */
@@ -340,6 +371,15 @@ export interface WorkloadConfig {
   distribution: string;
   concurrency: number;
   totalOps: number;
+  /** Same seed and config generate the same workload (default 0). */
+  seed?: number;
+  /** Arrivals per second; timestamps the workload for queueing delay. */
+  arrivalRate?: number;
+  arrivalDistribution?: 'poisson' | 'constant';
+  /** Seconds of arrivals to generate. */
+  duration?: number;
+  /** Records per engine round for run_graph / run_graph_streaming. */
+  batchSize?: number;
 }
 
 export interface OperationConfig {
@@ -369,6 +409,12 @@ export interface WASMMetricsResult {
   successfulOperations: number;
   failedOperations: number;
   blockMetrics: WASMBlockMetrics[];
+  /** Sum of every block's simulated I/O latency (ms). */
+  totalSimulatedLatency: number;
+  /** Mean time a request waits for the pipeline (ms); 0 for untimed workloads. */
+  queueingDelay: number;
+  /** Whether an exhausted block (e.g. a satisfied limit) let the engine skip input. */
+  shortCircuited: boolean;
 }
 
 export interface WASMBlockMetrics {
@@ -378,4 +424,8 @@ export interface WASMBlockMetrics {
   executionTime: number;
   percentage: number;
   counters: Record<string, number>;
+  /** Latency the block's I/O would take under the engine's cost model (ms). */
+  simulatedLatency: number;
+  /** Mean time a request waits for this block (ms); 0 for untimed workloads. */
+  queueingDelay: number;
 }